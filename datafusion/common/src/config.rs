@@ -264,6 +264,15 @@ config_namespace! {
         /// When set to true, SQL parser will normalize ident (convert ident to lowercase when not quoted)
         pub enable_ident_normalization: bool, default = true
 
+        /// Specifies how unquoted identifiers are normalized when
+        /// `enable_ident_normalization` is true. There are 4 options:
+        /// - `lower`: Identifiers are converted to lowercase.
+        /// - `upper`: Identifiers are converted to uppercase, as in Oracle.
+        /// - `preserve`: Identifiers are kept as written.
+        /// - `case_insensitive`: Identifiers are kept as written, but match a
+        ///   column or table name that differs only in case, as in SQL Server.
+        pub ident_case_folding: String, default = "lower".to_string()
+
         /// When set to true, SQL parser will normalize options value (convert value to lowercase).
         /// Note that this option is ignored and will be removed in the future. All case-insensitive values
         /// are normalized automatically.
@@ -290,9 +299,33 @@ config_namespace! {
         /// and recorded in the logical plan nodes.
         pub collect_spans: bool, default = false
 
+        /// When set to true, the SQL planner keeps planning a statement after
+        /// an error that does not affect the rest of the statement, such as
+        /// an unknown column, and returns all the errors it found together.
+        pub collect_errors: bool, default = false
+
         /// Specifies the recursion depth limit when parsing complex SQL Queries
         pub recursion_limit: usize, default = 50
 
+        /// Maximum number of edges matched by an unbounded quantified edge
+        /// pattern in `GRAPH_TABLE`, such as `-[e]->+`. Quantified edges are
+        /// planned as a union over each number of repetitions they allow.
+        pub max_graph_path_length: usize, default = 10
+
+        /// Maximum nesting depth of expressions, such as nested function calls
+        /// or `CASE` expressions, that the SQL planner accepts. Deeper
+        /// expressions are rejected with a planning error instead of
+        /// overflowing the stack.
+        pub expr_depth_limit: usize, default = 256
+
+        /// Maximum nesting depth of subqueries, including derived tables and
+        /// common table expressions, that the SQL planner accepts.
+        pub subquery_depth_limit: usize, default = 64
+
+        /// Maximum number of set operations, such as `UNION` or `INTERSECT`,
+        /// chained in a single query that the SQL planner accepts.
+        pub set_operation_limit: usize, default = 10000
+
         /// Specifies the default null ordering for query results. There are 4 options:
         /// - `nulls_max`: Nulls appear last in ascending order.
         /// - `nulls_min`: Nulls appear first in ascending order.
@@ -302,6 +335,12 @@ config_namespace! {
         /// By default, `nulls_max` is used to follow Postgres's behavior.
         /// postgres rule: <https://www.postgresql.org/docs/current/queries-order.html>
         pub default_null_ordering: String, default = "nulls_max".to_string()
+
+        /// Specifies how a name written without parentheses that names both a
+        /// function, such as `current_user`, and a column in scope is resolved:
+        /// - `prefer_function`: The name calls the function.
+        /// - `prefer_column`: The name references the column.
+        pub function_name_conflict: String, default = "prefer_function".to_string()
     }
 }
 
@@ -1002,6 +1041,12 @@ config_namespace! {
         /// process to reorder the join keys
         pub top_down_join_key_reordering: bool, default = true
 
+        /// When set to true, the logical plan optimizer will reorder inner joins
+        /// using the statistics of the joined tables, such as those stored by
+        /// `ANALYZE`. Joins keep their syntactic order unless the number of rows
+        /// of every joined table is known
+        pub enable_join_reordering: bool, default = true
+
         /// When set to true, the physical plan optimizer will prefer HashJoin over SortMergeJoin.
         /// HashJoin can work more efficiently than SortMergeJoin but consumes more memory
         pub prefer_hash_join: bool, default = true
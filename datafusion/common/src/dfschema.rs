@@ -18,10 +18,11 @@
 //! DFSchema is an extended schema struct that DataFusion uses to provide support for
 //! fields with optional relation names.
 
+use std::borrow::Borrow;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
-use std::hash::Hash;
-use std::sync::Arc;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
 
 use crate::error::{_plan_err, _schema_err, DataFusionError, Result};
 use crate::{
@@ -108,7 +109,7 @@ pub type DFSchemaRef = Arc<DFSchema>;
 /// let schema: &Schema = df_schema.as_arrow();
 /// assert_eq!(schema.fields().len(), 1);
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DFSchema {
     /// Inner Arrow schema reference.
     inner: SchemaRef,
@@ -117,6 +118,10 @@ pub struct DFSchema {
     field_qualifiers: Vec<Option<TableReference>>,
     /// Stores functional dependencies in the schema.
     functional_dependencies: FunctionalDependencies,
+    /// Index of the fields by name, built on the first lookup by name in
+    /// wide schemas. Qualifiers are not indexed, so the index is kept when
+    /// only the qualifiers change.
+    field_name_index: FieldNameIndex,
 }
 
 impl DFSchema {
@@ -126,6 +131,7 @@ impl DFSchema {
             inner: Arc::new(Schema::new([])),
             field_qualifiers: vec![],
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         }
     }
 
@@ -157,6 +163,7 @@ impl DFSchema {
             inner: schema,
             field_qualifiers: qualifiers,
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         dfschema.check_names()?;
         Ok(dfschema)
@@ -173,6 +180,7 @@ impl DFSchema {
             inner: schema,
             field_qualifiers: vec![None; field_count],
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         dfschema.check_names()?;
         Ok(dfschema)
@@ -191,6 +199,7 @@ impl DFSchema {
             inner: schema.clone().into(),
             field_qualifiers: vec![Some(qualifier); schema.fields.len()],
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         schema.check_names()?;
         Ok(schema)
@@ -205,6 +214,7 @@ impl DFSchema {
             inner: Arc::clone(schema),
             field_qualifiers: qualifiers,
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         dfschema.check_names()?;
         Ok(dfschema)
@@ -226,6 +236,7 @@ impl DFSchema {
             inner: Arc::clone(&self.inner),
             field_qualifiers: qualifiers,
             functional_dependencies: self.functional_dependencies.clone(),
+            field_name_index: self.field_name_index.clone(),
         })
     }
 
@@ -294,6 +305,7 @@ impl DFSchema {
             inner: Arc::new(new_schema_with_metadata),
             field_qualifiers: new_qualifiers,
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         new_self.check_names()?;
         Ok(new_self)
@@ -350,6 +362,7 @@ impl DFSchema {
         let finished_with_metadata = finished.with_metadata(metadata);
         self.inner = finished_with_metadata.into();
         self.field_qualifiers.extend(qualifiers);
+        self.field_name_index = FieldNameIndex::default();
     }
 
     /// Get a list of fields for this schema
@@ -371,26 +384,44 @@ impl DFSchema {
         (self.field_qualifiers[i].as_ref(), self.field(i))
     }
 
+    /// The indices of the fields named `name`, in order
+    fn indices_of_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = usize> + 'a {
+        // Hashing the names only pays off in wide schemas, smaller ones are
+        // scanned
+        let (indexed, scanned) = if self.inner.fields.len() > FIELD_NAME_INDEX_MIN_FIELDS
+        {
+            let index = self.field_name_index.get_or_init(&self.inner.fields);
+            (index.get(name).map_or(&[][..], Vec::as_slice), None)
+        } else {
+            let scanned = self
+                .inner
+                .fields
+                .iter()
+                .enumerate()
+                .filter(move |(_, field)| field.name() == name)
+                .map(|(idx, _)| idx);
+            (&[][..], Some(scanned))
+        };
+        indexed.iter().copied().chain(scanned.into_iter().flatten())
+    }
+
     pub fn index_of_column_by_name(
         &self,
         qualifier: Option<&TableReference>,
         name: &str,
     ) -> Option<usize> {
-        let mut matches = self
-            .iter()
-            .enumerate()
-            .filter(|(_, (q, f))| match (qualifier, q) {
+        self.indices_of_name(name).find(|&idx| {
+            match (qualifier, &self.field_qualifiers[idx]) {
                 // field to lookup is qualified.
                 // current field is qualified and not shared between relations, compare both
                 // qualifier and name.
-                (Some(q), Some(field_q)) => q.resolved_eq(field_q) && f.name() == name,
+                (Some(q), Some(field_q)) => q.resolved_eq(field_q),
                 // field to lookup is qualified but current field is unqualified.
                 (Some(_), None) => false,
                 // field to lookup is unqualified, no need to compare qualifier
-                (None, Some(_)) | (None, None) => f.name() == name,
-            })
-            .map(|(idx, _)| idx);
-        matches.next()
+                (None, Some(_)) | (None, None) => true,
+            }
+        })
     }
 
     /// Find the index of the column with the given qualifier and name,
@@ -468,9 +499,8 @@ impl DFSchema {
 
     /// Find all fields that match the given name
     pub fn fields_with_unqualified_name(&self, name: &str) -> Vec<&FieldRef> {
-        self.fields()
-            .iter()
-            .filter(|field| field.name() == name)
+        self.indices_of_name(name)
+            .map(|idx| self.field(idx))
             .collect()
     }
 
@@ -479,15 +509,15 @@ impl DFSchema {
         &self,
         name: &str,
     ) -> Vec<(Option<&TableReference>, &FieldRef)> {
-        self.iter()
-            .filter(|(_, field)| field.name() == name)
+        self.indices_of_name(name)
+            .map(|idx| self.qualified_field(idx))
             .collect()
     }
 
     /// Find all fields that match the given name and convert to column
     pub fn columns_with_unqualified_name(&self, name: &str) -> Vec<Column> {
-        self.iter()
-            .filter(|(_, field)| field.name() == name)
+        self.qualified_fields_with_unqualified_name(name)
+            .into_iter()
             .map(|(qualifier, field)| Column::new(qualifier.cloned(), field.name()))
             .collect()
     }
@@ -562,7 +592,7 @@ impl DFSchema {
 
     /// Find if the field exists with the given name
     pub fn has_column_with_unqualified_name(&self, name: &str) -> bool {
-        self.fields().iter().any(|field| field.name() == name)
+        self.indices_of_name(name).next().is_some()
     }
 
     /// Find if the field exists with the given qualified name
@@ -571,8 +601,11 @@ impl DFSchema {
         qualifier: &TableReference,
         name: &str,
     ) -> bool {
-        self.iter()
-            .any(|(q, f)| q.map(|q| q.eq(qualifier)).unwrap_or(false) && f.name() == name)
+        self.indices_of_name(name).any(|idx| {
+            self.field_qualifiers[idx]
+                .as_ref()
+                .is_some_and(|q| q.eq(qualifier))
+        })
     }
 
     /// Find if the field exists with the given qualified column
@@ -843,6 +876,7 @@ impl DFSchema {
             field_qualifiers: vec![None; self.inner.fields.len()],
             inner: self.inner,
             functional_dependencies: self.functional_dependencies,
+            field_name_index: self.field_name_index,
         }
     }
 
@@ -853,6 +887,7 @@ impl DFSchema {
             field_qualifiers: vec![Some(qualifier); self.inner.fields.len()],
             inner: self.inner,
             functional_dependencies: self.functional_dependencies,
+            field_name_index: self.field_name_index,
         }
     }
 
@@ -1124,6 +1159,7 @@ impl TryFrom<SchemaRef> for DFSchema {
             inner: schema,
             field_qualifiers: vec![None; field_count],
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         // Without checking names, because schema here may have duplicate field names.
         // For example, Partial AggregateMode will generate duplicate field names from
@@ -1134,9 +1170,79 @@ impl TryFrom<SchemaRef> for DFSchema {
     }
 }
 
+/// The number of fields above which a [`DFSchema`] indexes its fields by name
+const FIELD_NAME_INDEX_MIN_FIELDS: usize = 32;
+
+/// The index of the fields of a [`DFSchema`] by name.
+///
+/// The index is keyed by the fields themselves, which are shared with the
+/// schema, so that building it does not copy the names.
+#[derive(Debug, Clone, Default)]
+struct FieldNameIndex(OnceLock<Arc<HashMap<FieldName, Vec<usize>>>>);
+
+impl FieldNameIndex {
+    fn get_or_init(&self, fields: &Fields) -> &HashMap<FieldName, Vec<usize>> {
+        self.0.get_or_init(|| {
+            let mut index: HashMap<FieldName, Vec<usize>> =
+                HashMap::with_capacity(fields.len());
+            for (idx, field) in fields.iter().enumerate() {
+                index
+                    .entry(FieldName(Arc::clone(field)))
+                    .or_default()
+                    .push(idx);
+            }
+            Arc::new(index)
+        })
+    }
+}
+
+// The index is derived from the fields, so it does not take part in the
+// comparison of schemas
+impl PartialEq for FieldNameIndex {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for FieldNameIndex {}
+
+/// A field compared and hashed by its name
+#[derive(Debug)]
+struct FieldName(FieldRef);
+
+impl PartialEq for FieldName {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.name() == other.0.name()
+    }
+}
+
+impl Eq for FieldName {}
+
+impl Hash for FieldName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.name().as_str().hash(state)
+    }
+}
+
+impl Borrow<str> for FieldName {
+    fn borrow(&self) -> &str {
+        self.0.name()
+    }
+}
+
+impl std::fmt::Debug for DFSchema {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DFSchema")
+            .field("inner", &self.inner)
+            .field("field_qualifiers", &self.field_qualifiers)
+            .field("functional_dependencies", &self.functional_dependencies)
+            .finish()
+    }
+}
+
 // Hashing refers to a subset of fields considered in PartialEq.
 impl Hash for DFSchema {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: Hasher>(&self, state: &mut H) {
         self.inner.fields.hash(state);
         self.inner.metadata.len().hash(state); // HashMap is not hashable
     }
@@ -1179,6 +1285,7 @@ impl ToDFSchema for Vec<Field> {
             inner: schema.into(),
             field_qualifiers: vec![None; field_count],
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         Ok(dfschema)
     }
@@ -1570,6 +1677,7 @@ mod tests {
             inner: Arc::clone(&arrow_schema_ref),
             field_qualifiers: vec![None; arrow_schema_ref.fields.len()],
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
         let df_schema_ref = Arc::new(df_schema.clone());
 
@@ -1616,11 +1724,47 @@ mod tests {
             inner: Arc::clone(&schema),
             field_qualifiers: vec![None; schema.fields.len()],
             functional_dependencies: FunctionalDependencies::empty(),
+            field_name_index: FieldNameIndex::default(),
         };
 
         assert_eq!(df_schema.inner.metadata(), schema.metadata())
     }
 
+    #[test]
+    fn lookup_by_name_in_wide_schema() -> Result<()> {
+        let fields: Vec<Field> = (0..100)
+            .map(|i| Field::new(format!("c{i}"), DataType::Int32, true))
+            .collect();
+        let t1 = DFSchema::try_from_qualified_schema("t1", &Schema::new(fields.clone()))?;
+        let t2 = DFSchema::try_from_qualified_schema("t2", &Schema::new(fields))?;
+        let mut schema = t1.join(&t2)?;
+
+        assert_eq!(schema.index_of_column(&Column::from_name("c7"))?, 7);
+        assert_eq!(
+            schema.index_of_column(&Column::from_qualified_name("t2.c7"))?,
+            107
+        );
+        assert_eq!(schema.columns_with_unqualified_name("c99").len(), 2);
+        assert!(schema.has_column_with_qualified_name(&"t2".into(), "c0"));
+        assert!(!schema.has_column_with_unqualified_name("c100"));
+        assert_contains!(
+            schema
+                .field_with_unqualified_name("c1")
+                .unwrap_err()
+                .to_string(),
+            "Ambiguous reference to unqualified field c1"
+        );
+
+        // Merging fields invalidates the index
+        let extra = DFSchema::from_unqualified_fields(
+            vec![Field::new("c100", DataType::Int32, true)].into(),
+            HashMap::new(),
+        )?;
+        schema.merge(&extra);
+        assert_eq!(schema.index_of_column(&Column::from_name("c100"))?, 200);
+        Ok(())
+    }
+
     #[test]
     fn test_contain_column() -> Result<()> {
         // qualified exists
@@ -16,6 +16,10 @@
 // under the License.
 
 use crate::Span;
+use crate::utils::datafusion_strsim::normalized_levenshtein;
+
+/// The most names suggested by [`Diagnostic::add_did_you_mean`]
+const MAX_SUGGESTIONS: usize = 3;
 
 /// Additional contextual information intended for end users, to help them
 /// understand what went wrong by providing human-readable messages, and
@@ -135,6 +139,42 @@ impl Diagnostic {
         });
     }
 
+    /// Adds a "help" suggesting the `candidates` closest to `name`, an unknown
+    /// name of a column, table or function, by edit distance. Nothing is
+    /// added if no candidate is close enough to `name` to be a likely typo.
+    pub fn add_did_you_mean<'a>(
+        &mut self,
+        name: &str,
+        candidates: impl IntoIterator<Item = &'a str>,
+    ) {
+        let name = name.to_lowercase();
+        let mut matches = candidates
+            .into_iter()
+            .map(|candidate| {
+                let similarity = normalized_levenshtein(&candidate.to_lowercase(), &name);
+                (similarity, candidate)
+            })
+            .filter(|(similarity, _)| *similarity >= 0.5)
+            .collect::<Vec<_>>();
+        matches.sort_by(|(a, a_name), (b, b_name)| {
+            b.total_cmp(a).then_with(|| a_name.cmp(b_name))
+        });
+        matches.dedup_by_key(|(_, candidate)| *candidate);
+        let suggestions = matches
+            .iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, candidate)| format!("'{candidate}'"))
+            .collect::<Vec<_>>();
+        match suggestions.as_slice() {
+            [] => {}
+            [suggestion] => self.add_help(format!("did you mean {suggestion}?"), None),
+            suggestions => self.add_help(
+                format!("did you mean one of {}?", suggestions.join(", ")),
+                None,
+            ),
+        }
+    }
+
     /// Like [`Diagnostic::add_note`], but returns `self` to allow chaining.
     pub fn with_note(mut self, message: impl Into<String>, span: Option<Span>) -> Self {
         self.add_note(message.into(), span);
@@ -0,0 +1,235 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Progress reporting for `COPY TO` and `COPY FROM`
+//!
+//! Engines executing a [`CopyTo`] or [`CopyFrom`] create a
+//! [`CopyProgressTracker`] for it with [`CopyProgressTracker::try_new`], and
+//! record the rows, bytes and files they write as they go. The tracker
+//! reports them to the [`CopyProgressListener`] of the session running the
+//! statement, which can cancel the copy, e.g. when the user closes a
+//! progress dialog.
+
+use std::fmt::{self, Debug, Display};
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::LogicalPlan;
+use crate::dml::{CopyFrom, CopyTo};
+use crate::session::SessionProvider;
+use datafusion_common::{Result, exec_err};
+
+/// Direction of a copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CopyDirection {
+    /// `COPY ... TO`, writing files
+    To,
+    /// `COPY ... FROM`, loading a table
+    From,
+}
+
+impl Display for CopyDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyDirection::To => write!(f, "COPY TO"),
+            CopyDirection::From => write!(f, "COPY FROM"),
+        }
+    }
+}
+
+/// Snapshot of the work done by a copy so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyProgress {
+    /// Whether the copy writes files or loads a table
+    pub direction: CopyDirection,
+    /// The output URL of a `COPY TO`, or the source of a `COPY FROM`
+    pub location: String,
+    /// Rows copied
+    pub rows: u64,
+    /// Bytes written by a `COPY TO`, or read by a `COPY FROM`
+    pub bytes: u64,
+    /// Files completely written by a `COPY TO`, or read by a `COPY FROM`
+    pub files: u64,
+}
+
+/// Receives the progress of the copies run on a session
+///
+/// Returned by [`SessionProvider::copy_progress_listener`]. Copies report
+/// from the tasks executing them, so implementations should return quickly.
+pub trait CopyProgressListener: Debug + Send + Sync {
+    /// Called each time rows or a file have been copied. Returning
+    /// [`ControlFlow::Break`] cancels the copy.
+    fn on_progress(&self, progress: &CopyProgress) -> ControlFlow<()>;
+
+    /// Called once when the copy ends, with whether it succeeded
+    fn on_finish(&self, _progress: &CopyProgress, _succeeded: bool) {}
+}
+
+/// Counts the work done by a running copy and reports it to a
+/// [`CopyProgressListener`]
+///
+/// The counters are atomic, so a tracker can be shared by the partitions
+/// executing the copy.
+#[derive(Debug)]
+pub struct CopyProgressTracker {
+    direction: CopyDirection,
+    location: String,
+    rows: AtomicU64,
+    bytes: AtomicU64,
+    files: AtomicU64,
+    listener: Arc<dyn CopyProgressListener>,
+}
+
+impl CopyProgressTracker {
+    /// Creates a tracker reporting to `listener`
+    pub fn new(
+        direction: CopyDirection,
+        location: impl Into<String>,
+        listener: Arc<dyn CopyProgressListener>,
+    ) -> Self {
+        Self {
+            direction,
+            location: location.into(),
+            rows: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            files: AtomicU64::new(0),
+            listener,
+        }
+    }
+
+    /// Creates a tracker for `plan` when it is a [`CopyTo`] or
+    /// [`CopyFrom`] and `session` listens to the progress of copies
+    pub fn try_new(plan: &LogicalPlan, session: &dyn SessionProvider) -> Option<Self> {
+        let (direction, location) = match plan {
+            LogicalPlan::Copy(CopyTo { output_url, .. }) => {
+                (CopyDirection::To, output_url.clone())
+            }
+            LogicalPlan::CopyFrom(CopyFrom { source, .. }) => {
+                (CopyDirection::From, source.to_string())
+            }
+            _ => return None,
+        };
+        let listener = session.copy_progress_listener()?;
+        Some(Self::new(direction, location, listener))
+    }
+
+    /// Records that `rows` rows, taking `bytes` bytes, have been copied
+    ///
+    /// Returns an error when the listener cancels the copy.
+    pub fn record_rows(&self, rows: u64, bytes: u64) -> Result<()> {
+        self.rows.fetch_add(rows, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.report()
+    }
+
+    /// Records that a file has been completely written or read
+    ///
+    /// Returns an error when the listener cancels the copy.
+    pub fn record_file(&self) -> Result<()> {
+        self.files.fetch_add(1, Ordering::Relaxed);
+        self.report()
+    }
+
+    /// Tells the listener that the copy has ended
+    pub fn finish(&self, succeeded: bool) {
+        self.listener.on_finish(&self.progress(), succeeded);
+    }
+
+    /// The work done so far
+    pub fn progress(&self) -> CopyProgress {
+        CopyProgress {
+            direction: self.direction,
+            location: self.location.clone(),
+            rows: self.rows.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            files: self.files.load(Ordering::Relaxed),
+        }
+    }
+
+    fn report(&self) -> Result<()> {
+        let progress = self.progress();
+        match self.listener.on_progress(&progress) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => exec_err!(
+                "{} {} cancelled after {} rows",
+                self.direction,
+                self.location,
+                progress.rows
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records every report, and cancels copies after `max_rows` rows
+    #[derive(Debug, Default)]
+    struct TestListener {
+        max_rows: u64,
+        reports: Mutex<Vec<CopyProgress>>,
+        finished: Mutex<Option<bool>>,
+    }
+
+    impl CopyProgressListener for TestListener {
+        fn on_progress(&self, progress: &CopyProgress) -> ControlFlow<()> {
+            self.reports.lock().unwrap().push(progress.clone());
+            if progress.rows > self.max_rows {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+
+        fn on_finish(&self, _progress: &CopyProgress, succeeded: bool) {
+            *self.finished.lock().unwrap() = Some(succeeded);
+        }
+    }
+
+    #[test]
+    fn reports_and_cancels() -> Result<()> {
+        let listener = Arc::new(TestListener {
+            max_rows: 150,
+            ..Default::default()
+        });
+        let tracker =
+            CopyProgressTracker::new(CopyDirection::To, "out.csv", Arc::clone(&listener));
+        tracker.record_rows(100, 1000)?;
+        tracker.record_file()?;
+        let err = tracker.record_rows(100, 1000).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: COPY TO out.csv cancelled after 200 rows"
+        );
+        tracker.finish(false);
+
+        assert_eq!(
+            listener.reports.lock().unwrap()[1],
+            CopyProgress {
+                direction: CopyDirection::To,
+                location: "out.csv".to_string(),
+                rows: 100,
+                bytes: 1000,
+                files: 1,
+            }
+        );
+        assert_eq!(*listener.finished.lock().unwrap(), Some(false));
+        Ok(())
+    }
+}
@@ -75,6 +75,15 @@ impl From<sqlparser::ast::NullTreatment> for NullTreatment {
     }
 }
 
+impl From<NullTreatment> for sqlparser::ast::NullTreatment {
+    fn from(value: NullTreatment) -> Self {
+        match value {
+            NullTreatment::IgnoreNulls => Self::IgnoreNulls,
+            NullTreatment::RespectNulls => Self::RespectNulls,
+        }
+    }
+}
+
 /// Represents logical expressions such as `A + 1`, or `CAST(c1 AS int)`.
 ///
 /// For example the expression `A + 1` will be represented as
@@ -993,6 +1002,19 @@ impl WindowFunctionDefinition {
         }
     }
 
+    /// Whether the function supports the `[IGNORE NULLS | RESPECT NULLS]`
+    /// clause
+    pub fn supports_null_handling_clause(&self) -> bool {
+        match self {
+            WindowFunctionDefinition::AggregateUDF(fun) => {
+                fun.supports_null_handling_clause()
+            }
+            WindowFunctionDefinition::WindowUDF(fun) => {
+                fun.supports_null_handling_clause()
+            }
+        }
+    }
+
     /// Return the inner window simplification function, if any
     ///
     /// See [`WindowFunctionSimplification`] for more information
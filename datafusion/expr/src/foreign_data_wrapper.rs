@@ -0,0 +1,1139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SQL/MED (ISO/IEC 9075-9) foreign data wrappers
+//!
+//! `CREATE SERVER`, `CREATE USER MAPPING` and `CREATE FOREIGN TABLE` are
+//! planned as [`DdlStatement`]s. Executing them against a
+//! [`ForeignDataWrapperRegistry`] records the server, mapping and table
+//! definitions. When a foreign table is looked up, the
+//! [`ForeignDataWrapper`] named by its server builds a [`TableSource`] from
+//! the server and table options and the credentials of the user, which a
+//! [`ContextProvider`] can then return from
+//! [`ContextProvider::get_table_source`].
+//!
+//! `ALTER SERVER` and `ALTER FOREIGN TABLE` change the recorded options,
+//! and the wrapper is told to drop connections or metadata it cached for
+//! the old ones.
+//!
+//! User mapping options whose name ends in `_secret` do not hold a literal
+//! credential but the name of a secret. The registry resolves it through
+//! its [`CredentialProvider`] each time credentials are needed, and hands
+//! the value to the wrapper under the option name without the suffix.
+//!
+//! [`ContextProvider`]: crate::planner::ContextProvider
+//! [`ContextProvider::get_table_source`]: crate::planner::ContextProvider::get_table_source
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use crate::{
+    AlterForeignTable, AlterServer, CreateForeignTable, CreateServer, CreateUserMapping,
+    DdlStatement, DropForeignTable, DropServer, DropUserMapping, Expr, LogicalPlan,
+    OptionChange, TableProviderFilterPushDown, TableScan, TableSource, TableType,
+    UserMappingTarget,
+};
+use arrow::datatypes::SchemaRef;
+use datafusion_common::{
+    Constraints, Result, SchemaExt, TableReference, plan_datafusion_err, plan_err,
+};
+
+/// Suffix of the user mapping options that name a secret
+pub const SECRET_OPTION_SUFFIX: &str = "_secret";
+
+/// A foreign server created by `CREATE SERVER`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignServer {
+    /// The server name
+    pub name: String,
+    /// The name of the [`ForeignDataWrapper`] used to reach the server
+    pub wrapper: String,
+    /// Wrapper specific server options, such as connection parameters
+    pub options: BTreeMap<String, String>,
+}
+
+/// The connection options of a user on a foreign server, taken from its
+/// user mapping with secrets already resolved.
+///
+/// The [`Debug`] output omits the option values.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct UserCredentials {
+    user: String,
+    options: BTreeMap<String, String>,
+}
+
+impl UserCredentials {
+    pub fn new(user: impl Into<String>, options: BTreeMap<String, String>) -> Self {
+        Self {
+            user: user.into(),
+            options,
+        }
+    }
+
+    /// The local user the credentials belong to
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// The value of the connection option `key`, such as `user` or `password`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.options.get(key).map(String::as_str)
+    }
+}
+
+impl Debug for UserCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserCredentials")
+            .field("user", &self.user)
+            .field("options", &self.options.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Resolves the secrets named by user mapping options, for example by
+/// reading them from a vault, when a connection is opened
+pub trait CredentialProvider: Debug + Send + Sync {
+    /// Returns the value of the secret `name` for `user` connecting to
+    /// `server`
+    fn resolve_secret(
+        &self,
+        server: &ForeignServer,
+        user: &str,
+        name: &str,
+    ) -> Result<String>;
+}
+
+/// Builds [`TableSource`]s for the foreign tables of the servers it backs,
+/// such as `postgres_fdw` for tables living in a remote PostgreSQL database.
+pub trait ForeignDataWrapper: Debug + Send + Sync {
+    /// The name used by `CREATE SERVER ... FOREIGN DATA WRAPPER <name>`
+    fn name(&self) -> &str;
+
+    /// Checks the `OPTIONS` of a `CREATE SERVER` statement using this wrapper
+    fn validate_server_options(&self, _options: &BTreeMap<String, String>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Builds the [`TableSource`] of a foreign table on `server` from the
+    /// server and table options, connecting with `credentials`. The schema
+    /// of the returned source must match the schema declared by `table`.
+    fn create_table_source(
+        &self,
+        server: &ForeignServer,
+        table: &CreateForeignTable,
+        credentials: &UserCredentials,
+    ) -> Result<Arc<dyn TableSource>>;
+
+    /// Tests which of `filters` on `table` the wrapper can evaluate on the
+    /// remote server. Filters reported as [`Exact`] or [`Inexact`] are
+    /// pushed into the scan of the table and show up in its [`ForeignScan`].
+    ///
+    /// [`Exact`]: TableProviderFilterPushDown::Exact
+    /// [`Inexact`]: TableProviderFilterPushDown::Inexact
+    fn supports_filters_pushdown(
+        &self,
+        _server: &ForeignServer,
+        _table: &CreateForeignTable,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        Ok(vec![
+            TableProviderFilterPushDown::Unsupported;
+            filters.len()
+        ])
+    }
+
+    /// Called after the options of `server`, or of `table` on `server`,
+    /// were altered, so that connections, schemas or other metadata cached
+    /// for the old options can be dropped
+    fn invalidate(&self, _server: &ForeignServer, _table: Option<&CreateForeignTable>) {}
+}
+
+/// The work the optimizer pushed into a scan of a foreign table, for the
+/// wrapper to evaluate on the remote server instead of fetching the whole
+/// table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignScan {
+    /// The foreign table
+    pub table: TableReference,
+    /// The names of the columns to fetch, in output order
+    pub columns: Vec<String>,
+    /// The indices of the columns to fetch, or `None` for all columns
+    pub projection: Option<Vec<usize>>,
+    /// Filters the wrapper agreed to evaluate. [`Inexact`] filters are
+    /// evaluated again on the rows returned by the scan.
+    ///
+    /// [`Inexact`]: TableProviderFilterPushDown::Inexact
+    pub filters: Vec<Expr>,
+    /// The maximum number of rows needed from the scan
+    pub fetch: Option<usize>,
+}
+
+/// The [`TableSource`] of a foreign table returned by
+/// [`ForeignDataWrapperRegistry::table_source`].
+///
+/// Filter pushdown is decided by the [`ForeignDataWrapper`] of the table.
+/// Engines executing a [`TableScan`] of this source get the pushed down
+/// work from [`Self::foreign_scan`] and the source built by the wrapper
+/// from [`Self::inner`].
+pub struct ForeignTableSource {
+    wrapper: Arc<dyn ForeignDataWrapper>,
+    server: ForeignServer,
+    table: CreateForeignTable,
+    inner: Arc<dyn TableSource>,
+}
+
+impl ForeignTableSource {
+    /// The source built by the wrapper
+    pub fn inner(&self) -> &Arc<dyn TableSource> {
+        &self.inner
+    }
+
+    /// The server the table lives on
+    pub fn server(&self) -> &ForeignServer {
+        &self.server
+    }
+
+    /// The definition of the table
+    pub fn table(&self) -> &CreateForeignTable {
+        &self.table
+    }
+
+    /// Collects the projection, filters and limit the optimizer pushed
+    /// into `scan`, a scan of this source
+    pub fn foreign_scan(&self, scan: &TableScan) -> Result<ForeignScan> {
+        let schema = self.schema();
+        let columns: Vec<String> = match &scan.projection {
+            Some(projection) => projection
+                .iter()
+                .map(|i| match schema.fields().get(*i) {
+                    Some(field) => Ok(field.name().clone()),
+                    None => plan_err!(
+                        "Projection index {i} is out of bounds for foreign table {}",
+                        self.table.name
+                    ),
+                })
+                .collect::<Result<_>>()?,
+            None => schema.fields().iter().map(|f| f.name().clone()).collect(),
+        };
+        Ok(ForeignScan {
+            table: self.table.name.clone(),
+            columns,
+            projection: scan.projection.clone(),
+            filters: scan.filters.clone(),
+            fetch: scan.fetch,
+        })
+    }
+}
+
+impl TableSource for ForeignTableSource {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.inner.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.inner.table_type()
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.wrapper
+            .supports_filters_pushdown(&self.server, &self.table, filters)
+    }
+
+    fn get_logical_plan(&'_ self) -> Option<Cow<'_, LogicalPlan>> {
+        self.inner.get_logical_plan()
+    }
+
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        self.inner.get_column_default(column)
+    }
+}
+
+/// Keeps track of the registered [`ForeignDataWrapper`]s and of the
+/// foreign servers, user mappings and foreign tables created so far.
+#[derive(Debug, Default)]
+pub struct ForeignDataWrapperRegistry {
+    wrappers: HashMap<String, Arc<dyn ForeignDataWrapper>>,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
+    servers: HashMap<String, ForeignServer>,
+    /// User mapping options by server and user, `None` being `PUBLIC`
+    user_mappings: HashMap<(String, Option<String>), BTreeMap<String, String>>,
+    tables: HashMap<TableReference, CreateForeignTable>,
+}
+
+impl ForeignDataWrapperRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `wrapper`, returning the wrapper previously registered
+    /// under the same name, if any
+    pub fn register_wrapper(
+        &mut self,
+        wrapper: Arc<dyn ForeignDataWrapper>,
+    ) -> Option<Arc<dyn ForeignDataWrapper>> {
+        self.wrappers.insert(wrapper.name().to_string(), wrapper)
+    }
+
+    /// Returns the wrapper registered as `name`
+    pub fn wrapper(&self, name: &str) -> Option<&Arc<dyn ForeignDataWrapper>> {
+        self.wrappers.get(name)
+    }
+
+    /// Sets the provider resolving the secrets named by user mappings
+    pub fn with_credential_provider(
+        mut self,
+        credential_provider: Arc<dyn CredentialProvider>,
+    ) -> Self {
+        self.credential_provider = Some(credential_provider);
+        self
+    }
+
+    /// Returns the server named `name`
+    pub fn server(&self, name: &str) -> Option<&ForeignServer> {
+        self.servers.get(name)
+    }
+
+    /// Returns the [`ForeignTableSource`] of the foreign table `name`,
+    /// connecting to its server as `user`, or `None` if there is no such
+    /// table
+    pub fn table_source(
+        &self,
+        name: &TableReference,
+        user: &str,
+    ) -> Result<Option<Arc<dyn TableSource>>> {
+        let Some(table) = self.tables.get(name) else {
+            return Ok(None);
+        };
+        let server = self.servers.get(&table.server).ok_or_else(|| {
+            plan_datafusion_err!("Server {} does not exist", table.server)
+        })?;
+        let fdw = self.wrappers.get(&server.wrapper).ok_or_else(|| {
+            plan_datafusion_err!("Foreign data wrapper {} does not exist", server.wrapper)
+        })?;
+        let credentials = self.credentials(server, user)?;
+        let source = fdw.create_table_source(server, table, &credentials)?;
+        if !source
+            .schema()
+            .equivalent_names_and_types(table.schema.as_arrow())
+        {
+            return plan_err!(
+                "Foreign data wrapper {} returned a table that does not match the columns of foreign table {}",
+                fdw.name(),
+                table.name
+            );
+        }
+        Ok(Some(Arc::new(ForeignTableSource {
+            wrapper: Arc::clone(fdw),
+            server: server.clone(),
+            table: table.clone(),
+            inner: source,
+        })))
+    }
+
+    /// Returns the credentials of `user` on `server`, from the user mapping
+    /// of `user` or else from the `PUBLIC` one. Secrets are resolved on
+    /// every call so that rotated secrets are picked up.
+    pub fn credentials(
+        &self,
+        server: &ForeignServer,
+        user: &str,
+    ) -> Result<UserCredentials> {
+        let options = self
+            .user_mappings
+            .get(&(server.name.clone(), Some(user.to_string())))
+            .or_else(|| self.user_mappings.get(&(server.name.clone(), None)));
+        let mut resolved = BTreeMap::new();
+        for (key, value) in options.into_iter().flatten() {
+            let Some(option) = key.strip_suffix(SECRET_OPTION_SUFFIX) else {
+                resolved.insert(key.clone(), value.clone());
+                continue;
+            };
+            let Some(provider) = &self.credential_provider else {
+                return plan_err!(
+                    "User mapping option {key} of {user} on server {} names a secret, but no credential provider is registered",
+                    server.name
+                );
+            };
+            let secret = provider.resolve_secret(server, user, value)?;
+            resolved.insert(option.to_string(), secret);
+        }
+        Ok(UserCredentials::new(user, resolved))
+    }
+
+    /// Executes `statement` on behalf of `current_user` if it is a SQL/MED
+    /// statement handled by the registry, returning whether it was
+    pub fn execute(
+        &mut self,
+        statement: &DdlStatement,
+        current_user: &str,
+    ) -> Result<bool> {
+        match statement {
+            DdlStatement::CreateServer(server) => self.create_server(server)?,
+            DdlStatement::CreateUserMapping(mapping) => {
+                self.create_user_mapping(mapping, current_user)?
+            }
+            DdlStatement::CreateForeignTable(table) => {
+                self.create_foreign_table(table)?
+            }
+            DdlStatement::AlterServer(alter) => self.alter_server(alter)?,
+            DdlStatement::AlterForeignTable(alter) => self.alter_foreign_table(alter)?,
+            DdlStatement::DropServer(DropServer { name, if_exists }) => {
+                self.drop_server(name, *if_exists)?
+            }
+            DdlStatement::DropForeignTable(DropForeignTable { name, if_exists }) => {
+                self.drop_foreign_table(name, *if_exists)?
+            }
+            DdlStatement::DropUserMapping(DropUserMapping {
+                user,
+                server,
+                if_exists,
+            }) => self.drop_user_mapping(user, server, current_user, *if_exists)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Creates the server described by a `CREATE SERVER` statement
+    pub fn create_server(&mut self, statement: &CreateServer) -> Result<()> {
+        let CreateServer {
+            name,
+            wrapper,
+            options,
+            if_not_exists,
+        } = statement;
+        if self.servers.contains_key(name) {
+            if *if_not_exists {
+                return Ok(());
+            }
+            return plan_err!("Server {name} already exists");
+        }
+        let Some(fdw) = self.wrappers.get(wrapper) else {
+            return plan_err!("Foreign data wrapper {wrapper} does not exist");
+        };
+        fdw.validate_server_options(options)?;
+        self.servers.insert(
+            name.clone(),
+            ForeignServer {
+                name: name.clone(),
+                wrapper: wrapper.clone(),
+                options: options.clone(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Applies the option changes of an `ALTER SERVER` statement and
+    /// lets the wrapper drop what it cached for the old options
+    pub fn alter_server(&mut self, statement: &AlterServer) -> Result<()> {
+        let Some(server) = self.servers.get(&statement.name) else {
+            return plan_err!("Server {} does not exist", statement.name);
+        };
+        let fdw = self.wrappers.get(&server.wrapper).ok_or_else(|| {
+            plan_datafusion_err!("Foreign data wrapper {} does not exist", server.wrapper)
+        })?;
+        let mut altered = server.clone();
+        apply_option_changes(&mut altered.options, &statement.options)?;
+        fdw.validate_server_options(&altered.options)?;
+        fdw.invalidate(&altered, None);
+        self.servers.insert(statement.name.clone(), altered);
+        Ok(())
+    }
+
+    /// Drops the server `name`. Servers still used by user mappings or
+    /// foreign tables can not be dropped.
+    pub fn drop_server(&mut self, name: &str, if_exists: bool) -> Result<()> {
+        if !self.servers.contains_key(name) {
+            if if_exists {
+                return Ok(());
+            }
+            return plan_err!("Server {name} does not exist");
+        }
+        if let Some(table) = self.tables.values().find(|table| table.server == name) {
+            return plan_err!(
+                "Cannot drop server {name} because foreign table {} depends on it",
+                table.name
+            );
+        }
+        if self.user_mappings.keys().any(|(server, _)| server == name) {
+            return plan_err!(
+                "Cannot drop server {name} because user mappings depend on it"
+            );
+        }
+        self.servers.remove(name);
+        Ok(())
+    }
+
+    /// Creates the user mapping described by a `CREATE USER MAPPING`
+    /// statement executed by `current_user`
+    pub fn create_user_mapping(
+        &mut self,
+        statement: &CreateUserMapping,
+        current_user: &str,
+    ) -> Result<()> {
+        let CreateUserMapping {
+            user,
+            server,
+            options,
+            if_not_exists,
+        } = statement;
+        if !self.servers.contains_key(server) {
+            return plan_err!("Server {server} does not exist");
+        }
+        let key = (server.clone(), mapped_user(user, current_user));
+        if self.user_mappings.contains_key(&key) {
+            if *if_not_exists {
+                return Ok(());
+            }
+            return plan_err!(
+                "User mapping for {} already exists for server {server}",
+                key.1.as_deref().unwrap_or("PUBLIC")
+            );
+        }
+        self.user_mappings.insert(key, options.clone());
+        Ok(())
+    }
+
+    /// Drops the user mapping of `user` on `server`
+    pub fn drop_user_mapping(
+        &mut self,
+        user: &UserMappingTarget,
+        server: &str,
+        current_user: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        let key = (server.to_string(), mapped_user(user, current_user));
+        if self.user_mappings.remove(&key).is_none() && !if_exists {
+            return plan_err!(
+                "User mapping for {} does not exist for server {server}",
+                key.1.as_deref().unwrap_or("PUBLIC")
+            );
+        }
+        Ok(())
+    }
+
+    /// Creates the foreign table described by a `CREATE FOREIGN TABLE`
+    /// statement
+    pub fn create_foreign_table(&mut self, statement: &CreateForeignTable) -> Result<()> {
+        if self.tables.contains_key(&statement.name) {
+            if statement.if_not_exists {
+                return Ok(());
+            }
+            return plan_err!("Foreign table {} already exists", statement.name);
+        }
+        if !self.servers.contains_key(&statement.server) {
+            return plan_err!("Server {} does not exist", statement.server);
+        }
+        self.tables
+            .insert(statement.name.clone(), statement.clone());
+        Ok(())
+    }
+
+    /// Applies the option changes of an `ALTER FOREIGN TABLE` statement
+    /// and lets the wrapper drop what it cached for the old options
+    pub fn alter_foreign_table(&mut self, statement: &AlterForeignTable) -> Result<()> {
+        let Some(table) = self.tables.get_mut(&statement.name) else {
+            if statement.if_exists {
+                return Ok(());
+            }
+            return plan_err!("Foreign table {} does not exist", statement.name);
+        };
+        let mut options = table.options.clone();
+        apply_option_changes(&mut options, &statement.options)?;
+        table.options = options;
+        if let Some(server) = self.servers.get(&table.server)
+            && let Some(fdw) = self.wrappers.get(&server.wrapper)
+        {
+            fdw.invalidate(server, Some(table));
+        }
+        Ok(())
+    }
+
+    /// Drops the foreign table `name`
+    pub fn drop_foreign_table(
+        &mut self,
+        name: &TableReference,
+        if_exists: bool,
+    ) -> Result<()> {
+        if self.tables.remove(name).is_none() && !if_exists {
+            return plan_err!("Foreign table {name} does not exist");
+        }
+        Ok(())
+    }
+}
+
+/// Applies `changes` to `options` following SQL/MED rules: options are
+/// only added when not set yet, and only set or dropped when set
+pub(crate) fn apply_option_changes(
+    options: &mut BTreeMap<String, String>,
+    changes: &[OptionChange],
+) -> Result<()> {
+    for change in changes {
+        match change {
+            OptionChange::Add { key, value } => {
+                if options.contains_key(key) {
+                    return plan_err!("Option {key} provided more than once");
+                }
+                options.insert(key.clone(), value.clone());
+            }
+            OptionChange::Set { key, value } => match options.get_mut(key) {
+                Some(current) => *current = value.clone(),
+                None => return plan_err!("Option {key} not found"),
+            },
+            OptionChange::Drop { key } => {
+                if options.remove(key).is_none() {
+                    return plan_err!("Option {key} not found");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The user a mapping is stored under, `None` being `PUBLIC`
+fn mapped_user(user: &UserMappingTarget, current_user: &str) -> Option<String> {
+    match user {
+        UserMappingTarget::CurrentUser => Some(current_user.to_string()),
+        UserMappingTarget::Public => None,
+        UserMappingTarget::User(name) => Some(name.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logical_plan::builder::LogicalTableSource;
+    use crate::{col, lit};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::ToDFSchema;
+    use std::sync::Mutex;
+
+    /// Serves every foreign table with the schema it declares, requires a
+    /// `host` server option and records the credentials it connects with
+    /// and the objects it is asked to invalidate
+    #[derive(Debug, Default)]
+    struct TestWrapper {
+        connections: Mutex<Vec<(Option<String>, Option<String>)>>,
+        invalidations: Mutex<Vec<String>>,
+    }
+
+    impl ForeignDataWrapper for TestWrapper {
+        fn name(&self) -> &str {
+            "test_fdw"
+        }
+
+        fn validate_server_options(
+            &self,
+            options: &BTreeMap<String, String>,
+        ) -> Result<()> {
+            if !options.contains_key("host") {
+                return plan_err!("test_fdw servers require a host option");
+            }
+            Ok(())
+        }
+
+        fn create_table_source(
+            &self,
+            _server: &ForeignServer,
+            table: &CreateForeignTable,
+            credentials: &UserCredentials,
+        ) -> Result<Arc<dyn TableSource>> {
+            self.connections.lock().unwrap().push((
+                credentials.get("user").map(str::to_string),
+                credentials.get("password").map(str::to_string),
+            ));
+            let mut schema = table.schema.as_arrow().clone();
+            if table.options.contains_key("extra_column") {
+                let mut fields = schema.fields().to_vec();
+                fields.push(Arc::new(Field::new("extra", DataType::Int32, true)));
+                schema = Schema::new(fields);
+            }
+            Ok(Arc::new(LogicalTableSource::new(Arc::new(schema))))
+        }
+
+        /// Only comparisons can be evaluated remotely
+        fn supports_filters_pushdown(
+            &self,
+            _server: &ForeignServer,
+            _table: &CreateForeignTable,
+            filters: &[&Expr],
+        ) -> Result<Vec<TableProviderFilterPushDown>> {
+            Ok(filters
+                .iter()
+                .map(|filter| match filter {
+                    Expr::BinaryExpr(_) => TableProviderFilterPushDown::Exact,
+                    _ => TableProviderFilterPushDown::Unsupported,
+                })
+                .collect())
+        }
+
+        fn invalidate(&self, server: &ForeignServer, table: Option<&CreateForeignTable>) {
+            let invalidated = match table {
+                Some(table) => table.name.to_string(),
+                None => format!("{} {}", server.name, server.options["host"]),
+            };
+            self.invalidations.lock().unwrap().push(invalidated);
+        }
+    }
+
+    /// Resolves every secret to `<user>:<name>`
+    #[derive(Debug)]
+    struct TestCredentialProvider;
+
+    impl CredentialProvider for TestCredentialProvider {
+        fn resolve_secret(
+            &self,
+            _server: &ForeignServer,
+            user: &str,
+            name: &str,
+        ) -> Result<String> {
+            if name == "missing" {
+                return plan_err!("Secret {name} not found");
+            }
+            Ok(format!("{user}:{name}"))
+        }
+    }
+
+    fn options(options: &[(&str, &str)]) -> BTreeMap<String, String> {
+        options
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn create_server(name: &str, server_options: &[(&str, &str)]) -> CreateServer {
+        CreateServer {
+            name: name.to_string(),
+            wrapper: "test_fdw".to_string(),
+            options: options(server_options),
+            if_not_exists: false,
+        }
+    }
+
+    fn create_table(
+        name: &str,
+        server: &str,
+        table_options: &[(&str, &str)],
+    ) -> CreateForeignTable {
+        CreateForeignTable {
+            name: TableReference::bare(name),
+            schema: Schema::new(vec![Field::new("id", DataType::Int32, true)])
+                .to_dfschema_ref()
+                .unwrap(),
+            server: server.to_string(),
+            options: options(table_options),
+            if_not_exists: false,
+        }
+    }
+
+    fn create_user_mapping(
+        user: UserMappingTarget,
+        mapping_options: &[(&str, &str)],
+    ) -> CreateUserMapping {
+        CreateUserMapping {
+            user,
+            server: "remote".to_string(),
+            options: options(mapping_options),
+            if_not_exists: false,
+        }
+    }
+
+    fn registry(wrapper: Arc<TestWrapper>) -> Result<ForeignDataWrapperRegistry> {
+        let mut registry = ForeignDataWrapperRegistry::new()
+            .with_credential_provider(Arc::new(TestCredentialProvider));
+        registry.register_wrapper(wrapper);
+        let server = create_server("remote", &[("host", "localhost")]);
+        registry.execute(&DdlStatement::CreateServer(server), "admin")?;
+        Ok(registry)
+    }
+
+    fn users() -> TableReference {
+        TableReference::bare("users")
+    }
+
+    #[test]
+    fn foreign_tables_become_table_sources() -> Result<()> {
+        let mut registry = registry(Default::default())?;
+        let table = create_table("users", "remote", &[("table_name", "users")]);
+        assert!(registry.execute(&DdlStatement::CreateForeignTable(table), "admin")?);
+
+        let source = registry
+            .table_source(&users(), "admin")?
+            .expect("foreign table source");
+        assert_eq!(source.schema().field(0).name(), "id");
+        assert!(
+            registry
+                .table_source(&TableReference::bare("other"), "admin")?
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn foreign_scans_receive_pushed_down_work() -> Result<()> {
+        let mut registry = registry(Default::default())?;
+        let mut table = create_table("users", "remote", &[]);
+        table.schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+        ])
+        .to_dfschema_ref()?;
+        registry.create_foreign_table(&table)?;
+        let source = registry.table_source(&users(), "admin")?.unwrap();
+
+        let comparison = col("id").gt(lit(10));
+        let is_null = col("name").is_null();
+        assert_eq!(
+            source.supports_filters_pushdown(&[&comparison, &is_null])?,
+            vec![
+                TableProviderFilterPushDown::Exact,
+                TableProviderFilterPushDown::Unsupported
+            ]
+        );
+
+        let scan = TableScan::try_new(
+            users(),
+            Arc::clone(&source),
+            Some(vec![1]),
+            vec![comparison.clone()],
+            Some(5),
+        )?;
+        let source = source
+            .as_any()
+            .downcast_ref::<ForeignTableSource>()
+            .expect("foreign table source");
+        assert_eq!(
+            source.foreign_scan(&scan)?,
+            ForeignScan {
+                table: users(),
+                columns: vec!["name".to_string()],
+                projection: Some(vec![1]),
+                filters: vec![comparison],
+                fetch: Some(5),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn alter_options() -> Result<()> {
+        let wrapper = Arc::new(TestWrapper::default());
+        let mut registry = registry(Arc::clone(&wrapper))?;
+        registry.create_foreign_table(&create_table(
+            "users",
+            "remote",
+            &[("table_name", "users")],
+        ))?;
+
+        let alter = AlterServer {
+            name: "remote".to_string(),
+            options: vec![
+                OptionChange::Set {
+                    key: "host".to_string(),
+                    value: "newhost".to_string(),
+                },
+                OptionChange::Add {
+                    key: "port".to_string(),
+                    value: "5432".to_string(),
+                },
+            ],
+        };
+        assert!(registry.execute(&DdlStatement::AlterServer(alter), "admin")?);
+        assert_eq!(
+            registry.server("remote").unwrap().options,
+            options(&[("host", "newhost"), ("port", "5432")])
+        );
+
+        let alter = AlterForeignTable {
+            name: users(),
+            if_exists: false,
+            options: vec![
+                OptionChange::Drop {
+                    key: "table_name".to_string(),
+                },
+                OptionChange::Add {
+                    key: "schema_name".to_string(),
+                    value: "public".to_string(),
+                },
+            ],
+        };
+        assert!(registry.execute(&DdlStatement::AlterForeignTable(alter), "admin")?);
+        assert_eq!(
+            *wrapper.invalidations.lock().unwrap(),
+            vec!["remote newhost".to_string(), "users".to_string()]
+        );
+
+        // Failed changes leave the options untouched
+        let drop_host = AlterServer {
+            name: "remote".to_string(),
+            options: vec![OptionChange::Drop {
+                key: "host".to_string(),
+            }],
+        };
+        let err = registry.alter_server(&drop_host).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: test_fdw servers require a host option"
+        );
+        let alter = AlterForeignTable {
+            name: users(),
+            if_exists: false,
+            options: vec![
+                OptionChange::Set {
+                    key: "schema_name".to_string(),
+                    value: "private".to_string(),
+                },
+                OptionChange::Drop {
+                    key: "table_name".to_string(),
+                },
+            ],
+        };
+        let err = registry.alter_foreign_table(&alter).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Option table_name not found"
+        );
+        let source = registry.table_source(&users(), "admin")?.unwrap();
+        let source = source
+            .as_any()
+            .downcast_ref::<ForeignTableSource>()
+            .unwrap();
+        assert_eq!(source.server().options["host"], "newhost");
+        assert_eq!(
+            source.table().options,
+            options(&[("schema_name", "public")])
+        );
+        assert_eq!(wrapper.invalidations.lock().unwrap().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn user_mappings_provide_per_user_credentials() -> Result<()> {
+        let wrapper = Arc::new(TestWrapper::default());
+        let mut registry = registry(Arc::clone(&wrapper))?;
+        registry.create_foreign_table(&create_table("users", "remote", &[]))?;
+
+        let mapping = create_user_mapping(
+            UserMappingTarget::CurrentUser,
+            &[("user", "alice_remote"), ("password_secret", "alice_pw")],
+        );
+        registry.execute(&DdlStatement::CreateUserMapping(mapping), "alice")?;
+        let mapping =
+            create_user_mapping(UserMappingTarget::Public, &[("user", "guest")]);
+        registry.execute(&DdlStatement::CreateUserMapping(mapping), "admin")?;
+
+        registry.table_source(&users(), "alice")?;
+        registry.table_source(&users(), "bob")?;
+        assert_eq!(
+            *wrapper.connections.lock().unwrap(),
+            vec![
+                (
+                    Some("alice_remote".to_string()),
+                    Some("alice:alice_pw".to_string())
+                ),
+                (Some("guest".to_string()), None),
+            ]
+        );
+
+        // Secrets never show up in debug output
+        let credentials =
+            registry.credentials(registry.server("remote").unwrap(), "alice")?;
+        assert_eq!(
+            format!("{credentials:?}"),
+            r#"UserCredentials { user: "alice", options: ["password", "user"] }"#
+        );
+
+        let mapping = create_user_mapping(
+            UserMappingTarget::User("carol".to_string()),
+            &[("password_secret", "missing")],
+        );
+        registry.create_user_mapping(&mapping, "admin")?;
+        let err = registry.table_source(&users(), "carol").unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Secret missing not found"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn secrets_require_a_credential_provider() -> Result<()> {
+        let mut registry = ForeignDataWrapperRegistry::new();
+        registry.register_wrapper(Arc::new(TestWrapper::default()));
+        registry.create_server(&create_server("remote", &[("host", "localhost")]))?;
+        let mapping =
+            create_user_mapping(UserMappingTarget::Public, &[("password_secret", "pw")]);
+        registry.create_user_mapping(&mapping, "admin")?;
+        let err = registry
+            .credentials(registry.server("remote").unwrap(), "alice")
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: User mapping option password_secret of alice on server remote names a secret, but no credential provider is registered"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn foreign_table_errors() -> Result<()> {
+        let mut registry = ForeignDataWrapperRegistry::new();
+        registry.register_wrapper(Arc::new(TestWrapper::default()));
+
+        let mut server = create_server("remote", &[]);
+        let err = registry.create_server(&server).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: test_fdw servers require a host option"
+        );
+        server
+            .options
+            .insert("host".to_string(), "localhost".to_string());
+        server.wrapper = "missing_fdw".to_string();
+        let err = registry.create_server(&server).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Foreign data wrapper missing_fdw does not exist"
+        );
+        server.wrapper = "test_fdw".to_string();
+        registry.create_server(&server)?;
+
+        let err = registry
+            .create_foreign_table(&create_table("users", "nowhere", &[]))
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Server nowhere does not exist"
+        );
+        registry.create_foreign_table(&create_table(
+            "wide",
+            "remote",
+            &[("extra_column", "")],
+        ))?;
+        let err = registry
+            .table_source(&TableReference::bare("wide"), "admin")
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Foreign data wrapper test_fdw returned a table that does not match the columns of foreign table wide"
+        );
+        registry.drop_foreign_table(&TableReference::bare("wide"), false)?;
+
+        let mapping = create_user_mapping(UserMappingTarget::CurrentUser, &[]);
+        registry.create_user_mapping(&mapping, "alice")?;
+        let err = registry.create_user_mapping(&mapping, "alice").unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: User mapping for alice already exists for server remote"
+        );
+
+        registry.create_foreign_table(&create_table("users", "remote", &[]))?;
+        let err = registry.drop_server("remote", false).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Cannot drop server remote because foreign table users depends on it"
+        );
+        registry.drop_foreign_table(&users(), false)?;
+        let err = registry.drop_server("remote", false).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Cannot drop server remote because user mappings depend on it"
+        );
+        registry.drop_user_mapping(
+            &UserMappingTarget::User("alice".to_string()),
+            "remote",
+            "admin",
+            false,
+        )?;
+        registry.drop_server("remote", false)?;
+        Ok(())
+    }
+
+    #[test]
+    fn drop_statements() -> Result<()> {
+        let mut registry = registry(Default::default())?;
+        let table = create_table("users", "remote", &[]);
+        registry.execute(&DdlStatement::CreateForeignTable(table), "admin")?;
+        let mapping = create_user_mapping(UserMappingTarget::CurrentUser, &[]);
+        registry.execute(&DdlStatement::CreateUserMapping(mapping), "alice")?;
+
+        let drop_server = |if_exists| {
+            DdlStatement::DropServer(DropServer {
+                name: "remote".to_string(),
+                if_exists,
+            })
+        };
+        let drop_table = |if_exists| {
+            DdlStatement::DropForeignTable(DropForeignTable {
+                name: users(),
+                if_exists,
+            })
+        };
+        let drop_mapping = |if_exists| {
+            DdlStatement::DropUserMapping(DropUserMapping {
+                user: UserMappingTarget::CurrentUser,
+                server: "remote".to_string(),
+                if_exists,
+            })
+        };
+
+        let err = registry.execute(&drop_server(true), "admin").unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Cannot drop server remote because foreign table users depends on it"
+        );
+        assert!(registry.execute(&drop_table(false), "admin")?);
+        assert!(registry.table_source(&users(), "admin")?.is_none());
+        let err = registry.execute(&drop_table(false), "admin").unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Foreign table users does not exist"
+        );
+        assert!(registry.execute(&drop_table(true), "admin")?);
+
+        // The mapping of the current user is the one of alice
+        let err = registry.execute(&drop_mapping(false), "admin").unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: User mapping for admin does not exist for server remote"
+        );
+        assert!(registry.execute(&drop_mapping(true), "admin")?);
+        assert!(registry.execute(&drop_mapping(false), "alice")?);
+
+        assert!(registry.execute(&drop_server(false), "admin")?);
+        assert!(registry.server("remote").is_none());
+        let err = registry.execute(&drop_server(false), "admin").unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Server remote does not exist"
+        );
+        assert!(registry.execute(&drop_server(true), "admin")?);
+        Ok(())
+    }
+}
@@ -41,6 +41,7 @@ extern crate core;
 mod literal;
 mod operation;
 mod partition_evaluator;
+mod table_function;
 mod table_source;
 mod udaf;
 mod udf;
@@ -48,11 +49,13 @@ mod udwf;
 
 pub mod arguments;
 pub mod conditional_expressions;
+pub mod copy_progress;
 pub mod execution_props;
 pub mod expr;
 pub mod expr_fn;
 pub mod expr_rewriter;
 pub mod expr_schema;
+pub mod foreign_data_wrapper;
 pub mod function;
 pub mod select_expr;
 pub mod groups_accumulator {
@@ -75,10 +78,12 @@ pub mod statistics {
 }
 mod predicate_bounds;
 pub mod ptr_eq;
+pub mod table_maintenance;
 pub mod test;
 pub mod tree_node;
 pub mod type_coercion;
 pub mod udf_eq;
+pub mod user_catalog;
 pub mod utils;
 pub mod var_provider;
 pub mod window_frame;
@@ -114,15 +119,22 @@ pub use logical_plan::*;
 pub use partition_evaluator::PartitionEvaluator;
 
 pub use sqlparser;
-pub use table_source::{TableProviderFilterPushDown, TableSource, TableType};
+pub use table_function::PolymorphicTableFunctionImpl;
+pub use table_source::{
+    TableProviderFilterPushDown, TableProviderHintSupport, TableProviderRowLockSupport,
+    TableSource, TableType,
+};
 pub use udaf::{
-    AggregateUDF, AggregateUDFImpl, ReversedUDAF, SetMonotonicity, StatisticsArgs,
-    udaf_default_display_name, udaf_default_human_display, udaf_default_return_field,
-    udaf_default_schema_name, udaf_default_window_function_display_name,
-    udaf_default_window_function_schema_name,
+    AggregateUDF, AggregateUDFImpl, OnOverflow, ReversedUDAF, SetMonotonicity,
+    StatisticsArgs, udaf_default_display_name, udaf_default_human_display,
+    udaf_default_return_field, udaf_default_schema_name,
+    udaf_default_window_function_display_name, udaf_default_window_function_schema_name,
 };
 pub use session::{NoSession, SessionProvider, no_session};
-pub use udf::{ReturnFieldArgs, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl};
+pub use udf::{
+    JsonFunctionOptions, JsonWrapper, ReturnFieldArgs, ScalarFunctionArgs, ScalarUDF,
+    ScalarUDFImpl,
+};
 pub use udwf::{LimitEffect, ReversedUDWF, WindowUDF, WindowUDFImpl};
 pub use window_frame::{
     WindowFrame, WindowFrameBound, WindowFrameExclusion, WindowFrameUnits,
@@ -34,8 +34,8 @@ use crate::expr_rewriter::{
 use crate::logical_plan::{
     Aggregate, Analyze, Distinct, DistinctOn, EmptyRelation, Explain, Filter, Join,
     JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-    Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-    Window,
+    Projection, Repartition, Sample, SampleMethod, SampleSize, Sort, SubqueryAlias,
+    TableScan, Union, Unnest, Values, Window,
 };
 use crate::select_expr::SelectExpr;
 use crate::utils::{
@@ -49,6 +49,7 @@ use crate::{
 };
 
 use super::dml::InsertOp;
+use super::lineage::mark_view_columns;
 use arrow::compute::can_cast_types;
 use arrow::datatypes::{DataType, Field, FieldRef, Fields, Schema, SchemaRef};
 use datafusion_common::display::ToStringifiedPlan;
@@ -56,7 +57,7 @@ use datafusion_common::file_options::file_type::FileType;
 use datafusion_common::metadata::FieldMetadata;
 use datafusion_common::{
     Column, Constraints, DFSchema, DFSchemaRef, NullEquality, Result, ScalarValue,
-    TableReference, ToDFSchema, UnnestOptions, exec_err,
+    Statistics, TableReference, ToDFSchema, UnnestOptions, exec_err,
     get_target_functional_dependencies, internal_datafusion_err, plan_datafusion_err,
     plan_err,
 };
@@ -531,15 +532,18 @@ impl LogicalPlanBuilder {
                         Expr::Column(Column::from(sub_plan.schema().qualified_field(i)))
                     })
                     .collect::<Vec<_>>();
-                return Self::new(sub_plan)
+                let plan = Self::new(sub_plan)
                     .project(projection_exprs)?
-                    .alias(table_scan.table_name);
+                    .alias(table_scan.table_name)?
+                    .build()?;
+                return mark_view_columns(plan).map(Self::new);
             }
 
             // Ensures that the reference to the inlined table remains the
             // same, meaning we don't have to change any of the parent nodes
             // that reference this table.
-            return Self::new(sub_plan).alias(table_scan.table_name);
+            let plan = subquery_alias(sub_plan, table_scan.table_name)?;
+            return mark_view_columns(plan).map(Self::new);
         }
 
         Ok(Self::new(LogicalPlan::TableScan(table_scan)))
@@ -676,6 +680,18 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Keep a random subset of the rows (`TABLESAMPLE`)
+    pub fn sample(
+        self,
+        method: SampleMethod,
+        size: SampleSize,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        Sample::try_new(self.plan, method, size, seed)
+            .map(LogicalPlan::Sample)
+            .map(Self::new)
+    }
+
     /// Apply an alias
     pub fn alias(self, alias: impl Into<TableReference>) -> Result<Self> {
         subquery_alias(Arc::unwrap_or_clone(self.plan), alias).map(Self::new)
@@ -1993,23 +2009,38 @@ fn project_with_validation(
 
 /// If there is a REPLACE statement in the projected expression in the form of
 /// "REPLACE (some_column_within_an_expr AS some_column)", this function replaces
-/// that column with the given replace expression. Column name remains the same.
-/// Multiple REPLACEs are also possible with comma separations.
+/// that column with the given replace expression. Column name and position
+/// remain the same. Multiple REPLACEs are also possible with comma separations.
+/// Each replaced column must be selected by the wildcard.
 fn replace_columns(
     mut exprs: Vec<Expr>,
     replace: &PlannedReplaceSelectItem,
 ) -> Result<Vec<Expr>> {
+    let mut replaced = HashSet::new();
     for expr in exprs.iter_mut() {
-        if let Expr::Column(Column { name, .. }) = expr
-            && let Some((_, new_expr)) = replace
+        if let Expr::Column(Column { relation, name, .. }) = expr
+            && let Some((item, new_expr)) = replace
                 .items()
                 .iter()
                 .zip(replace.expressions().iter())
                 .find(|(item, _)| item.column_name.value == *name)
         {
-            *expr = new_expr.clone().alias(name.clone())
+            replaced.insert(&item.column_name.value);
+            *expr = new_expr
+                .clone()
+                .alias_qualified(relation.clone(), name.clone())
         }
     }
+    if let Some(item) = replace
+        .items()
+        .iter()
+        .find(|item| !replaced.contains(&item.column_name.value))
+    {
+        return plan_err!(
+            "REPLACE contains column {}, which is not selected by the wildcard",
+            item.column_name.value
+        );
+    }
     Ok(exprs)
 }
 
@@ -2073,10 +2104,7 @@ pub fn table_scan_with_filter_and_fetch(
 pub fn table_source(table_schema: &Schema) -> Arc<dyn TableSource> {
     // TODO should we take SchemaRef and avoid cloning?
     let table_schema = Arc::new(table_schema.clone());
-    Arc::new(LogicalTableSource {
-        table_schema,
-        constraints: Default::default(),
-    })
+    Arc::new(LogicalTableSource::new(table_schema))
 }
 
 pub fn table_source_with_constraints(
@@ -2085,10 +2113,7 @@ pub fn table_source_with_constraints(
 ) -> Arc<dyn TableSource> {
     // TODO should we take SchemaRef and avoid cloning?
     let table_schema = Arc::new(table_schema.clone());
-    Arc::new(LogicalTableSource {
-        table_schema,
-        constraints,
-    })
+    Arc::new(LogicalTableSource::new(table_schema).with_constraints(constraints))
 }
 
 /// Wrap projection for a plan, if the join keys contains normal expression.
@@ -2160,6 +2185,7 @@ pub fn wrap_projection_for_join_if_necessary(
 pub struct LogicalTableSource {
     table_schema: SchemaRef,
     constraints: Constraints,
+    statistics: Option<Statistics>,
 }
 
 impl LogicalTableSource {
@@ -2168,6 +2194,7 @@ impl LogicalTableSource {
         Self {
             table_schema,
             constraints: Constraints::default(),
+            statistics: None,
         }
     }
 
@@ -2175,6 +2202,12 @@ impl LogicalTableSource {
         self.constraints = constraints;
         self
     }
+
+    /// Sets the statistics of the table, as if collected by `ANALYZE`
+    pub fn with_statistics(mut self, statistics: Statistics) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
 }
 
 impl TableSource for LogicalTableSource {
@@ -2190,6 +2223,10 @@ impl TableSource for LogicalTableSource {
         Some(&self.constraints)
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        self.statistics.clone()
+    }
+
     fn supports_filters_pushdown(
         &self,
         filters: &[&Expr],
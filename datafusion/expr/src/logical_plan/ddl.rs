@@ -21,7 +21,7 @@ use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, LazyLock};
 use std::{
-    fmt::{self, Display},
+    fmt::{self, Debug, Display},
     hash::{Hash, Hasher},
 };
 
@@ -30,6 +30,7 @@ use arrow::datatypes::DataType;
 use datafusion_common::tree_node::{Transformed, TreeNodeContainer, TreeNodeRecursion};
 use datafusion_common::{
     Constraints, DFSchema, DFSchemaRef, Result, SchemaReference, TableReference,
+    internal_err, plan_err,
 };
 pub use sqlparser::ast::{
     AlterMaterializedViewOperation, AlterTable, CreateDomain, DropBehavior, DropDomain,
@@ -39,7 +40,7 @@ pub use sqlparser::ast::{
 pub use sqlparser::ast::{
     AlterForeignDataWrapperOperation, AlterForeignDataWrapperStatement,
     AlterForeignTableOperation, AlterForeignTableStatement, AlterServerOperation,
-    AlterServerStatement, CreateForeignDataWrapperStatement, CreateForeignTableStatement,
+    AlterServerStatement, CreateForeignDataWrapperStatement,
     CreateServerOption, CreateServerStatement, CreateUserMappingStatement,
     DropForeignDataWrapperStatement, DropForeignTableStatement, DropServerStatement,
     DropUserMappingStatement, ImportForeignSchemaLimitType, ImportForeignSchemaStatement,
@@ -109,17 +110,23 @@ pub enum DdlStatement {
     CreateRole(CreateRole),
     /// DROP ROLE
     DropRole(DropRole),
+    /// CREATE USER
+    CreateUser(CreateUser),
+    /// ALTER USER
+    AlterUser(AlterUser),
+    /// DROP USER
+    DropUser(DropUser),
     /// CREATE PROPERTY GRAPH (SQL/PGQ)
     CreatePropertyGraph(CreatePropertyGraph),
     /// DROP PROPERTY GRAPH (SQL/PGQ)
     DropPropertyGraph(DropPropertyGraph),
     // SQL/MED (Management of External Data) - ISO/IEC 9075-9
     /// CREATE SERVER
-    CreateServer(CreateServerStatement),
+    CreateServer(CreateServer),
     /// ALTER SERVER
-    AlterServer(AlterServerStatement),
+    AlterServer(AlterServer),
     /// DROP SERVER
-    DropServer(DropServerStatement),
+    DropServer(DropServer),
     /// CREATE FOREIGN DATA WRAPPER
     CreateForeignDataWrapper(CreateForeignDataWrapperStatement),
     /// ALTER FOREIGN DATA WRAPPER
@@ -127,17 +134,17 @@ pub enum DdlStatement {
     /// DROP FOREIGN DATA WRAPPER
     DropForeignDataWrapper(DropForeignDataWrapperStatement),
     /// CREATE FOREIGN TABLE
-    CreateForeignTable(CreateForeignTableStatement),
+    CreateForeignTable(CreateForeignTable),
     /// ALTER FOREIGN TABLE
-    AlterForeignTable(AlterForeignTableStatement),
+    AlterForeignTable(AlterForeignTable),
     /// DROP FOREIGN TABLE
-    DropForeignTable(DropForeignTableStatement),
+    DropForeignTable(DropForeignTable),
     /// CREATE USER MAPPING
-    CreateUserMapping(CreateUserMappingStatement),
+    CreateUserMapping(CreateUserMapping),
     /// ALTER USER MAPPING
     AlterUserMapping(AlterUserMappingStatement),
     /// DROP USER MAPPING
-    DropUserMapping(DropUserMappingStatement),
+    DropUserMapping(DropUserMapping),
     /// IMPORT FOREIGN SCHEMA
     ImportForeignSchema(ImportForeignSchemaStatement),
 }
@@ -146,7 +153,8 @@ impl DdlStatement {
     /// Get a reference to the logical plan's schema
     pub fn schema(&self) -> &DFSchemaRef {
         match self {
-            DdlStatement::CreateExternalTable(CreateExternalTable { schema, .. }) => {
+            DdlStatement::CreateExternalTable(CreateExternalTable { schema, .. })
+            | DdlStatement::CreateForeignTable(CreateForeignTable { schema, .. }) => {
                 schema
             }
             DdlStatement::CreateMemoryTable(CreateMemoryTable { input, .. })
@@ -184,6 +192,9 @@ impl DdlStatement {
             | DdlStatement::DropProcedure(_)
             | DdlStatement::CreateRole(_)
             | DdlStatement::DropRole(_)
+            | DdlStatement::CreateUser(_)
+            | DdlStatement::AlterUser(_)
+            | DdlStatement::DropUser(_)
             | DdlStatement::CreatePropertyGraph(_)
             | DdlStatement::DropPropertyGraph(_)
             // SQL/MED statements return empty schema
@@ -193,7 +204,6 @@ impl DdlStatement {
             | DdlStatement::CreateForeignDataWrapper(_)
             | DdlStatement::AlterForeignDataWrapper(_)
             | DdlStatement::DropForeignDataWrapper(_)
-            | DdlStatement::CreateForeignTable(_)
             | DdlStatement::AlterForeignTable(_)
             | DdlStatement::DropForeignTable(_)
             | DdlStatement::CreateUserMapping(_)
@@ -235,6 +245,9 @@ impl DdlStatement {
             DdlStatement::DropProcedure(_) => "DropProcedure",
             DdlStatement::CreateRole(_) => "CreateRole",
             DdlStatement::DropRole(_) => "DropRole",
+            DdlStatement::CreateUser(_) => "CreateUser",
+            DdlStatement::AlterUser(_) => "AlterUser",
+            DdlStatement::DropUser(_) => "DropUser",
             DdlStatement::CreatePropertyGraph(_) => "CreatePropertyGraph",
             DdlStatement::DropPropertyGraph(_) => "DropPropertyGraph",
             // SQL/MED statements
@@ -289,6 +302,9 @@ impl DdlStatement {
             DdlStatement::DropProcedure(_) => vec![],
             DdlStatement::CreateRole(_) => vec![],
             DdlStatement::DropRole(_) => vec![],
+            DdlStatement::CreateUser(_) => vec![],
+            DdlStatement::AlterUser(_) => vec![],
+            DdlStatement::DropUser(_) => vec![],
             DdlStatement::CreatePropertyGraph(_) => vec![],
             DdlStatement::DropPropertyGraph(_) => vec![],
             // SQL/MED statements have no inputs
@@ -507,6 +523,37 @@ impl DdlStatement {
                             "DropRole: {name:?} if not exist:={if_exists} cascade:={cascade}"
                         )
                     }
+                    // The passwords are not displayed
+                    DdlStatement::CreateUser(CreateUser {
+                        name,
+                        if_not_exists,
+                        password,
+                        default_role,
+                        attributes,
+                    }) => {
+                        write!(
+                            f,
+                            "CreateUser: {name:?} if not exist:={if_not_exists} password:={} default role:={default_role:?}",
+                            password.is_some()
+                        )?;
+                        write_options(f, attributes)
+                    }
+                    DdlStatement::AlterUser(AlterUser {
+                        name,
+                        password,
+                        default_role,
+                        attributes,
+                    }) => {
+                        write!(
+                            f,
+                            "AlterUser: {name:?} password:={} default role:={default_role:?}",
+                            password.is_some()
+                        )?;
+                        write_option_changes(f, attributes)
+                    }
+                    DdlStatement::DropUser(DropUser { names, if_exists }) => {
+                        write!(f, "DropUser: {names:?} if exists:={if_exists}")
+                    }
                     DdlStatement::CreatePropertyGraph(CreatePropertyGraph {
                         name,
                         or_replace,
@@ -529,14 +576,21 @@ impl DdlStatement {
                         )
                     }
                     // SQL/MED statements - use the Display impl from sqlparser
-                    DdlStatement::CreateServer(stmt) => {
-                        write!(f, "CreateServer: {stmt}")
+                    DdlStatement::CreateServer(CreateServer {
+                        name,
+                        wrapper,
+                        options,
+                        ..
+                    }) => {
+                        write!(f, "CreateServer: {name} wrapper:={wrapper}")?;
+                        write_options(f, options)
                     }
-                    DdlStatement::AlterServer(stmt) => {
-                        write!(f, "AlterServer: {stmt}")
+                    DdlStatement::AlterServer(AlterServer { name, options }) => {
+                        write!(f, "AlterServer: {name}")?;
+                        write_option_changes(f, options)
                     }
-                    DdlStatement::DropServer(stmt) => {
-                        write!(f, "DropServer: {stmt}")
+                    DdlStatement::DropServer(DropServer { name, if_exists }) => {
+                        write!(f, "DropServer: {name} if_exists:={if_exists}")
                     }
                     DdlStatement::CreateForeignDataWrapper(stmt) => {
                         write!(f, "CreateForeignDataWrapper: {stmt}")
@@ -547,23 +601,55 @@ impl DdlStatement {
                     DdlStatement::DropForeignDataWrapper(stmt) => {
                         write!(f, "DropForeignDataWrapper: {stmt}")
                     }
-                    DdlStatement::CreateForeignTable(stmt) => {
-                        write!(f, "CreateForeignTable: {stmt}")
+                    DdlStatement::CreateForeignTable(CreateForeignTable {
+                        name,
+                        server,
+                        options,
+                        ..
+                    }) => {
+                        write!(f, "CreateForeignTable: {name:?} server:={server}")?;
+                        write_options(f, options)
                     }
-                    DdlStatement::AlterForeignTable(stmt) => {
-                        write!(f, "AlterForeignTable: {stmt}")
+                    DdlStatement::AlterForeignTable(AlterForeignTable {
+                        name,
+                        if_exists,
+                        options,
+                    }) => {
+                        write!(f, "AlterForeignTable: {name:?} if_exists:={if_exists}")?;
+                        write_option_changes(f, options)
                     }
-                    DdlStatement::DropForeignTable(stmt) => {
-                        write!(f, "DropForeignTable: {stmt}")
+                    DdlStatement::DropForeignTable(DropForeignTable {
+                        name,
+                        if_exists,
+                    }) => {
+                        write!(f, "DropForeignTable: {name:?} if_exists:={if_exists}")
                     }
-                    DdlStatement::CreateUserMapping(stmt) => {
-                        write!(f, "CreateUserMapping: {stmt}")
+                    DdlStatement::CreateUserMapping(CreateUserMapping {
+                        user,
+                        server,
+                        options,
+                        ..
+                    }) => {
+                        // Option values may be credentials, so only show the keys
+                        write!(f, "CreateUserMapping: {user} server:={server}")?;
+                        if !options.is_empty() {
+                            let keys = options.keys().cloned().collect::<Vec<_>>();
+                            write!(f, " options:=({})", keys.join(", "))?;
+                        }
+                        Ok(())
                     }
                     DdlStatement::AlterUserMapping(stmt) => {
                         write!(f, "AlterUserMapping: {stmt}")
                     }
-                    DdlStatement::DropUserMapping(stmt) => {
-                        write!(f, "DropUserMapping: {stmt}")
+                    DdlStatement::DropUserMapping(DropUserMapping {
+                        user,
+                        server,
+                        if_exists,
+                    }) => {
+                        write!(
+                            f,
+                            "DropUserMapping: {user} server:={server} if_exists:={if_exists}"
+                        )
                     }
                     DdlStatement::ImportForeignSchema(stmt) => {
                         write!(f, "ImportForeignSchema: {stmt}")
@@ -606,6 +692,10 @@ pub struct CreateExternalTable {
     pub constraints: Constraints,
     /// Default values for columns
     pub column_defaults: HashMap<String, Expr>,
+    /// Whether the table was declared without columns. Its schema is then
+    /// inferred from the files at `location` when the table is registered,
+    /// see [`Self::with_inferred_schema`].
+    pub infer_schema: bool,
 }
 
 impl CreateExternalTable {
@@ -650,7 +740,36 @@ impl CreateExternalTable {
             options: HashMap::new(),
             constraints: Default::default(),
             column_defaults: HashMap::new(),
+            infer_schema: false,
+        }
+    }
+
+    /// Returns this table with the schema inferred from the files at
+    /// [`Self::location`]
+    ///
+    /// DDL executors call this when registering a table declared without
+    /// columns, once its file format has inferred the schema. The columns
+    /// the table is declared to be ordered by must be part of it.
+    pub fn with_inferred_schema(mut self, schema: DFSchemaRef) -> Result<Self> {
+        if !self.infer_schema {
+            return internal_err!(
+                "The schema of external table {} is declared, not inferred",
+                self.name
+            );
+        }
+        for sort in self.order_exprs.iter().flatten() {
+            for column in sort.expr.column_refs() {
+                if !schema.has_column(column) {
+                    return plan_err!(
+                        "Column {column} of the order of external table {} is not in the schema inferred from {}",
+                        self.name,
+                        self.location
+                    );
+                }
+            }
         }
+        self.schema = schema;
+        Ok(self)
     }
 }
 
@@ -673,6 +792,7 @@ pub struct CreateExternalTableBuilder {
     options: HashMap<String, String>,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    infer_schema: bool,
 }
 
 impl CreateExternalTableBuilder {
@@ -739,6 +859,12 @@ impl CreateExternalTableBuilder {
         self
     }
 
+    /// Set whether the schema is to be inferred from the files
+    pub fn with_infer_schema(mut self, infer_schema: bool) -> Self {
+        self.infer_schema = infer_schema;
+        self
+    }
+
     /// Build the [`CreateExternalTable`]
     pub fn build(self) -> CreateExternalTable {
         CreateExternalTable {
@@ -756,6 +882,7 @@ impl CreateExternalTableBuilder {
             options: self.options,
             constraints: self.constraints,
             column_defaults: self.column_defaults,
+            infer_schema: self.infer_schema,
         }
     }
 }
@@ -773,6 +900,7 @@ impl Hash for CreateExternalTable {
         self.order_exprs.hash(state);
         self.unbounded.hash(state);
         self.options.len().hash(state); // HashMap is not hashable
+        self.infer_schema.hash(state);
     }
 }
 
@@ -800,6 +928,8 @@ impl PartialOrd for CreateExternalTable {
             pub unbounded: &'a bool,
             /// The list of constraints in the schema, such as primary key, unique, etc.
             pub constraints: &'a Constraints,
+            /// Whether the schema is inferred from the files
+            pub infer_schema: &'a bool,
         }
         let comparable_self = ComparableCreateExternalTable {
             name: &self.name,
@@ -811,6 +941,7 @@ impl PartialOrd for CreateExternalTable {
             order_exprs: &self.order_exprs,
             unbounded: &self.unbounded,
             constraints: &self.constraints,
+            infer_schema: &self.infer_schema,
         };
         let comparable_other = ComparableCreateExternalTable {
             name: &other.name,
@@ -822,6 +953,7 @@ impl PartialOrd for CreateExternalTable {
             order_exprs: &other.order_exprs,
             unbounded: &other.unbounded,
             constraints: &other.constraints,
+            infer_schema: &other.infer_schema,
         };
         comparable_self
             .partial_cmp(&comparable_other)
@@ -1383,6 +1515,81 @@ pub struct DropRole {
     pub cascade: bool,
 }
 
+/// CREATE USER statement.
+///
+/// Example:
+/// ```sql
+/// CREATE USER alice WITH PASSWORD 'secret' DEFAULT ROLE analyst OPTIONS (team 'sales')
+/// ```
+///
+/// The [`Debug`] output omits the password.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct CreateUser {
+    /// The user name.
+    pub name: String,
+    /// IF NOT EXISTS clause.
+    pub if_not_exists: bool,
+    /// PASSWORD option.
+    pub password: Option<String>,
+    /// DEFAULT ROLE option, the role the sessions of the user start with.
+    pub default_role: Option<String>,
+    /// OPTIONS clause, the attributes of the user.
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl Debug for CreateUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateUser")
+            .field("name", &self.name)
+            .field("if_not_exists", &self.if_not_exists)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("default_role", &self.default_role)
+            .field("attributes", &self.attributes)
+            .finish()
+    }
+}
+
+/// ALTER USER statement. The password and default role are left unchanged
+/// when not given.
+///
+/// Example:
+/// ```sql
+/// ALTER USER alice WITH PASSWORD 'new secret' OPTIONS (SET team 'support')
+/// ```
+///
+/// The [`Debug`] output omits the password.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct AlterUser {
+    /// The user name.
+    pub name: String,
+    /// PASSWORD option.
+    pub password: Option<String>,
+    /// DEFAULT ROLE option.
+    pub default_role: Option<String>,
+    /// The changes to the attributes of the user, applied in order.
+    pub attributes: Vec<OptionChange>,
+}
+
+impl Debug for AlterUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlterUser")
+            .field("name", &self.name)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("default_role", &self.default_role)
+            .field("attributes", &self.attributes)
+            .finish()
+    }
+}
+
+/// DROP USER statement.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct DropUser {
+    /// The user names.
+    pub names: Vec<String>,
+    /// IF EXISTS clause.
+    pub if_exists: bool,
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct CreateIndex {
     pub name: Option<String>,
@@ -1559,6 +1766,241 @@ pub struct DropPropertyGraph {
     pub drop_behavior: Option<DropBehavior>,
 }
 
+/// Writes SQL/MED `OPTIONS` as ` options:=(key 'value', ...)`, if any
+fn write_options(
+    f: &mut fmt::Formatter,
+    options: &BTreeMap<String, String>,
+) -> fmt::Result {
+    if options.is_empty() {
+        return Ok(());
+    }
+    let options = options
+        .iter()
+        .map(|(key, value)| format!("{key} '{value}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    write!(f, " options:=({options})")
+}
+
+/// CREATE SERVER statement (SQL/MED).
+///
+/// Example:
+/// ```sql
+/// CREATE SERVER myserver FOREIGN DATA WRAPPER postgres_fdw
+///   OPTIONS (host 'localhost', port '5432')
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct CreateServer {
+    /// The server name
+    pub name: String,
+    /// The foreign data wrapper used to reach the server
+    pub wrapper: String,
+    /// Wrapper specific server options, such as connection parameters
+    pub options: BTreeMap<String, String>,
+    /// Option to not error if the server already exists
+    pub if_not_exists: bool,
+}
+
+/// A change to the SQL/MED `OPTIONS` of an object, as in
+/// `OPTIONS (ADD key 'value', SET key 'value', DROP key)`
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub enum OptionChange {
+    /// Adds an option that is not set yet
+    Add { key: String, value: String },
+    /// Changes the value of an option that is already set
+    Set { key: String, value: String },
+    /// Removes an option
+    Drop { key: String },
+}
+
+impl Display for OptionChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionChange::Add { key, value } => write!(f, "ADD {key} '{value}'"),
+            OptionChange::Set { key, value } => write!(f, "SET {key} '{value}'"),
+            OptionChange::Drop { key } => write!(f, "DROP {key}"),
+        }
+    }
+}
+
+/// Writes SQL/MED `OPTIONS` changes as ` options:=(SET key 'value', ...)`,
+/// if any
+fn write_option_changes(f: &mut fmt::Formatter, changes: &[OptionChange]) -> fmt::Result {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let changes = changes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    write!(f, " options:=({changes})")
+}
+
+/// ALTER SERVER statement (SQL/MED).
+///
+/// Example:
+/// ```sql
+/// ALTER SERVER myserver OPTIONS (SET host 'newhost', DROP port)
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct AlterServer {
+    /// The server name
+    pub name: String,
+    /// The changes to the server options, applied in order
+    pub options: Vec<OptionChange>,
+}
+
+/// DROP SERVER statement (SQL/MED).
+///
+/// Example:
+/// ```sql
+/// DROP SERVER IF EXISTS myserver
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct DropServer {
+    /// The server name
+    pub name: String,
+    /// Option to not error if the server does not exist
+    pub if_exists: bool,
+}
+
+/// ALTER FOREIGN TABLE statement (SQL/MED).
+///
+/// Example:
+/// ```sql
+/// ALTER FOREIGN TABLE remote_users OPTIONS (SET table_name 'new_users')
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct AlterForeignTable {
+    /// The table name
+    pub name: TableReference,
+    /// Option to not error if the table does not exist
+    pub if_exists: bool,
+    /// The changes to the table options, applied in order
+    pub options: Vec<OptionChange>,
+}
+
+/// DROP FOREIGN TABLE statement (SQL/MED).
+///
+/// Example:
+/// ```sql
+/// DROP FOREIGN TABLE IF EXISTS remote_users
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct DropForeignTable {
+    /// The table name
+    pub name: TableReference,
+    /// Option to not error if the table does not exist
+    pub if_exists: bool,
+}
+
+/// The user a SQL/MED user mapping applies to
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub enum UserMappingTarget {
+    /// `CURRENT_USER`, `CURRENT_ROLE` or `USER`: the user executing the
+    /// statement
+    CurrentUser,
+    /// `PUBLIC`: every user without a mapping of its own
+    Public,
+    /// A named user
+    User(String),
+}
+
+impl Display for UserMappingTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserMappingTarget::CurrentUser => write!(f, "CURRENT_USER"),
+            UserMappingTarget::Public => write!(f, "PUBLIC"),
+            UserMappingTarget::User(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// CREATE USER MAPPING statement (SQL/MED).
+///
+/// Options whose name ends in `_secret` name a secret that is resolved by
+/// the [`CredentialProvider`] when a connection is opened, so that no
+/// literal password needs to be stored with the mapping.
+///
+/// Example:
+/// ```sql
+/// CREATE USER MAPPING FOR current_user SERVER myserver
+///   OPTIONS (user 'remote_user', password_secret 'myserver/remote_user')
+/// ```
+///
+/// [`CredentialProvider`]: crate::foreign_data_wrapper::CredentialProvider
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct CreateUserMapping {
+    /// The user the mapping applies to
+    pub user: UserMappingTarget,
+    /// The foreign server the mapping applies to
+    pub server: String,
+    /// Wrapper specific connection options, such as the remote user
+    pub options: BTreeMap<String, String>,
+    /// Option to not error if the mapping already exists
+    pub if_not_exists: bool,
+}
+
+/// DROP USER MAPPING statement (SQL/MED).
+///
+/// Example:
+/// ```sql
+/// DROP USER MAPPING IF EXISTS FOR current_user SERVER myserver
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct DropUserMapping {
+    /// The user the mapping applies to
+    pub user: UserMappingTarget,
+    /// The foreign server the mapping applies to
+    pub server: String,
+    /// Option to not error if the mapping does not exist
+    pub if_exists: bool,
+}
+
+/// CREATE FOREIGN TABLE statement (SQL/MED).
+///
+/// The table is not materialized by DataFusion: executing the statement
+/// asks the [`ForeignDataWrapper`] of `server` for a [`TableSource`] built
+/// from the server and table `options`.
+///
+/// Example:
+/// ```sql
+/// CREATE FOREIGN TABLE remote_users (id INT, name VARCHAR(100))
+///   SERVER myserver OPTIONS (table_name 'users')
+/// ```
+///
+/// [`ForeignDataWrapper`]: crate::foreign_data_wrapper::ForeignDataWrapper
+/// [`TableSource`]: crate::TableSource
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CreateForeignTable {
+    /// The table name
+    pub name: TableReference,
+    /// The declared table schema
+    pub schema: DFSchemaRef,
+    /// The foreign server the table lives on
+    pub server: String,
+    /// Wrapper specific table options
+    pub options: BTreeMap<String, String>,
+    /// Option to not error if table already exists
+    pub if_not_exists: bool,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for CreateForeignTable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (&self.name, &self.server, &self.options, self.if_not_exists)
+            .partial_cmp(&(
+                &other.name,
+                &other.server,
+                &other.options,
+                other.if_not_exists,
+            ))
+            // TODO (https://github.com/apache/datafusion/issues/17477) avoid recomparing all fields
+            .filter(|cmp| *cmp != Ordering::Equal || self == other)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{CreateCatalog, DdlStatement, DropView};
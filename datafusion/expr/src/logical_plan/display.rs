@@ -21,10 +21,10 @@ use std::collections::HashMap;
 use std::fmt;
 
 use crate::{
-    Aggregate, DescribeTable, Distinct, DistinctOn, DmlStatement, Expr, Filter, Join,
-    Limit, LogicalPlan, Partitioning, Projection, RecursiveQuery, Repartition, Sort,
-    Subquery, SubqueryAlias, TableProviderFilterPushDown, TableScan, Unnest, Values,
-    Window, expr_vec_fmt,
+    Aggregate, CteReference, DescribeTable, Distinct, DistinctOn, DmlStatement, Expr,
+    Filter, Join, Limit, LogicalPlan, Partitioning, Projection, RecursiveQuery,
+    Repartition, Sample, Sort, Subquery, SubqueryAlias, TableProviderFilterPushDown,
+    TableScan, Unnest, Values, Window, expr_vec_fmt,
 };
 
 use crate::dml::{CopyFrom, CopyTo};
@@ -424,10 +424,12 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
             }
             LogicalPlan::CopyFrom(CopyFrom {
                 table_name,
-                source_url,
+                source,
                 columns,
                 file_type,
                 options,
+                column_exprs,
+                error_handling,
                 output_schema: _,
             }) => {
                 let op_str = options
@@ -440,11 +442,18 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                 } else {
                     columns.join(", ")
                 };
+                let exprs_str = column_exprs
+                    .iter()
+                    .map(|(name, expr)| format!("{name}={expr}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
                 json!({
                     "Node Type": "CopyFrom",
                     "Table": table_name.to_string(),
-                    "Source URL": source_url,
+                    "Source": source.to_string(),
                     "Columns": cols_str,
+                    "Column Expressions": exprs_str,
+                    "Error Handling": error_handling.to_string(),
                     "File Type": format!("{}", file_type.get_ext()),
                     "Options": op_str
                 })
@@ -455,6 +464,7 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                 file_type,
                 partition_by: _,
                 options,
+                partition_layout,
                 output_schema: _,
             }) => {
                 let op_str = options
@@ -465,6 +475,7 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                 json!({
                     "Node Type": "CopyTo",
                     "Output URL": output_url,
+                    "Partition Layout": partition_layout.to_string(),
                     "File Type": format!("{}", file_type.get_ext()),
                     "Options": op_str
                 })
@@ -578,6 +589,30 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                 };
                 object
             }
+            LogicalPlan::Sample(Sample {
+                method, size, seed, ..
+            }) => {
+                let mut object = json!({
+                    "Node Type": "Sample",
+                    "Method": method.to_string(),
+                    "Size": size.to_string(),
+                });
+                if let Some(seed) = seed {
+                    object["Seed"] = (*seed).into()
+                };
+                object
+            }
+            LogicalPlan::CteReference(CteReference {
+                name,
+                materialization,
+                ..
+            }) => {
+                json!({
+                    "Node Type": "CteReference",
+                    "CTE Name": name,
+                    "Materialization": materialization.to_string(),
+                })
+            }
             LogicalPlan::Subquery(Subquery { .. }) => {
                 json!({
                     "Node Type": "Subquery"
@@ -683,11 +718,24 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                     "Node Type": "JsonTable"
                 })
             }
+            LogicalPlan::XmlTable(xml_table) => {
+                json!({
+                    "Node Type": "XmlTable",
+                    "Row Expression": xml_table.row_expression
+                })
+            }
             LogicalPlan::GraphTable(_) => {
                 json!({
                     "Node Type": "GraphTable"
                 })
             }
+            LogicalPlan::PolymorphicTableFunction(ptf) => {
+                json!({
+                    "Node Type": "PolymorphicTableFunction",
+                    "Function": ptf.func.name(),
+                    "Call": ptf.to_string(),
+                })
+            }
         }
     }
 }
@@ -23,7 +23,10 @@ use std::sync::Arc;
 
 use arrow::datatypes::{DataType, Field, Schema};
 use datafusion_common::file_options::file_type::FileType;
-use datafusion_common::{DFSchemaRef, TableReference};
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{
+    Column, DFSchema, DFSchemaRef, Result, ScalarValue, TableReference, plan_err,
+};
 use sqlparser::ast::AssignmentTarget;
 
 /// Target specification for ON CONFLICT clauses.
@@ -37,7 +40,54 @@ pub enum ConflictTarget {
     OnConstraint(String),
 }
 
-use crate::{Expr, LogicalPlan, TableSource};
+use crate::{Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder, TableSource};
+
+/// How the partition directories written by a [`CopyTo`] are named
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Default)]
+pub enum PartitionNaming {
+    /// `<column>=<value>`, as read back by Hive-style partitioned tables
+    #[default]
+    Hive,
+    /// `<value>` alone
+    Values,
+}
+
+impl Display for PartitionNaming {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PartitionNaming::Hive => write!(f, "hive"),
+            PartitionNaming::Values => write!(f, "values"),
+        }
+    }
+}
+
+/// Layout of the files written by a [`CopyTo`]
+///
+/// Set with the `partition.naming`, `partition.max_file_size` and
+/// `partition.single_file` options of `COPY TO`, and implemented by the
+/// sinks writing its output.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash, Default)]
+pub struct PartitionLayout {
+    /// How the directory of each partition is named
+    pub naming: PartitionNaming,
+    /// Start a new file once the current one reaches this many bytes
+    pub max_file_size: Option<u64>,
+    /// Write each partition to a single file
+    pub single_file: bool,
+}
+
+impl Display for PartitionLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "naming={}", self.naming)?;
+        if let Some(max_file_size) = self.max_file_size {
+            write!(f, ", max_file_size={max_file_size}")?;
+        }
+        if self.single_file {
+            write!(f, ", single_file=true")?;
+        }
+        Ok(())
+    }
+}
 
 /// Operator that copies the contents of a database to file(s)
 #[derive(Clone)]
@@ -52,6 +102,8 @@ pub struct CopyTo {
     pub file_type: Arc<dyn FileType>,
     /// SQL Options that can affect the formats
     pub options: HashMap<String, String>,
+    /// Layout of the written files
+    pub partition_layout: PartitionLayout,
     /// The schema of the output (a single column "count")
     pub output_schema: DFSchemaRef,
 }
@@ -64,6 +116,7 @@ impl Debug for CopyTo {
             .field("partition_by", &self.partition_by)
             .field("file_type", &"...")
             .field("options", &self.options)
+            .field("partition_layout", &self.partition_layout)
             .field("output_schema", &self.output_schema)
             .finish_non_exhaustive()
     }
@@ -120,10 +173,17 @@ impl CopyTo {
             partition_by,
             file_type,
             options,
+            partition_layout: PartitionLayout::default(),
             // The output schema is always a single column "count" with the number of rows copied
             output_schema: make_count_schema(),
         }
     }
+
+    /// Sets the layout of the written files
+    pub fn with_partition_layout(mut self, partition_layout: PartitionLayout) -> Self {
+        self.partition_layout = partition_layout;
+        self
+    }
 }
 
 /// Modifies the content of a database
@@ -502,20 +562,100 @@ pub struct ConflictAssignment {
     pub value: Expr,
 }
 
+/// Where a [`CopyFrom`] reads its rows from
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum CopyFromSource {
+    /// A file or object store URL
+    File(String),
+    /// A stream supplied by the caller, e.g. the data following a psql
+    /// `\copy`. Engines read it from the stream registered on the session
+    /// that runs the statement.
+    Stdin,
+    /// The standard output of a shell command. Running it is left to the
+    /// engine, which should only allow it for trusted users.
+    Program(String),
+}
+
+impl Display for CopyFromSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyFromSource::File(url) => write!(f, "{url}"),
+            CopyFromSource::Stdin => write!(f, "STDIN"),
+            CopyFromSource::Program(command) => write!(f, "PROGRAM '{command}'"),
+        }
+    }
+}
+
+/// What a [`CopyFrom`] does with rows that cannot be read or converted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Default)]
+pub enum CopyOnError {
+    /// Fail the copy at the first malformed row
+    #[default]
+    Stop,
+    /// Skip malformed rows and load the others
+    Skip,
+}
+
+impl Display for CopyOnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyOnError::Stop => write!(f, "stop"),
+            CopyOnError::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+/// How a [`CopyFrom`] tolerates malformed rows
+///
+/// Set with the `errors.on_error`, `errors.max_errors` and
+/// `errors.reject_location` options of `COPY FROM`, and implemented by the
+/// engine reading the source.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash, Default)]
+pub struct CopyErrorHandling {
+    /// Whether malformed rows fail the copy or are skipped
+    pub on_error: CopyOnError,
+    /// Fail the copy once more than this many rows were skipped
+    pub max_errors: Option<u64>,
+    /// Where the skipped rows are written, along with why they were rejected
+    pub reject_location: Option<String>,
+}
+
+impl Display for CopyErrorHandling {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "on_error={}", self.on_error)?;
+        if let Some(max_errors) = self.max_errors {
+            write!(f, ", max_errors={max_errors}")?;
+        }
+        if let Some(reject_location) = &self.reject_location {
+            write!(f, ", reject_location={reject_location}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Operator that copies the contents of a file to a database table
 #[derive(Clone)]
 pub struct CopyFrom {
     /// The table name to insert into
     pub table_name: TableReference,
-    /// The source URL to read from
-    pub source_url: String,
-    /// Determines which columns to load from the file
+    /// The file, stream or program to read from
+    pub source: CopyFromSource,
+    /// Determines which columns to load from the file. Empty means all
+    /// columns of the table, in table order.
     pub columns: Vec<String>,
     /// File type trait
     pub file_type: Arc<dyn FileType>,
     /// SQL Options that can affect the formats
     pub options: HashMap<String, String>,
-    /// The schema of the output (a single column "count")
+    /// Expressions computing the values of some of the copied columns, in
+    /// place of copying them as is. They see every copied column as the text
+    /// of its value, e.g. `to_date(born, '%d/%m/%Y')`.
+    pub column_exprs: Vec<(String, Expr)>,
+    /// How malformed rows are handled
+    pub error_handling: CopyErrorHandling,
+    /// The schema of the output: the number of rows copied ("count") and,
+    /// when malformed rows are skipped, the number of rows skipped
+    /// ("rejected")
     pub output_schema: DFSchemaRef,
 }
 
@@ -523,10 +663,12 @@ impl Debug for CopyFrom {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("CopyFrom")
             .field("table_name", &self.table_name)
-            .field("source_url", &self.source_url)
+            .field("source", &self.source)
             .field("columns", &self.columns)
             .field("file_type", &"...")
             .field("options", &self.options)
+            .field("column_exprs", &self.column_exprs)
+            .field("error_handling", &self.error_handling)
             .field("output_schema", &self.output_schema)
             .finish_non_exhaustive()
     }
@@ -536,8 +678,9 @@ impl Debug for CopyFrom {
 impl PartialEq for CopyFrom {
     fn eq(&self, other: &Self) -> bool {
         self.table_name == other.table_name
-            && self.source_url == other.source_url
+            && self.source == other.source
             && self.columns == other.columns
+            && self.column_exprs == other.column_exprs
     }
 }
 
@@ -549,8 +692,13 @@ impl Eq for CopyFrom {}
 impl PartialOrd for CopyFrom {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self.table_name.partial_cmp(&other.table_name) {
-            Some(Ordering::Equal) => match self.source_url.partial_cmp(&other.source_url) {
-                Some(Ordering::Equal) => self.columns.partial_cmp(&other.columns),
+            Some(Ordering::Equal) => match self.source.partial_cmp(&other.source) {
+                Some(Ordering::Equal) => match self.columns.partial_cmp(&other.columns) {
+                    Some(Ordering::Equal) => {
+                        self.column_exprs.partial_cmp(&other.column_exprs)
+                    }
+                    cmp => cmp,
+                },
                 cmp => cmp,
             },
             cmp => cmp,
@@ -564,29 +712,160 @@ impl PartialOrd for CopyFrom {
 impl Hash for CopyFrom {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.table_name.hash(state);
-        self.source_url.hash(state);
+        self.source.hash(state);
         self.columns.hash(state);
+        self.column_exprs.hash(state);
     }
 }
 
 impl CopyFrom {
     pub fn new(
         table_name: TableReference,
-        source_url: String,
+        source: CopyFromSource,
         columns: Vec<String>,
         file_type: Arc<dyn FileType>,
         options: HashMap<String, String>,
     ) -> Self {
         Self {
             table_name,
-            source_url,
+            source,
             columns,
             file_type,
             options,
-            // The output schema is always a single column "count" with the number of rows copied
+            column_exprs: vec![],
+            error_handling: CopyErrorHandling::default(),
+            // The output schema is a single column "count" with the number of rows copied,
+            // until malformed rows are skipped
             output_schema: make_count_schema(),
         }
     }
+
+    /// Computes the values of the given copied columns with expressions
+    pub fn with_column_exprs(mut self, column_exprs: Vec<(String, Expr)>) -> Self {
+        self.column_exprs = column_exprs;
+        self
+    }
+
+    /// Sets how malformed rows are handled. When they are skipped, the
+    /// output also reports the number of rows skipped.
+    pub fn with_error_handling(mut self, error_handling: CopyErrorHandling) -> Self {
+        self.output_schema = Self::make_output_schema(error_handling.on_error);
+        self.error_handling = error_handling;
+        self
+    }
+
+    /// The output schema of a `COPY FROM` handling malformed rows with
+    /// `on_error`
+    pub(crate) fn make_output_schema(on_error: CopyOnError) -> DFSchemaRef {
+        match on_error {
+            CopyOnError::Stop => make_count_schema(),
+            CopyOnError::Skip => Arc::new(
+                Schema::new(vec![
+                    Field::new("count", DataType::UInt64, false),
+                    Field::new("rejected", DataType::UInt64, false),
+                ])
+                .try_into()
+                .unwrap(),
+            ),
+        }
+    }
+
+    /// Builds the `INSERT` appending the rows of the source to the table, so
+    /// that engines execute `COPY FROM` through their insert path.
+    ///
+    /// `target` is the table being copied into, and `source` reads
+    /// [`Self::source`] with [`Self::file_type`] and [`Self::options`].
+    /// The columns of `source` are matched by position with
+    /// [`Self::columns`], and transformed by [`Self::column_exprs`]. Columns
+    /// of `target` that are not copied get their default value, or NULL.
+    pub fn insert_plan(
+        &self,
+        target: Arc<dyn TableSource>,
+        source: Arc<dyn TableSource>,
+    ) -> Result<LogicalPlan> {
+        let table_schema = DFSchema::try_from(target.schema())?;
+        let columns = if self.columns.is_empty() {
+            table_schema
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect()
+        } else {
+            self.columns.clone()
+        };
+
+        let scan = LogicalPlanBuilder::scan(
+            TableReference::bare(self.source.to_string()),
+            source,
+            None,
+        )?
+        .build()?;
+        let source_schema = Arc::clone(scan.schema());
+        if source_schema.fields().len() != columns.len() {
+            return plan_err!(
+                "COPY FROM source {} has {} columns, but {} columns of {} are copied",
+                self.source,
+                source_schema.fields().len(),
+                columns.len(),
+                self.table_name
+            );
+        }
+
+        let exprs = table_schema
+            .fields()
+            .iter()
+            .map(|target_field| {
+                let column_expr = self
+                    .column_exprs
+                    .iter()
+                    .find(|(name, _)| name == target_field.name());
+                let position = columns.iter().position(|c| c == target_field.name());
+                let expr = match (column_expr, position) {
+                    // Replace the copied columns with the text of their source column
+                    (Some((_, expr)), Some(_)) => expr
+                        .clone()
+                        .transform(|expr| match &expr {
+                            Expr::Column(c) if c.relation.is_none() => {
+                                match columns.iter().position(|name| *name == c.name) {
+                                    Some(i) => Ok(Transformed::yes(
+                                        Expr::Column(Column::from(
+                                            source_schema.qualified_field(i),
+                                        ))
+                                        .cast_to(&DataType::Utf8, &source_schema)?,
+                                    )),
+                                    None => Ok(Transformed::no(expr)),
+                                }
+                            }
+                            _ => Ok(Transformed::no(expr)),
+                        })?
+                        .data
+                        .cast_to(target_field.data_type(), &source_schema)?,
+                    (None, Some(i)) => {
+                        Expr::Column(Column::from(source_schema.qualified_field(i)))
+                            .cast_to(target_field.data_type(), &source_schema)?
+                    }
+                    // The column is not copied. Fill in its default value, or NULL.
+                    (_, None) => target
+                        .get_column_default(target_field.name())
+                        .cloned()
+                        .unwrap_or_else(|| Expr::Literal(ScalarValue::Null, None))
+                        .cast_to(target_field.data_type(), &DFSchema::empty())?,
+                };
+                Ok(expr.alias(target_field.name()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let input = LogicalPlanBuilder::from(scan).project(exprs)?.build()?;
+
+        Ok(LogicalPlan::Dml(
+            DmlStatement::new(
+                self.table_name.clone(),
+                target,
+                WriteOp::Insert(InsertOp::Append),
+                Arc::new(input),
+            )
+            .with_target_columns(self.columns.clone()),
+        ))
+    }
 }
 
 pub(crate) fn make_count_schema() -> DFSchemaRef {
@@ -15,19 +15,24 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
+
+use arrow::datatypes::FieldRef;
 use datafusion_common::{
-    DFSchemaRef, Result, assert_or_internal_err, plan_err,
+    DFSchema, DFSchemaRef, Result, assert_or_internal_err, plan_err,
     tree_node::{TreeNode, TreeNodeRecursion},
 };
 
 use crate::{
-    Aggregate, Expr, Filter, Join, JoinType, LogicalPlan, Window,
+    Aggregate, CopyFrom, Expr, Filter, Join, JoinType, JsonTable, LogicalPlan,
+    MatchRecognize, Window,
     expr::{Exists, InSubquery},
     expr_rewriter::strip_outer_reference,
     utils::{collect_subquery_cols, split_conjunction},
 };
 
 use super::Extension;
+use super::dml::make_count_schema;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
 pub enum InvariantLevel {
@@ -49,6 +54,7 @@ pub enum InvariantLevel {
 pub fn assert_always_invariants_at_current_node(plan: &LogicalPlan) -> Result<()> {
     // Refer to <https://datafusion.apache.org/contributor-guide/specification/invariants.html#relation-name-tuples-in-logical-fields-and-logical-columns-are-unique>
     assert_unique_field_names(plan)?;
+    assert_schema_matches_definition(plan)?;
 
     Ok(())
 }
@@ -62,6 +68,7 @@ pub fn assert_executable_invariants(plan: &LogicalPlan) -> Result<()> {
 
     // Executable invariants
     assert_valid_extension_nodes(plan, InvariantLevel::Executable)?;
+    assert_consistent_nodes(plan)?;
     assert_valid_semantic_plan(plan)?;
     Ok(())
 }
@@ -101,6 +108,149 @@ fn assert_unique_field_names(plan: &LogicalPlan) -> Result<()> {
     plan.schema().check_names()
 }
 
+/// Returns an error if the plan node has a schema that differs from the one
+/// derived from its definition, for the nodes that derive it. Ignores
+/// qualifiers, metadata and nullability.
+fn assert_schema_matches_definition(plan: &LogicalPlan) -> Result<()> {
+    match plan {
+        LogicalPlan::MatchRecognize(match_recognize) => {
+            let fields = MatchRecognize::output_fields(
+                &match_recognize.input,
+                &match_recognize.measures,
+            )?;
+            assert_schema_fields(plan, &fields)
+        }
+        LogicalPlan::JsonTable(json_table) => {
+            let fields = JsonTable::columns_to_fields(&json_table.columns)?;
+            assert_schema_fields(plan, &fields)
+        }
+        LogicalPlan::GraphTable(graph_table) => {
+            // The types of the columns depend on the property graph, only
+            // their number and aliases are known
+            let fields = plan.schema().fields();
+            assert_or_internal_err!(
+                fields.len() == graph_table.columns.len(),
+                "GraphTable has {} columns but its schema has {} fields",
+                graph_table.columns.len(),
+                fields.len()
+            );
+            for (column, field) in graph_table.columns.iter().zip(fields.iter()) {
+                if let Some(alias) = &column.alias {
+                    assert_or_internal_err!(
+                        field.name() == alias,
+                        "GraphTable column {alias} is named {} in its schema",
+                        field.name()
+                    );
+                }
+            }
+            Ok(())
+        }
+        LogicalPlan::Merge(_) => assert_schema_fields(plan, make_count_schema().fields()),
+        LogicalPlan::CopyFrom(copy) => assert_schema_fields(
+            plan,
+            CopyFrom::make_output_schema(copy.error_handling.on_error).fields(),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Returns an error if the fields of the schema of `plan` do not have the
+/// names and types of `expected`
+fn assert_schema_fields(plan: &LogicalPlan, expected: &[FieldRef]) -> Result<()> {
+    let fields = plan.schema().fields();
+    let matches = fields.len() == expected.len()
+        && fields.iter().zip(expected).all(|(field, expected)| {
+            field.name() == expected.name()
+                && DFSchema::datatype_is_logically_equal(
+                    field.data_type(),
+                    expected.data_type(),
+                )
+        });
+    assert_or_internal_err!(
+        matches,
+        "Schema of {} differs from its definition: schema: {:?}, expected fields: {:?}",
+        plan.display(),
+        fields,
+        expected
+    );
+    Ok(())
+}
+
+/// Returns an error if the plan nodes, or the nodes of their subqueries, do
+/// not have schemas matching their definition, or have expressions that
+/// refer to columns the node does not provide to them.
+fn assert_consistent_nodes(plan: &LogicalPlan) -> Result<()> {
+    plan.apply_with_subqueries(|plan: &LogicalPlan| {
+        assert_schema_matches_definition(plan)?;
+        assert_expressions_resolve(plan)?;
+        Ok(TreeNodeRecursion::Continue)
+    })
+    .map(|_| ())
+}
+
+/// Returns an error if the expressions of the plan node refer to columns
+/// that are not in the schemas they are evaluated against.
+///
+/// Columns of outer queries are outer references, and not checked.
+fn assert_expressions_resolve(plan: &LogicalPlan) -> Result<()> {
+    match plan {
+        LogicalPlan::MatchRecognize(match_recognize) => {
+            // The columns qualified by pattern variables are columns of the input
+            assert_columns_in_schemas(plan, &[match_recognize.input.schema()])
+        }
+        LogicalPlan::Merge(merge) => assert_columns_in_schemas(
+            plan,
+            &[merge.target.schema(), merge.source.schema()],
+        ),
+        // JSON_TABLE has no input, its JSON expression may only refer to the
+        // columns of the preceding FROM items as outer references
+        LogicalPlan::JsonTable(_) => assert_columns_in_schemas(plan, &[]),
+        LogicalPlan::CopyFrom(copy) => {
+            let mut computed = HashSet::new();
+            for (name, expr) in &copy.column_exprs {
+                assert_or_internal_err!(
+                    computed.insert(name),
+                    "CopyFrom computes column {name} more than once"
+                );
+                // Without a column list, every column of the table is copied
+                if copy.columns.is_empty() {
+                    continue;
+                }
+                assert_or_internal_err!(
+                    copy.columns.contains(name),
+                    "CopyFrom computes column {name}, which is not copied"
+                );
+                for column in expr.column_refs() {
+                    assert_or_internal_err!(
+                        column.relation.is_none() && copy.columns.contains(&column.name),
+                        "CopyFrom computes column {name} from {column}, which is not copied"
+                    );
+                }
+            }
+            Ok(())
+        }
+        // The expressions of GRAPH_TABLE refer to the element variables of its
+        // graph patterns
+        _ => Ok(()),
+    }
+}
+
+/// Returns an error if an expression of `plan` refers to a column that is in
+/// none of `schemas`
+fn assert_columns_in_schemas(plan: &LogicalPlan, schemas: &[&DFSchemaRef]) -> Result<()> {
+    plan.apply_expressions(|expr| {
+        for column in expr.column_refs() {
+            assert_or_internal_err!(
+                schemas.iter().any(|schema| schema.has_column(column)),
+                "Column {column} of {} is not in the schema of its input",
+                plan.display()
+            );
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })
+    .map(|_| ())
+}
+
 /// Returns an error if the plan is not semantically valid.
 fn assert_valid_semantic_plan(plan: &LogicalPlan) -> Result<()> {
     assert_subqueries_are_valid(plan)?;
@@ -169,33 +319,7 @@ pub fn check_subquery_expr(
         }
         // Correlated scalar subquery must be aggregated to return at most one row
         if !subquery.outer_ref_columns.is_empty() {
-            match strip_inner_query(inner_plan) {
-                LogicalPlan::Aggregate(agg) => {
-                    check_aggregation_in_scalar_subquery(inner_plan, agg)
-                }
-                LogicalPlan::Filter(Filter { input, .. })
-                    if matches!(input.as_ref(), LogicalPlan::Aggregate(_)) =>
-                {
-                    if let LogicalPlan::Aggregate(agg) = input.as_ref() {
-                        check_aggregation_in_scalar_subquery(inner_plan, agg)
-                    } else {
-                        Ok(())
-                    }
-                }
-                _ => {
-                    if inner_plan
-                        .max_rows()
-                        .filter(|max_row| *max_row <= 1)
-                        .is_some()
-                    {
-                        Ok(())
-                    } else {
-                        plan_err!(
-                            "Correlated scalar subquery must be aggregated to return at most one row"
-                        )
-                    }
-                }
-            }?;
+            check_subquery_returns_one_row(inner_plan)?;
             match outer_plan {
                 LogicalPlan::Projection(_) | LogicalPlan::Filter(_) => Ok(()),
                 LogicalPlan::Aggregate(Aggregate {
@@ -247,6 +371,39 @@ pub fn check_subquery_expr(
     }
 }
 
+/// Check that the subquery `inner_plan` returns at most one row, because it
+/// is aggregated without grouping by other columns than its correlated ones,
+/// or because its plan bounds its number of rows
+fn check_subquery_returns_one_row(inner_plan: &LogicalPlan) -> Result<()> {
+    match strip_inner_query(inner_plan) {
+        LogicalPlan::Aggregate(agg) => {
+            check_aggregation_in_scalar_subquery(inner_plan, agg)
+        }
+        LogicalPlan::Filter(Filter { input, .. })
+            if matches!(input.as_ref(), LogicalPlan::Aggregate(_)) =>
+        {
+            if let LogicalPlan::Aggregate(agg) = input.as_ref() {
+                check_aggregation_in_scalar_subquery(inner_plan, agg)
+            } else {
+                Ok(())
+            }
+        }
+        _ => {
+            if inner_plan
+                .max_rows()
+                .filter(|max_row| *max_row <= 1)
+                .is_some()
+            {
+                Ok(())
+            } else {
+                plan_err!(
+                    "Correlated scalar subquery must be aggregated to return at most one row"
+                )
+            }
+        }
+    }
+}
+
 // Recursively check the unsupported outer references in the sub query plan.
 fn check_correlations_in_subquery(inner_plan: &LogicalPlan) -> Result<()> {
     check_inner_plan(inner_plan)
@@ -280,6 +437,8 @@ fn check_inner_plan(inner_plan: &LogicalPlan) -> Result<()> {
         | LogicalPlan::TableScan(_)
         | LogicalPlan::EmptyRelation(_)
         | LogicalPlan::Limit(_)
+        | LogicalPlan::Sample(_)
+        | LogicalPlan::CteReference(_)
         | LogicalPlan::Values(_)
         | LogicalPlan::Subquery(_)
         | LogicalPlan::SubqueryAlias(_)
@@ -419,7 +578,10 @@ mod test {
     use std::cmp::Ordering;
     use std::sync::Arc;
 
-    use crate::{Extension, UserDefinedLogicalNodeCore};
+    use crate::{
+        Extension, LogicalPlanBuilder, MeasureExpr, Pattern, PatternSymbol,
+        UserDefinedLogicalNodeCore, col, lit,
+    };
     use datafusion_common::{DFSchema, DFSchemaRef};
 
     use super::*;
@@ -481,4 +643,57 @@ mod test {
 
         check_inner_plan(&plan).unwrap();
     }
+
+    fn match_recognize(partition_by: Vec<Expr>) -> Result<MatchRecognize> {
+        let input = LogicalPlanBuilder::values(vec![vec![lit(1), lit("a")]])?.build()?;
+        MatchRecognize::try_new(
+            Arc::new(input),
+            partition_by,
+            vec![],
+            vec![MeasureExpr {
+                expr: col("column1"),
+                alias: "m".to_string(),
+            }],
+            None,
+            None,
+            Pattern::Symbol(PatternSymbol::Named("A".to_string())),
+            vec![],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn match_recognize_schema_differs_from_definition() -> Result<()> {
+        let plan = LogicalPlan::MatchRecognize(match_recognize(vec![col("column2")])?);
+        plan.check_invariants(InvariantLevel::Always)?;
+        plan.check_invariants(InvariantLevel::Executable)?;
+
+        // Drop the measure column from the schema
+        let LogicalPlan::MatchRecognize(mut node) = plan else {
+            unreachable!()
+        };
+        node.schema = Arc::clone(node.input.schema());
+        let plan = LogicalPlan::MatchRecognize(node);
+        let err = plan.check_invariants(InvariantLevel::Always).unwrap_err();
+        assert!(err.to_string().contains("differs from its definition"));
+        Ok(())
+    }
+
+    #[test]
+    fn match_recognize_expressions_resolve_against_input() -> Result<()> {
+        let plan = LogicalPlan::MatchRecognize(match_recognize(vec![col("unknown")])?);
+        plan.check_invariants(InvariantLevel::Always)?;
+        let err = plan
+            .check_invariants(InvariantLevel::Executable)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Column unknown of MatchRecognize is not in the schema")
+        );
+
+        // Also checked below other nodes
+        let plan = LogicalPlanBuilder::from(plan).limit(0, Some(1))?.build()?;
+        assert!(plan.check_invariants(InvariantLevel::Executable).is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,571 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Column-level lineage of a [`LogicalPlan`]
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use arrow::datatypes::Field;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::{Column, DFSchema, Result, TableReference};
+
+use crate::expr::InSubquery;
+use crate::{
+    Distinct, Expr, JsonTableColumnDef, LogicalPlan, Subquery, XmlTableColumnDef,
+};
+
+/// The field metadata key of the columns of a view expanded into a plan,
+/// with the name of the view as its value
+///
+/// It is set on the schema of the [`SubqueryAlias`] that replaces the scan
+/// of the view, so that [`LogicalPlan::column_lineage`] can report the views
+/// a column is read through.
+///
+/// [`SubqueryAlias`]: crate::SubqueryAlias
+pub const VIEW_METADATA_KEY: &str = "view";
+
+/// A column of a table that an output column of a [`LogicalPlan`] is read
+/// or computed from
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ColumnOrigin {
+    /// The table that is scanned
+    pub table: TableReference,
+    /// The name of the column of the table
+    pub column: String,
+    /// The views that the column is read through, from the outermost to the
+    /// innermost
+    pub views: Vec<TableReference>,
+    /// Whether the output column is computed from the column by an
+    /// expression, rather than being the column itself
+    pub derived: bool,
+}
+
+/// The lineage of an output column of a [`LogicalPlan`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLineage {
+    /// The output column
+    pub column: Column,
+    /// The columns of the tables that the output column is read or computed
+    /// from, which is empty for columns computed from literals only
+    pub origins: Vec<ColumnOrigin>,
+}
+
+impl LogicalPlan {
+    /// Returns the lineage of each output column of the plan, tracing it
+    /// through the expressions, subquery aliases and expanded views of the
+    /// plan to the columns of the tables that are scanned
+    ///
+    /// The columns referenced by subqueries and by lateral relations, such as
+    /// the documents of `JSON_TABLE` and `XMLTABLE`, contribute to the
+    /// columns computed from them. The columns of `GRAPH_TABLE` originate
+    /// from the properties of the graph, and those of `RETURNING` from the
+    /// target table of the DML statement. The row counts returned by DML
+    /// statements and `MERGE` have no origins.
+    ///
+    /// The views are known by the [`VIEW_METADATA_KEY`] of the columns of
+    /// their expansion, so the lineage is complete for the plan produced by
+    /// the SQL planner, before it is rewritten by the optimizer.
+    pub fn column_lineage(&self) -> Result<Vec<ColumnLineage>> {
+        Ok(self
+            .schema()
+            .columns()
+            .into_iter()
+            .zip(plan_origins(self, None)?)
+            .map(|(column, origins)| ColumnLineage {
+                column,
+                origins: origins.into_iter().collect(),
+            })
+            .collect())
+    }
+}
+
+/// Sets the [`VIEW_METADATA_KEY`] of the columns of `plan`, the
+/// [`SubqueryAlias`] that a view named by its alias is expanded into
+///
+/// [`SubqueryAlias`]: crate::SubqueryAlias
+pub(crate) fn mark_view_columns(plan: LogicalPlan) -> Result<LogicalPlan> {
+    let LogicalPlan::SubqueryAlias(mut subquery_alias) = plan else {
+        return Ok(plan);
+    };
+    let view = subquery_alias.alias.to_string();
+    let schema = &subquery_alias.schema;
+    let fields = schema
+        .iter()
+        .map(|(qualifier, field)| {
+            let mut metadata = field.metadata().clone();
+            metadata.insert(VIEW_METADATA_KEY.to_string(), view.clone());
+            let field = field.as_ref().clone().with_metadata(metadata);
+            (qualifier.cloned(), Arc::new(field))
+        })
+        .collect();
+    let marked = DFSchema::new_with_metadata(fields, schema.metadata().clone())?
+        .with_functional_dependencies(schema.functional_dependencies().clone())?;
+    subquery_alias.schema = Arc::new(marked);
+    Ok(LogicalPlan::SubqueryAlias(subquery_alias))
+}
+
+/// The origins of the columns of a plan, which the expressions of the plans
+/// that it is an input of reference
+#[derive(Clone, Copy)]
+struct Scope<'a> {
+    schema: &'a DFSchema,
+    origins: &'a [BTreeSet<ColumnOrigin>],
+    /// The scope of the outer query, whose columns are referenced by
+    /// correlated subqueries and lateral relations
+    outer: Option<&'a Scope<'a>>,
+}
+
+impl Scope<'_> {
+    /// The origins of `column`, in this scope or else in an outer scope
+    fn resolve(&self, column: &Column) -> Option<&BTreeSet<ColumnOrigin>> {
+        match self.schema.maybe_index_of_column(column) {
+            Some(index) => Some(&self.origins[index]),
+            None => self.outer?.resolve(column),
+        }
+    }
+}
+
+/// The origins of each output column of `plan`, whose outer references are
+/// resolved in `outer`
+fn plan_origins(
+    plan: &LogicalPlan,
+    outer: Option<&Scope>,
+) -> Result<Vec<BTreeSet<ColumnOrigin>>> {
+    match plan {
+        LogicalPlan::TableScan(scan) => match scan.source.get_logical_plan() {
+            // A view that is not expanded, because filters were pushed into
+            // its scan
+            Some(view_plan) => {
+                let origins = plan_origins(&view_plan, None)?
+                    .into_iter()
+                    .map(|origins| through_view(origins, &scan.table_name))
+                    .collect::<Vec<_>>();
+                Ok(match &scan.projection {
+                    Some(projection) => {
+                        projection.iter().map(|i| origins[*i].clone()).collect()
+                    }
+                    None => origins,
+                })
+            }
+            None => Ok(scan
+                .projected_schema
+                .fields()
+                .iter()
+                .map(|field| {
+                    BTreeSet::from([ColumnOrigin {
+                        table: scan.table_name.clone(),
+                        column: field.name().clone(),
+                        views: vec![],
+                        derived: false,
+                    }])
+                })
+                .collect()),
+        },
+        LogicalPlan::Projection(projection) => {
+            exprs_origins(&projection.expr, &projection.input, outer)
+        }
+        LogicalPlan::SubqueryAlias(subquery_alias) => {
+            let view = subquery_alias.alias.to_string();
+            let is_view =
+                |field: &Field| field.metadata().get(VIEW_METADATA_KEY) == Some(&view);
+            let input_schema = subquery_alias.input.schema();
+            Ok(plan_origins(&subquery_alias.input, outer)?
+                .into_iter()
+                .zip(
+                    subquery_alias
+                        .schema
+                        .fields()
+                        .iter()
+                        .zip(input_schema.fields()),
+                )
+                .map(|(origins, (field, input_field))| {
+                    // The view is expanded by this alias, rather than by an
+                    // alias of its expansion of the same name
+                    if is_view(field) && !is_view(input_field) {
+                        through_view(origins, &subquery_alias.alias)
+                    } else {
+                        origins
+                    }
+                })
+                .collect())
+        }
+        LogicalPlan::Aggregate(aggregate) => {
+            let exprs = plan
+                .columnized_output_exprs()?
+                .into_iter()
+                .map(|(expr, _)| expr.clone())
+                .collect::<Vec<_>>();
+            exprs_origins(&exprs, &aggregate.input, outer)
+        }
+        LogicalPlan::Window(window) => {
+            let input_origins = plan_origins(&window.input, outer)?;
+            let scope = Scope {
+                schema: window.input.schema(),
+                origins: &input_origins,
+                outer,
+            };
+            let window_origins = window
+                .window_expr
+                .iter()
+                .map(|expr| expr_origins(expr, &scope))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(input_origins.into_iter().chain(window_origins).collect())
+        }
+        LogicalPlan::Distinct(Distinct::On(distinct_on)) => {
+            exprs_origins(&distinct_on.select_expr, &distinct_on.input, outer)
+        }
+        LogicalPlan::Join(join) => {
+            // The right side of a lateral join references the left side
+            let left_origins = plan_origins(&join.left, outer)?;
+            let left_scope = Scope {
+                schema: join.left.schema(),
+                origins: &left_origins,
+                outer,
+            };
+            let right_origins = plan_origins(&join.right, Some(&left_scope))?;
+            Ok(origins_by_name(
+                plan.schema(),
+                &[
+                    (join.left.as_ref(), left_origins),
+                    (join.right.as_ref(), right_origins),
+                ],
+            ))
+        }
+        LogicalPlan::Union(union) => {
+            let mut origins = vec![BTreeSet::new(); union.schema.fields().len()];
+            for input in &union.inputs {
+                for (all, input_origins) in
+                    origins.iter_mut().zip(plan_origins(input, outer)?)
+                {
+                    all.extend(input_origins);
+                }
+            }
+            Ok(origins)
+        }
+        LogicalPlan::RecursiveQuery(recursive_query) => {
+            plan_origins(&recursive_query.static_term, outer)
+        }
+        LogicalPlan::Unnest(unnest) => {
+            let input_origins = plan_origins(&unnest.input, outer)?;
+            let unnested = unnest
+                .list_type_columns
+                .iter()
+                .map(|(index, _)| *index)
+                .chain(unnest.struct_type_columns.iter().copied())
+                .collect::<BTreeSet<_>>();
+            Ok(unnest
+                .dependency_indices
+                .iter()
+                .map(|index| {
+                    let origins = input_origins[*index].clone();
+                    if unnested.contains(index) {
+                        as_derived(origins)
+                    } else {
+                        origins
+                    }
+                })
+                .collect())
+        }
+        LogicalPlan::MatchRecognize(match_recognize) => {
+            let input_origins = plan_origins(&match_recognize.input, outer)?;
+            let scope = Scope {
+                schema: match_recognize.input.schema(),
+                origins: &input_origins,
+                outer,
+            };
+            let measure_origins = match_recognize
+                .measures
+                .iter()
+                .map(|measure| expr_origins(&measure.expr, &scope))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(input_origins.into_iter().chain(measure_origins).collect())
+        }
+        // The columns of JSON_TABLE and XMLTABLE are computed from the
+        // documents they are passed, which reference the relations to their
+        // left
+        LogicalPlan::JsonTable(json_table) => {
+            let scope = Scope {
+                schema: &DFSchema::empty(),
+                origins: &[],
+                outer,
+            };
+            let document = as_derived(expr_origins(&json_table.json_expr, &scope)?);
+            let mut origins = vec![];
+            json_table_origins(&json_table.columns, &document, &mut origins);
+            Ok(origins)
+        }
+        LogicalPlan::XmlTable(xml_table) => {
+            let scope = Scope {
+                schema: &DFSchema::empty(),
+                origins: &[],
+                outer,
+            };
+            let mut document = BTreeSet::new();
+            for argument in &xml_table.passing {
+                document.extend(as_derived(expr_origins(&argument.expr, &scope)?));
+            }
+            xml_table
+                .columns
+                .iter()
+                .map(|column| match column {
+                    XmlTableColumnDef::Path { default, .. } => {
+                        let mut origins = document.clone();
+                        if let Some(default) = default {
+                            origins.extend(as_derived(expr_origins(default, &scope)?));
+                        }
+                        Ok(origins)
+                    }
+                    XmlTableColumnDef::Ordinality { .. } => Ok(BTreeSet::new()),
+                })
+                .collect()
+        }
+        // The columns of GRAPH_TABLE are the properties of the graph
+        LogicalPlan::GraphTable(graph_table) => graph_table
+            .columns
+            .iter()
+            .map(|column| {
+                let derived = !is_column(&column.expr);
+                let mut origins = BTreeSet::new();
+                column.expr.apply(|expr| {
+                    if let Expr::Column(property) = expr {
+                        origins.insert(ColumnOrigin {
+                            table: graph_table.graph_name.clone(),
+                            column: property.name.clone(),
+                            views: vec![],
+                            derived,
+                        });
+                    }
+                    Ok(TreeNodeRecursion::Continue)
+                })?;
+                Ok(origins)
+            })
+            .collect(),
+        // The columns returned by a DML statement are those of its target
+        // table, and otherwise it returns the number of rows it changed
+        LogicalPlan::Dml(dml) => match &dml.returning_exprs {
+            Some(returning_exprs) => returning_exprs
+                .iter()
+                .map(|expr| {
+                    let derived = !is_column(expr);
+                    Ok(expr
+                        .column_refs()
+                        .into_iter()
+                        .map(|column| ColumnOrigin {
+                            table: dml.table_name.clone(),
+                            column: column.name.clone(),
+                            views: vec![],
+                            derived,
+                        })
+                        .collect())
+                })
+                .collect(),
+            None => Ok(vec![BTreeSet::new(); dml.output_schema.fields().len()]),
+        },
+        LogicalPlan::Merge(merge) => {
+            Ok(vec![BTreeSet::new(); merge.output_schema.fields().len()])
+        }
+        // The other plans pass the columns of their inputs through by name,
+        // such as filters, sorts and subqueries
+        _ => {
+            let inputs = plan
+                .inputs()
+                .into_iter()
+                .map(|input| Ok((input, plan_origins(input, outer)?)))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(origins_by_name(plan.schema(), &inputs))
+        }
+    }
+}
+
+/// The origins of the columns of `schema`, which are the columns of the
+/// same name of `inputs`
+fn origins_by_name(
+    schema: &DFSchema,
+    inputs: &[(&LogicalPlan, Vec<BTreeSet<ColumnOrigin>>)],
+) -> Vec<BTreeSet<ColumnOrigin>> {
+    schema
+        .columns()
+        .iter()
+        .map(|column| {
+            inputs
+                .iter()
+                .find_map(|(input, origins)| {
+                    let index = input.schema().maybe_index_of_column(column)?;
+                    Some(origins[index].clone())
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Appends the origins of the JSON_TABLE columns `columns`, which are
+/// computed from a document with the origins `document`
+fn json_table_origins(
+    columns: &[JsonTableColumnDef],
+    document: &BTreeSet<ColumnOrigin>,
+    origins: &mut Vec<BTreeSet<ColumnOrigin>>,
+) {
+    for column in columns {
+        match column {
+            JsonTableColumnDef::Path { .. } => origins.push(document.clone()),
+            JsonTableColumnDef::Ordinality { .. } => origins.push(BTreeSet::new()),
+            JsonTableColumnDef::Nested { columns, .. } => {
+                json_table_origins(columns, document, origins)
+            }
+        }
+    }
+}
+
+/// The origins of the output columns computed by `exprs` over `input`
+fn exprs_origins(
+    exprs: &[Expr],
+    input: &LogicalPlan,
+    outer: Option<&Scope>,
+) -> Result<Vec<BTreeSet<ColumnOrigin>>> {
+    let input_origins = plan_origins(input, outer)?;
+    let scope = Scope {
+        schema: input.schema(),
+        origins: &input_origins,
+        outer,
+    };
+    exprs
+        .iter()
+        .map(|expr| expr_origins(expr, &scope))
+        .collect()
+}
+
+/// The origins of the output column computed by `expr` over the columns of
+/// `scope`
+fn expr_origins(expr: &Expr, scope: &Scope) -> Result<BTreeSet<ColumnOrigin>> {
+    let mut origins = BTreeSet::new();
+    expr.apply(|expr| {
+        match expr {
+            Expr::Column(column) => {
+                if let Some(column_origins) = scope.resolve(column) {
+                    origins.extend(column_origins.iter().cloned());
+                }
+            }
+            Expr::OuterReferenceColumn(_, column) => {
+                if let Some(column_origins) =
+                    scope.outer.and_then(|outer| outer.resolve(column))
+                {
+                    origins.extend(column_origins.iter().cloned());
+                }
+            }
+            // The value of the subquery, or the values it is compared with
+            Expr::ScalarSubquery(Subquery { subquery, .. })
+            | Expr::InSubquery(InSubquery {
+                subquery: Subquery { subquery, .. },
+                ..
+            }) => {
+                if let Some(subquery_origins) =
+                    plan_origins(subquery, Some(scope))?.into_iter().next()
+                {
+                    origins.extend(subquery_origins);
+                }
+            }
+            _ => {}
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    Ok(if is_column(expr) {
+        origins
+    } else {
+        as_derived(origins)
+    })
+}
+
+/// Whether `expr` is a column, possibly renamed
+fn is_column(expr: &Expr) -> bool {
+    match expr {
+        Expr::Alias(alias) => is_column(&alias.expr),
+        expr => matches!(expr, Expr::Column(_)),
+    }
+}
+
+/// Marks the output column with the origins `origins` as computed from them
+fn as_derived(origins: BTreeSet<ColumnOrigin>) -> BTreeSet<ColumnOrigin> {
+    origins
+        .into_iter()
+        .map(|origin| ColumnOrigin {
+            derived: true,
+            ..origin
+        })
+        .collect()
+}
+
+/// Adds `view` as the outermost view that the columns of `origins` are read
+/// through
+fn through_view(
+    origins: BTreeSet<ColumnOrigin>,
+    view: &TableReference,
+) -> BTreeSet<ColumnOrigin> {
+    origins
+        .into_iter()
+        .map(|mut origin| {
+            origin.views.insert(0, view.clone());
+            origin
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logical_plan::table_scan;
+    use crate::{col, lit};
+    use arrow::datatypes::{DataType, Schema};
+
+    fn origin(table: &str, column: &str, derived: bool) -> ColumnOrigin {
+        ColumnOrigin {
+            table: TableReference::bare(table),
+            column: column.to_string(),
+            views: vec![],
+            derived,
+        }
+    }
+
+    #[test]
+    fn lineage_through_expressions() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let plan = table_scan(Some("t"), &schema, None)?
+            .filter(col("a").gt(lit(1)))?
+            .alias("s")?
+            .project(vec![
+                col("s.a"),
+                (col("s.a") + col("s.b")).alias("total"),
+                lit(1).alias("one"),
+            ])?
+            .build()?;
+
+        let lineage = plan.column_lineage()?;
+        assert_eq!(lineage.len(), 3);
+        assert_eq!(lineage[0].column, Column::new(Some("s"), "a"));
+        assert_eq!(lineage[0].origins, vec![origin("t", "a", false)]);
+        assert_eq!(lineage[1].column, Column::from_name("total"));
+        assert_eq!(
+            lineage[1].origins,
+            vec![origin("t", "a", true), origin("t", "b", true)]
+        );
+        assert!(lineage[2].origins.is_empty());
+        Ok(())
+    }
+}
@@ -20,7 +20,10 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-use datafusion_common::{DFSchemaRef, TableReference};
+use datafusion_common::tree_node::{
+    Transformed, TreeNodeContainer, TreeNodeRecursion, TreeNodeRefContainer,
+};
+use datafusion_common::{DFSchemaRef, Result, TableReference};
 use sqlparser::ast::{AssignmentTarget, MergeClauseKind, ObjectName};
 
 use crate::logical_plan::dml::make_count_schema;
@@ -161,3 +164,150 @@ pub struct MergeAssignment {
     pub target: AssignmentTarget,
     pub value: Expr,
 }
+
+impl<'a> TreeNodeContainer<'a, Expr> for MergeClause {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        f: F,
+    ) -> Result<TreeNodeRecursion> {
+        (&self.predicate, &self.action).apply_ref_elements(f)
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        (self.predicate, self.action)
+            .map_elements(f)?
+            .map_data(|(predicate, action)| {
+                Ok(Self {
+                    clause_kind: self.clause_kind,
+                    predicate,
+                    action,
+                })
+            })
+    }
+}
+
+impl<'a> TreeNodeContainer<'a, Expr> for MergeAction {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        f: F,
+    ) -> Result<TreeNodeRecursion> {
+        match self {
+            MergeAction::Insert(insert) => insert.apply_elements(f),
+            MergeAction::Update(update) => update.apply_elements(f),
+            MergeAction::Delete | MergeAction::DoNothing => {
+                Ok(TreeNodeRecursion::Continue)
+            }
+        }
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        Ok(match self {
+            MergeAction::Insert(insert) => {
+                insert.map_elements(f)?.update_data(MergeAction::Insert)
+            }
+            MergeAction::Update(update) => {
+                update.map_elements(f)?.update_data(MergeAction::Update)
+            }
+            MergeAction::Delete | MergeAction::DoNothing => Transformed::no(self),
+        })
+    }
+}
+
+impl<'a> TreeNodeContainer<'a, Expr> for MergeInsertExpr {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        mut f: F,
+    ) -> Result<TreeNodeRecursion> {
+        match &self.kind {
+            MergeInsertKind::Values(rows) => rows.apply_elements(&mut f)?,
+            MergeInsertKind::Row => TreeNodeRecursion::Continue,
+        }
+        .visit_sibling(|| self.insert_predicate.apply_elements(f))
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        let Self {
+            columns,
+            kind,
+            insert_predicate,
+        } = self;
+        Ok(match kind {
+            MergeInsertKind::Values(rows) => (rows, insert_predicate)
+                .map_elements(f)?
+                .update_data(|(rows, insert_predicate)| Self {
+                    columns,
+                    kind: MergeInsertKind::Values(rows),
+                    insert_predicate,
+                }),
+            MergeInsertKind::Row => {
+                insert_predicate
+                    .map_elements(f)?
+                    .update_data(|insert_predicate| Self {
+                        columns,
+                        kind: MergeInsertKind::Row,
+                        insert_predicate,
+                    })
+            }
+        })
+    }
+}
+
+impl<'a> TreeNodeContainer<'a, Expr> for MergeUpdateExpr {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        f: F,
+    ) -> Result<TreeNodeRecursion> {
+        (
+            &self.assignments,
+            &self.update_predicate,
+            &self.delete_predicate,
+        )
+            .apply_ref_elements(f)
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        (
+            self.assignments,
+            self.update_predicate,
+            self.delete_predicate,
+        )
+            .map_elements(f)?
+            .map_data(|(assignments, update_predicate, delete_predicate)| {
+                Ok(Self {
+                    assignments,
+                    update_predicate,
+                    delete_predicate,
+                })
+            })
+    }
+}
+
+impl<'a> TreeNodeContainer<'a, Expr> for MergeAssignment {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        f: F,
+    ) -> Result<TreeNodeRecursion> {
+        self.value.apply_elements(f)
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        self.value
+            .map_elements(f)?
+            .map_data(|value| Ok(Self { value, ..self }))
+    }
+}
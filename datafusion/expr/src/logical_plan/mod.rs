@@ -24,7 +24,9 @@ mod extension;
 pub mod psm;
 pub(crate) mod invariants;
 pub use invariants::{InvariantLevel, assert_expected_schema, check_subquery_expr};
+mod lineage;
 mod plan;
+mod privileges;
 mod statement;
 pub mod tree_node;
 
@@ -43,14 +45,18 @@ pub use ddl::{
     DropView, GraphEdgeEndpoint, GraphEdgeTableDefinition, GraphKeyClause,
     GraphPropertiesClause, GraphVertexTableDefinition, MaterializedViewRefreshMethod,
     OperateFunctionArg, RefreshMaterializedView, SequenceOptions,
+    AlterUser, CreateUser, DropUser,
     // SQL/MED (Management of External Data) types
     AlterForeignDataWrapperOperation, AlterForeignDataWrapperStatement,
-    AlterForeignTableOperation, AlterForeignTableStatement, AlterServerOperation,
-    AlterServerStatement, CreateForeignDataWrapperStatement, CreateForeignTableStatement,
-    CreateServerOption, CreateServerStatement, CreateUserMappingStatement,
-    DropForeignDataWrapperStatement, DropForeignTableStatement, DropServerStatement,
-    DropUserMappingStatement, ImportForeignSchemaLimitType, ImportForeignSchemaStatement,
-    AlterUserMappingStatement, UserMappingUser,
+    AlterForeignTable, AlterForeignTableOperation, AlterForeignTableStatement,
+    AlterServer, AlterServerOperation, AlterServerStatement,
+    CreateForeignDataWrapperStatement, CreateForeignTable,
+    CreateServer, CreateServerOption, CreateServerStatement, CreateUserMapping,
+    CreateUserMappingStatement,
+    DropForeignDataWrapperStatement, DropForeignTable, DropForeignTableStatement,
+    DropServer, DropServerStatement, DropUserMapping, DropUserMappingStatement,
+    ImportForeignSchemaLimitType, ImportForeignSchemaStatement,
+    AlterUserMappingStatement, OptionChange, UserMappingTarget, UserMappingUser,
 };
 pub use dml::{
     ConflictAssignment, ConflictTarget, DmlStatement, DoUpdateAction, InsertOp, OnConflict,
@@ -61,23 +67,29 @@ pub use merge::{
     MergeUpdateExpr,
 };
 pub use plan::{
-    AfterMatchSkipOption, Aggregate, Analyze, ColumnUnnestList, DescribeTable, Distinct,
+    AfterMatchSkipOption, Aggregate, Analyze, ColumnUnnestList, CteMaterialization,
+    CteReference, DescribeTable, Distinct,
     DistinctOn, EdgeDirection, EdgePattern, EmptyMatchesMode, EmptyRelation, Explain,
     ExplainOption, Extension, FetchType, Filter, GraphColumn, GraphPattern,
     GraphPatternElement, GraphPatternExpr, GraphTable, Join, JoinConstraint, JoinType,
     JsonTable, JsonTableColumnDef, JsonTableErrorHandling, LabelExpression, Limit,
     LogicalPlan, MatchRecognize, MeasureExpr, NodePattern, Partitioning, PathFinding,
-    PathMode, Pattern, PatternSymbol, PlanType, Projection, RecursiveQuery, Repartition,
-    RepetitionQuantifier, RowLimiting, RowsPerMatchOption, SkipType, Sort, StringifiedPlan,
-    Subquery, SubqueryAlias, SubsetDef, SymbolDef, TableScan, TableScanRowLock,
+    PathMode, Pattern, PatternSymbol, PlanType, PolymorphicTableFunction, Projection,
+    RecursiveQuery, Repartition,
+    RepetitionQuantifier, RowLimiting, RowsPerMatchOption, Sample, SampleMethod, SampleSize,
+    SkipType, Sort, StringifiedPlan,
+    Subquery, SubqueryAlias, SubsetDef, SymbolDef, TableFunctionArgument, TableScan,
+    TableScanRowLock,
     TableScanRowLockMode, TableScanRowLockWaitPolicy, ToStringifiedPlan, Union, Unnest,
-    Values, Window, projection_schema,
+    Values, Window, XmlNamespace, XmlPassingArgument, XmlTable, XmlTableColumnDef,
+    projection_schema,
 };
 pub use statement::{
     AnalyzeTable, Call, Deallocate, Execute, Grant, GrantRole, Prepare, ReleaseSavepoint,
-    ResetVariable, Revoke, RevokeRole, RollbackToSavepoint, Savepoint, SetTransaction, SetVariable,
-    Statement, TransactionAccessMode, TransactionConclusion, TransactionEnd,
-    TransactionIsolationLevel, TransactionStart, TruncateTable, UseDatabase, Vacuum,
+    ResetVariable, Revoke, RevokeRole, RollbackToSavepoint, Savepoint, SetRole,
+    SetTransaction, SetVariable, Statement, TransactionAccessMode, TransactionConclusion,
+    TransactionEnd, TransactionIsolationLevel, TransactionStart, TruncateTable,
+    UseDatabase, Vacuum, VacuumOptions,
 };
 pub use psm::{
     HandlerCondition, HandlerType, ParameterMode, ProcedureArg, PsmBlock, PsmCase,
@@ -91,3 +103,9 @@ pub use datafusion_common::format::ExplainFormat;
 pub use display::display_schema;
 
 pub use extension::{UserDefinedLogicalNode, UserDefinedLogicalNodeCore};
+
+pub use lineage::{ColumnLineage, ColumnOrigin, VIEW_METADATA_KEY};
+
+pub use privileges::{
+    INSUFFICIENT_PRIVILEGE, PUBLIC_ROLE, Privilege, PrivilegeObject, PrivilegeStore,
+};
@@ -18,13 +18,13 @@
 //! Logical plan types
 
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::{Arc, LazyLock};
 
 use super::DdlStatement;
-use super::dml::{CopyFrom, CopyTo};
+use super::dml::{CopyErrorHandling, CopyFrom, CopyTo, PartitionLayout};
 use super::invariants::{
     InvariantLevel, assert_always_invariants_at_current_node,
     assert_executable_invariants,
@@ -43,14 +43,16 @@ use crate::logical_plan::{
     DmlStatement, Merge, MergeAction, MergeAssignment, MergeClause, MergeInsertExpr,
     MergeInsertKind, MergeUpdateExpr, Statement,
 };
+use crate::planner::TableVersion;
 use crate::utils::{
     enumerate_grouping_sets, exprlist_to_fields, find_out_reference_exprs,
     grouping_set_expr_count, grouping_set_to_exprlist, split_conjunction,
 };
 use crate::{
     BinaryExpr, CreateMemoryTable, CreateView, Execute, Expr, ExprSchemable,
-    LogicalPlanBuilder, Operator, Prepare, TableProviderFilterPushDown, TableSource,
-    WindowFunctionDefinition, build_join_schema, expr_vec_fmt, requalify_sides_if_needed,
+    LogicalPlanBuilder, Operator, PolymorphicTableFunctionImpl, Prepare,
+    TableProviderFilterPushDown, TableSource, WindowFunctionDefinition,
+    build_join_schema, expr_vec_fmt, requalify_sides_if_needed,
 };
 
 use arrow::datatypes::{DataType, Field, FieldRef, Schema, SchemaRef};
@@ -65,7 +67,7 @@ use datafusion_common::{
     FunctionalDependence, FunctionalDependencies, NullEquality, ParamValues,
     Result, ScalarValue, Spans, TableReference, UnnestOptions, UsingColumns,
     aggregate_functional_dependencies, assert_eq_or_internal_err, assert_or_internal_err,
-    internal_err, plan_err,
+    internal_datafusion_err, internal_err, plan_err,
 };
 use indexmap::IndexSet;
 
@@ -287,6 +289,24 @@ pub struct MeasureExpr {
     pub alias: String,
 }
 
+impl<'a> TreeNodeContainer<'a, Expr> for MeasureExpr {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        f: F,
+    ) -> Result<TreeNodeRecursion> {
+        self.expr.apply_elements(f)
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        self.expr
+            .map_elements(f)?
+            .map_data(|expr| Ok(Self { expr, ..self }))
+    }
+}
+
 /// Represents the ROWS PER MATCH option.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum RowsPerMatchOption {
@@ -376,6 +396,24 @@ pub struct SymbolDef {
     pub definition: Expr,
 }
 
+impl<'a> TreeNodeContainer<'a, Expr> for SymbolDef {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        f: F,
+    ) -> Result<TreeNodeRecursion> {
+        self.definition.apply_elements(f)
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        self.definition
+            .map_elements(f)?
+            .map_data(|definition| Ok(Self { definition, ..self }))
+    }
+}
+
 /// Represents a subset definition in the SUBSET clause.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct SubsetDef {
@@ -387,15 +425,20 @@ pub struct SubsetDef {
 // JSON_TABLE Types (JSON table function - SQL:2016)
 // ============================================================================
 
-/// Error handling option for JSON_TABLE columns.
+/// Error handling option for JSON_TABLE columns, and for the ON EMPTY and
+/// ON ERROR clauses of the scalar SQL/JSON functions.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd)]
 pub enum JsonTableErrorHandling {
-    /// NULL ON ERROR/EMPTY
+    /// NULL ON ERROR/EMPTY, or UNKNOWN ON ERROR of JSON_EXISTS
     Null,
-    /// DEFAULT <value> ON ERROR/EMPTY
+    /// DEFAULT <value> ON ERROR/EMPTY, or TRUE/FALSE ON ERROR of JSON_EXISTS
     Default(ScalarValue),
     /// ERROR ON ERROR
     Error,
+    /// EMPTY ARRAY ON ERROR/EMPTY of JSON_QUERY
+    EmptyArray,
+    /// EMPTY OBJECT ON ERROR/EMPTY of JSON_QUERY
+    EmptyObject,
 }
 
 /// Represents a column definition in JSON_TABLE.
@@ -481,7 +524,9 @@ impl JsonTable {
     }
 
     /// Convert column definitions to Arrow fields
-    fn columns_to_fields(columns: &[JsonTableColumnDef]) -> Result<Vec<Arc<Field>>> {
+    pub(crate) fn columns_to_fields(
+        columns: &[JsonTableColumnDef],
+    ) -> Result<Vec<Arc<Field>>> {
         let mut fields = Vec::new();
         for col in columns {
             match col {
@@ -523,6 +568,194 @@ impl PartialOrd for JsonTable {
     }
 }
 
+// ============================================================================
+// XMLTABLE Types (XML table function - SQL/XML, ISO/IEC 9075-14)
+// ============================================================================
+
+/// A namespace declared by the `XMLNAMESPACES` clause of XMLTABLE.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd)]
+pub struct XmlNamespace {
+    /// The namespace URI
+    pub uri: String,
+    /// The prefix bound to the namespace
+    pub prefix: String,
+}
+
+/// An argument of the `PASSING` clause of XMLTABLE.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd)]
+pub struct XmlPassingArgument {
+    /// The value passed to the XQuery expressions
+    pub expr: Expr,
+    /// The name of the XQuery variable bound to the value, or `None` for the
+    /// context item
+    pub name: Option<String>,
+}
+
+/// Represents a column definition in XMLTABLE.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd)]
+pub enum XmlTableColumnDef {
+    /// Column extracted from each row item
+    /// Maps to: `name type [PATH 'path'] [DEFAULT expr] [NOT NULL]`
+    Path {
+        name: String,
+        data_type: DataType,
+        /// The XQuery expression evaluated against the row item, the column
+        /// name when absent
+        path: Option<String>,
+        /// The value of the column when the path selects no item
+        default: Option<Expr>,
+        nullable: bool,
+    },
+    /// Ordinality column (row number)
+    /// Maps to: `name FOR ORDINALITY`
+    Ordinality { name: String },
+}
+
+/// XMLTABLE: Transform XML data into a relational table (SQL/XML X300)
+///
+/// Evaluates the row XQuery expression against the values of the `PASSING`
+/// clause, and produces a row for each item of the result, with the columns
+/// extracted from the item by their own path expressions.
+///
+/// Example SQL:
+/// ```sql
+/// SELECT x.*
+/// FROM xml_data,
+///   XMLTABLE(
+///     '/orders/order' PASSING xml_data.doc
+///     COLUMNS
+///       id INT PATH '@id',
+///       customer VARCHAR(100) PATH 'customer' DEFAULT 'unknown',
+///       seq FOR ORDINALITY
+///   ) AS x
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XmlTable {
+    /// The namespaces of the `XMLNAMESPACES` clause
+    pub namespaces: Vec<XmlNamespace>,
+    /// The row XQuery expression
+    pub row_expression: String,
+    /// The arguments of the `PASSING` clause
+    pub passing: Vec<XmlPassingArgument>,
+    /// Column definitions specifying what to extract
+    pub columns: Vec<XmlTableColumnDef>,
+    /// The schema of the output table
+    pub schema: DFSchemaRef,
+}
+
+impl XmlTable {
+    /// Create a new XmlTable node
+    pub fn try_new(
+        namespaces: Vec<XmlNamespace>,
+        row_expression: String,
+        passing: Vec<XmlPassingArgument>,
+        columns: Vec<XmlTableColumnDef>,
+    ) -> Result<Self> {
+        let fields = columns
+            .iter()
+            .map(|column| match column {
+                XmlTableColumnDef::Path {
+                    name,
+                    data_type,
+                    nullable,
+                    ..
+                } => Arc::new(Field::new(name, data_type.clone(), *nullable)),
+                // Ordinality is a row number, always BIGINT
+                XmlTableColumnDef::Ordinality { name } => {
+                    Arc::new(Field::new(name, DataType::Int64, false))
+                }
+            })
+            .collect::<Vec<_>>();
+        let schema = Arc::new(DFSchema::from_unqualified_fields(
+            fields.into(),
+            HashMap::new(),
+        )?);
+
+        Ok(Self {
+            namespaces,
+            row_expression,
+            passing,
+            columns,
+            schema,
+        })
+    }
+
+    /// The expressions of this node: the `PASSING` arguments, followed by
+    /// the `DEFAULT` values of the columns
+    pub fn expressions(&self) -> Vec<Expr> {
+        self.passing
+            .iter()
+            .map(|argument| argument.expr.clone())
+            .chain(self.columns.iter().filter_map(|column| match column {
+                XmlTableColumnDef::Path { default, .. } => default.clone(),
+                XmlTableColumnDef::Ordinality { .. } => None,
+            }))
+            .collect()
+    }
+
+    /// Rebuilds this node with the expressions `exprs`, in the order of
+    /// [`Self::expressions`]
+    pub fn with_new_exprs(&self, exprs: Vec<Expr>) -> Result<Self> {
+        let mut exprs = exprs.into_iter();
+        let mut next = || {
+            exprs.next().ok_or_else(|| {
+                internal_datafusion_err!("XmlTable expects more expressions")
+            })
+        };
+        let passing = self
+            .passing
+            .iter()
+            .map(|argument| {
+                Ok(XmlPassingArgument {
+                    expr: next()?,
+                    name: argument.name.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| match column {
+                XmlTableColumnDef::Path {
+                    name,
+                    data_type,
+                    path,
+                    default,
+                    nullable,
+                } => Ok(XmlTableColumnDef::Path {
+                    name: name.clone(),
+                    data_type: data_type.clone(),
+                    path: path.clone(),
+                    default: default.as_ref().map(|_| next()).transpose()?,
+                    nullable: *nullable,
+                }),
+                XmlTableColumnDef::Ordinality { .. } => Ok(column.clone()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::try_new(
+            self.namespaces.clone(),
+            self.row_expression.clone(),
+            passing,
+            columns,
+        )
+    }
+}
+
+impl PartialOrd for XmlTable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.row_expression.partial_cmp(&other.row_expression) {
+            Some(Ordering::Equal) => {
+                match self.passing.partial_cmp(&other.passing) {
+                    Some(Ordering::Equal) => None, // schemas and columns don't matter for ordering
+                    cmp => cmp,
+                }
+            }
+            cmp => cmp,
+        }
+        .filter(|cmp| *cmp != Ordering::Equal || self == other)
+    }
+}
+
 // ============================================================================
 // GRAPH_TABLE: Property Graph Queries (SQL/PGQ - ISO/IEC 9075-16:2023)
 // ============================================================================
@@ -555,6 +788,27 @@ pub enum LabelExpression {
     Or(Box<LabelExpression>, Box<LabelExpression>),
 }
 
+impl Display for LabelExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Label(label) => f.write_str(label),
+            Self::Wildcard => f.write_str("%"),
+            Self::Not(expr) => match expr.as_ref() {
+                Self::And(..) | Self::Or(..) => write!(f, "!({expr})"),
+                _ => write!(f, "!{expr}"),
+            },
+            Self::And(left, right) => {
+                let operand = |expr: &Self| match expr {
+                    Self::Or(..) => format!("({expr})"),
+                    _ => expr.to_string(),
+                };
+                write!(f, "{}&{}", operand(left), operand(right))
+            }
+            Self::Or(left, right) => write!(f, "{left}|{right}"),
+        }
+    }
+}
+
 /// Node pattern in a graph query (SQL/PGQ)
 /// Example: `(a:Person {age: 30})`
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
@@ -588,6 +842,9 @@ pub struct EdgePattern {
     pub direction: EdgeDirection,
     /// Optional quantifier for path patterns
     pub quantifier: Option<RepetitionQuantifier>,
+    /// Optional `COST` expression weighing each matched edge in `CHEAPEST`
+    /// path search; edges without one cost 1
+    pub cost: Option<Expr>,
 }
 
 /// A graph pattern element (node or edge)
@@ -652,9 +909,9 @@ pub enum PathMode {
     Walk,
     /// TRAIL - no repeated edges
     Trail,
-    /// ACYCLIC - no repeated vertices (except start = end for cycles)
+    /// ACYCLIC - no repeated vertices
     Acyclic,
-    /// SIMPLE - no repeated vertices at all
+    /// SIMPLE - no repeated vertices, except that a path may end where it starts
     Simple,
 }
 
@@ -811,22 +1068,8 @@ impl MatchRecognize {
         subsets: Vec<SubsetDef>,
         symbols: Vec<SymbolDef>,
     ) -> Result<Self> {
-        // Build schema: input columns + measure columns
-        let mut field_vec: Vec<Arc<Field>> = input
-            .schema()
-            .iter()
-            .map(|(_, f)| Arc::clone(f))
-            .collect();
-
-        // Add measure fields
-        for measure in &measures {
-            let (_, field) = measure.expr.to_field(&input.schema())?;
-            let field = Arc::unwrap_or_clone(field).with_name(&measure.alias);
-            field_vec.push(Arc::new(field));
-        }
-
         let schema = Arc::new(DFSchema::from_unqualified_fields(
-            field_vec.into(),
+            Self::output_fields(&input, &measures)?.into(),
             HashMap::new(),
         )?);
 
@@ -843,6 +1086,27 @@ impl MatchRecognize {
             schema,
         })
     }
+
+    /// The fields of the output: the input columns followed by the measure
+    /// columns
+    pub(crate) fn output_fields(
+        input: &LogicalPlan,
+        measures: &[MeasureExpr],
+    ) -> Result<Vec<Arc<Field>>> {
+        let mut field_vec: Vec<Arc<Field>> = input
+            .schema()
+            .iter()
+            .map(|(_, f)| Arc::clone(f))
+            .collect();
+
+        // Add measure fields
+        for measure in measures {
+            let (_, field) = measure.expr.to_field(input.schema())?;
+            let field = Arc::unwrap_or_clone(field).with_name(&measure.alias);
+            field_vec.push(Arc::new(field));
+        }
+        Ok(field_vec)
+    }
 }
 
 impl PartialOrd for MatchRecognize {
@@ -929,6 +1193,9 @@ pub enum LogicalPlan {
     SubqueryAlias(SubqueryAlias),
     /// Skip some number of rows, and then fetch some number of rows.
     Limit(Limit),
+    /// Keeps a random subset of the input rows. This is used to implement
+    /// SQL `TABLESAMPLE`.
+    Sample(Sample),
     /// A DataFusion [`Statement`] such as `SET VARIABLE` or `START TRANSACTION`
     Statement(Statement),
     /// Values expression. See
@@ -967,12 +1234,20 @@ pub enum LogicalPlan {
     Unnest(Unnest),
     /// A variadic query (e.g. "Recursive CTEs")
     RecursiveQuery(RecursiveQuery),
+    /// A reference to a common table expression whose result can be shared
+    /// with its other references
+    CteReference(CteReference),
     /// Row Pattern Recognition (MATCH_RECOGNIZE) from SQL:2016
     MatchRecognize(MatchRecognize),
     /// JSON_TABLE function to transform JSON to relational format (SQL:2016 T827)
     JsonTable(JsonTable),
+    /// XMLTABLE function to transform XML to relational format (SQL/XML)
+    XmlTable(XmlTable),
     /// GRAPH_TABLE function for property graph queries (SQL/PGQ - ISO/IEC 9075-16:2023)
     GraphTable(GraphTable),
+    /// A call to a polymorphic table function, SQL:2016
+    /// `TABLE(func(TABLE t, ...))`
+    PolymorphicTableFunction(PolymorphicTableFunction),
 }
 
 impl Default for LogicalPlan {
@@ -1019,6 +1294,8 @@ impl LogicalPlan {
             LogicalPlan::Join(Join { schema, .. }) => schema,
             LogicalPlan::Repartition(Repartition { input, .. }) => input.schema(),
             LogicalPlan::Limit(Limit { input, .. }) => input.schema(),
+            LogicalPlan::Sample(Sample { input, .. }) => input.schema(),
+            LogicalPlan::CteReference(CteReference { input, .. }) => input.schema(),
             LogicalPlan::Statement(statement) => statement.schema(),
             LogicalPlan::Subquery(Subquery { subquery, .. }) => subquery.schema(),
             LogicalPlan::SubqueryAlias(SubqueryAlias { schema, .. }) => schema,
@@ -1041,7 +1318,12 @@ impl LogicalPlan {
             }
             LogicalPlan::MatchRecognize(MatchRecognize { schema, .. }) => schema,
             LogicalPlan::JsonTable(JsonTable { schema, .. }) => schema,
+            LogicalPlan::XmlTable(XmlTable { schema, .. }) => schema,
             LogicalPlan::GraphTable(GraphTable { schema, .. }) => schema,
+            LogicalPlan::PolymorphicTableFunction(PolymorphicTableFunction {
+                schema,
+                ..
+            }) => schema,
         }
     }
 
@@ -1146,6 +1428,8 @@ impl LogicalPlan {
             LogicalPlan::Sort(Sort { input, .. }) => vec![input],
             LogicalPlan::Join(Join { left, right, .. }) => vec![left, right],
             LogicalPlan::Limit(Limit { input, .. }) => vec![input],
+            LogicalPlan::Sample(Sample { input, .. }) => vec![input],
+            LogicalPlan::CteReference(CteReference { input, .. }) => vec![input],
             LogicalPlan::Subquery(Subquery { subquery, .. }) => vec![subquery],
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => vec![input],
             LogicalPlan::Extension(extension) => extension.node.inputs(),
@@ -1169,6 +1453,9 @@ impl LogicalPlan {
             }) => vec![static_term, recursive_term],
             LogicalPlan::Statement(stmt) => stmt.inputs(),
             LogicalPlan::MatchRecognize(MatchRecognize { input, .. }) => vec![input],
+            LogicalPlan::PolymorphicTableFunction(ptf) => {
+                ptf.inputs().into_iter().map(|arc| arc.as_ref()).collect()
+            }
             // plans without inputs
             LogicalPlan::TableScan { .. }
             | LogicalPlan::EmptyRelation { .. }
@@ -1176,6 +1463,7 @@ impl LogicalPlan {
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::CopyFrom(_)
             | LogicalPlan::JsonTable(_)
+            | LogicalPlan::XmlTable(_)
             | LogicalPlan::GraphTable(_) => vec![],
         }
     }
@@ -1241,6 +1529,8 @@ impl LogicalPlan {
             | LogicalPlan::Distinct(Distinct::All(input))
             | LogicalPlan::Sort(Sort { input, .. })
             | LogicalPlan::Limit(Limit { input, .. })
+            | LogicalPlan::Sample(Sample { input, .. })
+            | LogicalPlan::CteReference(CteReference { input, .. })
             | LogicalPlan::Repartition(Repartition { input, .. })
             | LogicalPlan::Window(Window { input, .. })
             | LogicalPlan::MatchRecognize(MatchRecognize { input, .. }) => input.head_output_expr(),
@@ -1288,9 +1578,20 @@ impl LogicalPlan {
             LogicalPlan::JsonTable(json_table) => Ok(Some(Expr::Column(Column::from(
                 json_table.schema.qualified_field(0),
             )))),
+            LogicalPlan::XmlTable(xml_table) => Ok(Some(Expr::Column(Column::from(
+                xml_table.schema.qualified_field(0),
+            )))),
             LogicalPlan::GraphTable(graph_table) => Ok(Some(Expr::Column(Column::from(
                 graph_table.schema.qualified_field(0),
             )))),
+            LogicalPlan::PolymorphicTableFunction(ptf) => {
+                if ptf.schema.fields().is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(Expr::Column(Column::from(
+                    ptf.schema.qualified_field(0),
+                ))))
+            }
             LogicalPlan::EmptyRelation(_)
             | LogicalPlan::Statement(_)
             | LogicalPlan::Values(_)
@@ -1402,6 +1703,8 @@ impl LogicalPlan {
                 schema: _,
             }) => SubqueryAlias::try_new(input, alias).map(LogicalPlan::SubqueryAlias),
             LogicalPlan::Limit(_) => Ok(self),
+            LogicalPlan::Sample(_) => Ok(self),
+            LogicalPlan::CteReference(_) => Ok(self),
             LogicalPlan::Ddl(_) => Ok(self),
             LogicalPlan::Extension(Extension { node }) => {
                 // todo make an API that does not require cloning
@@ -1495,6 +1798,14 @@ impl LogicalPlan {
                 JsonTable::try_new(json_expr, json_path, columns)
                     .map(LogicalPlan::JsonTable)
             }
+            LogicalPlan::XmlTable(XmlTable {
+                namespaces,
+                row_expression,
+                passing,
+                columns,
+                schema: _,
+            }) => XmlTable::try_new(namespaces, row_expression, passing, columns)
+                .map(LogicalPlan::XmlTable),
             LogicalPlan::GraphTable(GraphTable {
                 graph_name,
                 path_finding,
@@ -1518,6 +1829,16 @@ impl LogicalPlan {
                 )
                 .map(LogicalPlan::GraphTable)
             }
+            LogicalPlan::PolymorphicTableFunction(PolymorphicTableFunction {
+                func,
+                args,
+                arg_names,
+                schema: _,
+            }) => {
+                // Describe the call again, its inputs may have changed
+                PolymorphicTableFunction::try_new(func, args, arg_names)
+                    .map(LogicalPlan::PolymorphicTableFunction)
+            }
         }
     }
 
@@ -1716,35 +2037,56 @@ impl LogicalPlan {
                 file_type,
                 options,
                 partition_by,
+                partition_layout,
                 output_schema: _,
             }) => {
                 self.assert_no_expressions(expr)?;
                 let input = self.only_input(inputs)?;
-                Ok(LogicalPlan::Copy(CopyTo::new(
-                    Arc::new(input),
-                    output_url.clone(),
-                    partition_by.clone(),
-                    Arc::clone(file_type),
-                    options.clone(),
-                )))
+                Ok(LogicalPlan::Copy(
+                    CopyTo::new(
+                        Arc::new(input),
+                        output_url.clone(),
+                        partition_by.clone(),
+                        Arc::clone(file_type),
+                        options.clone(),
+                    )
+                    .with_partition_layout(partition_layout.clone()),
+                ))
             }
             LogicalPlan::CopyFrom(CopyFrom {
                 table_name,
-                source_url,
+                source,
                 columns,
                 file_type,
                 options,
+                column_exprs,
+                error_handling,
                 output_schema: _,
             }) => {
-                self.assert_no_expressions(expr)?;
                 self.assert_no_inputs(inputs)?;
-                Ok(LogicalPlan::CopyFrom(CopyFrom::new(
-                    table_name.clone(),
-                    source_url.clone(),
-                    columns.clone(),
-                    Arc::clone(file_type),
-                    options.clone(),
-                )))
+                if expr.len() != column_exprs.len() {
+                    return internal_err!(
+                        "Expected {} expressions for CopyFrom, got {}",
+                        column_exprs.len(),
+                        expr.len()
+                    );
+                }
+                let column_exprs = column_exprs
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .zip(expr)
+                    .collect();
+                Ok(LogicalPlan::CopyFrom(
+                    CopyFrom::new(
+                        table_name.clone(),
+                        source.clone(),
+                        columns.clone(),
+                        Arc::clone(file_type),
+                        options.clone(),
+                    )
+                    .with_column_exprs(column_exprs)
+                    .with_error_handling(error_handling.clone()),
+                ))
             }
             LogicalPlan::Values(Values { schema, .. }) => {
                 self.assert_no_inputs(inputs)?;
@@ -1906,6 +2248,27 @@ impl LogicalPlan {
                     input: Arc::new(input),
                 }))
             }
+            LogicalPlan::Sample(Sample {
+                method, size, seed, ..
+            }) => {
+                self.assert_no_expressions(expr)?;
+                let input = self.only_input(inputs)?;
+                Sample::try_new(Arc::new(input), *method, size.clone(), *seed)
+                    .map(LogicalPlan::Sample)
+            }
+            LogicalPlan::CteReference(CteReference {
+                name,
+                materialization,
+                ..
+            }) => {
+                self.assert_no_expressions(expr)?;
+                let input = self.only_input(inputs)?;
+                Ok(LogicalPlan::CteReference(CteReference::new(
+                    name.clone(),
+                    Arc::new(input),
+                    *materialization,
+                )))
+            }
             LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(CreateMemoryTable {
                 name,
                 if_not_exists,
@@ -2155,6 +2518,12 @@ impl LogicalPlan {
                 JsonTable::try_new(json_expr, json_path.clone(), columns.clone())
                     .map(LogicalPlan::JsonTable)
             }
+            LogicalPlan::XmlTable(xml_table) => {
+                // XmlTable has the PASSING arguments and the DEFAULT values
+                // as expressions, and no inputs
+                self.assert_no_inputs(inputs)?;
+                xml_table.with_new_exprs(expr).map(LogicalPlan::XmlTable)
+            }
             LogicalPlan::GraphTable(GraphTable {
                 graph_name,
                 path_finding,
@@ -2192,6 +2561,9 @@ impl LogicalPlan {
                 )
                 .map(LogicalPlan::GraphTable)
             }
+            LogicalPlan::PolymorphicTableFunction(ptf) => ptf
+                .with_new_exprs(expr, inputs)
+                .map(LogicalPlan::PolymorphicTableFunction),
         }
     }
 
@@ -2435,10 +2807,18 @@ impl LogicalPlan {
             LogicalPlan::RecursiveQuery(_) => None,
             LogicalPlan::Subquery(_) => None,
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => input.max_rows(),
+            LogicalPlan::CteReference(CteReference { input, .. }) => input.max_rows(),
             LogicalPlan::Limit(limit) => match limit.get_fetch_type() {
                 Ok(FetchType::Literal(s)) => s,
                 _ => None,
             },
+            LogicalPlan::Sample(Sample { input, size, .. }) => match size {
+                SampleSize::Rows(n) => {
+                    let n = usize::try_from(*n).ok()?;
+                    Some(input.max_rows().map_or(n, |max| max.min(n)))
+                }
+                SampleSize::Percent(_) => input.max_rows(),
+            },
             LogicalPlan::Distinct(
                 Distinct::All(input) | Distinct::On(DistinctOn { input, .. }),
             ) => input.max_rows(),
@@ -2446,7 +2826,9 @@ impl LogicalPlan {
             LogicalPlan::Unnest(_) => None,
             LogicalPlan::MatchRecognize(MatchRecognize { input, .. }) => input.max_rows(),
             LogicalPlan::JsonTable(_) => None, // JSON_TABLE output size depends on JSON content
+            LogicalPlan::XmlTable(_) => None, // XMLTABLE output size depends on XML content
             LogicalPlan::GraphTable(_) => None, // GRAPH_TABLE output size depends on graph data
+            LogicalPlan::PolymorphicTableFunction(_) => None,
             LogicalPlan::Ddl(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
@@ -2855,6 +3237,17 @@ impl LogicalPlan {
                     }) => {
                         write!(f, "RecursiveQuery: is_distinct={is_distinct}")
                     }
+                    LogicalPlan::CteReference(CteReference {
+                        name,
+                        materialization,
+                        ..
+                    }) => {
+                        write!(f, "CteReference: {name}")?;
+                        if *materialization != CteMaterialization::Default {
+                            write!(f, " {materialization}")?;
+                        }
+                        Ok(())
+                    }
                     LogicalPlan::Values(Values { values, .. }) => {
                         let str_values: Vec<_> = values
                             .iter()
@@ -2881,6 +3274,10 @@ impl LogicalPlan {
                         filters,
                         fetch,
                         row_lock,
+                        version,
+                        hints,
+                        policies,
+                        masks,
                         ..
                     }) => {
                         let projected_fields = match projection {
@@ -2952,6 +3349,32 @@ impl LogicalPlan {
                             write!(f, ", row_lock=[{lock}]")?;
                         }
 
+                        if let Some(version) = version {
+                            write!(f, ", version=[{version}]")?;
+                        }
+
+                        if !hints.is_empty() {
+                            let hints = hints
+                                .iter()
+                                .map(|(name, args)| {
+                                    if args.is_empty() {
+                                        name.clone()
+                                    } else {
+                                        format!("{name}({})", args.join(", "))
+                                    }
+                                })
+                                .collect::<Vec<_>>();
+                            write!(f, ", hints=[{}]", hints.join(", "))?;
+                        }
+
+                        if !policies.is_empty() {
+                            write!(f, ", policies=[{}]", policies.join(", "))?;
+                        }
+
+                        if !masks.is_empty() {
+                            write!(f, ", masks=[{}]", masks.join(", "))?;
+                        }
+
                         Ok(())
                     }
                     LogicalPlan::Projection(Projection { expr, .. }) => {
@@ -2975,6 +3398,7 @@ impl LogicalPlan {
                         output_url,
                         file_type,
                         options,
+                        partition_layout,
                         ..
                     }) => {
                         let op_str = options
@@ -2983,18 +3407,27 @@ impl LogicalPlan {
                             .collect::<Vec<String>>()
                             .join(", ");
 
+                        let layout_str =
+                            if *partition_layout == PartitionLayout::default() {
+                                String::new()
+                            } else {
+                                format!(" partition_layout=({partition_layout})")
+                            };
+
                         write!(
                             f,
-                            "CopyTo: format={} output_url={output_url} options: ({op_str})",
+                            "CopyTo: format={} output_url={output_url}{layout_str} options: ({op_str})",
                             file_type.get_ext()
                         )
                     }
                     LogicalPlan::CopyFrom(CopyFrom {
                         table_name,
-                        source_url,
+                        source,
                         file_type,
                         options,
                         columns,
+                        column_exprs,
+                        error_handling,
                         ..
                     }) => {
                         let op_str = options
@@ -3003,15 +3436,26 @@ impl LogicalPlan {
                             .collect::<Vec<String>>()
                             .join(", ");
 
-                        let cols_str = if columns.is_empty() {
+                        let mut cols_str = if columns.is_empty() {
                             String::new()
                         } else {
                             format!(" columns=[{}]", columns.join(", "))
                         };
+                        if !column_exprs.is_empty() {
+                            let exprs = column_exprs
+                                .iter()
+                                .map(|(name, expr)| format!("{name}={expr}"))
+                                .collect::<Vec<String>>()
+                                .join(", ");
+                            cols_str.push_str(&format!(" column_exprs=[{exprs}]"));
+                        }
+                        if *error_handling != CopyErrorHandling::default() {
+                            cols_str.push_str(&format!(" errors=({error_handling})"));
+                        }
 
                         write!(
                             f,
-                            "CopyFrom: table={table_name} format={} source_url={source_url}{cols_str} options: ({op_str})",
+                            "CopyFrom: table={table_name} format={} source={source}{cols_str} options: ({op_str})",
                             file_type.get_ext()
                         )
                     }
@@ -3144,6 +3588,15 @@ impl LogicalPlan {
                             write!(f, "Limit: skip={skip_str}, fetch={fetch_str}")
                         }
                     }
+                    LogicalPlan::Sample(Sample {
+                        method, size, seed, ..
+                    }) => {
+                        write!(f, "Sample: method={method}, size={size}")?;
+                        if let Some(seed) = seed {
+                            write!(f, ", seed={seed}")?;
+                        }
+                        Ok(())
+                    }
                     LogicalPlan::Subquery(Subquery { .. }) => {
                         write!(f, "Subquery:")
                     }
@@ -3214,9 +3667,30 @@ impl LogicalPlan {
                     LogicalPlan::JsonTable(JsonTable { json_path, .. }) => {
                         write!(f, "JsonTable: path={}", json_path)
                     }
+                    LogicalPlan::XmlTable(XmlTable {
+                        row_expression,
+                        passing,
+                        ..
+                    }) => {
+                        write!(f, "XmlTable: row={row_expression}")?;
+                        if !passing.is_empty() {
+                            let passing = passing
+                                .iter()
+                                .map(|argument| match &argument.name {
+                                    Some(name) => format!("{} AS {name}", argument.expr),
+                                    None => argument.expr.to_string(),
+                                })
+                                .collect::<Vec<_>>();
+                            write!(f, " passing=[{}]", passing.join(", "))?;
+                        }
+                        Ok(())
+                    }
                     LogicalPlan::GraphTable(GraphTable { graph_name, .. }) => {
                         write!(f, "GraphTable: graph={}", graph_name)
                     }
+                    LogicalPlan::PolymorphicTableFunction(ptf) => {
+                        write!(f, "PolymorphicTableFunction: {ptf}")
+                    }
                 }
             }
         }
@@ -3289,10 +3763,71 @@ pub struct RecursiveQuery {
     /// it returns an empty set)
     pub recursive_term: Arc<LogicalPlan>,
     /// Should the output of the recursive term be deduplicated (`UNION`) or
-    /// not (`UNION ALL`).
+    /// not (`UNION ALL`). When deduplicated, rows already produced by the
+    /// static term or any earlier iteration are discarded, so they are
+    /// neither returned again nor fed back into the working table.
     pub is_distinct: bool,
 }
 
+/// A reference to a common table expression (CTE).
+///
+/// All the references to a CTE hold the same `input`, the plan of the CTE,
+/// so it can be evaluated once and its result shared by the references
+/// instead of being evaluated once per reference. The optimizer inlines the
+/// references whose CTE is not worth sharing: those marked `NOT
+/// MATERIALIZED`, and by default those referenced only once.
+///
+/// Rewrites below a reference must leave the `input` of all references to
+/// the same CTE equal, so that an executor can recognize them; rules should
+/// treat the reference as a barrier rather than push work through it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct CteReference {
+    /// Name of the CTE
+    pub name: String,
+    /// The plan of the CTE
+    pub input: Arc<LogicalPlan>,
+    /// The `[NOT] MATERIALIZED` hint of the CTE
+    pub materialization: CteMaterialization,
+}
+
+impl CteReference {
+    /// Create a new reference to the CTE `name` planned as `input`
+    pub fn new(
+        name: impl Into<String>,
+        input: Arc<LogicalPlan>,
+        materialization: CteMaterialization,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            input,
+            materialization,
+        }
+    }
+}
+
+/// Whether the result of a CTE should be shared by its references, from the
+/// `WITH name AS [NOT] MATERIALIZED (...)` hint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub enum CteMaterialization {
+    /// No hint: shared when referenced more than once
+    #[default]
+    Default,
+    /// `MATERIALIZED`: always shared
+    Materialized,
+    /// `NOT MATERIALIZED`: always inlined into its references
+    NotMaterialized,
+}
+
+impl Display for CteMaterialization {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CteMaterialization::Default => write!(f, "DEFAULT"),
+            CteMaterialization::Materialized => write!(f, "MATERIALIZED"),
+            CteMaterialization::NotMaterialized => write!(f, "NOT MATERIALIZED"),
+        }
+    }
+}
+
 /// Values expression. See
 /// [Postgres VALUES](https://www.postgresql.org/docs/current/queries-values.html)
 /// documentation for more details.
@@ -3807,6 +4342,23 @@ pub struct TableScan {
     /// PostgreSQL `FROM ONLY <table>`: when true, descendant tables that
     /// inherit from `table_name` must be excluded from the scan.
     pub only: bool,
+    /// The historical version of the table read by a time travel query,
+    /// whose source was returned by
+    /// [`ContextProvider::get_table_source_at`] for this version.
+    ///
+    /// [`ContextProvider::get_table_source_at`]: crate::planner::ContextProvider::get_table_source_at
+    pub version: Option<TableVersion>,
+    /// Engine-specific table hints such as T-SQL `WITH (NOLOCK)` or MySQL
+    /// `USE INDEX (idx)`, keyed by upper-case hint name (`NOLOCK`,
+    /// `USE INDEX`) with the hint's arguments as values. The source is asked
+    /// about each hint with [`TableSource::supports_hint`].
+    pub hints: BTreeMap<String, Vec<String>>,
+    /// The names of the row-level security policies whose predicates filter
+    /// the rows of the scan, which the planner adds as filters above it
+    pub policies: Vec<String>,
+    /// The names of the columns of the scan whose values the planner replaces
+    /// by the expressions of their masks above it
+    pub masks: Vec<String>,
 }
 
 impl Debug for TableScan {
@@ -3820,6 +4372,10 @@ impl Debug for TableScan {
             .field("fetch", &self.fetch)
             .field("row_lock", &self.row_lock)
             .field("only", &self.only)
+            .field("version", &self.version)
+            .field("hints", &self.hints)
+            .field("policies", &self.policies)
+            .field("masks", &self.masks)
             .finish_non_exhaustive()
     }
 }
@@ -3833,6 +4389,10 @@ impl PartialEq for TableScan {
             && self.fetch == other.fetch
             && self.row_lock == other.row_lock
             && self.only == other.only
+            && self.version == other.version
+            && self.hints == other.hints
+            && self.policies == other.policies
+            && self.masks == other.masks
     }
 }
 
@@ -3856,6 +4416,14 @@ impl PartialOrd for TableScan {
             pub row_lock: &'a Option<TableScanRowLock>,
             /// PostgreSQL `FROM ONLY` modifier.
             pub only: &'a bool,
+            /// Time travel version.
+            pub version: &'a Option<TableVersion>,
+            /// Engine-specific table hints.
+            pub hints: &'a BTreeMap<String, Vec<String>>,
+            /// Row-level security policies.
+            pub policies: &'a Vec<String>,
+            /// Masked columns.
+            pub masks: &'a Vec<String>,
         }
         let comparable_self = ComparableTableScan {
             table_name: &self.table_name,
@@ -3864,6 +4432,10 @@ impl PartialOrd for TableScan {
             fetch: &self.fetch,
             row_lock: &self.row_lock,
             only: &self.only,
+            version: &self.version,
+            hints: &self.hints,
+            policies: &self.policies,
+            masks: &self.masks,
         };
         let comparable_other = ComparableTableScan {
             table_name: &other.table_name,
@@ -3872,6 +4444,10 @@ impl PartialOrd for TableScan {
             fetch: &other.fetch,
             row_lock: &other.row_lock,
             only: &other.only,
+            version: &other.version,
+            hints: &other.hints,
+            policies: &other.policies,
+            masks: &other.masks,
         };
         comparable_self
             .partial_cmp(&comparable_other)
@@ -3889,6 +4465,10 @@ impl Hash for TableScan {
         self.fetch.hash(state);
         self.row_lock.hash(state);
         self.only.hash(state);
+        self.version.hash(state);
+        self.hints.hash(state);
+        self.policies.hash(state);
+        self.masks.hash(state);
     }
 }
 
@@ -3944,6 +4524,10 @@ impl TableScan {
             fetch,
             row_lock: None,
             only: false,
+            version: None,
+            hints: BTreeMap::new(),
+            policies: vec![],
+            masks: vec![],
         })
     }
 }
@@ -4472,6 +5056,267 @@ impl Limit {
     }
 }
 
+/// Keeps a random subset of the rows produced by its input.
+///
+/// Planned from `TABLESAMPLE`, e.g.
+/// `SELECT * FROM t TABLESAMPLE BERNOULLI (10) REPEATABLE (42)`. The node
+/// does not change the schema of its input; storage engines may absorb it
+/// into their scan, otherwise it is executed as a filter over the input.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct Sample {
+    /// How rows are selected
+    pub method: SampleMethod,
+    /// How much of the input to keep
+    pub size: SampleSize,
+    /// Seed from `REPEATABLE (<seed>)`. Sampling the same data with the same
+    /// seed returns the same rows.
+    pub seed: Option<u64>,
+    /// The logical plan
+    pub input: Arc<LogicalPlan>,
+}
+
+impl Sample {
+    /// Create a new `Sample`, validating the requested size
+    pub fn try_new(
+        input: Arc<LogicalPlan>,
+        method: SampleMethod,
+        size: SampleSize,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        match &size {
+            SampleSize::Percent(ScalarValue::Float64(Some(p))) => {
+                if !(0.0..=100.0).contains(p) {
+                    return plan_err!(
+                        "TABLESAMPLE percentage must be between 0 and 100, '{p}' was provided"
+                    );
+                }
+            }
+            SampleSize::Percent(other) => {
+                return plan_err!(
+                    "TABLESAMPLE percentage must be a non-null Float64, got {other:?}"
+                );
+            }
+            SampleSize::Rows(_) => {
+                if method == SampleMethod::System {
+                    return plan_err!(
+                        "TABLESAMPLE SYSTEM does not support a fixed number of rows"
+                    );
+                }
+            }
+        }
+        Ok(Self {
+            method,
+            size,
+            seed,
+            input,
+        })
+    }
+
+    /// Fraction of rows to keep in `[0, 1]`, or `None` for a row-count sample
+    pub fn fraction(&self) -> Option<f64> {
+        match &self.size {
+            SampleSize::Percent(ScalarValue::Float64(Some(p))) => Some(p / 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Row selection strategy of a [`Sample`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum SampleMethod {
+    /// Every row is kept independently with the requested probability
+    Bernoulli,
+    /// Whole blocks of storage (files, row groups, batches) are kept or
+    /// skipped. Cheaper than [`SampleMethod::Bernoulli`] but less uniform.
+    System,
+}
+
+impl Display for SampleMethod {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SampleMethod::Bernoulli => write!(f, "BERNOULLI"),
+            SampleMethod::System => write!(f, "SYSTEM"),
+        }
+    }
+}
+
+/// How much of its input a [`Sample`] keeps
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum SampleSize {
+    /// Percentage of rows between 0 and 100, held as a `Float64` literal
+    Percent(ScalarValue),
+    /// Fixed number of rows
+    Rows(u64),
+}
+
+impl Display for SampleSize {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SampleSize::Percent(p) => write!(f, "{p}%"),
+            SampleSize::Rows(n) => write!(f, "{n} ROWS"),
+        }
+    }
+}
+
+/// A call to a [`PolymorphicTableFunctionImpl`].
+///
+/// Planned from `TABLE(func(TABLE t, ...))`. The table arguments are the
+/// inputs of this node and the scalar arguments its expressions; the output
+/// schema is computed by the function's describe phase.
+#[derive(Debug, Clone)]
+pub struct PolymorphicTableFunction {
+    /// The function being called
+    pub func: Arc<dyn PolymorphicTableFunctionImpl>,
+    /// The arguments, in call order
+    pub args: Vec<TableFunctionArgument>,
+    /// The name of each argument for `name => value` arguments, or `None`
+    pub arg_names: Vec<Option<String>>,
+    /// The output schema returned by the describe phase
+    pub schema: DFSchemaRef,
+}
+
+impl PolymorphicTableFunction {
+    /// Create a new call to `func`, running its describe phase to compute
+    /// the output schema
+    pub fn try_new(
+        func: Arc<dyn PolymorphicTableFunctionImpl>,
+        args: Vec<TableFunctionArgument>,
+        arg_names: Vec<Option<String>>,
+    ) -> Result<Self> {
+        if args.len() != arg_names.len() {
+            return internal_err!(
+                "Table function {} got {} arguments but {} argument names",
+                func.name(),
+                args.len(),
+                arg_names.len()
+            );
+        }
+        let schema = func.describe(&args, &arg_names)?;
+        let schema = Arc::new(DFSchema::try_from(schema)?);
+        Ok(Self {
+            func,
+            args,
+            arg_names,
+            schema,
+        })
+    }
+
+    /// The table arguments, in call order
+    pub fn inputs(&self) -> Vec<&Arc<LogicalPlan>> {
+        self.args
+            .iter()
+            .filter_map(|arg| match arg {
+                TableFunctionArgument::Table(plan) => Some(plan),
+                TableFunctionArgument::Scalar(_) => None,
+            })
+            .collect()
+    }
+
+    /// The scalar arguments, in call order
+    pub fn scalar_args(&self) -> Vec<&Expr> {
+        self.args
+            .iter()
+            .filter_map(|arg| match arg {
+                TableFunctionArgument::Table(_) => None,
+                TableFunctionArgument::Scalar(expr) => Some(expr),
+            })
+            .collect()
+    }
+
+    /// Replace the table arguments with `inputs` and the scalar arguments
+    /// with `exprs`, in call order, and describe the call again
+    pub fn with_new_exprs(
+        &self,
+        exprs: Vec<Expr>,
+        inputs: Vec<LogicalPlan>,
+    ) -> Result<Self> {
+        let mut exprs = exprs.into_iter();
+        let mut inputs = inputs.into_iter();
+        let args = self
+            .args
+            .iter()
+            .map(|arg| match arg {
+                TableFunctionArgument::Table(_) => inputs
+                    .next()
+                    .map(|input| TableFunctionArgument::Table(Arc::new(input))),
+                TableFunctionArgument::Scalar(_) => {
+                    exprs.next().map(TableFunctionArgument::Scalar)
+                }
+            })
+            .collect::<Option<Vec<_>>>();
+        match args {
+            Some(args) if exprs.next().is_none() && inputs.next().is_none() => {
+                Self::try_new(Arc::clone(&self.func), args, self.arg_names.clone())
+            }
+            _ => internal_err!(
+                "Table function {} got the wrong number of new arguments",
+                self.func.name()
+            ),
+        }
+    }
+}
+
+impl Display for PolymorphicTableFunction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}(", self.func.name())?;
+        for (i, (arg, name)) in self.args.iter().zip(&self.arg_names).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            if let Some(name) = name {
+                write!(f, "{name} => ")?;
+            }
+            match arg {
+                TableFunctionArgument::Table(_) => write!(f, "TABLE")?,
+                TableFunctionArgument::Scalar(expr) => write!(f, "{expr}")?,
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+// Manual implementations: calls to the same function with the same arguments
+// are equal, the schema follows from them
+impl PartialEq for PolymorphicTableFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.func.name() == other.func.name()
+            && self.args == other.args
+            && self.arg_names == other.arg_names
+    }
+}
+
+impl Eq for PolymorphicTableFunction {}
+
+impl Hash for PolymorphicTableFunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.func.name().hash(state);
+        self.args.hash(state);
+        self.arg_names.hash(state);
+    }
+}
+
+impl PartialOrd for PolymorphicTableFunction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.func.name().partial_cmp(other.func.name()) {
+            Some(Ordering::Equal) => match self.args.partial_cmp(&other.args) {
+                Some(Ordering::Equal) => self.arg_names.partial_cmp(&other.arg_names),
+                cmp => cmp,
+            },
+            cmp => cmp,
+        }
+        .filter(|cmp| *cmp != Ordering::Equal || self == other)
+    }
+}
+
+/// An argument of a [`PolymorphicTableFunction`] call
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum TableFunctionArgument {
+    /// A table argument, `TABLE t` or a subquery
+    Table(Arc<LogicalPlan>),
+    /// A scalar argument
+    Scalar(Expr),
+}
+
 /// Removes duplicate rows from the input
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
 pub enum Distinct {
@@ -6123,6 +6968,10 @@ mod tests {
             fetch: None,
             row_lock: None,
             only: false,
+            version: None,
+            hints: BTreeMap::new(),
+            policies: vec![],
+            masks: vec![],
         }));
         let col = schema.field_names()[0].clone();
 
@@ -6156,6 +7005,10 @@ mod tests {
             fetch: None,
             row_lock: None,
             only: false,
+            version: None,
+            hints: BTreeMap::new(),
+            policies: vec![],
+            masks: vec![],
         }));
         let col = schema.field_names()[0].clone();
 
@@ -6939,4 +7792,83 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_expressions_of_match_recognize_and_merge() -> Result<()> {
+        use sqlparser::ast::{AssignmentTarget, Ident, MergeClauseKind, ObjectName};
+
+        let input = Arc::new(
+            table_scan(Some("employee_csv"), &employee_schema(), None)?.build()?,
+        );
+        let match_recognize = LogicalPlan::MatchRecognize(MatchRecognize::try_new(
+            Arc::clone(&input),
+            vec![col("state")],
+            vec![col("id").sort(true, false)],
+            vec![MeasureExpr {
+                expr: col("salary") + lit(1),
+                alias: "raised".to_string(),
+            }],
+            None,
+            None,
+            Pattern::Symbol(PatternSymbol::Named("A".to_string())),
+            vec![],
+            vec![SymbolDef {
+                symbol: "A".to_string(),
+                definition: col("salary").gt(lit(1)),
+            }],
+        )?);
+        let merge = LogicalPlan::Merge(Merge::new(
+            "employee_csv".into(),
+            Arc::clone(&input),
+            Arc::clone(&input),
+            col("id").eq(lit(1)),
+            vec![MergeClause {
+                clause_kind: MergeClauseKind::Matched,
+                predicate: Some(col("id").gt(lit(1))),
+                action: MergeAction::Update(MergeUpdateExpr {
+                    assignments: vec![MergeAssignment {
+                        target: AssignmentTarget::ColumnName(ObjectName::from(vec![
+                            Ident::new("salary"),
+                        ])),
+                        value: col("salary") + lit(1),
+                    }],
+                    update_predicate: None,
+                    delete_predicate: None,
+                }),
+            }],
+        ));
+
+        for (plan, literal_count) in [(match_recognize, 2), (merge, 3)] {
+            // Every literal of the measures, definitions, predicates and
+            // assignments is rewritten
+            let rewritten = plan.map_expressions(|expr| {
+                expr.transform(|expr| match expr {
+                    Expr::Literal(ScalarValue::Int32(Some(1)), _) => {
+                        Ok(Transformed::yes(lit(2)))
+                    }
+                    expr => Ok(Transformed::no(expr)),
+                })
+            })?;
+            assert!(rewritten.transformed);
+            let mut literals = vec![];
+            rewritten.data.apply_expressions(|expr| {
+                expr.apply(|expr| {
+                    if let Expr::Literal(value, _) = expr {
+                        literals.push(value.clone());
+                    }
+                    Ok(TreeNodeRecursion::Continue)
+                })
+            })?;
+            assert_eq!(literals, vec![ScalarValue::Int32(Some(2)); literal_count]);
+
+            // Visiting stops at the first expression
+            let mut visited = 0;
+            rewritten.data.apply_expressions(|_| {
+                visited += 1;
+                Ok(TreeNodeRecursion::Stop)
+            })?;
+            assert_eq!(visited, 1);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,973 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Privileges of roles on tables, granted and revoked by `GRANT` and `REVOKE`
+//! statements, and the privileges that a [`LogicalPlan`] requires
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+use arrow::datatypes::Field;
+
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::{Result, TableReference, exec_err, not_impl_err, plan_err};
+use sqlparser::ast::{
+    Action, GrantObjects, Grantee, GranteeName, GranteesType, Ident, ObjectName,
+    Privileges,
+};
+
+use crate::logical_plan::{
+    DdlStatement, Grant, GrantRole, Revoke, RevokeRole, SetRole, Statement,
+    VIEW_METADATA_KEY,
+};
+use crate::session::SessionProvider;
+use crate::{
+    DmlStatement, Expr, Filter, LogicalPlan, MergeAction, Projection, SubqueryAlias,
+    WriteOp,
+};
+
+/// The SQLSTATE of the errors raised for statements that the current role
+/// lacks the privileges to run (`insufficient_privilege`)
+pub const INSUFFICIENT_PRIVILEGE: &str = "42501";
+
+/// The role that every role is a member of, whose privileges are granted
+/// `TO PUBLIC`
+pub const PUBLIC_ROLE: &str = "public";
+
+/// A privilege on a table or view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Privilege {
+    /// Read the rows of the table
+    Select,
+    /// Insert rows into the table
+    Insert,
+    /// Update the rows of the table
+    Update,
+    /// Delete the rows of the table
+    Delete,
+    /// Create, alter and drop the table
+    Create,
+}
+
+impl Privilege {
+    /// The privileges granted by `ALL PRIVILEGES`
+    pub const ALL: [Privilege; 5] = [
+        Privilege::Select,
+        Privilege::Insert,
+        Privilege::Update,
+        Privilege::Delete,
+        Privilege::Create,
+    ];
+}
+
+impl Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Privilege::Select => write!(f, "SELECT"),
+            Privilege::Insert => write!(f, "INSERT"),
+            Privilege::Update => write!(f, "UPDATE"),
+            Privilege::Delete => write!(f, "DELETE"),
+            Privilege::Create => write!(f, "CREATE"),
+        }
+    }
+}
+
+/// The object that a privilege is granted on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PrivilegeObject {
+    /// A table or view
+    Table(TableReference),
+    /// The tables and views of a schema
+    Schema(String),
+}
+
+impl PrivilegeObject {
+    /// Whether the privileges on this object apply to `table`, a fully
+    /// qualified table reference
+    fn covers(&self, table: &TableReference) -> bool {
+        match self {
+            PrivilegeObject::Table(granted) => granted.resolved_eq(table),
+            PrivilegeObject::Schema(schema) => table.schema() == Some(schema.as_str()),
+        }
+    }
+
+    /// Whether the privileges on this object include those on `object`: it
+    /// is the same object, or the schema of the table `object`
+    fn includes(&self, object: &PrivilegeObject) -> bool {
+        match (self, object) {
+            (PrivilegeObject::Table(table), PrivilegeObject::Table(other)) => {
+                table.resolved_eq(other)
+            }
+            (PrivilegeObject::Schema(schema), PrivilegeObject::Schema(other)) => {
+                schema == other
+            }
+            (PrivilegeObject::Schema(_), PrivilegeObject::Table(table)) => {
+                self.covers(table)
+            }
+            (PrivilegeObject::Table(_), PrivilegeObject::Schema(_)) => false,
+        }
+    }
+}
+
+impl Display for PrivilegeObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrivilegeObject::Table(table) => write!(f, "table {table}"),
+            PrivilegeObject::Schema(schema) => write!(f, "schema {schema}"),
+        }
+    }
+}
+
+/// The privileges granted to roles, and the roles granted to other roles
+///
+/// The store is kept up to date by [`Self::apply`] with the `GRANT` and
+/// `REVOKE` statements that are run, and checked with [`Self::check`] for the
+/// privileges that a plan requires. A role may only grant the privileges on
+/// the objects it owns, recorded with [`Self::set_owner`], and the
+/// privileges and roles it was granted with the grant or admin option.
+#[derive(Debug, Clone, Default)]
+pub struct PrivilegeStore {
+    /// The privileges granted to each role
+    privileges: HashMap<String, HashSet<(Privilege, PrivilegeObject)>>,
+    /// The privileges that each role may grant to other roles
+    grant_options: HashMap<String, HashSet<(Privilege, PrivilegeObject)>>,
+    /// The roles granted to each role, whose privileges it inherits
+    roles: HashMap<String, HashSet<String>>,
+    /// The roles that each role may grant to other roles
+    admin_options: HashMap<String, HashSet<String>>,
+    /// The owner of each object, which may grant every privilege on it
+    owners: HashMap<PrivilegeObject, String>,
+}
+
+impl PrivilegeStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grants `privilege` on `object` to `role`
+    pub fn grant(
+        &mut self,
+        role: impl Into<String>,
+        privilege: Privilege,
+        object: PrivilegeObject,
+    ) {
+        self.privileges
+            .entry(role.into())
+            .or_default()
+            .insert((privilege, object));
+    }
+
+    /// Revokes `privilege` on `object` from `role`, returning whether it was
+    /// granted
+    pub fn revoke(
+        &mut self,
+        role: &str,
+        privilege: Privilege,
+        object: &PrivilegeObject,
+    ) -> bool {
+        self.privileges
+            .get_mut(role)
+            .is_some_and(|privileges| privileges.remove(&(privilege, object.clone())))
+    }
+
+    /// Lets `role` grant `privilege` on `object` to other roles, as
+    /// `WITH GRANT OPTION` does
+    pub fn grant_option(
+        &mut self,
+        role: impl Into<String>,
+        privilege: Privilege,
+        object: PrivilegeObject,
+    ) {
+        self.grant_options
+            .entry(role.into())
+            .or_default()
+            .insert((privilege, object));
+    }
+
+    /// Revokes the grant option of `privilege` on `object` from `role`,
+    /// returning whether it was granted
+    pub fn revoke_grant_option(
+        &mut self,
+        role: &str,
+        privilege: Privilege,
+        object: &PrivilegeObject,
+    ) -> bool {
+        self.grant_options
+            .get_mut(role)
+            .is_some_and(|options| options.remove(&(privilege, object.clone())))
+    }
+
+    /// Records `role` as the owner of `object`, which may grant and revoke
+    /// every privilege on it
+    pub fn set_owner(&mut self, object: PrivilegeObject, role: impl Into<String>) {
+        self.owners.insert(object, role.into());
+    }
+
+    /// Grants the role `granted` to `role`, which inherits its privileges
+    pub fn grant_role(&mut self, granted: impl Into<String>, role: impl Into<String>) {
+        self.roles
+            .entry(role.into())
+            .or_default()
+            .insert(granted.into());
+    }
+
+    /// Lets `role` grant the role `granted` to other roles, as
+    /// `WITH ADMIN OPTION` does
+    pub fn grant_admin_option(
+        &mut self,
+        granted: impl Into<String>,
+        role: impl Into<String>,
+    ) {
+        self.admin_options
+            .entry(role.into())
+            .or_default()
+            .insert(granted.into());
+    }
+
+    /// Revokes the role `granted` from `role`, returning whether it was
+    /// granted
+    pub fn revoke_role(&mut self, granted: &str, role: &str) -> bool {
+        self.roles
+            .get_mut(role)
+            .is_some_and(|roles| roles.remove(granted))
+    }
+
+    /// Revokes the admin option of the role `granted` from `role`, returning
+    /// whether it was granted
+    pub fn revoke_admin_option(&mut self, granted: &str, role: &str) -> bool {
+        self.admin_options
+            .get_mut(role)
+            .is_some_and(|roles| roles.remove(granted))
+    }
+
+    /// Records the grants or revocations of `statement`, run by `session`,
+    /// returning whether it is a `GRANT` or `REVOKE` statement
+    ///
+    /// Unless the session is a superuser, its current role must own the
+    /// objects of the privileges it grants or revokes, or hold their grant
+    /// option, and hold the admin option of the roles it grants or revokes.
+    /// Nothing is recorded when a check fails.
+    pub fn apply(
+        &mut self,
+        session: &dyn SessionProvider,
+        statement: &Statement,
+    ) -> Result<bool> {
+        let grantor = (!session.is_superuser()).then(|| session.current_role());
+        match statement {
+            Statement::Grant(Grant {
+                privileges,
+                objects,
+                grantees,
+                with_grant_option,
+                ..
+            }) => {
+                let granted = granted_privileges(privileges, objects.as_ref())?;
+                if let Some(grantor) = &grantor {
+                    self.check_grant_options(grantor, "grant", &granted)?;
+                }
+                for grantee in grantees {
+                    let role = grantee_role(grantee)?;
+                    for (privilege, object) in &granted {
+                        self.grant(role.clone(), *privilege, object.clone());
+                        if *with_grant_option {
+                            self.grant_option(role.clone(), *privilege, object.clone());
+                        }
+                    }
+                }
+            }
+            Statement::Revoke(Revoke {
+                privileges,
+                objects,
+                grantees,
+                ..
+            }) => {
+                let revoked = granted_privileges(privileges, objects.as_ref())?;
+                if let Some(grantor) = &grantor {
+                    self.check_grant_options(grantor, "revoke", &revoked)?;
+                }
+                for grantee in grantees {
+                    let role = grantee_role(grantee)?;
+                    for (privilege, object) in &revoked {
+                        self.revoke(&role, *privilege, object);
+                        self.revoke_grant_option(&role, *privilege, object);
+                    }
+                }
+            }
+            Statement::GrantRole(GrantRole {
+                roles,
+                grantees,
+                with_admin_option,
+                ..
+            }) => {
+                let roles = roles.iter().map(normalize_ident).collect::<Vec<_>>();
+                if let Some(grantor) = &grantor {
+                    self.check_admin_options(grantor, "grant", &roles)?;
+                }
+                for grantee in grantees {
+                    let role = grantee_role(grantee)?;
+                    for granted in &roles {
+                        self.grant_role(granted.clone(), role.clone());
+                        if *with_admin_option {
+                            self.grant_admin_option(granted.clone(), role.clone());
+                        }
+                    }
+                }
+            }
+            Statement::RevokeRole(RevokeRole {
+                roles,
+                grantees,
+                admin_option_for,
+                ..
+            }) => {
+                let roles = roles.iter().map(normalize_ident).collect::<Vec<_>>();
+                if let Some(grantor) = &grantor {
+                    self.check_admin_options(grantor, "revoke", &roles)?;
+                }
+                for grantee in grantees {
+                    let role = grantee_role(grantee)?;
+                    for revoked in &roles {
+                        if !*admin_option_for {
+                            self.revoke_role(revoked, &role);
+                        }
+                        self.revoke_admin_option(revoked, &role);
+                    }
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Whether `role` may grant and revoke `privilege` on `object`: a role
+    /// it is a member of owns the object, or holds the grant option of the
+    /// privilege on it
+    pub fn can_grant(
+        &self,
+        role: &str,
+        privilege: Privilege,
+        object: &PrivilegeObject,
+    ) -> bool {
+        let roles = self.member_roles(role);
+        let owns = self.owners.iter().any(|(owned, owner)| {
+            roles.contains(owner.as_str()) && owned.includes(object)
+        });
+        owns || roles.into_iter().any(|role| {
+            self.grant_options.get(role).is_some_and(|options| {
+                options.iter().any(|(granted, owned)| {
+                    *granted == privilege && owned.includes(object)
+                })
+            })
+        })
+    }
+
+    /// Whether `role` may grant and revoke the role `granted`: a role it is
+    /// a member of holds the admin option of `granted`
+    pub fn can_grant_role(&self, role: &str, granted: &str) -> bool {
+        self.member_roles(role).into_iter().any(|role| {
+            self.admin_options
+                .get(role)
+                .is_some_and(|roles| roles.contains(granted))
+        })
+    }
+
+    /// Returns an error with the [`INSUFFICIENT_PRIVILEGE`] SQLSTATE unless
+    /// `grantor` may grant and revoke all the `privileges`
+    fn check_grant_options(
+        &self,
+        grantor: &str,
+        action: &str,
+        privileges: &[(Privilege, PrivilegeObject)],
+    ) -> Result<()> {
+        match privileges
+            .iter()
+            .find(|(privilege, object)| !self.can_grant(grantor, *privilege, object))
+        {
+            Some((privilege, object)) => exec_err!(
+                "Permission denied to {action} {privilege} on {object}: role {grantor} neither owns it nor holds the grant option (SQLSTATE {INSUFFICIENT_PRIVILEGE})"
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns an error with the [`INSUFFICIENT_PRIVILEGE`] SQLSTATE unless
+    /// `grantor` may grant and revoke all the `roles`
+    fn check_admin_options(
+        &self,
+        grantor: &str,
+        action: &str,
+        roles: &[String],
+    ) -> Result<()> {
+        match roles
+            .iter()
+            .find(|role| !self.can_grant_role(grantor, role))
+        {
+            Some(role) => exec_err!(
+                "Permission denied to {action} role {role}: role {grantor} does not hold its admin option (SQLSTATE {INSUFFICIENT_PRIVILEGE})"
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether `role` has `privilege` on `table`, a fully qualified table
+    /// reference, directly, through the roles granted to it, or through
+    /// [`PUBLIC_ROLE`]
+    pub fn has_privilege(
+        &self,
+        role: &str,
+        privilege: Privilege,
+        table: &TableReference,
+    ) -> bool {
+        self.member_roles(role).into_iter().any(|role| {
+            self.privileges.get(role).is_some_and(|privileges| {
+                privileges.iter().any(|(granted, object)| {
+                    *granted == privilege && object.covers(table)
+                })
+            })
+        })
+    }
+
+    /// Whether `role` is a member of `group`: it is the role itself, a role
+    /// granted to `role` directly or through other roles, or [`PUBLIC_ROLE`]
+    pub fn is_member(&self, role: &str, group: &str) -> bool {
+        self.member_roles(role).contains(group)
+    }
+
+    /// `role`, the roles granted to it directly or through other roles, and
+    /// [`PUBLIC_ROLE`]
+    fn member_roles<'a>(&'a self, role: &'a str) -> HashSet<&'a str> {
+        let mut pending = vec![role, PUBLIC_ROLE];
+        let mut visited = HashSet::new();
+        while let Some(role) = pending.pop() {
+            if visited.insert(role)
+                && let Some(roles) = self.roles.get(role)
+            {
+                pending.extend(roles.iter().map(String::as_str));
+            }
+        }
+        visited
+    }
+
+    /// Applies `set_role` to `session`, after checking that the session may
+    /// change to the role
+    ///
+    /// A superuser may change to any role. Other users may only `SET ROLE` to
+    /// the roles they are members of, and not change the session
+    /// authorization to another user.
+    pub fn set_role(
+        &self,
+        session: &dyn SessionProvider,
+        set_role: &SetRole,
+    ) -> Result<()> {
+        if let Some(role) = &set_role.role
+            && !session.is_superuser()
+        {
+            let user = session.current_user();
+            if set_role.session_authorization && role.as_str() != user {
+                return exec_err!(
+                    "Permission denied to set session authorization to {role} (SQLSTATE {INSUFFICIENT_PRIVILEGE})"
+                );
+            }
+            if !self.is_member(user, role) {
+                return exec_err!(
+                    "Permission denied to set role {role}: user {user} is not a member of it (SQLSTATE {INSUFFICIENT_PRIVILEGE})"
+                );
+            }
+        }
+        session.set_role(set_role)
+    }
+
+    /// Returns an error with the [`INSUFFICIENT_PRIVILEGE`] SQLSTATE unless
+    /// `role` has `privilege` on `table`, a fully qualified table reference
+    pub fn check(
+        &self,
+        role: &str,
+        privilege: Privilege,
+        table: &TableReference,
+    ) -> Result<()> {
+        if self.has_privilege(role, privilege, table) {
+            Ok(())
+        } else {
+            plan_err!(
+                "Permission denied for table {table}: role {role} lacks the {privilege} privilege (SQLSTATE {INSUFFICIENT_PRIVILEGE})"
+            )
+        }
+    }
+}
+
+impl LogicalPlan {
+    /// Returns the privileges on tables that running this plan requires, in
+    /// the order they are found
+    ///
+    /// Scanning a table requires [`Privilege::Select`], except for the scans
+    /// of the target of an `UPDATE`, `DELETE` or `MERGE`, which require the
+    /// privileges of the statement, and [`Privilege::Select`] as well when
+    /// the statement reads the columns of the target: in its `WHERE` or `ON`
+    /// condition, the values it assigns or its `RETURNING` clause. `COPY
+    /// FROM` requires [`Privilege::Insert`]. A view expanded into the plan
+    /// requires [`Privilege::Select`] on the view, but not on the tables it
+    /// reads. The tables, views and graphs that DDL statements define, alter
+    /// or drop require [`Privilege::Create`].
+    pub fn required_privileges(&self) -> Result<Vec<(Privilege, TableReference)>> {
+        let mut required = vec![];
+        let mut targets = HashSet::new();
+        self.apply_with_subqueries(|plan| {
+            match plan {
+                LogicalPlan::TableScan(scan) => {
+                    if !targets.contains(&scan.table_name) {
+                        required.push((Privilege::Select, scan.table_name.clone()));
+                    }
+                }
+                LogicalPlan::SubqueryAlias(subquery_alias) => {
+                    if expands_view(subquery_alias) {
+                        required.push((Privilege::Select, subquery_alias.alias.clone()));
+                        return Ok(TreeNodeRecursion::Jump);
+                    }
+                }
+                LogicalPlan::Dml(dml) => {
+                    let privilege = match dml.op {
+                        WriteOp::Insert(_) => Privilege::Insert,
+                        WriteOp::Update => Privilege::Update,
+                        WriteOp::Delete => Privilege::Delete,
+                        WriteOp::Ctas => Privilege::Create,
+                    };
+                    required.push((privilege, dml.table_name.clone()));
+                    if matches!(dml.op, WriteOp::Update | WriteOp::Delete) {
+                        targets.insert(dml.table_name.clone());
+                        if dml_reads_target(dml)? {
+                            required.push((Privilege::Select, dml.table_name.clone()));
+                        }
+                    }
+                }
+                LogicalPlan::Merge(merge) => {
+                    targets.insert(merge.target_table.clone());
+                    for clause in &merge.clauses {
+                        let privilege = match clause.action {
+                            MergeAction::Insert(_) => Privilege::Insert,
+                            MergeAction::Update(_) => Privilege::Update,
+                            MergeAction::Delete => Privilege::Delete,
+                            MergeAction::DoNothing => continue,
+                        };
+                        required.push((privilege, merge.target_table.clone()));
+                    }
+                    let qualifiers = table_qualifiers(plan, &merge.target_table)?;
+                    if reads_columns(plan, &qualifiers)? {
+                        required.push((Privilege::Select, merge.target_table.clone()));
+                    }
+                }
+                LogicalPlan::CopyFrom(copy) => {
+                    required.push((Privilege::Insert, copy.table_name.clone()));
+                }
+                LogicalPlan::Ddl(ddl) => {
+                    if let Some(table) = ddl_table(ddl) {
+                        required.push((Privilege::Create, table));
+                    }
+                }
+                _ => {}
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        Ok(required)
+    }
+}
+
+/// The table or view that `ddl` defines, alters or drops
+fn ddl_table(ddl: &DdlStatement) -> Option<TableReference> {
+    match ddl {
+        DdlStatement::CreateExternalTable(create) => Some(create.name.clone()),
+        DdlStatement::CreateMemoryTable(create) => Some(create.name.clone()),
+        DdlStatement::CreateView(create) => Some(create.name.clone()),
+        DdlStatement::CreateMaterializedView(create) => Some(create.name.clone()),
+        DdlStatement::DropMaterializedView(drop) => Some(drop.name.clone()),
+        DdlStatement::RefreshMaterializedView(refresh) => Some(refresh.name.clone()),
+        DdlStatement::AlterMaterializedView(alter) => Some(alter.name.clone()),
+        DdlStatement::CreateIndex(create) => Some(create.table.clone()),
+        DdlStatement::DropTable(drop) => Some(drop.name.clone()),
+        DdlStatement::DropView(drop) => Some(drop.name.clone()),
+        DdlStatement::AlterTable(alter) => Some(object_table(&alter.name)),
+        DdlStatement::CreateForeignTable(create) => Some(create.name.clone()),
+        DdlStatement::CreatePropertyGraph(create) => Some(create.name.clone()),
+        DdlStatement::DropPropertyGraph(drop) => Some(drop.name.clone()),
+        _ => None,
+    }
+}
+
+/// Whether `subquery_alias` is the expansion of the view named by its alias:
+/// all its columns are marked with the view, and none of the columns of its
+/// input, which an alias of a query reading the view would be
+fn expands_view(subquery_alias: &SubqueryAlias) -> bool {
+    let view = subquery_alias.alias.to_string();
+    let is_view =
+        |field: &Arc<Field>| field.metadata().get(VIEW_METADATA_KEY) == Some(&view);
+    subquery_alias.schema.fields().iter().all(is_view)
+        && !subquery_alias.input.schema().fields().iter().any(is_view)
+}
+
+/// Whether the `UPDATE` or `DELETE` `dml` reads the columns of its target
+/// table: in its `WHERE` clause, the values of its `SET` clause or its
+/// `RETURNING` clause
+fn dml_reads_target(dml: &DmlStatement) -> Result<bool> {
+    if dml
+        .returning_columns
+        .as_ref()
+        .is_some_and(|columns| !columns.is_empty())
+    {
+        return Ok(true);
+    }
+    let qualifiers = table_qualifiers(&dml.input, &dml.table_name)?;
+    // The input of an UPDATE projects the new values of the columns, and the
+    // columns it does not assign unchanged
+    let input = match (&dml.op, dml.input.as_ref()) {
+        (WriteOp::Update, LogicalPlan::Projection(projection)) => {
+            for expr in &projection.expr {
+                if !is_unchanged_column(expr) && expr_reads_columns(expr, &qualifiers) {
+                    return Ok(true);
+                }
+            }
+            projection.input.as_ref()
+        }
+        (_, input) => input,
+    };
+    reads_columns(input, &qualifiers)
+}
+
+/// The qualifiers of the columns of `table` in `plan`: the name of the table
+/// in its scans, and the aliases of the scans
+fn table_qualifiers(
+    plan: &LogicalPlan,
+    table: &TableReference,
+) -> Result<Vec<TableReference>> {
+    let mut qualifiers = vec![];
+    plan.apply_with_subqueries(|plan| {
+        match plan {
+            LogicalPlan::TableScan(scan) if scan.table_name.resolved_eq(table) => {
+                qualifiers.push(scan.table_name.clone());
+            }
+            LogicalPlan::SubqueryAlias(subquery_alias)
+                if scans_table(&subquery_alias.input, table) =>
+            {
+                qualifiers.push(subquery_alias.alias.clone());
+            }
+            _ => {}
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    Ok(qualifiers)
+}
+
+/// Whether `plan` is a scan of `table`, secured by its security policies
+fn scans_table(plan: &LogicalPlan, table: &TableReference) -> bool {
+    match plan {
+        LogicalPlan::TableScan(scan) => scan.table_name.resolved_eq(table),
+        LogicalPlan::Filter(Filter { input, .. })
+        | LogicalPlan::Projection(Projection { input, .. }) => scans_table(input, table),
+        _ => false,
+    }
+}
+
+/// Whether the expressions of `plan` and its subqueries read the columns
+/// qualified by one of `qualifiers`, other than to apply the security
+/// policies of a scan
+fn reads_columns(plan: &LogicalPlan, qualifiers: &[TableReference]) -> Result<bool> {
+    let mut reads = false;
+    plan.apply_with_subqueries(|plan| {
+        if secures_scan(plan) {
+            return Ok(TreeNodeRecursion::Continue);
+        }
+        plan.apply_expressions(|expr| {
+            reads |= expr_reads_columns(expr, qualifiers);
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        Ok(if reads {
+            TreeNodeRecursion::Stop
+        } else {
+            TreeNodeRecursion::Continue
+        })
+    })?;
+    Ok(reads)
+}
+
+/// Whether `expr` reads a column qualified by one of `qualifiers`, or refers
+/// to one as an outer reference
+fn expr_reads_columns(expr: &Expr, qualifiers: &[TableReference]) -> bool {
+    expr.exists(|expr| {
+        Ok(match expr {
+            Expr::Column(column) | Expr::OuterReferenceColumn(_, column) => column
+                .relation
+                .as_ref()
+                .is_some_and(|relation| qualifiers.contains(relation)),
+            _ => false,
+        })
+    })
+    .unwrap_or(true)
+}
+
+/// Whether `expr` is a column projected under its own name
+fn is_unchanged_column(expr: &Expr) -> bool {
+    match expr {
+        Expr::Column(_) => true,
+        Expr::Alias(alias) => {
+            matches!(alias.expr.as_ref(), Expr::Column(column) if column.name == alias.name)
+        }
+        _ => false,
+    }
+}
+
+/// Whether `plan` is the filter or the projection added above a scan to
+/// apply the row security policies and column masks of its table
+fn secures_scan(plan: &LogicalPlan) -> bool {
+    match plan {
+        LogicalPlan::Filter(Filter { input, .. }) => {
+            matches!(input.as_ref(), LogicalPlan::TableScan(scan) if !scan.policies.is_empty())
+        }
+        LogicalPlan::Projection(Projection { input, .. }) => match input.as_ref() {
+            LogicalPlan::Filter(Filter { input, .. }) => {
+                matches!(input.as_ref(), LogicalPlan::TableScan(scan) if !scan.masks.is_empty())
+            }
+            LogicalPlan::TableScan(scan) => !scan.masks.is_empty(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The privileges granted or revoked by `privileges` on `objects`
+fn granted_privileges(
+    privileges: &Privileges,
+    objects: Option<&GrantObjects>,
+) -> Result<Vec<(Privilege, PrivilegeObject)>> {
+    let privileges = match privileges {
+        Privileges::All { .. } => Privilege::ALL.to_vec(),
+        Privileges::Actions(actions) => actions
+            .iter()
+            .map(|action| match action {
+                Action::Select { columns: None } => Ok(Privilege::Select),
+                Action::Insert { columns: None } => Ok(Privilege::Insert),
+                Action::Update { columns: None } => Ok(Privilege::Update),
+                Action::Delete => Ok(Privilege::Delete),
+                Action::Create { .. } => Ok(Privilege::Create),
+                action => not_impl_err!("Unsupported privilege: {action}"),
+            })
+            .collect::<Result<_>>()?,
+    };
+    let objects: Vec<_> = match objects {
+        Some(GrantObjects::Tables(names)) => names
+            .iter()
+            .map(|name| PrivilegeObject::Table(object_table(name)))
+            .collect(),
+        Some(
+            GrantObjects::Schemas(names)
+            | GrantObjects::AllTablesInSchema { schemas: names },
+        ) => names
+            .iter()
+            .map(|name| PrivilegeObject::Schema(object_table(name).table().to_string()))
+            .collect(),
+        Some(objects) => {
+            return not_impl_err!("Unsupported privilege objects: {objects}");
+        }
+        None => return plan_err!("The objects of the privileges are missing"),
+    };
+    Ok(privileges
+        .into_iter()
+        .flat_map(|privilege| {
+            objects
+                .iter()
+                .map(move |object| (privilege, object.clone()))
+        })
+        .collect())
+}
+
+/// The role named by `grantee`
+fn grantee_role(grantee: &Grantee) -> Result<String> {
+    match (&grantee.grantee_type, &grantee.name) {
+        (GranteesType::Public, _) => Ok(PUBLIC_ROLE.to_string()),
+        (_, Some(GranteeName::ObjectName(name))) => {
+            Ok(object_table(name).table().to_string())
+        }
+        _ => not_impl_err!("Unsupported grantee: {grantee}"),
+    }
+}
+
+/// The table named by `name`, with its unquoted identifiers lowercased
+fn object_table(name: &ObjectName) -> TableReference {
+    TableReference::from(name.to_string())
+}
+
+/// The value of `ident`, lowercased unless it is quoted
+fn normalize_ident(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_ascii_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    /// A session of `user` that records its current role
+    #[derive(Debug)]
+    struct TestSession {
+        user: String,
+        role: Mutex<Option<String>>,
+    }
+
+    impl SessionProvider for TestSession {
+        fn session_id(&self) -> u64 {
+            1
+        }
+        fn timezone(&self) -> &str {
+            "UTC"
+        }
+        fn current_user(&self) -> &str {
+            &self.user
+        }
+        fn current_database(&self) -> &str {
+            ""
+        }
+        fn is_superuser(&self) -> bool {
+            false
+        }
+        fn active_txn_id(&self) -> Option<u64> {
+            None
+        }
+        fn search_path(&self) -> &[String] {
+            &[]
+        }
+        fn statement_deadline(&self) -> Option<Instant> {
+            None
+        }
+        fn session_var(&self, _name: &str) -> Option<String> {
+            None
+        }
+        fn set_session_var(
+            &self,
+            _name: &str,
+            _value: &str,
+            _is_local: bool,
+        ) -> Result<String> {
+            not_impl_err!("Session variables are not supported")
+        }
+        fn current_role(&self) -> String {
+            let role = self.role.lock().unwrap();
+            role.clone().unwrap_or_else(|| self.user.clone())
+        }
+        fn set_role(&self, set_role: &SetRole) -> Result<()> {
+            *self.role.lock().unwrap() = set_role.role.clone();
+            Ok(())
+        }
+    }
+
+    fn set_role(role: Option<&str>, session_authorization: bool) -> SetRole {
+        SetRole {
+            role: role.map(str::to_string),
+            session_authorization,
+            local: false,
+        }
+    }
+
+    #[test]
+    fn roles_inherit_privileges() {
+        let person = TableReference::full("datafusion", "public", "person");
+        let mut store = PrivilegeStore::new();
+        store.grant(
+            "readers",
+            Privilege::Select,
+            PrivilegeObject::Table(TableReference::bare("person")),
+        );
+        store.grant(
+            "writers",
+            Privilege::Insert,
+            PrivilegeObject::Schema("public".to_string()),
+        );
+        store.grant_role("readers", "analyst");
+        store.grant_role("analyst", "alice");
+
+        assert!(store.is_member("alice", "readers"));
+        assert!(store.is_member("alice", PUBLIC_ROLE));
+        assert!(!store.is_member("alice", "writers"));
+        assert!(store.has_privilege("alice", Privilege::Select, &person));
+        assert!(!store.has_privilege("alice", Privilege::Insert, &person));
+
+        store.grant_role("writers", PUBLIC_ROLE);
+        assert!(store.has_privilege("bob", Privilege::Insert, &person));
+
+        assert!(store.revoke_role("analyst", "alice"));
+        assert!(!store.has_privilege("alice", Privilege::Select, &person));
+        let err = store
+            .check("alice", Privilege::Select, &person)
+            .unwrap_err();
+        assert!(err.to_string().contains("SQLSTATE 42501"), "{err}");
+    }
+
+    #[test]
+    fn granting_requires_ownership_or_grant_option() {
+        let person = PrivilegeObject::Table(TableReference::bare("person"));
+        let public = PrivilegeObject::Schema("public".to_string());
+        let mut store = PrivilegeStore::new();
+        store.set_owner(public.clone(), "owner");
+        store.grant_role("owner", "alice");
+        store.grant_option("bob", Privilege::Select, person.clone());
+
+        assert!(store.can_grant("owner", Privilege::Delete, &person));
+        assert!(store.can_grant("alice", Privilege::Insert, &public));
+        assert!(store.can_grant("bob", Privilege::Select, &person));
+        assert!(!store.can_grant("bob", Privilege::Update, &person));
+        assert!(!store.can_grant("bob", Privilege::Select, &public));
+        assert!(!store.can_grant("carol", Privilege::Select, &person));
+
+        assert!(store.revoke_grant_option("bob", Privilege::Select, &person));
+        assert!(!store.can_grant("bob", Privilege::Select, &person));
+
+        store.grant_admin_option("readers", "alice");
+        assert!(store.can_grant_role("alice", "readers"));
+        assert!(!store.can_grant_role("alice", "writers"));
+        assert!(!store.can_grant_role("readers", "readers"));
+        assert!(store.revoke_admin_option("readers", "alice"));
+        assert!(!store.can_grant_role("alice", "readers"));
+    }
+
+    #[test]
+    fn set_role_requires_membership() {
+        let mut store = PrivilegeStore::new();
+        store.grant_role("analyst", "alice");
+        let session = TestSession {
+            user: "alice".to_string(),
+            role: Mutex::new(None),
+        };
+
+        store
+            .set_role(&session, &set_role(Some("analyst"), false))
+            .unwrap();
+        assert_eq!(session.current_role(), "analyst");
+
+        let err = store
+            .set_role(&session, &set_role(Some("admin"), false))
+            .unwrap_err();
+        assert!(err.to_string().contains("not a member"), "{err}");
+        let err = store
+            .set_role(&session, &set_role(Some("bob"), true))
+            .unwrap_err();
+        assert!(err.to_string().contains("session authorization"), "{err}");
+        assert_eq!(session.current_role(), "analyst");
+
+        store.set_role(&session, &set_role(None, false)).unwrap();
+        assert_eq!(session.current_role(), "alice");
+    }
+}
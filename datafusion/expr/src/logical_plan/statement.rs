@@ -24,6 +24,7 @@ use sqlparser::ast::{
 };
 use std::fmt::{self, Display};
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 
 use crate::{Expr, LogicalPlan, expr_vec_fmt};
 
@@ -60,6 +61,8 @@ pub enum Statement {
     GrantRole(GrantRole),
     /// REVOKE role
     RevokeRole(RevokeRole),
+    /// SET ROLE or SET SESSION AUTHORIZATION
+    SetRole(SetRole),
     /// Prepare a statement and find any bind parameters
     /// (e.g. `?`). This is used to implement SQL-prepared statements.
     Prepare(Prepare),
@@ -106,6 +109,7 @@ impl Statement {
             Statement::Revoke(_) => "Revoke",
             Statement::GrantRole(_) => "GrantRole",
             Statement::RevokeRole(_) => "RevokeRole",
+            Statement::SetRole(_) => "SetRole",
             Statement::Prepare(_) => "Prepare",
             Statement::Execute(_) => "Execute",
             Statement::Deallocate(_) => "Deallocate",
@@ -192,6 +196,17 @@ impl Statement {
                     Statement::RevokeRole(RevokeRole { roles, .. }) => {
                         write!(f, "RevokeRole: {}", roles.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(", "))
                     }
+                    Statement::SetRole(SetRole {
+                        role,
+                        session_authorization,
+                        local,
+                    }) => {
+                        write!(
+                            f,
+                            "SetRole: {} session_authorization:={session_authorization} local:={local}",
+                            role.as_deref().unwrap_or("NONE")
+                        )
+                    }
                     Statement::Prepare(Prepare { name, fields, .. }) => {
                         write!(
                             f,
@@ -232,8 +247,24 @@ impl Statement {
                     Statement::TruncateTable(TruncateTable { table_name }) => {
                         write!(f, "TruncateTable: {table_name}")
                     }
-                    Statement::Vacuum(Vacuum { table_name }) => {
-                        write!(f, "Vacuum: {:?}", table_name)
+                    Statement::Vacuum(Vacuum {
+                        table_name,
+                        options,
+                    }) => {
+                        write!(f, "Vacuum: {:?}", table_name)?;
+                        if !options.compact {
+                            write!(f, " DELETE ONLY")?;
+                        }
+                        if !options.purge_deleted {
+                            write!(f, " SORT ONLY")?;
+                        }
+                        if let Some(threshold) = options.threshold_percent {
+                            write!(f, " TO {threshold} PERCENT")?;
+                        }
+                        if let Some(retain) = options.retain {
+                            write!(f, " RETAIN {}s", retain.as_secs())?;
+                        }
+                        Ok(())
                     }
                     Statement::UseDatabase(UseDatabase { db_name }) => {
                         write!(f, "UseDatabase: {db_name}")
@@ -379,6 +410,21 @@ pub struct RevokeRole {
     /// If true, this is REVOKE ADMIN OPTION FOR
     pub admin_option_for: bool,
 }
+
+/// SET ROLE or SET SESSION AUTHORIZATION statement, changing the role whose
+/// privileges the statements of the session are checked against
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
+pub struct SetRole {
+    /// The role to change to, or `None` to change back to the session user
+    /// (`SET ROLE NONE`, `SET SESSION AUTHORIZATION DEFAULT`)
+    pub role: Option<String>,
+    /// If true, the session user changes to the role as well
+    /// (`SET SESSION AUTHORIZATION`)
+    pub session_authorization: bool,
+    /// If true, the change only lasts until the end of the current
+    /// transaction (`SET LOCAL`)
+    pub local: bool,
+}
 /// Prepare a statement but do not execute it. Prepare statements can have 0 or more
 /// `Expr::Placeholder` expressions that are filled in during execution
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
@@ -431,10 +477,46 @@ pub struct TruncateTable {
 }
 
 /// VACUUM statement.
+///
+/// Engines execute it by calling [`vacuum_table`] with the
+/// [`TableMaintenance`] of each table, if any.
+///
+/// [`vacuum_table`]: crate::table_maintenance::vacuum_table
+/// [`TableMaintenance`]: crate::table_maintenance::TableMaintenance
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
 pub struct Vacuum {
     /// Optional table name to vacuum (None means vacuum all).
     pub table_name: Option<String>,
+    /// What the vacuum does to the tables.
+    pub options: VacuumOptions,
+}
+
+/// The maintenance done by a VACUUM statement.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Hash)]
+pub struct VacuumOptions {
+    /// Whether the files of the table are compacted, and sorted for tables
+    /// with a sort order. False for `DELETE ONLY`.
+    pub compact: bool,
+    /// Whether deleted rows are purged from the files. False for
+    /// `SORT ONLY`.
+    pub purge_deleted: bool,
+    /// `TO n PERCENT`: the table is not compacted when at least this
+    /// percentage of its rows are already in compacted, sorted files.
+    pub threshold_percent: Option<u8>,
+    /// `RETAIN n HOURS`: the snapshots of the table older than this are
+    /// expired, and the files only they reference are removed.
+    pub retain: Option<Duration>,
+}
+
+impl Default for VacuumOptions {
+    fn default() -> Self {
+        Self {
+            compact: true,
+            purge_deleted: true,
+            threshold_percent: None,
+            retain: None,
+        }
+    }
 }
 
 /// USE DATABASE statement.
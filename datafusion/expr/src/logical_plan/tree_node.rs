@@ -38,12 +38,14 @@
 //! * [`LogicalPlan::expressions`]: Return a copy of the plan's expressions
 
 use crate::{
-    Aggregate, Analyze, CreateMaterializedView, CreateMemoryTable, CreateView, DdlStatement,
-    Distinct, DistinctOn, DmlStatement, Execute, Explain, Expr, Extension, Filter, Join, Limit,
-    LogicalPlan, MatchRecognize, Merge, MergeAction, MergeInsertKind, Partitioning, Prepare,
-    Projection, RecursiveQuery, Repartition, Sort, Statement, Subquery, SubqueryAlias,
-    TableScan, Union, Unnest, UserDefinedLogicalNode, Values, Window, dml::{CopyFrom, CopyTo},
-    logical_plan::plan::{GraphTable, JsonTable},
+    Aggregate, Analyze, CreateMaterializedView, CreateMemoryTable, CreateView,
+    CteReference, DdlStatement, Distinct, DistinctOn, DmlStatement, Execute, Explain,
+    Expr, Extension, Filter, Join, Limit, LogicalPlan, MatchRecognize, Merge,
+    Partitioning, PolymorphicTableFunction, Prepare, Projection, RecursiveQuery,
+    Repartition, Sample, Sort, Statement, Subquery, SubqueryAlias, TableFunctionArgument,
+    TableScan, Union, Unnest, UserDefinedLogicalNode, Values, Window,
+    dml::{CopyFrom, CopyTo},
+    logical_plan::plan::{GraphTable, JsonTable, XmlTable, XmlTableColumnDef},
 };
 use datafusion_common::tree_node::TreeNodeRefContainer;
 
@@ -151,6 +153,30 @@ impl TreeNode for LogicalPlan {
             LogicalPlan::Limit(Limit { skip, fetch, with_ties, input }) => input
                 .map_elements(f)?
                 .update_data(|input| LogicalPlan::Limit(Limit { skip, fetch, with_ties, input })),
+            LogicalPlan::Sample(Sample {
+                method,
+                size,
+                seed,
+                input,
+            }) => input.map_elements(f)?.update_data(|input| {
+                LogicalPlan::Sample(Sample {
+                    method,
+                    size,
+                    seed,
+                    input,
+                })
+            }),
+            LogicalPlan::CteReference(CteReference {
+                name,
+                input,
+                materialization,
+            }) => input.map_elements(f)?.update_data(|input| {
+                LogicalPlan::CteReference(CteReference {
+                    name,
+                    input,
+                    materialization,
+                })
+            }),
             LogicalPlan::Subquery(Subquery {
                 subquery,
                 outer_ref_columns,
@@ -269,17 +295,21 @@ impl TreeNode for LogicalPlan {
             ),
             LogicalPlan::CopyFrom(CopyFrom {
                 table_name,
-                source_url,
+                source,
                 columns,
                 file_type,
                 options,
+                column_exprs,
+                error_handling,
                 output_schema,
             }) => Transformed::no(LogicalPlan::CopyFrom(CopyFrom {
                 table_name,
-                source_url,
+                source,
                 columns,
                 file_type,
                 options,
+                column_exprs,
+                error_handling,
                 output_schema,
             })),
             LogicalPlan::Copy(CopyTo {
@@ -288,6 +318,7 @@ impl TreeNode for LogicalPlan {
                 partition_by,
                 file_type,
                 options,
+                partition_layout,
                 output_schema,
             }) => input.map_elements(f)?.update_data(|input| {
                 LogicalPlan::Copy(CopyTo {
@@ -296,6 +327,7 @@ impl TreeNode for LogicalPlan {
                     partition_by,
                     file_type,
                     options,
+                    partition_layout,
                     output_schema,
                 })
             }),
@@ -382,6 +414,9 @@ impl TreeNode for LogicalPlan {
                     | DdlStatement::DropProcedure(_)
                     | DdlStatement::CreateRole(_)
                     | DdlStatement::DropRole(_)
+                    | DdlStatement::CreateUser(_)
+                    | DdlStatement::AlterUser(_)
+                    | DdlStatement::DropUser(_)
                     | DdlStatement::CreatePropertyGraph(_)
                     | DdlStatement::DropPropertyGraph(_)
                     // SQL/MED statements have no child plans to transform
@@ -468,12 +503,36 @@ impl TreeNode for LogicalPlan {
                     schema,
                 })
             }),
+            LogicalPlan::PolymorphicTableFunction(PolymorphicTableFunction {
+                func,
+                args,
+                arg_names,
+                schema,
+            }) => {
+                let mut f = f;
+                args.into_iter()
+                    .map_until_stop_and_collect(|arg| match arg {
+                        TableFunctionArgument::Table(input) => input
+                            .map_elements(&mut f)?
+                            .update_data(TableFunctionArgument::Table),
+                        scalar => Ok(Transformed::no(scalar)),
+                    })?
+                    .update_data(|args| {
+                        LogicalPlan::PolymorphicTableFunction(PolymorphicTableFunction {
+                            func,
+                            args,
+                            arg_names,
+                            schema,
+                        })
+                    })
+            }
             // plans without inputs
             LogicalPlan::TableScan { .. }
             | LogicalPlan::EmptyRelation { .. }
             | LogicalPlan::Values { .. }
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::JsonTable(_)
+            | LogicalPlan::XmlTable(_)
             | LogicalPlan::GraphTable(_) => Transformed::no(self),
         })
     }
@@ -586,41 +645,8 @@ impl LogicalPlan {
                 }
                 _ => Ok(TreeNodeRecursion::Continue),
             },
-            LogicalPlan::Merge(merge) => {
-                f(&merge.on)?;
-                for clause in &merge.clauses {
-                    if let Some(predicate) = &clause.predicate {
-                        f(predicate)?;
-                    }
-                    match &clause.action {
-                        MergeAction::Insert(insert) => {
-                            if let MergeInsertKind::Values(rows) = &insert.kind {
-                                for row in rows {
-                                    for value in row {
-                                        f(value)?;
-                                    }
-                                }
-                            }
-                            if let Some(predicate) = &insert.insert_predicate {
-                                f(predicate)?;
-                            }
-                        }
-                        MergeAction::Update(update) => {
-                            for assignment in &update.assignments {
-                                f(&assignment.value)?;
-                            }
-                            if let Some(predicate) = &update.update_predicate {
-                                f(predicate)?;
-                            }
-                            if let Some(predicate) = &update.delete_predicate {
-                                f(predicate)?;
-                            }
-                        }
-                        MergeAction::Delete => {}
-                        MergeAction::DoNothing => {}
-                    }
-                }
-                Ok(TreeNodeRecursion::Continue)
+            LogicalPlan::Merge(Merge { on, clauses, .. }) => {
+                (on, clauses).apply_ref_elements(f)
             }
             LogicalPlan::MatchRecognize(MatchRecognize {
                 partition_by,
@@ -628,28 +654,30 @@ impl LogicalPlan {
                 measures,
                 symbols,
                 ..
-            }) => {
-                // Apply to partition by expressions
-                partition_by.apply_elements(&mut f)?;
-                // Apply to order by expressions (which are Sort expressions)
-                for sort_expr in order_by {
-                    f(&sort_expr.expr)?;
-                }
-                // Apply to measure expressions
-                for measure in measures {
-                    f(&measure.expr)?;
-                }
-                // Apply to symbol definitions
-                for symbol in symbols {
-                    f(&symbol.definition)?;
-                }
-                Ok(TreeNodeRecursion::Continue)
-            }
+            }) => (partition_by, order_by, measures, symbols).apply_ref_elements(f),
             LogicalPlan::JsonTable(JsonTable { json_expr, .. }) => {
                 // Apply to the JSON expression
                 f(json_expr)?;
                 Ok(TreeNodeRecursion::Continue)
             }
+            LogicalPlan::XmlTable(XmlTable {
+                passing, columns, ..
+            }) => {
+                // Apply to the PASSING arguments and the DEFAULT values
+                for argument in passing {
+                    f(&argument.expr)?;
+                }
+                for column in columns {
+                    if let XmlTableColumnDef::Path {
+                        default: Some(default),
+                        ..
+                    } = column
+                    {
+                        f(default)?;
+                    }
+                }
+                Ok(TreeNodeRecursion::Continue)
+            }
             LogicalPlan::GraphTable(GraphTable { where_clause, columns, .. }) => {
                 // Apply to the where clause if present
                 if let Some(where_expr) = where_clause {
@@ -661,11 +689,20 @@ impl LogicalPlan {
                 }
                 Ok(TreeNodeRecursion::Continue)
             }
+            LogicalPlan::PolymorphicTableFunction(ptf) => {
+                ptf.scalar_args().apply_ref_elements(f)
+            }
+            LogicalPlan::CopyFrom(CopyFrom { column_exprs, .. }) => column_exprs
+                .iter()
+                .map(|(_, expr)| expr)
+                .apply_until_stop(f),
             // plans without expressions
             LogicalPlan::EmptyRelation(_)
             | LogicalPlan::RecursiveQuery(_)
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::Sample(_)
+            | LogicalPlan::CteReference(_)
             | LogicalPlan::Analyze(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Union(_)
@@ -673,7 +710,6 @@ impl LogicalPlan {
             | LogicalPlan::Dml(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
-            | LogicalPlan::CopyFrom(_)
             | LogicalPlan::DescribeTable(_) => Ok(TreeNodeRecursion::Continue),
         }
     }
@@ -802,6 +838,10 @@ impl LogicalPlan {
                 fetch,
                 row_lock,
                 only,
+                version,
+                hints,
+                policies,
+                masks,
             }) => filters.map_elements(f)?.update_data(|filters| {
                 LogicalPlan::TableScan(TableScan {
                     table_name,
@@ -812,6 +852,10 @@ impl LogicalPlan {
                     fetch,
                     row_lock,
                     only,
+                    version,
+                    hints,
+                    policies,
+                    masks,
                 })
             }),
             LogicalPlan::Distinct(Distinct::On(DistinctOn {
@@ -845,30 +889,66 @@ impl LogicalPlan {
                 _ => Transformed::no(stmt),
             }
             .update_data(LogicalPlan::Statement),
-            LogicalPlan::Merge(merge) => {
-                let exprs = LogicalPlan::Merge(merge.clone()).expressions();
-                let exprs = exprs.map_elements(f)?;
-                let inputs = vec![
-                    Arc::unwrap_or_clone(Arc::clone(&merge.target)),
-                    Arc::unwrap_or_clone(Arc::clone(&merge.source)),
-                ];
-                let plan = LogicalPlan::Merge(merge)
-                    .with_new_exprs(exprs.data, inputs)?;
-                Transformed::new(plan, exprs.transformed, exprs.tnr)
-            }
-            LogicalPlan::MatchRecognize(mr) => {
-                let exprs = LogicalPlan::MatchRecognize(mr.clone()).expressions();
-                let exprs = exprs.map_elements(f)?;
-                let inputs = vec![Arc::unwrap_or_clone(Arc::clone(&mr.input))];
-                let plan = LogicalPlan::MatchRecognize(mr)
-                    .with_new_exprs(exprs.data, inputs)?;
-                Transformed::new(plan, exprs.transformed, exprs.tnr)
-            }
-            LogicalPlan::JsonTable(jt) => {
-                let exprs = LogicalPlan::JsonTable(jt.clone()).expressions();
-                let exprs = exprs.map_elements(f)?;
-                let plan = LogicalPlan::JsonTable(jt)
-                    .with_new_exprs(exprs.data, vec![])?;
+            LogicalPlan::Merge(Merge {
+                target_table,
+                target,
+                source,
+                on,
+                clauses,
+                output_schema,
+            }) => (on, clauses).map_elements(f)?.update_data(|(on, clauses)| {
+                LogicalPlan::Merge(Merge {
+                    target_table,
+                    target,
+                    source,
+                    on,
+                    clauses,
+                    output_schema,
+                })
+            }),
+            LogicalPlan::MatchRecognize(MatchRecognize {
+                input,
+                partition_by,
+                order_by,
+                measures,
+                rows_per_match,
+                after_match_skip,
+                pattern,
+                subsets,
+                symbols,
+                schema,
+            }) => (partition_by, order_by, measures, symbols)
+                .map_elements(f)?
+                .update_data(|(partition_by, order_by, measures, symbols)| {
+                    LogicalPlan::MatchRecognize(MatchRecognize {
+                        input,
+                        partition_by,
+                        order_by,
+                        measures,
+                        rows_per_match,
+                        after_match_skip,
+                        pattern,
+                        subsets,
+                        symbols,
+                        schema,
+                    })
+                }),
+            LogicalPlan::JsonTable(JsonTable {
+                json_expr,
+                json_path,
+                columns,
+                schema,
+            }) => f(json_expr)?.update_data(|json_expr| {
+                LogicalPlan::JsonTable(JsonTable {
+                    json_expr,
+                    json_path,
+                    columns,
+                    schema,
+                })
+            }),
+            LogicalPlan::XmlTable(xt) => {
+                let exprs = xt.expressions().map_elements(f)?;
+                let plan = LogicalPlan::XmlTable(xt.with_new_exprs(exprs.data)?);
                 Transformed::new(plan, exprs.transformed, exprs.tnr)
             }
             LogicalPlan::GraphTable(gt) => {
@@ -878,12 +958,35 @@ impl LogicalPlan {
                     .with_new_exprs(exprs.data, vec![])?;
                 Transformed::new(plan, exprs.transformed, exprs.tnr)
             }
+            LogicalPlan::PolymorphicTableFunction(ptf) => {
+                let exprs = ptf.scalar_args().into_iter().cloned().collect::<Vec<_>>();
+                let exprs = exprs.map_elements(f)?;
+                let inputs = ptf
+                    .inputs()
+                    .into_iter()
+                    .map(|input| Arc::unwrap_or_clone(Arc::clone(input)))
+                    .collect();
+                let plan = LogicalPlan::PolymorphicTableFunction(
+                    ptf.with_new_exprs(exprs.data, inputs)?,
+                );
+                Transformed::new(plan, exprs.transformed, exprs.tnr)
+            }
+            LogicalPlan::CopyFrom(mut copy) => {
+                let (names, exprs): (Vec<_>, Vec<_>) =
+                    std::mem::take(&mut copy.column_exprs).into_iter().unzip();
+                exprs.map_elements(f)?.update_data(|exprs| {
+                    copy.column_exprs = names.into_iter().zip(exprs).collect();
+                    LogicalPlan::CopyFrom(copy)
+                })
+            }
             // plans without expressions
             LogicalPlan::EmptyRelation(_)
             | LogicalPlan::Unnest(_)
             | LogicalPlan::RecursiveQuery(_)
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::Sample(_)
+            | LogicalPlan::CteReference(_)
             | LogicalPlan::Analyze(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Union(_)
@@ -891,7 +994,6 @@ impl LogicalPlan {
             | LogicalPlan::Dml(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
-            | LogicalPlan::CopyFrom(_)
             | LogicalPlan::DescribeTable(_) => Transformed::no(self),
         })
     }
@@ -17,25 +17,76 @@
 
 //! [`ContextProvider`] and [`ExprPlanner`] APIs to customize SQL query planning
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::expr::NullTreatment;
 
-use crate::logical_plan::LogicalPlan;
+use crate::logical_plan::{CreatePropertyGraph, LogicalPlan, Privilege, PrivilegeStore};
 use crate::{
-    AggregateUDF, Expr, GetFieldAccess, ScalarUDF, SortExpr, TableSource, WindowFrame,
-    WindowFunctionDefinition, WindowUDF,
+    AggregateUDF, Expr, GetFieldAccess, PolymorphicTableFunctionImpl, ScalarUDF,
+    SortExpr, TableSource, WindowFrame, WindowFunctionDefinition, WindowUDF,
 };
 use arrow::datatypes::{DataType, Field, FieldRef, SchemaRef};
+use async_trait::async_trait;
 use datafusion_common::datatype::DataTypeExt;
 use datafusion_common::{
     DFSchema, Result, TableReference, config::ConfigOptions,
     file_options::file_type::FileType, not_impl_err,
 };
 
-use sqlparser::ast::{Expr as SQLExpr, Ident, ObjectName, TableAlias, TableFactor};
+use sqlparser::ast::{
+    Expr as SQLExpr, Ident, ObjectName, Statement, TableAlias, TableFactor,
+};
+
+/// The field metadata key of the collation of a character string column or
+/// expression, as given by `COLLATE`
+pub const COLLATION_METADATA_KEY: &str = "collation";
+
+/// The field metadata key of the declared character set of a character
+/// string column or expression, such as [`NATIONAL_CHARACTER_SET`] for
+/// `NCHAR` and `NVARCHAR`
+pub const CHARACTER_SET_METADATA_KEY: &str = "character_set";
+
+/// The field metadata key of the declared maximum length, in characters, of
+/// a character string column or expression
+pub const CHARACTER_LENGTH_METADATA_KEY: &str = "character_length";
+
+/// The value of [`CHARACTER_SET_METADATA_KEY`] for the national character
+/// set of `NCHAR`, `NVARCHAR` and `NCLOB`
+pub const NATIONAL_CHARACTER_SET: &str = "national";
+
+/// The field metadata key of the fields of an interval column or expression,
+/// such as `DAY TO SECOND`
+pub const INTERVAL_FIELDS_METADATA_KEY: &str = "interval_fields";
+
+/// The field metadata key of the fractional seconds precision of an interval
+/// column or expression, such as `3` for `INTERVAL DAY TO SECOND(3)`
+pub const INTERVAL_PRECISION_METADATA_KEY: &str = "interval_precision";
+
+/// The field metadata key of the bounds of the system time period of a
+/// system-versioned table, with the value [`SYSTEM_TIME_ROW_START`] for its
+/// `GENERATED ALWAYS AS ROW START` column and [`SYSTEM_TIME_ROW_END`] for its
+/// `GENERATED ALWAYS AS ROW END` column
+pub const SYSTEM_TIME_METADATA_KEY: &str = "system_time";
+
+/// The value of [`SYSTEM_TIME_METADATA_KEY`] for the column of the time from
+/// which a row version is current
+pub const SYSTEM_TIME_ROW_START: &str = "row_start";
+
+/// The value of [`SYSTEM_TIME_METADATA_KEY`] for the column of the time from
+/// which a row version is no longer current
+pub const SYSTEM_TIME_ROW_END: &str = "row_end";
+
+/// The field metadata key of the start column of an application-time period,
+/// declared by `PERIOD FOR name (start, end)`, with the name of the period as
+/// its value
+pub const PERIOD_START_METADATA_KEY: &str = "period_start";
+
+/// The field metadata key of the end column of an application-time period,
+/// with the name of the period as its value
+pub const PERIOD_END_METADATA_KEY: &str = "period_end";
 
 /// Provides the `SQL` query planner meta-data about tables and
 /// functions referenced in SQL statements, without a direct dependency on the
@@ -46,6 +97,24 @@ pub trait ContextProvider {
     /// Returns a table by reference, if it exists
     fn get_table_source(&self, name: TableReference) -> Result<Arc<dyn TableSource>>;
 
+    /// Returns the tables of `names`, in order, as
+    /// [`Self::get_table_source`] would
+    ///
+    /// The SQL planner calls this once per statement with the tables and
+    /// views that the statement references, so that a remote catalog can
+    /// return them in a single request. The planner calls
+    /// [`Self::get_table_source`] for the tables that are not returned. The
+    /// default implementation calls [`Self::get_table_source`] for each name.
+    fn get_table_sources(
+        &self,
+        names: &[TableReference],
+    ) -> Vec<Result<Arc<dyn TableSource>>> {
+        names
+            .iter()
+            .map(|name| self.get_table_source(name.clone()))
+            .collect()
+    }
+
     /// Return the type of a file based on its extension (e.g. `.parquet`)
     ///
     /// This is used to plan `COPY` statements
@@ -53,6 +122,22 @@ pub trait ContextProvider {
         not_impl_err!("Registered file types are not supported")
     }
 
+    /// Returns a table by reference as it existed at `version`, for time
+    /// travel queries such as `SELECT * FROM t FOR SYSTEM_TIME AS OF <ts>`,
+    /// `SELECT * FROM t AT(TIMESTAMP => <ts>)` and
+    /// `CREATE TABLE t2 CLONE t AT(...)`
+    ///
+    /// Providers backed by versioned storage (e.g. Delta Lake or Iceberg)
+    /// can return a source that reads the matching snapshot. The default
+    /// implementation rejects all time travel queries.
+    fn get_table_source_at(
+        &self,
+        name: TableReference,
+        version: &TableVersion,
+    ) -> Result<Arc<dyn TableSource>> {
+        not_impl_err!("Time travel queries are not supported: {name} {version}")
+    }
+
     /// Getter for a table function
     fn get_table_function_source(
         &self,
@@ -62,6 +147,125 @@ pub trait ContextProvider {
         not_impl_err!("Table Functions are not supported")
     }
 
+    /// Getter for a table function called with named arguments, such as
+    /// `read_csv(path => 'data.csv', header => true)`
+    ///
+    /// `arg_names` holds the normalized name of each argument in `args`, or
+    /// `None` for positional arguments. The default implementation rejects
+    /// named arguments and otherwise calls
+    /// [`Self::get_table_function_source`].
+    fn get_table_function_source_with_names(
+        &self,
+        name: &str,
+        args: Vec<Expr>,
+        arg_names: Vec<Option<String>>,
+    ) -> Result<Arc<dyn TableSource>> {
+        if arg_names.iter().any(Option::is_some) {
+            return not_impl_err!(
+                "Table function {name} does not support named arguments"
+            );
+        }
+        self.get_table_function_source(name, args)
+    }
+
+    /// Return the polymorphic table function with a given name, if any.
+    ///
+    /// Polymorphic table functions are called as `TABLE(func(...))` and may
+    /// take table arguments; see [`PolymorphicTableFunctionImpl`].
+    fn get_polymorphic_table_function(
+        &self,
+        _name: &str,
+    ) -> Option<Arc<dyn PolymorphicTableFunctionImpl>> {
+        None
+    }
+
+    /// Return the definition of the property graph `name`, as declared by
+    /// `CREATE PROPERTY GRAPH`, if it exists.
+    ///
+    /// `GRAPH_TABLE` queries over a graph returned here are planned as
+    /// joins of the graph's vertex and edge tables. Queries over other
+    /// graphs produce a [`LogicalPlan::GraphTable`] node instead.
+    ///
+    /// Engines executing a `CREATE PROPERTY GRAPH` plan should register its
+    /// definition here. The planner has already checked it against the
+    /// graph's element tables and filled in vertex keys omitted in favor of
+    /// a primary key. It also rejects creating a graph returned here unless
+    /// `OR REPLACE` or `IF NOT EXISTS` is given.
+    fn get_property_graph(
+        &self,
+        _name: &TableReference,
+    ) -> Option<Arc<CreatePropertyGraph>> {
+        None
+    }
+
+    /// Return the sort key function of the collation `name`, if it exists.
+    ///
+    /// Character strings with this collation, given by `COLLATE name` or by
+    /// the collation of their column, are compared and ordered by the
+    /// result of the function, which maps a string to a key whose binary
+    /// order is the order of the collation; for example `lower` for a
+    /// case-insensitive collation. The binary collations `C`, `POSIX` and
+    /// `UCS_BASIC` are always available and need no function.
+    fn get_collation(&self, _name: &str) -> Option<Arc<ScalarUDF>> {
+        None
+    }
+
+    /// Return the function converting character strings to the character
+    /// set `name`, if it exists.
+    ///
+    /// `CONVERT(expr USING name)` is planned as a call of this function on
+    /// `expr`, which may be a character or a binary string. The function
+    /// returns a `Utf8` string holding the characters representable in the
+    /// character set, or fails on the others. `UTF8` and `UTF8MB4` are always
+    /// available and need no function.
+    fn get_character_set(&self, _name: &str) -> Option<Arc<ScalarUDF>> {
+        None
+    }
+
+    /// Return the row-level security policies of the table `name`, a fully
+    /// qualified table reference.
+    ///
+    /// Every scan of the table is filtered by the predicates of its policies,
+    /// so that queries only see the rows satisfying all of them. The policies
+    /// are listed by `EXPLAIN` on the scan.
+    fn get_row_security_policies(
+        &self,
+        _name: &TableReference,
+    ) -> Vec<RowSecurityPolicy> {
+        vec![]
+    }
+
+    /// Return the masks of the columns of the table `name`, a fully qualified
+    /// table reference.
+    ///
+    /// Every scan of the table replaces the values of the masked columns by
+    /// the expressions of their masks, such as `hash(email)` for sessions that
+    /// may not see the email addresses, so the original values are not
+    /// available to the query, not even through `SELECT *`. The masked
+    /// columns are listed by `EXPLAIN` on the scan.
+    fn get_column_masks(&self, _name: &TableReference) -> Vec<ColumnMask> {
+        vec![]
+    }
+
+    /// Return the role that statements are planned for.
+    ///
+    /// When both the role and the [`Self::get_privilege_store`] are provided,
+    /// the planner checks that the role has the privileges that the plan of
+    /// each statement requires, as returned by
+    /// [`LogicalPlan::required_privileges`], and fails with the
+    /// [`INSUFFICIENT_PRIVILEGE`] SQLSTATE otherwise.
+    ///
+    /// [`INSUFFICIENT_PRIVILEGE`]: crate::INSUFFICIENT_PRIVILEGE
+    fn get_current_role(&self) -> Option<String> {
+        None
+    }
+
+    /// Return the privileges granted to roles, which the
+    /// [`Self::get_current_role`] is checked against
+    fn get_privilege_store(&self) -> Option<Arc<PrivilegeStore>> {
+        None
+    }
+
     /// Provides an intermediate table that is used to store the results of a CTE during execution
     ///
     /// CTE stands for "Common Table Expression"
@@ -95,6 +299,23 @@ pub trait ContextProvider {
         &[]
     }
 
+    /// Return [`StatementPlanner`] extensions for planning statements
+    fn get_statement_planners(&self) -> &[Arc<dyn StatementPlanner>] {
+        &[]
+    }
+
+    /// Return [`StatementRewriter`] extensions that rewrite SQL statements
+    /// before they are planned, in the order they are applied
+    fn get_statement_rewriters(&self) -> &[Arc<dyn StatementRewriter>] {
+        &[]
+    }
+
+    /// Return the [`AuditLogger`]s recording the statements planned by the
+    /// SQL planner, in the order they are called
+    fn get_audit_loggers(&self) -> &[Arc<dyn AuditLogger>] {
+        &[]
+    }
+
     /// Return [`TypePlanner`] extensions for planning data types
     
     fn get_type_planner(&self) -> Option<Arc<dyn TypePlanner>> {
@@ -137,12 +358,88 @@ pub trait ContextProvider {
 
     /// Return all window function names
     fn udwf_names(&self) -> Vec<String>;
+
+    /// Return the names of the tables that can be referenced, used to
+    /// suggest a table when a table name cannot be resolved
+    fn table_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Return how the planner treats the unquoted identifier `word`, in
+    /// addition to the keywords of the SQL dialect, or `None` to keep the
+    /// default treatment. `word` is given as written in the query.
+    fn word_reservation(&self, _word: &str) -> Option<WordReservation> {
+        None
+    }
+}
+
+/// Provides the `SQL` query planner the tables, functions and file types
+/// referenced in SQL statements asynchronously, such as from a remote
+/// catalog.
+///
+/// The planner resolves the references of a statement with this provider
+/// before planning it, so that no catalog is accessed while planning. See
+/// `AsyncSqlToRel` in the `datafusion-sql` crate.
+#[async_trait]
+pub trait AsyncContextProvider: Send + Sync {
+    /// Returns the provider of the configuration, planners, variables and
+    /// everything else the planner needs that is not resolved by this
+    /// provider, such as the functions that DataFusion plans its own syntax
+    /// with. Its [`ContextProvider::get_table_source`] is not called.
+    fn context_provider(&self) -> &dyn ContextProvider;
+
+    /// Returns a table by reference, if it exists
+    async fn get_table_source(
+        &self,
+        name: TableReference,
+    ) -> Result<Arc<dyn TableSource>>;
+
+    /// Return the scalar function with a given name, if any
+    async fn get_function_meta(&self, name: &str) -> Result<Option<Arc<ScalarUDF>>> {
+        Ok(self.context_provider().get_function_meta(name))
+    }
+
+    /// Return the aggregate function with a given name, if any
+    async fn get_aggregate_meta(&self, name: &str) -> Result<Option<Arc<AggregateUDF>>> {
+        Ok(self.context_provider().get_aggregate_meta(name))
+    }
+
+    /// Return the window function with a given name, if any
+    async fn get_window_meta(&self, name: &str) -> Result<Option<Arc<WindowUDF>>> {
+        Ok(self.context_provider().get_window_meta(name))
+    }
+
+    /// Return the type of a file based on its extension (e.g. `.parquet`)
+    async fn get_file_type(&self, ext: &str) -> Result<Arc<dyn FileType>> {
+        self.context_provider().get_file_type(ext)
+    }
+}
+
+/// How the SQL planner treats a word, as returned by
+/// [`ContextProvider::word_reservation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordReservation {
+    /// The word cannot be used as an unquoted identifier, such as a column,
+    /// alias or table name
+    Reserved,
+    /// The word is an identifier, even where the dialect parses it as a
+    /// function called without parentheses, such as `current_user`
+    Unreserved,
 }
 
 /// Customize planning of SQL AST expressions to [`Expr`]s
 pub trait ExprPlanner: Debug + Send + Sync {
     /// Plan the binary operation between two expressions, returns original
     /// BinaryExpr if not possible
+    ///
+    /// This is called for every binary operator before DataFusion's own
+    /// mapping, including operators DataFusion does not support. `schema` is
+    /// the schema of the operands, so that an operator can be planned by
+    /// their types. For example, the JSON operators `->`, `->>`, `#>` and
+    /// `#>>` are planned as [`Operator::Arrow`] and the like unless a planner
+    /// plans them as calls of JSON functions.
+    ///
+    /// [`Operator::Arrow`]: crate::Operator::Arrow
     fn plan_binary_op(
         &self,
         expr: RawBinaryExpr,
@@ -359,6 +656,150 @@ pub struct RawWindowExpr {
     pub distinct: bool,
 }
 
+/// Historical version of a table requested by a time travel query, passed
+/// to [`ContextProvider::get_table_source_at`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum TableVersion {
+    /// `FOR SYSTEM_TIME AS OF <expr>`: the table as it was at the given
+    /// point. The expression is usually a timestamp, but providers that
+    /// number their snapshots may also accept an integer version.
+    AsOf(Expr),
+    /// `AT(<point>)`: the table as it was at the given point, including
+    /// changes made by the statement named by [`SnapshotPoint::Statement`]
+    At(SnapshotPoint),
+    /// `BEFORE(<point>)`: the table as it was just before the given point,
+    /// excluding changes made by the statement named by
+    /// [`SnapshotPoint::Statement`]
+    Before(SnapshotPoint),
+}
+
+impl std::fmt::Display for TableVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableVersion::AsOf(expr) => write!(f, "FOR SYSTEM_TIME AS OF {expr}"),
+            TableVersion::At(point) => write!(f, "AT({point})"),
+            TableVersion::Before(point) => write!(f, "BEFORE({point})"),
+        }
+    }
+}
+
+/// Point in a table's history named by an `AT(...)` or `BEFORE(...)` clause
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum SnapshotPoint {
+    /// `TIMESTAMP => <expr>`: a point in time
+    Timestamp(Expr),
+    /// `OFFSET => <expr>`: a number of seconds relative to the current time,
+    /// usually negative
+    Offset(Expr),
+    /// `STATEMENT => <expr>`: the id of a query or transaction
+    Statement(Expr),
+    /// `STREAM => <expr>`: the current offset of a change stream
+    Stream(Expr),
+}
+
+impl std::fmt::Display for SnapshotPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotPoint::Timestamp(expr) => write!(f, "TIMESTAMP => {expr}"),
+            SnapshotPoint::Offset(expr) => write!(f, "OFFSET => {expr}"),
+            SnapshotPoint::Statement(expr) => write!(f, "STATEMENT => {expr}"),
+            SnapshotPoint::Stream(expr) => write!(f, "STREAM => {expr}"),
+        }
+    }
+}
+
+/// A row-level security policy of a table, returned by
+/// [`ContextProvider::get_row_security_policies`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RowSecurityPolicy {
+    /// The name of the policy
+    pub name: String,
+    /// The SQL predicate that the visible rows satisfy, such as
+    /// `owner = @user`. It may reference the columns of the table, unqualified
+    /// or qualified by its name, and session variables, which are read when
+    /// the query is executed.
+    pub predicate: String,
+}
+
+impl RowSecurityPolicy {
+    pub fn new(name: impl Into<String>, predicate: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            predicate: predicate.into(),
+        }
+    }
+}
+
+/// The row-level security policies of tables, for implementing
+/// [`ContextProvider::get_row_security_policies`]
+#[derive(Debug, Clone, Default)]
+pub struct RowSecurityPolicyRegistry {
+    policies: BTreeMap<TableReference, Vec<RowSecurityPolicy>>,
+}
+
+impl RowSecurityPolicyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `policy` to the policies of `table`, replacing its policy of the
+    /// same name
+    pub fn add_policy(
+        &mut self,
+        table: impl Into<TableReference>,
+        policy: RowSecurityPolicy,
+    ) {
+        let policies = self.policies.entry(table.into()).or_default();
+        policies.retain(|existing| existing.name != policy.name);
+        policies.push(policy);
+    }
+
+    /// Removes the policy `name` of `table`, returning it if it existed
+    pub fn remove_policy(
+        &mut self,
+        table: &TableReference,
+        name: &str,
+    ) -> Option<RowSecurityPolicy> {
+        let policies = self.policies.get_mut(table)?;
+        let index = policies.iter().position(|policy| policy.name == name)?;
+        Some(policies.remove(index))
+    }
+
+    /// Returns the policies of `table`, in the order they were added, which
+    /// include the policies added for the same table under a reference with
+    /// fewer or more parts, such as `t` for `datafusion.public.t`
+    pub fn policies(&self, table: &TableReference) -> Vec<RowSecurityPolicy> {
+        self.policies
+            .iter()
+            .filter(|(policy_table, _)| {
+                policy_table.resolved_eq(table) || table.resolved_eq(policy_table)
+            })
+            .flat_map(|(_, policies)| policies.iter().cloned())
+            .collect()
+    }
+}
+
+/// The mask of a column of a table, returned by
+/// [`ContextProvider::get_column_masks`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnMask {
+    /// The name of the masked column
+    pub column: String,
+    /// The SQL expression whose value replaces the value of the column, such
+    /// as `hash(email)`. It may reference the columns of the table, unqualified
+    /// or qualified by its name, and session variables.
+    pub expression: String,
+}
+
+impl ColumnMask {
+    pub fn new(column: impl Into<String>, expression: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            expression: expression.into(),
+        }
+    }
+}
+
 /// Result of planning a raw expr with [`ExprPlanner`]
 #[derive(Debug, Clone)]
 pub enum PlannerResult<T> {
@@ -447,6 +888,111 @@ pub trait RelationPlannerContext {
     fn object_name_to_table_reference(&self, name: ObjectName) -> Result<TableReference>;
 }
 
+/// Result of attempting to plan a statement with extension planners
+#[derive(Debug)]
+pub enum StatementPlanning {
+    /// The statement was successfully planned by an extension planner
+    Planned(LogicalPlan),
+    /// No extension planner handled the statement, return it for default processing
+    Original(Statement),
+}
+
+/// Customize planning SQL statements to [`LogicalPlan`]s, for example to plan
+/// custom DDL or utility statements into [`LogicalPlan::Extension`] nodes.
+pub trait StatementPlanner: Debug + Send + Sync {
+    /// Plan a statement into a [`LogicalPlan`].
+    ///
+    /// Returning [`StatementPlanning::Planned`] short-circuits further planning and uses the
+    /// provided plan. Returning [`StatementPlanning::Original`] allows the next registered
+    /// planner, or DataFusion's default logic, to handle the statement.
+    fn plan_statement(
+        &self,
+        statement: Statement,
+        context: &mut dyn StatementPlannerContext,
+    ) -> Result<StatementPlanning>;
+}
+
+/// Provides utilities for statement planners to interact with DataFusion's
+/// SQL planner, such as planning the queries and expressions of a statement.
+pub trait StatementPlannerContext {
+    /// Provides access to the underlying context provider for reading session
+    /// configuration, accessing tables, functions, and other metadata.
+    fn context_provider(&self) -> &dyn ContextProvider;
+
+    /// Plans the specified statement through the full planner pipeline,
+    /// starting from the first registered statement planner. A planner
+    /// calling this with the statement it was given must not handle it again.
+    fn plan(&mut self, statement: Statement) -> Result<LogicalPlan>;
+
+    /// Converts a SQL expression into a logical expression using the current
+    /// planner context.
+    fn sql_to_expr(&mut self, expr: SQLExpr, schema: &DFSchema) -> Result<Expr>;
+
+    /// Normalizes an identifier according to session settings.
+    fn normalize_ident(&self, ident: Ident) -> String;
+
+    /// Normalizes a SQL object name into a [`TableReference`].
+    fn object_name_to_table_reference(&self, name: ObjectName) -> Result<TableReference>;
+}
+
+/// Rewrite SQL statements before they are planned to [`LogicalPlan`]s, for
+/// example to expand macros, to qualify table names with the schema of a
+/// tenant, or to add filters to the queries of some tables.
+///
+/// The rewriters returned by [`ContextProvider::get_statement_rewriters`]
+/// are applied in order, each to the statement returned by the previous one.
+/// They run once per statement given to the SQL planner, which does not
+/// rewrite the statements it plans on its behalf, such as the inner
+/// statement of an `EXPLAIN` or the query that a `SHOW` statement stands for.
+pub trait StatementRewriter: Debug + Send + Sync {
+    /// Rewrite `statement`, or return it unmodified.
+    ///
+    /// `context_provider` gives access to the session configuration, tables
+    /// and functions.
+    fn rewrite_statement(
+        &self,
+        statement: Statement,
+        context_provider: &dyn ContextProvider,
+    ) -> Result<Statement>;
+}
+
+/// A statement planned by the SQL planner, as recorded by the
+/// [`AuditLogger`]s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// The kind of the statement: `Query` for queries, otherwise the name
+    /// of its plan, such as `Insert Into`, `CreateView` or `SetVariable`
+    pub kind: String,
+    /// The tables and views the statement references, fully qualified, with
+    /// the privilege their access requires, as returned by
+    /// [`LogicalPlan::required_privileges`]
+    pub objects: Vec<(Privilege, TableReference)>,
+    /// The SQL text of the statement, with its literals replaced by
+    /// placeholders, such as `SELECT name FROM users WHERE id = $1`, so that
+    /// the values it holds are not recorded, or [`REDACTED_SQL`] when its
+    /// literals can not be told apart
+    pub sql: String,
+}
+
+/// The [`AuditRecord::sql`] of the statements whose text can not be
+/// parameterized
+pub const REDACTED_SQL: &str = "<redacted>";
+
+/// Record the statements planned by the SQL planner, for example to keep
+/// the audit trail of the queries and DDL run on a database.
+///
+/// The loggers returned by [`ContextProvider::get_audit_loggers`] are called
+/// once per statement given to the SQL planner, after the statement is
+/// planned and the privileges it requires are checked, including when a
+/// [`PlanCache`] returns the cached plan of a query.
+///
+/// [`PlanCache`]: https://docs.rs/datafusion-sql/latest/datafusion_sql/plan_cache/struct.PlanCache.html
+pub trait AuditLogger: Debug + Send + Sync {
+    /// Record `record`. Returning an error fails the statement, so that no
+    /// statement runs without being recorded.
+    fn log_statement(&self, record: &AuditRecord) -> Result<()>;
+}
+
 /// Customize planning SQL types to DataFusion (Arrow) types.
 
 pub trait TypePlanner: Debug + Send + Sync {
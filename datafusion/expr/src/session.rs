@@ -34,7 +34,10 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use std::time::Instant;
 
-use datafusion_common::Result;
+use datafusion_common::{Result, not_impl_err};
+
+use crate::copy_progress::CopyProgressListener;
+use crate::logical_plan::SetRole;
 
 /// Ambient per-session context available to scalar UDFs at invoke time.
 ///
@@ -61,6 +64,22 @@ pub trait SessionProvider: Debug + Send + Sync {
     /// Authenticated role name for the session.
     fn current_user(&self) -> &str;
 
+    /// Role whose privileges the statements of the session are checked
+    /// against, which `SET ROLE` changes. Defaults to [`Self::current_user`].
+    fn current_role(&self) -> String {
+        self.current_user().to_string()
+    }
+
+    /// Apply a `SET ROLE` or `SET SESSION AUTHORIZATION` statement to the
+    /// session, changing its [`Self::current_role`]. Callers are expected to
+    /// check that the session may change to the role first, e.g. with
+    /// [`PrivilegeStore::set_role`].
+    ///
+    /// [`PrivilegeStore::set_role`]: crate::PrivilegeStore::set_role
+    fn set_role(&self, _set_role: &SetRole) -> Result<()> {
+        not_impl_err!("SET ROLE is not supported by this session")
+    }
+
     /// Name of the database the session is connected to.
     fn current_database(&self) -> &str;
 
@@ -98,6 +117,12 @@ pub trait SessionProvider: Debug + Send + Sync {
     fn runtime_ctx_ptr(&self) -> *mut () {
         std::ptr::null_mut()
     }
+
+    /// Listener notified of the progress of the `COPY TO` and `COPY FROM`
+    /// statements run on the session, if any.
+    fn copy_progress_listener(&self) -> Option<Arc<dyn CopyProgressListener>> {
+        None
+    }
 }
 
 /// A no-op `SessionProvider` useful for tests, ad-hoc expression evaluation
@@ -0,0 +1,61 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PolymorphicTableFunctionImpl`]: table functions that take tables as
+//! arguments and compute their output schema while planning
+
+use std::fmt::Debug;
+
+use arrow::datatypes::Schema;
+use datafusion_common::Result;
+
+use crate::logical_plan::TableFunctionArgument;
+
+/// A polymorphic table function (PTF) in the sense of SQL:2016.
+///
+/// Unlike the table functions resolved through
+/// [`ContextProvider::get_table_function_source`], a PTF may take whole
+/// tables as arguments and its row type is not fixed: it is computed by
+/// [`Self::describe`] from the arguments of each call. This is the
+/// extension point for operators such as sessionization or custom
+/// windowing, invoked as
+///
+/// ```sql
+/// SELECT * FROM TABLE(sessionize(TABLE clicks, 30))
+/// ```
+///
+/// The planner produces a [`LogicalPlan::PolymorphicTableFunction`] node;
+/// executing it is up to the engine that registered the function.
+///
+/// [`ContextProvider::get_table_function_source`]: crate::planner::ContextProvider::get_table_function_source
+/// [`LogicalPlan::PolymorphicTableFunction`]: crate::LogicalPlan::PolymorphicTableFunction
+pub trait PolymorphicTableFunctionImpl: Debug + Send + Sync {
+    /// Returns this function's name
+    fn name(&self) -> &str;
+
+    /// Describe phase: validate the arguments of a call and return the
+    /// schema of the rows it produces.
+    ///
+    /// `arg_names` holds the normalized name of each argument in `args`, or
+    /// `None` for positional arguments. Table arguments expose their input
+    /// plan, so the output may be derived from the input columns.
+    fn describe(
+        &self,
+        args: &[TableFunctionArgument],
+        arg_names: &[Option<String>],
+    ) -> Result<Schema>;
+}
@@ -0,0 +1,117 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Table maintenance run by `VACUUM`
+//!
+//! A [`TableSource`] whose storage needs maintenance, such as a table
+//! format writing a new file for each insert and marking deleted rows
+//! instead of rewriting files, returns a [`TableMaintenance`] from
+//! [`TableSource::maintenance`]. Engines executing a [`Vacuum`] call
+//! [`vacuum_table`] for each vacuumed table, which dispatches to the
+//! maintenance operations selected by the [`VacuumOptions`] of the
+//! statement.
+//!
+//! [`Vacuum`]: crate::Vacuum
+
+use std::fmt::Debug;
+use std::ops::AddAssign;
+use std::time::Duration;
+
+use crate::{TableSource, VacuumOptions};
+use async_trait::async_trait;
+use datafusion_common::Result;
+
+/// The work done by the maintenance of a table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    /// Files removed from the table, because they were compacted or only
+    /// referenced by expired snapshots
+    pub files_removed: u64,
+    /// Files written by compaction
+    pub files_added: u64,
+    /// Deleted rows purged from the files
+    pub rows_purged: u64,
+    /// Snapshots expired
+    pub snapshots_expired: u64,
+}
+
+impl AddAssign for MaintenanceReport {
+    fn add_assign(&mut self, other: Self) {
+        self.files_removed += other.files_removed;
+        self.files_added += other.files_added;
+        self.rows_purged += other.rows_purged;
+        self.snapshots_expired += other.snapshots_expired;
+    }
+}
+
+/// The maintenance operations of the storage of a table, run by `VACUUM`.
+///
+/// Operations a table does not need are left to their default, which does
+/// nothing.
+#[async_trait]
+pub trait TableMaintenance: Debug + Send + Sync {
+    /// Rewrites the small files of the table into fewer, larger files,
+    /// sorted by the sort order of the table if it has one.
+    ///
+    /// `threshold_percent` is the `TO n PERCENT` of the statement: nothing
+    /// needs to be done when at least this percentage of the rows are
+    /// already in compacted files.
+    async fn compact_files(
+        &self,
+        _threshold_percent: Option<u8>,
+    ) -> Result<MaintenanceReport> {
+        Ok(MaintenanceReport::default())
+    }
+
+    /// Rewrites the files containing deleted rows without them
+    async fn purge_deleted_rows(&self) -> Result<MaintenanceReport> {
+        Ok(MaintenanceReport::default())
+    }
+
+    /// Expires the snapshots of the table older than `retain`, and removes
+    /// the files that are not referenced by the remaining snapshots
+    async fn expire_snapshots(&self, _retain: Duration) -> Result<MaintenanceReport> {
+        Ok(MaintenanceReport::default())
+    }
+}
+
+/// Runs the maintenance selected by `options` on the table `source`,
+/// returning what was done. Tables without [`TableMaintenance`] need no
+/// maintenance.
+///
+/// Deleted rows are purged before the files are compacted, so that
+/// compaction does not rewrite them, and snapshots are expired last, so
+/// that the files replaced by the other operations can be removed.
+pub async fn vacuum_table(
+    source: &dyn TableSource,
+    options: &VacuumOptions,
+) -> Result<MaintenanceReport> {
+    let mut report = MaintenanceReport::default();
+    let Some(maintenance) = source.maintenance() else {
+        return Ok(report);
+    };
+    if options.purge_deleted {
+        report += maintenance.purge_deleted_rows().await?;
+    }
+    if options.compact {
+        report += maintenance.compact_files(options.threshold_percent).await?;
+    }
+    if let Some(retain) = options.retain {
+        report += maintenance.expire_snapshots(retain).await?;
+    }
+    Ok(report)
+}
@@ -17,10 +17,11 @@
 
 //! Table source
 
-use crate::{Expr, LogicalPlan};
+use crate::table_maintenance::TableMaintenance;
+use crate::{Expr, LogicalPlan, TableScanRowLock};
 
 use arrow::datatypes::SchemaRef;
-use datafusion_common::{Constraints, Result};
+use datafusion_common::{Constraints, Result, Statistics};
 
 use std::{any::Any, borrow::Cow};
 
@@ -50,6 +51,50 @@ pub enum TableProviderFilterPushDown {
     Exact,
 }
 
+/// Indicates how a row-level lock requested by `SELECT ... FOR UPDATE` /
+/// `FOR SHARE` is handled by a [`TableSource`].
+///
+/// The SQL planner attaches the requested lock to every affected
+/// [`TableScan`] and asks the source whether it can honor it.
+///
+/// [`TableScan`]: crate::TableScan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableProviderRowLockSupport {
+    /// The source cannot honor the requested lock mode or wait policy, and
+    /// planning fails.
+    Unsupported,
+    /// The source does not acquire row locks. The lock stays on the
+    /// [`TableScan`] but rows are produced without any locking, which is
+    /// the behavior of non-transactional sources.
+    ///
+    /// [`TableScan`]: crate::TableScan
+    Ignored,
+    /// The source **guarantees** that it acquires the requested lock on every
+    /// row it produces and applies the requested wait policy (`NOWAIT` or
+    /// `SKIP LOCKED`).
+    Exact,
+}
+
+/// Indicates how a table hint, such as T-SQL `WITH (NOLOCK)` or MySQL
+/// `FORCE INDEX (idx)`, is handled by a [`TableSource`].
+///
+/// The SQL planner records hints on the [`TableScan`] and asks the source
+/// about each of them.
+///
+/// [`TableScan`]: crate::TableScan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableProviderHintSupport {
+    /// The source rejects the hint, and planning fails.
+    Unsupported,
+    /// The source does not act on the hint. It stays on the [`TableScan`]
+    /// but has no effect, as hints are advisory in most engines.
+    ///
+    /// [`TableScan`]: crate::TableScan
+    Ignored,
+    /// The source honors the hint when scanning.
+    Exact,
+}
+
 /// Indicates the type of this table for metadata/catalog purposes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TableType {
@@ -102,6 +147,16 @@ pub trait TableSource: Sync + Send {
         None
     }
 
+    /// Get the statistics of this table, such as its number of rows and the
+    /// number of distinct values of its columns, if available.
+    ///
+    /// These are typically the statistics stored in the catalog by
+    /// `ANALYZE`, and are used by the logical optimizer to estimate the
+    /// cost of plans, e.g. to reorder joins.
+    fn statistics(&self) -> Option<Statistics> {
+        None
+    }
+
     /// Get the type of this table for metadata/catalog purposes.
     fn table_type(&self) -> TableType {
         TableType::Base
@@ -118,6 +173,32 @@ pub trait TableSource: Sync + Send {
             .collect())
     }
 
+    /// Tests whether the table provider can honor a row-level lock requested
+    /// by a `SELECT ... FOR UPDATE / FOR SHARE [NOWAIT | SKIP LOCKED]` clause.
+    ///
+    /// Returning [`TableProviderRowLockSupport::Unsupported`] makes the SQL
+    /// planner reject the query instead of silently scanning without locks.
+    fn supports_row_lock(
+        &self,
+        _lock: &TableScanRowLock,
+    ) -> Result<TableProviderRowLockSupport> {
+        Ok(TableProviderRowLockSupport::Ignored)
+    }
+
+    /// Tests whether the table provider can honor a table hint, such as
+    /// `NOLOCK` from T-SQL `WITH (NOLOCK)` or `USE INDEX` from MySQL
+    /// `USE INDEX (idx)`, with its arguments.
+    ///
+    /// `name` is upper case. Sources that validate hints may return an error
+    /// for invalid arguments, e.g. an index that does not exist.
+    fn supports_hint(
+        &self,
+        _name: &str,
+        _args: &[String],
+    ) -> Result<TableProviderHintSupport> {
+        Ok(TableProviderHintSupport::Ignored)
+    }
+
     /// Get the Logical plan of this table provider, if available.
     ///
     /// For example, a view may have a logical plan, but a CSV file does not.
@@ -129,4 +210,10 @@ pub trait TableSource: Sync + Send {
     fn get_column_default(&self, _column: &str) -> Option<&Expr> {
         None
     }
+
+    /// Get the maintenance operations of the storage of this table, run by
+    /// `VACUUM`, if it needs any.
+    fn maintenance(&self) -> Option<&dyn TableMaintenance> {
+        None
+    }
 }
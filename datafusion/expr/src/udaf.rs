@@ -26,6 +26,7 @@ use std::vec;
 
 use arrow::datatypes::{DataType, Field, FieldRef};
 
+use datafusion_common::scalar::ScalarStructBuilder;
 use datafusion_common::{Result, ScalarValue, Statistics, exec_err, not_impl_err};
 use datafusion_expr_common::dyn_eq::{DynEq, DynHash};
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
@@ -334,6 +335,16 @@ impl AggregateUDF {
         self.inner.supports_within_group_clause()
     }
 
+    /// See [`AggregateUDFImpl::supports_on_overflow_clause`] for more details.
+    pub fn supports_on_overflow_clause(&self) -> bool {
+        self.inner.supports_on_overflow_clause()
+    }
+
+    /// See [`AggregateUDFImpl::supports_json_clauses`] for more details.
+    pub fn supports_json_clauses(&self) -> bool {
+        self.inner.supports_json_clauses()
+    }
+
     /// Returns the documentation for this Aggregate UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -804,6 +815,34 @@ pub trait AggregateUDFImpl: Debug + DynEq + DynHash + Send + Sync {
         false
     }
 
+    /// If this function supports the `ON OVERFLOW ERROR | TRUNCATE` SQL
+    /// clause of `LISTAGG`, return `true`. Otherwise, return `false` (default)
+    /// which will cause an error when planning SQL where this clause is
+    /// detected for this function.
+    ///
+    /// When the clause is present, the planner passes the [`OnOverflow`]
+    /// behavior as an additional last argument, a literal created with
+    /// [`OnOverflow::to_scalar`]. Functions which implement this as `true` are
+    /// expected to decode it with [`OnOverflow::try_from_scalar`], and to
+    /// raise an error on overflow when it is absent.
+    fn supports_on_overflow_clause(&self) -> bool {
+        false
+    }
+
+    /// If this function supports the clauses of the SQL/JSON aggregate
+    /// functions, such as `ABSENT ON NULL` and `RETURNING` of
+    /// `JSON_ARRAYAGG`, return `true`. Otherwise, return `false` (default)
+    /// which will cause an error when planning SQL where these clauses are
+    /// detected for this function.
+    ///
+    /// The clauses are passed as for scalar functions, see
+    /// [`ScalarUDFImpl::supports_json_clauses`].
+    ///
+    /// [`ScalarUDFImpl::supports_json_clauses`]: crate::ScalarUDFImpl::supports_json_clauses
+    fn supports_json_clauses(&self) -> bool {
+        false
+    }
+
     /// Returns the documentation for this Aggregate UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -1268,6 +1307,14 @@ impl AggregateUDFImpl for AliasedAggregateUDFImpl {
         self.inner.supports_within_group_clause()
     }
 
+    fn supports_on_overflow_clause(&self) -> bool {
+        self.inner.supports_on_overflow_clause()
+    }
+
+    fn supports_json_clauses(&self) -> bool {
+        self.inner.supports_json_clauses()
+    }
+
     fn set_monotonicity(&self, data_type: &DataType) -> SetMonotonicity {
         self.inner.set_monotonicity(data_type)
     }
@@ -1298,11 +1345,77 @@ pub enum SetMonotonicity {
     NotMonotonic,
 }
 
+/// Behavior of an aggregate function that concatenates its input, such as
+/// `LISTAGG`, when its result is longer than its return type allows, as given
+/// by the SQL `ON OVERFLOW` clause.
+///
+/// See [`AggregateUDFImpl::supports_on_overflow_clause`] for how it is passed
+/// to the function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnOverflow {
+    /// `ON OVERFLOW ERROR`: raise an error, the default behavior
+    Error,
+    /// `ON OVERFLOW TRUNCATE [filler] WITH | WITHOUT COUNT`: truncate the
+    /// result after the last value that fits and append `filler`, followed by
+    /// the number of values that were left out if `with_count` is set
+    Truncate { filler: String, with_count: bool },
+}
+
+impl OnOverflow {
+    /// The filler appended to a truncated result when none is specified
+    pub const DEFAULT_FILLER: &'static str = "...";
+
+    /// Returns the struct literal that represents this behavior, with the
+    /// fields `truncate`, `filler` and `with_count`
+    pub fn to_scalar(&self) -> Result<ScalarValue> {
+        let (truncate, filler, with_count) = match self {
+            OnOverflow::Error => (false, None, false),
+            OnOverflow::Truncate { filler, with_count } => {
+                (true, Some(filler.clone()), *with_count)
+            }
+        };
+        ScalarStructBuilder::new()
+            .with_scalar(
+                Field::new("truncate", DataType::Boolean, false),
+                ScalarValue::Boolean(Some(truncate)),
+            )
+            .with_scalar(
+                Field::new("filler", DataType::Utf8, true),
+                ScalarValue::Utf8(filler),
+            )
+            .with_scalar(
+                Field::new("with_count", DataType::Boolean, false),
+                ScalarValue::Boolean(Some(with_count)),
+            )
+            .build()
+    }
+
+    /// Decodes a literal created with [`Self::to_scalar`]
+    pub fn try_from_scalar(value: &ScalarValue) -> Result<Self> {
+        let ScalarValue::Struct(array) = value else {
+            return exec_err!("Expected an ON OVERFLOW struct, got {value:?}");
+        };
+        let field = |name: &str| match array.column_by_name(name) {
+            Some(column) if array.len() == 1 => ScalarValue::try_from_array(column, 0),
+            _ => exec_err!("Expected an ON OVERFLOW struct, got {value:?}"),
+        };
+        match (field("truncate")?, field("filler")?, field("with_count")?) {
+            (ScalarValue::Boolean(Some(false)), _, _) => Ok(OnOverflow::Error),
+            (
+                ScalarValue::Boolean(Some(true)),
+                ScalarValue::Utf8(Some(filler)),
+                ScalarValue::Boolean(Some(with_count)),
+            ) => Ok(OnOverflow::Truncate { filler, with_count }),
+            _ => exec_err!("Expected an ON OVERFLOW struct, got {value:?}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{AggregateUDF, AggregateUDFImpl};
+    use crate::{AggregateUDF, AggregateUDFImpl, OnOverflow};
     use arrow::datatypes::{DataType, FieldRef};
-    use datafusion_common::Result;
+    use datafusion_common::{Result, ScalarValue};
     use datafusion_expr_common::accumulator::Accumulator;
     use datafusion_expr_common::signature::{Signature, Volatility};
     use datafusion_functions_aggregate_common::accumulator::{
@@ -1416,6 +1529,26 @@ mod test {
         assert!(!(a1 == b1));
     }
 
+    #[test]
+    fn test_on_overflow_scalar_roundtrip() -> Result<()> {
+        for on_overflow in [
+            OnOverflow::Error,
+            OnOverflow::Truncate {
+                filler: OnOverflow::DEFAULT_FILLER.to_string(),
+                with_count: true,
+            },
+            OnOverflow::Truncate {
+                filler: "~".to_string(),
+                with_count: false,
+            },
+        ] {
+            let scalar = on_overflow.to_scalar()?;
+            assert_eq!(OnOverflow::try_from_scalar(&scalar)?, on_overflow);
+        }
+        assert!(OnOverflow::try_from_scalar(&ScalarValue::Boolean(Some(true))).is_err());
+        Ok(())
+    }
+
     fn hash<T: Hash>(value: T) -> u64 {
         let hasher = &mut DefaultHasher::new();
         value.hash(hasher);
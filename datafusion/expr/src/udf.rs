@@ -19,6 +19,7 @@
 
 use crate::async_udf::AsyncScalarUDF;
 use crate::expr::schema_name_from_exprs_comma_separated_without_space;
+use crate::logical_plan::JsonTableErrorHandling;
 use crate::session::SessionProvider;
 use crate::simplify::{ExprSimplifyResult, SimplifyInfo};
 use crate::sort_properties::{ExprProperties, SortProperties};
@@ -26,8 +27,10 @@ use crate::udf_eq::UdfEq;
 use crate::{ColumnarValue, Documentation, Expr, Signature};
 use arrow::datatypes::{DataType, Field, FieldRef};
 use datafusion_common::config::ConfigOptions;
+use datafusion_common::scalar::ScalarStructBuilder;
 use datafusion_common::{
-    ExprSchema, Result, ScalarValue, assert_or_internal_err, not_impl_err,
+    ExprSchema, Result, ScalarValue, assert_or_internal_err, exec_datafusion_err,
+    exec_err, not_impl_err,
 };
 use datafusion_expr_common::dyn_eq::{DynEq, DynHash};
 use datafusion_expr_common::interval_arithmetic::Interval;
@@ -337,6 +340,11 @@ impl ScalarUDF {
         self.inner.coerce_types(arg_types)
     }
 
+    /// See [`ScalarUDFImpl::supports_json_clauses`] for more details.
+    pub fn supports_json_clauses(&self) -> bool {
+        self.inner.supports_json_clauses()
+    }
+
     /// Returns the documentation for this Scalar UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -849,6 +857,24 @@ pub trait ScalarUDFImpl: Debug + DynEq + DynHash + Send + Sync {
         not_impl_err!("Function {} does not implement coerce_types", self.name())
     }
 
+    /// If this function supports the clauses of the SQL/JSON functions, such
+    /// as `ABSENT ON NULL`, `WITH UNIQUE KEYS` and `RETURNING` of
+    /// `JSON_OBJECT`, return `true`. Otherwise, return `false` (default)
+    /// which will cause an error when planning SQL where these clauses are
+    /// detected for this function.
+    ///
+    /// When any of the clauses is present, the planner passes them as an
+    /// additional last argument, a literal created with
+    /// [`JsonFunctionOptions::to_scalar`]. Functions which implement this as
+    /// `true` are expected to decode it with
+    /// [`JsonFunctionOptions::try_from_scalar`], and to return the type of
+    /// the `RETURNING` clause, which [`Self::return_field_from_args`] can
+    /// find with [`JsonFunctionOptions::from_scalar_arguments`]. Otherwise,
+    /// the planner casts their result to that type.
+    fn supports_json_clauses(&self) -> bool {
+        false
+    }
+
     /// Returns the documentation for this Scalar UDF.
     ///
     /// Documentation can be accessed programmatically as well as generating
@@ -971,11 +997,205 @@ impl ScalarUDFImpl for AliasedScalarUDFImpl {
         self.inner.coerce_types(arg_types)
     }
 
+    fn supports_json_clauses(&self) -> bool {
+        self.inner.supports_json_clauses()
+    }
+
     fn documentation(&self) -> Option<&Documentation> {
         self.inner.documentation()
     }
 }
 
+/// The clauses of a call to a SQL/JSON function, such as `ABSENT ON NULL`
+/// and `RETURNING` of `JSON_OBJECT`.
+///
+/// See [`ScalarUDFImpl::supports_json_clauses`] for how they are passed to
+/// the function.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JsonFunctionOptions {
+    /// `NULL ON NULL` (`Some(true)`) or `ABSENT ON NULL` (`Some(false)`):
+    /// whether a constructor keeps or skips null values
+    pub null_on_null: Option<bool>,
+    /// `WITH UNIQUE KEYS` (`Some(true)`) or `WITHOUT UNIQUE KEYS`
+    /// (`Some(false)`): whether a constructor rejects duplicate keys
+    pub unique_keys: Option<bool>,
+    /// The data type of the `RETURNING` clause
+    pub returning: Option<DataType>,
+    /// The `ARRAY WRAPPER` clause of `JSON_QUERY`
+    pub wrapper: Option<JsonWrapper>,
+    /// `KEEP QUOTES` (`Some(true)`) or `OMIT QUOTES` (`Some(false)`) of
+    /// `JSON_QUERY`: whether a scalar string result keeps its quotes
+    pub keep_quotes: Option<bool>,
+    /// The `ON EMPTY` clause of `JSON_VALUE` and `JSON_QUERY`, applied when
+    /// the path selects no item
+    pub on_empty: Option<JsonTableErrorHandling>,
+    /// The `ON ERROR` clause of `JSON_VALUE`, `JSON_QUERY` and `JSON_EXISTS`
+    pub on_error: Option<JsonTableErrorHandling>,
+}
+
+/// The `ARRAY WRAPPER` clause of `JSON_QUERY`, which wraps the items
+/// selected by the path in a JSON array
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonWrapper {
+    /// `WITHOUT ARRAY WRAPPER`: the path must select a single item, the
+    /// default behavior
+    Without,
+    /// `WITH CONDITIONAL ARRAY WRAPPER`: wrap the items unless the path
+    /// selects a single array or object
+    Conditional,
+    /// `WITH [UNCONDITIONAL] ARRAY WRAPPER`: always wrap the items
+    Unconditional,
+}
+
+impl JsonWrapper {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JsonWrapper::Without => "without",
+            JsonWrapper::Conditional => "conditional",
+            JsonWrapper::Unconditional => "unconditional",
+        }
+    }
+}
+
+/// The name and the `DEFAULT` value of the ON EMPTY or ON ERROR behavior
+/// `behavior` in the struct literal of [`JsonFunctionOptions`]
+fn json_behavior_to_scalars(
+    behavior: Option<&JsonTableErrorHandling>,
+) -> (ScalarValue, ScalarValue) {
+    let (name, default) = match behavior {
+        None => return (ScalarValue::Utf8(None), ScalarValue::Null),
+        Some(JsonTableErrorHandling::Null) => ("null", ScalarValue::Null),
+        Some(JsonTableErrorHandling::Default(value)) => ("default", value.clone()),
+        Some(JsonTableErrorHandling::Error) => ("error", ScalarValue::Null),
+        Some(JsonTableErrorHandling::EmptyArray) => ("empty_array", ScalarValue::Null),
+        Some(JsonTableErrorHandling::EmptyObject) => ("empty_object", ScalarValue::Null),
+    };
+    (ScalarValue::from(name), default)
+}
+
+impl JsonFunctionOptions {
+    /// Returns the struct literal that represents these clauses, with the
+    /// boolean fields `null_on_null`, `unique_keys` and `keep_quotes`, the
+    /// string field `wrapper`, and the field `returning`, a null of the
+    /// returning type or of type `Null` when there is none. The `ON EMPTY`
+    /// and `ON ERROR` clauses are the string fields `on_empty` and `on_error`,
+    /// with their `DEFAULT` value in `on_empty_default` and `on_error_default`.
+    pub fn to_scalar(&self) -> Result<ScalarValue> {
+        let returning = match &self.returning {
+            Some(data_type) => ScalarValue::try_from(data_type)?,
+            None => ScalarValue::Null,
+        };
+        let mut builder = ScalarStructBuilder::new()
+            .with_scalar(
+                Field::new("null_on_null", DataType::Boolean, true),
+                ScalarValue::Boolean(self.null_on_null),
+            )
+            .with_scalar(
+                Field::new("unique_keys", DataType::Boolean, true),
+                ScalarValue::Boolean(self.unique_keys),
+            )
+            .with_scalar(
+                Field::new("returning", returning.data_type(), true),
+                returning,
+            )
+            .with_scalar(
+                Field::new("wrapper", DataType::Utf8, true),
+                ScalarValue::Utf8(self.wrapper.map(|wrapper| wrapper.as_str().into())),
+            )
+            .with_scalar(
+                Field::new("keep_quotes", DataType::Boolean, true),
+                ScalarValue::Boolean(self.keep_quotes),
+            );
+        for (name, behavior) in [
+            ("on_empty", self.on_empty.as_ref()),
+            ("on_error", self.on_error.as_ref()),
+        ] {
+            let (behavior, default) = json_behavior_to_scalars(behavior);
+            builder = builder
+                .with_scalar(Field::new(name, DataType::Utf8, true), behavior)
+                .with_scalar(
+                    Field::new(format!("{name}_default"), default.data_type(), true),
+                    default,
+                );
+        }
+        builder.build()
+    }
+
+    /// Decodes the clauses passed as the last of the `scalar_arguments` of
+    /// [`ReturnFieldArgs`], if any
+    pub fn from_scalar_arguments(
+        scalar_arguments: &[Option<&ScalarValue>],
+    ) -> Option<Self> {
+        let value = scalar_arguments.last().copied().flatten()?;
+        Self::try_from_scalar(value).ok()
+    }
+
+    /// Decodes a literal created with [`Self::to_scalar`]
+    pub fn try_from_scalar(value: &ScalarValue) -> Result<Self> {
+        let ScalarValue::Struct(array) = value else {
+            return exec_err!("Expected a SQL/JSON clauses struct, got {value:?}");
+        };
+        let field = |name: &str| match array.column_by_name(name) {
+            Some(column) if array.len() == 1 => ScalarValue::try_from_array(column, 0),
+            _ => exec_err!("Expected a SQL/JSON clauses struct, got {value:?}"),
+        };
+        let boolean = |name: &str| match field(name)? {
+            ScalarValue::Boolean(value) => Ok(value),
+            _ => exec_err!("Expected a SQL/JSON clauses struct, got {value:?}"),
+        };
+        let returning = match field("returning")?.data_type() {
+            DataType::Null => None,
+            data_type => Some(data_type),
+        };
+        let wrapper = match field("wrapper")? {
+            ScalarValue::Utf8(None) => None,
+            ScalarValue::Utf8(Some(wrapper)) => Some(
+                [
+                    JsonWrapper::Without,
+                    JsonWrapper::Conditional,
+                    JsonWrapper::Unconditional,
+                ]
+                .into_iter()
+                .find(|candidate| candidate.as_str() == wrapper)
+                .ok_or_else(|| {
+                    exec_datafusion_err!("Unknown JSON array wrapper {wrapper}")
+                })?,
+            ),
+            _ => return exec_err!("Expected a SQL/JSON clauses struct, got {value:?}"),
+        };
+        let behavior = |name: &str| {
+            let behavior = match field(name)? {
+                ScalarValue::Utf8(None) => return Ok(None),
+                ScalarValue::Utf8(Some(behavior)) => behavior,
+                _ => {
+                    return exec_err!(
+                        "Expected a SQL/JSON clauses struct, got {value:?}"
+                    );
+                }
+            };
+            Ok(Some(match behavior.as_str() {
+                "null" => JsonTableErrorHandling::Null,
+                "default" => {
+                    JsonTableErrorHandling::Default(field(&format!("{name}_default"))?)
+                }
+                "error" => JsonTableErrorHandling::Error,
+                "empty_array" => JsonTableErrorHandling::EmptyArray,
+                "empty_object" => JsonTableErrorHandling::EmptyObject,
+                _ => return exec_err!("Unknown SQL/JSON behavior {behavior}"),
+            }))
+        };
+        Ok(Self {
+            null_on_null: boolean("null_on_null")?,
+            unique_keys: boolean("unique_keys")?,
+            returning,
+            wrapper,
+            keep_quotes: boolean("keep_quotes")?,
+            on_empty: behavior("on_empty")?,
+            on_error: behavior("on_error")?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1054,4 +1274,58 @@ mod tests {
         value.hash(hasher);
         hasher.finish()
     }
+
+    #[test]
+    fn test_json_function_options_scalar_roundtrip() -> Result<()> {
+        for options in [
+            JsonFunctionOptions::default(),
+            JsonFunctionOptions {
+                null_on_null: Some(false),
+                unique_keys: Some(true),
+                returning: Some(DataType::LargeUtf8),
+                ..Default::default()
+            },
+            JsonFunctionOptions {
+                null_on_null: Some(true),
+                returning: Some(DataType::Int64),
+                ..Default::default()
+            },
+            JsonFunctionOptions {
+                wrapper: Some(JsonWrapper::Conditional),
+                keep_quotes: Some(false),
+                ..Default::default()
+            },
+            JsonFunctionOptions {
+                returning: Some(DataType::Int32),
+                on_empty: Some(JsonTableErrorHandling::Default(ScalarValue::Int32(
+                    Some(0),
+                ))),
+                on_error: Some(JsonTableErrorHandling::Error),
+                ..Default::default()
+            },
+            JsonFunctionOptions {
+                on_empty: Some(JsonTableErrorHandling::EmptyArray),
+                on_error: Some(JsonTableErrorHandling::Null),
+                ..Default::default()
+            },
+        ] {
+            let scalar = options.to_scalar()?;
+            assert_eq!(JsonFunctionOptions::try_from_scalar(&scalar)?, options);
+            let scalar_arguments = [None, Some(&scalar)];
+            assert_eq!(
+                JsonFunctionOptions::from_scalar_arguments(&scalar_arguments),
+                Some(options)
+            );
+        }
+        let path = ScalarValue::from("$.a");
+        assert_eq!(
+            JsonFunctionOptions::from_scalar_arguments(&[None, Some(&path)]),
+            None
+        );
+        assert!(
+            JsonFunctionOptions::try_from_scalar(&ScalarValue::Boolean(Some(true)))
+                .is_err()
+        );
+        Ok(())
+    }
 }
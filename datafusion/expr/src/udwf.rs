@@ -206,6 +206,11 @@ impl WindowUDF {
         self.inner.reverse_expr()
     }
 
+    /// See [`WindowUDFImpl::supports_null_handling_clause`] for more details.
+    pub fn supports_null_handling_clause(&self) -> bool {
+        self.inner.supports_null_handling_clause()
+    }
+
     /// Returns the documentation for this Window UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -419,6 +424,18 @@ pub trait WindowUDFImpl: Debug + DynEq + DynHash + Send + Sync {
         ReversedUDWF::NotSupported
     }
 
+    /// If this function supports the `[IGNORE NULLS | RESPECT NULLS]` SQL
+    /// clause, return `true`. Otherwise, return `false` (default) which will
+    /// cause an error to be raised during SQL planning if these clauses are
+    /// detected for this function.
+    ///
+    /// Functions which implement this as `true`, such as `lead`, `lag` or
+    /// `nth_value`, are expected to skip null values when
+    /// [`PartitionEvaluatorArgs::ignore_nulls`] is set.
+    fn supports_null_handling_clause(&self) -> bool {
+        false
+    }
+
     /// Returns the documentation for this Window UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -549,6 +566,10 @@ impl WindowUDFImpl for AliasedWindowUDFImpl {
         self.inner.reverse_expr()
     }
 
+    fn supports_null_handling_clause(&self) -> bool {
+        self.inner.supports_null_handling_clause()
+    }
+
     fn documentation(&self) -> Option<&Documentation> {
         self.inner.documentation()
     }
@@ -0,0 +1,236 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Users created by `CREATE USER`
+//!
+//! `CREATE USER`, `ALTER USER` and `DROP USER` are planned as
+//! [`DdlStatement`]s. Engines executing them call [`execute_user_statement`]
+//! with the [`UserCatalog`] storing their users, which checks that the
+//! users exist, or not, and applies the options of the statement to the
+//! stored [`UserDefinition`]s.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+
+use crate::foreign_data_wrapper::apply_option_changes;
+use crate::{AlterUser, CreateUser, DdlStatement, DropUser};
+use datafusion_common::{Result, plan_err};
+
+/// A user created by `CREATE USER`
+///
+/// The [`Debug`] output omits the password.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct UserDefinition {
+    /// The user name
+    pub name: String,
+    /// The password of the user, as given by the statement
+    pub password: Option<String>,
+    /// The role the sessions of the user start with
+    pub default_role: Option<String>,
+    /// Attributes of the user, set by the `OPTIONS` clause
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl Debug for UserDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserDefinition")
+            .field("name", &self.name)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("default_role", &self.default_role)
+            .field("attributes", &self.attributes)
+            .finish()
+    }
+}
+
+/// The storage of the users of an engine, such as a system table
+pub trait UserCatalog: Debug + Send + Sync {
+    /// Returns the user `name`, if it exists
+    fn get_user(&self, name: &str) -> Result<Option<UserDefinition>>;
+
+    /// Stores `user`, replacing the user of the same name if it exists
+    fn put_user(&self, user: UserDefinition) -> Result<()>;
+
+    /// Removes the user `name`, which exists
+    fn remove_user(&self, name: &str) -> Result<()>;
+}
+
+/// Executes `statement` against `catalog` if it is a `CREATE USER`,
+/// `ALTER USER` or `DROP USER` statement, returning whether it was
+pub fn execute_user_statement(
+    catalog: &dyn UserCatalog,
+    statement: &DdlStatement,
+) -> Result<bool> {
+    match statement {
+        DdlStatement::CreateUser(create) => create_user(catalog, create)?,
+        DdlStatement::AlterUser(alter) => alter_user(catalog, alter)?,
+        DdlStatement::DropUser(drop) => drop_user(catalog, drop)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Creates the user described by a `CREATE USER` statement
+pub fn create_user(catalog: &dyn UserCatalog, statement: &CreateUser) -> Result<()> {
+    let CreateUser {
+        name,
+        if_not_exists,
+        password,
+        default_role,
+        attributes,
+    } = statement;
+    if catalog.get_user(name)?.is_some() {
+        if *if_not_exists {
+            return Ok(());
+        }
+        return plan_err!("User {name} already exists");
+    }
+    catalog.put_user(UserDefinition {
+        name: name.clone(),
+        password: password.clone(),
+        default_role: default_role.clone(),
+        attributes: attributes.clone(),
+    })
+}
+
+/// Applies the options of an `ALTER USER` statement to the user, keeping
+/// the password and default role it does not change
+pub fn alter_user(catalog: &dyn UserCatalog, statement: &AlterUser) -> Result<()> {
+    let Some(mut user) = catalog.get_user(&statement.name)? else {
+        return plan_err!("User {} does not exist", statement.name);
+    };
+    if let Some(password) = &statement.password {
+        user.password = Some(password.clone());
+    }
+    if let Some(default_role) = &statement.default_role {
+        user.default_role = Some(default_role.clone());
+    }
+    apply_option_changes(&mut user.attributes, &statement.attributes)?;
+    catalog.put_user(user)
+}
+
+/// Drops the users of a `DROP USER` statement. Either all of them are
+/// dropped or, if one does not exist and `IF EXISTS` is not given, none.
+pub fn drop_user(catalog: &dyn UserCatalog, statement: &DropUser) -> Result<()> {
+    let mut existing = vec![];
+    for name in &statement.names {
+        if catalog.get_user(name)?.is_some() {
+            existing.push(name);
+        } else if !statement.if_exists {
+            return plan_err!("User {name} does not exist");
+        }
+    }
+    for name in existing {
+        catalog.remove_user(name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OptionChange;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct TestCatalog {
+        users: Mutex<BTreeMap<String, UserDefinition>>,
+    }
+
+    impl UserCatalog for TestCatalog {
+        fn get_user(&self, name: &str) -> Result<Option<UserDefinition>> {
+            Ok(self.users.lock().unwrap().get(name).cloned())
+        }
+
+        fn put_user(&self, user: UserDefinition) -> Result<()> {
+            self.users.lock().unwrap().insert(user.name.clone(), user);
+            Ok(())
+        }
+
+        fn remove_user(&self, name: &str) -> Result<()> {
+            self.users.lock().unwrap().remove(name);
+            Ok(())
+        }
+    }
+
+    fn create(name: &str) -> DdlStatement {
+        DdlStatement::CreateUser(CreateUser {
+            name: name.to_string(),
+            if_not_exists: false,
+            password: Some("secret".to_string()),
+            default_role: Some("analyst".to_string()),
+            attributes: BTreeMap::from([("team".to_string(), "sales".to_string())]),
+        })
+    }
+
+    #[test]
+    fn users_created_altered_and_dropped() -> Result<()> {
+        let catalog = TestCatalog::default();
+        assert!(!format!("{:?}", create("alice")).contains("secret"));
+        assert!(execute_user_statement(&catalog, &create("alice"))?);
+        let err = execute_user_statement(&catalog, &create("alice")).unwrap_err();
+        assert!(err.to_string().contains("User alice already exists"));
+
+        let alter_password = DdlStatement::AlterUser(AlterUser {
+            name: "alice".to_string(),
+            password: Some("new secret".to_string()),
+            default_role: None,
+            attributes: vec![],
+        });
+        assert!(!format!("{alter_password:?}").contains("secret"));
+
+        let alter = DdlStatement::AlterUser(AlterUser {
+            name: "alice".to_string(),
+            password: None,
+            default_role: Some("admin".to_string()),
+            attributes: vec![
+                OptionChange::Set {
+                    key: "team".to_string(),
+                    value: "support".to_string(),
+                },
+                OptionChange::Add {
+                    key: "site".to_string(),
+                    value: "paris".to_string(),
+                },
+            ],
+        });
+        assert!(execute_user_statement(&catalog, &alter)?);
+        let user = catalog.get_user("alice")?.unwrap();
+        assert_eq!(user.password.as_deref(), Some("secret"));
+        assert_eq!(user.default_role.as_deref(), Some("admin"));
+        assert_eq!(
+            user.attributes,
+            BTreeMap::from([
+                ("site".to_string(), "paris".to_string()),
+                ("team".to_string(), "support".to_string()),
+            ])
+        );
+        assert!(!format!("{user:?}").contains("secret"));
+
+        let drop = |if_exists| {
+            DdlStatement::DropUser(DropUser {
+                names: vec!["alice".to_string(), "bob".to_string()],
+                if_exists,
+            })
+        };
+        let err = execute_user_statement(&catalog, &drop(false)).unwrap_err();
+        assert!(err.to_string().contains("User bob does not exist"));
+        assert!(catalog.get_user("alice")?.is_some());
+        assert!(execute_user_statement(&catalog, &drop(true))?);
+        assert!(catalog.get_user("alice")?.is_none());
+        Ok(())
+    }
+}
@@ -324,6 +324,9 @@ pub fn expr_to_columns(expr: &Expr, accum: &mut HashSet<Column>) -> Result<()> {
 
 /// Find excluded columns in the schema, if any
 /// SELECT * EXCLUDE(col1, col2), would return `vec![col1, col2]`
+///
+/// Every column of the wildcard with an excluded name is excluded, and each
+/// excluded name must be the name of at least one of them.
 fn get_excluded_columns(
     opt_except: Option<&ExceptSelectItem>,
     schema: &DFSchema,
@@ -334,20 +337,30 @@ fn get_excluded_columns(
         idents.push(&excepts.first_element);
         idents.extend(&excepts.additional_elements);
     }
-    // Excluded columns should be unique
-    let n_elem = idents.len();
-    let unique_idents = idents.into_iter().collect::<HashSet<_>>();
-    // If HashSet size, and vector length are different, this means that some of the excluded columns
-    // are not unique. In this case return error.
-    if n_elem != unique_idents.len() {
-        return plan_err!("EXCLUDE or EXCEPT contains duplicate column names");
-    }
 
+    let mut names = HashSet::new();
     let mut result = vec![];
-    for ident in unique_idents.into_iter() {
+    for ident in idents {
         let col_name = ident.value.as_str();
-        let (qualifier, field) = schema.qualified_field_with_name(qualifier, col_name)?;
-        result.push(Column::from((qualifier, field)));
+        // Excluded columns should be unique
+        if !names.insert(col_name) {
+            return plan_err!("EXCLUDE or EXCEPT contains duplicate column names");
+        }
+        let columns = match qualifier {
+            Some(qualifier) => schema
+                .fields_indices_with_qualified(qualifier)
+                .into_iter()
+                .map(|i| Column::from(schema.qualified_field(i)))
+                .filter(|column| column.name == col_name)
+                .collect(),
+            None => schema.columns_with_unqualified_name(col_name),
+        };
+        if columns.is_empty() {
+            return plan_err!(
+                "EXCLUDE or EXCEPT contains column {col_name}, which is not selected by the wildcard"
+            );
+        }
+        result.extend(columns);
     }
     Ok(result)
 }
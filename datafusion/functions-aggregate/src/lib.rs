@@ -85,6 +85,7 @@ pub mod median;
 pub mod min_max;
 pub mod nth_value;
 pub mod percentile_cont;
+pub mod percentile_disc;
 pub mod regr;
 pub mod stddev;
 pub mod string_agg;
@@ -129,6 +130,7 @@ pub mod expr_fn {
     pub use super::min_max::min;
     pub use super::nth_value::nth_value;
     pub use super::percentile_cont::percentile_cont;
+    pub use super::percentile_disc::percentile_disc;
     pub use super::regr::regr_avgx;
     pub use super::regr::regr_avgy;
     pub use super::regr::regr_count;
@@ -178,6 +180,7 @@ pub fn all_default_aggregate_functions() -> Vec<Arc<AggregateUDF>> {
         approx_percentile_cont_udaf(),
         approx_percentile_cont_with_weight_udaf(),
         percentile_cont::percentile_cont_udaf(),
+        percentile_disc::percentile_disc_udaf(),
         string_agg::string_agg_udaf(),
         bit_and_or_xor::bit_and_udaf(),
         bit_and_or_xor::bit_or_udaf(),
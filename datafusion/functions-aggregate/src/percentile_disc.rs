@@ -0,0 +1,282 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::{Debug, Formatter};
+use std::mem::size_of_val;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::compute::{SortOptions, concat, filter, is_not_null, sort_to_indices, take};
+use arrow::datatypes::{DataType, Field, FieldRef};
+
+use datafusion_common::utils::{SingleRowListArrayBuilder, take_function_args};
+use datafusion_common::{Result, ScalarValue, plan_err};
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Expr, Signature, Volatility,
+};
+use datafusion_expr::{
+    expr::{AggregateFunction, Sort},
+    function::{AccumulatorArgs, StateFieldsArgs},
+};
+use datafusion_macros::user_doc;
+
+use crate::utils::validate_percentile_expr;
+
+create_func!(PercentileDisc, percentile_disc_udaf);
+
+/// Computes the exact percentile discrete of a set of values
+pub fn percentile_disc(order_by: Sort, percentile: Expr) -> Expr {
+    let expr = order_by.expr.clone();
+    let args = vec![expr, percentile];
+
+    Expr::AggregateFunction(AggregateFunction::new_udf(
+        percentile_disc_udaf(),
+        args,
+        false,
+        None,
+        vec![order_by],
+        None,
+    ))
+}
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = "Returns the first input value, in the order of the WITHIN GROUP clause, whose position in that order is greater than or equal to the percentile. Unlike `percentile_cont`, the result is always one of the input values.",
+    syntax_example = "percentile_disc(percentile) WITHIN GROUP (ORDER BY expression)",
+    sql_example = r#"```sql
+> SELECT percentile_disc(0.75) WITHIN GROUP (ORDER BY column_name) FROM table_name;
++----------------------------------------------------------+
+| percentile_disc(0.75) WITHIN GROUP (ORDER BY column_name) |
++----------------------------------------------------------+
+| 45                                                       |
++----------------------------------------------------------+
+```"#,
+    standard_argument(name = "expression", prefix = "The"),
+    argument(
+        name = "percentile",
+        description = "Percentile to compute. Must be a float value between 0 and 1 (inclusive)."
+    )
+)]
+/// PERCENTILE_DISC aggregate expression. Like PERCENTILE_CONT, this stores all
+/// values in memory before computing the result, but it accepts any orderable
+/// input type since the result is picked among the input values instead of
+/// being interpolated between them.
+#[derive(PartialEq, Eq, Hash)]
+pub struct PercentileDisc {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl Debug for PercentileDisc {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("PercentileDisc")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for PercentileDisc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentileDisc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable)
+                .with_parameter_names(vec!["expr".to_string(), "percentile".to_string()])
+                .expect("valid parameter names for percentile_disc"),
+            aliases: vec![String::from("quantile_disc")],
+        }
+    }
+}
+
+impl AggregateUDFImpl for PercentileDisc {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "percentile_disc"
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        let [expr, percentile] = take_function_args(self.name(), arg_types)?;
+        if !percentile.is_numeric() && !percentile.is_null() {
+            return plan_err!(
+                "percentile_disc requires a numeric percentile, got {percentile}"
+            );
+        }
+        Ok(vec![expr.clone(), DataType::Float64])
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        // The result is one of the input values
+        Ok(arg_types[0].clone())
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        // Intermediate state is a list of the elements we have collected so far
+        let field = Field::new_list_field(args.input_fields[0].data_type().clone(), true);
+        let state_name = if args.is_distinct {
+            "distinct_percentile_disc"
+        } else {
+            "percentile_disc"
+        };
+
+        Ok(vec![
+            Field::new(
+                format_state_name(args.name, state_name),
+                DataType::List(Arc::new(field)),
+                true,
+            )
+            .into(),
+        ])
+    }
+
+    fn accumulator(&self, args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let percentile = validate_percentile_expr(&args.exprs[1], "PERCENTILE_DISC")?;
+
+        let descending = args
+            .order_bys
+            .first()
+            .map(|sort_expr| sort_expr.options.descending)
+            .unwrap_or(false);
+
+        Ok(Box::new(PercentileDiscAccumulator {
+            data_type: args.exprs[0].data_type(args.schema)?,
+            values: vec![],
+            percentile,
+            descending,
+            distinct: args.is_distinct,
+        }))
+    }
+
+    fn supports_within_group_clause(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// The percentile_disc accumulator collects the non-null input values and
+/// sorts them in the final evaluation step.
+#[derive(Debug)]
+struct PercentileDiscAccumulator {
+    data_type: DataType,
+    values: Vec<ArrayRef>,
+    percentile: f64,
+    /// Whether the values are ordered descending in the WITHIN GROUP clause
+    descending: bool,
+    distinct: bool,
+}
+
+impl PercentileDiscAccumulator {
+    /// Returns all the collected values as a single array
+    fn take_values(&mut self) -> Result<ArrayRef> {
+        let values = std::mem::take(&mut self.values);
+        if values.is_empty() {
+            return Ok(arrow::array::new_empty_array(&self.data_type));
+        }
+        let values = values.iter().map(|v| v.as_ref()).collect::<Vec<_>>();
+        Ok(concat(&values)?)
+    }
+}
+
+impl Accumulator for PercentileDiscAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let values = self.take_values()?;
+        Ok(vec![
+            SingleRowListArrayBuilder::new(values).build_list_scalar(),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = &values[0];
+        if values.null_count() == 0 {
+            self.values.push(Arc::clone(values));
+        } else {
+            self.values.push(filter(values, &is_not_null(values)?)?);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = states[0].as_list::<i32>();
+        for v in array.iter().flatten() {
+            self.update_batch(&[v])?
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let values = self.take_values()?;
+        let options = SortOptions {
+            descending: self.descending,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(&values, Some(options), None)?;
+        let sorted = take(&values, &indices, None)?;
+
+        if self.distinct {
+            let mut distinct_values: Vec<ScalarValue> = vec![];
+            for index in 0..sorted.len() {
+                let value = ScalarValue::try_from_array(&sorted, index)?;
+                if distinct_values.last() != Some(&value) {
+                    distinct_values.push(value);
+                }
+            }
+            match percentile_index(distinct_values.len(), self.percentile) {
+                Some(index) => Ok(distinct_values.swap_remove(index)),
+                None => ScalarValue::try_from(&self.data_type),
+            }
+        } else {
+            match percentile_index(sorted.len(), self.percentile) {
+                Some(index) => ScalarValue::try_from_array(&sorted, index),
+                None => ScalarValue::try_from(&self.data_type),
+            }
+        }
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+            + self
+                .values
+                .iter()
+                .map(|v| v.get_array_memory_size())
+                .sum::<usize>()
+    }
+}
+
+/// Returns the index, among `len` sorted values, of the first value whose
+/// cumulative distribution is greater than or equal to `percentile`
+fn percentile_index(len: usize, percentile: f64) -> Option<usize> {
+    (len > 0).then(|| ((percentile * len as f64).ceil() as usize).clamp(1, len) - 1)
+}
@@ -0,0 +1,475 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CostModel`] estimates the number of rows of logical plans from the
+//! statistics of their tables
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{Column, ScalarValue};
+use datafusion_expr::logical_plan::{
+    Aggregate, Distinct, EmptyRelation, FetchType, Filter, Join, JoinType, Limit,
+    LogicalPlan, Projection, Sort, SubqueryAlias, TableScan, Union, Values, Window,
+};
+use datafusion_expr::{BinaryExpr, Expr, Operator};
+
+/// Estimates the number of rows, and the number of distinct values of
+/// columns, of logical plans from the statistics of their tables, as given
+/// by [`TableSource::statistics`].
+///
+/// The estimates are `None` when the plan reads a table without statistics,
+/// or contains a node whose output cannot be estimated.
+///
+/// [`TableSource::statistics`]: datafusion_expr::TableSource::statistics
+#[derive(Debug, Clone, Copy)]
+pub struct CostModel {
+    /// The selectivity of predicates whose selectivity cannot be estimated
+    default_selectivity: f64,
+}
+
+impl CostModel {
+    /// Create a new `CostModel` with the default filter selectivity of
+    /// `options`
+    pub fn new(options: &ConfigOptions) -> Self {
+        Self {
+            default_selectivity: f64::from(
+                options.optimizer.default_filter_selectivity.min(100),
+            ) / 100.0,
+        }
+    }
+
+    /// The estimated number of rows of `plan`
+    pub fn row_count(&self, plan: &LogicalPlan) -> Option<f64> {
+        match plan {
+            LogicalPlan::TableScan(TableScan {
+                source,
+                filters,
+                fetch,
+                ..
+            }) => {
+                let statistics = source.statistics()?;
+                let rows = *statistics.num_rows.get_value()? as f64;
+                let rows = filters
+                    .iter()
+                    .fold(rows, |rows, filter| rows * self.selectivity(plan, filter));
+                Some(fetch.map_or(rows, |fetch| rows.min(fetch as f64)))
+            }
+            LogicalPlan::Filter(Filter {
+                predicate, input, ..
+            }) => Some(self.row_count(input)? * self.selectivity(input, predicate)),
+            LogicalPlan::Projection(Projection { input, .. })
+            | LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. })
+            | LogicalPlan::Window(Window { input, .. })
+            | LogicalPlan::Distinct(Distinct::All(input)) => self.row_count(input),
+            LogicalPlan::Sort(Sort { input, fetch, .. }) => {
+                let rows = self.row_count(input)?;
+                Some(fetch.map_or(rows, |fetch| rows.min(fetch as f64)))
+            }
+            LogicalPlan::Limit(limit @ Limit { input, .. }) => {
+                let rows = self.row_count(input)?;
+                match limit.get_fetch_type() {
+                    Ok(FetchType::Literal(Some(fetch))) if !limit.with_ties => {
+                        Some(rows.min(fetch as f64))
+                    }
+                    _ => Some(rows),
+                }
+            }
+            LogicalPlan::Join(join) => self.join_row_count(plan, join),
+            LogicalPlan::Aggregate(Aggregate {
+                input, group_expr, ..
+            }) => {
+                if group_expr.is_empty() {
+                    return Some(1.0);
+                }
+                let rows = self.row_count(input)?;
+                let groups =
+                    group_expr
+                        .iter()
+                        .try_fold(1.0, |groups, expr| match unalias(expr) {
+                            Expr::Column(column) => {
+                                Some(groups * self.distinct_count(input, column)?)
+                            }
+                            _ => None,
+                        });
+                Some(groups.map_or(rows, |groups| groups.min(rows)))
+            }
+            LogicalPlan::Union(Union { inputs, .. }) => {
+                inputs.iter().map(|input| self.row_count(input)).sum()
+            }
+            LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row, ..
+            }) => Some(if *produce_one_row { 1.0 } else { 0.0 }),
+            LogicalPlan::Values(Values { values, .. }) => Some(values.len() as f64),
+            _ => None,
+        }
+    }
+
+    /// The estimated number of distinct values of `column` in the output of
+    /// `plan`
+    pub fn distinct_count(&self, plan: &LogicalPlan, column: &Column) -> Option<f64> {
+        if let LogicalPlan::TableScan(TableScan { source, .. }) = plan {
+            // The filters of a scan may reference columns that are not
+            // projected, so the column is found in the schema of the table
+            let statistics = source.statistics()?;
+            let index = source.schema().index_of(&column.name).ok()?;
+            let distinct = *statistics
+                .column_statistics
+                .get(index)?
+                .distinct_count
+                .get_value()? as f64;
+            return Some(match statistics.num_rows.get_value() {
+                Some(rows) => distinct.min(*rows as f64),
+                None => distinct,
+            });
+        }
+
+        let index = plan.schema().maybe_index_of_column(column)?;
+        let distinct = match plan {
+            LogicalPlan::Filter(Filter { input, .. })
+            | LogicalPlan::Sort(Sort { input, .. })
+            | LogicalPlan::Limit(Limit { input, .. })
+            | LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. })
+            | LogicalPlan::Window(Window { input, .. })
+            | LogicalPlan::Distinct(Distinct::All(input)) => {
+                self.input_distinct_count(input, index)?
+            }
+            LogicalPlan::Projection(Projection { expr, input, .. }) => {
+                match unalias(&expr[index]) {
+                    Expr::Column(column) => self.distinct_count(input, column)?,
+                    _ => return None,
+                }
+            }
+            LogicalPlan::Aggregate(Aggregate {
+                input, group_expr, ..
+            }) => match group_expr.get(index).map(unalias) {
+                Some(Expr::Column(column)) => self.distinct_count(input, column)?,
+                _ => return None,
+            },
+            LogicalPlan::Join(Join {
+                left,
+                right,
+                join_type,
+                ..
+            }) => match join_type {
+                JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
+                    let left_len = left.schema().fields().len();
+                    if index < left_len {
+                        self.input_distinct_count(left, index)?
+                    } else {
+                        self.input_distinct_count(right, index - left_len)?
+                    }
+                }
+                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
+                    self.input_distinct_count(left, index)?
+                }
+                JoinType::RightSemi | JoinType::RightAnti | JoinType::RightMark => {
+                    self.input_distinct_count(right, index)?
+                }
+            },
+            _ => return None,
+        };
+        Some(match self.row_count(plan) {
+            Some(rows) => distinct.min(rows),
+            None => distinct,
+        })
+    }
+
+    /// The estimated number of distinct values of the column at `index` in
+    /// the output of `input`
+    fn input_distinct_count(&self, input: &LogicalPlan, index: usize) -> Option<f64> {
+        let schema = input.schema();
+        if index >= schema.fields().len() {
+            return None;
+        }
+        self.distinct_count(input, &Column::from(schema.qualified_field(index)))
+    }
+
+    /// The estimated number of rows of `plan`, which is `join`
+    fn join_row_count(&self, plan: &LogicalPlan, join: &Join) -> Option<f64> {
+        let left = self.row_count(&join.left)?;
+        let right = self.row_count(&join.right)?;
+        // Each equijoin key matches the rows of the side with fewer distinct
+        // values to one distinct value of the other side. Keys without
+        // statistics are assumed to be unique on the larger side.
+        let mut rows = left * right;
+        for (left_key, right_key) in &join.on {
+            let distinct = [
+                self.key_distinct_count(&join.left, left_key),
+                self.key_distinct_count(&join.right, right_key),
+            ]
+            .into_iter()
+            .flatten()
+            .reduce(f64::max)
+            .unwrap_or_else(|| left.max(right));
+            rows /= distinct.max(1.0);
+        }
+        if let Some(filter) = &join.filter {
+            rows *= self.selectivity(plan, filter);
+        }
+        Some(match join.join_type {
+            JoinType::Inner => rows,
+            JoinType::Left => rows.max(left),
+            JoinType::Right => rows.max(right),
+            JoinType::Full => rows.max(left).max(right),
+            JoinType::LeftSemi => rows.min(left),
+            JoinType::RightSemi => rows.min(right),
+            JoinType::LeftAnti | JoinType::LeftMark => left,
+            JoinType::RightAnti | JoinType::RightMark => right,
+        })
+    }
+
+    /// The estimated number of distinct values of the join key `key` of
+    /// `input`
+    fn key_distinct_count(&self, input: &LogicalPlan, key: &Expr) -> Option<f64> {
+        match unalias(key) {
+            Expr::Column(column) => self.distinct_count(input, column),
+            _ => None,
+        }
+    }
+
+    /// The estimated fraction of the rows of `plan` that satisfy `predicate`
+    fn selectivity(&self, plan: &LogicalPlan, predicate: &Expr) -> f64 {
+        match predicate {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::And,
+                right,
+            }) => self.selectivity(plan, left) * self.selectivity(plan, right),
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Or,
+                right,
+            }) => {
+                let (left, right) =
+                    (self.selectivity(plan, left), self.selectivity(plan, right));
+                left + right - left * right
+            }
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            }) => match (left.as_ref(), right.as_ref()) {
+                (Expr::Column(column), Expr::Literal(_, _))
+                | (Expr::Literal(_, _), Expr::Column(column)) => self
+                    .distinct_count(plan, column)
+                    .map_or(self.default_selectivity, |distinct| 1.0 / distinct.max(1.0)),
+                _ => self.default_selectivity,
+            },
+            Expr::Literal(ScalarValue::Boolean(Some(true)), _) => 1.0,
+            _ => self.default_selectivity,
+        }
+    }
+}
+
+/// `expr` without its aliases
+fn unalias(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Alias(alias) => unalias(&alias.expr),
+        expr => expr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{ColumnStatistics, Result, Statistics};
+    use datafusion_expr::logical_plan::builder::LogicalTableSource;
+    use datafusion_expr::test::function_stub::sum;
+    use datafusion_expr::{LogicalPlanBuilder, col, lit};
+
+    /// A scan of the table `name` with the columns `a` and `b`, with `rows`
+    /// rows and `distinct` distinct values of `a` when given. The distinct
+    /// values of `b` are unknown.
+    fn scan(
+        name: &str,
+        statistics: Option<(usize, usize)>,
+    ) -> Result<LogicalPlanBuilder> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::UInt32, false),
+            Field::new("b", DataType::UInt32, false),
+        ]);
+        let mut source = LogicalTableSource::new(Arc::new(schema));
+        if let Some((rows, distinct)) = statistics {
+            source = source.with_statistics(Statistics {
+                num_rows: Precision::Exact(rows),
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![
+                    ColumnStatistics::new_unknown()
+                        .with_distinct_count(Precision::Exact(distinct)),
+                    ColumnStatistics::new_unknown(),
+                ],
+            });
+        }
+        LogicalPlanBuilder::scan(name, Arc::new(source), None)
+    }
+
+    fn row_count(plan: &LogicalPlan) -> Option<f64> {
+        CostModel::new(&ConfigOptions::default()).row_count(plan)
+    }
+
+    fn assert_rows(plan: &LogicalPlan, expected: f64) {
+        let rows = row_count(plan).expect("estimated row count");
+        assert!(
+            (rows - expected).abs() < 1e-6,
+            "{rows} != {expected}\n{plan}"
+        );
+    }
+
+    #[test]
+    fn scan_estimates() -> Result<()> {
+        let plan = scan("t", Some((1_000, 10)))?.build()?;
+        assert_rows(&plan, 1_000.0);
+        let cost_model = CostModel::new(&ConfigOptions::default());
+        assert_eq!(
+            cost_model.distinct_count(&plan, &Column::new(Some("t"), "a")),
+            Some(10.0)
+        );
+        assert_eq!(
+            cost_model.distinct_count(&plan, &Column::new(Some("t"), "b")),
+            None
+        );
+
+        // Distinct values are found through aliases, and never exceed the rows
+        let plan = scan("t", Some((5, 10)))?
+            .project(vec![col("a").alias("x")])?
+            .build()?;
+        assert_eq!(
+            cost_model.distinct_count(&plan, &Column::from_name("x")),
+            Some(5.0)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn filter_selectivity() -> Result<()> {
+        let filter =
+            |predicate: Expr| scan("t", Some((1_000, 10)))?.filter(predicate)?.build();
+        // Equality with a literal selects one of the distinct values
+        assert_rows(&filter(col("a").eq(lit(1u32)))?, 100.0);
+        // Other predicates select the default filter selectivity of 20%
+        assert_rows(&filter(col("b").eq(lit(1u32)))?, 200.0);
+        assert_rows(&filter(col("a").gt(lit(1u32)))?, 200.0);
+        assert_rows(
+            &filter(col("a").eq(lit(1u32)).and(col("b").eq(lit(1u32))))?,
+            20.0,
+        );
+        assert_rows(
+            &filter(col("a").eq(lit(1u32)).or(col("b").eq(lit(1u32))))?,
+            280.0,
+        );
+        assert_rows(&filter(lit(true))?, 1_000.0);
+
+        let options = {
+            let mut options = ConfigOptions::default();
+            options.optimizer.default_filter_selectivity = 50;
+            options
+        };
+        assert_eq!(
+            CostModel::new(&options).row_count(&filter(col("b").eq(lit(1u32)))?),
+            Some(500.0)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn join_cardinality() -> Result<()> {
+        let join = |join_type: JoinType| {
+            scan("t1", Some((1_000, 100)))?
+                .join(
+                    scan("t2", Some((10, 10)))?.build()?,
+                    join_type,
+                    (vec!["t1.a"], vec!["t2.a"]),
+                    None,
+                )?
+                .build()
+        };
+        // Each of the 10 rows of t2 matches the 10 rows of t1 of its key
+        assert_rows(&join(JoinType::Inner)?, 100.0);
+        assert_rows(&join(JoinType::Left)?, 1_000.0);
+        assert_rows(&join(JoinType::Right)?, 100.0);
+        assert_rows(&join(JoinType::LeftSemi)?, 100.0);
+        assert_rows(&join(JoinType::LeftAnti)?, 1_000.0);
+
+        // Without distinct values, the keys are unique on the larger side
+        let plan = scan("t1", Some((1_000, 100)))?
+            .join(
+                scan("t2", Some((10, 10)))?.build()?,
+                JoinType::Inner,
+                (vec!["t1.b"], vec!["t2.b"]),
+                None,
+            )?
+            .build()?;
+        assert_rows(&plan, 10.0);
+        Ok(())
+    }
+
+    #[test]
+    fn aggregate_and_limit_cardinality() -> Result<()> {
+        let plan = scan("t", Some((1_000, 10)))?
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+        assert_rows(&plan, 10.0);
+        // Groups of columns without distinct values are not estimated
+        let plan = scan("t", Some((1_000, 10)))?
+            .aggregate(vec![col("b")], vec![sum(col("a"))])?
+            .build()?;
+        assert_rows(&plan, 1_000.0);
+        let plan = scan("t", None)?
+            .aggregate(Vec::<Expr>::new(), vec![sum(col("a"))])?
+            .build()?;
+        assert_rows(&plan, 1.0);
+
+        let plan = scan("t", Some((1_000, 10)))?.limit(0, Some(5))?.build()?;
+        assert_rows(&plan, 5.0);
+        let plan = scan("t1", Some((1_000, 10)))?
+            .union(scan("t2", Some((10, 10)))?.build()?)?
+            .build()?;
+        assert_rows(&plan, 1_010.0);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_statistics() -> Result<()> {
+        let plan = scan("t", None)?.build()?;
+        assert_eq!(row_count(&plan), None);
+        assert_eq!(
+            CostModel::new(&ConfigOptions::default())
+                .distinct_count(&plan, &Column::new(Some("t"), "a")),
+            None
+        );
+        let plan = scan("t", None)?.filter(col("a").eq(lit(1u32)))?.build()?;
+        assert_eq!(row_count(&plan), None);
+        // A join is only estimated when both of its sides are
+        let plan = scan("t1", Some((1_000, 10)))?
+            .join(
+                scan("t2", None)?.build()?,
+                JoinType::Inner,
+                (vec!["t1.a"], vec!["t2.a"]),
+                None,
+            )?
+            .build()?;
+        assert_eq!(row_count(&plan), None);
+        let plan = scan("t1", Some((1_000, 10)))?
+            .union(scan("t2", None)?.build()?)?
+            .build()?;
+        assert_eq!(row_count(&plan), None);
+        Ok(())
+    }
+}
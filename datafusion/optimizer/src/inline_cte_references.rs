@@ -0,0 +1,216 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`InlineCteReferences`] inlines the CTEs whose result is not shared
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::Result;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_expr::logical_plan::{CteMaterialization, CteReference, LogicalPlan};
+
+/// Optimizer rule that replaces a [`CteReference`] with the plan of its CTE
+/// when the result of the CTE is not worth sharing, that is when the CTE
+///
+/// * is marked `NOT MATERIALIZED`, or
+/// * has no hint and is referenced only once in the query.
+///
+/// The remaining references are left for the executor to evaluate their CTE
+/// once and share the result between them.
+#[derive(Default, Debug)]
+pub struct InlineCteReferences;
+
+impl InlineCteReferences {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for InlineCteReferences {
+    fn name(&self) -> &str {
+        "inline_cte_references"
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        // References to the same CTE have the same name and input
+        let mut references: HashMap<(String, Arc<LogicalPlan>), usize> = HashMap::new();
+        plan.apply_with_subqueries(|plan| {
+            if let LogicalPlan::CteReference(cte) = plan {
+                *references
+                    .entry((cte.name.clone(), Arc::clone(&cte.input)))
+                    .or_default() += 1;
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        if references.is_empty() {
+            return Ok(Transformed::no(plan));
+        }
+
+        // Top down, so that each reference is looked up before the CTEs it
+        // references are inlined into its input
+        plan.transform_down_with_subqueries(|plan| match plan {
+            LogicalPlan::CteReference(cte) if is_inlined(&cte, &references) => {
+                Ok(Transformed::yes(Arc::unwrap_or_clone(cte.input)))
+            }
+            plan => Ok(Transformed::no(plan)),
+        })
+    }
+}
+
+fn is_inlined(
+    cte: &CteReference,
+    references: &HashMap<(String, Arc<LogicalPlan>), usize>,
+) -> bool {
+    match cte.materialization {
+        CteMaterialization::Materialized => false,
+        CteMaterialization::NotMaterialized => true,
+        CteMaterialization::Default => {
+            let key = (cte.name.clone(), Arc::clone(&cte.input));
+            references.get(&key).is_none_or(|count| *count <= 1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OptimizerContext;
+    use crate::assert_optimized_plan_eq_snapshot;
+    use crate::test::*;
+    use datafusion_expr::{JoinType, LogicalPlanBuilder, col};
+
+    macro_rules! assert_optimized_plan_equal {
+        (
+            $plan:expr,
+            @ $expected:literal $(,)?
+        ) => {{
+            let rules: Vec<Arc<dyn crate::OptimizerRule + Send + Sync>> =
+                vec![Arc::new(InlineCteReferences::new())];
+            let optimizer_ctx = OptimizerContext::new();
+            assert_optimized_plan_eq_snapshot!(
+                optimizer_ctx,
+                rules,
+                $plan,
+                @ $expected,
+            )
+        }};
+    }
+
+    fn cte_reference(materialization: CteMaterialization) -> Result<LogicalPlan> {
+        let input = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(vec![col("a"), col("b")])?
+            .alias("cte")?
+            .build()?;
+        Ok(LogicalPlan::CteReference(CteReference::new(
+            "cte",
+            Arc::new(input),
+            materialization,
+        )))
+    }
+
+    fn self_join(materialization: CteMaterialization) -> Result<LogicalPlan> {
+        let left = LogicalPlanBuilder::from(cte_reference(materialization)?)
+            .alias("l")?
+            .build()?;
+        LogicalPlanBuilder::from(left)
+            .join_on(
+                LogicalPlanBuilder::from(cte_reference(materialization)?)
+                    .alias("r")?
+                    .build()?,
+                JoinType::Inner,
+                vec![col("l.a").eq(col("r.a"))],
+            )?
+            .build()
+    }
+
+    #[test]
+    fn inline_single_reference() -> Result<()> {
+        let plan = cte_reference(CteMaterialization::Default)?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        SubqueryAlias: cte
+          Projection: test.a, test.b
+            TableScan: test
+        "
+        )
+    }
+
+    #[test]
+    fn keep_materialized_single_reference() -> Result<()> {
+        let plan = cte_reference(CteMaterialization::Materialized)?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        CteReference: cte MATERIALIZED
+          SubqueryAlias: cte
+            Projection: test.a, test.b
+              TableScan: test
+        "
+        )
+    }
+
+    #[test]
+    fn share_multiple_references() -> Result<()> {
+        let plan = self_join(CteMaterialization::Default)?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Inner Join:  Filter: l.a = r.a
+          SubqueryAlias: l
+            CteReference: cte
+              SubqueryAlias: cte
+                Projection: test.a, test.b
+                  TableScan: test
+          SubqueryAlias: r
+            CteReference: cte
+              SubqueryAlias: cte
+                Projection: test.a, test.b
+                  TableScan: test
+        "
+        )
+    }
+
+    #[test]
+    fn inline_not_materialized_references() -> Result<()> {
+        let plan = self_join(CteMaterialization::NotMaterialized)?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Inner Join:  Filter: l.a = r.a
+          SubqueryAlias: l
+            SubqueryAlias: cte
+              Projection: test.a, test.b
+                TableScan: test
+          SubqueryAlias: r
+            SubqueryAlias: cte
+              Projection: test.a, test.b
+                TableScan: test
+        "
+        )
+    }
+}
@@ -39,6 +39,7 @@
 //! [`LogicalPlan`]: datafusion_expr::LogicalPlan
 //! [`TypeCoercion`]: analyzer::type_coercion::TypeCoercion
 pub mod analyzer;
+pub mod cost_model;
 pub mod eliminate_duplicated_expr;
 pub mod eliminate_filter;
 pub mod eliminate_group_by_constant;
@@ -53,12 +54,14 @@ pub mod eliminate_nested_union {
 pub mod eliminate_outer_join;
 pub mod extract_equijoin_predicate;
 pub mod filter_null_join_keys;
+pub mod inline_cte_references;
 pub mod optimize_projections;
 pub mod optimize_unions;
 pub mod optimizer;
 pub mod propagate_empty_relation;
 pub mod push_down_filter;
 pub mod push_down_limit;
+pub mod reorder_joins;
 pub mod replace_distinct_aggregate;
 pub mod simplify_expressions;
 pub mod single_distinct_to_groupby;
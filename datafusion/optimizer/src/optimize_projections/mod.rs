@@ -261,6 +261,10 @@ fn optimize_projections(
                 fetch,
                 row_lock,
                 only,
+                version,
+                hints,
+                policies,
+                masks,
                 projected_schema: _,
             } = table_scan;
 
@@ -280,6 +284,10 @@ fn optimize_projections(
             .map(|mut scan| {
                 scan.row_lock = row_lock;
                 scan.only = only;
+                scan.version = version;
+                scan.hints = hints;
+                scan.policies = policies;
+                scan.masks = masks;
                 scan
             })
             .map(LogicalPlan::TableScan)
@@ -312,7 +320,7 @@ fn optimize_projections(
                 })
                 .collect::<Result<_>>()?
         }
-        LogicalPlan::Limit(_) => {
+        LogicalPlan::Limit(_) | LogicalPlan::Sample(_) => {
             // Pass index requirements from the parent as well as column indices
             // that appear in this plan's expressions to its child. These operators
             // do not benefit from "small" inputs, so the projection_beneficial
@@ -331,10 +339,13 @@ fn optimize_projections(
         | LogicalPlan::Subquery(_)
         | LogicalPlan::Statement(_)
         | LogicalPlan::Distinct(Distinct::All(_))
-        | LogicalPlan::MatchRecognize(_) => {
+        | LogicalPlan::MatchRecognize(_)
+        | LogicalPlan::PolymorphicTableFunction(_)
+        | LogicalPlan::CteReference(_) => {
             // These plans require all their fields, and their children should
             // be treated as final plans -- otherwise, we may have schema a
-            // mismatch.
+            // mismatch. The input of a `CteReference` is shared with the other
+            // references to the CTE, so it must not depend on this reference.
             // TODO: For some subquery variants (e.g. a subquery arising from an
             //       EXISTS expression), we may not need to require all indices.
             plan.inputs()
@@ -372,6 +383,7 @@ fn optimize_projections(
         | LogicalPlan::DescribeTable(_)
         | LogicalPlan::CopyFrom(_)
         | LogicalPlan::JsonTable(_)
+        | LogicalPlan::XmlTable(_)
         | LogicalPlan::GraphTable(_) => {
             // These operators have no inputs, so stop the optimization process.
             return Ok(Transformed::no(plan));
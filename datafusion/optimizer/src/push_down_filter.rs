@@ -2851,6 +2851,10 @@ mod tests {
             fetch: None,
             row_lock: None,
             only: false,
+            version: None,
+            hints: Default::default(),
+            policies: vec![],
+            masks: vec![],
         });
 
         Ok(LogicalPlanBuilder::from(table_scan))
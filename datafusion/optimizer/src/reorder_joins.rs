@@ -0,0 +1,421 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ReorderJoins`] reorders inner joins using the statistics of their tables
+
+use std::sync::Arc;
+
+use crate::cost_model::CostModel;
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{Column, DFSchema, NullEquality, Result};
+use datafusion_expr::logical_plan::{Join, JoinConstraint, JoinType, Projection};
+use datafusion_expr::utils::{
+    can_hash, conjunction, find_valid_equijoin_key_pair, split_conjunction_owned,
+};
+use datafusion_expr::{BinaryExpr, Expr, ExprSchemable, LogicalPlan, Operator};
+
+/// Reorders trees of inner joins by their estimated cost.
+///
+/// The tables joined by a tree of inner joins, with all the join conditions,
+/// are joined again greedily: starting from the table with the fewest rows,
+/// the next table is the one joined to the previous ones by a condition that
+/// gives the fewest rows. The new order is only used when its estimated cost,
+/// the total number of rows produced by the joins, is lower than the cost of
+/// the syntactic order.
+///
+/// The number of rows are estimated by the [`CostModel`] from the statistics
+/// of the tables, such as those stored by `ANALYZE`, so joins keep their
+/// order unless the number of rows of every joined table is known. The rule
+/// is disabled by the `datafusion.optimizer.enable_join_reordering` option.
+///
+/// For example, when `small` is much smaller than `big`,
+///
+/// ```text
+/// Inner Join: medium.b = small.b
+///   Inner Join: big.a = medium.a
+///     TableScan: big
+///     TableScan: medium
+///   TableScan: small
+/// ```
+///
+/// is reordered to
+///
+/// ```text
+/// Projection: big.a, medium.a, medium.b, small.b
+///   Inner Join: medium.a = big.a
+///     Inner Join: small.b = medium.b
+///       TableScan: small
+///       TableScan: medium
+///     TableScan: big
+/// ```
+#[derive(Default, Debug)]
+pub struct ReorderJoins;
+
+impl ReorderJoins {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for ReorderJoins {
+    fn name(&self) -> &str {
+        "reorder_joins"
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let options = config.options();
+        if !options.optimizer.enable_join_reordering {
+            return Ok(Transformed::no(plan));
+        }
+        let cost_model = CostModel::new(&options);
+        plan.transform_down(|plan| {
+            if !is_reorderable_join(&plan) {
+                return Ok(Transformed::no(plan));
+            }
+            let reordered = reorder_joins(&plan, &cost_model)?;
+            // The inputs of the joins are reordered when the traversal reaches
+            // them, and the reordered joins are left as they are
+            Ok(match reordered {
+                Some(reordered) => {
+                    Transformed::new(reordered, true, TreeNodeRecursion::Continue)
+                }
+                None => Transformed::no(plan),
+            })
+        })
+    }
+}
+
+/// Returns true if `plan` is an inner join that can be joined in any order
+/// with the other inner joins of its tree
+fn is_reorderable_join(plan: &LogicalPlan) -> bool {
+    matches!(
+        plan,
+        LogicalPlan::Join(Join {
+            join_type: JoinType::Inner,
+            join_constraint: JoinConstraint::On,
+            null_equality: NullEquality::NullEqualsNothing,
+            ..
+        })
+    )
+}
+
+/// Collects the inputs of the tree of inner joins `plan`, and its join
+/// conditions
+fn flatten_joins(
+    plan: &LogicalPlan,
+    inputs: &mut Vec<LogicalPlan>,
+    predicates: &mut Vec<Expr>,
+) {
+    match plan {
+        LogicalPlan::Join(join) if is_reorderable_join(plan) => {
+            flatten_joins(&join.left, inputs, predicates);
+            flatten_joins(&join.right, inputs, predicates);
+            predicates.extend(
+                join.on
+                    .iter()
+                    .map(|(left, right)| left.clone().eq(right.clone())),
+            );
+            if let Some(filter) = &join.filter {
+                predicates.extend(split_conjunction_owned(filter.clone()));
+            }
+        }
+        _ => inputs.push(plan.clone()),
+    }
+}
+
+/// The total number of rows produced by the joins of the tree of inner
+/// joins `plan`
+fn joins_cost(plan: &LogicalPlan, cost_model: &CostModel) -> Option<f64> {
+    match plan {
+        LogicalPlan::Join(join) if is_reorderable_join(plan) => Some(
+            cost_model.row_count(plan)?
+                + joins_cost(&join.left, cost_model)?
+                + joins_cost(&join.right, cost_model)?,
+        ),
+        _ => Some(0.0),
+    }
+}
+
+/// Reorders the tree of inner joins `plan`, returning `None` when it is
+/// kept in its order
+fn reorder_joins(
+    plan: &LogicalPlan,
+    cost_model: &CostModel,
+) -> Result<Option<LogicalPlan>> {
+    let mut inputs = vec![];
+    let mut predicates = vec![];
+    flatten_joins(plan, &mut inputs, &mut predicates);
+    if inputs.len() < 3 {
+        return Ok(None);
+    }
+    // Conditions that reference columns of no input, e.g. outer references,
+    // or that are volatile are kept where they are
+    let covered =
+        |column: &Column| inputs.iter().any(|input| input.schema().has_column(column));
+    if predicates.iter().any(|predicate| {
+        predicate.is_volatile() || !predicate.column_refs().into_iter().all(covered)
+    }) {
+        return Ok(None);
+    }
+    let Some(rows) = inputs
+        .iter()
+        .map(|input| cost_model.row_count(input))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(None);
+    };
+    let Some(original_cost) = joins_cost(plan, cost_model) else {
+        return Ok(None);
+    };
+
+    let Some(first) = (0..inputs.len()).min_by(|a, b| rows[*a].total_cmp(&rows[*b]))
+    else {
+        return Ok(None);
+    };
+    let mut order = vec![first];
+    let mut remaining = (0..inputs.len())
+        .filter(|index| *index != first)
+        .collect::<Vec<_>>();
+    let mut joined = inputs[first].clone();
+    let mut cost = 0.0;
+    while !remaining.is_empty() {
+        // Inputs joined to the previous ones by a condition come first, so
+        // that cross joins are only used when nothing else is left
+        let connected = remaining
+            .iter()
+            .copied()
+            .filter(|index| {
+                predicates.iter().any(|predicate| {
+                    connects(predicate, joined.schema(), inputs[*index].schema())
+                })
+            })
+            .collect::<Vec<_>>();
+        let candidates = if connected.is_empty() {
+            remaining.clone()
+        } else {
+            connected
+        };
+
+        let mut best: Option<(f64, usize, LogicalPlan, Vec<Expr>)> = None;
+        for index in candidates {
+            let (join, rest) =
+                join_inputs(joined.clone(), inputs[index].clone(), predicates.clone())?;
+            let Some(join_rows) = cost_model.row_count(&join) else {
+                return Ok(None);
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(best_rows, ..)| join_rows < *best_rows)
+            {
+                best = Some((join_rows, index, join, rest));
+            }
+        }
+        let Some((join_rows, index, join, rest)) = best else {
+            return Ok(None);
+        };
+        cost += join_rows;
+        order.push(index);
+        remaining.retain(|remaining| *remaining != index);
+        joined = join;
+        predicates = rest;
+    }
+
+    if cost >= original_cost || order.iter().copied().eq(0..inputs.len()) {
+        return Ok(None);
+    }
+    // The columns keep the order of the original joins
+    let schema = Arc::clone(plan.schema());
+    let expr = schema.columns().into_iter().map(Expr::Column).collect();
+    Projection::try_new_with_schema(expr, Arc::new(joined), schema)
+        .map(LogicalPlan::Projection)
+        .map(Some)
+}
+
+/// Returns true if `predicate` compares columns of both `left` and `right`
+fn connects(predicate: &Expr, left: &DFSchema, right: &DFSchema) -> bool {
+    let columns = predicate.column_refs();
+    columns.iter().any(|column| left.has_column(column))
+        && columns.iter().any(|column| right.has_column(column))
+        && columns
+            .iter()
+            .all(|column| left.has_column(column) || right.has_column(column))
+}
+
+/// Inner joins `left` and `right` on the conditions of `predicates` that
+/// only reference their columns, returning the join and the other conditions
+fn join_inputs(
+    left: LogicalPlan,
+    right: LogicalPlan,
+    predicates: Vec<Expr>,
+) -> Result<(LogicalPlan, Vec<Expr>)> {
+    let (left_schema, right_schema) = (left.schema(), right.schema());
+    let mut on = vec![];
+    let mut filters = vec![];
+    let mut rest = vec![];
+    for predicate in predicates {
+        if !predicate.column_refs().iter().all(|column| {
+            left_schema.has_column(column) || right_schema.has_column(column)
+        }) {
+            rest.push(predicate);
+            continue;
+        }
+        if let Expr::BinaryExpr(BinaryExpr {
+            left: left_key,
+            op: Operator::Eq,
+            right: right_key,
+        }) = &predicate
+            && let Some((left_key, right_key)) = find_valid_equijoin_key_pair(
+                left_key,
+                right_key,
+                left_schema,
+                right_schema,
+            )?
+            && can_hash(&left_key.get_type(left_schema)?)
+            && can_hash(&right_key.get_type(right_schema)?)
+        {
+            on.push((left_key, right_key));
+        } else {
+            filters.push(predicate);
+        }
+    }
+    let join = Join::try_new(
+        Arc::new(left),
+        Arc::new(right),
+        on,
+        conjunction(filters),
+        JoinType::Inner,
+        JoinConstraint::On,
+        NullEquality::NullEqualsNothing,
+    )?;
+    Ok((LogicalPlan::Join(join), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OptimizerContext;
+    use crate::assert_optimized_plan_eq_snapshot;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::config::ConfigOptions;
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{ColumnStatistics, Statistics};
+    use datafusion_expr::logical_plan::builder::LogicalTableSource;
+    use datafusion_expr::{LogicalPlanBuilder, col};
+
+    macro_rules! assert_optimized_plan_equal {
+        (
+            $plan:expr,
+            @ $expected:literal $(,)?
+        ) => {{
+            let optimizer_ctx = OptimizerContext::new().with_max_passes(1);
+            let rules: Vec<Arc<dyn crate::OptimizerRule + Send + Sync>> = vec![Arc::new(ReorderJoins::new())];
+            assert_optimized_plan_eq_snapshot!(
+                optimizer_ctx,
+                rules,
+                $plan,
+                @ $expected,
+            )
+        }};
+    }
+
+    /// A scan of the table `name` with the columns `a` and `b`, with `rows`
+    /// rows when given, and as many distinct values of each column
+    fn scan(name: &str, rows: Option<usize>) -> Result<LogicalPlanBuilder> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::UInt32, false),
+            Field::new("b", DataType::UInt32, false),
+        ]);
+        let mut source = LogicalTableSource::new(Arc::new(schema.clone()));
+        if let Some(rows) = rows {
+            let column = ColumnStatistics::new_unknown()
+                .with_distinct_count(Precision::Exact(rows));
+            source = source.with_statistics(Statistics {
+                num_rows: Precision::Exact(rows),
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![column.clone(), column],
+            });
+        }
+        LogicalPlanBuilder::scan(name, Arc::new(source), None)
+    }
+
+    fn big_medium_small(small_rows: Option<usize>) -> Result<LogicalPlan> {
+        scan("big", Some(100_000))?
+            .join_on(
+                scan("medium", Some(1_000))?.build()?,
+                JoinType::Inner,
+                vec![col("big.a").eq(col("medium.a"))],
+            )?
+            .join_on(
+                scan("small", small_rows)?.build()?,
+                JoinType::Inner,
+                vec![col("medium.b").eq(col("small.b"))],
+            )?
+            .build()
+    }
+
+    #[test]
+    fn reorder_by_row_count() -> Result<()> {
+        let plan = big_medium_small(Some(10))?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Projection: big.a, big.b, medium.a, medium.b, small.a, small.b
+          Inner Join: medium.a = big.a
+            Inner Join: small.b = medium.b
+              TableScan: small
+              TableScan: medium
+            TableScan: big
+        "
+        )
+    }
+
+    #[test]
+    fn keep_order_without_statistics() -> Result<()> {
+        let plan = big_medium_small(None)?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Inner Join:  Filter: medium.b = small.b
+          Inner Join:  Filter: big.a = medium.a
+            TableScan: big
+            TableScan: medium
+          TableScan: small
+        "
+        )
+    }
+
+    #[test]
+    fn keep_order_when_disabled() -> Result<()> {
+        let plan = big_medium_small(Some(10))?;
+        let mut options = ConfigOptions::default();
+        options.optimizer.enable_join_reordering = false;
+        let optimizer_ctx = OptimizerContext::new_with_config_options(Arc::new(options));
+        let optimized = ReorderJoins::new().rewrite(plan, &optimizer_ctx)?;
+        assert!(!optimized.transformed);
+        Ok(())
+    }
+}
@@ -0,0 +1,446 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AsyncSqlToRel`]: plans SQL statements with an [`AsyncContextProvider`]
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, FieldRef, SchemaRef};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::file_options::file_type::FileType;
+use datafusion_common::{DataFusionError, Result, TableReference, plan_err};
+use datafusion_expr::planner::{
+    AsyncContextProvider, AuditLogger, ColumnMask, ContextProvider, ExprPlanner,
+    RelationPlanner, RowSecurityPolicy, StatementPlanner, StatementRewriter,
+    TableVersion, TypePlanner, WordReservation,
+};
+use datafusion_expr::{
+    AggregateUDF, CreatePropertyGraph, Expr, LogicalPlan, PolymorphicTableFunctionImpl,
+    PrivilegeStore, ScalarUDF, TableSource, WindowUDF,
+};
+use sqlparser::ast::{Expr as SQLExpr, ObjectName, visit_expressions};
+
+use crate::parser::{
+    CopyFromSource, CopyFromStatement, CopyToSource, CopyToStatement,
+    Statement as DFStatement,
+};
+use crate::planner::{ParserOptions, SqlToRel};
+use crate::resolve::{resolve_graph_names, resolve_object_names};
+use crate::utils::normalize_ident;
+
+/// SQL query planner whose tables, functions and file types are resolved
+/// asynchronously by an [`AsyncContextProvider`], such as a remote catalog.
+///
+/// Before planning a statement, the planner collects the tables and views it
+/// references, the functions it calls and the file types of its `COPY`
+/// statements, and resolves them with the provider. The statement is then
+/// planned by [`SqlToRel`] without accessing the catalog.
+///
+/// The [`StatementRewriter`]s of the provider are applied before the
+/// references are collected, so that the references of the rewritten
+/// statement are resolved.
+pub struct AsyncSqlToRel<'a, P: AsyncContextProvider + ?Sized> {
+    provider: &'a P,
+    options: ParserOptions,
+}
+
+impl<'a, P: AsyncContextProvider + ?Sized> AsyncSqlToRel<'a, P> {
+    /// Create a new query planner.
+    ///
+    /// The query planner derives the parser options from the context provider.
+    pub fn new(provider: &'a P) -> Self {
+        let options =
+            ParserOptions::from(&provider.context_provider().options().sql_parser);
+        Self::new_with_options(provider, options)
+    }
+
+    /// Create a new query planner with the given parser options.
+    pub fn new_with_options(provider: &'a P, options: ParserOptions) -> Self {
+        Self { provider, options }
+    }
+
+    /// Generate a logical plan from a DataFusion SQL statement
+    pub async fn statement_to_plan(&self, statement: DFStatement) -> Result<LogicalPlan> {
+        let (statement, references) = {
+            let provider = ResolvedContextProvider::new(self.provider.context_provider());
+            let planner = SqlToRel::new_with_options(&provider, self.options);
+            let statement = planner.rewrite_df_statement(statement)?;
+            let references = References::collect(&statement, &planner)?;
+            (statement, references)
+        };
+        let resolved = self.resolve(references).await?;
+
+        let provider = ResolvedContextProvider {
+            resolved,
+            rewrite: false,
+            ..ResolvedContextProvider::new(self.provider.context_provider())
+        };
+        SqlToRel::new_with_options(&provider, self.options).statement_to_plan(statement)
+    }
+
+    async fn resolve(&self, references: References) -> Result<Resolved> {
+        let mut resolved = Resolved::default();
+        for table in references.tables {
+            // A missing table is only an error if the planner needs it, which
+            // it does not for `CREATE EXTERNAL TABLE` or `SHOW TABLES`
+            let source = self
+                .provider
+                .get_table_source(table.clone())
+                .await
+                .map_err(Arc::new);
+            resolved.tables.insert(table, source);
+        }
+        for name in references.functions {
+            if let Some(udf) = self.provider.get_function_meta(&name).await? {
+                resolved.functions.insert(name.clone(), udf);
+            }
+            if let Some(udaf) = self.provider.get_aggregate_meta(&name).await? {
+                resolved.aggregates.insert(name.clone(), udaf);
+            }
+            if let Some(udwf) = self.provider.get_window_meta(&name).await? {
+                resolved.windows.insert(name, udwf);
+            }
+        }
+        for ext in references.file_types {
+            let file_type = self.provider.get_file_type(&ext).await.map_err(Arc::new);
+            resolved.file_types.insert(ext, file_type);
+        }
+        Ok(resolved)
+    }
+}
+
+/// The references of a statement to resolve before planning it
+struct References {
+    tables: BTreeSet<TableReference>,
+    functions: BTreeSet<String>,
+    file_types: BTreeSet<String>,
+}
+
+impl References {
+    fn collect<S: ContextProvider>(
+        statement: &DFStatement,
+        planner: &SqlToRel<S>,
+    ) -> Result<Self> {
+        let (relations, _) = resolve_object_names(statement);
+        let mut tables = relations
+            .into_iter()
+            .map(|name| planner.object_name_to_table_reference(name))
+            .collect::<Result<BTreeSet<_>>>()?;
+        // The element tables of the property graphs queried by GRAPH_TABLE
+        // are scanned, but only named by the definition of the graph
+        for name in resolve_graph_names(statement) {
+            let name = planner.object_name_to_table_reference(name)?;
+            if let Some(graph) = planner.context_provider.get_property_graph(&name) {
+                let vertices = graph.vertex_tables.iter().map(|vertex| &vertex.table);
+                let edges = graph.edge_tables.iter().map(|edge| &edge.table);
+                tables.extend(vertices.chain(edges).cloned());
+            }
+        }
+        let mut references = Self {
+            tables,
+            functions: BTreeSet::new(),
+            file_types: BTreeSet::new(),
+        };
+        references.collect_statement(statement);
+        Ok(references)
+    }
+
+    fn collect_statement(&mut self, statement: &DFStatement) {
+        match statement {
+            DFStatement::Statement(statement) => self.collect_functions(statement),
+            DFStatement::Explain(explain) => self.collect_statement(&explain.statement),
+            DFStatement::CopyTo(CopyToStatement {
+                source,
+                target,
+                stored_as,
+                ..
+            }) => {
+                if let CopyToSource::Query(query) = source {
+                    self.collect_functions(query);
+                }
+                self.collect_file_type(stored_as.as_deref(), Some(target.as_str()));
+            }
+            DFStatement::CopyFrom(CopyFromStatement {
+                source, stored_as, ..
+            }) => {
+                let url = match source {
+                    CopyFromSource::File(url) => Some(url.as_str()),
+                    CopyFromSource::Stdin | CopyFromSource::Program(_) => None,
+                };
+                self.collect_file_type(stored_as.as_deref(), url);
+            }
+            DFStatement::PortionOf(statement) => {
+                for expr in statement.exprs() {
+                    self.collect_functions(expr);
+                }
+            }
+            DFStatement::CreateExternalTable(_)
+            | DFStatement::Reset(_)
+            | DFStatement::CreateUser(_)
+            | DFStatement::AlterUser(_)
+            | DFStatement::DropUser(_) => {}
+        }
+    }
+
+    /// Collects the names of the functions called in `node`, normalized as
+    /// the planner looks them up
+    fn collect_functions<V: sqlparser::ast::Visit>(&mut self, node: &V) {
+        let _ = visit_expressions(node, |expr| {
+            if let SQLExpr::Function(function) = expr {
+                self.functions.insert(function_name(&function.name));
+            }
+            ControlFlow::<()>::Continue(())
+        });
+    }
+
+    /// Collects the file type given by `STORED AS`, or else by the extension
+    /// of the file
+    fn collect_file_type(&mut self, stored_as: Option<&str>, path: Option<&str>) {
+        if let Some(stored_as) = stored_as {
+            self.file_types.insert(stored_as.to_string());
+        } else if let Some(ext) = path
+            .and_then(|path| Path::new(path).extension())
+            .and_then(|ext| ext.to_str())
+        {
+            self.file_types.insert(ext.to_lowercase());
+        }
+    }
+}
+
+fn function_name(name: &ObjectName) -> String {
+    match name.0.as_slice() {
+        [part] => match part.as_ident() {
+            Some(ident) => normalize_ident(ident.clone()),
+            None => name.to_string(),
+        },
+        _ => name.to_string(),
+    }
+}
+
+/// The references of a statement resolved by an [`AsyncContextProvider`]
+#[derive(Default)]
+struct Resolved {
+    tables: HashMap<TableReference, Result<Arc<dyn TableSource>, Arc<DataFusionError>>>,
+    functions: HashMap<String, Arc<ScalarUDF>>,
+    aggregates: HashMap<String, Arc<AggregateUDF>>,
+    windows: HashMap<String, Arc<WindowUDF>>,
+    file_types: HashMap<String, Result<Arc<dyn FileType>, Arc<DataFusionError>>>,
+}
+
+/// A [`ContextProvider`] answering from the resolved references of a
+/// statement, and from the provider of everything else
+struct ResolvedContextProvider<'a> {
+    base: &'a dyn ContextProvider,
+    resolved: Resolved,
+    /// Whether the statement is to be rewritten, or has already been
+    rewrite: bool,
+}
+
+impl<'a> ResolvedContextProvider<'a> {
+    fn new(base: &'a dyn ContextProvider) -> Self {
+        Self {
+            base,
+            resolved: Resolved::default(),
+            rewrite: true,
+        }
+    }
+}
+
+impl ContextProvider for ResolvedContextProvider<'_> {
+    fn get_table_source(&self, name: TableReference) -> Result<Arc<dyn TableSource>> {
+        match self.resolved.tables.get(&name) {
+            Some(Ok(source)) => Ok(Arc::clone(source)),
+            Some(Err(err)) => Err(DataFusionError::Shared(Arc::clone(err))),
+            None => plan_err!("Table '{name}' was not resolved before planning"),
+        }
+    }
+
+    fn get_file_type(&self, ext: &str) -> Result<Arc<dyn FileType>> {
+        match self.resolved.file_types.get(ext) {
+            Some(Ok(file_type)) => Ok(Arc::clone(file_type)),
+            Some(Err(err)) => Err(DataFusionError::Shared(Arc::clone(err))),
+            None => self.base.get_file_type(ext),
+        }
+    }
+
+    fn get_table_source_at(
+        &self,
+        name: TableReference,
+        version: &TableVersion,
+    ) -> Result<Arc<dyn TableSource>> {
+        self.base.get_table_source_at(name, version)
+    }
+
+    fn get_table_function_source(
+        &self,
+        name: &str,
+        args: Vec<Expr>,
+    ) -> Result<Arc<dyn TableSource>> {
+        self.base.get_table_function_source(name, args)
+    }
+
+    fn get_table_function_source_with_names(
+        &self,
+        name: &str,
+        args: Vec<Expr>,
+        arg_names: Vec<Option<String>>,
+    ) -> Result<Arc<dyn TableSource>> {
+        self.base
+            .get_table_function_source_with_names(name, args, arg_names)
+    }
+
+    fn get_polymorphic_table_function(
+        &self,
+        name: &str,
+    ) -> Option<Arc<dyn PolymorphicTableFunctionImpl>> {
+        self.base.get_polymorphic_table_function(name)
+    }
+
+    fn get_property_graph(
+        &self,
+        name: &TableReference,
+    ) -> Option<Arc<CreatePropertyGraph>> {
+        self.base.get_property_graph(name)
+    }
+
+    fn get_collation(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.base.get_collation(name)
+    }
+
+    fn get_character_set(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.base.get_character_set(name)
+    }
+
+    fn get_row_security_policies(&self, name: &TableReference) -> Vec<RowSecurityPolicy> {
+        self.base.get_row_security_policies(name)
+    }
+
+    fn get_column_masks(&self, name: &TableReference) -> Vec<ColumnMask> {
+        self.base.get_column_masks(name)
+    }
+
+    fn get_current_role(&self) -> Option<String> {
+        self.base.get_current_role()
+    }
+
+    fn get_privilege_store(&self) -> Option<Arc<PrivilegeStore>> {
+        self.base.get_privilege_store()
+    }
+
+    fn create_cte_work_table(
+        &self,
+        name: &str,
+        schema: SchemaRef,
+    ) -> Result<Arc<dyn TableSource>> {
+        self.base.create_cte_work_table(name, schema)
+    }
+
+    fn get_expr_planners(&self) -> &[Arc<dyn ExprPlanner>] {
+        self.base.get_expr_planners()
+    }
+
+    fn get_relation_planners(&self) -> &[Arc<dyn RelationPlanner>] {
+        self.base.get_relation_planners()
+    }
+
+    fn get_statement_planners(&self) -> &[Arc<dyn StatementPlanner>] {
+        self.base.get_statement_planners()
+    }
+
+    fn get_statement_rewriters(&self) -> &[Arc<dyn StatementRewriter>] {
+        if self.rewrite {
+            self.base.get_statement_rewriters()
+        } else {
+            &[]
+        }
+    }
+
+    fn get_audit_loggers(&self) -> &[Arc<dyn AuditLogger>] {
+        self.base.get_audit_loggers()
+    }
+
+    fn get_type_planner(&self) -> Option<Arc<dyn TypePlanner>> {
+        self.base.get_type_planner()
+    }
+
+    fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        match self.resolved.functions.get(name) {
+            Some(udf) => Some(Arc::clone(udf)),
+            None => self.base.get_function_meta(name),
+        }
+    }
+
+    fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        match self.resolved.aggregates.get(name) {
+            Some(udaf) => Some(Arc::clone(udaf)),
+            None => self.base.get_aggregate_meta(name),
+        }
+    }
+
+    fn get_window_meta(&self, name: &str) -> Option<Arc<WindowUDF>> {
+        match self.resolved.windows.get(name) {
+            Some(udwf) => Some(Arc::clone(udwf)),
+            None => self.base.get_window_meta(name),
+        }
+    }
+
+    fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType> {
+        self.base.get_variable_type(variable_names)
+    }
+
+    fn get_variable_field(&self, variable_names: &[String]) -> Option<FieldRef> {
+        self.base.get_variable_field(variable_names)
+    }
+
+    fn options(&self) -> &ConfigOptions {
+        self.base.options()
+    }
+
+    fn udf_names(&self) -> Vec<String> {
+        with_names(self.base.udf_names(), self.resolved.functions.keys())
+    }
+
+    fn udaf_names(&self) -> Vec<String> {
+        with_names(self.base.udaf_names(), self.resolved.aggregates.keys())
+    }
+
+    fn udwf_names(&self) -> Vec<String> {
+        with_names(self.base.udwf_names(), self.resolved.windows.keys())
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        self.base.table_names()
+    }
+
+    fn word_reservation(&self, word: &str) -> Option<WordReservation> {
+        self.base.word_reservation(word)
+    }
+}
+
+/// Adds the names of resolved functions to `names`
+fn with_names<'a>(
+    mut names: Vec<String>,
+    resolved: impl Iterator<Item = &'a String>,
+) -> Vec<String> {
+    names.extend(resolved.cloned());
+    names.sort();
+    names.dedup();
+    names
+}
@@ -0,0 +1,121 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+
+use crate::planner::{ContextProvider, SqlToRel};
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::LogicalPlan;
+use datafusion_expr::planner::{AuditRecord, REDACTED_SQL};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// The SQL text of `statement` to record once it is planned, or `None`
+    /// when the [`ContextProvider`] has no [`AuditLogger`]
+    ///
+    /// The text is [`REDACTED_SQL`] when the literals of the statement can not
+    /// be found, because its text does not tokenize, so that auditing never
+    /// fails the statement nor records its values.
+    ///
+    /// [`AuditLogger`]: datafusion_expr::planner::AuditLogger
+    pub(crate) fn audited_sql(&self, statement: &impl Display) -> Option<String> {
+        if self.context_provider.get_audit_loggers().is_empty() {
+            return None;
+        }
+        Some(
+            parameterize_sql(&statement.to_string())
+                .unwrap_or_else(|_| REDACTED_SQL.to_string()),
+        )
+    }
+
+    /// Record `plan`, planned from the statement of the [`Self::audited_sql`]
+    /// `sql`, with the [`AuditLogger`]s of the [`ContextProvider`]
+    ///
+    /// [`AuditLogger`]: datafusion_expr::planner::AuditLogger
+    pub(crate) fn audit(
+        &self,
+        sql: Option<String>,
+        plan: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let Some(sql) = sql else {
+            return Ok(plan);
+        };
+        let record = AuditRecord {
+            kind: statement_kind(&plan).to_string(),
+            objects: self.resolved_required_privileges(&plan)?,
+            sql,
+        };
+        for logger in self.context_provider.get_audit_loggers() {
+            logger.log_statement(&record)?;
+        }
+        Ok(plan)
+    }
+}
+
+/// The kind of the statement planned to `plan`
+fn statement_kind(plan: &LogicalPlan) -> &str {
+    match plan {
+        LogicalPlan::Dml(dml) => dml.name(),
+        LogicalPlan::Merge(_) => "Merge",
+        LogicalPlan::Ddl(ddl) => ddl.name(),
+        LogicalPlan::Statement(statement) => statement.name(),
+        LogicalPlan::Explain(_) => "Explain",
+        LogicalPlan::Analyze(_) => "Analyze",
+        LogicalPlan::Copy(_) => "CopyTo",
+        LogicalPlan::CopyFrom(_) => "CopyFrom",
+        LogicalPlan::DescribeTable(_) => "DescribeTable",
+        LogicalPlan::Extension(extension) => extension.node.name(),
+        _ => "Query",
+    }
+}
+
+/// Replace the literals of the SQL text `sql` by placeholders, numbered
+/// after the placeholders it already has
+fn parameterize_sql(sql: &str) -> Result<String> {
+    let tokens = Tokenizer::new(&GenericDialect {}, sql)
+        .tokenize()
+        .map_err(|err| DataFusionError::from(ParserError::from(err)))?;
+    let mut last = tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Placeholder(placeholder) => {
+                placeholder.strip_prefix('$')?.parse::<usize>().ok()
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0);
+    Ok(tokens
+        .iter()
+        .map(|token| match token {
+            Token::Number(_, _)
+            | Token::SingleQuotedString(_)
+            | Token::DollarQuotedString(_)
+            | Token::NationalStringLiteral(_)
+            | Token::EscapedStringLiteral(_)
+            | Token::UnicodeStringLiteral(_)
+            | Token::HexStringLiteral(_)
+            | Token::SingleQuotedByteStringLiteral(_) => {
+                last += 1;
+                format!("${last}")
+            }
+            token => token.to_string(),
+        })
+        .collect())
+}
@@ -15,27 +15,178 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
 use arrow::datatypes::{Field, Schema, SchemaRef};
 use datafusion_common::{
-    Result, not_impl_err, plan_err,
-    tree_node::{TreeNode, TreeNodeRecursion},
+    Column, DFSchema, Diagnostic, Result, Span, internal_err, not_impl_err, plan_err,
+    tree_node::{Transformed, TreeNode, TreeNodeRecursion},
 };
-use datafusion_expr::{LogicalPlan, LogicalPlanBuilder, TableSource};
-use sqlparser::ast::{Ident, Query, SelectItem, SetExpr, SetOperator, With};
+use datafusion_expr::{
+    CteMaterialization, CteReference, Expr, Filter, LogicalPlan, LogicalPlanBuilder,
+    TableSource, lit, when,
+};
+use sqlparser::ast::{
+    self, CteAsMaterialized, Ident, Query, SelectItem, SetExpr, SetOperator, With,
+};
+
+/// The `SEARCH` clause of a recursive query, with its names normalized
+struct SearchClause {
+    breadth_first: bool,
+    /// Columns ordering the rows of each level or path
+    columns: Vec<String>,
+    /// Column holding the sort key of each row
+    sequence_column: String,
+}
+
+/// The `CYCLE` clause of a recursive query, with its names normalized
+struct CycleClause {
+    /// Columns whose values identify a row
+    columns: Vec<String>,
+    /// Column set to `mark_value` on the rows closing a cycle, and to
+    /// `mark_default` on the others
+    mark_column: String,
+    mark_value: Expr,
+    mark_default: Expr,
+    /// Column holding the rows visited on the way to each row
+    path_column: String,
+}
+
+/// The columns the `SEARCH` and `CYCLE` clauses add to a recursive query,
+/// after those of the query itself
+struct RecursiveColumns {
+    /// Number of columns of the query itself
+    query_columns: usize,
+    /// The `SEARCH` clause and the indices of its columns
+    search: Option<(SearchClause, Vec<usize>)>,
+    /// The `CYCLE` clause and the indices of its columns
+    cycle: Option<(CycleClause, Vec<usize>)>,
+}
+
+impl RecursiveColumns {
+    /// Resolves the clauses against the static term of `cte_name`
+    fn try_new(
+        cte_name: &str,
+        search: Option<SearchClause>,
+        cycle: Option<CycleClause>,
+        schema: &DFSchema,
+    ) -> Result<Self> {
+        let mut added = vec![];
+        if let Some(search) = &search {
+            added.push(("SEARCH", &search.sequence_column));
+        }
+        if let Some(cycle) = &cycle {
+            added.push(("CYCLE", &cycle.mark_column));
+            added.push(("CYCLE", &cycle.path_column));
+        }
+        for (i, (clause, name)) in added.iter().enumerate() {
+            if schema.fields().iter().any(|f| f.name() == *name)
+                || added[..i].iter().any(|(_, other)| other == name)
+            {
+                return plan_err!(
+                    "{clause} column {name} conflicts with a column of {cte_name}"
+                );
+            }
+        }
+
+        let search = match search {
+            Some(search) => {
+                let indices =
+                    column_indices("SEARCH", cte_name, &search.columns, schema)?;
+                Some((search, indices))
+            }
+            None => None,
+        };
+        let cycle = match cycle {
+            Some(cycle) => {
+                let indices = column_indices("CYCLE", cte_name, &cycle.columns, schema)?;
+                Some((cycle, indices))
+            }
+            None => None,
+        };
+        Ok(Self {
+            query_columns: schema.fields().len(),
+            search,
+            cycle,
+        })
+    }
+
+    /// The clause named in errors about the recursive query
+    fn clause(&self) -> &'static str {
+        if self.cycle.is_some() {
+            "CYCLE"
+        } else {
+            "SEARCH"
+        }
+    }
+
+    /// Makes the recursive term also select the columns of the row each of
+    /// its rows is derived from, so they can be extended
+    fn select_previous(&self, mut set_expr: SetExpr) -> Result<SetExpr> {
+        let SetExpr::Select(select) = &mut set_expr else {
+            return not_impl_err!(
+                "{} clause with a recursive term that is not a SELECT",
+                self.clause()
+            );
+        };
+        let search = self.search.iter().map(|(s, _)| &s.sequence_column);
+        let cycle = self.cycle.iter().map(|(c, _)| &c.path_column);
+        for name in search.chain(cycle) {
+            select
+                .projection
+                .push(SelectItem::UnnamedExpr(ast::Expr::Identifier(
+                    Ident::with_quote('"', name),
+                )));
+        }
+        Ok(set_expr)
+    }
+}
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans the CTEs of `with` into `planner_context`, returning their
+    /// names and the spans of the names
     pub(super) fn plan_with_clause(
         &self,
         with: With,
         planner_context: &mut PlannerContext,
-    ) -> Result<()> {
+    ) -> Result<Vec<(String, Option<Span>)>> {
         let is_recursive = with.recursive;
+        // The SEARCH and CYCLE clauses follow, and apply to, the last CTE of
+        // the block
+        let mut search = with.search.map(|search| SearchClause {
+            breadth_first: matches!(search.kind, ast::SearchKind::BreadthFirst),
+            columns: self.normalize_idents(search.columns),
+            sequence_column: self.ident_normalizer.normalize(search.sequence_column),
+        });
+        let mut cycle = match with.cycle {
+            Some(cycle) => Some(CycleClause {
+                columns: self.normalize_idents(cycle.columns),
+                mark_column: self.ident_normalizer.normalize(cycle.mark_column),
+                mark_value: self.cycle_mark(cycle.mark_value, true, planner_context)?,
+                mark_default: self.cycle_mark(
+                    cycle.mark_default,
+                    false,
+                    planner_context,
+                )?,
+                path_column: self.ident_normalizer.normalize(cycle.path_column),
+            }),
+            None => None,
+        };
+        if !is_recursive {
+            if cycle.is_some() {
+                return plan_err!("CYCLE clause requires WITH RECURSIVE");
+            }
+            if search.is_some() {
+                return plan_err!("SEARCH clause requires WITH RECURSIVE");
+            }
+        }
+        let cte_count = with.cte_tables.len();
+        let mut cte_names = Vec::with_capacity(cte_count);
         // Process CTEs from top to bottom
-        for cte in with.cte_tables {
+        for (i, cte) in with.cte_tables.into_iter().enumerate() {
             // A `WITH` block can't use the same name more than once
             let cte_name = self.ident_normalizer.normalize(cte.alias.name.clone());
             if planner_context.contains_cte(&cte_name) {
@@ -43,6 +194,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     "WITH query name {cte_name:?} specified more than once"
                 );
             }
+            cte_names.push((
+                cte_name.clone(),
+                Span::try_from_sqlparser_span(cte.alias.name.span),
+            ));
 
             // Create a logical plan for the CTE
             // For recursive CTEs, we need to extract column aliases early and pass them
@@ -51,7 +206,19 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 // Extract column aliases from cte.alias.columns
                 let column_aliases: Vec<Ident> =
                     cte.alias.columns.iter().map(|c| c.name.clone()).collect();
-                self.recursive_cte(&cte_name, *cte.query, column_aliases, planner_context)?
+                let (search, cycle) = if i + 1 == cte_count {
+                    (search.take(), cycle.take())
+                } else {
+                    (None, None)
+                };
+                self.recursive_cte(
+                    &cte_name,
+                    *cte.query,
+                    column_aliases,
+                    search,
+                    cycle,
+                    planner_context,
+                )?
             } else {
                 self.non_recursive_cte(*cte.query, planner_context)?
             };
@@ -61,8 +228,55 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             // For recursive CTEs, column aliases have already been applied within recursive_cte(),
             // but apply_table_alias will still apply the table name alias.
             let final_plan = self.apply_table_alias(cte_plan, cte.alias)?;
-            // Export the CTE to the outer query
-            planner_context.insert_cte(cte_name, final_plan);
+            // Export the CTE to the outer query. Its references all share the
+            // plan, so that the optimizer can decide to evaluate it only once.
+            let materialization = match cte.materialized {
+                None => CteMaterialization::Default,
+                Some(CteAsMaterialized::Materialized) => CteMaterialization::Materialized,
+                Some(CteAsMaterialized::NotMaterialized) => {
+                    CteMaterialization::NotMaterialized
+                }
+            };
+            let reference = CteReference::new(
+                cte_name.clone(),
+                Arc::new(final_plan),
+                materialization,
+            );
+            planner_context.insert_cte(cte_name, LogicalPlan::CteReference(reference));
+        }
+        Ok(cte_names)
+    }
+
+    /// Warns about the CTEs named `cte_names` that are not referenced by
+    /// `plan`, the plan of the query defining them
+    pub(super) fn warn_unused_ctes(
+        &self,
+        plan: &LogicalPlan,
+        cte_names: Vec<(String, Option<Span>)>,
+        planner_context: &PlannerContext,
+    ) -> Result<()> {
+        // The plans of the referenced CTEs, each visited once as a CTE may be
+        // referenced many times
+        let mut referenced = HashSet::new();
+        plan.apply_with_subqueries(|plan| {
+            if let LogicalPlan::CteReference(reference) = plan
+                && !referenced.insert(Arc::as_ptr(&reference.input))
+            {
+                return Ok(TreeNodeRecursion::Jump);
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+
+        for (cte_name, span) in cte_names {
+            if let Some(LogicalPlan::CteReference(reference)) =
+                planner_context.get_cte(&cte_name)
+                && !referenced.contains(&Arc::as_ptr(&reference.input))
+            {
+                planner_context.add_warning(Diagnostic::new_warning(
+                    format!("WITH query {cte_name:?} is not used"),
+                    span,
+                ));
+            }
         }
         Ok(())
     }
@@ -80,6 +294,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         cte_name: &str,
         mut cte_query: Query,
         column_aliases: Vec<Ident>,
+        search: Option<SearchClause>,
+        cycle: Option<CycleClause>,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         if !self
@@ -99,6 +315,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 set_quantifier,
             } => (left, right, set_quantifier),
             other => {
+                let clause = match (&search, &cycle) {
+                    (_, Some(_)) => Some("CYCLE"),
+                    (Some(_), None) => Some("SEARCH"),
+                    (None, None) => None,
+                };
+                if let Some(clause) = clause {
+                    return plan_err!(
+                        "{clause} clause requires {cte_name} to be a recursive UNION"
+                    );
+                }
                 // If the query is not a UNION, then it is not a recursive CTE
                 *cte_query.body = other;
                 return self.non_recursive_cte(cte_query, planner_context);
@@ -125,7 +351,19 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         } else {
             *left_expr
         };
-        let static_plan = self.set_expr_to_plan(left_expr, planner_context)?;
+        let mut static_plan = self.set_expr_to_plan(left_expr, planner_context)?;
+
+        // With a SEARCH or CYCLE clause, the static term also starts the
+        // columns these clauses add
+        let recursive_columns = if search.is_some() || cycle.is_some() {
+            static_plan = self.apply_expr_alias(static_plan, column_aliases.clone())?;
+            let columns =
+                RecursiveColumns::try_new(cte_name, search, cycle, static_plan.schema())?;
+            static_plan = self.start_recursive_columns(static_plan, &columns)?;
+            Some(columns)
+        } else {
+            None
+        };
 
         // Since the recursive CTEs include a component that references a
         // table with its name, like the example below:
@@ -189,11 +427,24 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // this uses the named_relation we inserted above to resolve the
         // relation. This ensures that the recursive term uses the named relation logical plan
         // and thus the 'continuance' physical plan as its input and source
-        let recursive_plan = self.set_expr_to_plan(*right_expr, planner_context)?;
+        let recursive_plan = match &recursive_columns {
+            Some(columns) => {
+                let right_expr = columns.select_previous(*right_expr)?;
+                let plan = self.set_expr_to_plan(right_expr, planner_context)?;
+                self.extend_recursive_columns(plan, columns, &work_table_source)?
+            }
+            None => self.set_expr_to_plan(*right_expr, planner_context)?,
+        };
 
         // Check if the recursive term references the CTE itself,
         // if not, it is a non-recursive CTE
         if !has_work_table_reference(&recursive_plan, &work_table_source) {
+            if let Some(columns) = &recursive_columns {
+                return plan_err!(
+                    "{} clause requires {cte_name} to reference itself",
+                    columns.clause()
+                );
+            }
             // Remove the work table plan from the context
             planner_context.remove_cte(cte_name);
             // Compile it as a non-recursive CTE
@@ -207,9 +458,181 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         // ---------- Step 4: Create the final plan ------------------
         let distinct = !Self::is_union_all(set_quantifier)?;
-        LogicalPlanBuilder::from(static_plan)
-            .to_recursive_query(name, recursive_plan, distinct)?
-            .build()
+        let mut builder = LogicalPlanBuilder::from(static_plan).to_recursive_query(
+            name,
+            recursive_plan,
+            distinct,
+        )?;
+        // The SEARCH clause orders the result by its sequence column
+        if let Some((search, _)) = recursive_columns.and_then(|c| c.search) {
+            let sequence = Expr::Column(Column::from_name(search.sequence_column));
+            builder = builder.sort([sequence.sort(true, false)])?;
+        }
+        builder.build()
+    }
+
+    fn normalize_idents(&self, idents: Vec<Ident>) -> Vec<String> {
+        idents
+            .into_iter()
+            .map(|ident| self.ident_normalizer.normalize(ident))
+            .collect()
+    }
+
+    /// Plans a CYCLE mark value, or `default` when it is not given
+    fn cycle_mark(
+        &self,
+        mark: Option<ast::Expr>,
+        default: bool,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        match mark {
+            Some(mark) => self.sql_to_expr(mark, &DFSchema::empty(), planner_context),
+            None => Ok(lit(default)),
+        }
+    }
+
+    /// Adds the initial SEARCH sequence and CYCLE mark and path to the rows
+    /// of the static term of a recursive query
+    fn start_recursive_columns(
+        &self,
+        plan: LogicalPlan,
+        columns: &RecursiveColumns,
+    ) -> Result<LogicalPlan> {
+        let schema = Arc::clone(plan.schema());
+        let mut projection: Vec<Expr> =
+            schema.columns().into_iter().map(Expr::Column).collect();
+        if let Some((search, indices)) = &columns.search {
+            let sequence = if search.breadth_first {
+                // Rows are ordered by their depth first
+                let fields = [lit(0i64)].into_iter().chain(columns_at(&schema, indices));
+                self.recursive_function("SEARCH", "struct", fields.collect())?
+            } else {
+                let row = self.recursive_row("SEARCH", &schema, indices)?;
+                self.recursive_function("SEARCH", "make_array", vec![row])?
+            };
+            projection.push(sequence.alias(&search.sequence_column));
+        }
+        if let Some((cycle, indices)) = &columns.cycle {
+            let row = self.recursive_row("CYCLE", &schema, indices)?;
+            let path = self.recursive_function("CYCLE", "make_array", vec![row])?;
+            projection.push(cycle.mark_default.clone().alias(&cycle.mark_column));
+            projection.push(path.alias(&cycle.path_column));
+        }
+        LogicalPlanBuilder::from(plan).project(projection)?.build()
+    }
+
+    /// Computes the SEARCH sequence and CYCLE mark and path of the rows of a
+    /// recursive term from those of the rows they are derived from, which
+    /// follow its own columns, and stops the recursion at rows closing a
+    /// cycle
+    fn extend_recursive_columns(
+        &self,
+        plan: LogicalPlan,
+        columns: &RecursiveColumns,
+        work_table_source: &Arc<dyn TableSource>,
+    ) -> Result<LogicalPlan> {
+        let plan = match &columns.cycle {
+            Some((cycle, _)) => {
+                plan.transform_up(|node| match node {
+                    LogicalPlan::TableScan(scan)
+                        if Arc::ptr_eq(&scan.source, work_table_source) =>
+                    {
+                        let mark = Expr::Column(Column::new(
+                            Some(scan.table_name.clone()),
+                            &cycle.mark_column,
+                        ));
+                        let predicate = mark.not_eq(cycle.mark_value.clone());
+                        let input = Arc::new(LogicalPlan::TableScan(scan));
+                        Ok(Transformed::yes(LogicalPlan::Filter(Filter::try_new(
+                            predicate, input,
+                        )?)))
+                    }
+                    node => Ok(Transformed::no(node)),
+                })?
+                .data
+            }
+            None => plan,
+        };
+
+        let schema = Arc::clone(plan.schema());
+        let added =
+            usize::from(columns.search.is_some()) + usize::from(columns.cycle.is_some());
+        if schema.fields().len() != columns.query_columns + added {
+            return plan_err!(
+                "{} clause requires the recursive term to return {} columns",
+                columns.clause(),
+                columns.query_columns
+            );
+        }
+        let column = |i| Expr::Column(Column::from(schema.qualified_field(i)));
+        let mut projection: Vec<Expr> = (0..columns.query_columns).map(column).collect();
+        let mut previous = (columns.query_columns..schema.fields().len()).map(column);
+        if let Some((search, indices)) = &columns.search {
+            let Some(previous_sequence) = previous.next() else {
+                return internal_err!("Missing previous SEARCH sequence column");
+            };
+            let sequence = if search.breadth_first {
+                let depth = self.recursive_function(
+                    "SEARCH",
+                    "get_field",
+                    vec![previous_sequence, lit("c0")],
+                )? + lit(1i64);
+                let fields = [depth].into_iter().chain(columns_at(&schema, indices));
+                self.recursive_function("SEARCH", "struct", fields.collect())?
+            } else {
+                let row = self.recursive_row("SEARCH", &schema, indices)?;
+                self.recursive_function(
+                    "SEARCH",
+                    "array_append",
+                    vec![previous_sequence, row],
+                )?
+            };
+            projection.push(sequence.alias(&search.sequence_column));
+        }
+        if let Some((cycle, indices)) = &columns.cycle {
+            let Some(previous_path) = previous.next() else {
+                return internal_err!("Missing previous CYCLE path column");
+            };
+            let row = self.recursive_row("CYCLE", &schema, indices)?;
+            let is_cycle = self.recursive_function(
+                "CYCLE",
+                "array_has",
+                vec![previous_path.clone(), row.clone()],
+            )?;
+            let mark = when(is_cycle, cycle.mark_value.clone())
+                .otherwise(cycle.mark_default.clone())?;
+            let path = self.recursive_function(
+                "CYCLE",
+                "array_append",
+                vec![previous_path, row],
+            )?;
+            projection.push(mark.alias(&cycle.mark_column));
+            projection.push(path.alias(&cycle.path_column));
+        }
+        LogicalPlanBuilder::from(plan).project(projection)?.build()
+    }
+
+    /// The value recorded for a row in a SEARCH sequence or CYCLE path: a
+    /// struct of the columns of the clause
+    fn recursive_row(
+        &self,
+        clause: &str,
+        schema: &DFSchema,
+        indices: &[usize],
+    ) -> Result<Expr> {
+        self.recursive_function(clause, "struct", columns_at(schema, indices).collect())
+    }
+
+    fn recursive_function(
+        &self,
+        clause: &str,
+        name: &str,
+        args: Vec<Expr>,
+    ) -> Result<Expr> {
+        let Some(udf) = self.context_provider.get_function_meta(name) else {
+            return plan_err!("{clause} clause requires the {name} function");
+        };
+        Ok(udf.call(args))
     }
 
     /// Apply column aliases to a schema, returning a new schema with the aliased names
@@ -297,6 +720,34 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 }
 
+/// Resolves the columns of a SEARCH or CYCLE clause against the static term
+/// of a recursive query
+fn column_indices(
+    clause: &str,
+    cte_name: &str,
+    names: &[String],
+    schema: &DFSchema,
+) -> Result<Vec<usize>> {
+    names
+        .iter()
+        .map(|name| {
+            let Some(i) = schema.fields().iter().position(|f| f.name() == name) else {
+                return plan_err!("{clause} column {name} is not a column of {cte_name}");
+            };
+            Ok(i)
+        })
+        .collect()
+}
+
+fn columns_at<'a>(
+    schema: &'a DFSchema,
+    indices: &'a [usize],
+) -> impl Iterator<Item = Expr> + 'a {
+    indices
+        .iter()
+        .map(|i| Expr::Column(Column::from(schema.qualified_field(*i))))
+}
+
 fn has_work_table_reference(
     plan: &LogicalPlan,
     work_table_source: &Arc<dyn TableSource>,
@@ -69,46 +69,60 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             BinaryOperator::PGOverlap => Ok(Operator::ArrayOverlap),
             BinaryOperator::PGCustomBinaryOperator(ref names) => {
                 let op_name = names.last().map(String::as_str).unwrap_or("");
-                match op_name {
-                    "=" => Ok(Operator::Eq),
-                    "<>" | "!=" => Ok(Operator::NotEq),
-                    "<" => Ok(Operator::Lt),
-                    ">" => Ok(Operator::Gt),
-                    "<=" => Ok(Operator::LtEq),
-                    ">=" => Ok(Operator::GtEq),
-                    "~" => Ok(Operator::RegexMatch),
-                    "~*" => Ok(Operator::RegexIMatch),
-                    "!~" => Ok(Operator::RegexNotMatch),
-                    "!~*" => Ok(Operator::RegexNotIMatch),
-                    "~~" => Ok(Operator::LikeMatch),
-                    "~~*" => Ok(Operator::ILikeMatch),
-                    "!~~" => Ok(Operator::NotLikeMatch),
-                    "!~~*" => Ok(Operator::NotILikeMatch),
-                    "||" => Ok(Operator::StringConcat),
-                    "<<" => Ok(Operator::BitwiseShiftLeft),
-                    ">>" => Ok(Operator::BitwiseShiftRight),
-                    "#" => Ok(Operator::BitwiseXor),
-                    "&" => Ok(Operator::BitwiseAnd),
-                    "|" => Ok(Operator::BitwiseOr),
-                    "&&" => Ok(Operator::ArrayOverlap),
-                    "@>" => Ok(Operator::AtArrow),
-                    "<@" => Ok(Operator::ArrowAt),
-                    "->" => Ok(Operator::Arrow),
-                    "->>" => Ok(Operator::LongArrow),
-                    "#>" => Ok(Operator::HashArrow),
-                    "#>>" => Ok(Operator::HashLongArrow),
-                    "#-" => Ok(Operator::HashMinus),
-                    "@@" => Ok(Operator::AtAt),
-                    "?" => Ok(Operator::Question),
-                    "?&" => Ok(Operator::QuestionAnd),
-                    "?|" => Ok(Operator::QuestionPipe),
-                    _ => not_impl_err!(
+                match custom_binary_op(op_name) {
+                    Some(op) => Ok(op),
+                    None => not_impl_err!(
                         "Unsupported qualified operator: OPERATOR({})",
                         names.join(".")
                     ),
                 }
             }
+            // Operators that the dialect does not know, such as the JSON
+            // operators of PostgreSQL in other dialects
+            BinaryOperator::Custom(ref name) => match custom_binary_op(name) {
+                Some(op) => Ok(op),
+                None => not_impl_err!("Unsupported binary operator: {name}"),
+            },
             _ => not_impl_err!("Unsupported binary operator: {:?}", op),
         }
     }
 }
+
+/// Returns the operator spelled `name`, as in `OPERATOR(pg_catalog.->)`
+fn custom_binary_op(name: &str) -> Option<Operator> {
+    match name {
+        "=" => Some(Operator::Eq),
+        "<>" | "!=" => Some(Operator::NotEq),
+        "<" => Some(Operator::Lt),
+        ">" => Some(Operator::Gt),
+        "<=" => Some(Operator::LtEq),
+        ">=" => Some(Operator::GtEq),
+        "~" => Some(Operator::RegexMatch),
+        "~*" => Some(Operator::RegexIMatch),
+        "!~" => Some(Operator::RegexNotMatch),
+        "!~*" => Some(Operator::RegexNotIMatch),
+        "~~" => Some(Operator::LikeMatch),
+        "~~*" => Some(Operator::ILikeMatch),
+        "!~~" => Some(Operator::NotLikeMatch),
+        "!~~*" => Some(Operator::NotILikeMatch),
+        "||" => Some(Operator::StringConcat),
+        "<<" => Some(Operator::BitwiseShiftLeft),
+        ">>" => Some(Operator::BitwiseShiftRight),
+        "#" => Some(Operator::BitwiseXor),
+        "&" => Some(Operator::BitwiseAnd),
+        "|" => Some(Operator::BitwiseOr),
+        "&&" => Some(Operator::ArrayOverlap),
+        "@>" => Some(Operator::AtArrow),
+        "<@" => Some(Operator::ArrowAt),
+        "->" => Some(Operator::Arrow),
+        "->>" => Some(Operator::LongArrow),
+        "#>" => Some(Operator::HashArrow),
+        "#>>" => Some(Operator::HashLongArrow),
+        "#-" => Some(Operator::HashMinus),
+        "@@" => Some(Operator::AtAt),
+        "?" => Some(Operator::Question),
+        "?&" => Some(Operator::QuestionAnd),
+        "?|" => Some(Operator::QuestionPipe),
+        _ => None,
+    }
+}
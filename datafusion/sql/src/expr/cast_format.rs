@@ -0,0 +1,240 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of `CAST(expr AS type FORMAT 'template')` between character
+//! strings and datetimes
+
+use std::collections::HashSet;
+
+use crate::planner::{ContextProvider, SqlToRel};
+use crate::utils::value_to_string;
+use arrow::datatypes::DataType;
+use datafusion_common::{DFSchema, Result, not_impl_err, plan_err};
+use datafusion_expr::{Expr, ExprSchemable, cast, lit};
+use sqlparser::ast::{CastFormat, CastKind};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans `CAST(expr AS data_type FORMAT format)`, which parses a
+    /// character string into a datetime, or renders a datetime as a
+    /// character string, following a datetime template such as
+    /// `'YYYY-MM-DD HH24:MI:SS'`.
+    ///
+    /// The cast is planned onto the `to_date`, `to_timestamp` and `to_char`
+    /// functions, with the template translated to their format strings.
+    pub(super) fn sql_cast_format_to_expr(
+        &self,
+        expr: Expr,
+        data_type: DataType,
+        cast_kind: CastKind,
+        format: CastFormat,
+        schema: &DFSchema,
+    ) -> Result<Expr> {
+        if cast_kind == CastKind::TryCast {
+            return not_impl_err!("TRY_CAST with FORMAT is not supported");
+        }
+        let template = match &format {
+            CastFormat::Value(template) => value_to_string(template),
+            CastFormat::ValueAtTimeZone(..) => {
+                return not_impl_err!(
+                    "CAST with FORMAT ... AT TIME ZONE is not supported"
+                );
+            }
+        };
+        let Some(template) = template else {
+            return plan_err!("CAST FORMAT requires a character string template");
+        };
+        let format_string = datetime_template_to_format(&template)?;
+
+        let source_type = expr.get_type(schema)?;
+        let function = match (&source_type, &data_type) {
+            (source, DataType::Date32 | DataType::Date64) if is_string(source) => {
+                "to_date"
+            }
+            (
+                source,
+                DataType::Timestamp(_, _) | DataType::Time32(_) | DataType::Time64(_),
+            ) if is_string(source) => "to_timestamp",
+            (source, target) if is_datetime(source) && is_string(target) => "to_char",
+            (source, target) => {
+                return not_impl_err!(
+                    "CAST with FORMAT from {source} to {target} is not supported"
+                );
+            }
+        };
+        let Some(udf) = self.context_provider.get_function_meta(function) else {
+            return plan_err!(
+                "CAST with FORMAT to {data_type} requires the {function} function"
+            );
+        };
+
+        let (relation, name) = expr.qualified_name();
+        let result = udf.call(vec![expr, lit(format_string)]);
+        let result = if result.get_type(schema)? == data_type {
+            result
+        } else {
+            cast(result, data_type)
+        };
+        Ok(result.alias_qualified(relation, name))
+    }
+}
+
+fn is_string(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View | DataType::Null
+    )
+}
+
+fn is_datetime(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Date32
+            | DataType::Date64
+            | DataType::Timestamp(_, _)
+            | DataType::Time32(_)
+            | DataType::Time64(_)
+    )
+}
+
+/// The template elements with their format string, longest first so that
+/// the template is split greedily. Elements without a format string have
+/// no equivalent in the format strings.
+const TEMPLATE_ELEMENTS: &[(&str, Option<&str>)] = &[
+    ("SSSSS", None),
+    ("YYYY", Some("%Y")),
+    ("RRRR", Some("%Y")),
+    ("HH24", Some("%H")),
+    ("HH12", Some("%I")),
+    ("A.M.", Some("%p")),
+    ("P.M.", Some("%p")),
+    ("YYY", None),
+    ("DDD", Some("%j")),
+    ("FF1", None),
+    ("FF2", None),
+    ("FF3", Some("%3f")),
+    ("FF4", None),
+    ("FF5", None),
+    ("FF6", Some("%6f")),
+    ("FF7", None),
+    ("FF8", None),
+    ("FF9", Some("%9f")),
+    ("TZH", None),
+    ("TZM", None),
+    ("YY", Some("%y")),
+    ("RR", Some("%y")),
+    ("MM", Some("%m")),
+    ("DD", Some("%d")),
+    ("HH", Some("%I")),
+    ("MI", Some("%M")),
+    ("SS", Some("%S")),
+    ("Y", None),
+];
+
+/// The delimiters that may separate the elements of a datetime template
+const TEMPLATE_DELIMITERS: &[char] = &['-', '.', '/', ',', '\'', ';', ':', ' '];
+
+/// Translates the datetime template `template` to the format string of the
+/// datetime functions. Each element may appear at most once, and the time
+/// zone is given by `TZH:TZM` or `TZHTZM`.
+fn datetime_template_to_format(template: &str) -> Result<String> {
+    let mut format = String::new();
+    let mut seen = HashSet::new();
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if TEMPLATE_DELIMITERS.contains(&c) {
+            format.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let Some((element, format_string)) =
+            TEMPLATE_ELEMENTS.iter().find(|(element, _)| {
+                rest.get(..element.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(element))
+            })
+        else {
+            return plan_err!("Invalid datetime template '{template}' at '{rest}'");
+        };
+        rest = &rest[element.len()..];
+        if !seen.insert(*element) {
+            return plan_err!(
+                "Datetime template element {element} appears more than once in '{template}'"
+            );
+        }
+        match (*element, format_string) {
+            ("TZH", _) => {
+                let Some((minutes, format_string)) = [(":TZM", "%:z"), ("TZM", "%z")]
+                    .into_iter()
+                    .find(|(minutes, _)| {
+                        rest.get(..minutes.len())
+                            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(minutes))
+                    })
+                else {
+                    return not_impl_err!(
+                        "Datetime template element TZH is only supported before TZM"
+                    );
+                };
+                rest = &rest[minutes.len()..];
+                seen.insert("TZM");
+                format.push_str(format_string);
+            }
+            (_, Some(format_string)) => format.push_str(format_string),
+            (element, None) => {
+                return not_impl_err!(
+                    "Datetime template element {element} is not supported"
+                );
+            }
+        }
+    }
+    Ok(format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::datetime_template_to_format;
+
+    #[test]
+    fn test_datetime_template_to_format() {
+        for (template, format) in [
+            ("YYYY-MM-DD", "%Y-%m-%d"),
+            ("yyyy/mm/dd hh24:mi:ss", "%Y/%m/%d %H:%M:%S"),
+            ("DD.MM.RR HH12:MI A.M.", "%d.%m.%y %I:%M %p"),
+            (
+                "YYYY-MM-DD HH24:MI:SS.FF6 TZH:TZM",
+                "%Y-%m-%d %H:%M:%S.%6f %:z",
+            ),
+            ("DDD YYYY", "%j %Y"),
+        ] {
+            assert_eq!(datetime_template_to_format(template).unwrap(), format);
+        }
+
+        for (template, error) in [
+            (
+                "YYYY-MM-DD-YYYY",
+                "Datetime template element YYYY appears more than once",
+            ),
+            ("YYYY%MM", "Invalid datetime template 'YYYY%MM' at '%MM'"),
+            ("SSSSS", "Datetime template element SSSSS is not supported"),
+            (
+                "HH24 TZH",
+                "Datetime template element TZH is only supported before TZM",
+            ),
+        ] {
+            let err = datetime_template_to_format(template).unwrap_err();
+            assert!(err.to_string().contains(error), "{err}");
+        }
+    }
+}
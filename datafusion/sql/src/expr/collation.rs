@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of `COLLATE`, and of the comparisons and orderings of character
+//! strings with a collation
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::unalias_metadata;
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use arrow::datatypes::DataType;
+use datafusion_common::metadata::FieldMetadata;
+use datafusion_common::{DFSchema, Result, plan_err};
+use datafusion_expr::expr::Alias;
+use datafusion_expr::planner::COLLATION_METADATA_KEY;
+use datafusion_expr::{Expr, ExprSchemable, ScalarUDF};
+use sqlparser::ast::{Expr as SQLExpr, ObjectName};
+
+/// The collation of an operand, explicit when it is given by `COLLATE`
+/// rather than by a column
+struct Collation {
+    name: String,
+    explicit: bool,
+}
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans `expr COLLATE collation` as `expr` aliased to its own name,
+    /// with the collation in the metadata of its field
+    pub(super) fn sql_collate_to_expr(
+        &self,
+        expr: SQLExpr,
+        collation: ObjectName,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let expr = self.sql_expr_to_logical_expr(expr, schema, planner_context)?;
+        let collation = self.collation_metadata(collation, &expr.get_type(schema)?)?;
+        // The collation replaces any collation of `expr`, but other metadata
+        // of its alias, such as a declared character set, is kept
+        let mut metadata = match &expr {
+            Expr::Alias(Alias {
+                metadata: Some(metadata),
+                ..
+            }) => metadata.inner().clone(),
+            _ => BTreeMap::new(),
+        };
+        metadata.extend(collation.inner().clone());
+        let expr = unalias_metadata(expr);
+        let (relation, name) = expr.qualified_name();
+        Ok(expr.alias_qualified_with_metadata(
+            relation,
+            name,
+            Some(FieldMetadata::new(metadata)),
+        ))
+    }
+
+    /// The field metadata of the collation `collation` of a column or an
+    /// expression of type `data_type`, which must be a known collation of a
+    /// character string type
+    pub(crate) fn collation_metadata(
+        &self,
+        collation: ObjectName,
+        data_type: &DataType,
+    ) -> Result<FieldMetadata> {
+        check_collatable(data_type)?;
+        let mut parts = vec![];
+        for part in collation.0 {
+            let Some(ident) = part.as_ident() else {
+                return plan_err!("Invalid collation name {part}");
+            };
+            parts.push(self.ident_normalizer.normalize(ident.clone()));
+        }
+        let name = parts.join(".");
+        self.collation_sort_key(&name)?;
+        Ok(FieldMetadata::new(BTreeMap::from([(
+            COLLATION_METADATA_KEY.to_string(),
+            name,
+        )])))
+    }
+
+    /// Plans the operands of a comparison with their common collation, by
+    /// comparing their sort keys instead of them
+    pub(super) fn collate_comparison(
+        &self,
+        left: Expr,
+        right: Expr,
+        schema: &DFSchema,
+    ) -> Result<(Expr, Expr)> {
+        let collation = match (
+            expr_collation(&left, schema),
+            expr_collation(&right, schema),
+        ) {
+            (Some(left), Some(right)) if left.name != right.name => {
+                match (left.explicit, right.explicit) {
+                    (true, false) => Some(left.name),
+                    (false, true) => Some(right.name),
+                    _ => {
+                        return plan_err!(
+                            "Cannot compare character strings with collations {} and {}",
+                            left.name,
+                            right.name
+                        );
+                    }
+                }
+            }
+            (left, right) => left.or(right).map(|collation| collation.name),
+        };
+        let (left, right) = (unalias_metadata(left), unalias_metadata(right));
+        let sort_key = match collation {
+            Some(name) => self.collation_sort_key(&name)?,
+            None => None,
+        };
+        Ok(match sort_key {
+            Some(sort_key) => (sort_key.call(vec![left]), sort_key.call(vec![right])),
+            None => (left, right),
+        })
+    }
+
+    /// Plans an `ORDER BY` expression with a collation by ordering by its
+    /// sort key instead
+    pub(super) fn collate_sort_expr(
+        &self,
+        expr: Expr,
+        schema: &DFSchema,
+    ) -> Result<Expr> {
+        let sort_key = match expr_collation(&expr, schema) {
+            Some(collation) => self.collation_sort_key(&collation.name)?,
+            None => None,
+        };
+        let expr = unalias_metadata(expr);
+        Ok(match sort_key {
+            Some(sort_key) => sort_key.call(vec![expr]),
+            None => expr,
+        })
+    }
+
+    /// The sort key function of the collation `name`, or `None` for a
+    /// binary collation
+    fn collation_sort_key(&self, name: &str) -> Result<Option<Arc<ScalarUDF>>> {
+        if ["c", "posix", "ucs_basic"]
+            .iter()
+            .any(|binary| name.eq_ignore_ascii_case(binary))
+        {
+            return Ok(None);
+        }
+        match self.context_provider.get_collation(name) {
+            Some(sort_key) => Ok(Some(sort_key)),
+            None => plan_err!("Unknown collation {name}"),
+        }
+    }
+}
+
+/// Returns an error unless values of `data_type` may have a collation
+fn check_collatable(data_type: &DataType) -> Result<()> {
+    match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View | DataType::Null => {
+            Ok(())
+        }
+        DataType::Dictionary(_, value_type) => check_collatable(value_type),
+        other => {
+            plan_err!("COLLATE can only be applied to character strings, got {other}")
+        }
+    }
+}
+
+/// The collation of `expr`, if any. Expressions whose type cannot be
+/// resolved against `schema` yet have no collation.
+fn expr_collation(expr: &Expr, schema: &DFSchema) -> Option<Collation> {
+    let explicit = matches!(
+        expr,
+        Expr::Alias(Alias { metadata: Some(metadata), .. })
+            if metadata.inner().contains_key(COLLATION_METADATA_KEY)
+    );
+    let metadata = expr.metadata(schema).ok()?;
+    metadata
+        .inner()
+        .get(COLLATION_METADATA_KEY)
+        .map(|name| Collation {
+            name: name.clone(),
+            explicit,
+        })
+}
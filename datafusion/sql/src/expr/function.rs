@@ -15,24 +15,31 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use super::hypothetical_set::is_hypothetical_set_function;
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
 use arrow::datatypes::DataType;
+use datafusion_common::error::DataFusionErrorBuilder;
 use datafusion_common::{
-    Column, DFSchema, Dependency, Diagnostic, Result, Span, Spans,
+    Column, DFSchema, Dependency, Diagnostic, Result, ScalarValue, Span, Spans,
     internal_datafusion_err, internal_err, not_impl_err, plan_datafusion_err, plan_err,
 };
 use datafusion_expr::{
-    Expr, ExprSchemable, LogicalPlanBuilder, SortExpr, Subquery, WindowFrame,
-    WindowFunctionDefinition, expr,
+    Expr, ExprSchemable, JsonFunctionOptions, JsonTableErrorHandling, JsonWrapper,
+    LogicalPlanBuilder, OnOverflow, SortExpr, Subquery, WindowFrame,
+    WindowFunctionDefinition, cast, expr,
     expr::{NullTreatment, ScalarFunction, Unnest, WildcardOptions, WindowFunction},
     planner::{PlannerResult, RawAggregateExpr, RawWindowExpr},
+    utils::find_aggregate_exprs,
 };
 use sqlparser::ast::{
-    DuplicateTreatment, Expr as SQLExpr, Function as SQLFunction, FunctionArg,
-    FunctionArgExpr, FunctionArgumentClause, FunctionArgumentList, FunctionArguments,
-    ObjectName, OrderByExpr, Spanned, WindowType,
+    DataType as SQLDataType, DuplicateTreatment, Expr as SQLExpr,
+    Function as SQLFunction, FunctionArg, FunctionArgExpr, FunctionArgumentClause,
+    FunctionArgumentList, FunctionArguments, JsonNullClause, ListAggOnOverflow,
+    ObjectName, OrderByExpr, Spanned, Value, ValueWithSpan, WindowType,
 };
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
 
 /// Suggest a valid function based on an invalid input function name
 ///
@@ -94,6 +101,16 @@ struct FunctionArgs {
     distinct: bool,
     /// WITHIN GROUP clause, if any
     within_group: Vec<OrderByExpr>,
+    /// ON OVERFLOW clause of LISTAGG, if any
+    on_overflow: Option<ListAggOnOverflow>,
+    /// Clauses of the SQL/JSON functions other than RETURNING
+    json_options: JsonFunctionOptions,
+    /// RETURNING clause of the SQL/JSON functions, if any
+    json_returning: Option<SQLDataType>,
+    /// ON EMPTY clause of the SQL/JSON functions, if any
+    json_on_empty: Option<String>,
+    /// ON ERROR clause of the SQL/JSON functions, if any
+    json_on_error: Option<String>,
     /// Was the function called without parenthesis, i.e. could this also be a column reference?
     function_without_parentheses: bool,
 }
@@ -121,6 +138,11 @@ impl FunctionArgs {
                 null_treatment: null_treatment.map(|v| v.into()),
                 distinct: false,
                 within_group,
+                on_overflow: None,
+                json_options: JsonFunctionOptions::default(),
+                json_returning: None,
+                json_on_empty: None,
+                json_on_error: None,
                 function_without_parentheses: matches!(args, FunctionArguments::None),
             });
         };
@@ -139,6 +161,11 @@ impl FunctionArgs {
 
         // Pull out argument handling
         let mut order_by = None;
+        let mut on_overflow = None;
+        let mut json_options = JsonFunctionOptions::default();
+        let mut json_returning = None;
+        let mut json_on_empty = None;
+        let mut json_on_error = None;
         for clause in clauses {
             match clause {
                 FunctionArgumentClause::IgnoreOrRespectNulls(nt) => {
@@ -168,9 +195,12 @@ impl FunctionArgs {
                     );
                 }
                 FunctionArgumentClause::OnOverflow(overflow) => {
-                    return not_impl_err!(
-                        "Calling {name}: ON OVERFLOW not supported in function arguments: {overflow}"
-                    );
+                    if on_overflow.is_some() {
+                        return not_impl_err!(
+                            "Calling {name}: Duplicated ON OVERFLOW clause in function arguments"
+                        );
+                    }
+                    on_overflow = Some(overflow);
                 }
                 FunctionArgumentClause::Having(having) => {
                     return not_impl_err!(
@@ -182,43 +212,82 @@ impl FunctionArgs {
                         "Calling {name}: SEPARATOR not supported in function arguments: {sep}"
                     );
                 }
-                FunctionArgumentClause::JsonNullClause(_) => {
-                    // JSON NULL clause is accepted but ignored for now
-                    // SQL:2016 T8xx JSON support
+                FunctionArgumentClause::JsonNullClause(null_clause) => {
+                    if json_options.null_on_null.is_some() {
+                        return plan_err!(
+                            "Calling {name}: Duplicated ON NULL clause in function arguments"
+                        );
+                    }
+                    json_options.null_on_null =
+                        Some(matches!(null_clause, JsonNullClause::NullOnNull));
                 }
-                FunctionArgumentClause::JsonReturningClause(_) => {
-                    // JSON RETURNING clause is accepted but ignored for now
-                    // SQL:2016 T8xx JSON support
+                FunctionArgumentClause::JsonReturningClause(returning) => {
+                    if json_returning.is_some() {
+                        return plan_err!(
+                            "Calling {name}: Duplicated RETURNING clause in function arguments"
+                        );
+                    }
+                    json_returning = Some(returning.data_type);
                 }
-                FunctionArgumentClause::JsonOnEmpty(_) => {
-                    // JSON ON EMPTY clause is accepted but ignored for now
-                    // SQL:2016 T8xx JSON support
+                FunctionArgumentClause::JsonOnEmpty(on_empty) => {
+                    if json_on_empty.is_some() {
+                        return plan_err!(
+                            "Calling {name}: Duplicated ON EMPTY clause in function arguments"
+                        );
+                    }
+                    json_on_empty = Some(on_empty.to_string());
                 }
-                FunctionArgumentClause::JsonOnError(_) => {
-                    // JSON ON ERROR clause is accepted but ignored for now
-                    // SQL:2016 T8xx JSON support
+                FunctionArgumentClause::JsonOnError(on_error) => {
+                    if json_on_error.is_some() {
+                        return plan_err!(
+                            "Calling {name}: Duplicated ON ERROR clause in function arguments"
+                        );
+                    }
+                    json_on_error = Some(on_error.to_string());
                 }
-                FunctionArgumentClause::JsonQueryWrapper(jw) => {
-                    return not_impl_err!(
-                        "Calling {name}: JSON query wrapper not supported in function arguments: {jw}"
-                    );
+                FunctionArgumentClause::JsonQueryWrapper(wrapper) => {
+                    let (wrapper, keep_quotes) =
+                        json_query_wrapper_to_options(&wrapper.to_string());
+                    if (wrapper.is_some() && json_options.wrapper.is_some())
+                        || (keep_quotes.is_some() && json_options.keep_quotes.is_some())
+                    {
+                        return plan_err!(
+                            "Calling {name}: Duplicated WRAPPER or QUOTES clause in function arguments"
+                        );
+                    }
+                    json_options.wrapper = json_options.wrapper.or(wrapper);
+                    json_options.keep_quotes = json_options.keep_quotes.or(keep_quotes);
                 }
-                FunctionArgumentClause::JsonUniqueKeys(uk) => {
-                    return not_impl_err!(
-                        "Calling {name}: JSON unique keys not supported in function arguments: {uk}"
+                FunctionArgumentClause::JsonUniqueKeys(unique_keys) => {
+                    if json_options.unique_keys.is_some() {
+                        return plan_err!(
+                            "Calling {name}: Duplicated UNIQUE KEYS clause in function arguments"
+                        );
+                    }
+                    json_options.unique_keys = Some(
+                        !unique_keys
+                            .to_string()
+                            .trim_start()
+                            .to_uppercase()
+                            .starts_with("WITHOUT"),
                     );
                 }
             }
         }
 
-        if within_group.len() > 1 {
-            return not_impl_err!(
-                "Only a single ordering expression is permitted in a WITHIN GROUP clause"
+        let order_by = order_by.unwrap_or_default();
+
+        if json_options.keep_quotes.is_some()
+            && matches!(
+                json_options.wrapper,
+                Some(JsonWrapper::Conditional | JsonWrapper::Unconditional)
+            )
+        {
+            return plan_err!(
+                "Calling {name}: QUOTES clause is not permitted with WITH ARRAY WRAPPER"
             );
         }
 
-        let order_by = order_by.unwrap_or_default();
-
         Ok(Self {
             name,
             args,
@@ -228,11 +297,118 @@ impl FunctionArgs {
             null_treatment: null_treatment.map(|v| v.into()),
             distinct,
             within_group,
+            on_overflow,
+            json_options,
+            json_returning,
+            json_on_empty,
+            json_on_error,
             function_without_parentheses: false,
         })
     }
 }
 
+/// Converts the ARRAY WRAPPER and QUOTES clauses of JSON_QUERY, such as
+/// `WITH CONDITIONAL ARRAY WRAPPER` or `OMIT QUOTES`, into the options passed
+/// to the function
+fn json_query_wrapper_to_options(clause: &str) -> (Option<JsonWrapper>, Option<bool>) {
+    let words = clause.to_uppercase();
+    let words = words.split_whitespace().collect::<Vec<_>>();
+    let wrapper = words.contains(&"WRAPPER").then(|| {
+        if words.contains(&"WITHOUT") {
+            JsonWrapper::Without
+        } else if words.contains(&"CONDITIONAL") {
+            JsonWrapper::Conditional
+        } else {
+            JsonWrapper::Unconditional
+        }
+    });
+    let keep_quotes = if words.contains(&"KEEP") {
+        Some(true)
+    } else if words.contains(&"OMIT") {
+        Some(false)
+    } else {
+        None
+    };
+    (wrapper, keep_quotes)
+}
+
+/// Casts the result of the call `expr` of a SQL/JSON function to the type of
+/// its RETURNING clause, unless the function already returns that type
+fn json_returning_to_expr(
+    expr: Expr,
+    json_options: &JsonFunctionOptions,
+    schema: &DFSchema,
+) -> Result<Expr> {
+    let Some(returning) = &json_options.returning else {
+        return Ok(expr);
+    };
+    if expr.get_type(schema)? == *returning {
+        return Ok(expr);
+    }
+    Ok(cast(expr, returning.clone()))
+}
+
+/// Converts the ON OVERFLOW clause of LISTAGG into the option passed to the
+/// aggregate function
+fn on_overflow_to_option(on_overflow: ListAggOnOverflow) -> Result<OnOverflow> {
+    match on_overflow {
+        ListAggOnOverflow::Error => Ok(OnOverflow::Error),
+        ListAggOnOverflow::Truncate { filler, with_count } => {
+            let filler = match filler.map(|filler| *filler) {
+                None => OnOverflow::DEFAULT_FILLER.to_string(),
+                Some(SQLExpr::Value(ValueWithSpan {
+                    value: Value::SingleQuotedString(filler),
+                    ..
+                })) => filler,
+                Some(filler) => {
+                    return plan_err!(
+                        "ON OVERFLOW TRUNCATE filler must be a string literal, got {filler}"
+                    );
+                }
+            };
+            Ok(OnOverflow::Truncate { filler, with_count })
+        }
+    }
+}
+
+/// Validates the percentile argument of the inverse distribution functions
+/// `percentile_cont` and `percentile_disc`, which must be a constant between
+/// 0 and 1. `args` are the arguments of the aggregate function, with the
+/// `WITHIN GROUP` ordering expression prepended.
+fn validate_inverse_distribution_args(name: &str, args: &[Expr]) -> Result<()> {
+    let [_, percentile] = args else {
+        return plan_err!(
+            "{name} requires a single percentile argument and a single ordering expression"
+        );
+    };
+    if percentile.any_column_refs() || !find_aggregate_exprs([percentile]).is_empty() {
+        return plan_err!(
+            "The percentile of {name} must be a constant, got {percentile}"
+        );
+    }
+    if let Expr::Literal(value, _) = percentile {
+        if value.is_null() {
+            return Ok(());
+        }
+        if !value.data_type().is_numeric() {
+            return plan_err!(
+                "The percentile of {name} must be numeric, got {}",
+                value.data_type()
+            );
+        }
+        let fraction = match value.cast_to(&DataType::Float64)? {
+            ScalarValue::Float64(Some(fraction)) => fraction,
+            _ => return Ok(()),
+        };
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!(
+                "The percentile of {name} must be between 0 and 1, got {value}"
+            );
+        }
+    }
+    Ok(())
+}
+
 // Helper type for extracting WITHIN GROUP ordering and prepended args
 type WithinGroupExtraction = (Vec<SortExpr>, Vec<Expr>, Vec<Option<String>>);
 
@@ -254,6 +430,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         };
 
+        // A name called without parentheses may reference a column instead
+        if matches!(function.args, FunctionArguments::None)
+            && let [part] = function.name.0.as_slice()
+            && let Some(ident) = part.as_ident()
+            && self.function_name_is_column(ident, schema, planner_context)
+        {
+            return self.sql_identifier_to_expr(ident.clone(), schema, planner_context);
+        }
+
         if name.eq_ignore_ascii_case("array") {
             if let FunctionArguments::Subquery(query) = &function.args {
                 return self.plan_array_subquery_constructor(
@@ -295,6 +480,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             null_treatment,
             distinct,
             within_group,
+            on_overflow,
+            mut json_options,
+            json_returning,
+            json_on_empty,
+            json_on_error,
             function_without_parentheses,
         } = function_args;
 
@@ -311,6 +501,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             );
         }
 
+        let on_overflow = on_overflow.map(on_overflow_to_option).transpose()?;
+
         // If function is a window function (it has an OVER clause),
         // it shouldn't have ordering requirement as function argument
         // required ordering should be defined in OVER clause.
@@ -332,6 +524,87 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         };
 
+        // The ON OVERFLOW clause is only planned for the aggregate functions
+        // that support it, such as LISTAGG
+        if on_overflow.is_some()
+            && (over.is_some()
+                || !self
+                    .context_provider
+                    .get_aggregate_meta(&name)
+                    .is_some_and(|fm| fm.supports_on_overflow_clause()))
+        {
+            return plan_err!("ON OVERFLOW is not permitted for {name}");
+        }
+
+        // The clauses of the SQL/JSON functions are only planned for the
+        // functions that support them, such as JSON_OBJECT and JSON_ARRAYAGG
+        if let Some(returning) = json_returning {
+            json_options.returning = Some(
+                self.convert_data_type_to_field(&returning)?
+                    .data_type()
+                    .clone(),
+            );
+        }
+        if let Some(on_empty) = json_on_empty {
+            json_options.on_empty = Some(self.json_behavior_to_option(
+                &on_empty,
+                json_options.returning.as_ref(),
+                schema,
+                planner_context,
+            )?);
+        }
+        if let Some(on_error) = json_on_error {
+            json_options.on_error = Some(self.json_behavior_to_option(
+                &on_error,
+                json_options.returning.as_ref(),
+                schema,
+                planner_context,
+            )?);
+        }
+        // JSON_VALUE extracts an SQL scalar, whereas JSON_QUERY and the
+        // constructors return JSON text
+        if let Some(returning) = &json_options.returning
+            && returning.is_nested()
+            && name == "json_value"
+        {
+            return plan_err!("JSON_VALUE cannot return values of type {returning}");
+        }
+        let has_json_clauses = json_options != JsonFunctionOptions::default();
+        if has_json_clauses
+            && (over.is_some()
+                || !(self
+                    .context_provider
+                    .get_function_meta(&name)
+                    .is_some_and(|fm| fm.supports_json_clauses())
+                    || self
+                        .context_provider
+                        .get_aggregate_meta(&name)
+                        .is_some_and(|fm| fm.supports_json_clauses())))
+        {
+            return plan_err!("SQL/JSON clauses are not permitted for {name}");
+        }
+
+        // Hypothetical-set aggregate functions, such as
+        // `rank(x) WITHIN GROUP (ORDER BY y)`
+        if !within_group.is_empty() && is_hypothetical_set_function(&name) {
+            return self.sql_hypothetical_set_to_expr(
+                &name,
+                args,
+                within_group,
+                filter,
+                null_treatment,
+                distinct,
+                schema,
+                planner_context,
+            );
+        }
+
+        if within_group.len() > 1 {
+            return not_impl_err!(
+                "Only a single ordering expression is permitted in a WITHIN GROUP clause"
+            );
+        }
+
         // handle make_map and map functions
         // make_map always uses plan_make_map: make_map(k1, v1, k2, v2, ...)
         // map has 2 syntaxes:
@@ -437,7 +710,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 (args, arg_names)
             };
 
-            let resolved_args = if arg_names.iter().any(|name| name.is_some()) {
+            let mut resolved_args = if arg_names.iter().any(|name| name.is_some()) {
                 if let Some(param_names) = &fm.signature().parameter_names {
                     datafusion_expr::arguments::resolve_function_arguments(
                         param_names,
@@ -454,11 +727,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 args
             };
 
+            // The SQL/JSON clauses are passed as the last argument
+            if has_json_clauses {
+                resolved_args.push(Expr::Literal(json_options.to_scalar()?, None));
+            }
+
             // After resolution, all arguments are positional
             let inner = ScalarFunction::new_udf(fm, resolved_args);
 
             if name.eq_ignore_ascii_case(inner.name()) {
-                return Ok(Expr::ScalarFunction(inner));
+                return json_returning_to_expr(
+                    Expr::ScalarFunction(inner),
+                    &json_options,
+                    schema,
+                );
             } else {
                 // If the function is called by an alias, a verbose string representation is created
                 // (e.g., "my_alias(arg1, arg2)") and the expression is wrapped in an `Alias`
@@ -471,19 +753,35 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     .join(",");
                 let verbose_alias = format!("{name}({arg_names})");
 
-                return Ok(Expr::ScalarFunction(inner).alias(verbose_alias));
+                let expr = json_returning_to_expr(
+                    Expr::ScalarFunction(inner),
+                    &json_options,
+                    schema,
+                )?;
+                return Ok(expr.alias(verbose_alias));
             }
         }
 
         // Build Unnest expression
         if name.eq("unnest") {
-            let mut exprs = self.function_args_to_expr(args, schema, planner_context)?;
-            if exprs.len() != 1 {
-                return plan_err!("unnest() requires exactly one argument");
-            }
+            let (mut exprs, arg_names) =
+                self.function_args_to_expr_with_names(args, schema, planner_context)?;
+            // `unnest(nested, max_depth => n)` unnests the `n` outer levels
+            // of a nested list, as `n` nested `unnest` calls would
+            let depth = match arg_names.as_slice() {
+                [None] => 1,
+                [None, Some(arg_name)] if arg_name == "max_depth" => {
+                    let depth = exprs.pop().unwrap();
+                    Self::unnest_depth(&depth, &exprs[0], schema)?
+                }
+                [None, Some(arg_name)] => {
+                    return plan_err!("Unknown parameter name '{arg_name}' for unnest()");
+                }
+                _ => return plan_err!("unnest() requires exactly one argument"),
+            };
             let expr = exprs.swap_remove(0);
             Self::check_unnest_arg(&expr, schema)?;
-            return Ok(Expr::Unnest(Unnest::new(expr)));
+            return Ok((0..depth).fold(expr, |expr, _| Expr::Unnest(Unnest::new(expr))));
         }
 
         if !order_by.is_empty() && is_function_window {
@@ -546,6 +844,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             };
 
             if let Ok(fun) = self.find_window_func(&name) {
+                if null_treatment.is_some() && !fun.supports_null_handling_clause() {
+                    return plan_err!(
+                        "[IGNORE | RESPECT] NULLS are not permitted for {}",
+                        fun.name()
+                    );
+                }
+
                 let (args, arg_names) =
                     self.function_args_to_expr_with_names(args, schema, planner_context)?;
 
@@ -689,6 +994,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     )?
                 };
 
+                // As the duplicates are eliminated before the values are
+                // ordered, the values can only be ordered by the arguments
+                if distinct
+                    && !supports_within_group
+                    && order_by.iter().any(|sort| !args.contains(&sort.expr))
+                {
+                    return plan_err!(
+                        "In an aggregate with DISTINCT, ORDER BY expressions must appear in argument list"
+                    );
+                }
+
                 let filter: Option<Box<Expr>> = filter
                     .map(|e| self.sql_expr_to_logical_expr(*e, schema, planner_context))
                     .transpose()?
@@ -724,7 +1040,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         (args, arg_names)
                     };
 
-                let resolved_args = if arg_names.iter().any(|name| name.is_some()) {
+                let mut resolved_args = if arg_names.iter().any(|name| name.is_some()) {
                     if let Some(param_names) = &fm.signature().parameter_names {
                         datafusion_expr::arguments::resolve_function_arguments(
                             param_names,
@@ -741,6 +1057,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     args
                 };
 
+                if matches!(fm.name(), "percentile_cont" | "percentile_disc") {
+                    validate_inverse_distribution_args(fm.name(), &resolved_args)?;
+                }
+
+                // The ON OVERFLOW behavior is passed as the last argument
+                if let Some(on_overflow) = &on_overflow {
+                    resolved_args.push(Expr::Literal(on_overflow.to_scalar()?, None));
+                }
+
+                // The SQL/JSON clauses are passed as the last argument
+                if has_json_clauses {
+                    resolved_args.push(Expr::Literal(json_options.to_scalar()?, None));
+                }
+
                 let mut aggregate_expr = RawAggregateExpr {
                     func: fm,
                     args: resolved_args,
@@ -775,7 +1105,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 );
 
                 if name.eq_ignore_ascii_case(inner.func.name()) {
-                    return Ok(Expr::AggregateFunction(inner));
+                    return json_returning_to_expr(
+                        Expr::AggregateFunction(inner),
+                        &json_options,
+                        schema,
+                    );
                 } else {
                     // If the function is called by an alias, a verbose string representation is created
                     // (e.g., "my_alias(arg1, arg2)") and the expression is wrapped in an `Alias`
@@ -789,7 +1123,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         .join(",");
                     let verbose_alias = format!("{name}({arg_names})");
 
-                    return Ok(Expr::AggregateFunction(inner).alias(verbose_alias));
+                    let expr = json_returning_to_expr(
+                        Expr::AggregateFunction(inner),
+                        &json_options,
+                        schema,
+                    )?;
+                    return Ok(expr.alias(verbose_alias));
                 }
             }
         }
@@ -827,6 +1166,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 Diagnostic::new_error(format!("Invalid function '{name}'"), span);
             diagnostic
                 .add_note(format!("Possible function '{suggested_func_name}'"), None);
+            diagnostic.add_did_you_mean(&name, [suggested_func_name.as_str()]);
             plan_err!("Invalid function '{name}'.\nDid you mean '{suggested_func_name}'?"; diagnostic=diagnostic)
         } else {
             internal_err!("No functions registered with this context.")
@@ -990,9 +1330,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Vec<Expr>> {
-        args.into_iter()
-            .map(|a| self.sql_fn_arg_to_logical_expr(a, schema, planner_context))
-            .collect::<Result<Vec<Expr>>>()
+        let mut errors = DataFusionErrorBuilder::new();
+        let mut exprs = Vec::with_capacity(args.len());
+        for arg in args {
+            let expr = self
+                .sql_fn_arg_to_logical_expr(arg, schema, planner_context)
+                .map(Some);
+            if let Some(expr) = self.collect_error(expr, &mut errors)? {
+                exprs.push(expr);
+            }
+        }
+        errors.error_or(exprs)
     }
 
     pub(super) fn function_args_to_expr_with_names(
@@ -1001,16 +1349,89 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<(Vec<Expr>, Vec<Option<String>>)> {
-        let results: Result<Vec<(Expr, Option<String>)>> = args
-            .into_iter()
-            .map(|a| {
-                self.sql_fn_arg_to_logical_expr_with_name(a, schema, planner_context)
-            })
-            .collect();
-
-        let pairs = results?;
-        let (exprs, names): (Vec<Expr>, Vec<Option<String>>) = pairs.into_iter().unzip();
-        Ok((exprs, names))
+        let mut errors = DataFusionErrorBuilder::new();
+        let mut exprs = Vec::with_capacity(args.len());
+        let mut names = Vec::with_capacity(args.len());
+        for arg in args {
+            let pair = self
+                .sql_fn_arg_to_logical_expr_with_name(arg, schema, planner_context)
+                .map(Some);
+            if let Some((expr, name)) = self.collect_error(pair, &mut errors)? {
+                exprs.push(expr);
+                names.push(name);
+            }
+        }
+        errors.error_or((exprs, names))
+    }
+
+    /// Converts the ON EMPTY or ON ERROR clause `clause` of a SQL/JSON
+    /// function, as written in SQL, into its behavior. A DEFAULT value must
+    /// be a literal, and is cast to the type of the RETURNING clause, if any.
+    fn json_behavior_to_option(
+        &self,
+        clause: &str,
+        returning: Option<&DataType>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<JsonTableErrorHandling> {
+        let mut behavior = clause.trim();
+        for suffix in [" ON EMPTY", " ON ERROR"] {
+            if let Some(start) = behavior.len().checked_sub(suffix.len())
+                && behavior.is_char_boundary(start)
+                && behavior[start..].eq_ignore_ascii_case(suffix)
+            {
+                behavior = behavior[..start].trim_end();
+            }
+        }
+        let words = behavior.to_uppercase();
+        let words = words.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            ["NULL"] | ["UNKNOWN"] => return Ok(JsonTableErrorHandling::Null),
+            ["ERROR"] => return Ok(JsonTableErrorHandling::Error),
+            ["TRUE"] => {
+                return Ok(JsonTableErrorHandling::Default(ScalarValue::from(true)));
+            }
+            ["FALSE"] => {
+                return Ok(JsonTableErrorHandling::Default(ScalarValue::from(false)));
+            }
+            ["EMPTY"] | ["EMPTY", "ARRAY"] => {
+                return Ok(JsonTableErrorHandling::EmptyArray);
+            }
+            ["EMPTY", "OBJECT"] => return Ok(JsonTableErrorHandling::EmptyObject),
+            ["DEFAULT", ..] => {}
+            _ => return plan_err!("Unsupported SQL/JSON behavior {clause}"),
+        }
+
+        let default = behavior["DEFAULT".len()..].trim();
+        let default = Parser::new(&GenericDialect {})
+            .try_with_sql(default)?
+            .parse_expr()?;
+        let default = self.sql_expr_to_logical_expr(default, schema, planner_context)?;
+        let value = match default {
+            Expr::Literal(value, _) => value,
+            Expr::Negative(expr) => match *expr {
+                Expr::Literal(value, _) => value.arithmetic_negate()?,
+                expr => {
+                    return not_impl_err!(
+                        "DEFAULT value of a SQL/JSON function must be a literal, got -{expr}"
+                    );
+                }
+            },
+            expr => {
+                return not_impl_err!(
+                    "DEFAULT value of a SQL/JSON function must be a literal, got {expr}"
+                );
+            }
+        };
+        let value = match returning {
+            Some(data_type) => value.cast_to(data_type).map_err(|e| {
+                plan_datafusion_err!(
+                    "DEFAULT value of a SQL/JSON function cannot be cast to {data_type}: {e}"
+                )
+            })?,
+            None => value,
+        };
+        Ok(JsonTableErrorHandling::Default(value))
     }
 
     fn extract_and_prepend_within_group_args(
@@ -1164,6 +1585,44 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }))
     }
 
+    /// The number of levels that `unnest(arg, max_depth => depth)` unnests,
+    /// which must not exceed the levels of nesting of the lists of `arg`
+    fn unnest_depth(depth: &Expr, arg: &Expr, schema: &DFSchema) -> Result<usize> {
+        let depth = match depth {
+            Expr::Literal(value, _) if value.data_type().is_integer() => {
+                match value.cast_to(&DataType::Int64)? {
+                    ScalarValue::Int64(Some(depth)) if depth > 0 => depth as usize,
+                    _ => {
+                        return plan_err!(
+                            "unnest() max_depth must be a positive integer, got {value}"
+                        );
+                    }
+                }
+            }
+            _ => {
+                return plan_err!(
+                    "unnest() max_depth must be a positive integer literal, got {depth}"
+                );
+            }
+        };
+        let data_type = arg.get_type(schema)?;
+        let mut levels = 0;
+        let mut nested = &data_type;
+        while let DataType::List(field)
+        | DataType::LargeList(field)
+        | DataType::FixedSizeList(field, _) = nested
+        {
+            levels += 1;
+            nested = field.data_type();
+        }
+        if depth > levels.max(1) {
+            return plan_err!(
+                "unnest() max_depth {depth} exceeds the {levels} levels of nesting of {data_type}"
+            );
+        }
+        Ok(depth)
+    }
+
     pub(crate) fn check_unnest_arg(arg: &Expr, schema: &DFSchema) -> Result<()> {
         // Check argument type, array types are supported
         match arg.get_type(schema)? {
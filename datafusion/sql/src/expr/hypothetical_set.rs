@@ -0,0 +1,229 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of hypothetical-set aggregate functions, such as
+//! `rank(x) WITHIN GROUP (ORDER BY y)`
+
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use arrow::datatypes::DataType;
+use datafusion_common::{DFSchema, Result, plan_err};
+use datafusion_expr::expr::{AggregateFunction, NullTreatment, ScalarFunction};
+use datafusion_expr::utils::{COUNT_STAR_EXPANSION, find_aggregate_exprs};
+use datafusion_expr::{
+    AggregateUDF, Expr, Operator, SortExpr, and, binary_expr, cast, lit, or, when,
+};
+use sqlparser::ast::{Expr as SQLExpr, FunctionArg, OrderByExpr};
+
+/// Returns true if `name` is a hypothetical-set aggregate function when it
+/// is called with a `WITHIN GROUP` clause
+pub(super) fn is_hypothetical_set_function(name: &str) -> bool {
+    matches!(name, "rank" | "dense_rank" | "percent_rank" | "cume_dist")
+}
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans a hypothetical-set aggregate function, which computes the rank
+    /// that a hypothetical row with the values of its arguments would have if
+    /// it were added to the group ordered by the `WITHIN GROUP` clause.
+    ///
+    /// The function is planned as `count` aggregates over the rows of the
+    /// group that sort before (or as peers of) the hypothetical row:
+    ///
+    /// * `rank`: `1 + count(*) FILTER (WHERE <before>)`
+    /// * `dense_rank`: `1 + count(DISTINCT y) FILTER (WHERE <before>)`
+    /// * `percent_rank`: `count(*) FILTER (WHERE <before>) / count(*)`
+    /// * `cume_dist`: `(count(*) FILTER (WHERE <before or peer>) + 1) / (count(*) + 1)`
+    #[expect(clippy::too_many_arguments)]
+    pub(super) fn sql_hypothetical_set_to_expr(
+        &self,
+        name: &str,
+        args: Vec<FunctionArg>,
+        within_group: Vec<OrderByExpr>,
+        filter: Option<Box<SQLExpr>>,
+        null_treatment: Option<NullTreatment>,
+        distinct: bool,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        if distinct {
+            return plan_err!(
+                "DISTINCT is not supported for hypothetical-set aggregate {name}"
+            );
+        }
+        if null_treatment.is_some() {
+            return plan_err!("[IGNORE | RESPECT] NULLS are not permitted for {name}");
+        }
+        let Some(count) = self.context_provider.get_aggregate_meta("count") else {
+            return plan_err!("Hypothetical-set aggregate {name} requires count");
+        };
+
+        let (args, arg_names) =
+            self.function_args_to_expr_with_names(args, schema, planner_context)?;
+        if arg_names.iter().any(|arg_name| arg_name.is_some()) {
+            return plan_err!("Function '{name}' does not support named arguments");
+        }
+        if !find_aggregate_exprs(&args).is_empty() {
+            return plan_err!(
+                "Arguments of hypothetical-set aggregate {name} must not contain aggregate functions"
+            );
+        }
+        let within_group = self.order_by_to_sort_expr(
+            within_group,
+            schema,
+            planner_context,
+            false,
+            None,
+        )?;
+        if args.len() != within_group.len() {
+            return plan_err!(
+                "Hypothetical-set aggregate {name} requires as many arguments as \
+                WITHIN GROUP ordering expressions, got {} arguments and {} ordering expressions",
+                args.len(),
+                within_group.len()
+            );
+        }
+        let filter = filter
+            .map(|e| self.sql_expr_to_logical_expr(*e, schema, planner_context))
+            .transpose()?;
+
+        // Whether a row sorts before the hypothetical row, lexicographically
+        // over the ordering expressions, and whether it is one of its peers
+        let mut conditions: Option<(Expr, Expr)> = None;
+        for (sort, arg) in within_group.iter().zip(args.iter()).rev() {
+            let (sort_before, peer) = sorts_before(sort, arg);
+            conditions = Some(match conditions {
+                None => (sort_before.clone(), or(sort_before, peer)),
+                Some((before, before_or_peer)) => (
+                    or(sort_before.clone(), and(peer.clone(), before)),
+                    or(sort_before, and(peer, before_or_peer)),
+                ),
+            });
+        }
+        let Some((before, before_or_peer)) = conditions else {
+            return plan_err!(
+                "Hypothetical-set aggregate {name} requires at least one argument"
+            );
+        };
+
+        let count_rows = |condition: Option<Expr>| {
+            let filter = match (filter.clone(), condition) {
+                (Some(filter), Some(condition)) => Some(and(filter, condition)),
+                (filter, condition) => filter.or(condition),
+            };
+            count_expr(&count, lit(COUNT_STAR_EXPANSION), false, filter)
+        };
+        let expr = match name {
+            "rank" => count_rows(Some(before)) + lit(1i64),
+            "dense_rank" => {
+                let before = match filter.clone() {
+                    Some(filter) => and(filter, before),
+                    None => before,
+                };
+                self.hypothetical_dense_rank(&count, &within_group, before)?
+            }
+            "percent_rank" => when(count_rows(None).eq(lit(0i64)), lit(0.0)).otherwise(
+                cast(count_rows(Some(before)), DataType::Float64)
+                    / cast(count_rows(None), DataType::Float64),
+            )?,
+            "cume_dist" => {
+                cast(
+                    count_rows(Some(before_or_peer)) + lit(1i64),
+                    DataType::Float64,
+                ) / cast(count_rows(None) + lit(1i64), DataType::Float64)
+            }
+            _ => return plan_err!("{name} is not a hypothetical-set aggregate function"),
+        };
+
+        let args = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let within_group = within_group
+            .iter()
+            .map(|sort| sort.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(expr.alias(format!("{name}({args}) WITHIN GROUP [{within_group}]")))
+    }
+
+    /// `1 +` the number of distinct ordering values that sort before the
+    /// hypothetical row, counting the null ordering value as a value of its own
+    fn hypothetical_dense_rank(
+        &self,
+        count: &Arc<AggregateUDF>,
+        within_group: &[SortExpr],
+        before: Expr,
+    ) -> Result<Expr> {
+        let distinct_before = if let [sort] = within_group {
+            let null_before = count_expr(
+                count,
+                lit(COUNT_STAR_EXPANSION),
+                false,
+                Some(and(sort.expr.clone().is_null(), before.clone())),
+            );
+            count_expr(count, sort.expr.clone(), true, Some(before))
+                + when(null_before.gt(lit(0i64)), lit(1i64)).otherwise(lit(0i64))?
+        } else {
+            // A struct is never null, so that rows with null ordering values
+            // are counted as well
+            let Some(struct_udf) = self.context_provider.get_function_meta("struct")
+            else {
+                return plan_err!(
+                    "dense_rank with several WITHIN GROUP ordering expressions requires the struct function"
+                );
+            };
+            let values = within_group.iter().map(|sort| sort.expr.clone()).collect();
+            let values =
+                Expr::ScalarFunction(ScalarFunction::new_udf(struct_udf, values));
+            count_expr(count, values, true, Some(before))
+        };
+        Ok(distinct_before + lit(1i64))
+    }
+}
+
+fn count_expr(
+    count: &Arc<AggregateUDF>,
+    arg: Expr,
+    distinct: bool,
+    filter: Option<Expr>,
+) -> Expr {
+    Expr::AggregateFunction(AggregateFunction::new_udf(
+        Arc::clone(count),
+        vec![arg],
+        distinct,
+        filter.map(Box::new),
+        vec![],
+        None,
+    ))
+}
+
+/// Returns whether a row sorts before the hypothetical row with the value
+/// `arg` for the ordering expression `sort`, and whether it is a peer of it
+fn sorts_before(sort: &SortExpr, arg: &Expr) -> (Expr, Expr) {
+    let value = sort.expr.clone();
+    let op = if sort.asc { Operator::Lt } else { Operator::Gt };
+    let before = binary_expr(value.clone(), op, arg.clone());
+    let null_before = if sort.nulls_first {
+        and(value.clone().is_null(), arg.clone().is_not_null())
+    } else {
+        and(value.clone().is_not_null(), arg.clone().is_null())
+    };
+    let peer = binary_expr(value, Operator::IsNotDistinctFrom, arg.clone());
+    (or(null_before, before), peer)
+}
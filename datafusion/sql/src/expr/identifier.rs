@@ -26,7 +26,7 @@ use datafusion_expr::{Case, Expr};
 use sqlparser::ast::{CaseWhen, Expr as SQLExpr, Ident};
 use std::sync::Arc;
 
-use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel, names_in_scope};
 use datafusion_expr::UNNAMED_TABLE;
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
@@ -53,11 +53,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 })?;
             Ok(Expr::ScalarVariable(field, var_names))
         } else {
+            self.check_unreserved(&id)?;
             // Don't use `col()` here because it will try to
             // interpret names with '.' as if they were
             // compound identifiers, but this is not a compound
             // identifier. (e.g. it is "foo.bar" not foo.bar)
-            let normalize_ident = self.ident_normalizer.normalize(id);
+            let normalize_ident =
+                self.resolve_ident_case(id, names_in_scope(schema, planner_context))?;
 
             // Check for qualified field with unqualified name
             if let Ok((qualifier, _)) =
@@ -113,6 +115,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 .filter_map(|id| Span::try_from_sqlparser_span(id.span)),
         );
 
+        if !ids[0].value.starts_with('@') {
+            for id in &ids {
+                self.check_unreserved(id)?;
+            }
+        }
+
         if ids[0].value.starts_with('@') {
             let var_names: Vec<_> = ids
                 .into_iter()
@@ -131,12 +139,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 })?;
             Ok(Expr::ScalarVariable(field, var_names))
         } else {
+            let names = names_in_scope(schema, planner_context);
             let ids = ids
                 .into_iter()
-                .map(|id| self.ident_normalizer.normalize(id))
-                .collect::<Vec<_>>();
-
-            let search_result = search_dfschema(&ids, schema);
+                .map(|id| self.resolve_ident_case(id, names.iter().copied()))
+                .collect::<Result<Vec<_>>>()?;
+
+            let search_result = if planner_context.is_pattern_variable(&ids[0]) {
+                // Pattern variables of MATCH_RECOGNIZE qualify the unqualified
+                // columns of its input
+                let (_, field) = schema.qualified_field_with_unqualified_name(&ids[1])?;
+                Some((field, None, &ids[2..]))
+            } else {
+                search_dfschema(&ids, schema)
+            };
             match search_result {
                 // Found matching field with spare identifier(s) for nested field(s) in structure
                 Some((field, qualifier, nested_names)) if !nested_names.is_empty() => {
@@ -22,35 +22,44 @@ use datafusion_expr::planner::{
 use sqlparser::ast::{
     AccessExpr, BinaryOperator, CastFormat, CastKind, CeilFloorKind,
     DataType as SQLDataType, DateTimeField, Expr as SQLExpr,
-    ExprWithAlias as SQLExprWithAlias, JsonPathElem, StructField, Subscript,
+    ExprWithAlias as SQLExprWithAlias, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments, JsonPathElem, ObjectName, Spanned, StructField, Subscript,
     TrimWhereField, TypedString, Value, ValueWithSpan,
 };
 
+use datafusion_common::metadata::FieldMetadata;
 use datafusion_common::{
-    DFSchema, Result, ScalarValue, internal_datafusion_err, internal_err, not_impl_err,
-    plan_datafusion_err, plan_err,
+    DFSchema, Result, ScalarValue, Span, internal_datafusion_err, internal_err,
+    not_impl_err, plan_err,
 };
 
 use datafusion_expr::expr::ScalarFunction;
 use datafusion_expr::expr::{
-    AllExpr, AnyExpr, InList, QuantifiedSource, WildcardOptions,
+    Alias, AllExpr, AnyExpr, InList, QuantifiedSource, WildcardOptions,
 };
+use datafusion_expr::utils::{conjunction, disjunction};
 use datafusion_expr::{
     Between, BinaryExpr, Cast, Expr, ExprSchemable, GetFieldAccess, Like, Operator,
     TryCast, lit,
 };
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use period::PeriodPredicate;
 
 mod binary_op;
+mod cast_format;
+mod collation;
 mod function;
 mod grouping_set;
+mod hypothetical_set;
 mod identifier;
 mod order_by;
+mod period;
 mod subquery;
 mod substring;
 mod unary_op;
 mod value;
+mod xml;
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
     pub(crate) fn sql_expr_to_logical_expr_with_alias(
@@ -74,7 +83,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     ) -> Result<Expr> {
         enum StackEntry {
             SQLExpr(Box<SQLExpr>),
-            Operator(BinaryOperator),
+            Operator(BinaryOperator, Option<Span>),
         }
 
         // Virtual stack machine to convert SQLExpr to Expr
@@ -89,8 +98,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 StackEntry::SQLExpr(sql_expr) => {
                     match *sql_expr {
                         SQLExpr::BinaryOp { left, op, right } => {
-                            if matches!(op, BinaryOperator::Overlaps) {
-                                let expr = self.plan_pg_overlaps_expr(
+                            if let Some(predicate) = PeriodPredicate::from_operator(&op) {
+                                let expr = self.sql_period_predicate_to_expr(
+                                    predicate,
                                     *left,
                                     *right,
                                     schema,
@@ -98,27 +108,54 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 )?;
                                 eval_stack.push(expr);
                             } else {
+                                let span = if self.options.collect_spans {
+                                    Span::try_from_sqlparser_span(
+                                        left.span().union(&right.span()),
+                                    )
+                                } else {
+                                    None
+                                };
                                 // Note the order that we push the entries to the stack
                                 // is important. We want to visit the left node first.
-                                stack.push(StackEntry::Operator(op));
+                                stack.push(StackEntry::Operator(op, span));
                                 stack.push(StackEntry::SQLExpr(right));
                                 stack.push(StackEntry::SQLExpr(left));
                             }
                         }
                         _ => {
+                            let span = self.span_of(sql_expr.as_ref());
+                            let depth = planner_context.expr_depth();
+                            let limit = self
+                                .context_provider
+                                .options()
+                                .sql_parser
+                                .expr_depth_limit;
+                            if depth >= limit {
+                                return self.limit_exceeded_error(
+                                    "Expression nesting depth",
+                                    limit,
+                                    "expr_depth_limit",
+                                    Span::try_from_sqlparser_span(sql_expr.span()),
+                                );
+                            }
+                            planner_context.set_expr_depth(depth + 1);
                             let expr = self.sql_expr_to_logical_expr_internal(
                                 *sql_expr,
                                 schema,
                                 planner_context,
-                            )?;
-                            eval_stack.push(expr);
+                            );
+                            planner_context.set_expr_depth(depth);
+                            eval_stack
+                                .push(expr.map_err(|err| self.locate_error(err, span))?);
                         }
                     }
                 }
-                StackEntry::Operator(op) => {
+                StackEntry::Operator(op, span) => {
                     let right = eval_stack.pop().unwrap();
                     let left = eval_stack.pop().unwrap();
-                    let expr = self.build_logical_expr(op, left, right, schema)?;
+                    let expr = self
+                        .build_logical_expr(op, left, right, schema)
+                        .map_err(|err| self.locate_error(err, span))?;
                     eval_stack.push(expr);
                 }
             }
@@ -129,139 +166,74 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(expr)
     }
 
-    fn plan_pg_overlaps_expr(
+    /// Plans `left IS [NOT] DISTINCT FROM right`.
+    ///
+    /// When both sides are row value constructors, the predicate is planned
+    /// field by field, so that each pair of fields is coerced on its own and
+    /// compared with null-safe semantics:
+    ///
+    /// * `(a1, a2) IS DISTINCT FROM (b1, b2)` is
+    ///   `a1 IS DISTINCT FROM b1 OR a2 IS DISTINCT FROM b2`
+    /// * `(a1, a2) IS NOT DISTINCT FROM (b1, b2)` is
+    ///   `a1 IS NOT DISTINCT FROM b1 AND a2 IS NOT DISTINCT FROM b2`
+    ///
+    /// Other struct values are compared as a whole, which is null-safe for
+    /// their fields as well.
+    fn sql_distinct_predicate_to_expr(
         &self,
         left: SQLExpr,
         right: SQLExpr,
+        negated: bool,
         schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
-        let (left_start_sql, left_end_sql) = self.extract_pg_overlaps_period(left)?;
-        let (right_start_sql, right_end_sql) = self.extract_pg_overlaps_period(right)?;
-
-        let left_end_is_interval = matches!(left_end_sql, SQLExpr::Interval(_));
-        let right_end_is_interval = matches!(right_end_sql, SQLExpr::Interval(_));
-
-        let left_start_expr =
-            self.sql_expr_to_logical_expr(left_start_sql, schema, planner_context)?;
-        let left_end_raw =
-            self.sql_expr_to_logical_expr(left_end_sql, schema, planner_context)?;
-        let right_start_expr =
-            self.sql_expr_to_logical_expr(right_start_sql, schema, planner_context)?;
-        let right_end_raw =
-            self.sql_expr_to_logical_expr(right_end_sql, schema, planner_context)?;
-
-        let left_end_expr = if left_end_is_interval {
-            Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(left_start_expr.clone()),
-                Operator::Plus,
-                Box::new(left_end_raw),
-            ))
-        } else {
-            left_end_raw
-        };
-        let right_end_expr = if right_end_is_interval {
-            Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(right_start_expr.clone()),
-                Operator::Plus,
-                Box::new(right_end_raw),
-            ))
-        } else {
-            right_end_raw
-        };
+        if let (Some(left), Some(right)) = (
+            row_constructor_fields(&left),
+            row_constructor_fields(&right),
+        ) {
+            if left.len() != right.len() {
+                return plan_err!(
+                    "Row values compared by IS [NOT] DISTINCT FROM must have the same number of fields, got {} and {}",
+                    left.len(),
+                    right.len()
+                );
+            }
+            let predicates = left
+                .into_iter()
+                .zip(right)
+                .map(|(left, right)| {
+                    self.sql_distinct_predicate_to_expr(
+                        left,
+                        right,
+                        negated,
+                        schema,
+                        planner_context,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let predicate = if negated {
+                conjunction(predicates)
+            } else {
+                disjunction(predicates)
+            };
+            return match predicate {
+                Some(predicate) => Ok(predicate),
+                None => plan_err!("Row values must have at least one field"),
+            };
+        }
 
-        let least = |lhs: Expr, rhs: Expr| -> Result<Expr> {
-            let udf = self
-                .context_provider
-                .get_function_meta("least")
-                .ok_or_else(|| {
-                    plan_datafusion_err!("Function least is required to plan OVERLAPS")
-                })?;
-            Ok(Expr::ScalarFunction(ScalarFunction::new_udf(
-                udf,
-                vec![lhs, rhs],
-            )))
-        };
-        let greatest = |lhs: Expr, rhs: Expr| -> Result<Expr> {
-            let udf = self
-                .context_provider
-                .get_function_meta("greatest")
-                .ok_or_else(|| {
-                    plan_datafusion_err!("Function greatest is required to plan OVERLAPS")
-                })?;
-            Ok(Expr::ScalarFunction(ScalarFunction::new_udf(
-                udf,
-                vec![lhs, rhs],
-            )))
+        let op = if negated {
+            Operator::IsNotDistinctFrom
+        } else {
+            Operator::IsDistinctFrom
         };
-
-        let start1 = least(left_start_expr.clone(), left_end_expr.clone())?;
-        let end1 = greatest(left_start_expr, left_end_expr)?;
-        let start2 = least(right_start_expr.clone(), right_end_expr.clone())?;
-        let end2 = greatest(right_start_expr, right_end_expr)?;
-
-        // PostgreSQL OVERLAPS uses half-open intervals [start, end), with point-in-time
-        // periods treated specially (start == end).
-        let half_open_overlap = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(start1.clone()),
-                Operator::Lt,
-                Box::new(end2.clone()),
-            ))),
-            Operator::And,
-            Box::new(Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(start2.clone()),
-                Operator::Lt,
-                Box::new(end1.clone()),
-            ))),
-        ));
-
-        let both_points_same = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(Expr::BinaryExpr(BinaryExpr::new(
-                    Box::new(start1.clone()),
-                    Operator::Eq,
-                    Box::new(end1),
-                ))),
-                Operator::And,
-                Box::new(Expr::BinaryExpr(BinaryExpr::new(
-                    Box::new(start2.clone()),
-                    Operator::Eq,
-                    Box::new(end2),
-                ))),
-            ))),
-            Operator::And,
-            Box::new(Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(start1),
-                Operator::Eq,
-                Box::new(start2),
-            ))),
-        ));
-
         Ok(Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(half_open_overlap),
-            Operator::Or,
-            Box::new(both_points_same),
+            Box::new(self.sql_expr_to_logical_expr(left, schema, planner_context)?),
+            op,
+            Box::new(self.sql_expr_to_logical_expr(right, schema, planner_context)?),
         )))
     }
 
-    fn extract_pg_overlaps_period(&self, expr: SQLExpr) -> Result<(SQLExpr, SQLExpr)> {
-        match expr {
-            SQLExpr::Nested(inner) => self.extract_pg_overlaps_period(*inner),
-            SQLExpr::Tuple(mut values) => {
-                if values.len() != 2 {
-                    return plan_err!(
-                        "OVERLAPS requires tuple arguments with exactly two elements"
-                    );
-                }
-                let second = values.pop().unwrap();
-                let first = values.pop().unwrap();
-                Ok((first, second))
-            }
-            _ => not_impl_err!("OVERLAPS requires tuple arguments"),
-        }
-    }
-
     fn build_logical_expr(
         &self,
         op: BinaryOperator,
@@ -269,6 +241,22 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         right: Expr,
         schema: &DFSchema,
     ) -> Result<Expr> {
+        // Character strings are compared in the order of their collation,
+        // while other operators ignore it
+        let (left, right) = if matches!(
+            op,
+            BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+        ) {
+            self.collate_comparison(left, right, schema)?
+        } else {
+            (unalias_metadata(left), unalias_metadata(right))
+        };
+
         // try extension planers
         let mut binary_expr = RawBinaryExpr { op, left, right };
         for planner in self.context_provider.get_expr_planners() {
@@ -426,6 +414,23 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 planner_context,
             ),
 
+            SQLExpr::Convert {
+                is_try,
+                expr,
+                data_type,
+                charset,
+                target_before_value: _,
+                styles,
+            } => self.sql_convert_to_expr(
+                is_try,
+                *expr,
+                data_type,
+                charset,
+                styles,
+                schema,
+                planner_context,
+            ),
+
             SQLExpr::TypedString(TypedString {
                 data_type,
                 value,
@@ -454,37 +459,22 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 self.sql_expr_to_logical_expr(*expr, schema, planner_context)?,
             ))),
 
-            SQLExpr::IsDistinctFrom(left, right) => {
-                Ok(Expr::BinaryExpr(BinaryExpr::new(
-                    Box::new(self.sql_expr_to_logical_expr(
-                        *left,
-                        schema,
-                        planner_context,
-                    )?),
-                    Operator::IsDistinctFrom,
-                    Box::new(self.sql_expr_to_logical_expr(
-                        *right,
-                        schema,
-                        planner_context,
-                    )?),
-                )))
-            }
+            SQLExpr::IsDistinctFrom(left, right) => self.sql_distinct_predicate_to_expr(
+                *left,
+                *right,
+                false,
+                schema,
+                planner_context,
+            ),
 
-            SQLExpr::IsNotDistinctFrom(left, right) => {
-                Ok(Expr::BinaryExpr(BinaryExpr::new(
-                    Box::new(self.sql_expr_to_logical_expr(
-                        *left,
-                        schema,
-                        planner_context,
-                    )?),
-                    Operator::IsNotDistinctFrom,
-                    Box::new(self.sql_expr_to_logical_expr(
-                        *right,
-                        schema,
-                        planner_context,
-                    )?),
-                )))
-            }
+            SQLExpr::IsNotDistinctFrom(left, right) => self
+                .sql_distinct_predicate_to_expr(
+                    *left,
+                    *right,
+                    true,
+                    schema,
+                    planner_context,
+                ),
 
             SQLExpr::IsTrue { expr, .. } => Ok(Expr::IsTrue(Box::new(
                 self.sql_expr_to_logical_expr(*expr, schema, planner_context)?,
@@ -779,6 +769,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             SQLExpr::Position { expr, r#in } => {
                 self.sql_position_to_expr(*expr, *r#in, schema, planner_context)
             }
+            SQLExpr::XmlElement {
+                name,
+                attributes,
+                content,
+            } => self.sql_xml_element_to_expr(
+                name,
+                attributes,
+                content,
+                schema,
+                planner_context,
+            ),
+            SQLExpr::XmlForest(elements) => {
+                self.sql_xml_forest_to_expr(elements, schema, planner_context)
+            }
             SQLExpr::AtTimeZone {
                 timestamp,
                 time_zone,
@@ -877,8 +881,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             SQLExpr::JsonAccess { value, path } => {
                 self.plan_json_access(*value, path.path, schema, planner_context)
             }
-            SQLExpr::Collate { expr, .. } => {
-                self.sql_expr_to_logical_expr(*expr, schema, planner_context)
+            SQLExpr::Collate { expr, collation } => {
+                self.sql_collate_to_expr(*expr, collation, schema, planner_context)
             }
             _ => not_impl_err!("Unsupported ast node in sqltorel: {sql:?}"),
         }
@@ -1051,9 +1055,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
+        // Large lists are mostly literals, which are parsed directly rather
+        // than through the general expression planning
         let list_expr = list
             .into_iter()
-            .map(|e| self.sql_expr_to_logical_expr(e, schema, planner_context))
+            .map(|e| match e {
+                SQLExpr::Value(value) => self.parse_value(
+                    value.into(),
+                    planner_context.prepare_param_data_types(),
+                    planner_context,
+                ),
+                e => self.sql_expr_to_logical_expr(e, schema, planner_context),
+            })
             .collect::<Result<Vec<_>>>()?;
 
         Ok(Expr::InList(InList::new(
@@ -1223,11 +1236,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
-        if let Some(format) = format {
-            return not_impl_err!("CAST with format is not supported: {format}");
-        }
-
         if Self::is_regclass_sql_type(data_type) {
+            if let Some(format) = format {
+                return not_impl_err!("CAST with format is not supported: {format}");
+            }
             return self.sql_regclass_cast_to_expr(expr, schema, planner_context);
         }
 
@@ -1235,6 +1247,74 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         self.finish_cast_expr(expr, data_type, cast_kind, format, schema)
     }
 
+    /// Plans `CONVERT(expr USING charset)`, which converts the character
+    /// string `expr` to the character set `charset`, and `CONVERT(expr, type
+    /// [CHARACTER SET charset])`, which casts `expr` to `type` first
+    #[expect(clippy::too_many_arguments)]
+    fn sql_convert_to_expr(
+        &self,
+        is_try: bool,
+        expr: SQLExpr,
+        data_type: Option<SQLDataType>,
+        charset: Option<ObjectName>,
+        styles: Vec<SQLExpr>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        if !styles.is_empty() {
+            return not_impl_err!("CONVERT with styles is not supported");
+        }
+        let mut expr = self.sql_expr_to_logical_expr(expr, schema, planner_context)?;
+        if let Some(data_type) = data_type {
+            let cast_kind = if is_try {
+                CastKind::TryCast
+            } else {
+                CastKind::Cast
+            };
+            expr = self.finish_cast_expr(expr, &data_type, cast_kind, None, schema)?;
+        }
+        let Some(charset) = charset else {
+            return Ok(expr);
+        };
+
+        let mut parts = vec![];
+        for part in charset.0 {
+            let Some(ident) = part.as_ident() else {
+                return plan_err!("Invalid character set name {part}");
+            };
+            parts.push(self.ident_normalizer.normalize(ident.clone()));
+        }
+        let name = parts.join(".");
+        let data_type = expr.get_type(schema)?;
+        if !matches!(
+            data_type,
+            DataType::Utf8
+                | DataType::LargeUtf8
+                | DataType::Utf8View
+                | DataType::Binary
+                | DataType::LargeBinary
+                | DataType::BinaryView
+                | DataType::Null
+        ) {
+            return plan_err!(
+                "CONVERT USING {name} requires a character or binary string, got {data_type}"
+            );
+        }
+        if name.eq_ignore_ascii_case("utf8") || name.eq_ignore_ascii_case("utf8mb4") {
+            // Binary strings are decoded, which fails on invalid UTF-8
+            return Ok(match data_type {
+                DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+                    Expr::Cast(Cast::new(Box::new(expr), DataType::Utf8))
+                }
+                _ => expr,
+            });
+        }
+        match self.context_provider.get_character_set(&name) {
+            Some(conversion) => Ok(conversion.call(vec![expr])),
+            None => plan_err!("Unknown character set {name}"),
+        }
+    }
+
     fn finish_cast_expr(
         &self,
         expr: Expr,
@@ -1245,10 +1325,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     ) -> Result<Expr> {
         // numeric constants are treated as seconds (rather as nanoseconds)
         // to align with postgres / duckdb semantics
-        let target_data_type = self
-            .convert_data_type_to_field(sql_data_type)?
-            .data_type()
-            .clone();
+        let target_field = self.convert_data_type_to_field(sql_data_type)?;
+        let target_data_type = target_field.data_type().clone();
         let mut cast_expr = RawCastExpr {
             cast_kind,
             expr,
@@ -1266,16 +1344,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         }
 
-        if let Some(format) = cast_expr.format {
-            return not_impl_err!("CAST with format is not supported: {format}");
-        }
-
         let RawCastExpr {
             cast_kind,
             expr,
             data_type,
+            format,
             ..
         } = cast_expr;
+        if let Some(format) = format {
+            return self
+                .sql_cast_format_to_expr(expr, data_type, cast_kind, format, schema);
+        }
         let expr = match &data_type {
             DataType::Timestamp(TimeUnit::Nanosecond, tz)
                 if expr.get_type(schema)? == DataType::Int64 =>
@@ -1288,16 +1367,22 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             _ => expr,
         };
 
-        // Currently drops metadata attached to the type
-        // https://github.com/apache/datafusion/issues/18060
-        match cast_kind {
-            CastKind::TryCast => {
-                Ok(Expr::TryCast(TryCast::new(Box::new(expr), data_type)))
-            }
+        let expr = match cast_kind {
+            CastKind::TryCast => Expr::TryCast(TryCast::new(Box::new(expr), data_type)),
             CastKind::Cast | CastKind::DoubleColon => {
-                Ok(Expr::Cast(Cast::new(Box::new(expr), data_type)))
+                Expr::Cast(Cast::new(Box::new(expr), data_type))
             }
+        };
+
+        // The metadata attached to the type, such as the declared character
+        // set of `NCHAR`, is kept in the field of the cast by aliasing it to
+        // its own name
+        if target_field.metadata().is_empty() {
+            return Ok(expr);
         }
+        let metadata = FieldMetadata::from(target_field.metadata());
+        let (relation, name) = expr.qualified_name();
+        Ok(expr.alias_qualified_with_metadata(relation, name, Some(metadata)))
     }
 
     fn sql_regclass_cast_to_expr(
@@ -1541,6 +1626,28 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         }
                     }
                 }
+                // Otherwise slices, such as `arr[2:]` or `arr[-3:-1]`, are
+                // planned with the `array_slice` function, which counts
+                // negative indexes from the end of the array
+                if let GetFieldAccess::ListRange {
+                    start,
+                    stop,
+                    stride,
+                } = &field_access_expr.field_access
+                    && let Some(array_slice) =
+                        self.context_provider.get_function_meta("array_slice")
+                {
+                    let args = vec![
+                        field_access_expr.expr,
+                        start.as_ref().clone(),
+                        stop.as_ref().clone(),
+                        stride.as_ref().clone(),
+                    ];
+                    return Ok(Expr::ScalarFunction(ScalarFunction::new_udf(
+                        array_slice,
+                        args,
+                    )));
+                }
                 not_impl_err!(
                     "GetFieldAccess not supported by ExprPlanner: {field_access_expr:?}"
                 )
@@ -1579,6 +1686,54 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 }
 
+/// Removes the alias that gives `expr` field metadata under its own name,
+/// such as an explicit collation or the declared character set of a cast,
+/// once the operator it is an operand of no longer needs it
+pub(super) fn unalias_metadata(expr: Expr) -> Expr {
+    match expr {
+        Expr::Alias(Alias {
+            expr,
+            relation,
+            name,
+            metadata: Some(_),
+        }) if expr.qualified_name() == (relation.clone(), name.clone()) => *expr,
+        expr => expr,
+    }
+}
+
+/// Returns the fields of a row value constructor, `(a, b)` or `ROW(a, b)`
+fn row_constructor_fields(expr: &SQLExpr) -> Option<Vec<SQLExpr>> {
+    match expr {
+        SQLExpr::Tuple(fields) => Some(fields.clone()),
+        SQLExpr::Function(Function {
+            name,
+            args: FunctionArguments::List(args),
+            over: None,
+            filter: None,
+            within_group,
+            ..
+        }) if name.0.len() == 1
+            && name.0[0]
+                .as_ident()
+                .is_some_and(|ident| ident.value.eq_ignore_ascii_case("row"))
+            && within_group.is_empty()
+            && args.duplicate_treatment.is_none()
+            && args.clauses.is_empty() =>
+        {
+            args.args
+                .iter()
+                .map(|arg| match arg {
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                        Some(expr.clone())
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -1733,7 +1888,7 @@ mod tests {
     }
 
     #[test]
-    fn test_overlaps_keyword_routes_to_pg_overlaps_planner() {
+    fn test_overlaps_keyword_routes_to_period_predicate_planner() {
         let schema = DFSchema::empty();
         let mut planner_context = PlannerContext::default();
 
@@ -1746,18 +1901,65 @@ mod tests {
         let context_provider = TestContextProvider::new();
         let sql_to_rel = SqlToRel::new(&context_provider);
 
-        let err = sql_to_rel
+        // The keyword is not planned as a comparison of tuples
+        let expr = sql_to_rel
             .sql_expr_to_logical_expr(sql_expr, &schema, &mut planner_context)
-            .unwrap_err();
-        let err_text = err.to_string();
-
-        assert!(
-            err_text.contains("Function least is required to plan OVERLAPS"),
-            "unexpected planner error: {err_text}"
-        );
+            .unwrap();
         assert!(
-            !err_text.contains("Only identifiers and literals are supported in tuples"),
-            "keyword OVERLAPS fell back to tuple parser: {err_text}"
+            matches!(
+                &expr,
+                Expr::BinaryExpr(BinaryExpr {
+                    op: Operator::Or,
+                    ..
+                })
+            ),
+            "unexpected OVERLAPS plan: {expr}"
         );
     }
+
+    #[test]
+    fn test_period_predicates() {
+        let schema = DFSchema::empty();
+        let context_provider = TestContextProvider::new();
+        let sql_to_rel = SqlToRel::new(&context_provider);
+        let dialect = PostgreSqlDialect {};
+        let parse = |sql: &str| {
+            Box::new(
+                Parser::new(&dialect)
+                    .try_with_sql(sql)
+                    .unwrap()
+                    .parse_expr()
+                    .unwrap(),
+            )
+        };
+
+        for (op, right, expected) in [
+            ("PRECEDES", "(3, 4)", "Int32(2) <= Int32(3)"),
+            ("immediately  succeeds", "(3, 4)", "Int32(1) = Int32(4)"),
+            (
+                "EQUALS",
+                "PERIOD(3, 4)",
+                "Int32(1) = Int32(3) AND Int32(2) = Int32(4)",
+            ),
+            (
+                "CONTAINS",
+                "5",
+                "Int32(1) <= Int32(5) AND Int32(2) > Int32(5)",
+            ),
+        ] {
+            let sql_expr = SQLExpr::BinaryOp {
+                left: parse("(1, 2)"),
+                op: BinaryOperator::Custom(op.to_string()),
+                right: parse(right),
+            };
+            let expr = sql_to_rel
+                .sql_expr_to_logical_expr(
+                    sql_expr,
+                    &schema,
+                    &mut PlannerContext::default(),
+                )
+                .unwrap();
+            assert_eq!(expr.to_string(), expected, "{op}");
+        }
+    }
 }
@@ -112,6 +112,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     self.sql_expr_to_logical_expr(e, order_by_schema, planner_context)?
                 }
             };
+            // Character strings are ordered by the sort key of their collation
+            let expr = self.collate_sort_expr(expr, order_by_schema)?;
             sort_expr_vec.push(make_sort_expr(expr, asc, nulls_first));
         }
 
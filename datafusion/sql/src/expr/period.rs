@@ -0,0 +1,222 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of `OVERLAPS` and of the other predicates between two periods,
+//! such as `(start1, end1) PRECEDES (start2, end2)`
+
+use std::fmt::{self, Display};
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use arrow::datatypes::DataType;
+use datafusion_common::{DFSchema, Result, not_impl_err, plan_err};
+use datafusion_expr::{Expr, ExprSchemable, and, not, or, when};
+use sqlparser::ast::{
+    BinaryOperator, Expr as SQLExpr, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments,
+};
+
+/// A predicate between two periods, each given by its start and its end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PeriodPredicate {
+    Overlaps,
+    Equals,
+    Contains,
+    Precedes,
+    Succeeds,
+    ImmediatelyPrecedes,
+    ImmediatelySucceeds,
+}
+
+impl PeriodPredicate {
+    /// The period predicate of the binary operator `op`, if any. The period
+    /// predicates other than `OVERLAPS` are custom operators to the parser.
+    pub(super) fn from_operator(op: &BinaryOperator) -> Option<Self> {
+        let name = match op {
+            BinaryOperator::Overlaps => return Some(Self::Overlaps),
+            BinaryOperator::Custom(name) => name.split_whitespace().collect::<Vec<_>>(),
+            _ => return None,
+        };
+        [
+            Self::Equals,
+            Self::Contains,
+            Self::Precedes,
+            Self::Succeeds,
+            Self::ImmediatelyPrecedes,
+            Self::ImmediatelySucceeds,
+        ]
+        .into_iter()
+        .find(|predicate| predicate.to_string().eq_ignore_ascii_case(&name.join(" ")))
+    }
+}
+
+impl Display for PeriodPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Overlaps => "OVERLAPS",
+            Self::Equals => "EQUALS",
+            Self::Contains => "CONTAINS",
+            Self::Precedes => "PRECEDES",
+            Self::Succeeds => "SUCCEEDS",
+            Self::ImmediatelyPrecedes => "IMMEDIATELY PRECEDES",
+            Self::ImmediatelySucceeds => "IMMEDIATELY SUCCEEDS",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans `left <predicate> right` between the periods `left` and `right`,
+    /// which are `(start, end)` or `PERIOD(start, end)`, and contain the
+    /// points in time from their start up to, but excluding, their end.
+    ///
+    /// `CONTAINS` may also test whether a period contains a point in time,
+    /// and `OVERLAPS` follows the rules of the row value predicate of the
+    /// standard, where the end may be an interval from the start and the
+    /// bounds are swapped when the end is before the start.
+    pub(super) fn sql_period_predicate_to_expr(
+        &self,
+        predicate: PeriodPredicate,
+        left: SQLExpr,
+        right: SQLExpr,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let (start1, end1) =
+            self.period_to_exprs(predicate, left, schema, planner_context)?;
+        if predicate == PeriodPredicate::Contains && !is_period(&right) {
+            let point = self.sql_expr_to_logical_expr(right, schema, planner_context)?;
+            return Ok(and(start1.lt_eq(point.clone()), end1.gt(point)));
+        }
+        let (start2, end2) =
+            self.period_to_exprs(predicate, right, schema, planner_context)?;
+
+        Ok(match predicate {
+            PeriodPredicate::Overlaps => overlaps(start1, end1, start2, end2),
+            PeriodPredicate::Equals => and(start1.eq(start2), end1.eq(end2)),
+            PeriodPredicate::Contains => and(start1.lt_eq(start2), end1.gt_eq(end2)),
+            PeriodPredicate::Precedes => end1.lt_eq(start2),
+            PeriodPredicate::Succeeds => start1.gt_eq(end2),
+            PeriodPredicate::ImmediatelyPrecedes => end1.eq(start2),
+            PeriodPredicate::ImmediatelySucceeds => start1.eq(end2),
+        })
+    }
+
+    /// Plans the start and the end of the period `expr`
+    fn period_to_exprs(
+        &self,
+        predicate: PeriodPredicate,
+        expr: SQLExpr,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<(Expr, Expr)> {
+        let (start, end) = period_bounds(predicate, expr)?;
+        let start = self.sql_expr_to_logical_expr(start, schema, planner_context)?;
+        let end = self.sql_expr_to_logical_expr(end, schema, planner_context)?;
+        if predicate != PeriodPredicate::Overlaps {
+            return Ok((start, end));
+        }
+
+        let end = if matches!(end.get_type(schema)?, DataType::Interval(_)) {
+            start.clone() + end
+        } else {
+            end
+        };
+        let swap = or(start.clone().is_null(), end.clone().lt(start.clone()));
+        Ok((
+            when(swap.clone(), end.clone()).otherwise(start.clone())?,
+            when(swap, start).otherwise(end)?,
+        ))
+    }
+}
+
+/// `(s1, t1) OVERLAPS (s2, t2)` for bounds ordered so that each start is
+/// not after its end, as defined by the standard. The last term is only
+/// true when neither `t1` nor `t2` is null.
+fn overlaps(s1: Expr, t1: Expr, s2: Expr, t2: Expr) -> Expr {
+    let s1_first = and(
+        s1.clone().gt(s2.clone()),
+        not(and(
+            s1.clone().gt_eq(t2.clone()),
+            t1.clone().gt_eq(t2.clone()),
+        )),
+    );
+    let s2_first = and(
+        s2.clone().gt(s1.clone()),
+        not(and(
+            s2.clone().gt_eq(t1.clone()),
+            t2.clone().gt_eq(t1.clone()),
+        )),
+    );
+    let same_start = and(s1.eq(s2), or(t1.clone().not_eq(t2.clone()), t1.eq(t2)));
+    or(or(s1_first, s2_first), same_start)
+}
+
+/// The start and the end of the period `expr`
+fn period_bounds(
+    predicate: PeriodPredicate,
+    expr: SQLExpr,
+) -> Result<(SQLExpr, SQLExpr)> {
+    match expr {
+        SQLExpr::Nested(inner) => period_bounds(predicate, *inner),
+        SQLExpr::Tuple(values) => match <[SQLExpr; 2]>::try_from(values) {
+            Ok([start, end]) => Ok((start, end)),
+            Err(_) => {
+                plan_err!(
+                    "{predicate} requires tuple arguments with exactly two elements"
+                )
+            }
+        },
+        SQLExpr::Function(function) if is_period_constructor(&function) => {
+            let FunctionArguments::List(list) = function.args else {
+                return plan_err!("PERIOD requires a start and an end");
+            };
+            let bounds = list
+                .args
+                .into_iter()
+                .map(|arg| match arg {
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                    _ => plan_err!("PERIOD requires a start and an end"),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            match <[SQLExpr; 2]>::try_from(bounds) {
+                Ok([start, end]) => Ok((start, end)),
+                Err(_) => plan_err!("PERIOD requires a start and an end"),
+            }
+        }
+        _ => not_impl_err!("{predicate} requires tuple arguments"),
+    }
+}
+
+/// Returns true if `function` is `PERIOD(start, end)`
+fn is_period_constructor(function: &Function) -> bool {
+    matches!(
+        function.name.0.as_slice(),
+        [part] if part
+            .as_ident()
+            .is_some_and(|ident| ident.value.eq_ignore_ascii_case("period"))
+    )
+}
+
+/// Returns true if `expr` is a period rather than a point in time
+fn is_period(expr: &SQLExpr) -> bool {
+    match expr {
+        SQLExpr::Nested(inner) => is_period(inner),
+        SQLExpr::Tuple(_) => true,
+        SQLExpr::Function(function) => is_period_constructor(function),
+        _ => false,
+    }
+}
@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use crate::planner::{
+    ContextProvider, MAX_FRACTIONAL_SECONDS_PRECISION, PlannerContext, SqlToRel,
+};
 use arrow::compute::kernels::cast_utils::{
     IntervalParseConfig, IntervalUnit, parse_interval_month_day_nano_config,
 };
@@ -60,12 +62,24 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             Value::HexStringLiteral(s) => {
                 if let Some(v) = try_decode_hex_literal(&s) {
                     Ok(lit(v))
+                } else if s.chars().filter(|c| *c != ' ').count() % 2 != 0 {
+                    plan_err!("HexStringLiteral '{s}' has an odd number of hex digits")
                 } else {
                     plan_err!("Invalid HexStringLiteral '{s}'")
                 }
             }
+            Value::SingleQuotedByteStringLiteral(s) => {
+                if let Some(v) = try_decode_bit_literal(&s) {
+                    Ok(lit(v))
+                } else {
+                    plan_err!("Invalid binary string literal B'{s}'")
+                }
+            }
             Value::DollarQuotedString(s) => Ok(lit(s.value)),
             Value::EscapedStringLiteral(s) => Ok(lit(s)),
+            Value::UnicodeStringLiteral(s) => {
+                Ok(lit(decode_unicode_escapes(&s, DEFAULT_UNICODE_ESCAPE)?))
+            }
             _ => plan_err!("Unsupported Value '{value:?}'"),
         }
     }
@@ -209,31 +223,25 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         negative: bool,
         interval: Interval,
     ) -> Result<Expr> {
-        if interval.leading_precision.is_some() {
-            return not_impl_err!(
-                "Unsupported Interval Expression with leading_precision {:?}",
-                interval.leading_precision
-            );
-        }
+        let leading_precision = interval.leading_precision;
+        let fractional_seconds_precision = interval.fractional_seconds_precision;
 
         // Handle compound intervals like INTERVAL '1-6' YEAR TO MONTH
         if let (Some(leading), Some(last)) =
             (&interval.leading_field, &interval.last_field)
         {
             let raw_value = interval_literal(*interval.value, negative)?;
+            check_interval_precision(
+                &raw_value,
+                leading_precision,
+                fractional_seconds_precision,
+            )?;
             let compound_value = parse_compound_interval(&raw_value, leading, last)?;
             let config = IntervalParseConfig::new(IntervalUnit::Second);
             let val = parse_interval_month_day_nano_config(&compound_value, config)?;
             return Ok(lit(ScalarValue::IntervalMonthDayNano(Some(val))));
         }
 
-        if interval.fractional_seconds_precision.is_some() {
-            return not_impl_err!(
-                "Unsupported Interval Expression with fractional_seconds_precision {:?}",
-                interval.fractional_seconds_precision
-            );
-        }
-
         if let SQLExpr::BinaryOp { left, op, right } = *interval.value {
             let df_op = match op {
                 BinaryOperator::Plus => Operator::Plus,
@@ -247,9 +255,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 Interval {
                     value: left,
                     leading_field: interval.leading_field.clone(),
-                    leading_precision: None,
+                    leading_precision,
                     last_field: None,
-                    fractional_seconds_precision: None,
+                    fractional_seconds_precision,
                 },
             )?;
             let right_expr = self.sql_interval_to_expr(
@@ -257,9 +265,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 Interval {
                     value: right,
                     leading_field: interval.leading_field,
-                    leading_precision: None,
+                    leading_precision,
                     last_field: None,
-                    fractional_seconds_precision: None,
+                    fractional_seconds_precision,
                 },
             )?;
             return Ok(Expr::BinaryExpr(BinaryExpr::new(
@@ -270,6 +278,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
 
         let value = interval_literal(*interval.value, negative)?;
+        check_interval_precision(
+            &value,
+            leading_precision,
+            fractional_seconds_precision,
+        )?;
 
         // leading_field really means the unit if specified
         // For example, "month" in  `INTERVAL '5' month`
@@ -392,6 +405,49 @@ fn normalize_iso8601_interval_literal(value: &str) -> Option<String> {
     Some(normalized)
 }
 
+/// Returns an error if the leading field of the interval literal `value` has
+/// more digits than `leading_precision`, or its seconds have more fractional
+/// digits than `fractional_seconds_precision`, as in `INTERVAL '123' DAY(2)`
+/// or `INTERVAL '1 12:30:45.1234' DAY TO SECOND(3)`
+fn check_interval_precision(
+    value: &str,
+    leading_precision: Option<u64>,
+    fractional_seconds_precision: Option<u64>,
+) -> Result<()> {
+    if let Some(precision) = leading_precision {
+        if precision == 0 {
+            return plan_err!("Interval leading field precision must be positive");
+        }
+        let digits = value
+            .trim_start_matches(['-', '+', ' '])
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>();
+        let significant_digits = digits.trim_start_matches('0').len();
+        if significant_digits as u64 > precision {
+            return plan_err!(
+                "Interval field overflow: leading field {digits} of '{value}' exceeds its precision {precision}"
+            );
+        }
+    }
+    if let Some(precision) = fractional_seconds_precision {
+        if precision > MAX_FRACTIONAL_SECONDS_PRECISION {
+            return plan_err!(
+                "Interval fractional seconds precision must be at most {MAX_FRACTIONAL_SECONDS_PRECISION}, got {precision}"
+            );
+        }
+        let fraction_digits = value.rsplit_once('.').map_or(0, |(_, fraction)| {
+            fraction.chars().take_while(char::is_ascii_digit).count()
+        });
+        if fraction_digits as u64 > precision {
+            return plan_err!(
+                "Interval field overflow: fractional seconds of '{value}' exceed their precision {precision}"
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Parse compound interval literals like "1-6" (YEAR TO MONTH) or "1 12:30:45" (DAY TO SECOND).
 ///
 /// Converts SQL standard compound interval formats to DataFusion's interval string format:
@@ -531,29 +587,52 @@ fn parse_compound_interval(
     }
 }
 
-/// Try to decode bytes from hex literal string.
+/// Try to decode bytes from hex literal string, which is made of pairs of
+/// hex digits, optionally separated by spaces.
 ///
-/// None will be returned if the input literal is hex-invalid.
+/// None will be returned if the input literal is hex-invalid, or has an odd
+/// number of hex digits.
 fn try_decode_hex_literal(s: &str) -> Option<Vec<u8>> {
-    let hex_bytes = s.as_bytes();
+    let mut hex_bytes = s.bytes();
 
-    let mut decoded_bytes = Vec::with_capacity(hex_bytes.len().div_ceil(2));
-
-    let start_idx = hex_bytes.len() % 2;
-    if start_idx > 0 {
-        // The first byte is formed of only one char.
-        decoded_bytes.push(try_decode_hex_char(hex_bytes[0])?);
-    }
+    let mut decoded_bytes = Vec::with_capacity(s.len() / 2);
 
-    for i in (start_idx..hex_bytes.len()).step_by(2) {
-        let high = try_decode_hex_char(hex_bytes[i])?;
-        let low = try_decode_hex_char(hex_bytes[i + 1])?;
+    while let Some(c) = hex_bytes.next() {
+        if c == b' ' {
+            continue;
+        }
+        let high = try_decode_hex_char(c)?;
+        let low = try_decode_hex_char(hex_bytes.next()?)?;
         decoded_bytes.push((high << 4) | low);
     }
 
     Some(decoded_bytes)
 }
 
+/// Try to decode bytes from the bits of a binary literal string such as
+/// `B'1010'`, most significant bit first. As for MySQL bit-value literals,
+/// the bits are padded with leading zeros to a whole number of bytes.
+///
+/// None will be returned if the input literal has a char other than `0` and
+/// `1`.
+fn try_decode_bit_literal(s: &str) -> Option<Vec<u8>> {
+    let mut decoded_bytes = Vec::with_capacity(s.len().div_ceil(8));
+    let mut byte = 0u8;
+    for (i, c) in s.bytes().enumerate() {
+        let bit = match c {
+            b'0' => 0,
+            b'1' => 1,
+            _ => return None,
+        };
+        byte = (byte << 1) | bit;
+        if (s.len() - i - 1) % 8 == 0 {
+            decoded_bytes.push(byte);
+            byte = 0;
+        }
+    }
+    Some(decoded_bytes)
+}
+
 /// Try to decode a byte from a hex char.
 ///
 /// None will be returned if the input char is hex-invalid.
@@ -566,6 +645,79 @@ const fn try_decode_hex_char(c: u8) -> Option<u8> {
     }
 }
 
+/// The escape character of a Unicode string literal without a `UESCAPE`
+/// clause
+const DEFAULT_UNICODE_ESCAPE: char = '\\';
+
+/// Decodes the body of the Unicode string literal `U&'value'` with the
+/// escape character `escape`, which introduces a code point as four hex
+/// digits, or as `+` and six hex digits. The escape character written twice
+/// stands for itself, and a UTF-16 surrogate pair written as two escapes
+/// stands for the code point it encodes.
+fn decode_unicode_escapes(value: &str, escape: char) -> Result<String> {
+    if escape.is_ascii_hexdigit()
+        || escape.is_whitespace()
+        || matches!(escape, '+' | '\'' | '"')
+    {
+        return plan_err!("Invalid Unicode escape character '{escape}'");
+    }
+
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    // A high surrogate waiting for its low surrogate
+    let mut high_surrogate: Option<u32> = None;
+    while let Some(c) = chars.next() {
+        if c != escape {
+            if high_surrogate.is_some() {
+                return plan_err!("Invalid Unicode surrogate pair in U&'{value}'");
+            }
+            decoded.push(c);
+            continue;
+        }
+        let rest = chars.as_str();
+        if rest.starts_with(escape) {
+            if high_surrogate.is_some() {
+                return plan_err!("Invalid Unicode surrogate pair in U&'{value}'");
+            }
+            decoded.push(escape);
+            chars.next();
+            continue;
+        }
+        let digits = if rest.starts_with('+') { 7 } else { 4 };
+        let code_point = rest
+            .get(..digits)
+            .map(|digits| digits.trim_start_matches('+'))
+            .filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+        let Some(code_point) = code_point else {
+            return plan_err!("Invalid Unicode escape in U&'{value}'");
+        };
+        chars = rest[digits..].chars();
+
+        let code_point = match (high_surrogate.take(), code_point) {
+            (None, 0xD800..=0xDBFF) => {
+                high_surrogate = Some(code_point);
+                continue;
+            }
+            (Some(high), 0xDC00..=0xDFFF) => {
+                0x10000 + ((high - 0xD800) << 10) + (code_point - 0xDC00)
+            }
+            (None, _) if !(0xDC00..=0xDFFF).contains(&code_point) => code_point,
+            _ => {
+                return plan_err!("Invalid Unicode surrogate pair in U&'{value}'");
+            }
+        };
+        let Some(c) = char::from_u32(code_point) else {
+            return plan_err!("Invalid Unicode code point {code_point:X} in U&'{value}'");
+        };
+        decoded.push(c);
+    }
+    if high_surrogate.is_some() {
+        return plan_err!("Invalid Unicode surrogate pair in U&'{value}'");
+    }
+    Ok(decoded)
+}
+
 /// Returns None if the value can't be converted to i256.
 /// Modified from <https://github.com/apache/arrow-rs/blob/c4dbf0d8af6ca5a19b8b2ea777da3c276807fc5e/arrow-buffer/src/bigint/mod.rs#L303>
 fn bigint_to_i256(v: &BigInt) -> Option<i256> {
@@ -666,14 +818,43 @@ fn parse_decimal(unsigned_number: &str, negative: bool) -> Result<Expr> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_decode_unicode_escapes() {
+        for (value, escape, expected) in [
+            ("data", '\\', "data"),
+            ("d\\0061t\\+000061", '\\', "data"),
+            ("\\\\", '\\', "\\"),
+            ("d!0061t!!", '!', "dat!"),
+            ("\\D83D\\DE00", '\\', "\u{1F600}"),
+            ("\\+01F600", '\\', "\u{1F600}"),
+        ] {
+            assert_eq!(decode_unicode_escapes(value, escape).unwrap(), expected);
+        }
+
+        for (value, escape, error) in [
+            ("\\006", '\\', "Invalid Unicode escape in U&'\\006'"),
+            ("\\00G1", '\\', "Invalid Unicode escape"),
+            ("\\D83D", '\\', "Invalid Unicode surrogate pair"),
+            ("\\D83Dx", '\\', "Invalid Unicode surrogate pair"),
+            ("\\DE00", '\\', "Invalid Unicode surrogate pair"),
+            ("\\+110000", '\\', "Invalid Unicode code point 110000"),
+            ("abc", 'a', "Invalid Unicode escape character 'a'"),
+        ] {
+            let err = decode_unicode_escapes(value, escape).unwrap_err();
+            assert!(err.to_string().contains(error), "{err}");
+        }
+    }
+
     #[test]
     fn test_decode_hex_literal() {
         let cases = [
             ("", Some(vec![])),
             ("FF00", Some(vec![255, 0])),
             ("a00a", Some(vec![160, 10])),
-            ("FF0", Some(vec![15, 240])),
-            ("f", Some(vec![15])),
+            ("DE AD BE EF", Some(vec![222, 173, 190, 239])),
+            ("FF0", None),
+            ("F F", None),
+            ("f", None),
             ("FF0X", None),
             ("X0", None),
             ("XX", None),
@@ -686,6 +867,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decode_bit_literal() {
+        let cases = [
+            ("", Some(vec![])),
+            ("1010", Some(vec![10])),
+            ("11111111", Some(vec![255])),
+            ("100000001", Some(vec![1, 1])),
+            ("0000000011111111", Some(vec![0, 255])),
+            ("102", None),
+            ("1 0", None),
+        ];
+
+        for (input, expect) in cases {
+            let output = try_decode_bit_literal(input);
+            assert_eq!(output, expect);
+        }
+    }
+
     #[test]
     fn test_bigint_to_i256() {
         let cases = [
@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of the SQL/XML publishing functions `XMLELEMENT` and
+//! `XMLFOREST`, whose element and attribute names are not ordinary
+//! function arguments
+
+use std::collections::HashSet;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use arrow::datatypes::DataType;
+use datafusion_common::{DFSchema, Result, ScalarValue, plan_err};
+use datafusion_expr::{Expr, lit};
+use sqlparser::ast::{Expr as SQLExpr, ExprWithAlias, Ident};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans `XMLELEMENT(NAME name [, XMLATTRIBUTES(value [AS attr], ...)]
+    /// [, content, ...])` as a call to the `xmlelement` function:
+    ///
+    /// ```text
+    /// xmlelement('name', ['attr', ...], value, ..., content, ...)
+    /// ```
+    ///
+    /// where the list of attribute names gives the number of attribute
+    /// values that follow it.
+    pub(super) fn sql_xml_element_to_expr(
+        &self,
+        name: Ident,
+        attributes: Vec<ExprWithAlias>,
+        content: Vec<SQLExpr>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let name = self.ident_normalizer.normalize(name);
+        let (names, values) =
+            self.xml_named_values("XMLATTRIBUTES", attributes, schema, planner_context)?;
+        if let Some(duplicate) = first_duplicate(&names) {
+            return plan_err!(
+                "XMLATTRIBUTES has more than one attribute named {duplicate}"
+            );
+        }
+        let mut args = vec![lit(name), names_literal(names)];
+        args.extend(values);
+        for expr in content {
+            args.push(self.sql_expr_to_logical_expr(expr, schema, planner_context)?);
+        }
+        self.xml_function_call("XMLELEMENT", "xmlelement", args)
+    }
+
+    /// Plans `XMLFOREST(value [AS name], ...)` as a call to the `xmlforest`
+    /// function:
+    ///
+    /// ```text
+    /// xmlforest(['name', ...], value, ...)
+    /// ```
+    pub(super) fn sql_xml_forest_to_expr(
+        &self,
+        elements: Vec<ExprWithAlias>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let (names, values) =
+            self.xml_named_values("XMLFOREST", elements, schema, planner_context)?;
+        let mut args = vec![names_literal(names)];
+        args.extend(values);
+        self.xml_function_call("XMLFOREST", "xmlforest", args)
+    }
+
+    /// Plans the values of `XMLATTRIBUTES` or `XMLFOREST` with their names,
+    /// which default to the name of a column reference
+    fn xml_named_values(
+        &self,
+        clause: &str,
+        values: Vec<ExprWithAlias>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<(Vec<String>, Vec<Expr>)> {
+        let mut names = Vec::with_capacity(values.len());
+        let mut exprs = Vec::with_capacity(values.len());
+        for ExprWithAlias { expr, alias } in values {
+            let name = match (alias, &expr) {
+                (Some(alias), _) => alias,
+                (None, SQLExpr::Identifier(ident)) => ident.clone(),
+                (None, SQLExpr::CompoundIdentifier(idents)) if !idents.is_empty() => {
+                    idents[idents.len() - 1].clone()
+                }
+                (None, _) => {
+                    return plan_err!(
+                        "{clause} requires a name for the value {expr}, given by AS"
+                    );
+                }
+            };
+            names.push(self.ident_normalizer.normalize(name));
+            exprs.push(self.sql_expr_to_logical_expr(expr, schema, planner_context)?);
+        }
+        Ok((names, exprs))
+    }
+
+    /// Calls the engine function `function` that implements `syntax`
+    fn xml_function_call(
+        &self,
+        syntax: &str,
+        function: &str,
+        args: Vec<Expr>,
+    ) -> Result<Expr> {
+        match self.context_provider.get_function_meta(function) {
+            Some(udf) => Ok(udf.call(args)),
+            None => plan_err!("{syntax} requires the {function} function"),
+        }
+    }
+}
+
+/// The literal list of the names `names`
+fn names_literal(names: Vec<String>) -> Expr {
+    let names = names
+        .into_iter()
+        .map(|name| ScalarValue::Utf8(Some(name)))
+        .collect::<Vec<_>>();
+    lit(ScalarValue::List(ScalarValue::new_list_nullable(
+        &names,
+        &DataType::Utf8,
+    )))
+}
+
+/// The first name of `names` that appears more than once, if any
+fn first_duplicate(names: &[String]) -> Option<&String> {
+    let mut seen = HashSet::new();
+    names.iter().find(|name| !seen.insert(*name))
+}
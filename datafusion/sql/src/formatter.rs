@@ -0,0 +1,514 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`format_sql`] and [`format_statement`] pretty-print SQL statements
+
+use std::fmt::Display;
+
+use datafusion_common::{DataFusionError, Result};
+use sqlparser::dialect::Dialect;
+use sqlparser::dialect::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+use crate::parser::DFParserBuilder;
+use crate::splitter::ByteOffsets;
+
+/// The case of the keywords printed by the formatter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordCase {
+    /// Keywords are printed in uppercase
+    Upper,
+    /// Keywords are printed in lowercase
+    Lower,
+    /// Keywords are printed as written
+    Preserve,
+}
+
+/// Options of [`format_sql`] and [`format_statement`]
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// The number of spaces each level of nesting is indented by
+    pub indent: usize,
+    /// The case of keywords. Unquoted identifiers that are keywords, such as
+    /// a column named `value`, are cased as well
+    pub keyword_case: KeywordCase,
+    /// The width beyond which the items of a clause, such as the columns of
+    /// a `SELECT`, are printed on their own lines
+    pub max_line_width: usize,
+}
+
+impl FormatOptions {
+    /// Creates a new `FormatOptions` instance with default values: an indent
+    /// of 2 spaces, uppercase keywords and a line width of 80
+    pub fn new() -> Self {
+        Self {
+            indent: 2,
+            keyword_case: KeywordCase::Upper,
+            max_line_width: 80,
+        }
+    }
+
+    /// Sets the `indent` option.
+    pub fn with_indent(mut self, value: usize) -> Self {
+        self.indent = value;
+        self
+    }
+
+    /// Sets the `keyword_case` option.
+    pub fn with_keyword_case(mut self, value: KeywordCase) -> Self {
+        self.keyword_case = value;
+        self
+    }
+
+    /// Sets the `max_line_width` option.
+    pub fn with_max_line_width(mut self, value: usize) -> Self {
+        self.max_line_width = value;
+        self
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses the SQL script `sql` with `dialect` and pretty-prints its
+/// statements, separated by `;` and a blank line. Comments are not kept.
+///
+/// # Example
+/// ```
+/// # use datafusion_sql::formatter::{FormatOptions, format_sql};
+/// # use datafusion_sql::sqlparser::dialect::GenericDialect;
+/// # use datafusion_common::Result;
+/// # fn main() -> Result<()> {
+/// let sql = "select a, count(*) from t where b > 1 group by a";
+/// let formatted = format_sql(sql, &GenericDialect {}, &FormatOptions::new())?;
+/// assert_eq!(
+///     formatted,
+///     "SELECT a, count(*)\nFROM t\nWHERE b > 1\nGROUP BY a"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn format_sql(
+    sql: &str,
+    dialect: &dyn Dialect,
+    options: &FormatOptions,
+) -> Result<String> {
+    let statements = DFParserBuilder::new(sql)
+        .with_dialect(dialect)
+        .build()?
+        .parse_statements()?;
+    let statements = statements
+        .iter()
+        .map(|statement| format_statement(statement, dialect, options))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(statements.join(";\n\n"))
+}
+
+/// Pretty-prints `statement`, such as a [`Statement`] of [`DFParser`] or the
+/// statement that the [`unparser`] produces from a plan, whose SQL text is
+/// tokenized with `dialect`.
+///
+/// Each clause of a query starts a line, subqueries are indented, and the
+/// items of a clause longer than [`FormatOptions::max_line_width`] are
+/// printed one per line.
+///
+/// [`Statement`]: crate::parser::Statement
+/// [`DFParser`]: crate::parser::DFParser
+/// [`unparser`]: https://docs.rs/datafusion-sql/latest/datafusion_sql/unparser/index.html
+pub fn format_statement(
+    statement: &impl Display,
+    dialect: &dyn Dialect,
+    options: &FormatOptions,
+) -> Result<String> {
+    let sql = statement.to_string();
+    let tokens = Tokenizer::new(dialect, &sql)
+        .tokenize_with_location()
+        .map_err(|err| DataFusionError::from(ParserError::from(err)))?;
+    let mut offsets = ByteOffsets::new(&sql);
+
+    // Group the tokens by parentheses
+    let mut groups = vec![Group::default()];
+    let mut space_before = false;
+    for token in &tokens {
+        let start = offsets.offset(token.span.start);
+        let end = offsets.offset(token.span.end);
+        let item = match &token.token {
+            Token::Whitespace(_) => {
+                space_before = true;
+                continue;
+            }
+            Token::LParen => {
+                groups.push(Group {
+                    space_before,
+                    ..Default::default()
+                });
+                space_before = false;
+                continue;
+            }
+            Token::RParen if groups.len() > 1 => {
+                let mut group = groups.pop().unwrap();
+                group.close_space_before = space_before;
+                Item::Group(group)
+            }
+            Token::Word(word) if word.quote_style.is_none() => Item::Token {
+                text: &sql[start..end],
+                keyword: Some(word.keyword).filter(|k| *k != Keyword::NoKeyword),
+                space_before,
+            },
+            _ => Item::Token {
+                text: &sql[start..end],
+                keyword: None,
+                space_before,
+            },
+        };
+        groups.last_mut().unwrap().items.push(item);
+        space_before = false;
+    }
+    // Close the parentheses left open
+    while groups.len() > 1 {
+        let group = groups.pop().unwrap();
+        groups.last_mut().unwrap().items.push(Item::Group(group));
+    }
+
+    let formatter = Formatter { options };
+    Ok(formatter.query(&groups[0].items, 0))
+}
+
+/// Pretty-prints the SQL that the [`unparser`](crate::unparser) produces
+/// from `plan`
+#[cfg(feature = "unparser")]
+pub fn format_plan(
+    plan: &datafusion_expr::LogicalPlan,
+    options: &FormatOptions,
+) -> Result<String> {
+    let statement = crate::unparser::plan_to_sql(plan)?;
+    format_statement(&statement, &sqlparser::dialect::GenericDialect {}, options)
+}
+
+/// A token, or tokens enclosed in parentheses
+enum Item<'a> {
+    Token {
+        text: &'a str,
+        /// The keyword of an unquoted word
+        keyword: Option<Keyword>,
+        space_before: bool,
+    },
+    Group(Group<'a>),
+}
+
+/// Tokens enclosed in parentheses
+#[derive(Default)]
+struct Group<'a> {
+    items: Vec<Item<'a>>,
+    /// Whether the `(` is preceded by whitespace
+    space_before: bool,
+    /// Whether the `)` is preceded by whitespace
+    close_space_before: bool,
+}
+
+impl Group<'_> {
+    /// Returns true if the group encloses a query
+    fn is_query(&self) -> bool {
+        match self.items.first() {
+            Some(Item::Group(group)) => group.is_query(),
+            first => matches!(
+                keyword(first),
+                Some(Keyword::SELECT | Keyword::WITH | Keyword::VALUES)
+            ),
+        }
+    }
+}
+
+fn keyword(item: Option<&Item>) -> Option<Keyword> {
+    match item {
+        Some(Item::Token { keyword, .. }) => *keyword,
+        _ => None,
+    }
+}
+
+struct Formatter<'a> {
+    options: &'a FormatOptions,
+}
+
+impl Formatter<'_> {
+    /// Prints the clauses of a query or statement, one per line
+    fn query(&self, items: &[Item], level: usize) -> String {
+        let mut starts = (0..items.len())
+            .filter_map(|index| Some((index, clause_head_len(items, index)?)))
+            .collect::<Vec<_>>();
+        if starts.first().is_none_or(|(index, _)| *index > 0) {
+            // The statement starts with text that is not a clause, such as
+            // `CREATE VIEW v AS`
+            starts.insert(0, (0, 0));
+        }
+        let ends = starts.iter().skip(1).map(|(index, _)| *index);
+        starts
+            .iter()
+            .zip(ends.chain([items.len()]))
+            .map(|((start, head_len), end)| {
+                let head = &items[*start..start + head_len];
+                let body = &items[start + head_len..end];
+                self.clause(head, body, level)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Prints a clause on one line, or with its items on their own lines if
+    /// the line would be too long
+    fn clause(&self, head: &[Item], body: &[Item], level: usize) -> String {
+        let indent = self.indent(level);
+        let head = self.inline(head, level);
+        let separator = if head.is_empty() || body.is_empty() {
+            ""
+        } else {
+            " "
+        };
+        let line = format!("{indent}{head}{separator}{}", self.inline(body, level));
+        let width = line.lines().next().map_or(0, |line| line.chars().count());
+        let elements = split_list(body);
+        if width <= self.options.max_line_width || head.is_empty() || elements.len() < 2 {
+            return line;
+        }
+        let mut clause = format!("{indent}{head}");
+        for element in elements {
+            clause.push('\n');
+            clause.push_str(&self.indent(level + 1));
+            clause.push_str(&self.inline(element, level + 1));
+        }
+        clause
+    }
+
+    /// Prints items on one line, except for the subqueries they contain
+    fn inline(&self, items: &[Item], level: usize) -> String {
+        let mut out = String::new();
+        for (index, item) in items.iter().enumerate() {
+            match item {
+                Item::Token {
+                    text,
+                    keyword,
+                    space_before,
+                } => {
+                    if index > 0 && *space_before {
+                        out.push(' ');
+                    }
+                    match (keyword, self.options.keyword_case) {
+                        (Some(_), KeywordCase::Upper) => {
+                            out.push_str(&text.to_uppercase())
+                        }
+                        (Some(_), KeywordCase::Lower) => {
+                            out.push_str(&text.to_lowercase())
+                        }
+                        _ => out.push_str(text),
+                    }
+                }
+                Item::Group(group) => {
+                    if index > 0 && group.space_before {
+                        out.push(' ');
+                    }
+                    out.push('(');
+                    if group.is_query() {
+                        out.push('\n');
+                        out.push_str(&self.query(&group.items, level + 1));
+                        out.push('\n');
+                        out.push_str(&self.indent(level));
+                    } else {
+                        out.push_str(&self.inline(&group.items, level));
+                        if group.close_space_before {
+                            out.push(' ');
+                        }
+                    }
+                    out.push(')');
+                }
+            }
+        }
+        out
+    }
+
+    fn indent(&self, level: usize) -> String {
+        " ".repeat(self.options.indent * level)
+    }
+}
+
+/// Returns the number of words of the clause keyword at `index`, such as 2
+/// for `GROUP BY`, or `None` if no clause starts at `index`
+fn clause_head_len(items: &[Item], index: usize) -> Option<usize> {
+    let previous = index
+        .checked_sub(1)
+        .and_then(|index| keyword(items.get(index)));
+    let next = keyword(items.get(index + 1));
+    match keyword(items.get(index))? {
+        Keyword::SELECT
+        | Keyword::WHERE
+        | Keyword::HAVING
+        | Keyword::WINDOW
+        | Keyword::QUALIFY
+        | Keyword::LIMIT
+        | Keyword::OFFSET
+        | Keyword::FETCH
+        | Keyword::VALUES
+        | Keyword::RETURNING => Some(1),
+        // Not `IS DISTINCT FROM` or `DELETE FROM`
+        Keyword::FROM
+            if !matches!(previous, Some(Keyword::DISTINCT | Keyword::DELETE)) =>
+        {
+            Some(1)
+        }
+        // Not `WITH TIES` or `WITH TIME ZONE`
+        Keyword::WITH if index == 0 || previous == Some(Keyword::AS) => Some(1),
+        Keyword::SET if keyword(items.first()) == Some(Keyword::UPDATE) => Some(1),
+        Keyword::GROUP | Keyword::ORDER if next == Some(Keyword::BY) => Some(2),
+        Keyword::UNION | Keyword::INTERSECT | Keyword::EXCEPT => match next {
+            Some(Keyword::ALL | Keyword::DISTINCT) => Some(2),
+            _ => Some(1),
+        },
+        Keyword::JOIN if !previous.is_some_and(is_join_modifier) => Some(1),
+        keyword
+            if is_join_modifier(keyword) && !previous.is_some_and(is_join_modifier) =>
+        {
+            // The head extends to the `JOIN` or `APPLY`
+            let mut len = 1;
+            loop {
+                match keyword(items.get(index + len))? {
+                    Keyword::JOIN | Keyword::APPLY => return Some(len + 1),
+                    keyword if is_join_modifier(keyword) => len += 1,
+                    _ => return None,
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_join_modifier(keyword: Keyword) -> bool {
+    matches!(
+        keyword,
+        Keyword::INNER
+            | Keyword::LEFT
+            | Keyword::RIGHT
+            | Keyword::FULL
+            | Keyword::OUTER
+            | Keyword::CROSS
+            | Keyword::NATURAL
+            | Keyword::SEMI
+            | Keyword::ANTI
+    )
+}
+
+/// Splits the body of a clause after its top-level commas or, if it has
+/// none, before its top-level `AND` and `OR` operators
+fn split_list<'a, 'b>(items: &'b [Item<'a>]) -> Vec<&'b [Item<'a>]> {
+    let is_comma = |item: &Item| matches!(item, Item::Token { text: ",", .. });
+    if items.iter().any(is_comma) {
+        return items.split_inclusive(is_comma).collect();
+    }
+
+    let mut elements = vec![];
+    let mut start = 0;
+    let mut case_depth = 0_usize;
+    let mut between = false;
+    for (index, item) in items.iter().enumerate() {
+        match keyword(Some(item)) {
+            Some(Keyword::CASE) => case_depth += 1,
+            Some(Keyword::END) => case_depth = case_depth.saturating_sub(1),
+            Some(Keyword::BETWEEN) => between = true,
+            // The `AND` of `BETWEEN x AND y`
+            Some(Keyword::AND) if between => between = false,
+            Some(Keyword::AND | Keyword::OR) if case_depth == 0 && index > start => {
+                elements.push(&items[start..index]);
+                start = index;
+            }
+            _ => {}
+        }
+    }
+    elements.push(&items[start..]);
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::dialect::GenericDialect;
+
+    fn format(sql: &str, options: &FormatOptions) -> String {
+        format_sql(sql, &GenericDialect {}, options).unwrap()
+    }
+
+    #[test]
+    fn format_query_clauses() {
+        let sql = "with c as (select a, b from t where a between 1 and 2) \
+                   select c.a, sum(u.x) as total from c left outer join u on c.b = u.b \
+                   group by c.a having sum(u.x) > 10 order by total desc limit 5";
+        assert_eq!(
+            format(sql, &FormatOptions::new()),
+            "WITH c AS (\n  \
+               SELECT a, b\n  \
+               FROM t\n  \
+               WHERE a BETWEEN 1 AND 2\n\
+             )\n\
+             SELECT c.a, sum(u.x) AS total\n\
+             FROM c\n\
+             LEFT OUTER JOIN u ON c.b = u.b\n\
+             GROUP BY c.a\n\
+             HAVING sum(u.x) > 10\n\
+             ORDER BY total DESC\n\
+             LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn format_long_clauses() {
+        let sql = "SELECT first_column, second_column FROM t \
+                   WHERE first_column = 'it''s' AND second_column IN (SELECT x FROM u)";
+        let options = FormatOptions::new()
+            .with_indent(4)
+            .with_keyword_case(KeywordCase::Lower)
+            .with_max_line_width(30);
+        assert_eq!(
+            format(sql, &options),
+            "select\n    \
+               first_column,\n    \
+               second_column\n\
+             from t\n\
+             where\n    \
+               first_column = 'it''s'\n    \
+               and second_column in (\n        \
+                 select x\n        \
+                 from u\n    \
+               )"
+        );
+    }
+
+    #[test]
+    fn format_statements() {
+        let sql = "insert into t (a, b) values (1, 'x'); \
+                   update t set a = 2 where b is distinct from 'y'";
+        let options = FormatOptions::new().with_keyword_case(KeywordCase::Preserve);
+        assert_eq!(
+            format(sql, &options),
+            "INSERT INTO t (a, b)\n\
+             VALUES (1, 'x');\n\n\
+             UPDATE t\n\
+             SET a = 2\n\
+             WHERE b IS DISTINCT FROM 'y'"
+        );
+    }
+}
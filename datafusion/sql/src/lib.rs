@@ -43,9 +43,13 @@
 //! [`LogicalPlan`]: datafusion_expr::logical_plan::LogicalPlan
 //! [`Expr`]: datafusion_expr::expr::Expr
 
+pub mod async_planner;
+mod audit;
 mod cte;
 mod expr;
+pub mod formatter;
 pub mod parser;
+pub mod plan_cache;
 pub mod planner;
 mod psm;
 mod query;
@@ -53,6 +57,7 @@ mod relation;
 pub mod resolve;
 mod select;
 mod set_expr;
+pub mod splitter;
 mod stack;
 mod statement;
 #[cfg(feature = "unparser")]
@@ -23,7 +23,7 @@
 use datafusion_common::DataFusionError;
 use datafusion_common::config::SqlParserOptions;
 use datafusion_common::{Diagnostic, Span, sql_err};
-use sqlparser::ast::{ExprWithAlias, Ident, OrderByOptions};
+use sqlparser::ast::{Assignment, Expr, ExprWithAlias, Ident, OrderByOptions};
 use sqlparser::tokenizer::TokenWithSpan;
 use sqlparser::{
     ast::{
@@ -180,6 +180,27 @@ impl fmt::Display for CopyToSource {
     }
 }
 
+/// Where `COPY FROM` reads its data from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyFromSource {
+    /// `COPY <table> FROM '<source_url>'`
+    File(String),
+    /// `COPY <table> FROM STDIN`
+    Stdin,
+    /// `COPY <table> FROM PROGRAM '<command>'`
+    Program(String),
+}
+
+impl fmt::Display for CopyFromSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyFromSource::File(url) => write!(f, "{url}"),
+            CopyFromSource::Stdin => write!(f, "STDIN"),
+            CopyFromSource::Program(command) => write!(f, "PROGRAM '{command}'"),
+        }
+    }
+}
+
 /// DataFusion extension DDL for `COPY FROM`
 ///
 /// # Syntax:
@@ -187,7 +208,7 @@ impl fmt::Display for CopyToSource {
 /// ```text
 /// COPY <table_name> [(<column_list>)]
 /// FROM
-/// <source_url>
+/// { <source_url> | STDIN | PROGRAM <command> }
 /// (key_value_list)
 /// ```
 ///
@@ -201,6 +222,8 @@ impl fmt::Display for CopyToSource {
 /// )
 ///
 /// COPY person (id, name, age) FROM 'data.parquet' STORED AS PARQUET;
+///
+/// COPY person FROM STDIN STORED AS CSV;
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CopyFromStatement {
@@ -208,8 +231,8 @@ pub struct CopyFromStatement {
     pub table_name: ObjectName,
     /// Optional list of columns to load
     pub columns: Vec<String>,
-    /// The URL to load data from
-    pub source: String,
+    /// The URL, stream or program to load data from
+    pub source: CopyFromSource,
     /// File type (Parquet, NDJSON, CSV etc.)
     pub stored_as: Option<String>,
     /// Source specific options
@@ -339,6 +362,227 @@ impl fmt::Display for ResetStatement {
     }
 }
 
+/// A change to the `OPTIONS` of a user, as in
+/// `OPTIONS (ADD key 'value', SET key 'value', DROP key)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserOptionChange {
+    /// Adds an option, the default when no action is given
+    Add { key: String, value: Value },
+    /// Changes the value of an option
+    Set { key: String, value: Value },
+    /// Removes an option
+    Drop { key: String },
+}
+
+impl fmt::Display for UserOptionChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserOptionChange::Add { key, value } => write!(f, "{key} {value}"),
+            UserOptionChange::Set { key, value } => write!(f, "SET {key} {value}"),
+            UserOptionChange::Drop { key } => write!(f, "DROP {key}"),
+        }
+    }
+}
+
+/// The options of `CREATE USER` and `ALTER USER`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserOptions {
+    /// `PASSWORD '<password>'`
+    pub password: Option<String>,
+    /// `DEFAULT ROLE <role>`
+    pub default_role: Option<Ident>,
+    /// `OPTIONS (...)`, the attributes of the user
+    pub options: Vec<UserOptionChange>,
+}
+
+impl fmt::Display for UserOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(password) = &self.password {
+            write!(
+                f,
+                " PASSWORD {}",
+                Value::SingleQuotedString(password.clone())
+            )?;
+        }
+        if let Some(default_role) = &self.default_role {
+            write!(f, " DEFAULT ROLE {default_role}")?;
+        }
+        if !self.options.is_empty() {
+            let options: Vec<_> = self.options.iter().map(ToString::to_string).collect();
+            write!(f, " OPTIONS ({})", options.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// DataFusion extension for `CREATE USER`
+///
+/// Syntax:
+///
+/// ```text
+/// CREATE USER [ IF NOT EXISTS ] <name> [ WITH ]
+///     [ PASSWORD '<password>' ]
+///     [ DEFAULT ROLE <role> ]
+///     [ OPTIONS ( <key> '<value>' [, ...] ) ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateUserStatement {
+    /// The user name
+    pub name: Ident,
+    /// Whether `IF NOT EXISTS` was given
+    pub if_not_exists: bool,
+    /// The options of the user
+    pub options: UserOptions,
+}
+
+impl fmt::Display for CreateUserStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CREATE USER ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{}{}", self.name, self.options)
+    }
+}
+
+/// DataFusion extension for `ALTER USER`
+///
+/// Syntax:
+///
+/// ```text
+/// ALTER USER <name> [ WITH ]
+///     [ PASSWORD '<password>' ]
+///     [ DEFAULT ROLE <role> ]
+///     [ OPTIONS ( [ ADD | SET | DROP ] <key> [ '<value>' ] [, ...] ) ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlterUserStatement {
+    /// The user name
+    pub name: Ident,
+    /// The options changed
+    pub options: UserOptions,
+}
+
+impl fmt::Display for AlterUserStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ALTER USER {}{}", self.name, self.options)
+    }
+}
+
+/// DataFusion extension for `DROP USER`
+///
+/// Syntax:
+///
+/// ```text
+/// DROP USER [ IF EXISTS ] <name> [, ...]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropUserStatement {
+    /// The user names
+    pub names: Vec<Ident>,
+    /// Whether `IF EXISTS` was given
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropUserStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DROP USER ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        let names: Vec<_> = self.names.iter().map(ToString::to_string).collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+/// The change made by an `UPDATE` or `DELETE` with `FOR PORTION OF`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortionOfChange {
+    /// `UPDATE`, with its `SET` assignments
+    Update(Vec<Assignment>),
+    /// `DELETE`
+    Delete,
+}
+
+/// DataFusion extension for the `UPDATE` and `DELETE` of a portion of an
+/// application-time period (SQL:2011 T180), which sqlparser does not parse
+///
+/// Syntax:
+///
+/// ```text
+/// UPDATE <table> FOR PORTION OF <period> FROM <start> TO <end> [ [ AS ] <alias> ]
+///     SET <assignment> [, ...]
+///     [ WHERE <condition> ]
+///
+/// DELETE FROM <table> FOR PORTION OF <period> FROM <start> TO <end> [ [ AS ] <alias> ]
+///     [ WHERE <condition> ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortionOfStatement {
+    /// The table to update or delete from
+    pub table_name: ObjectName,
+    /// The name of the application-time period
+    pub period: Ident,
+    /// The start of the portion, inclusive
+    pub from: Expr,
+    /// The end of the portion, exclusive
+    pub to: Expr,
+    /// The alias of the table
+    pub alias: Option<Ident>,
+    /// The update or delete
+    pub change: PortionOfChange,
+    /// The `WHERE` condition of the changed rows
+    pub selection: Option<Expr>,
+}
+
+impl PortionOfStatement {
+    /// The expressions of the statement: the bounds of the portion, the
+    /// assigned values and the condition
+    pub(crate) fn exprs(&self) -> Vec<&Expr> {
+        let mut exprs = vec![&self.from, &self.to];
+        if let PortionOfChange::Update(assignments) = &self.change {
+            exprs.extend(assignments.iter().map(|assignment| &assignment.value));
+        }
+        exprs.extend(&self.selection);
+        exprs
+    }
+}
+
+impl fmt::Display for PortionOfStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            table_name,
+            period,
+            from,
+            to,
+            alias,
+            change,
+            selection,
+        } = self;
+
+        match change {
+            PortionOfChange::Update(_) => write!(f, "UPDATE ")?,
+            PortionOfChange::Delete => write!(f, "DELETE FROM ")?,
+        }
+        write!(
+            f,
+            "{table_name} FOR PORTION OF {period} FROM {from} TO {to}"
+        )?;
+        if let Some(alias) = alias {
+            write!(f, " AS {alias}")?;
+        }
+        if let PortionOfChange::Update(assignments) = change {
+            let assignments: Vec<_> =
+                assignments.iter().map(ToString::to_string).collect();
+            write!(f, " SET {}", assignments.join(", "))?;
+        }
+        if let Some(selection) = selection {
+            write!(f, " WHERE {selection}")?;
+        }
+        Ok(())
+    }
+}
+
 /// DataFusion SQL Statement.
 ///
 /// This can either be a [`Statement`] from [`sqlparser`] from a
@@ -360,6 +604,14 @@ pub enum Statement {
     Explain(ExplainStatement),
     /// Extension: `RESET`
     Reset(ResetStatement),
+    /// Extension: `CREATE USER`
+    CreateUser(CreateUserStatement),
+    /// Extension: `ALTER USER`
+    AlterUser(AlterUserStatement),
+    /// Extension: `DROP USER`
+    DropUser(DropUserStatement),
+    /// Extension: `UPDATE` and `DELETE` with `FOR PORTION OF`
+    PortionOf(PortionOfStatement),
 }
 
 impl fmt::Display for Statement {
@@ -371,6 +623,10 @@ impl fmt::Display for Statement {
             Statement::CopyFrom(stmt) => write!(f, "{stmt}"),
             Statement::Explain(stmt) => write!(f, "{stmt}"),
             Statement::Reset(stmt) => write!(f, "{stmt}"),
+            Statement::CreateUser(stmt) => write!(f, "{stmt}"),
+            Statement::AlterUser(stmt) => write!(f, "{stmt}"),
+            Statement::DropUser(stmt) => write!(f, "{stmt}"),
+            Statement::PortionOf(stmt) => write!(f, "{stmt}"),
         }
     }
 }
@@ -399,6 +655,46 @@ pub struct DFParser<'a> {
 const DEFAULT_RECURSION_LIMIT: usize = 50;
 const DEFAULT_DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 
+/// A [`Statement`] of a SQL script, with the location of its text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementWithSpan {
+    /// The parsed statement
+    pub statement: Statement,
+    /// The location of the statement in the script, from its first token to
+    /// its last one, excluding the `;` delimiter
+    pub span: Span,
+}
+
+/// Iterator over the statements of a SQL script, created by
+/// [`DFParser::parse_sql_iter`] or [`DFParser::into_statement_iter`].
+///
+/// Statements are parsed one at a time as the iterator is advanced, so that
+/// a caller can plan and execute each statement before the next one is
+/// parsed, and only the tokens of the script are kept in memory. The
+/// iterator stops after the first error.
+pub struct StatementIter<'a> {
+    parser: DFParser<'a>,
+    expecting_statement_delimiter: bool,
+    done: bool,
+}
+
+impl Iterator for StatementIter<'_> {
+    type Item = Result<StatementWithSpan, DataFusionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self
+            .parser
+            .parse_next_statement(self.expecting_statement_delimiter)
+            .transpose();
+        self.expecting_statement_delimiter = true;
+        self.done = !matches!(result, Some(Ok(_)));
+        result
+    }
+}
+
 /// Builder for [`DFParser`]
 ///
 /// # Example: Create and Parse SQL statements
@@ -498,6 +794,20 @@ impl<'a> DFParser<'a> {
         token_with_span.token == Token::make_keyword(&keyword_str)
     }
 
+    /// Returns whether the `n`th next token is `USER`, not followed by the
+    /// `MAPPING` of the SQL/MED user mapping statements parsed by sqlparser
+    fn peek_nth_is_user(&self, n: usize) -> bool {
+        let is_user = matches!(
+            self.parser.peek_nth_token(n).token,
+            Token::Word(word) if word.keyword == Keyword::USER
+        );
+        let is_mapping = matches!(
+            self.parser.peek_nth_token(n + 1).token,
+            Token::Word(word) if word.value.eq_ignore_ascii_case("MAPPING")
+        );
+        is_user && !is_mapping
+    }
+
     #[deprecated(since = "46.0.0", note = "DFParserBuilder")]
     pub fn new(sql: &'a str) -> Result<Self, DataFusionError> {
         DFParserBuilder::new(sql).build()
@@ -529,6 +839,46 @@ impl<'a> DFParser<'a> {
         parser.parse_statements()
     }
 
+    /// Parse a sql string into an iterator of [`Statement`]s, using the
+    /// [`PostgreSqlDialect`], that parses each statement on demand.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion_sql::parser::DFParser;
+    /// # use datafusion_common::Result;
+    /// # fn test() -> Result<()> {
+    /// for statement in DFParser::parse_sql_iter("SELECT 1; SELECT 2")? {
+    ///     let statement = statement?;
+    ///     println!("{} at {:?}", statement.statement, statement.span);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_sql_iter(sql: &'a str) -> Result<StatementIter<'a>, DataFusionError> {
+        Ok(DFParserBuilder::new(sql).build()?.into_statement_iter())
+    }
+
+    /// Parse a sql string into an iterator of [`Statement`]s with the
+    /// specified dialect, that parses each statement on demand.
+    pub fn parse_sql_iter_with_dialect(
+        sql: &'a str,
+        dialect: &'a dyn Dialect,
+    ) -> Result<StatementIter<'a>, DataFusionError> {
+        Ok(DFParserBuilder::new(sql)
+            .with_dialect(dialect)
+            .build()?
+            .into_statement_iter())
+    }
+
+    /// Returns an iterator over the statements that remain to be parsed
+    pub fn into_statement_iter(self) -> StatementIter<'a> {
+        StatementIter {
+            parser: self,
+            expecting_statement_delimiter: false,
+            done: false,
+        }
+    }
+
     pub fn parse_sql_into_expr(sql: &str) -> Result<ExprWithAlias, DataFusionError> {
         DFParserBuilder::new(sql).build()?.parse_into_expr()
     }
@@ -547,26 +897,41 @@ impl<'a> DFParser<'a> {
     pub fn parse_statements(&mut self) -> Result<VecDeque<Statement>, DataFusionError> {
         let mut stmts = VecDeque::new();
         let mut expecting_statement_delimiter = false;
-        loop {
-            // ignore empty statements (between successive statement delimiters)
-            while self.parser.consume_token(&Token::SemiColon) {
-                expecting_statement_delimiter = false;
-            }
-
-            if self.parser.peek_token() == Token::EOF {
-                break;
-            }
-            if expecting_statement_delimiter {
-                return self.expected("end of statement", &self.parser.peek_token());
-            }
-
-            let statement = self.parse_statement()?;
-            stmts.push_back(statement);
+        while let Some(statement) =
+            self.parse_next_statement(expecting_statement_delimiter)?
+        {
+            stmts.push_back(statement.statement);
             expecting_statement_delimiter = true;
         }
         Ok(stmts)
     }
 
+    /// Parse the next statement of a script, or return `None` at its end
+    fn parse_next_statement(
+        &mut self,
+        mut expecting_statement_delimiter: bool,
+    ) -> Result<Option<StatementWithSpan>, DataFusionError> {
+        // ignore empty statements (between successive statement delimiters)
+        while self.parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+
+        if self.parser.peek_token() == Token::EOF {
+            return Ok(None);
+        }
+        if expecting_statement_delimiter {
+            return self.expected("end of statement", &self.parser.peek_token());
+        }
+
+        let start = self.parser.peek_token_ref().span.start;
+        let statement = self.parse_statement()?;
+        let end = self.parser.get_current_token().span.end;
+        Ok(Some(StatementWithSpan {
+            statement,
+            span: Span::new(start.into(), end.into()),
+        }))
+    }
+
     /// Report an unexpected token
     fn expected<T>(
         &self,
@@ -618,6 +983,16 @@ impl<'a> DFParser<'a> {
         } else if self.parser.peek_keyword(Keyword::RESET) {
             self.parser.next_token(); // RESET
             self.parse_reset()
+        } else if self.parser.peek_keyword(Keyword::ALTER) && self.peek_nth_is_user(1) {
+            self.parser.next_token(); // ALTER
+            self.parser.next_token(); // USER
+            self.parse_alter_user()
+        } else if self.parser.peek_keyword(Keyword::DROP) && self.peek_nth_is_user(1) {
+            self.parser.next_token(); // DROP
+            self.parser.next_token(); // USER
+            self.parse_drop_user()
+        } else if self.peek_portion_of() {
+            self.parse_portion_of()
         } else if self.parser.peek_keyword(Keyword::ABORT) {
             // ABORT is a PostgreSQL extension that is an alias for ROLLBACK
             self.parser.next_token(); // ABORT
@@ -808,15 +1183,23 @@ impl<'a> DFParser<'a> {
             }
         };
 
-        // Parse the source file
-        let source = self.parser.parse_literal_string()?;
+        // Parse the source: a file, STDIN or PROGRAM
+        let source = if self.parser.parse_keyword(Keyword::STDIN) {
+            CopyFromSource::Stdin
+        } else if self.parser.parse_keyword(Keyword::PROGRAM) {
+            CopyFromSource::Program(self.parser.parse_literal_string()?)
+        } else {
+            CopyFromSource::File(self.parser.parse_literal_string()?)
+        };
 
-        // Check for inline options: COPY t FROM 'file.csv' (FORMAT CSV)
+        // Check for inline options: COPY t FROM 'file.csv' [WITH] (FORMAT CSV)
         let mut stored_as = None;
         let mut options = None;
 
         // parse_value_options expects the LParen to NOT be consumed
-        if self.parser.peek_token() == Token::LParen {
+        if self.parser.parse_keyword(Keyword::WITH)
+            || self.parser.peek_token() == Token::LParen
+        {
             options = Some(self.parse_value_options()?);
         }
 
@@ -1048,6 +1431,9 @@ impl<'a> DFParser<'a> {
             .parse_keywords(&[Keyword::UNBOUNDED, Keyword::EXTERNAL])
         {
             self.parse_create_external_table(true, false)
+        } else if self.peek_nth_is_user(0) {
+            self.parser.next_token(); // USER
+            self.parse_create_user()
         } else {
             Ok(Statement::Statement(Box::from(self.parser.parse_create()?)))
         }
@@ -1359,6 +1745,187 @@ impl<'a> DFParser<'a> {
         }
     }
 
+    /// Parse a SQL `CREATE USER`, after `CREATE USER`
+    fn parse_create_user(&mut self) -> Result<Statement, DataFusionError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+        let options = self.parse_user_options(false)?;
+        Ok(Statement::CreateUser(CreateUserStatement {
+            name,
+            if_not_exists,
+            options,
+        }))
+    }
+
+    /// Parse a SQL `ALTER USER`, after `ALTER USER`
+    fn parse_alter_user(&mut self) -> Result<Statement, DataFusionError> {
+        let name = self.parser.parse_identifier()?;
+        let options = self.parse_user_options(true)?;
+        Ok(Statement::AlterUser(AlterUserStatement { name, options }))
+    }
+
+    /// Parse a SQL `DROP USER`, after `DROP USER`
+    fn parse_drop_user(&mut self) -> Result<Statement, DataFusionError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let names = self
+            .parser
+            .parse_comma_separated(|parser| parser.parse_identifier())?;
+        Ok(Statement::DropUser(DropUserStatement { names, if_exists }))
+    }
+
+    /// Returns whether the next tokens start an `UPDATE` or `DELETE` with a
+    /// `FOR PORTION OF` clause after the table name
+    fn peek_portion_of(&self) -> bool {
+        let mut n = if self.parser.peek_keyword(Keyword::UPDATE) {
+            1
+        } else if self.parser.peek_keyword(Keyword::DELETE)
+            && self.peek_nth_is_keyword(1, Keyword::FROM)
+        {
+            2
+        } else {
+            return false;
+        };
+        // Skip the parts of the table name
+        while matches!(self.parser.peek_nth_token(n).token, Token::Word(_)) {
+            n += 1;
+            if self.parser.peek_nth_token(n).token != Token::Period {
+                break;
+            }
+            n += 1;
+        }
+        self.peek_nth_is_keyword(n, Keyword::FOR)
+            && matches!(
+                self.parser.peek_nth_token(n + 1).token,
+                Token::Word(word) if word.value.eq_ignore_ascii_case("PORTION")
+            )
+    }
+
+    /// Parse an `UPDATE` or `DELETE` with a `FOR PORTION OF` clause
+    fn parse_portion_of(&mut self) -> Result<Statement, DataFusionError> {
+        let is_update = self.parser.parse_keyword(Keyword::UPDATE);
+        if !is_update {
+            self.parser
+                .expect_keywords(&[Keyword::DELETE, Keyword::FROM])?;
+        }
+        let table_name = self.parser.parse_object_name(true)?;
+        self.parser.expect_keyword(Keyword::FOR)?;
+        self.parser.next_token(); // PORTION
+        self.parser.expect_keyword(Keyword::OF)?;
+        let period = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let from = self.parser.parse_expr()?;
+        self.parser.expect_keyword(Keyword::TO)?;
+        let to = self.parser.parse_expr()?;
+
+        let alias = if self.parser.parse_keyword(Keyword::AS) {
+            Some(self.parser.parse_identifier()?)
+        } else if matches!(
+            self.parser.peek_token().token,
+            Token::Word(word) if !matches!(word.keyword, Keyword::SET | Keyword::WHERE)
+        ) {
+            Some(self.parser.parse_identifier()?)
+        } else {
+            None
+        };
+        let change = if is_update {
+            self.parser.expect_keyword(Keyword::SET)?;
+            let assignments = self
+                .parser
+                .parse_comma_separated(|parser| parser.parse_assignment())?;
+            PortionOfChange::Update(assignments)
+        } else {
+            PortionOfChange::Delete
+        };
+        let selection = if self.parser.parse_keyword(Keyword::WHERE) {
+            Some(self.parser.parse_expr()?)
+        } else {
+            None
+        };
+        Ok(Statement::PortionOf(PortionOfStatement {
+            table_name,
+            period,
+            from,
+            to,
+            alias,
+            change,
+            selection,
+        }))
+    }
+
+    /// Parse the options of `CREATE USER` or `ALTER USER`, given in any
+    /// order. Only `ALTER USER` may set or drop the options of its `OPTIONS`
+    fn parse_user_options(
+        &mut self,
+        alter: bool,
+    ) -> Result<UserOptions, DataFusionError> {
+        let _ = self.parser.parse_keyword(Keyword::WITH);
+        let mut password = None;
+        let mut default_role = None;
+        let mut options = None;
+        loop {
+            if self.parser.parse_keyword(Keyword::PASSWORD) {
+                ensure_not_set(&password, "PASSWORD")?;
+                password = Some(self.parser.parse_literal_string()?);
+            } else if self
+                .parser
+                .parse_keywords(&[Keyword::DEFAULT, Keyword::ROLE])
+            {
+                ensure_not_set(&default_role, "DEFAULT ROLE")?;
+                default_role = Some(self.parser.parse_identifier()?);
+            } else if self.parser.parse_keyword(Keyword::OPTIONS) {
+                ensure_not_set(&options, "OPTIONS")?;
+                options = Some(self.parse_user_option_changes(alter)?);
+            } else {
+                break;
+            }
+        }
+        Ok(UserOptions {
+            password,
+            default_role,
+            options: options.unwrap_or_default(),
+        })
+    }
+
+    /// Parse the parenthesized `OPTIONS` of a user
+    fn parse_user_option_changes(
+        &mut self,
+        alter: bool,
+    ) -> Result<Vec<UserOptionChange>, DataFusionError> {
+        let mut changes = vec![];
+        self.parser.expect_token(&Token::LParen)?;
+
+        loop {
+            let change = if alter && self.parser.parse_keyword(Keyword::SET) {
+                let key = self.parse_option_key()?;
+                let value = self.parse_option_value()?;
+                UserOptionChange::Set { key, value }
+            } else if alter && self.parser.parse_keyword(Keyword::DROP) {
+                let key = self.parse_option_key()?;
+                UserOptionChange::Drop { key }
+            } else {
+                if alter {
+                    let _ = self.parser.parse_keyword(Keyword::ADD);
+                }
+                let key = self.parse_option_key()?;
+                let value = self.parse_option_value()?;
+                UserOptionChange::Add { key, value }
+            };
+            changes.push(change);
+            let comma = self.parser.consume_token(&Token::Comma);
+            if self.parser.consume_token(&Token::RParen) {
+                break;
+            } else if !comma {
+                return self.expected(
+                    "',' or ')' after option definition",
+                    &self.parser.peek_token(),
+                );
+            }
+        }
+        Ok(changes)
+    }
+
     /// Parses (key value) style options into a map of String --> [`Value`].
     ///
     /// This method supports keywords as key names as well as multiple
@@ -2193,6 +2760,125 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn copy_from_stdin_and_program() -> Result<(), DataFusionError> {
+        let sql = "COPY foo FROM STDIN STORED AS CSV";
+        let expected = Statement::CopyFrom(CopyFromStatement {
+            table_name: ObjectName::from(vec![Ident::new("foo")]),
+            columns: vec![],
+            source: CopyFromSource::Stdin,
+            stored_as: Some("CSV".to_owned()),
+            options: vec![],
+        });
+        assert_eq!(verified_stmt(sql), expected);
+
+        let sql = "COPY foo (a, b) FROM PROGRAM 'gunzip -c foo.csv.gz' STORED AS CSV";
+        let expected = Statement::CopyFrom(CopyFromStatement {
+            table_name: ObjectName::from(vec![Ident::new("foo")]),
+            columns: vec!["a".to_string(), "b".to_string()],
+            source: CopyFromSource::Program("gunzip -c foo.csv.gz".to_string()),
+            stored_as: Some("CSV".to_owned()),
+            options: vec![],
+        });
+        assert_eq!(verified_stmt(sql), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn user_statements() -> Result<(), DataFusionError> {
+        let sql = "CREATE USER IF NOT EXISTS alice PASSWORD 'it''s secret' DEFAULT ROLE analyst OPTIONS (team 'sales')";
+        let expected = Statement::CreateUser(CreateUserStatement {
+            name: Ident::new("alice"),
+            if_not_exists: true,
+            options: UserOptions {
+                password: Some("it's secret".to_string()),
+                default_role: Some(Ident::new("analyst")),
+                options: vec![UserOptionChange::Add {
+                    key: "team".to_string(),
+                    value: Value::SingleQuotedString("sales".to_string()),
+                }],
+            },
+        });
+        assert_eq!(verified_stmt(sql), expected);
+
+        let sql = "ALTER USER alice WITH DEFAULT ROLE admin OPTIONS (ADD site 'paris', SET team 'support', DROP level)";
+        let canonical = "ALTER USER alice DEFAULT ROLE admin OPTIONS (site 'paris', SET team 'support', DROP level)";
+        let expected = Statement::AlterUser(AlterUserStatement {
+            name: Ident::new("alice"),
+            options: UserOptions {
+                password: None,
+                default_role: Some(Ident::new("admin")),
+                options: vec![
+                    UserOptionChange::Add {
+                        key: "site".to_string(),
+                        value: Value::SingleQuotedString("paris".to_string()),
+                    },
+                    UserOptionChange::Set {
+                        key: "team".to_string(),
+                        value: Value::SingleQuotedString("support".to_string()),
+                    },
+                    UserOptionChange::Drop {
+                        key: "level".to_string(),
+                    },
+                ],
+            },
+        });
+        assert_eq!(one_statement_parses_to(sql, canonical), expected);
+
+        let sql = "DROP USER IF EXISTS alice, bob";
+        let expected = Statement::DropUser(DropUserStatement {
+            names: vec![Ident::new("alice"), Ident::new("bob")],
+            if_exists: true,
+        });
+        assert_eq!(verified_stmt(sql), expected);
+
+        // The SQL/MED user mappings are parsed by sqlparser
+        let statements =
+            DFParser::parse_sql("DROP USER MAPPING FOR current_user SERVER myserver")?;
+        assert!(matches!(statements[0], Statement::Statement(_)));
+
+        expect_parse_error(
+            "CREATE USER alice OPTIONS (SET team 'sales')",
+            "Expected: ',' or ')' after option definition",
+        );
+        expect_parse_error(
+            "CREATE USER alice PASSWORD 'a' PASSWORD 'b'",
+            "PASSWORD specified more than once",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn portion_of_statements() -> Result<(), DataFusionError> {
+        let sql = "UPDATE contracts FOR PORTION OF validity FROM '2024-01-01' TO '2025-01-01' \
+                   SET rate = rate * 2 WHERE id = 1";
+        let Statement::PortionOf(statement) = verified_stmt(sql) else {
+            panic!("Expected FOR PORTION OF statement");
+        };
+        assert_eq!(statement.period, Ident::new("validity"));
+        assert_eq!(statement.alias, None);
+        assert!(
+            matches!(statement.change, PortionOfChange::Update(ref a) if a.len() == 1)
+        );
+
+        one_statement_parses_to(
+            "DELETE FROM s.contracts FOR PORTION OF validity FROM '2024-01-01' TO '2025-01-01' c",
+            "DELETE FROM s.contracts FOR PORTION OF validity FROM '2024-01-01' TO '2025-01-01' AS c",
+        );
+
+        // Without FOR PORTION OF, the statements are parsed by sqlparser
+        let statements = DFParser::parse_sql("UPDATE contracts SET rate = 1")?;
+        assert!(matches!(statements[0], Statement::Statement(_)));
+        let statements = DFParser::parse_sql("DELETE FROM contracts WHERE id = 1")?;
+        assert!(matches!(statements[0], Statement::Statement(_)));
+
+        expect_parse_error(
+            "UPDATE contracts FOR PORTION OF validity FROM '2024-01-01' SET rate = 1",
+            "Expected: TO, found: SET",
+        );
+        Ok(())
+    }
+
     // For error cases, see: `copy.slt`
 
     fn object_name(name: &str) -> CopyToSource {
@@ -2235,6 +2921,44 @@ mod tests {
         one_statement_parses_to(sql, sql)
     }
 
+    #[test]
+    fn parse_sql_iter() -> Result<(), DataFusionError> {
+        let sql = "SELECT 1;\n;\nSELECT a\n  FROM t; SELECT 1 +";
+        let mut statements = DFParser::parse_sql_iter(sql)?;
+
+        let first = statements.next().unwrap()?;
+        assert_eq!(first.statement.to_string(), "SELECT 1");
+        assert_eq!(
+            first.span,
+            datafusion_common::Span::new(
+                datafusion_common::Location { line: 1, column: 1 },
+                datafusion_common::Location { line: 1, column: 9 },
+            )
+        );
+
+        let second = statements.next().unwrap()?;
+        assert_eq!(second.statement.to_string(), "SELECT a FROM t");
+        assert_eq!(
+            second.span,
+            datafusion_common::Span::new(
+                datafusion_common::Location { line: 3, column: 1 },
+                datafusion_common::Location { line: 4, column: 9 },
+            )
+        );
+
+        // Parsing stops at the first error
+        let err = statements.next().unwrap().unwrap_err();
+        assert_contains!(err.to_string(), "Expected: an expression");
+        assert!(statements.next().is_none());
+
+        let sql = "SELECT 1 SELECT 2";
+        let mut statements = DFParser::parse_sql_iter(sql)?;
+        assert!(statements.next().unwrap().is_ok());
+        let err = statements.next().unwrap().unwrap_err();
+        assert_contains!(err.to_string(), "Expected: end of statement");
+        Ok(())
+    }
+
     #[test]
     /// Checks the recursion limit works for sql queries
     /// Recursion can happen easily with binary exprs (i.e, AND or OR)
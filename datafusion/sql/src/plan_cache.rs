@@ -0,0 +1,178 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PlanCache`] caches the logical plans of queries planned by a
+//! [`SqlToRel`]
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use datafusion_common::Result;
+use datafusion_expr::LogicalPlan;
+use datafusion_expr::planner::ContextProvider;
+use sqlparser::ast::Statement;
+
+use crate::parser::Statement as DFStatement;
+use crate::planner::SqlToRel;
+
+/// The key of a cached plan
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PlanCacheKey {
+    /// The SQL text of the statement, normalized by printing its AST
+    sql: String,
+    /// The parser options of the planner
+    options: String,
+    /// The catalog version given by the caller
+    catalog_version: u64,
+    /// The current role, whose security policies and column masks are
+    /// applied by the plan
+    role: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PlanCacheState {
+    entries: HashMap<PlanCacheKey, (LogicalPlan, u64)>,
+    /// Incremented on each access, to find the least recently used entry
+    clock: u64,
+}
+
+/// An opt-in cache of the logical plans of queries, for applications that
+/// plan the same queries repeatedly, such as dashboards.
+///
+/// Plans are keyed by the normalized SQL text of the query, so that queries
+/// only differing in whitespace or keyword case share a plan, by the
+/// [`ParserOptions`] of the planner, by the current role of the
+/// [`ContextProvider`], and by a catalog version given by the caller. The
+/// caller must change the catalog version whenever the tables, functions,
+/// options or security policies seen by the [`ContextProvider`] change
+/// outside of the statements planned through the cache. Planning a DDL
+/// statement through the cache clears it.
+///
+/// Placeholders are kept in the cached plans, so a plan is shared by all
+/// the executions of a prepared query, which bind their parameters with
+/// [`LogicalPlan::with_param_values`].
+///
+/// Only queries are cached: other statements are planned on each call.
+/// When the cache is full, the least recently used plan is evicted. The
+/// privileges of the current role of the [`ContextProvider`] are checked
+/// on each call, including the calls returning a cached plan.
+///
+/// [`ParserOptions`]: crate::planner::ParserOptions
+#[derive(Debug)]
+pub struct PlanCache {
+    capacity: usize,
+    state: Mutex<PlanCacheState>,
+}
+
+impl PlanCache {
+    /// Create a new `PlanCache` holding at most `capacity` plans
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(PlanCacheState::default()),
+        }
+    }
+
+    /// Plan `statement` with `planner`, returning the cached plan of the
+    /// same query planned with the same options, role and `catalog_version`
+    /// if there is one.
+    pub fn statement_to_plan<S: ContextProvider>(
+        &self,
+        planner: &SqlToRel<S>,
+        statement: DFStatement,
+        catalog_version: u64,
+    ) -> Result<LogicalPlan> {
+        if !is_query(&statement) {
+            let plan = planner.statement_to_plan(statement)?;
+            if matches!(plan, LogicalPlan::Ddl(_)) {
+                self.invalidate();
+            }
+            return Ok(plan);
+        }
+
+        let key = PlanCacheKey {
+            sql: statement.to_string(),
+            options: format!("{:?}", planner.options),
+            catalog_version,
+            role: planner.context_provider.get_current_role(),
+        };
+        // The privileges of the role may have been revoked since the plan
+        // was cached
+        if let Some(plan) = self.get(&key) {
+            let sql = planner.audited_sql(&key.sql);
+            return planner.audit(sql, planner.authorize(plan)?);
+        }
+        let plan = planner.statement_to_plan(statement)?;
+        self.insert(key, plan.clone());
+        Ok(plan)
+    }
+
+    /// Remove all the cached plans
+    pub fn invalidate(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// The number of cached plans
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether no plan is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, key: &PlanCacheKey) -> Option<LogicalPlan> {
+        let mut state = self.lock();
+        state.clock += 1;
+        let clock = state.clock;
+        state.entries.get_mut(key).map(|(plan, last_used)| {
+            *last_used = clock;
+            plan.clone()
+        })
+    }
+
+    fn insert(&self, key: PlanCacheKey, plan: LogicalPlan) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.lock();
+        if state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+            let lru = state
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(lru) = lru {
+                state.entries.remove(&lru);
+            }
+        }
+        state.clock += 1;
+        let clock = state.clock;
+        state.entries.insert(key, (plan, clock));
+    }
+
+    /// Plans are only replaced whole under the lock, so the state of a
+    /// poisoned lock is still consistent
+    fn lock(&self) -> MutexGuard<'_, PlanCacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn is_query(statement: &DFStatement) -> bool {
+    matches!(statement, DFStatement::Statement(s) if matches!(**s, Statement::Query(_)))
+}
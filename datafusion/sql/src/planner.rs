@@ -17,29 +17,42 @@
 
 //! [`SqlToRel`]: SQL Query Planner (produces [`LogicalPlan`] from SQL AST)
 use std::cell::Cell;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::vec;
 
+use crate::resolve::resolve_sql_object_names;
 use crate::utils::make_decimal_type;
 use arrow::datatypes::*;
 use datafusion_common::TableReference;
 use datafusion_common::config::SqlParserOptions;
 use datafusion_common::datatype::{DataTypeExt, FieldExt};
-use datafusion_common::error::add_possible_columns_to_diag;
+use datafusion_common::error::{DataFusionErrorBuilder, add_possible_columns_to_diag};
 use datafusion_common::{DFSchema, DataFusionError, Result, not_impl_err, plan_err};
 use datafusion_common::{
-    DFSchemaRef, Diagnostic, SchemaError, field_not_found, internal_err,
+    DFSchemaRef, Diagnostic, SchemaError, Span, field_not_found, internal_err,
     plan_datafusion_err,
 };
-use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
+use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder, Values};
 pub use datafusion_expr::planner::ContextProvider;
+use datafusion_expr::planner::{
+    CHARACTER_LENGTH_METADATA_KEY, CHARACTER_SET_METADATA_KEY,
+    INTERVAL_FIELDS_METADATA_KEY, INTERVAL_PRECISION_METADATA_KEY,
+    NATIONAL_CHARACTER_SET, SYSTEM_TIME_METADATA_KEY, SYSTEM_TIME_ROW_END,
+    SYSTEM_TIME_ROW_START, WordReservation,
+};
 use datafusion_expr::utils::find_column_exprs;
-use datafusion_expr::{Expr, col};
-use sqlparser::ast::{ArrayElemTypeDef, ExactNumberInfo, TimezoneInfo};
+use datafusion_expr::{Expr, TableSource, col};
+use sqlparser::ast::{ArrayElemTypeDef, CharacterLength, ExactNumberInfo, TimezoneInfo};
 use sqlparser::ast::{ColumnDef as SQLColumnDef, ColumnOption, ColumnOptionDef};
-use sqlparser::ast::{DataType as SQLDataType, Ident, ObjectName, TableAlias};
+use sqlparser::ast::{
+    DataType as SQLDataType, Ident, ObjectName, SetExpr, Spanned, Statement, TableAlias,
+};
+
+/// The maximum fractional seconds precision of an interval, whose smallest
+/// unit is the nanosecond
+pub(crate) const MAX_FRACTIONAL_SECONDS_PRECISION: u64 = 9;
 
 /// SQL parser options
 #[derive(Debug, Clone, Copy)]
@@ -48,16 +61,23 @@ pub struct ParserOptions {
     pub parse_float_as_decimal: bool,
     /// Whether to normalize identifiers.
     pub enable_ident_normalization: bool,
+    /// How identifiers are normalized when `enable_ident_normalization` is set.
+    pub ident_case_folding: IdentCaseFolding,
     /// Whether to support varchar with length.
     pub support_varchar_with_length: bool,
     /// Whether to normalize options value.
     pub enable_options_value_normalization: bool,
     /// Whether to collect spans
     pub collect_spans: bool,
+    /// Whether to keep planning after independent errors and return them all
+    pub collect_errors: bool,
     /// Whether string types (VARCHAR, CHAR, Text, and String) are mapped to `Utf8View` during SQL planning.
     pub map_string_types_to_utf8view: bool,
     /// Default null ordering for sorting expressions.
     pub default_null_ordering: NullOrdering,
+    /// How names written without parentheses that name both a function and a
+    /// column are resolved.
+    pub function_name_conflict: FunctionNameConflict,
 }
 
 impl ParserOptions {
@@ -75,13 +95,16 @@ impl ParserOptions {
         Self {
             parse_float_as_decimal: false,
             enable_ident_normalization: true,
+            ident_case_folding: IdentCaseFolding::Lower,
             support_varchar_with_length: true,
             map_string_types_to_utf8view: true,
             enable_options_value_normalization: false,
             collect_spans: false,
+            collect_errors: false,
             // By default, `nulls_max` is used to follow Postgres's behavior.
             // postgres rule: https://www.postgresql.org/docs/current/queries-order.html
             default_null_ordering: NullOrdering::NullsMax,
+            function_name_conflict: FunctionNameConflict::PreferFunction,
         }
     }
 
@@ -113,6 +136,20 @@ impl ParserOptions {
         self
     }
 
+    /// Sets the `ident_case_folding` option.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use datafusion_sql::planner::{IdentCaseFolding, ParserOptions};
+    /// let opts = ParserOptions::new().with_ident_case_folding(IdentCaseFolding::Upper);
+    /// assert_eq!(opts.ident_case_folding, IdentCaseFolding::Upper);
+    /// ```
+    pub fn with_ident_case_folding(mut self, value: IdentCaseFolding) -> Self {
+        self.ident_case_folding = value;
+        self
+    }
+
     /// Sets the `support_varchar_with_length` option.
     pub fn with_support_varchar_with_length(mut self, value: bool) -> Self {
         self.support_varchar_with_length = value;
@@ -137,11 +174,23 @@ impl ParserOptions {
         self
     }
 
+    /// Sets the `collect_errors` option.
+    pub fn with_collect_errors(mut self, value: bool) -> Self {
+        self.collect_errors = value;
+        self
+    }
+
     /// Sets the `default_null_ordering` option.
     pub fn with_default_null_ordering(mut self, value: NullOrdering) -> Self {
         self.default_null_ordering = value;
         self
     }
+
+    /// Sets the `function_name_conflict` option.
+    pub fn with_function_name_conflict(mut self, value: FunctionNameConflict) -> Self {
+        self.function_name_conflict = value;
+        self
+    }
 }
 
 impl Default for ParserOptions {
@@ -155,12 +204,15 @@ impl From<&SqlParserOptions> for ParserOptions {
         Self {
             parse_float_as_decimal: options.parse_float_as_decimal,
             enable_ident_normalization: options.enable_ident_normalization,
+            ident_case_folding: options.ident_case_folding.as_str().into(),
             support_varchar_with_length: options.support_varchar_with_length,
             map_string_types_to_utf8view: options.map_string_types_to_utf8view,
             enable_options_value_normalization: options
                 .enable_options_value_normalization,
             collect_spans: options.collect_spans,
+            collect_errors: options.collect_errors,
             default_null_ordering: options.default_null_ordering.as_str().into(),
+            function_name_conflict: options.function_name_conflict.as_str().into(),
         }
     }
 }
@@ -216,28 +268,108 @@ impl From<&str> for NullOrdering {
     }
 }
 
+/// Resolution of a name written without parentheses, such as `current_user`,
+/// that names both a function and a column in scope.
+///
+/// A [`ContextProvider::word_reservation`] of the name takes precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionNameConflict {
+    /// The name calls the function.
+    PreferFunction,
+    /// The name references the column.
+    PreferColumn,
+}
+
+impl FromStr for FunctionNameConflict {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "prefer_function" => Ok(Self::PreferFunction),
+            "prefer_column" => Ok(Self::PreferColumn),
+            _ => plan_err!(
+                "Unknown function name conflict resolution: Expected one of 'prefer_function' or 'prefer_column'. Got {s}"
+            ),
+        }
+    }
+}
+
+impl From<&str> for FunctionNameConflict {
+    fn from(s: &str) -> Self {
+        Self::from_str(s).unwrap_or(Self::PreferFunction)
+    }
+}
+
+/// How unquoted identifiers are normalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentCaseFolding {
+    /// Identifiers are converted to lowercase.
+    Lower,
+    /// Identifiers are converted to uppercase.
+    Upper,
+    /// Identifiers are kept as written.
+    Preserve,
+    /// Identifiers are kept as written, but match a column or table name that
+    /// differs only in case, unless several names do.
+    CaseInsensitive,
+}
+
+impl FromStr for IdentCaseFolding {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "lower" => Ok(Self::Lower),
+            "upper" => Ok(Self::Upper),
+            "preserve" => Ok(Self::Preserve),
+            "case_insensitive" => Ok(Self::CaseInsensitive),
+            _ => plan_err!(
+                "Unknown identifier case folding: Expected one of 'lower', 'upper', 'preserve' or 'case_insensitive'. Got {s}"
+            ),
+        }
+    }
+}
+
+impl From<&str> for IdentCaseFolding {
+    fn from(s: &str) -> Self {
+        Self::from_str(s).unwrap_or(Self::Lower)
+    }
+}
+
 /// Ident Normalizer
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct IdentNormalizer {
-    normalize: bool,
+    case_folding: IdentCaseFolding,
 }
 
 impl Default for IdentNormalizer {
     fn default() -> Self {
-        Self { normalize: true }
+        Self::new(true)
     }
 }
 
 impl IdentNormalizer {
     pub fn new(normalize: bool) -> Self {
-        Self { normalize }
+        if normalize {
+            Self::new_with_case_folding(IdentCaseFolding::Lower)
+        } else {
+            Self::new_with_case_folding(IdentCaseFolding::Preserve)
+        }
+    }
+
+    pub fn new_with_case_folding(case_folding: IdentCaseFolding) -> Self {
+        Self { case_folding }
+    }
+
+    pub fn case_folding(&self) -> IdentCaseFolding {
+        self.case_folding
     }
 
     pub fn normalize(&self, ident: Ident) -> String {
-        if self.normalize {
-            crate::utils::normalize_ident(ident)
-        } else {
-            ident.value
+        match (self.case_folding, ident.quote_style) {
+            (IdentCaseFolding::Lower, _) => crate::utils::normalize_ident(ident),
+            (IdentCaseFolding::Upper, None) => ident.value.to_ascii_uppercase(),
+            _ => ident.value,
         }
     }
 }
@@ -254,6 +386,9 @@ impl IdentNormalizer {
 /// This helps resolve scoping issues of CTEs.
 /// By using cloning, a subquery can inherit CTEs from the outer query
 /// and can also define its own private CTEs without affecting the outer query.
+/// The warnings are shared by the clones, so that the warnings raised while
+/// planning a subquery are reported with the statement, and so are the
+/// tables resolved before planning.
 #[derive(Debug, Clone)]
 pub struct PlannerContext {
     /// Data types for numbered parameters ($1, $2, etc), if supplied
@@ -281,6 +416,28 @@ pub struct PlannerContext {
     /// Counter for generating unique IDs for anonymous placeholders (?)
     /// Each ? is converted to $1, $2, etc.
     next_anonymous_placeholder: Cell<usize>,
+    /// The pattern variables of the MATCH_RECOGNIZE whose MEASURES and
+    /// DEFINE clauses are being planned, which qualify the input columns
+    pattern_variables: Vec<String>,
+    /// Non-fatal warnings raised while planning, such as lossy implicit
+    /// casts, ignored hints, deprecated syntax and unused CTEs
+    warnings: Arc<Mutex<Vec<Diagnostic>>>,
+    /// The number of queries enclosing the query being planned
+    query_depth: usize,
+    /// The number of expressions enclosing the expression being planned
+    expr_depth: usize,
+    /// The tables referenced by the statement, resolved before planning it
+    table_sources: TableSources,
+}
+
+/// The tables of a [`PlannerContext`], resolved before planning
+#[derive(Clone, Default)]
+struct TableSources(Arc<HashMap<TableReference, Arc<dyn TableSource>>>);
+
+impl std::fmt::Debug for TableSources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
 }
 
 impl Default for PlannerContext {
@@ -301,6 +458,11 @@ impl PlannerContext {
             values_defaults: None,
             psm_schema: None,
             next_anonymous_placeholder: Cell::new(1),
+            pattern_variables: Vec::new(),
+            warnings: Arc::new(Mutex::new(Vec::new())),
+            query_depth: 0,
+            expr_depth: 0,
+            table_sources: TableSources::default(),
         }
     }
 
@@ -407,6 +569,23 @@ impl PlannerContext {
         Ok(())
     }
 
+    /// Sets the pattern variables of MATCH_RECOGNIZE, returning the existing
+    /// ones
+    pub fn set_pattern_variables(
+        &mut self,
+        mut pattern_variables: Vec<String>,
+    ) -> Vec<String> {
+        std::mem::swap(&mut self.pattern_variables, &mut pattern_variables);
+        pattern_variables
+    }
+
+    /// Returns true if `name` is a pattern variable of MATCH_RECOGNIZE
+    pub fn is_pattern_variable(&self, name: &str) -> bool {
+        self.pattern_variables
+            .iter()
+            .any(|variable| variable == name)
+    }
+
     /// Return the types of parameters (`$1`, `$2`, etc) if known
     pub fn prepare_param_data_types(&self) -> &[FieldRef] {
         &self.prepare_param_data_types
@@ -425,6 +604,11 @@ impl PlannerContext {
         self.ctes.insert(cte_name, Arc::new(plan));
     }
 
+    /// Returns the names of the Common Table Expressions (CTEs) / Subqueries
+    pub fn cte_names(&self) -> impl Iterator<Item = &str> {
+        self.ctes.keys().map(String::as_str)
+    }
+
     /// Return a plan for the Common Table Expression (CTE) / Subquery for the
     /// specified name
     pub fn get_cte(&self, cte_name: &str) -> Option<&LogicalPlan> {
@@ -470,6 +654,57 @@ impl PlannerContext {
         self.next_anonymous_placeholder.set(current + 1);
         current
     }
+
+    /// Returns the number of queries enclosing the query being planned
+    pub fn query_depth(&self) -> usize {
+        self.query_depth
+    }
+
+    /// Sets the number of queries enclosing the query being planned
+    pub(crate) fn set_query_depth(&mut self, depth: usize) {
+        self.query_depth = depth;
+    }
+
+    /// Returns the number of expressions enclosing the expression being
+    /// planned
+    pub fn expr_depth(&self) -> usize {
+        self.expr_depth
+    }
+
+    /// Sets the number of expressions enclosing the expression being planned
+    pub(crate) fn set_expr_depth(&mut self, depth: usize) {
+        self.expr_depth = depth;
+    }
+
+    /// Sets the tables resolved before planning, which are used instead of
+    /// calling [`ContextProvider::get_table_source`]
+    pub fn set_table_sources(
+        &mut self,
+        table_sources: HashMap<TableReference, Arc<dyn TableSource>>,
+    ) {
+        self.table_sources = TableSources(Arc::new(table_sources));
+    }
+
+    /// Returns the table `name`, if it was resolved before planning
+    pub fn table_source(&self, name: &TableReference) -> Option<Arc<dyn TableSource>> {
+        self.table_sources.0.get(name).cloned()
+    }
+
+    /// Records a non-fatal warning about the statement being planned
+    pub fn add_warning(&self, warning: Diagnostic) {
+        self.lock_warnings().push(warning);
+    }
+
+    /// Removes and returns the warnings recorded so far
+    pub fn take_warnings(&self) -> Vec<Diagnostic> {
+        std::mem::take(&mut *self.lock_warnings())
+    }
+
+    /// Warnings are only pushed or taken under the lock, so the list of a
+    /// poisoned lock is still consistent
+    fn lock_warnings(&self) -> MutexGuard<'_, Vec<Diagnostic>> {
+        self.warnings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 /// SQL query planner and binder
@@ -495,8 +730,15 @@ pub struct SqlToRel<'a, S: ContextProvider> {
     pub(crate) context_provider: &'a S,
     pub(crate) options: ParserOptions,
     pub(crate) ident_normalizer: IdentNormalizer,
+    /// Plans the branches of large `UNION ALL` chains, when the planner was
+    /// created with `with_parallel_planning`
+    pub(crate) branch_planner: Option<BranchPlanner<'a, S>>,
 }
 
+/// Plans the branches of a `UNION ALL` chain, in order
+pub(crate) type BranchPlanner<'a, S> =
+    fn(&SqlToRel<'a, S>, Vec<SetExpr>, &PlannerContext) -> Vec<Result<LogicalPlan>>;
+
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// Create a new query planner.
     ///
@@ -511,12 +753,17 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// The query planner ignores the parser options from the context provider
     /// and uses the given parser options instead.
     pub fn new_with_options(context_provider: &'a S, options: ParserOptions) -> Self {
-        let ident_normalize = options.enable_ident_normalization;
+        let case_folding = if options.enable_ident_normalization {
+            options.ident_case_folding
+        } else {
+            IdentCaseFolding::Preserve
+        };
 
         SqlToRel {
             context_provider,
             options,
-            ident_normalizer: IdentNormalizer::new(ident_normalize),
+            ident_normalizer: IdentNormalizer::new_with_case_folding(case_folding),
+            branch_planner: None,
         }
     }
 
@@ -535,16 +782,65 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 .with_name(self.ident_normalizer.normalize(column.name))
                 .with_nullable(!not_nullable);
 
+            // The generated columns of the system time period are not
+            // identity columns
+            let system_time_bound = column
+                .options
+                .iter()
+                .find_map(|option| system_time_bound(&option.option));
             let identity_meta = extract_identity_metadata(&column.options, &column.data_type);
-            if !identity_meta.is_empty() {
+            if !identity_meta.is_empty() && system_time_bound.is_none() {
                 let mut metadata = field.metadata().clone();
                 metadata.extend(identity_meta);
                 field = field.with_metadata(metadata);
             }
 
+            if let Some(bound) = system_time_bound {
+                let mut metadata = field.metadata().clone();
+                metadata.insert(SYSTEM_TIME_METADATA_KEY.to_string(), bound.to_string());
+                field = field.with_metadata(metadata);
+            }
+
+            for option in &column.options {
+                if let ColumnOption::Collation(collation) = &option.option {
+                    let collation =
+                        self.collation_metadata(collation.clone(), field.data_type())?;
+                    let mut metadata = field.metadata().clone();
+                    metadata.extend(collation.to_hashmap());
+                    field = field.with_metadata(metadata);
+                }
+            }
+
             fields.push(field);
         }
 
+        // A system-versioned table has one column for each bound of its
+        // system time period
+        let bound_columns = |bound: &str| {
+            fields
+                .iter()
+                .filter(|field| {
+                    field
+                        .metadata()
+                        .get(SYSTEM_TIME_METADATA_KEY)
+                        .map(String::as_str)
+                        == Some(bound)
+                })
+                .count()
+        };
+        if !matches!(
+            (
+                bound_columns(SYSTEM_TIME_ROW_START),
+                bound_columns(SYSTEM_TIME_ROW_END)
+            ),
+            (0, 0) | (1, 1)
+        ) {
+            return plan_err!(
+                "A system-versioned table requires one GENERATED ALWAYS AS ROW START \
+                and one GENERATED ALWAYS AS ROW END column"
+            );
+        }
+
         Ok(Schema::new(fields))
     }
 
@@ -594,8 +890,14 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         plan: LogicalPlan,
         alias: TableAlias,
     ) -> Result<LogicalPlan> {
+        self.check_unreserved(&alias.name)?;
         let idents = alias.columns.into_iter().map(|c| c.name).collect();
-        let plan = self.apply_expr_alias(plan, idents)?;
+        let plan = match plan {
+            // The columns of VALUES are renamed in place rather than by a
+            // projection of its `column1`, `column2`, ... columns
+            LogicalPlan::Values(values) => self.apply_values_alias(values, idents)?,
+            plan => self.apply_expr_alias(plan, idents)?,
+        };
 
         LogicalPlanBuilder::from(plan)
             .alias(TableReference::bare(
@@ -610,62 +912,274 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         idents: Vec<Ident>,
     ) -> Result<LogicalPlan> {
         if idents.is_empty() {
-            Ok(plan)
-        } else if idents.len() > plan.schema().fields().len() {
-            plan_err!(
+            return Ok(plan);
+        }
+        let schema = plan.schema().clone();
+        let names = self.derived_column_names(&schema, idents)?;
+
+        // Create projection expressions without validation to prevent
+        // normalization which would re-qualify columns with their original
+        // table names. We need unqualified columns so that SubqueryAlias
+        // can properly apply the new table qualifier.
+        let exprs = schema
+            .fields()
+            .iter()
+            .zip(names)
+            .map(|(field, name)| {
+                let col_expr = col(field.name());
+                let expr = if *field.name() == name {
+                    col_expr
+                } else {
+                    col_expr.alias(name)
+                };
+                // Don't validate/normalize to keep columns unqualified
+                (expr, false)
+            })
+            .collect::<Vec<_>>();
+        LogicalPlanBuilder::from(plan)
+            .project_with_validation(exprs)?
+            .build()
+    }
+
+    /// Renames the columns of `values` to the names of the derived column
+    /// list `idents`
+    fn apply_values_alias(
+        &self,
+        values: Values,
+        idents: Vec<Ident>,
+    ) -> Result<LogicalPlan> {
+        let Values { schema, values } = values;
+        let names = self.derived_column_names(&schema, idents)?;
+        let fields = schema
+            .fields()
+            .iter()
+            .zip(names)
+            .map(|(field, name)| Arc::new(field.as_ref().clone().with_name(name)))
+            .collect::<Vec<_>>();
+        let schema =
+            DFSchema::from_unqualified_fields(fields.into(), schema.metadata().clone())?;
+        Ok(LogicalPlan::Values(Values {
+            schema: Arc::new(schema),
+            values,
+        }))
+    }
+
+    /// The names of the columns of `schema` renamed by the derived column
+    /// list `idents`, whose names must be distinct
+    fn derived_column_names(
+        &self,
+        schema: &DFSchema,
+        idents: Vec<Ident>,
+    ) -> Result<Vec<String>> {
+        let num_fields = schema.fields().len();
+        if idents.len() > num_fields {
+            return plan_err!(
                 "Source table contains {} columns but {} \
                 names given as column alias",
-                plan.schema().fields().len(),
+                num_fields,
                 idents.len()
-            )
+            );
+        }
+
+        // Collect the new column names for the renamed columns
+        let mut new_names = std::collections::HashSet::new();
+        let mut names = Vec::with_capacity(num_fields);
+        for ident in idents {
+            let name = self.ident_normalizer.normalize(ident);
+            if !new_names.insert(name.clone()) {
+                return plan_err!("Column alias {name} is given more than once");
+            }
+            names.push(name);
+        }
+
+        // SQL:2016 E051-09: Allow partial column aliasing
+        // If fewer aliases than columns, only rename the first N columns
+        for field in schema.fields().iter().skip(names.len()) {
+            // Keep original name, but check for conflicts with renamed columns
+            let original_name = field.name();
+            if new_names.contains(original_name) {
+                // Conflict detected: this column's original name matches a renamed column
+                // Rename it to avoid ambiguity by adding a unique suffix
+                let mut unique_name = format!("{}_1", original_name);
+                let mut counter = 2;
+                while new_names.contains(&unique_name) {
+                    unique_name = format!("{}_{}", original_name, counter);
+                    counter += 1;
+                }
+                new_names.insert(unique_name.clone());
+                names.push(unique_name);
+            } else {
+                new_names.insert(original_name.to_string());
+                names.push(original_name.to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    /// The span of `node` in the SQL text, if spans are collected
+    pub(crate) fn span_of(&self, node: &impl Spanned) -> Option<Span> {
+        if self.options.collect_spans {
+            Span::try_from_sqlparser_span(node.span())
         } else {
-            // SQL:2016 E051-09: Allow partial column aliasing
-            // If fewer aliases than columns, only rename the first N columns
-            let schema = plan.schema().clone();
-            let num_fields = schema.fields().len();
+            None
+        }
+    }
 
-            // Collect the new column names for the renamed columns
-            let mut new_names: std::collections::HashSet<String> = idents
-                .iter()
-                .map(|id| self.ident_normalizer.normalize(id.clone()))
-                .collect();
-
-            // Create projection expressions without validation to prevent
-            // normalization which would re-qualify columns with their original
-            // table names. We need unqualified columns so that SubqueryAlias
-            // can properly apply the new table qualifier.
-            let mut exprs: Vec<_> = Vec::with_capacity(num_fields);
-            for i in 0..num_fields {
-                let (_qualifier, field) = schema.qualified_field(i);
-                let col_expr = col(field.name());
-                let expr = if i < idents.len() {
-                    // Rename this column
-                    col_expr.alias(self.ident_normalizer.normalize(idents[i].clone()))
-                } else {
-                    // Keep original name, but check for conflicts with renamed columns
-                    let original_name = field.name();
-                    if new_names.contains(original_name) {
-                        // Conflict detected: this column's original name matches a renamed column
-                        // Rename it to avoid ambiguity by adding a unique suffix
-                        let mut unique_name = format!("{}_1", original_name);
-                        let mut counter = 2;
-                        while new_names.contains(&unique_name) {
-                            unique_name = format!("{}_{}", original_name, counter);
-                            counter += 1;
-                        }
-                        new_names.insert(unique_name.clone());
-                        col_expr.alias(unique_name)
-                    } else {
-                        new_names.insert(original_name.to_string());
-                        col_expr
-                    }
-                };
-                // Don't validate/normalize to keep columns unqualified
-                exprs.push((expr, false));
+    /// Locates `err` at `span`, the span of the SQL node whose planning
+    /// failed, unless the error already has a [`Diagnostic`] or collects
+    /// several errors, each located on its own.
+    ///
+    /// Expressions, relations and statements are wrapped with this, so that
+    /// errors raised without a location, such as schema errors, report the
+    /// innermost node that failed.
+    pub(crate) fn locate_error(
+        &self,
+        err: DataFusionError,
+        span: Option<Span>,
+    ) -> DataFusionError {
+        match span {
+            Some(span)
+                if !matches!(err, DataFusionError::Collection(_))
+                    && err.diagnostic().is_none() =>
+            {
+                let diagnostic = Diagnostic::new_error(err.message(), Some(span));
+                err.with_diagnostic(diagnostic)
+            }
+            _ => err,
+        }
+    }
+
+    /// Fails if `ident` is unquoted and reserved by the context provider, so
+    /// that it cannot be used as an identifier
+    pub(crate) fn check_unreserved(&self, ident: &Ident) -> Result<()> {
+        if ident.quote_style.is_none()
+            && self.context_provider.word_reservation(&ident.value)
+                == Some(WordReservation::Reserved)
+        {
+            let diagnostic = Diagnostic::new_error(
+                format!("'{}' is a reserved word", ident.value),
+                Span::try_from_sqlparser_span(ident.span),
+            )
+            .with_help(
+                format!("quote it to use it as an identifier: \"{}\"", ident.value),
+                None,
+            );
+            return plan_err!(
+                "'{}' is a reserved word and must be quoted to be used as an identifier",
+                ident.value;
+                diagnostic=diagnostic
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the error for a statement that exceeds `limit`, the value of
+    /// the `datafusion.sql_parser` option `option`
+    pub(crate) fn limit_exceeded_error<T>(
+        &self,
+        what: &str,
+        limit: usize,
+        option: &str,
+        span: Option<Span>,
+    ) -> Result<T> {
+        let diagnostic =
+            Diagnostic::new_error(format!("{what} exceeds the limit of {limit}"), span)
+                .with_help(
+                    format!("simplify the query or raise datafusion.sql_parser.{option}"),
+                    None,
+                );
+        plan_err!(
+            "{what} exceeds the limit of {limit} set by datafusion.sql_parser.{option}";
+            diagnostic=diagnostic
+        )
+    }
+
+    /// Returns the name among `names` that `ident` refers to.
+    ///
+    /// With [`IdentCaseFolding::CaseInsensitive`], an unquoted identifier
+    /// that is none of `names` takes the spelling of the name it matches
+    /// ignoring case. Otherwise, and when it matches no name, the identifier
+    /// is normalized as usual.
+    pub(crate) fn resolve_ident_case<'n>(
+        &self,
+        ident: Ident,
+        names: impl IntoIterator<Item = &'n str>,
+    ) -> Result<String> {
+        if self.ident_normalizer.case_folding() != IdentCaseFolding::CaseInsensitive
+            || ident.quote_style.is_some()
+        {
+            return Ok(self.ident_normalizer.normalize(ident));
+        }
+        let mut matches = BTreeSet::new();
+        for name in names {
+            if name == ident.value {
+                return Ok(ident.value);
+            }
+            if name.eq_ignore_ascii_case(&ident.value) {
+                matches.insert(name);
+            }
+        }
+        match matches.len() {
+            0 => Ok(ident.value),
+            1 => Ok(matches.into_iter().next().unwrap().to_string()),
+            _ => {
+                let names = matches
+                    .iter()
+                    .map(|name| format!("\"{name}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let diagnostic = Diagnostic::new_error(
+                    format!("'{}' is ambiguous", ident.value),
+                    Span::try_from_sqlparser_span(ident.span),
+                )
+                .with_help(format!("quote one of {names}"), None);
+                plan_err!(
+                    "Identifier '{}' matches several names that differ only in case: {names}",
+                    ident.value;
+                    diagnostic=diagnostic
+                )
+            }
+        }
+    }
+
+    /// Returns true if `ident`, the name of a function called without
+    /// parentheses, references a column instead
+    pub(crate) fn function_name_is_column(
+        &self,
+        ident: &Ident,
+        schema: &DFSchema,
+        planner_context: &PlannerContext,
+    ) -> bool {
+        match self.context_provider.word_reservation(&ident.value) {
+            Some(WordReservation::Unreserved) => true,
+            Some(WordReservation::Reserved) => false,
+            None if self.options.function_name_conflict
+                == FunctionNameConflict::PreferColumn =>
+            {
+                let name = self.ident_normalizer.normalize(ident.clone());
+                let outer_schemas = planner_context.outer_query_schema_stack().iter();
+                std::iter::once(schema)
+                    .chain(outer_schemas.map(|schema| schema.as_ref()))
+                    .any(|schema| schema.has_column_with_unqualified_name(&name))
             }
-            LogicalPlanBuilder::from(plan)
-                .project_with_validation(exprs)?
-                .build()
+            None => false,
+        }
+    }
+
+    /// Returns `result`, unless errors are collected and `result` is an
+    /// error, in which case the error is added to `errors` and
+    /// `T::default()` is returned so that planning can continue.
+    pub(crate) fn collect_error<T: Default>(
+        &self,
+        result: Result<T>,
+        errors: &mut DataFusionErrorBuilder,
+    ) -> Result<T> {
+        match result {
+            Err(err) if self.options.collect_errors => {
+                errors.add_error(err);
+                Ok(T::default())
+            }
+            result => result,
         }
     }
 
@@ -725,6 +1239,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                             field,
                             valid_fields,
                         );
+                        diagnostic.add_did_you_mean(
+                            field.name(),
+                            valid_fields.iter().map(|column| column.name()),
+                        );
                         err.with_diagnostic(diagnostic)
                     }
                     _ => err,
@@ -775,12 +1293,77 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             SQLDataType::Array(ArrayElemTypeDef::None) => {
                 not_impl_err!("Arrays with unspecified type is not supported")
             }
+            SQLDataType::Interval { fields, precision } => {
+                // The interval qualifier is kept in the metadata of the field,
+                // as all intervals are planned as MonthDayNano
+                let mut metadata = HashMap::new();
+                if let Some(fields) = fields {
+                    metadata.insert(
+                        INTERVAL_FIELDS_METADATA_KEY.to_string(),
+                        fields.to_string(),
+                    );
+                }
+                if let Some(precision) = precision {
+                    if *precision > MAX_FRACTIONAL_SECONDS_PRECISION {
+                        return plan_err!(
+                            "Interval fractional seconds precision must be at most \
+                            {MAX_FRACTIONAL_SECONDS_PRECISION}, got {precision}"
+                        );
+                    }
+                    metadata.insert(
+                        INTERVAL_PRECISION_METADATA_KEY.to_string(),
+                        precision.to_string(),
+                    );
+                }
+                let field = self
+                    .convert_simple_data_type(sql_type)?
+                    .into_nullable_field();
+                Ok(Arc::new(field.with_metadata(metadata)))
+            }
+            SQLDataType::Nvarchar(length) => {
+                let length = match length {
+                    Some(CharacterLength::IntegerLength { length, .. }) => Some(*length),
+                    Some(CharacterLength::Max) | None => None,
+                };
+                Ok(self.national_character_field(length))
+            }
+            SQLDataType::Custom(name, modifiers) if is_national_character_type(name) => {
+                let length = match modifiers.as_slice() {
+                    [] => None,
+                    [length] => Some(length.parse::<u64>().map_err(|_| {
+                        plan_datafusion_err!("Invalid length {length} of type {sql_type}")
+                    })?),
+                    _ => return plan_err!("Invalid type {sql_type}"),
+                };
+                Ok(self.national_character_field(length))
+            }
             other => Ok(self
                 .convert_simple_data_type(other)?
                 .into_nullable_field_ref()),
         }
     }
 
+    /// A character string field of the national character set, with its
+    /// declared maximum length if any in its metadata
+    fn national_character_field(&self, length: Option<u64>) -> FieldRef {
+        let data_type = if self.options.map_string_types_to_utf8view {
+            DataType::Utf8View
+        } else {
+            DataType::Utf8
+        };
+        let mut metadata = HashMap::from([(
+            CHARACTER_SET_METADATA_KEY.to_string(),
+            NATIONAL_CHARACTER_SET.to_string(),
+        )]);
+        if let Some(length) = length {
+            metadata.insert(
+                CHARACTER_LENGTH_METADATA_KEY.to_string(),
+                length.to_string(),
+            );
+        }
+        Arc::new(data_type.into_nullable_field().with_metadata(metadata))
+    }
+
     fn convert_simple_data_type(&self, sql_type: &SQLDataType) -> Result<DataType> {
         let custom_type_name = |name: &ObjectName| -> Option<String> {
             if name.0.len() != 1 {
@@ -861,6 +1444,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             }
             SQLDataType::Char(_)
             | SQLDataType::Character(_)
+            | SQLDataType::Nvarchar(_)
             | SQLDataType::Text
             | SQLDataType::String(_) => {
                 if self.options.map_string_types_to_utf8view {
@@ -1025,8 +1609,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     }
                 }
             }
-            SQLDataType::Nvarchar(_)
-            | SQLDataType::Binary(_)
+            SQLDataType::Binary(_)
             | SQLDataType::Varbinary(_)
             | SQLDataType::Blob(_)
             | SQLDataType::Datetime(_)
@@ -1091,11 +1674,77 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         &self,
         object_name: ObjectName,
     ) -> Result<TableReference> {
-        object_name_to_table_reference(
-            object_name,
-            self.options.enable_ident_normalization,
+        idents_to_table_reference(
+            object_name_to_idents(object_name)?,
+            self.ident_normalizer,
         )
     }
+
+    pub(crate) fn object_name_to_qualifier(
+        &self,
+        sql_table_name: &ObjectName,
+    ) -> Result<String> {
+        object_name_to_qualifier_with_normalizer(sql_table_name, self.ident_normalizer)
+    }
+
+    /// Resolves the tables and views referenced in `statement` with a single
+    /// call of [`ContextProvider::get_table_sources`], and caches the ones
+    /// that exist in `planner_context`
+    pub(crate) fn resolve_table_sources(
+        &self,
+        statement: &Statement,
+        planner_context: &mut PlannerContext,
+    ) {
+        // Names that are not valid table references fail when planned
+        let names = resolve_sql_object_names(statement)
+            .into_iter()
+            .filter_map(|name| self.object_name_to_table_reference(name).ok())
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return;
+        }
+        let sources = self.context_provider.get_table_sources(&names);
+        let table_sources = names
+            .into_iter()
+            .zip(sources)
+            .filter_map(|(name, source)| Some((name, source.ok()?)))
+            .collect();
+        planner_context.set_table_sources(table_sources);
+    }
+
+    /// Returns the table `name`, as resolved before planning or else by the
+    /// [`ContextProvider`]
+    pub(crate) fn get_table_source(
+        &self,
+        name: TableReference,
+        planner_context: &PlannerContext,
+    ) -> Result<Arc<dyn TableSource>> {
+        match planner_context.table_source(&name) {
+            Some(source) => Ok(source),
+            None => self.context_provider.get_table_source(name),
+        }
+    }
+}
+
+/// Returns the names of the columns of `schema` and of the outer query
+/// schemas, and of the relations that qualify them
+pub(crate) fn names_in_scope<'a>(
+    schema: &'a DFSchema,
+    planner_context: &'a PlannerContext,
+) -> Vec<&'a str> {
+    let outer_schemas = planner_context.outer_query_schema_stack().iter();
+    let mut names = vec![];
+    for schema in std::iter::once(schema).chain(outer_schemas.map(|s| s.as_ref())) {
+        for (qualifier, field) in schema.iter() {
+            names.push(field.name().as_str());
+            if let Some(qualifier) = qualifier {
+                names.push(qualifier.table());
+                names.extend(qualifier.schema());
+                names.extend(qualifier.catalog());
+            }
+        }
+    }
+    names
 }
 
 /// Create a [`TableReference`] after normalizing the specified ObjectName
@@ -1112,9 +1761,16 @@ pub fn object_name_to_table_reference(
     object_name: ObjectName,
     enable_normalization: bool,
 ) -> Result<TableReference> {
+    idents_to_table_reference(
+        object_name_to_idents(object_name)?,
+        IdentNormalizer::new(enable_normalization),
+    )
+}
+
+fn object_name_to_idents(object_name: ObjectName) -> Result<Vec<Ident>> {
     // Use destructure to make it clear no fields on ObjectName are ignored
     let ObjectName(object_name_parts) = object_name;
-    let idents = object_name_parts
+    object_name_parts
         .into_iter()
         .map(|object_name_part| {
             object_name_part.as_ident().cloned().ok_or_else(|| {
@@ -1124,8 +1780,7 @@ pub fn object_name_to_table_reference(
                 )
             })
         })
-        .collect::<Result<Vec<_>>>()?;
-    idents_to_table_reference(idents, enable_normalization)
+        .collect()
 }
 
 struct IdentTaker {
@@ -1136,11 +1791,8 @@ struct IdentTaker {
 /// Take the next identifier from the back of idents, panic'ing if
 /// there are none left
 impl IdentTaker {
-    fn new(idents: Vec<Ident>, enable_normalization: bool) -> Self {
-        Self {
-            normalizer: IdentNormalizer::new(enable_normalization),
-            idents,
-        }
+    fn new(idents: Vec<Ident>, normalizer: IdentNormalizer) -> Self {
+        Self { normalizer, idents }
     }
 
     fn take(&mut self) -> String {
@@ -1173,9 +1825,9 @@ impl std::fmt::Display for IdentTaker {
 /// Create a [`TableReference`] after normalizing the specified identifier
 pub(crate) fn idents_to_table_reference(
     idents: Vec<Ident>,
-    enable_normalization: bool,
+    normalizer: IdentNormalizer,
 ) -> Result<TableReference> {
-    let mut taker = IdentTaker::new(idents, enable_normalization);
+    let mut taker = IdentTaker::new(idents, normalizer);
 
     match taker.len() {
         1 => {
@@ -1206,9 +1858,18 @@ pub(crate) fn idents_to_table_reference(
 pub fn object_name_to_qualifier(
     sql_table_name: &ObjectName,
     enable_normalization: bool,
+) -> Result<String> {
+    object_name_to_qualifier_with_normalizer(
+        sql_table_name,
+        IdentNormalizer::new(enable_normalization),
+    )
+}
+
+fn object_name_to_qualifier_with_normalizer(
+    sql_table_name: &ObjectName,
+    normalizer: IdentNormalizer,
 ) -> Result<String> {
     let columns = vec!["table_name", "table_schema", "table_catalog"].into_iter();
-    let normalizer = IdentNormalizer::new(enable_normalization);
     sql_table_name
         .0
         .iter()
@@ -1235,6 +1896,30 @@ pub fn object_name_to_qualifier(
         .map(|parts| parts.join(" AND "))
 }
 
+/// The bound of the system time period that the column option `option`
+/// generates, `GENERATED ALWAYS AS ROW START` or `GENERATED ALWAYS AS ROW END`
+fn system_time_bound(option: &ColumnOption) -> Option<&'static str> {
+    let option = option.to_string().to_ascii_uppercase();
+    match option.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["GENERATED", "ALWAYS", "AS", "ROW", "START"] => Some(SYSTEM_TIME_ROW_START),
+        ["GENERATED", "ALWAYS", "AS", "ROW", "END"] => Some(SYSTEM_TIME_ROW_END),
+        _ => None,
+    }
+}
+
+/// Returns true if `name` is a custom type of the national character set,
+/// `NCHAR`, `NVARCHAR` or `NCLOB`
+fn is_national_character_type(name: &ObjectName) -> bool {
+    matches!(
+        name.0.as_slice(),
+        [part] if part.as_ident().is_some_and(|ident| {
+            ["NCHAR", "NVARCHAR", "NCLOB"]
+                .iter()
+                .any(|national| ident.value.eq_ignore_ascii_case(national))
+        })
+    )
+}
+
 fn extract_identity_metadata(
     options: &[ColumnOptionDef],
     data_type: &SQLDataType,
@@ -23,19 +23,20 @@ use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 use crate::stack::StackGuard;
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
-    Constraints, DFSchema, Result, TableReference, not_impl_err, plan_err,
+    Constraints, DFSchema, Diagnostic, Result, Span, TableReference, not_impl_err,
+    plan_err,
 };
 use datafusion_expr::expr::Sort;
 
 use datafusion_expr::{
-    CreateMemoryTable, DdlStatement, Distinct, Expr, LogicalPlan, LogicalPlanBuilder,
-    SubqueryAlias, TableScanRowLock, TableScanRowLockMode, TableScanRowLockWaitPolicy,
+    CreateMemoryTable, DdlStatement, Distinct, LogicalPlan, LogicalPlanBuilder,
+    SubqueryAlias, TableProviderRowLockSupport, TableScan, TableScanRowLock,
+    TableScanRowLockMode, TableScanRowLockWaitPolicy,
 };
 use sqlparser::ast::{
-    Expr as SQLExpr, Fetch, Ident, LimitClause, LockClause, LockType, NonBlock, OrderBy,
-    OrderByExpr, OrderByKind, Query, SelectInto, SetExpr,
+    Expr as SQLExpr, Fetch, LimitClause, LockClause, LockType, NonBlock, OrderBy,
+    OrderByExpr, OrderByKind, Query, SelectInto, SetExpr, Spanned, Value,
 };
-use sqlparser::tokenizer::Span;
 
 /// Internal representation of limit/offset with WITH TIES support
 #[derive(Debug, Clone)]
@@ -59,9 +60,25 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         query: Query,
         outer_planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
+        let depth = outer_planner_context.query_depth();
+        let limit = self
+            .context_provider
+            .options()
+            .sql_parser
+            .subquery_depth_limit;
+        if depth > limit {
+            return self.limit_exceeded_error(
+                "Subquery nesting depth",
+                limit,
+                "subquery_depth_limit",
+                Span::try_from_sqlparser_span(query.span()),
+            );
+        }
+
         // Each query has its own planner context, including CTEs that are visible within that query.
         // It also inherits the CTEs from the outer query by cloning the outer planner context.
         let mut query_plan_context = outer_planner_context.clone();
+        query_plan_context.set_query_depth(depth + 1);
         let planner_context = &mut query_plan_context;
 
         let Query {
@@ -78,12 +95,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // Combine FETCH clause with LIMIT/OFFSET handling
         let limit_info = self.combine_limit_and_fetch(limit_clause, fetch)?;
 
-        if let Some(with) = with {
-            self.plan_with_clause(with, planner_context)?;
-        }
+        let cte_names = match with {
+            Some(with) => self.plan_with_clause(with, planner_context)?,
+            None => vec![],
+        };
 
         let set_expr = *body;
-        match set_expr {
+        let plan = match set_expr {
             SetExpr::Select(mut select) => {
                 let select_into = select.into.take();
                 let plan =
@@ -91,9 +109,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let plan = self.limit(plan, limit_info.clone(), planner_context)?;
                 let plan = self.apply_query_locks(plan, locks)?;
                 // Process the `SELECT INTO` after `LIMIT`.
-                self.select_into(plan, select_into)
+                self.select_into(plan, select_into, planner_context)
             }
             other => {
+                self.check_set_operation_limit(&other)?;
                 // The functions called from `set_expr_to_plan()` need more than 128KB
                 // stack in debug builds as investigated in:
                 // https://github.com/apache/datafusion/pull/13310#discussion_r1836813902
@@ -102,7 +121,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     let _guard = StackGuard::new(256 * 1024);
                     self.set_expr_to_plan(other, planner_context)
                 }?;
-                let oby_exprs = to_order_by_exprs(order_by)?;
+                let oby_exprs =
+                    to_order_by_exprs(order_by, plan.schema().fields().len())?;
                 let order_by_rex = self.order_by_to_sort_expr(
                     oby_exprs,
                     plan.schema(),
@@ -114,7 +134,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let plan = self.limit(plan, limit_info, planner_context)?;
                 self.apply_query_locks(plan, locks)
             }
+        }?;
+
+        if !cte_names.is_empty() {
+            self.warn_unused_ctes(&plan, cte_names, planner_context)?;
         }
+        Ok(plan)
     }
 
     fn apply_query_locks(
@@ -347,20 +372,27 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         &self,
         plan: LogicalPlan,
         select_into: Option<SelectInto>,
+        planner_context: &PlannerContext,
     ) -> Result<LogicalPlan> {
         match select_into {
-            Some(into) => Ok(LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(
-                CreateMemoryTable {
-                    name: self.object_name_to_table_reference(into.name)?,
-                    constraints: Constraints::default(),
-                    input: Arc::new(plan),
-                    if_not_exists: false,
-                    or_replace: false,
-                    temporary: false,
-                    column_defaults: vec![],
-                    storage_parameters: BTreeMap::new(),
-                },
-            ))),
+            Some(into) => {
+                planner_context.add_warning(Diagnostic::new_warning(
+                    "SELECT INTO to create a table is deprecated, use CREATE TABLE AS instead",
+                    self.span_of(&into.name),
+                ));
+                Ok(LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(
+                    CreateMemoryTable {
+                        name: self.object_name_to_table_reference(into.name)?,
+                        constraints: Constraints::default(),
+                        input: Arc::new(plan),
+                        if_not_exists: false,
+                        or_replace: false,
+                        temporary: false,
+                        column_defaults: vec![],
+                        storage_parameters: BTreeMap::new(),
+                    },
+                )))
+            }
             _ => Ok(plan),
         }
     }
@@ -400,12 +432,14 @@ fn apply_locks_to_plan(
         }
         LogicalPlan::TableScan(mut scan) => {
             let mut row_lock = scan.row_lock;
+            let mut matched = false;
             for (idx, lock) in locks.iter().enumerate() {
                 if lock_applies_to_scan(
                     lock.target.as_ref(),
                     visible_relation.as_ref(),
                     &scan.table_name,
                 ) {
+                    matched = true;
                     matched_targets[idx] = true;
                     row_lock = Some(match row_lock {
                         None => lock.row_lock,
@@ -413,6 +447,9 @@ fn apply_locks_to_plan(
                     });
                 }
             }
+            if matched && let Some(lock) = &row_lock {
+                check_row_lock_support(&scan, lock)?;
+            }
             scan.row_lock = row_lock;
             Ok(LogicalPlan::TableScan(scan))
         }
@@ -444,6 +481,20 @@ fn lock_applies_to_scan(
     }
 }
 
+/// Ask the scan's source whether it can honor `lock`, rejecting the query
+/// when it cannot rather than silently scanning without locks.
+fn check_row_lock_support(scan: &TableScan, lock: &TableScanRowLock) -> Result<()> {
+    match scan.source.supports_row_lock(lock)? {
+        TableProviderRowLockSupport::Unsupported => plan_err!(
+            "{lock} is not supported by table {}",
+            scan.table_name
+        ),
+        TableProviderRowLockSupport::Ignored | TableProviderRowLockSupport::Exact => {
+            Ok(())
+        }
+    }
+}
+
 fn combine_row_locks(
     left: TableScanRowLock,
     right: TableScanRowLock,
@@ -471,15 +522,14 @@ fn combine_row_locks(
     })
 }
 
-/// Returns the order by expressions from the query.
-fn to_order_by_exprs(order_by: Option<OrderBy>) -> Result<Vec<OrderByExpr>> {
-    to_order_by_exprs_with_select(order_by, None)
-}
-
-/// Returns the order by expressions from the query with the select expressions.
-pub(crate) fn to_order_by_exprs_with_select(
+/// Returns the order by expressions from the query, whose select list has
+/// `select_columns` columns.
+///
+/// `ORDER BY ALL` orders by every column of the select list from left to
+/// right, and is expanded to the positions of the columns.
+pub(crate) fn to_order_by_exprs(
     order_by: Option<OrderBy>,
-    select_exprs: Option<&Vec<Expr>>,
+    select_columns: usize,
 ) -> Result<Vec<OrderByExpr>> {
     let Some(OrderBy { kind, interpolate }) = order_by else {
         // If no order by, return an empty array.
@@ -489,30 +539,13 @@ pub(crate) fn to_order_by_exprs_with_select(
         return not_impl_err!("ORDER BY INTERPOLATE is not supported");
     }
     match kind {
-        OrderByKind::All(order_by_options) => {
-            let Some(exprs) = select_exprs else {
-                return Ok(vec![]);
-            };
-            let order_by_exprs = exprs
-                .iter()
-                .map(|select_expr| match select_expr {
-                    Expr::Column(column) => Ok(OrderByExpr {
-                        expr: SQLExpr::Identifier(Ident {
-                            value: column.name.clone(),
-                            quote_style: None,
-                            span: Span::empty(),
-                        }),
-                        options: order_by_options,
-                        with_fill: None,
-                    }),
-                    // TODO: Support other types of expressions
-                    _ => not_impl_err!(
-                        "ORDER BY ALL is not supported for non-column expressions"
-                    ),
-                })
-                .collect::<Result<Vec<_>>>()?;
-            Ok(order_by_exprs)
-        }
+        OrderByKind::All(order_by_options) => Ok((1..=select_columns)
+            .map(|position| OrderByExpr {
+                expr: SQLExpr::value(Value::Number(position.to_string(), false)),
+                options: order_by_options,
+                with_fill: None,
+            })
+            .collect()),
         OrderByKind::Expressions(order_by_exprs) => Ok(order_by_exprs),
     }
 }
@@ -0,0 +1,1718 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::{ControlFlow, RangeInclusive};
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, SqlToRel};
+use arrow::datatypes::DataType;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{
+    Column, Constraint, Result, ScalarValue, TableReference, internal_err, not_impl_err,
+    plan_err,
+};
+use datafusion_expr::expr::{
+    AggregateFunction, AggregateFunctionParams, WildcardOptions,
+};
+use datafusion_expr::utils::{conjunction, disjunction};
+use datafusion_expr::{
+    CreatePropertyGraph, EdgeDirection, EdgePattern, Expr, ExprSchemable, GraphColumn,
+    GraphEdgeEndpoint, GraphEdgeTableDefinition, GraphPatternElement, GraphPatternExpr,
+    GraphTable, GraphVertexTableDefinition, JoinType, LabelExpression, LogicalPlan,
+    LogicalPlanBuilder, NodePattern, Operator, PathFinding, PathMode,
+    RepetitionQuantifier, RowLimiting, Union, binary_expr, cast, lit, when,
+};
+use sqlparser::ast::{
+    Expr as SQLExpr, FunctionArg, FunctionArgExpr, FunctionArguments, Ident,
+    visit_expressions_mut,
+};
+
+/// Column holding the number of edges of a matched path while path search
+/// selects among the paths
+const PATH_LENGTH: &str = "__graph_path_length";
+
+/// Column holding the total `COST` of the edges of a matched path while
+/// cheapest path search selects among the paths
+const PATH_COST: &str = "__graph_path_cost";
+
+/// Columns of an element variable that [`SqlToRel::plan_graph_match`]
+/// resolves to the identifier of the element bound to it, for calls to
+/// `ELEMENT_ID(v)`, `VERTEX_ID(v)` and `EDGE_ID(v)`
+const ELEMENT_ID: &str = "__graph_element_id";
+const VERTEX_ID: &str = "__graph_vertex_id";
+const EDGE_ID: &str = "__graph_edge_id";
+
+/// The functions of graph variables in `GRAPH_TABLE` queries, with the
+/// column of the variable each call is rewritten to
+const GRAPH_FUNCTIONS: [(&str, &str); 4] = [
+    ("path_length", PATH_LENGTH),
+    ("element_id", ELEMENT_ID),
+    ("vertex_id", VERTEX_ID),
+    ("edge_id", EDGE_ID),
+];
+
+/// Prefix of the columns holding the keys of the endpoints of a matched path
+/// while path search selects among the paths
+const PATH_ENDPOINT: &str = "__graph_endpoint_";
+
+/// Limit on the number of ways of unrolling the quantified edges of a
+/// `MATCH` clause and assigning element tables to its patterns, each of
+/// which is planned as a branch of a union
+const MAX_MATCH_BRANCHES: usize = 1024;
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plan a `GRAPH_TABLE` query over `graph` as joins of the graph's
+    /// element tables. For example
+    ///
+    /// ```text
+    /// GRAPH_TABLE (g MATCH (a:person)-[e:knows]->(b:person) COLUMNS (a.name, b.name))
+    /// ```
+    ///
+    /// is planned as
+    ///
+    /// ```text
+    /// SELECT a.name, b.name
+    /// FROM person AS a
+    /// JOIN knows AS e ON e.src = a.id
+    /// JOIN person AS b ON e.dst = b.id
+    /// ```
+    ///
+    /// Each vertex and edge pattern scans an element table its label
+    /// expression matches, under the pattern's variable name. An element
+    /// table is labeled by its `LABEL`, or else by its name; label
+    /// expressions combine labels with `&`, `|` and `!`, and `%` matches
+    /// any label. A repeated variable refers to the same element, and paths
+    /// that share no variable form a cross product. Element property
+    /// constraints and `WHERE` clauses filter the joined rows before
+    /// `COLUMNS` projects them. In `COLUMNS`, `v.*` stands for every
+    /// property of the element bound to `v`, and `ELEMENT_ID(v)`,
+    /// `VERTEX_ID(v)` and `EDGE_ID(v)` identify the element by its table
+    /// and key.
+    ///
+    /// When patterns match several element tables, the match is planned
+    /// for each assignment of tables to patterns and the results are
+    /// unioned. Properties that the table assigned to a pattern does not
+    /// expose read as null.
+    ///
+    /// A quantified edge pattern such as `-[e]->{1,3}` is unrolled: the
+    /// match is planned for each number of repetitions the quantifier
+    /// allows and the results are unioned. Unbounded quantifiers (`*`, `+`,
+    /// `{n,}`) repeat at most `sql_parser.max_graph_path_length` times.
+    /// Outside its own pattern, the variable of a quantified edge may only
+    /// be aggregated, as in `SUM(e.weight)`, and `PATH_LENGTH(p)` gives the
+    /// number of edges of the path bound to path variable `p`.
+    ///
+    /// Path modes restrict repetition within a path: `TRAIL` rejects paths
+    /// that repeat an edge, `ACYCLIC` paths that repeat a vertex, and
+    /// `SIMPLE` paths that repeat a vertex other than by returning to their
+    /// start. Path search prefixes keep, for each pair of endpoints, one of
+    /// the matched paths (`ANY`), one of the shortest (`ANY SHORTEST`) or
+    /// all of the shortest (`ALL SHORTEST`). `ANY CHEAPEST` and
+    /// `ALL CHEAPEST` do the same for the paths of least total cost, where
+    /// each edge costs its pattern's `COST` expression, or 1 without one.
+    pub(super) fn plan_graph_match(
+        &self,
+        graph: &CreatePropertyGraph,
+        graph_table: GraphTable,
+    ) -> Result<LogicalPlan> {
+        let GraphTable {
+            path_finding,
+            path_mode,
+            row_limiting,
+            patterns,
+            where_clause,
+            columns,
+            ..
+        } = graph_table;
+        let selector = match path_finding {
+            None | Some(PathFinding::All) => None,
+            Some(
+                selector @ (PathFinding::Any
+                | PathFinding::AnyShortest
+                | PathFinding::AllShortest
+                | PathFinding::AnyCheapest
+                | PathFinding::AllCheapest),
+            ) => Some(selector),
+            Some(path_finding) => {
+                return not_impl_err!(
+                    "GRAPH_TABLE path search {path_finding:?} is not supported"
+                );
+            }
+        };
+        if selector.is_some() && patterns.len() != 1 {
+            return not_impl_err!(
+                "GRAPH_TABLE path search over several path patterns is not supported"
+            );
+        }
+        let path_mode = path_mode.unwrap_or(PathMode::Walk);
+        if let Some(row_limiting) = row_limiting
+            && row_limiting != RowLimiting::OneRowPerMatch
+        {
+            return not_impl_err!("GRAPH_TABLE {row_limiting:?} is not supported");
+        }
+        if columns.is_empty() {
+            return plan_err!("GRAPH_TABLE requires a COLUMNS clause");
+        }
+
+        let paths = patterns
+            .iter()
+            .map(|pattern| flatten_path(&pattern.expr))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The variable of a quantified edge binds a list of edges, which
+        // outside the edge's own pattern can only be aggregated
+        let group_variables = paths
+            .iter()
+            .flat_map(|path| &path.edges)
+            .filter(|edge| edge.quantifier.is_some())
+            .filter_map(|edge| edge.variable.as_deref())
+            .collect::<HashSet<_>>();
+
+        let edge_keys = self.graph_edge_keys(graph)?;
+        let max_length = self
+            .context_provider
+            .options()
+            .sql_parser
+            .max_graph_path_length;
+        let max_length = u32::try_from(max_length).unwrap_or(u32::MAX);
+        let mut matches = vec![];
+        for counts in repetition_counts(&paths, max_length)? {
+            let unrolled = paths
+                .iter()
+                .zip(&counts)
+                .map(|(path, counts)| path.unroll(counts))
+                .collect::<Vec<_>>();
+            let assignments =
+                GraphMatch::new(graph, &edge_keys).table_assignments(&unrolled)?;
+            matches.push((unrolled, assignments));
+        }
+        let outputs =
+            self.graph_table_columns(columns, &paths, &matches, &group_variables)?;
+
+        let mut branches = vec![];
+        let mut reads = PropertyReads::default();
+        for (unrolled, assignments) in &matches {
+            for assignment in assignments {
+                if branches.len() == MAX_MATCH_BRANCHES {
+                    return plan_err!(
+                        "GRAPH_TABLE pattern matches more than {MAX_MATCH_BRANCHES} combinations of path lengths and element tables"
+                    );
+                }
+                let mut state = GraphMatch::new(graph, &edge_keys);
+                let mut matched = Vec::with_capacity(paths.len());
+                for (path, tables) in unrolled.iter().zip(&assignment.paths) {
+                    matched.push(self.plan_graph_path(&mut state, path, tables)?);
+                }
+                let Some(plan) = state.plan.take() else {
+                    return plan_err!("GRAPH_TABLE requires a MATCH pattern");
+                };
+                let path_lengths = patterns
+                    .iter()
+                    .zip(&matched)
+                    .filter_map(|(pattern, elements)| {
+                        let path = pattern.path_variable.as_deref()?;
+                        Some((path, elements.edges.len()))
+                    })
+                    .collect::<HashMap<_, _>>();
+                let resolve = |expr: Expr| {
+                    state.resolve_path_references(expr, &path_lengths, &group_variables)
+                };
+                let where_clause = where_clause.clone().map(resolve).transpose()?;
+                let outputs = outputs
+                    .iter()
+                    .map(|(expr, name)| Ok((resolve(expr.clone())?, name)))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let mut filters = std::mem::take(&mut state.filters);
+                for elements in &matched {
+                    filters.extend(state.path_mode_filters(elements, &path_mode)?);
+                }
+                filters.extend(where_clause);
+                let filters = filters
+                    .into_iter()
+                    .map(|expr| state.null_missing_properties(expr, &plan, &mut reads))
+                    .collect::<Result<Vec<_>>>()?;
+                let plan = match conjunction(filters) {
+                    Some(predicate) => plan.filter(predicate)?,
+                    None => plan,
+                };
+
+                let mut projection = outputs
+                    .into_iter()
+                    .map(|(expr, name)| {
+                        let expr =
+                            state.null_missing_properties(expr, &plan, &mut reads)?;
+                        Ok(expr.alias(name))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if let Some(selector) = &selector {
+                    let elements = matched.swap_remove(0);
+                    projection.extend(state.endpoint_keys(&elements)?);
+                    projection.push(match path_measure(selector) {
+                        PATH_COST => {
+                            let cost = elements.costs.into_iter().reduce(|l, r| l + r);
+                            cost.unwrap_or_else(|| lit(0_i64)).alias(PATH_COST)
+                        }
+                        _ => lit(elements.edges.len() as u64).alias(PATH_LENGTH),
+                    });
+                }
+                branches.push(Arc::new(plan.project(projection)?.build()?));
+            }
+        }
+
+        if let Some((column, table)) = reads
+            .missing
+            .iter()
+            .find(|(column, _)| !reads.exposed.contains(column))
+        {
+            return plan_err!(
+                "{} is not a property of {table} in property graph {}",
+                column.name,
+                graph.name
+            );
+        }
+
+        let plan = match branches.len() {
+            0 => {
+                return plan_err!(
+                    "No path of property graph {} matches the GRAPH_TABLE pattern",
+                    graph.name
+                );
+            }
+            1 => Arc::unwrap_or_clone(branches.remove(0)),
+            _ => {
+                // Path search adds the keys of the endpoint vertex tables
+                let width = branches[0].schema().fields().len();
+                if branches
+                    .iter()
+                    .any(|branch| branch.schema().fields().len() != width)
+                {
+                    return not_impl_err!(
+                        "GRAPH_TABLE path search between vertex tables with keys of different lengths is not supported"
+                    );
+                }
+                LogicalPlan::Union(Union::try_new_with_loose_types(branches)?)
+            }
+        };
+        match selector {
+            Some(selector) => {
+                let names = outputs.iter().map(|(_, name)| name.as_str());
+                select_paths(plan, &selector, names.collect())
+            }
+            None => Ok(plan),
+        }
+    }
+
+    /// The `COLUMNS` of a `GRAPH_TABLE` query with their output names.
+    /// `v.*` expands to a column for each property of the element tables
+    /// `v` is bound to in any of the `matches` of `paths`, and `*` to those
+    /// of every element variable.
+    fn graph_table_columns(
+        &self,
+        columns: Vec<GraphColumn>,
+        paths: &[QuantifiedPath],
+        matches: &[(Vec<UnrolledPath<'_>>, Vec<TableAssignment<'_>>)],
+        group_variables: &HashSet<&str>,
+    ) -> Result<Vec<(Expr, String)>> {
+        let element_variables = element_variables(paths, group_variables);
+        let mut expanded = vec![];
+        for GraphColumn { expr, alias } in columns {
+            let Expr::Wildcard { qualifier, options } = &expr else {
+                expanded.push((expr, alias));
+                continue;
+            };
+            if **options != WildcardOptions::default() {
+                return not_impl_err!("{expr} is not supported in GRAPH_TABLE COLUMNS");
+            }
+            if let Some(alias) = alias {
+                return plan_err!(
+                    "{expr} in GRAPH_TABLE COLUMNS cannot be aliased as {alias}"
+                );
+            }
+            let variables = match qualifier {
+                Some(qualifier) => {
+                    let variable = qualifier.table();
+                    if group_variables.contains(variable) {
+                        return plan_err!(
+                            "Quantified edge variable {variable} can only be referenced in an aggregate"
+                        );
+                    }
+                    if !element_variables.iter().any(|v| v == variable) {
+                        return plan_err!(
+                            "{variable}.* does not refer to an element variable of the MATCH clause"
+                        );
+                    }
+                    vec![variable.to_string()]
+                }
+                None => element_variables.clone(),
+            };
+            for variable in variables {
+                let mut properties: Vec<String> = vec![];
+                for assignment in matches.iter().flat_map(|(_, assignments)| assignments)
+                {
+                    if let Some(element) = assignment.variables.get(&variable) {
+                        for property in self.element_properties(element)? {
+                            if !properties.contains(&property) {
+                                properties.push(property);
+                            }
+                        }
+                    }
+                }
+                expanded.extend(properties.into_iter().map(|property| {
+                    (element_column(&variable, &property), Some(property))
+                }));
+            }
+        }
+
+        let mut names = HashSet::new();
+        expanded
+            .into_iter()
+            .map(|(expr, alias)| {
+                let name = match (alias, &expr) {
+                    (Some(alias), _) => alias,
+                    (None, Expr::Column(column)) => {
+                        match GRAPH_FUNCTIONS
+                            .iter()
+                            .find(|(_, name)| *name == column.name)
+                        {
+                            Some((function, _)) => {
+                                let variable =
+                                    element_variable(column).unwrap_or_default();
+                                format!("{function}({variable})")
+                            }
+                            None => column.name.clone(),
+                        }
+                    }
+                    (None, expr) => expr.schema_name().to_string(),
+                };
+                if !names.insert(name.clone()) {
+                    return plan_err!(
+                        "GRAPH_TABLE column {name} is specified more than once"
+                    );
+                }
+                Ok((expr, name))
+            })
+            .collect()
+    }
+
+    /// The columns identifying the edges of each edge table of `graph`: its
+    /// KEY, or else the primary key of the table. The rows of a table with
+    /// neither are told apart by all their columns: the KEY columns of the
+    /// endpoints alone would confuse parallel edges.
+    fn graph_edge_keys(
+        &self,
+        graph: &CreatePropertyGraph,
+    ) -> Result<HashMap<TableReference, Vec<String>>> {
+        graph
+            .edge_tables
+            .iter()
+            .map(|edge| {
+                if let Some(key) = &edge.key
+                    && !key.columns.is_empty()
+                {
+                    return Ok((edge.table.clone(), key.columns.clone()));
+                }
+                let source =
+                    self.context_provider.get_table_source(edge.table.clone())?;
+                let schema = source.schema();
+                let primary_key = source.constraints().and_then(|constraints| {
+                    constraints.iter().find_map(|constraint| match constraint {
+                        Constraint::PrimaryKey(indices) => Some(indices),
+                        _ => None,
+                    })
+                });
+                let columns = match primary_key {
+                    Some(indices) => indices
+                        .iter()
+                        .map(|&i| schema.field(i).name().clone())
+                        .collect(),
+                    None => schema
+                        .fields()
+                        .iter()
+                        .map(|field| field.name().clone())
+                        .collect(),
+                };
+                Ok((edge.table.clone(), columns))
+            })
+            .collect()
+    }
+
+    /// The properties of `element`: its `PROPERTIES` columns, or all the
+    /// columns of its table without a `PROPERTIES` clause
+    fn element_properties(&self, element: &Element<'_>) -> Result<Vec<String>> {
+        if let Some(properties) = element.properties() {
+            return Ok(properties.to_vec());
+        }
+        let source = self
+            .context_provider
+            .get_table_source(element.table().clone())?;
+        Ok(source
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect())
+    }
+
+    /// Join the elements of `path` into the plan from the element `tables`
+    /// assigned to its patterns, each edge on the key columns of its
+    /// endpoint vertices. Returns the path's vertices and edges.
+    fn plan_graph_path<'a>(
+        &self,
+        state: &mut GraphMatch<'a>,
+        path: &UnrolledPath<'_>,
+        tables: &PathTables<'a>,
+    ) -> Result<PathElements<'a>> {
+        let PathTables {
+            vertices: vertex_tables,
+            edges: edge_tables,
+        } = tables;
+
+        let first = &path.vertices[0];
+        let mut left = state.alias(first.variable.as_deref());
+        self.bind_graph_element(state, &left, Element::Vertex(vertex_tables[0]), vec![])?;
+        state.add_element_filters(&left, None, &first.properties, &first.where_clause)?;
+        let mut elements = PathElements {
+            vertices: vec![(left.clone(), vertex_tables[0])],
+            edges: vec![],
+            costs: vec![],
+        };
+
+        for (i, step) in path.steps.iter().enumerate() {
+            let (left_table, right_table) = (vertex_tables[i], vertex_tables[i + 1]);
+            let vertex = &path.vertices[i + 1];
+            let right = state.alias(vertex.variable.as_deref());
+
+            let right_on = match (step, edge_tables[i]) {
+                (Step::Edge(edge), Some(edge_table)) => {
+                    // Repetitions of a quantified edge are anonymous; within
+                    // the edge's pattern its variable refers to each of them
+                    let (edge_alias, group) = match &edge.quantifier {
+                        Some(_) => (state.alias(None), edge.variable.as_deref()),
+                        None => (state.alias(edge.variable.as_deref()), None),
+                    };
+                    if let Some(group) = group {
+                        let repetitions = state.groups.entry(group.to_string());
+                        repetitions.or_default().push(edge_alias.clone());
+                    }
+
+                    // Each orientation pairs the endpoint at the left vertex
+                    // with the endpoint at the right vertex
+                    let mut edge_on = vec![];
+                    let mut right_on = vec![];
+                    for (at_left, at_right) in
+                        orientations(edge_table, &edge.direction, left_table, right_table)
+                    {
+                        let left_condition = state.endpoint_condition(
+                            &edge_alias,
+                            at_left,
+                            &left,
+                            left_table,
+                        )?;
+                        let right_condition = state.endpoint_condition(
+                            &edge_alias,
+                            at_right,
+                            &right,
+                            right_table,
+                        )?;
+                        edge_on.push(left_condition.clone());
+                        right_on.push((left_condition, right_condition));
+                    }
+                    self.bind_graph_element(
+                        state,
+                        &edge_alias,
+                        Element::Edge(edge_table),
+                        disjunction(edge_on).into_iter().collect(),
+                    )?;
+                    state.add_element_filters(
+                        &edge_alias,
+                        group,
+                        &edge.properties,
+                        &edge.where_clause,
+                    )?;
+                    let cost = state.edge_cost(edge, &edge_alias, group)?;
+                    elements.costs.push(cost);
+                    elements.edges.push((edge_alias, edge_table));
+                    elements.vertices.push((right.clone(), right_table));
+
+                    // A directed edge is joined to its left vertex, then the
+                    // right vertex to the edge; an edge traversable both ways
+                    // must match one orientation as a whole
+                    match right_on.len() {
+                        1 => right_on.pop().map(|(_, right)| right),
+                        _ => disjunction(right_on.into_iter().map(|(l, r)| l.and(r))),
+                    }
+                }
+                // Zero repetitions of a quantified edge stay on the vertex
+                _ => Some(state.same_vertex(&left, &right, left_table)?),
+            };
+            self.bind_graph_element(
+                state,
+                &right,
+                Element::Vertex(right_table),
+                right_on.into_iter().collect(),
+            )?;
+            state.add_element_filters(
+                &right,
+                None,
+                &vertex.properties,
+                &vertex.where_clause,
+            )?;
+            left = right;
+        }
+        Ok(elements)
+    }
+
+    /// Bind `alias` to `element`, joining the element's table, secured by its
+    /// security policies, into the plan on `on`. When `alias` is already
+    /// bound to the element, `on` filters the joined rows instead.
+    fn bind_graph_element<'a>(
+        &self,
+        state: &mut GraphMatch<'a>,
+        alias: &str,
+        element: Element<'a>,
+        on: Vec<Expr>,
+    ) -> Result<()> {
+        if let Some(bound) = state.variables.get(alias) {
+            if *bound != element {
+                return plan_err!(
+                    "Graph variable {alias} cannot be bound to both {} and {}",
+                    bound.table(),
+                    element.table()
+                );
+            }
+            state.filters.extend(on);
+            return Ok(());
+        }
+
+        let source = self
+            .context_provider
+            .get_table_source(element.table().clone())?;
+        let scan =
+            LogicalPlanBuilder::scan(element.table().clone(), source, None)?.build()?;
+        let scan = LogicalPlanBuilder::from(self.apply_security_policies(scan)?)
+            .alias(alias)?
+            .build()?;
+        state.plan = Some(match state.plan.take() {
+            Some(plan) if !on.is_empty() => plan.join_on(scan, JoinType::Inner, on)?,
+            Some(plan) => plan.cross_join(scan)?,
+            None => {
+                state.filters.extend(on);
+                LogicalPlanBuilder::from(scan)
+            }
+        });
+        state.variables.insert(alias.to_string(), element);
+        Ok(())
+    }
+
+    /// Rewrite the calls of [`GRAPH_FUNCTIONS`] in `expr` to references to
+    /// columns of their argument, which [`Self::plan_graph_match`] resolves:
+    /// `PATH_LENGTH(p)` for each of the `path_variables` to
+    /// `p.__graph_path_length`, and `ELEMENT_ID(v)` for an element variable
+    /// `v` to `v.__graph_element_id`
+    pub(super) fn resolve_graph_function_calls(
+        &self,
+        expr: &mut SQLExpr,
+        path_variables: &HashSet<String>,
+    ) {
+        let _ = visit_expressions_mut(expr, |expr| {
+            if let SQLExpr::Function(function) = expr
+                && let Some((function_name, column)) =
+                    GRAPH_FUNCTIONS.iter().find(|(name, _)| {
+                        function.name.to_string().eq_ignore_ascii_case(name)
+                    })
+                && let FunctionArguments::List(list) = &function.args
+                && let [
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Identifier(
+                        variable,
+                    ))),
+                ] = list.args.as_slice()
+                && (*function_name == "path_length")
+                    == path_variables
+                        .contains(&self.ident_normalizer.normalize(variable.clone()))
+            {
+                let variable = variable.clone();
+                *expr = SQLExpr::CompoundIdentifier(vec![variable, Ident::new(*column)]);
+            }
+            ControlFlow::<()>::Continue(())
+        });
+    }
+}
+
+/// The column `selector` minimizes: the length or the cost of paths
+fn path_measure(selector: &PathFinding) -> &'static str {
+    match selector {
+        PathFinding::AnyCheapest | PathFinding::AllCheapest => PATH_COST,
+        _ => PATH_LENGTH,
+    }
+}
+
+/// Keep the paths `selector` chooses from `plan`, the union of the matched
+/// paths. Besides the `outputs` columns, `plan` has the keys of each path's
+/// endpoints and its [`path_measure`], which the result drops.
+fn select_paths(
+    plan: LogicalPlan,
+    selector: &PathFinding,
+    outputs: Vec<&str>,
+) -> Result<LogicalPlan> {
+    let endpoints = plan
+        .schema()
+        .columns()
+        .into_iter()
+        .filter(|column| column.name.starts_with(PATH_ENDPOINT))
+        .map(|column| column.name)
+        .collect::<Vec<_>>();
+    let measure = path_measure(selector);
+    match selector {
+        PathFinding::Any | PathFinding::AnyShortest | PathFinding::AnyCheapest => {
+            let on = endpoints
+                .iter()
+                .map(|name| Expr::Column(Column::from_name(name)))
+                .collect::<Vec<_>>();
+            let mut sort = on
+                .iter()
+                .map(|endpoint| endpoint.clone().sort(true, false))
+                .collect::<Vec<_>>();
+            if *selector != PathFinding::Any {
+                sort.push(Expr::Column(Column::from_name(measure)).sort(true, false));
+            }
+            let select = outputs
+                .iter()
+                .map(|name| Expr::Column(Column::from_name(*name)))
+                .collect();
+            LogicalPlanBuilder::from(plan)
+                .distinct_on(on, select, Some(sort))?
+                .build()
+        }
+        // Keep the paths no path between the same endpoints is shorter or
+        // cheaper than
+        _ => {
+            let better = LogicalPlanBuilder::from(plan.clone())
+                .alias("__graph_better")?
+                .build()?;
+            let on = endpoints
+                .iter()
+                .map(|name| {
+                    element_column("__graph_paths", name)
+                        .eq(element_column("__graph_better", name))
+                })
+                .chain([element_column("__graph_better", measure)
+                    .lt(element_column("__graph_paths", measure))])
+                .collect::<Vec<_>>();
+            LogicalPlanBuilder::from(plan)
+                .alias("__graph_paths")?
+                .join_on(better, JoinType::LeftAnti, on)?
+                .project(
+                    outputs
+                        .iter()
+                        .map(|name| element_column("__graph_paths", name).alias(*name)),
+                )?
+                .build()
+        }
+    }
+}
+
+/// A vertex or edge table of a property graph
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Element<'a> {
+    Vertex(&'a GraphVertexTableDefinition),
+    Edge(&'a GraphEdgeTableDefinition),
+}
+
+impl Element<'_> {
+    fn table(&self) -> &TableReference {
+        match self {
+            Self::Vertex(vertex) => &vertex.table,
+            Self::Edge(edge) => &edge.table,
+        }
+    }
+
+    /// The columns exposed as properties, or `None` if all columns are
+    fn properties(&self) -> Option<&[String]> {
+        let properties = match self {
+            Self::Vertex(vertex) => vertex.properties.as_ref(),
+            Self::Edge(edge) => edge.properties.as_ref(),
+        };
+        properties.map(|properties| properties.columns.as_slice())
+    }
+}
+
+/// A path pattern as its vertex patterns and the edge pattern between each
+/// consecutive pair of them, which may be quantified
+struct QuantifiedPath {
+    vertices: Vec<NodePattern>,
+    edges: Vec<EdgePattern>,
+}
+
+impl QuantifiedPath {
+    /// The path with edge `i` repeated `counts[i]` times, through anonymous
+    /// vertices
+    fn unroll(&self, counts: &[u32]) -> UnrolledPath<'_> {
+        let mut vertices = vec![self.vertices[0].clone()];
+        let mut steps = vec![];
+        for ((edge, count), vertex) in
+            self.edges.iter().zip(counts).zip(&self.vertices[1..])
+        {
+            if *count == 0 {
+                steps.push(Step::Stay);
+            }
+            for i in 0..*count {
+                if i > 0 {
+                    vertices.push(anonymous_vertex());
+                }
+                steps.push(Step::Edge(edge));
+            }
+            vertices.push(vertex.clone());
+        }
+        UnrolledPath { vertices, steps }
+    }
+}
+
+/// A path pattern with a single step between each consecutive pair of
+/// vertex patterns
+struct UnrolledPath<'p> {
+    vertices: Vec<NodePattern>,
+    steps: Vec<Step<'p>>,
+}
+
+/// A step from a vertex pattern to the next
+enum Step<'p> {
+    /// Traverse an edge
+    Edge(&'p EdgePattern),
+    /// Stay on the same vertex, for a quantified edge repeated zero times
+    Stay,
+}
+
+/// The vertices and edges of a matched path, as relation aliases and
+/// element tables, and the cost of each edge
+struct PathElements<'a> {
+    vertices: Vec<(String, &'a GraphVertexTableDefinition)>,
+    edges: Vec<(String, &'a GraphEdgeTableDefinition)>,
+    costs: Vec<Expr>,
+}
+
+/// The element tables assigned to the vertex patterns and steps of a path.
+/// Steps that stay on a vertex have no edge table.
+#[derive(Debug, Clone)]
+struct PathTables<'a> {
+    vertices: Vec<&'a GraphVertexTableDefinition>,
+    edges: Vec<Option<&'a GraphEdgeTableDefinition>>,
+}
+
+/// Element tables assigned to the patterns of the paths of a `MATCH`
+/// clause, with the element bound to each variable
+#[derive(Debug, Clone, Default)]
+struct TableAssignment<'a> {
+    paths: Vec<PathTables<'a>>,
+    variables: HashMap<String, Element<'a>>,
+}
+
+impl<'a> TableAssignment<'a> {
+    /// Bind `variable` to `element`, unless it is bound to another element
+    fn bind(&mut self, variable: &Option<String>, element: Element<'a>) -> bool {
+        match variable {
+            Some(variable) => {
+                *self.variables.entry(variable.clone()).or_insert(element) == element
+            }
+            None => true,
+        }
+    }
+}
+
+/// The element properties read by the branches of a `MATCH`: those the
+/// element tables of some branch expose, and those read as null because a
+/// branch's tables do not
+#[derive(Debug, Default)]
+struct PropertyReads {
+    exposed: HashSet<Column>,
+    missing: Vec<(Column, TableReference)>,
+}
+
+/// The state of planning the `MATCH` patterns of a `GRAPH_TABLE` query
+struct GraphMatch<'a> {
+    graph: &'a CreatePropertyGraph,
+    /// The columns identifying the edges of each edge table
+    edge_keys: &'a HashMap<TableReference, Vec<String>>,
+    /// The element tables joined so far
+    plan: Option<LogicalPlanBuilder>,
+    /// The element bound to each variable, keyed by relation alias
+    variables: HashMap<String, Element<'a>>,
+    /// Element property constraints and `WHERE` clauses
+    filters: Vec<Expr>,
+    /// Number of anonymous elements, used to name their relations
+    anonymous: usize,
+    /// The relations of the repetitions of each quantified edge variable
+    groups: HashMap<String, Vec<String>>,
+}
+
+impl<'a> GraphMatch<'a> {
+    fn new(
+        graph: &'a CreatePropertyGraph,
+        edge_keys: &'a HashMap<TableReference, Vec<String>>,
+    ) -> Self {
+        Self {
+            graph,
+            edge_keys,
+            plan: None,
+            variables: HashMap::new(),
+            filters: vec![],
+            anonymous: 0,
+            groups: HashMap::new(),
+        }
+    }
+
+    /// The relation alias of an element pattern: its variable, or a fresh
+    /// name for anonymous elements
+    fn alias(&mut self, variable: Option<&str>) -> String {
+        match variable {
+            Some(variable) => variable.to_string(),
+            None => {
+                self.anonymous += 1;
+                format!("__graph_element_{}", self.anonymous)
+            }
+        }
+    }
+
+    /// Every assignment of element tables to the vertex patterns and steps
+    /// of `paths`. Each table's label must match its pattern, each edge
+    /// table must connect the vertex tables on either side of it, a step
+    /// that stays on a vertex must be between patterns assigned the same
+    /// vertex table, and a variable must be assigned the same table
+    /// wherever it appears.
+    fn table_assignments(
+        &self,
+        paths: &[UnrolledPath<'_>],
+    ) -> Result<Vec<TableAssignment<'a>>> {
+        let mut assignments = vec![TableAssignment::default()];
+        for path in paths {
+            let vertex_candidates = path
+                .vertices
+                .iter()
+                .map(|vertex| self.vertex_candidates(vertex))
+                .collect::<Result<Vec<_>>>()?;
+
+            let first = &path.vertices[0];
+            let mut extended = vec![];
+            for assignment in &assignments {
+                for &table in &vertex_candidates[0] {
+                    let mut assignment = assignment.clone();
+                    if assignment.bind(&first.variable, Element::Vertex(table)) {
+                        assignment.paths.push(PathTables {
+                            vertices: vec![table],
+                            edges: vec![],
+                        });
+                        extended.push(assignment);
+                    }
+                }
+            }
+            assignments = extended;
+
+            for (i, step) in path.steps.iter().enumerate() {
+                let vertex = &path.vertices[i + 1];
+                // Repetitions of a quantified edge bind its variable to a
+                // list of edges, which may be of different tables
+                let (edge_candidates, edge_variable) = match step {
+                    Step::Edge(edge) => {
+                        let candidates = self.edge_candidates(edge)?;
+                        let variable = match edge.quantifier {
+                            Some(_) => None,
+                            None => edge.variable.clone(),
+                        };
+                        (candidates.into_iter().map(Some).collect(), variable)
+                    }
+                    Step::Stay => (vec![None], None),
+                };
+                let mut extended = vec![];
+                for assignment in &assignments {
+                    let tables =
+                        assignment.paths.last().expect("path has a first vertex");
+                    let left = *tables.vertices.last().expect("path has a first vertex");
+                    for &right in &vertex_candidates[i + 1] {
+                        for &edge_table in &edge_candidates {
+                            let connects = match (step, edge_table) {
+                                (Step::Edge(edge), Some(edge_table)) => !orientations(
+                                    edge_table,
+                                    &edge.direction,
+                                    left,
+                                    right,
+                                )
+                                .is_empty(),
+                                _ => left == right,
+                            };
+                            if !connects {
+                                continue;
+                            }
+                            let mut assignment = assignment.clone();
+                            let bound = edge_table.is_none_or(|edge_table| {
+                                assignment.bind(&edge_variable, Element::Edge(edge_table))
+                            }) && assignment
+                                .bind(&vertex.variable, Element::Vertex(right));
+                            if !bound {
+                                continue;
+                            }
+                            let tables = assignment
+                                .paths
+                                .last_mut()
+                                .expect("path has a first vertex");
+                            tables.vertices.push(right);
+                            tables.edges.push(edge_table);
+                            extended.push(assignment);
+                            if extended.len() > MAX_MATCH_BRANCHES {
+                                return plan_err!(
+                                    "GRAPH_TABLE pattern matches more than {MAX_MATCH_BRANCHES} combinations of element tables"
+                                );
+                            }
+                        }
+                    }
+                }
+                assignments = extended;
+            }
+        }
+        Ok(assignments)
+    }
+
+    /// The vertex tables whose labels match `vertex`
+    fn vertex_candidates(
+        &self,
+        vertex: &NodePattern,
+    ) -> Result<Vec<&'a GraphVertexTableDefinition>> {
+        let candidates = self
+            .graph
+            .vertex_tables
+            .iter()
+            .filter(|table| {
+                let label = table.label.as_deref().unwrap_or(table.table.table());
+                labels_match(&vertex.labels, label)
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return plan_err!(
+                "No vertex table of property graph {} matches ({})",
+                self.graph.name,
+                element_text(&vertex.variable, &vertex.labels)
+            );
+        }
+        Ok(candidates)
+    }
+
+    /// The edge tables whose labels match `edge`
+    fn edge_candidates(
+        &self,
+        edge: &EdgePattern,
+    ) -> Result<Vec<&'a GraphEdgeTableDefinition>> {
+        let candidates = self
+            .graph
+            .edge_tables
+            .iter()
+            .filter(|table| {
+                let label = table.label.as_deref().unwrap_or(table.table.table());
+                labels_match(&edge.labels, label)
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return plan_err!(
+                "No edge table of property graph {} matches -[{}]-",
+                self.graph.name,
+                element_text(&edge.variable, &edge.labels)
+            );
+        }
+        Ok(candidates)
+    }
+
+    /// The KEY columns of `vertex_table`
+    fn vertex_key<'t>(
+        &self,
+        vertex_table: &'t GraphVertexTableDefinition,
+    ) -> Result<&'t [String]> {
+        match &vertex_table.key {
+            Some(key) if !key.columns.is_empty() => Ok(&key.columns),
+            _ => plan_err!(
+                "Vertex table {} of property graph {} has no KEY",
+                vertex_table.table,
+                self.graph.name
+            ),
+        }
+    }
+
+    /// The columns identifying an edge of `edge_table`, see
+    /// [`SqlToRel::graph_edge_keys`]
+    fn edge_key(&self, edge_table: &GraphEdgeTableDefinition) -> Result<&'a [String]> {
+        match self.edge_keys.get(&edge_table.table) {
+            Some(key) => Ok(key),
+            None => internal_err!(
+                "Edge table {} of property graph {} has no key",
+                edge_table.table,
+                self.graph.name
+            ),
+        }
+    }
+
+    /// `edge.<endpoint key> = vertex.<vertex key>`
+    fn endpoint_condition(
+        &self,
+        edge: &str,
+        endpoint: &GraphEdgeEndpoint,
+        vertex: &str,
+        vertex_table: &GraphVertexTableDefinition,
+    ) -> Result<Expr> {
+        let vertex_key = self.vertex_key(vertex_table)?;
+        let Some(endpoint_key) = &endpoint.key else {
+            return plan_err!(
+                "Edge endpoint referencing {} in property graph {} has no KEY",
+                endpoint.references,
+                self.graph.name
+            );
+        };
+        if endpoint_key.columns.len() != vertex_key.len() {
+            return plan_err!(
+                "Edge endpoint KEY ({}) does not match the KEY ({}) of vertex table {}",
+                endpoint_key.columns.join(", "),
+                vertex_key.join(", "),
+                vertex_table.table
+            );
+        }
+        let condition = endpoint_key.columns.iter().zip(vertex_key).map(
+            |(edge_column, vertex_column)| {
+                element_column(edge, edge_column)
+                    .eq(element_column(vertex, vertex_column))
+            },
+        );
+        Ok(conjunction(condition).expect("vertex keys are not empty"))
+    }
+
+    /// `left.<key> = right.<key>`: `left` and `right` are the same vertex of
+    /// `vertex_table`
+    fn same_vertex(
+        &self,
+        left: &str,
+        right: &str,
+        vertex_table: &GraphVertexTableDefinition,
+    ) -> Result<Expr> {
+        let condition = self
+            .vertex_key(vertex_table)?
+            .iter()
+            .map(|column| element_column(left, column).eq(element_column(right, column)));
+        Ok(conjunction(condition).expect("vertex keys are not empty"))
+    }
+
+    /// Filters rejecting the matches of a path that `mode` forbids. Elements
+    /// of different tables are always distinct, so only elements of the
+    /// same table are compared.
+    fn path_mode_filters(
+        &self,
+        elements: &PathElements<'_>,
+        mode: &PathMode,
+    ) -> Result<Vec<Expr>> {
+        let mut filters = vec![];
+        match mode {
+            PathMode::Walk => {}
+            PathMode::Trail => {
+                for (i, (left, left_table)) in elements.edges.iter().enumerate() {
+                    for (right, right_table) in &elements.edges[i + 1..] {
+                        if left_table == right_table {
+                            let key = self.edge_key(left_table)?;
+                            filters.extend(distinct_elements(left, right, key));
+                        }
+                    }
+                }
+            }
+            PathMode::Acyclic | PathMode::Simple => {
+                let last = elements.vertices.len() - 1;
+                for (i, (left, left_table)) in elements.vertices.iter().enumerate() {
+                    for (j, (right, right_table)) in
+                        elements.vertices.iter().enumerate().skip(i + 1)
+                    {
+                        // A simple path may return to its start
+                        let closes_cycle =
+                            *mode == PathMode::Simple && (i, j) == (0, last);
+                        if left_table == right_table && !closes_cycle {
+                            let key = self.vertex_key(left_table)?;
+                            filters.extend(distinct_elements(left, right, key));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(filters)
+    }
+
+    /// The KEY columns of the first and last vertex of a path, aliased as
+    /// `__graph_endpoint_<n>`. In graphs with several vertex tables, the
+    /// name of each endpoint's table precedes its key.
+    fn endpoint_keys(&self, elements: &PathElements<'_>) -> Result<Vec<Expr>> {
+        let (Some(first), Some(last)) =
+            (elements.vertices.first(), elements.vertices.last())
+        else {
+            return Ok(vec![]);
+        };
+        let mut keys = vec![];
+        for (alias, vertex_table) in [first, last] {
+            if self.graph.vertex_tables.len() > 1 {
+                let name = format!("{PATH_ENDPOINT}{}", keys.len());
+                keys.push(lit(vertex_table.table.to_string()).alias(name));
+            }
+            for column in self.vertex_key(vertex_table)? {
+                let name = format!("{PATH_ENDPOINT}{}", keys.len());
+                keys.push(element_column(alias, column).alias(name));
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Record the property constraints `{name: value, ...}` and the `WHERE`
+    /// clause of the element pattern bound to `alias`. For a repetition of a
+    /// quantified edge, the edge's variable `group` refers to `alias`.
+    fn add_element_filters(
+        &mut self,
+        alias: &str,
+        group: Option<&str>,
+        properties: &[(String, Expr)],
+        where_clause: &Option<Expr>,
+    ) -> Result<()> {
+        self.filters.extend(
+            properties
+                .iter()
+                .map(|(name, value)| element_column(alias, name).eq(value.clone())),
+        );
+        let Some(predicate) = where_clause.clone() else {
+            return Ok(());
+        };
+        let predicate = match group {
+            Some(group) => rename_variable(predicate, group, alias)?,
+            None => predicate,
+        };
+        self.filters.push(predicate);
+        Ok(())
+    }
+
+    /// The cost of the edge bound to `alias` for the edge pattern `edge`,
+    /// whose `COST` expression must be a number computed from the edge's
+    /// properties. For a repetition of a quantified edge, the edge's
+    /// variable `group` refers to `alias`.
+    fn edge_cost(
+        &self,
+        edge: &EdgePattern,
+        alias: &str,
+        group: Option<&str>,
+    ) -> Result<Expr> {
+        let Some(cost) = &edge.cost else {
+            return Ok(lit(1_i64));
+        };
+        if cost.column_refs().iter().any(|column| {
+            let variable = element_variable(column);
+            variable.is_none() || variable != edge.variable.as_deref()
+        }) {
+            return plan_err!(
+                "COST expression {cost} of -[{}]- may only reference properties of the edge",
+                element_text(&edge.variable, &edge.labels)
+            );
+        }
+        let edge_cost = match group {
+            Some(group) => rename_variable(cost.clone(), group, alias)?,
+            None => cost.clone(),
+        };
+        self.check_properties(&edge_cost)?;
+        let Some(plan) = &self.plan else {
+            return internal_err!("Edge {alias} is not bound");
+        };
+        let data_type = edge_cost.get_type(plan.schema())?;
+        if !data_type.is_numeric() {
+            return plan_err!(
+                "COST expression {cost} must be numeric, found {data_type}"
+            );
+        }
+        Ok(edge_cost)
+    }
+
+    /// Resolve the references to matched paths in `expr`, a `WHERE` or
+    /// `COLUMNS` expression. `PATH_LENGTH(p)` becomes the number of edges of
+    /// path `p`, given by `path_lengths`, and an aggregate over a quantified
+    /// edge variable is computed across the edges bound to it: `SUM(e.w)`
+    /// over three repetitions of `e` adds their three `w` properties.
+    /// `ELEMENT_ID(v)` becomes the [`Self::element_id`] of the element bound
+    /// to `v`.
+    fn resolve_path_references(
+        &self,
+        expr: Expr,
+        path_lengths: &HashMap<&str, usize>,
+        group_variables: &HashSet<&str>,
+    ) -> Result<Expr> {
+        let expr = expr
+            .transform(|expr| match expr {
+                Expr::Column(column) if column.name == PATH_LENGTH => {
+                    match element_variable(&column).and_then(|p| path_lengths.get(p)) {
+                        Some(length) => Ok(Transformed::yes(lit(*length as u64))),
+                        None => Ok(Transformed::no(Expr::Column(column))),
+                    }
+                }
+                Expr::AggregateFunction(aggregate) => self
+                    .aggregate_repetitions(aggregate, group_variables)
+                    .map(Transformed::yes),
+                expr => Ok(Transformed::no(expr)),
+            })
+            .data()?
+            // After aggregates, whose arguments now read each repetition
+            .transform(|expr| match expr {
+                Expr::Column(column)
+                    if [ELEMENT_ID, VERTEX_ID, EDGE_ID]
+                        .contains(&column.name.as_str()) =>
+                {
+                    let Some(variable) = element_variable(&column) else {
+                        return Ok(Transformed::no(Expr::Column(column)));
+                    };
+                    match self.variables.get(variable) {
+                        Some(element) => self
+                            .element_id(&column.name, variable, element)
+                            .map(Transformed::yes),
+                        None if group_variables.contains(variable) => {
+                            Ok(Transformed::no(Expr::Column(column)))
+                        }
+                        None => plan_err!(
+                            "{variable} is not an element variable of the MATCH clause"
+                        ),
+                    }
+                }
+                expr => Ok(Transformed::no(expr)),
+            })
+            .data()?;
+        for column in expr.column_refs() {
+            if let Some(variable) = element_variable(column)
+                && group_variables.contains(variable)
+            {
+                return plan_err!(
+                    "Quantified edge variable {variable} can only be referenced in an aggregate"
+                );
+            }
+        }
+        Ok(expr)
+    }
+
+    /// The identifier of the element bound to `alias`, for the column
+    /// `function` of an element-id function: the name of the element's
+    /// table and its KEY values, as in `person:1`
+    fn element_id(
+        &self,
+        function: &str,
+        alias: &str,
+        element: &Element<'_>,
+    ) -> Result<Expr> {
+        let key = match (function, element) {
+            (VERTEX_ID, Element::Edge(_)) => {
+                return plan_err!("VERTEX_ID({alias}) requires a vertex variable");
+            }
+            (EDGE_ID, Element::Vertex(_)) => {
+                return plan_err!("EDGE_ID({alias}) requires an edge variable");
+            }
+            (_, Element::Vertex(vertex_table)) => self.vertex_key(vertex_table)?.to_vec(),
+            (_, Element::Edge(edge_table)) => self.edge_key(edge_table)?.to_vec(),
+        };
+        let mut id = lit(format!("{}:", element.table()));
+        for (i, column) in key.iter().enumerate() {
+            if i > 0 {
+                id = binary_expr(id, Operator::StringConcat, lit(","));
+            }
+            let value = cast(element_column(alias, column), DataType::Utf8);
+            id = binary_expr(id, Operator::StringConcat, value);
+        }
+        Ok(id)
+    }
+
+    /// Compute `aggregate`, whose argument reads a quantified edge variable,
+    /// across the repetitions of the edge
+    fn aggregate_repetitions(
+        &self,
+        aggregate: AggregateFunction,
+        group_variables: &HashSet<&str>,
+    ) -> Result<Expr> {
+        let AggregateFunctionParams {
+            args,
+            distinct,
+            filter,
+            order_by,
+            ..
+        } = aggregate.params.clone();
+        let variables = args
+            .iter()
+            .flat_map(|arg| arg.column_refs())
+            .filter_map(element_variable)
+            .collect::<HashSet<_>>();
+        let (Some(group), [arg]) = (variables.iter().next(), args.as_slice()) else {
+            return plan_err!(
+                "Aggregate {} in GRAPH_TABLE must read a quantified edge variable",
+                Expr::AggregateFunction(aggregate)
+            );
+        };
+        if variables.len() > 1 || !group_variables.contains(group) {
+            return plan_err!(
+                "Aggregate {} in GRAPH_TABLE must read a single quantified edge variable",
+                Expr::AggregateFunction(aggregate)
+            );
+        }
+        if distinct || filter.is_some() || !order_by.is_empty() {
+            return not_impl_err!(
+                "DISTINCT, FILTER and ORDER BY are not supported in aggregates over quantified edge variables"
+            );
+        }
+        let values = self
+            .groups
+            .get(*group)
+            .into_iter()
+            .flatten()
+            .map(|alias| rename_variable(arg.clone(), group, alias))
+            .collect::<Result<Vec<_>>>()?;
+        match aggregate.func.name().to_ascii_lowercase().as_str() {
+            "count" => count_values(&values),
+            "sum" => sum_values(values),
+            "min" => fold_values(values, |acc, value| {
+                when(
+                    acc.clone().is_null().or(value.clone().lt(acc.clone())),
+                    value,
+                )
+                .otherwise(acc)
+            }),
+            "max" => fold_values(values, |acc, value| {
+                when(
+                    acc.clone().is_null().or(value.clone().gt(acc.clone())),
+                    value,
+                )
+                .otherwise(acc)
+            }),
+            "avg" => {
+                let count = count_values(&values)?;
+                let sum = sum_values(values)?;
+                when(
+                    count.clone().gt(lit(0_i64)),
+                    cast(sum, DataType::Float64) / cast(count, DataType::Float64),
+                )
+                .end()
+            }
+            name => not_impl_err!(
+                "Aggregate {name} over quantified edge variables is not supported"
+            ),
+        }
+    }
+
+    /// Check that `expr` only reads the properties of bound elements
+    fn check_properties(&self, expr: &Expr) -> Result<()> {
+        for column in expr.column_refs() {
+            let Some(element) = element_variable(column)
+                .and_then(|variable| self.variables.get(variable))
+            else {
+                continue;
+            };
+            if let Some(properties) = element.properties()
+                && !properties.contains(&column.name)
+            {
+                return plan_err!(
+                    "{} is not a property of {} in property graph {}",
+                    column.name,
+                    element.table(),
+                    self.graph.name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the element properties `expr` reads that the tables bound in
+    /// `plan` do not expose by nulls, recording in `reads` which properties
+    /// are exposed and which are not
+    fn null_missing_properties(
+        &self,
+        expr: Expr,
+        plan: &LogicalPlanBuilder,
+        reads: &mut PropertyReads,
+    ) -> Result<Expr> {
+        expr.transform(|expr| {
+            let Expr::Column(column) = expr else {
+                return Ok(Transformed::no(expr));
+            };
+            let Some(element) = element_variable(&column)
+                .and_then(|variable| self.variables.get(variable))
+            else {
+                return Ok(Transformed::no(Expr::Column(column)));
+            };
+            let exposed = element
+                .properties()
+                .is_none_or(|properties| properties.contains(&column.name))
+                && plan.schema().has_column(&column);
+            if exposed {
+                reads.exposed.insert(column.clone());
+                Ok(Transformed::no(Expr::Column(column)))
+            } else {
+                reads.missing.push((column, element.table().clone()));
+                Ok(Transformed::yes(lit(ScalarValue::Null)))
+            }
+        })
+        .data()
+    }
+}
+
+/// Flatten the path `expr` into its vertex and edge patterns, adding
+/// anonymous vertices so that every edge is between two vertices
+fn flatten_path(expr: &GraphPatternExpr) -> Result<QuantifiedPath> {
+    let elements = match expr {
+        GraphPatternExpr::Chain(elements) => elements,
+        GraphPatternExpr::Group {
+            pattern,
+            quantifier: None,
+        } => return flatten_path(pattern),
+        GraphPatternExpr::Group { .. } => {
+            return not_impl_err!("Quantified path patterns are not supported");
+        }
+        GraphPatternExpr::Alternation(_) => {
+            return not_impl_err!("Path pattern alternation is not supported");
+        }
+    };
+    let mut vertices = vec![];
+    let mut edges = vec![];
+    for element in elements {
+        match element {
+            GraphPatternElement::Node(vertex) => {
+                if vertices.len() > edges.len() {
+                    return not_impl_err!("Adjacent vertex patterns are not supported");
+                }
+                vertices.push(vertex.clone());
+            }
+            GraphPatternElement::Edge(edge) => {
+                if vertices.len() == edges.len() {
+                    vertices.push(anonymous_vertex());
+                }
+                edges.push(edge.clone());
+            }
+            GraphPatternElement::Subpattern(_) => {
+                return not_impl_err!("Parenthesized path patterns are not supported");
+            }
+        }
+    }
+    if vertices.len() == edges.len() {
+        vertices.push(anonymous_vertex());
+    }
+    Ok(QuantifiedPath { vertices, edges })
+}
+
+/// Every combination of the repetition counts the quantifiers of the edges
+/// of `paths` allow, as the counts of each path
+fn repetition_counts(
+    paths: &[QuantifiedPath],
+    max_length: u32,
+) -> Result<Vec<Vec<Vec<u32>>>> {
+    let mut combinations: Vec<Vec<Vec<u32>>> = vec![vec![]];
+    for (i, path) in paths.iter().enumerate() {
+        for (j, edge) in path.edges.iter().enumerate() {
+            let repetitions = repetitions(edge.quantifier.as_ref(), max_length);
+            combinations = combinations
+                .into_iter()
+                .flat_map(|counts| {
+                    repetitions.clone().map(move |count| {
+                        let mut counts = counts.clone();
+                        if j == 0 {
+                            counts.push(vec![]);
+                        }
+                        counts[i].push(count);
+                        counts
+                    })
+                })
+                .collect();
+            if combinations.len() > MAX_MATCH_BRANCHES {
+                return plan_err!(
+                    "GRAPH_TABLE quantifiers allow more than {MAX_MATCH_BRANCHES} combinations of path lengths"
+                );
+            }
+        }
+        if path.edges.is_empty() {
+            for counts in &mut combinations {
+                counts.push(vec![]);
+            }
+        }
+    }
+    Ok(combinations)
+}
+
+/// The numbers of repetitions `quantifier` allows, with unbounded
+/// quantifiers capped at `max_length`
+fn repetitions(
+    quantifier: Option<&RepetitionQuantifier>,
+    max_length: u32,
+) -> RangeInclusive<u32> {
+    match quantifier {
+        None => 1..=1,
+        Some(RepetitionQuantifier::ZeroOrMore) => 0..=max_length,
+        Some(RepetitionQuantifier::OneOrMore) => 1..=max_length.max(1),
+        Some(RepetitionQuantifier::AtMostOne) => 0..=1,
+        Some(RepetitionQuantifier::Exactly(n)) => *n..=*n,
+        Some(RepetitionQuantifier::AtLeast(n)) => *n..=max_length.max(*n),
+        Some(RepetitionQuantifier::AtMost(n)) => 0..=*n,
+        Some(RepetitionQuantifier::Range(min, max)) => *min..=*max,
+    }
+}
+
+/// The variables of the vertex and unquantified edge patterns of `paths`,
+/// in order of first appearance
+fn element_variables(
+    paths: &[QuantifiedPath],
+    group_variables: &HashSet<&str>,
+) -> Vec<String> {
+    let mut variables: Vec<String> = vec![];
+    for path in paths {
+        let patterns = path.vertices.iter().enumerate().flat_map(|(i, vertex)| {
+            let edge = path.edges.get(i).map(|edge| &edge.variable);
+            [Some(&vertex.variable), edge].into_iter().flatten()
+        });
+        for variable in patterns.flatten() {
+            if !group_variables.contains(variable.as_str())
+                && !variables.contains(variable)
+            {
+                variables.push(variable.clone());
+            }
+        }
+    }
+    variables
+}
+
+fn anonymous_vertex() -> NodePattern {
+    NodePattern {
+        variable: None,
+        labels: vec![],
+        properties: vec![],
+        where_clause: None,
+    }
+}
+
+/// The ways `edge` can be traversed from `left` to `right` in `direction`,
+/// as pairs of the endpoints at `left` and at `right`
+fn orientations<'a>(
+    edge: &'a GraphEdgeTableDefinition,
+    direction: &EdgeDirection,
+    left: &GraphVertexTableDefinition,
+    right: &GraphVertexTableDefinition,
+) -> Vec<(&'a GraphEdgeEndpoint, &'a GraphEdgeEndpoint)> {
+    let (source, destination) = (&edge.source.references, &edge.destination.references);
+    let forward =
+        source.resolved_eq(&left.table) && destination.resolved_eq(&right.table);
+    let backward =
+        destination.resolved_eq(&left.table) && source.resolved_eq(&right.table);
+    let mut orientations = vec![];
+    if forward && *direction != EdgeDirection::Left {
+        orientations.push((&edge.source, &edge.destination));
+    }
+    if backward && *direction != EdgeDirection::Right {
+        orientations.push((&edge.destination, &edge.source));
+    }
+    orientations
+}
+
+/// Whether an element with `label` satisfies every label expression of a
+/// pattern
+fn labels_match(labels: &[LabelExpression], label: &str) -> bool {
+    labels.iter().all(|expr| label_matches(expr, label))
+}
+
+/// Whether an element with `label` satisfies the label expression `expr`
+fn label_matches(expr: &LabelExpression, label: &str) -> bool {
+    match expr {
+        LabelExpression::Label(name) => name == label,
+        LabelExpression::Wildcard => true,
+        LabelExpression::Not(expr) => !label_matches(expr, label),
+        LabelExpression::And(left, right) => {
+            label_matches(left, label) && label_matches(right, label)
+        }
+        LabelExpression::Or(left, right) => {
+            label_matches(left, label) || label_matches(right, label)
+        }
+    }
+}
+
+/// `left` and `right` differ in at least one of the `key` columns, where a
+/// null differs from a value, as the columns of a key made of all the
+/// columns of a table may be null
+fn distinct_elements(left: &str, right: &str, key: &[String]) -> Option<Expr> {
+    disjunction(key.iter().map(|column| {
+        binary_expr(
+            element_column(left, column),
+            Operator::IsDistinctFrom,
+            element_column(right, column),
+        )
+    }))
+}
+
+/// `variable:label` describing an element pattern in messages
+fn element_text(variable: &Option<String>, labels: &[LabelExpression]) -> String {
+    let labels = labels
+        .iter()
+        .map(|label| format!(":{label}"))
+        .collect::<String>();
+    format!("{}{labels}", variable.as_deref().unwrap_or_default())
+}
+
+/// The number of non-null `values`
+fn count_values(values: &[Expr]) -> Result<Expr> {
+    let counts = values
+        .iter()
+        .map(|value| when(value.clone().is_not_null(), lit(1_i64)).otherwise(lit(0_i64)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(counts
+        .into_iter()
+        .reduce(|l, r| l + r)
+        .unwrap_or_else(|| lit(0_i64)))
+}
+
+/// The sum of the non-null `values`, null if there are none
+fn sum_values(values: Vec<Expr>) -> Result<Expr> {
+    fold_values(values, |acc, value| {
+        when(acc.clone().is_null(), value.clone())
+            .when(value.clone().is_null(), acc.clone())
+            .otherwise(acc + value)
+    })
+}
+
+/// Combine `values` left to right with `combine`, or null if there are none
+fn fold_values(
+    values: Vec<Expr>,
+    combine: impl Fn(Expr, Expr) -> Result<Expr>,
+) -> Result<Expr> {
+    let mut values = values.into_iter();
+    match values.next() {
+        Some(first) => values.try_fold(first, combine),
+        None => Ok(lit(ScalarValue::Null)),
+    }
+}
+
+/// Replace the references to element variable `from` in `expr` by
+/// references to the relation `to`
+fn rename_variable(expr: Expr, from: &str, to: &str) -> Result<Expr> {
+    expr.transform(|expr| match expr {
+        Expr::Column(column) if element_variable(&column) == Some(from) => {
+            Ok(Transformed::yes(element_column(to, &column.name)))
+        }
+        expr => Ok(Transformed::no(expr)),
+    })
+    .data()
+}
+
+/// The element variable qualifying `column`, if any
+fn element_variable(column: &Column) -> Option<&str> {
+    match &column.relation {
+        Some(TableReference::Bare { table }) => Some(table.as_ref()),
+        _ => None,
+    }
+}
+
+fn element_column(alias: &str, name: &str) -> Expr {
+    Expr::Column(Column::new(Some(TableReference::bare(alias)), name))
+}
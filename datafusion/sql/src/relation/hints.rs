@@ -0,0 +1,152 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::{Diagnostic, Result, plan_err};
+use datafusion_expr::{LogicalPlan, TableProviderHintSupport};
+use sqlparser::ast::{
+    BinaryOperator, Expr as SQLExpr, FunctionArg, FunctionArgExpr, FunctionArguments,
+    TableIndexHintForClause, TableIndexHintType, TableIndexHints,
+};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Collect the table hints of a `FROM` item, keyed by upper-case hint
+    /// name:
+    ///
+    /// * T-SQL `WITH (NOLOCK, INDEX(ix))` gives `NOLOCK` and `INDEX` with
+    ///   argument `ix`; `INDEX = ix` is accepted as well.
+    /// * MySQL `FORCE INDEX FOR JOIN (a, b)` gives `FORCE INDEX FOR JOIN`
+    ///   with arguments `a` and `b`. `KEY` is a synonym of `INDEX`.
+    ///
+    /// Arguments of repeated hints are appended.
+    pub(super) fn plan_table_hints(
+        &self,
+        with_hints: Vec<SQLExpr>,
+        index_hints: Vec<TableIndexHints>,
+    ) -> Result<BTreeMap<String, Vec<String>>> {
+        let mut hints: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for hint in with_hints {
+            let (name, args) = match hint {
+                SQLExpr::Identifier(name) => (name.value, vec![]),
+                SQLExpr::Function(function) => {
+                    let args = match function.args {
+                        FunctionArguments::None => vec![],
+                        FunctionArguments::List(list) => list
+                            .args
+                            .into_iter()
+                            .map(|arg| match arg {
+                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                                    SQLExpr::Identifier(ident),
+                                )) => Ok(self.ident_normalizer.normalize(ident)),
+                                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                                    Ok(expr.to_string())
+                                }
+                                other => {
+                                    plan_err!("Unsupported table hint argument {other}")
+                                }
+                            })
+                            .collect::<Result<_>>()?,
+                        FunctionArguments::Subquery(_) => {
+                            return plan_err!("Unsupported table hint {function}");
+                        }
+                    };
+                    (function.name.to_string(), args)
+                }
+                SQLExpr::BinaryOp {
+                    left,
+                    op: BinaryOperator::Eq,
+                    right,
+                } => {
+                    let SQLExpr::Identifier(name) = *left else {
+                        return plan_err!("Unsupported table hint {left} = {right}");
+                    };
+                    let arg = match *right {
+                        SQLExpr::Identifier(ident) => {
+                            self.ident_normalizer.normalize(ident)
+                        }
+                        other => other.to_string(),
+                    };
+                    (name.value, vec![arg])
+                }
+                other => return plan_err!("Unsupported table hint {other}"),
+            };
+            hints
+                .entry(name.to_ascii_uppercase())
+                .or_default()
+                .extend(args);
+        }
+
+        for hint in index_hints {
+            let hint_type = match hint.hint_type {
+                TableIndexHintType::Use => "USE",
+                TableIndexHintType::Ignore => "IGNORE",
+                TableIndexHintType::Force => "FORCE",
+            };
+            let for_clause = match hint.for_clause {
+                None => "",
+                Some(TableIndexHintForClause::Join) => " FOR JOIN",
+                Some(TableIndexHintForClause::OrderBy) => " FOR ORDER BY",
+                Some(TableIndexHintForClause::GroupBy) => " FOR GROUP BY",
+            };
+            hints
+                .entry(format!("{hint_type} INDEX{for_clause}"))
+                .or_default()
+                .extend(
+                    hint.index_names
+                        .into_iter()
+                        .map(|ident| self.ident_normalizer.normalize(ident)),
+                );
+        }
+        Ok(hints)
+    }
+}
+
+/// Attach `hints` to the table scan `plan`, checking that its source
+/// accepts each of them and warning about those it ignores
+pub(super) fn apply_table_hints(
+    plan: LogicalPlan,
+    hints: BTreeMap<String, Vec<String>>,
+    planner_context: &PlannerContext,
+) -> Result<LogicalPlan> {
+    if hints.is_empty() {
+        return Ok(plan);
+    }
+    let LogicalPlan::TableScan(mut scan) = plan else {
+        return plan_err!("Table hints are only supported on tables");
+    };
+    for (name, args) in &hints {
+        match scan.source.supports_hint(name, args)? {
+            TableProviderHintSupport::Unsupported => {
+                return plan_err!(
+                    "Table {} does not support the {name} hint",
+                    scan.table_name
+                );
+            }
+            TableProviderHintSupport::Ignored => {
+                planner_context.add_warning(Diagnostic::new_warning(
+                    format!("Table {} ignores the {name} hint", scan.table_name),
+                    None,
+                ));
+            }
+            TableProviderHintSupport::Exact => {}
+        }
+    }
+    scan.hints = hints;
+    Ok(LogicalPlan::TableScan(scan))
+}
@@ -16,10 +16,15 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{Column, Result, not_impl_err, plan_datafusion_err};
-use datafusion_expr::{JoinType, LogicalPlan, LogicalPlanBuilder};
+use datafusion_common::{
+    Column, DFSchema, Result, not_impl_err, plan_datafusion_err, plan_err,
+};
+use datafusion_expr::{
+    Expr, ExprFunctionExt, JoinType, LogicalPlan, LogicalPlanBuilder, and, lit,
+};
 use sqlparser::ast::{
-    Join, JoinConstraint, JoinOperator, ObjectName, TableFactor, TableWithJoins,
+    BinaryOperator, Expr as SQLExpr, Join, JoinConstraint, JoinOperator, ObjectName,
+    TableFactor, TableWithJoins,
 };
 use std::collections::HashSet;
 
@@ -64,13 +69,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             JoinOperator::Inner(constraint) | JoinOperator::Join(constraint) => {
                 self.parse_join(left, right, constraint, JoinType::Inner, planner_context)
             }
-            JoinOperator::LeftSemi(constraint) => self.parse_join(
-                left,
-                right,
-                constraint,
-                JoinType::LeftSemi,
-                planner_context,
-            ),
+            JoinOperator::LeftSemi(constraint) | JoinOperator::Semi(constraint) => self
+                .parse_join(left, right, constraint, JoinType::LeftSemi, planner_context),
             JoinOperator::RightSemi(constraint) => self.parse_join(
                 left,
                 right,
@@ -78,13 +78,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 JoinType::RightSemi,
                 planner_context,
             ),
-            JoinOperator::LeftAnti(constraint) => self.parse_join(
-                left,
-                right,
-                constraint,
-                JoinType::LeftAnti,
-                planner_context,
-            ),
+            JoinOperator::LeftAnti(constraint) | JoinOperator::Anti(constraint) => self
+                .parse_join(left, right, constraint, JoinType::LeftAnti, planner_context),
             JoinOperator::RightAnti(constraint) => self.parse_join(
                 left,
                 right,
@@ -98,6 +93,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             JoinOperator::CrossJoin(JoinConstraint::None) => {
                 self.parse_cross_join(left, right)
             }
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => self.parse_asof_join(
+                left,
+                right,
+                match_condition,
+                constraint,
+                planner_context,
+            ),
             other => not_impl_err!("Unsupported JOIN operator {other:?}"),
         }
     }
@@ -110,6 +115,139 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         LogicalPlanBuilder::from(left).cross_join(right)?.build()
     }
 
+    /// Plan `left ASOF JOIN right MATCH_CONDITION (l.key >= r.key) [ON ...]`,
+    /// which joins each row of `left` with the row of `right` whose key is
+    /// the closest one satisfying the match condition, or with nulls when
+    /// there is none.
+    ///
+    /// The join is planned as a left join on both conditions that keeps,
+    /// for each row of `left`, only the first match when ordered by the key
+    /// of `right` from the closest:
+    ///
+    /// ```text
+    /// SELECT <left and right columns>
+    /// FROM (
+    ///   SELECT *, row_number() OVER (
+    ///     PARTITION BY left_row ORDER BY r.key DESC) AS rank
+    ///   FROM (SELECT *, row_number() OVER () AS left_row FROM left)
+    ///     LEFT JOIN right ON <ON condition> AND l.key >= r.key
+    /// )
+    /// WHERE rank = 1
+    /// ```
+    fn parse_asof_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        match_condition: SQLExpr,
+        constraint: JoinConstraint,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let Some(row_number) = self.context_provider.get_window_meta("row_number") else {
+            return plan_err!("ASOF JOIN requires the row_number window function");
+        };
+        let join_schema = left.schema().join(right.schema())?;
+        let (right_key, descending) = self.asof_match_key(
+            match_condition.clone(),
+            &left,
+            &right,
+            &join_schema,
+            planner_context,
+        )?;
+        let mut condition =
+            self.sql_to_expr(match_condition, &join_schema, planner_context)?;
+        match constraint {
+            JoinConstraint::On(on) => {
+                let on = self.sql_to_expr(on, &join_schema, planner_context)?;
+                condition = and(on, condition);
+            }
+            JoinConstraint::None => {}
+            _ => return not_impl_err!("ASOF JOIN only supports an ON constraint"),
+        }
+        let output = join_schema.columns().into_iter().map(Expr::Column);
+
+        let left = LogicalPlanBuilder::from(left)
+            .window(vec![row_number.call(vec![])])?
+            .build()?;
+        let left_row = last_column(&left);
+        let joined = LogicalPlanBuilder::from(left)
+            .join_on(right, JoinType::Left, Some(condition))?
+            .build()?;
+        let rank = row_number
+            .call(vec![])
+            .partition_by(vec![left_row])
+            .order_by(vec![right_key.sort(!descending, false)])
+            .build()?;
+        let ranked = LogicalPlanBuilder::from(joined)
+            .window(vec![rank])?
+            .build()?;
+        let rank = last_column(&ranked);
+        LogicalPlanBuilder::from(ranked)
+            .filter(rank.eq(lit(1_u64)))?
+            .project(output)?
+            .build()
+    }
+
+    /// The key of the right side of the match condition of an ASOF JOIN,
+    /// which compares a key of each side, and whether the closest key of the
+    /// right side is the greatest one
+    fn asof_match_key(
+        &self,
+        match_condition: SQLExpr,
+        left: &LogicalPlan,
+        right: &LogicalPlan,
+        join_schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<(Expr, bool)> {
+        let (left_key, op, right_key) = match match_condition {
+            SQLExpr::Nested(inner) => {
+                return self.asof_match_key(
+                    *inner,
+                    left,
+                    right,
+                    join_schema,
+                    planner_context,
+                );
+            }
+            SQLExpr::BinaryOp {
+                left: left_key,
+                op,
+                right: right_key,
+            } => (left_key, op, right_key),
+            _ => {
+                return plan_err!(
+                    "ASOF JOIN MATCH_CONDITION must compare a key of each side with >=, >, <= or <"
+                );
+            }
+        };
+        let greatest = match op {
+            BinaryOperator::Gt | BinaryOperator::GtEq => true,
+            BinaryOperator::Lt | BinaryOperator::LtEq => false,
+            _ => {
+                return plan_err!(
+                    "ASOF JOIN MATCH_CONDITION must compare a key of each side with >=, >, <= or <"
+                );
+            }
+        };
+        let left_key = self.sql_to_expr(*left_key, join_schema, planner_context)?;
+        let right_key = self.sql_to_expr(*right_key, join_schema, planner_context)?;
+        let references = |expr: &Expr, plan: &LogicalPlan| {
+            let columns = expr.column_refs();
+            !columns.is_empty()
+                && columns
+                    .iter()
+                    .all(|column| plan.schema().has_column(column))
+        };
+        if references(&left_key, left) && references(&right_key, right) {
+            Ok((right_key, greatest))
+        } else if references(&left_key, right) && references(&right_key, left) {
+            Ok((left_key, !greatest))
+        } else {
+            plan_err!(
+                "ASOF JOIN MATCH_CONDITION must compare a key of each side with >=, >, <= or <"
+            )
+        }
+    }
+
     fn parse_join(
         &self,
         left: LogicalPlan,
@@ -179,12 +317,25 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 }
 
+/// The last column of `plan`, such as the one added by a window
+fn last_column(plan: &LogicalPlan) -> Expr {
+    let schema = plan.schema();
+    Expr::Column(Column::from(
+        schema.qualified_field(schema.fields().len() - 1),
+    ))
+}
+
 /// Return `true` iff the given [`TableFactor`] is lateral.
 pub(crate) fn is_lateral(factor: &TableFactor) -> bool {
     match factor {
         TableFactor::Derived { lateral, .. } => *lateral,
         TableFactor::Function { lateral, .. } => *lateral,
+        // Table functions may take columns of preceding FROM items as
+        // arguments, with or without the LATERAL keyword
+        TableFactor::Table { args: Some(_), .. } => true,
         TableFactor::UNNEST { .. } => true,
+        // The PASSING clause may reference preceding FROM items
+        TableFactor::XmlTable { .. } => true,
         _ => false,
     }
 }
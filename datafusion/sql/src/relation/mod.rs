@@ -15,12 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
-use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use crate::planner::{ContextProvider, IdentCaseFolding, PlannerContext, SqlToRel};
 
 use arrow::datatypes::Field;
-use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
     Column, DFSchema, Diagnostic, Result, Span, Spans, TableReference, UnnestOptions,
     not_impl_err, plan_err,
@@ -33,15 +34,23 @@ use datafusion_expr::{
     EdgeDirection, EdgePattern, GraphColumn, GraphPattern, GraphPatternElement,
     GraphPatternExpr, GraphTable, JsonTable, JsonTableColumnDef, JsonTableErrorHandling,
     LabelExpression, NodePattern, PathFinding, PathMode, RepetitionQuantifier,
-    RowLimiting, Subquery, SubqueryAlias,
+    RowLimiting, Subquery, SubqueryAlias, XmlNamespace, XmlPassingArgument, XmlTable,
+    XmlTableColumnDef,
 };
-use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, expr::Unnest};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, expr::Unnest, lit};
 use sqlparser::ast::{
-    Expr as SQLExpr, FunctionArg, FunctionArgExpr, FunctionArguments, Ident, Spanned,
-    TableAliasColumnDef, TableFactor,
+    Expr as SQLExpr, FunctionArguments, Ident, Spanned, TableAliasColumnDef, TableFactor,
+    Value, ValueWithSpan,
 };
 
+mod graph_table;
+mod hints;
 mod join;
+mod pivot;
+mod policies;
+mod sample;
+mod table_function;
+mod version;
 
 struct SqlToRelRelationContext<'a, 'b, S: ContextProvider> {
     planner: &'a SqlToRel<'b, S>,
@@ -167,72 +176,6 @@ fn extract_pattern_symbols_recursive(
 }
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
-    /// Creates an augmented schema for MATCH_RECOGNIZE that includes pattern variables
-    /// as valid table qualifiers.
-    ///
-    /// For each pattern variable (e.g., "A", "B"), adds all input columns qualified
-    /// with that pattern variable name. This allows expressions like `A.value` to
-    /// resolve correctly in MEASURES and DEFINE clauses.
-    fn create_match_recognize_schema(
-        &self,
-        input_schema: &Arc<DFSchema>,
-        pattern_var_names: &[String],
-    ) -> Result<Arc<DFSchema>> {
-        let mut qualified_fields: Vec<(Option<TableReference>, Arc<Field>)> = Vec::new();
-
-        // For each pattern variable, add all input columns with that qualifier
-        for pattern_var in pattern_var_names {
-            let pattern_var_ref = TableReference::Bare {
-                table: pattern_var.clone().into(),
-            };
-
-            for field in input_schema.fields() {
-                // Create a qualified field with the pattern variable as the qualifier
-                qualified_fields.push((Some(pattern_var_ref.clone()), field.clone()));
-            }
-        }
-
-        // Also include the original input schema fields (with their original qualifiers)
-        for (qualifier, field) in input_schema.iter() {
-            qualified_fields.push((qualifier.cloned(), field.clone()));
-        }
-
-        // Create new schema with all qualified fields
-        DFSchema::new_with_metadata(qualified_fields, input_schema.metadata().clone())
-            .map(Arc::new)
-    }
-
-    /// Strips pattern variable qualifiers from expressions in MATCH_RECOGNIZE.
-    ///
-    /// Converts references like `A.value` to unqualified `value`, making the expression
-    /// compatible with the input schema validation while preserving the semantic meaning
-    /// for later pattern matching execution.
-    fn strip_pattern_var_qualifiers(
-        &self,
-        expr: Expr,
-        pattern_var_names: &[String],
-    ) -> Expr {
-        expr.transform(|e| {
-            if let Expr::Column(col) = &e {
-                if let Some(qualifier) = &col.relation {
-                    // Check if this qualifier is a pattern variable
-                    if pattern_var_names
-                        .iter()
-                        .any(|pv| qualifier.table() == pv.as_str())
-                    {
-                        // Strip the pattern variable qualifier, making it unqualified
-                        return Ok(Transformed::yes(Expr::Column(
-                            Column::new_unqualified(&col.name),
-                        )));
-                    }
-                }
-            }
-            Ok(Transformed::no(e))
-        })
-        .data()
-        .expect("transform should not fail")
-    }
-
     /// Create a `LogicalPlan` that scans the named relation.
     ///
     /// First tries any registered extension planners. If no extension handles
@@ -241,6 +184,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         &self,
         relation: TableFactor,
         planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let span = self.span_of(&relation);
+        self.create_relation_impl(relation, planner_context)
+            .map_err(|err| self.locate_error(err, span))
+    }
+
+    fn create_relation_impl(
+        &self,
+        relation: TableFactor,
+        planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         let planned_relation =
             match self.create_extension_relation(relation, planner_context)? {
@@ -300,38 +253,39 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 alias,
                 args,
                 only,
+                sample,
+                version,
+                with_hints,
+                index_hints,
                 ..
             } => {
-                if let Some(func_args) = args {
+                let version = version
+                    .map(|version| self.plan_table_version(version, planner_context))
+                    .transpose()?;
+                let hints = self.plan_table_hints(with_hints, index_hints)?;
+                let (plan, alias) = if let Some(func_args) = args {
+                    if version.is_some() {
+                        return not_impl_err!(
+                            "Time travel is not supported for table functions"
+                        );
+                    }
+                    if !hints.is_empty() {
+                        return plan_err!(
+                            "Table hints are not supported for table functions"
+                        );
+                    }
                     let tbl_func_name =
                         name.0.last().unwrap().as_ident().unwrap().to_string();
-                    let args = func_args
-                        .args
-                        .into_iter()
-                        .flat_map(|arg| {
-                            if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg
-                            {
-                                self.sql_expr_to_logical_expr(
-                                    expr,
-                                    &DFSchema::empty(),
-                                    planner_context,
-                                )
-                            } else {
-                                plan_err!("Unsupported function argument type: {}", arg)
-                            }
-                        })
-                        .collect::<Vec<_>>();
-                    let provider = self
-                        .context_provider
-                        .get_table_function_source(&tbl_func_name, args)?;
-                    let mut plan = LogicalPlanBuilder::scan(
+                    let (args, arg_names) = self
+                        .table_function_args_to_exprs(func_args.args, planner_context)?;
+                    let mut plan = self.table_function_to_plan(
+                        &tbl_func_name,
                         TableReference::Bare {
                             table: format!("{tbl_func_name}()").into(),
                         },
-                        provider,
-                        None,
-                    )?
-                    .build()?;
+                        args,
+                        arg_names,
+                    )?;
                     // For single-column table functions with a table alias but no column
                     // aliases, add a projection that renames the column to match the table
                     // alias. PostgreSQL allows using the table alias as a column name for
@@ -351,14 +305,52 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     (plan, alias)
                 } else {
                     // Normalize name and alias
-                    let table_ref = self.object_name_to_table_reference(name)?;
+                    let table_ref = if self.ident_normalizer.case_folding()
+                        == IdentCaseFolding::CaseInsensitive
+                        && let [part] = name.0.as_slice()
+                        && let Some(ident) = part.as_ident()
+                    {
+                        let tables = self.context_provider.table_names();
+                        let names = planner_context
+                            .cte_names()
+                            .chain(tables.iter().map(String::as_str));
+                        TableReference::bare(
+                            self.resolve_ident_case(ident.clone(), names)?,
+                        )
+                    } else {
+                        self.object_name_to_table_reference(name)?
+                    };
                     let table_name = table_ref.to_string();
                     let cte = planner_context.get_cte(&table_name);
+                    if cte.is_some() && version.is_some() {
+                        return plan_err!(
+                            "Time travel is not supported for common table expression {table_name}"
+                        );
+                    }
+                    if cte.is_some() && !hints.is_empty() {
+                        return plan_err!(
+                            "Table hints are not supported for common table expression {table_name}"
+                        );
+                    }
+                    let system_time = match (&cte, &version) {
+                        (None, Some(version)) => self.plan_system_time_version(
+                            &table_ref,
+                            version,
+                            planner_context,
+                        )?,
+                        _ => None,
+                    };
+                    let source = match (&version, &system_time) {
+                        (_, Some((source, _))) => Ok(Arc::clone(source)),
+                        (Some(version), None) => self
+                            .context_provider
+                            .get_table_source_at(table_ref.clone(), version),
+                        (None, None) => {
+                            self.get_table_source(table_ref.clone(), planner_context)
+                        }
+                    };
                     (
-                        match (
-                            cte,
-                            self.context_provider.get_table_source(table_ref.clone()),
-                        ) {
+                        match (cte, source) {
                             (Some(cte_plan), _) => Ok(cte_plan.clone()),
                             (_, Ok(provider)) => {
                                 let plan = LogicalPlanBuilder::scan(
@@ -369,28 +361,61 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 .build()?;
                                 // Preserve the PostgreSQL `FROM ONLY t` modifier on
                                 // the scan so the engine can exclude inheriting
-                                // descendant tables.
-                                if only {
-                                    if let LogicalPlan::TableScan(mut scan) = plan {
-                                        scan.only = true;
-                                        Ok(LogicalPlan::TableScan(scan))
-                                    } else {
-                                        Ok(plan)
+                                // descendant tables, and the version of a snapshot
+                                // served by the provider so that it is displayed
+                                // and unparsed.
+                                let plan = match plan {
+                                    LogicalPlan::TableScan(mut scan) => {
+                                        scan.only = only;
+                                        if system_time.is_none() {
+                                            scan.version = version.clone();
+                                        }
+                                        LogicalPlan::TableScan(scan)
                                     }
-                                } else {
-                                    Ok(plan)
+                                    plan => plan,
+                                };
+                                let plan = hints::apply_table_hints(
+                                    plan,
+                                    hints,
+                                    planner_context,
+                                )?;
+                                let plan = self.apply_security_policies(plan)?;
+                                match system_time {
+                                    Some((_, predicate)) => {
+                                        LogicalPlanBuilder::from(plan)
+                                            .filter(predicate)?
+                                            .build()
+                                    }
+                                    None => Ok(plan),
                                 }
                             }
+                            // Keep the provider's reason when it cannot serve
+                            // the requested snapshot
+                            (None, Err(e)) if version.is_some() => Err(e),
                             (None, Err(e)) => {
-                                let e = e.with_diagnostic(Diagnostic::new_error(
+                                let mut diagnostic = Diagnostic::new_error(
                                     format!("table '{table_ref}' not found"),
                                     Span::try_from_sqlparser_span(relation_span),
-                                ));
-                                Err(e)
+                                );
+                                let table_names = self.context_provider.table_names();
+                                diagnostic.add_did_you_mean(
+                                    table_ref.table(),
+                                    table_names
+                                        .iter()
+                                        .map(String::as_str)
+                                        .chain(planner_context.cte_names()),
+                                );
+                                Err(e.with_diagnostic(diagnostic))
                             }
                         }?,
                         alias,
                     )
+                };
+                match sample {
+                    Some(sample) => {
+                        (self.plan_table_sample(plan, sample, planner_context)?, alias)
+                    }
+                    None => (plan, alias),
                 }
             }
             TableFactor::Derived {
@@ -409,10 +434,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             TableFactor::UNNEST {
                 mut alias,
                 array_exprs,
-                with_offset: false,
-                with_offset_alias: None,
+                with_offset,
+                with_offset_alias,
                 with_ordinality,
             } => {
+                // `WITH OFFSET [AS alias]` is the 0-based counterpart of
+                // `WITH ORDINALITY`, so both share the unnest ordinality column
+                let with_offset = with_offset || with_offset_alias.is_some();
+                if with_offset && with_ordinality {
+                    return plan_err!(
+                        "UNNEST cannot use both WITH ORDINALITY and WITH OFFSET"
+                    );
+                }
                 // Unnest table factor has empty input
                 let schema = DFSchema::empty();
                 let input = LogicalPlanBuilder::empty(true).build()?;
@@ -435,7 +468,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 }
 
                 // Create options with ordinality if requested
-                let options = if with_ordinality {
+                let options = if with_ordinality || with_offset {
                     Some(
                         UnnestOptions::new()
                             .with_preserve_nulls(false)
@@ -445,9 +478,30 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     None
                 };
 
-                let single_unnest_output = !with_ordinality && unnest_exprs.len() == 1;
-                let logical_plan =
+                let single_unnest_output =
+                    !with_ordinality && !with_offset && unnest_exprs.len() == 1;
+                let mut logical_plan =
                     self.try_process_unnest_with_options(input, unnest_exprs, options)?;
+                if with_offset {
+                    let offset_name = with_offset_alias
+                        .map(|alias| self.ident_normalizer.normalize(alias))
+                        .unwrap_or_else(|| "offset".to_string());
+                    let projection = logical_plan
+                        .schema()
+                        .columns()
+                        .into_iter()
+                        .map(|column| {
+                            if column.relation.is_none() && column.name == "ordinality" {
+                                (Expr::Column(column) - lit(1i64)).alias(&offset_name)
+                            } else {
+                                Expr::Column(column)
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    logical_plan = LogicalPlanBuilder::from(logical_plan)
+                        .project(projection)?
+                        .build()?;
+                }
 
                 // PostgreSQL compatibility: for a single-argument UNNEST with an alias but no
                 // explicit column alias list, treat the relation alias as the output column name.
@@ -465,74 +519,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
                 (logical_plan, alias)
             }
-            TableFactor::UNNEST { .. } => {
-                return not_impl_err!(
-                    "UNNEST table factor with offset is not supported yet"
-                );
-            }
             TableFactor::Function {
                 name, args, alias, ..
             } => {
                 let tbl_func_ref = self.object_name_to_table_reference(name)?;
-                let schema = planner_context
-                    .outer_query_schema()
-                    .cloned()
-                    .unwrap_or_else(DFSchema::empty);
-                let func_args = args
-                    .into_iter()
-                    .map(|arg| match arg {
-                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
-                        | FunctionArg::Variadic(FunctionArgExpr::Expr(expr))
-                        | FunctionArg::Named {
-                            arg: FunctionArgExpr::Expr(expr),
-                            ..
-                        } => {
-                            let expr = self.sql_expr_to_logical_expr(
-                                expr,
-                                &schema,
-                                planner_context,
-                            )?;
-                            // A bare column argument can only refer to the
-                            // enclosing (lateral) query, so carry its real
-                            // field so the table function receives a correctly
-                            // typed outer reference.
-                            Ok(match expr {
-                                Expr::Column(col) => {
-                                    match schema.qualified_field_from_column(&col) {
-                                        Ok((_, field)) => Expr::OuterReferenceColumn(
-                                            Arc::clone(field),
-                                            col,
-                                        ),
-                                        Err(_) => Expr::Column(col),
-                                    }
-                                }
-                                other => other,
-                            })
-                        }
-                        _ => plan_err!("Unsupported function argument: {arg:?}"),
-                    })
-                    .collect::<Result<Vec<Expr>>>()?;
-                let provider = self
-                    .context_provider
-                    .get_table_function_source(tbl_func_ref.table(), func_args)?;
-                let plan = if let Some(inline_plan) = provider.get_logical_plan() {
-                    let inline_plan = inline_plan.into_owned();
-                    if inline_plan.all_out_ref_exprs().is_empty() {
-                        LogicalPlanBuilder::scan(
-                            tbl_func_ref.table(),
-                            provider,
-                            None,
-                        )?
-                        .build()?
-                    } else {
-                        LogicalPlanBuilder::new(inline_plan)
-                            .alias(tbl_func_ref.table())?
-                            .build()?
-                    }
-                } else {
-                    LogicalPlanBuilder::scan(tbl_func_ref.table(), provider, None)?
-                        .build()?
-                };
+                let (func_args, arg_names) =
+                    self.table_function_args_to_exprs(args, planner_context)?;
+                let plan = self.table_function_to_plan(
+                    tbl_func_ref.table(),
+                    TableReference::from(tbl_func_ref.table()),
+                    func_args,
+                    arg_names,
+                )?;
                 (plan, alias)
             }
             TableFactor::MatchRecognize {
@@ -594,25 +592,21 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     }
                 }
 
-                // Create an augmented schema that includes pattern variables as qualifiers
-                // This allows expressions like A.value to resolve correctly in MEASURES and DEFINE
-                let augmented_schema =
-                    self.create_match_recognize_schema(input_schema, &pattern_var_names)?;
+                // Pattern variables qualify the input columns in MEASURES and
+                // DEFINE, so that expressions like A.value resolve to the
+                // unqualified input column value
+                let old_pattern_variables =
+                    planner_context.set_pattern_variables(pattern_var_names);
 
-                // Convert measures using the augmented schema, then strip pattern variable qualifiers
                 let measure_exprs: Vec<MeasureExpr> = measures
                     .into_iter()
                     .map(|Measure { expr, alias }| {
-                        let mut converted_expr =
-                            self.sql_to_expr(expr, &augmented_schema, planner_context)?;
-                        // Strip pattern variable qualifiers from the expression
-                        // to make it compatible with input schema validation
-                        converted_expr = self.strip_pattern_var_qualifiers(
-                            converted_expr,
-                            &pattern_var_names,
-                        );
                         Ok(MeasureExpr {
-                            expr: converted_expr,
+                            expr: self.sql_to_expr(
+                                expr,
+                                input_schema,
+                                planner_context,
+                            )?,
                             alias: self.ident_normalizer.normalize(alias),
                         })
                     })
@@ -781,27 +775,23 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     })
                     .collect();
 
-                // Convert symbol definitions using the augmented schema, then strip pattern var qualifiers
+                // Convert symbol definitions
                 let symbol_defs: Vec<SymbolDef> = symbols
                     .into_iter()
                     .map(|SymbolDefinition { symbol, definition }| {
-                        let mut converted_expr = self.sql_to_expr(
-                            definition,
-                            &augmented_schema,
-                            planner_context,
-                        )?;
-                        // Strip pattern variable qualifiers from the expression
-                        converted_expr = self.strip_pattern_var_qualifiers(
-                            converted_expr,
-                            &pattern_var_names,
-                        );
                         Ok(SymbolDef {
                             symbol: self.ident_normalizer.normalize(symbol),
-                            definition: converted_expr,
+                            definition: self.sql_to_expr(
+                                definition,
+                                input_schema,
+                                planner_context,
+                            )?,
                         })
                     })
                     .collect::<Result<Vec<_>>>()?;
 
+                planner_context.set_pattern_variables(old_pattern_variables);
+
                 // Create the MatchRecognize plan
                 let match_recognize_plan = MatchRecognize::try_new(
                     Arc::new(input_plan),
@@ -832,6 +822,22 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 )?;
                 (plan, alias)
             }
+            TableFactor::XmlTable {
+                namespaces,
+                row_expression,
+                passing,
+                columns,
+                alias,
+            } => {
+                let plan = self.plan_xml_table(
+                    namespaces,
+                    row_expression,
+                    passing,
+                    columns,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
             TableFactor::GraphTable {
                 graph_name,
                 match_clause,
@@ -845,27 +851,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             TableFactor::TableFunction { expr, alias } => {
                 if let SQLExpr::Function(func) = expr {
                     let tbl_func_ref = self.object_name_to_table_reference(func.name)?;
-                    let schema = planner_context
-                        .outer_query_schema()
-                        .cloned()
-                        .unwrap_or_else(DFSchema::empty);
-                    let func_args = match func.args {
-                        FunctionArguments::List(list) => list
-                            .args
-                            .into_iter()
-                            .map(|arg| match arg {
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
-                                | FunctionArg::Named {
-                                    arg: FunctionArgExpr::Expr(expr),
-                                    ..
-                                } => self.sql_expr_to_logical_expr(
-                                    expr,
-                                    &schema,
-                                    planner_context,
-                                ),
-                                _ => plan_err!("Unsupported function argument: {arg:?}"),
-                            })
-                            .collect::<Result<Vec<Expr>>>()?,
+                    let args = match func.args {
+                        FunctionArguments::List(list) => list.args,
                         FunctionArguments::None => vec![],
                         other => {
                             return not_impl_err!(
@@ -873,12 +860,28 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                             );
                         }
                     };
-                    let provider = self
+                    let plan = if let Some(ptf) = self
                         .context_provider
-                        .get_table_function_source(tbl_func_ref.table(), func_args)?;
-                    let plan =
-                        LogicalPlanBuilder::scan(tbl_func_ref.table(), provider, None)?
-                            .build()?;
+                        .get_polymorphic_table_function(tbl_func_ref.table())
+                    {
+                        let plan = self.plan_polymorphic_table_function(
+                            ptf,
+                            args,
+                            planner_context,
+                        )?;
+                        LogicalPlanBuilder::from(plan)
+                            .alias(tbl_func_ref.table())?
+                            .build()?
+                    } else {
+                        let (func_args, arg_names) =
+                            self.table_function_args_to_exprs(args, planner_context)?;
+                        self.table_function_to_plan(
+                            tbl_func_ref.table(),
+                            TableReference::from(tbl_func_ref.table()),
+                            func_args,
+                            arg_names,
+                        )?
+                    };
                     (plan, alias)
                 } else {
                     return not_impl_err!(
@@ -886,6 +889,44 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     );
                 }
             }
+            TableFactor::Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let plan = self.plan_pivot(
+                    input,
+                    aggregate_functions,
+                    value_column,
+                    value_source,
+                    default_on_null,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
+            TableFactor::Unpivot {
+                table,
+                value,
+                name,
+                columns,
+                null_inclusion,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let plan = self.plan_unpivot(
+                    input,
+                    value,
+                    name,
+                    columns,
+                    null_inclusion,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
             _ => {
                 return not_impl_err!(
                     "Unsupported ast node {relation:?} in create_relation"
@@ -1121,6 +1162,113 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    /// Plan XMLTABLE table factor (SQL/XML).
+    ///
+    /// XMLTABLE produces a row for each item of the row XQuery expression,
+    /// evaluated against the values of the `PASSING` clause.
+    /// Syntax: XMLTABLE([XMLNAMESPACES(...),] row_expr PASSING ... COLUMNS(...))
+    fn plan_xml_table(
+        &self,
+        namespaces: Vec<sqlparser::ast::XmlNamespaceDefinition>,
+        row_expression: SQLExpr,
+        passing: sqlparser::ast::XmlPassingClause,
+        columns: Vec<sqlparser::ast::XmlTableColumn>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let namespaces = namespaces
+            .into_iter()
+            .map(|namespace| {
+                Ok(XmlNamespace {
+                    uri: xml_string_literal(namespace.uri, "namespace URI")?,
+                    prefix: self.ident_normalizer.normalize(namespace.name),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let row_expression = xml_string_literal(row_expression, "row expression")?;
+
+        // The passed values may reference preceding FROM items, which are
+        // resolved as outer references
+        let empty_schema = DFSchema::empty();
+        let mut names = HashSet::new();
+        let mut has_context_item = false;
+        let mut df_passing = Vec::with_capacity(passing.arguments.len());
+        for argument in passing.arguments {
+            let name = match argument.alias {
+                Some(alias) => {
+                    let name = self.ident_normalizer.normalize(alias);
+                    if !names.insert(name.clone()) {
+                        return plan_err!(
+                            "XMLTABLE passes more than one value named {name}"
+                        );
+                    }
+                    Some(name)
+                }
+                None if has_context_item => {
+                    return plan_err!("XMLTABLE passes more than one context item");
+                }
+                None => {
+                    has_context_item = true;
+                    None
+                }
+            };
+            let expr = self.sql_expr_to_logical_expr(
+                argument.expr,
+                &empty_schema,
+                planner_context,
+            )?;
+            df_passing.push(XmlPassingArgument { expr, name });
+        }
+
+        let mut has_ordinality = false;
+        let mut df_columns = Vec::with_capacity(columns.len());
+        for column in columns {
+            let name = self.ident_normalizer.normalize(column.name);
+            let column = match column.option {
+                sqlparser::ast::XmlTableColumnOption::NamedInfo {
+                    r#type,
+                    path,
+                    default,
+                    nullable,
+                } => {
+                    let field = self.convert_data_type_to_field(&r#type)?;
+                    let path = path
+                        .map(|path| xml_string_literal(path, "column path"))
+                        .transpose()?;
+                    let default = default
+                        .map(|default| {
+                            self.sql_expr_to_logical_expr(
+                                default,
+                                &empty_schema,
+                                planner_context,
+                            )
+                        })
+                        .transpose()?;
+                    XmlTableColumnDef::Path {
+                        name,
+                        data_type: field.data_type().clone(),
+                        path,
+                        default,
+                        nullable,
+                    }
+                }
+                sqlparser::ast::XmlTableColumnOption::ForOrdinality => {
+                    if has_ordinality {
+                        return plan_err!(
+                            "XMLTABLE can have at most one FOR ORDINALITY column"
+                        );
+                    }
+                    has_ordinality = true;
+                    XmlTableColumnDef::Ordinality { name }
+                }
+            };
+            df_columns.push(column);
+        }
+
+        let xml_table =
+            XmlTable::try_new(namespaces, row_expression, df_passing, df_columns)?;
+        Ok(LogicalPlan::XmlTable(xml_table))
+    }
+
     /// Plan GRAPH_TABLE table factor (SQL/PGQ).
     ///
     /// GRAPH_TABLE performs pattern matching on property graphs and returns
@@ -1149,6 +1297,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             .row_limiting
             .map(|rl| self.convert_row_limiting(rl));
 
+        // Calls to PATH_LENGTH(p) refer to the path variables of the patterns,
+        // and calls to ELEMENT_ID(v) to other variables
+        let path_variables = match_clause
+            .patterns
+            .iter()
+            .filter_map(|p| p.path_variable.clone())
+            .map(|v| self.ident_normalizer.normalize(v))
+            .collect::<HashSet<_>>();
+
         // Convert graph patterns
         let patterns = match_clause
             .patterns
@@ -1161,7 +1318,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let empty_schema = DFSchema::empty();
         let where_clause = match_clause
             .where_clause
-            .map(|expr| {
+            .map(|mut expr| {
+                self.resolve_graph_function_calls(&mut expr, &path_variables);
                 self.sql_expr_to_logical_expr(expr, &empty_schema, planner_context)
             })
             .transpose()?;
@@ -1172,7 +1330,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             .map(|cols| {
                 cols.columns
                     .into_iter()
-                    .map(|col| {
+                    .map(|mut col| {
+                        self.resolve_graph_function_calls(&mut col.expr, &path_variables);
                         let expr = self.sql_expr_to_logical_expr(
                             col.expr,
                             &empty_schema,
@@ -1192,6 +1351,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let schema = self.build_graph_table_schema(&columns, &empty_schema)?;
 
         // Create the GraphTable logical plan node
+        let graph = self.context_provider.get_property_graph(&graph_ref);
         let graph_table = GraphTable::try_new(
             graph_ref,
             path_finding,
@@ -1203,7 +1363,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             Arc::new(schema),
         )?;
 
-        Ok(LogicalPlan::GraphTable(graph_table))
+        // Graphs known to the provider are planned as joins of their element
+        // tables; others are left to the engine as a GraphTable node
+        match graph {
+            Some(graph) => self.plan_graph_match(&graph, graph_table),
+            None => Ok(LogicalPlan::GraphTable(graph_table)),
+        }
     }
 
     /// Convert sqlparser path finding to DataFusion PathFinding
@@ -1386,6 +1551,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 .transpose()?,
             direction: self.convert_edge_direction(edge.direction),
             quantifier: edge.quantifier.map(|q| self.convert_quantifier(q)),
+            cost: edge
+                .cost
+                .map(|e| {
+                    self.sql_expr_to_logical_expr(e, &empty_schema, &mut planner_context)
+                })
+                .transpose()?,
         })
     }
 
@@ -1469,3 +1640,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         DFSchema::from_unqualified_fields(fields.into(), HashMap::new())
     }
 }
+
+/// The string of the literal `expr` of XMLTABLE, which must be a character
+/// string literal
+fn xml_string_literal(expr: SQLExpr, what: &str) -> Result<String> {
+    match expr {
+        SQLExpr::Value(ValueWithSpan {
+            value: Value::SingleQuotedString(s) | Value::DoubleQuotedString(s),
+            ..
+        }) => Ok(s),
+        _ => plan_err!("XMLTABLE {what} must be a string literal"),
+    }
+}
@@ -0,0 +1,243 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::{Column, DFSchema, Result, not_impl_err, plan_err};
+use datafusion_expr::utils::expr_to_columns;
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, Union, lit, when};
+use sqlparser::ast::{Expr as SQLExpr, ExprWithAlias, Ident, NullInclusion, PivotValueSource};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plan `input PIVOT (agg [AS a], ... FOR value_column IN (v [AS n], ...))`
+    /// as conditional aggregation:
+    ///
+    /// ```text
+    /// SELECT <other columns>,
+    ///        agg FILTER (WHERE value_column = v) AS n, ...
+    /// FROM input
+    /// GROUP BY <other columns>
+    /// ```
+    ///
+    /// where the other columns are every input column not referenced by
+    /// the aggregates or the value column. A single unaliased aggregate
+    /// produces one column per value, named after the value; otherwise
+    /// columns are named `<value>_<aggregate alias or name>`.
+    pub(super) fn plan_pivot(
+        &self,
+        input: LogicalPlan,
+        aggregate_functions: Vec<ExprWithAlias>,
+        value_column: Vec<SQLExpr>,
+        value_source: PivotValueSource,
+        default_on_null: Option<SQLExpr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let schema = input.schema().as_ref().clone();
+
+        let mut value_columns = value_column.into_iter();
+        let (Some(value_column), None) = (value_columns.next(), value_columns.next())
+        else {
+            return not_impl_err!("PIVOT on multiple value columns is not supported");
+        };
+        let value_column = self.sql_to_expr(value_column, &schema, planner_context)?;
+
+        let values = match value_source {
+            PivotValueSource::List(values) => values,
+            PivotValueSource::Any(_) => {
+                return not_impl_err!("PIVOT ... IN (ANY) is not supported");
+            }
+            PivotValueSource::Subquery(_) => {
+                return not_impl_err!("PIVOT ... IN (<subquery>) is not supported");
+            }
+        };
+        if values.is_empty() {
+            return plan_err!("PIVOT requires at least one value");
+        }
+        let values = values
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let expr = self.sql_to_expr(expr, &DFSchema::empty(), planner_context)?;
+                let name = match (alias, &expr) {
+                    (Some(alias), _) => self.ident_normalizer.normalize(alias),
+                    (None, Expr::Literal(value, _)) => value.to_string(),
+                    (None, expr) => expr.schema_name().to_string(),
+                };
+                Ok((expr, name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let single_unaliased_aggregate =
+            aggregate_functions.len() == 1 && aggregate_functions[0].alias.is_none();
+        let aggregates = aggregate_functions
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let Expr::AggregateFunction(aggregate) =
+                    self.sql_to_expr(expr, &schema, planner_context)?
+                else {
+                    return plan_err!("PIVOT expects aggregate functions");
+                };
+                let name = match alias {
+                    Some(alias) => self.ident_normalizer.normalize(alias),
+                    None => aggregate.func.name().to_string(),
+                };
+                Ok((aggregate, name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Columns feeding the aggregates and the pivot column are consumed;
+        // every other input column becomes a grouping key
+        let mut consumed = HashSet::new();
+        expr_to_columns(&value_column, &mut consumed)?;
+        for (aggregate, _) in &aggregates {
+            for arg in &aggregate.params.args {
+                expr_to_columns(arg, &mut consumed)?;
+            }
+        }
+        let group_by = schema
+            .columns()
+            .into_iter()
+            .filter(|column| !consumed.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let mut pivot_exprs = Vec::with_capacity(values.len() * aggregates.len());
+        let mut pivot_names = Vec::with_capacity(values.len() * aggregates.len());
+        for (value, value_name) in &values {
+            for (aggregate, aggregate_name) in &aggregates {
+                let name = if single_unaliased_aggregate {
+                    value_name.clone()
+                } else {
+                    format!("{value_name}_{aggregate_name}")
+                };
+                let condition = value_column.clone().eq(value.clone());
+                let mut aggregate = aggregate.clone();
+                aggregate.params.filter = Some(Box::new(match aggregate.params.filter {
+                    Some(filter) => (*filter).and(condition),
+                    None => condition,
+                }));
+                pivot_exprs.push(Expr::AggregateFunction(aggregate).alias(&name));
+                pivot_names.push(name);
+            }
+        }
+
+        let plan = LogicalPlanBuilder::from(input)
+            .aggregate(group_by.clone(), pivot_exprs)?
+            .build()?;
+        let Some(default_on_null) = default_on_null else {
+            return Ok(plan);
+        };
+
+        // DEFAULT ON NULL (<expr>) replaces the NULL produced for groups
+        // that have no row for a value
+        let default_on_null =
+            self.sql_to_expr(default_on_null, &DFSchema::empty(), planner_context)?;
+        let mut projection = group_by;
+        for name in pivot_names {
+            let column = Expr::Column(Column::from_name(&name));
+            projection.push(
+                when(column.clone().is_null(), default_on_null.clone())
+                    .otherwise(column)?
+                    .alias(name),
+            );
+        }
+        LogicalPlanBuilder::from(plan).project(projection)?.build()
+    }
+
+    /// Plan `input UNPIVOT [{INCLUDE | EXCLUDE} NULLS] (value FOR name IN (c1, c2, ...))`
+    /// as a union with one branch per unpivoted column:
+    ///
+    /// ```text
+    /// SELECT <other columns>, 'c1' AS name, c1 AS value FROM input WHERE c1 IS NOT NULL
+    /// UNION ALL
+    /// SELECT <other columns>, 'c2' AS name, c2 AS value FROM input WHERE c2 IS NOT NULL
+    /// ...
+    /// ```
+    ///
+    /// The `IS NOT NULL` filters are omitted for `INCLUDE NULLS`; `EXCLUDE
+    /// NULLS` is the default. A column alias (`c1 AS a`) replaces the column
+    /// name in the `name` column.
+    pub(super) fn plan_unpivot(
+        &self,
+        input: LogicalPlan,
+        value: SQLExpr,
+        name: Ident,
+        columns: Vec<ExprWithAlias>,
+        null_inclusion: Option<NullInclusion>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let schema = input.schema().as_ref().clone();
+        let value_name = match value {
+            SQLExpr::Identifier(ident) => self.ident_normalizer.normalize(ident),
+            other => {
+                return not_impl_err!("UNPIVOT value must be a single column name, got {other}");
+            }
+        };
+        let name_name = self.ident_normalizer.normalize(name);
+        if columns.is_empty() {
+            return plan_err!("UNPIVOT requires at least one column");
+        }
+
+        let columns = columns
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let Expr::Column(column) =
+                    self.sql_to_expr(expr, &schema, planner_context)?
+                else {
+                    return plan_err!("UNPIVOT expects column references in its IN list");
+                };
+                let label = match alias {
+                    Some(alias) => self.ident_normalizer.normalize(alias),
+                    None => column.name.clone(),
+                };
+                Ok((column, label))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let unpivoted = columns
+            .iter()
+            .map(|(column, _)| column.clone())
+            .collect::<HashSet<_>>();
+        let others = schema
+            .columns()
+            .into_iter()
+            .filter(|column| !unpivoted.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let include_nulls = matches!(null_inclusion, Some(NullInclusion::IncludeNulls));
+        let mut branches = columns
+            .into_iter()
+            .map(|(column, label)| {
+                let mut builder = LogicalPlanBuilder::from(input.clone());
+                if !include_nulls {
+                    builder = builder.filter(Expr::Column(column.clone()).is_not_null())?;
+                }
+                let mut projection = others.clone();
+                projection.push(lit(label).alias(&name_name));
+                projection.push(Expr::Column(column).alias(&value_name));
+                builder.project(projection)?.build().map(Arc::new)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if branches.len() == 1 {
+            return Ok(Arc::unwrap_or_clone(branches.remove(0)));
+        }
+        Union::try_new_with_loose_types(branches).map(LogicalPlan::Union)
+    }
+}
@@ -0,0 +1,146 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{Column, DFSchema, Result, plan_err};
+use datafusion_expr::utils::conjunction;
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, TableScan};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Mask the columns and filter the rows of the scans of `plan`, a table or
+    /// an expanded view, by the security policies of their tables
+    pub(crate) fn apply_security_policies(
+        &self,
+        plan: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        plan.transform_up_with_subqueries(|plan| match plan {
+            // The scans of a view may already be secured by the policies, if
+            // the view was planned by this planner
+            LogicalPlan::TableScan(scan)
+                if scan.policies.is_empty() && scan.masks.is_empty() =>
+            {
+                self.secure_scan(scan)
+            }
+            plan => Ok(Transformed::no(plan)),
+        })
+        .map(|transformed| transformed.data)
+    }
+
+    /// Filter `scan` by the predicates of the row-level security policies of
+    /// its table and replace its masked columns by their masks, listing both
+    /// on the scan
+    fn secure_scan(&self, scan: TableScan) -> Result<Transformed<LogicalPlan>> {
+        // The policies and masks are those of the table, however it is named
+        let table = self.resolve_table_reference(scan.table_name.clone());
+        let policies = self.context_provider.get_row_security_policies(&table);
+        let masks = self.context_provider.get_column_masks(&table);
+        if policies.is_empty() && masks.is_empty() {
+            return Ok(Transformed::no(LogicalPlan::TableScan(scan)));
+        }
+
+        // The predicates and masks may reference the columns that the scan of
+        // a view does not project
+        let TableScan {
+            table_name,
+            source,
+            projection,
+            filters,
+            fetch,
+            row_lock,
+            only,
+            version,
+            hints,
+            ..
+        } = scan;
+        let mut scan = TableScan::try_new(table_name, source, None, filters, fetch)?;
+        scan.row_lock = row_lock;
+        scan.only = only;
+        scan.version = version;
+        scan.hints = hints;
+
+        let schema = scan.projected_schema.as_ref();
+        let predicates = policies
+            .iter()
+            .map(|policy| {
+                self.security_expr(&policy.predicate, schema).map_err(|e| {
+                    e.context(format!(
+                        "Invalid predicate of row security policy {} of table {}",
+                        policy.name, scan.table_name
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut masked = vec![None; schema.fields().len()];
+        for mask in &masks {
+            let Some(i) = schema.index_of_column_by_name(None, &mask.column) else {
+                return plan_err!(
+                    "Masked column {} not found in table {}",
+                    mask.column,
+                    scan.table_name
+                );
+            };
+            let expr = self.security_expr(&mask.expression, schema).map_err(|e| {
+                e.context(format!(
+                    "Invalid mask of column {} of table {}",
+                    mask.column, scan.table_name
+                ))
+            })?;
+            masked[i] = Some(expr);
+        }
+
+        // The masks take the place of their columns, so that the original
+        // values are not available above the scan
+        let columns = (!masks.is_empty() || projection.is_some()).then(|| {
+            let indices = projection.unwrap_or_else(|| (0..masked.len()).collect());
+            indices
+                .into_iter()
+                .map(|i| {
+                    let (qualifier, field) = schema.qualified_field(i);
+                    match masked[i].take() {
+                        Some(expr) => {
+                            expr.alias_qualified(qualifier.cloned(), field.name())
+                        }
+                        None => Expr::Column(Column::from((qualifier, field))),
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        scan.policies = policies.into_iter().map(|policy| policy.name).collect();
+        scan.masks = masks.into_iter().map(|mask| mask.column).collect();
+
+        let mut builder = LogicalPlanBuilder::from(LogicalPlan::TableScan(scan));
+        if let Some(predicate) = conjunction(predicates) {
+            builder = builder.filter(predicate)?;
+        }
+        if let Some(columns) = columns {
+            builder = builder.project(columns)?;
+        }
+        builder.build().map(Transformed::yes)
+    }
+
+    /// Plan the SQL expression `sql` of a security policy against the columns
+    /// of the scanned table
+    fn security_expr(&self, sql: &str, schema: &DFSchema) -> Result<Expr> {
+        let expr = Parser::new(&GenericDialect {})
+            .try_with_sql(sql)?
+            .parse_expr()?;
+        self.sql_to_expr(expr, schema, &mut PlannerContext::new())
+    }
+}
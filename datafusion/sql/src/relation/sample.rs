@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use arrow::datatypes::DataType;
+use datafusion_common::{DFSchema, Result, ScalarValue, not_impl_err, plan_err};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, SampleMethod, SampleSize};
+use sqlparser::ast::{
+    TableSample, TableSampleKind, TableSampleMethod, TableSampleUnit, Value,
+};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Wrap `plan` in a [`LogicalPlan::Sample`] for a `TABLESAMPLE` clause.
+    ///
+    /// `BERNOULLI`/`ROW` and `SYSTEM`/`BLOCK` are treated as synonyms. When no
+    /// method is given, rows are sampled independently (`BERNOULLI`), and when
+    /// no unit is given the quantity is a percentage, as in the SQL standard.
+    pub(super) fn plan_table_sample(
+        &self,
+        plan: LogicalPlan,
+        sample: TableSampleKind,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let sample: TableSample = match sample {
+            TableSampleKind::BeforeTableAlias(sample)
+            | TableSampleKind::AfterTableAlias(sample) => *sample,
+        };
+        if sample.bucket.is_some() {
+            return not_impl_err!("TABLESAMPLE BUCKET is not supported");
+        }
+        if sample.offset.is_some() {
+            return not_impl_err!("TABLESAMPLE OFFSET is not supported");
+        }
+        let Some(quantity) = sample.quantity else {
+            return plan_err!("TABLESAMPLE requires a sample size");
+        };
+
+        let method = match sample.name {
+            None | Some(TableSampleMethod::Bernoulli) | Some(TableSampleMethod::Row) => {
+                SampleMethod::Bernoulli
+            }
+            Some(TableSampleMethod::System) | Some(TableSampleMethod::Block) => {
+                SampleMethod::System
+            }
+        };
+
+        let value = match self.sql_expr_to_logical_expr(
+            quantity.value,
+            &DFSchema::empty(),
+            planner_context,
+        )? {
+            Expr::Literal(value, _) if !value.is_null() => value,
+            other => {
+                return plan_err!("TABLESAMPLE size must be a numeric literal, got {other}");
+            }
+        };
+        let size = match quantity.unit {
+            Some(TableSampleUnit::Rows) => match value.cast_to(&DataType::UInt64) {
+                Ok(ScalarValue::UInt64(Some(rows))) => SampleSize::Rows(rows),
+                _ => {
+                    return plan_err!(
+                        "TABLESAMPLE row count must be a non-negative integer, got {value}"
+                    );
+                }
+            },
+            None | Some(TableSampleUnit::Percent) => {
+                if !value.data_type().is_numeric() {
+                    return plan_err!(
+                        "TABLESAMPLE percentage must be numeric, got {value}"
+                    );
+                }
+                SampleSize::Percent(value.cast_to(&DataType::Float64)?)
+            }
+        };
+
+        let seed = match sample.seed {
+            None => None,
+            Some(seed) => match &seed.value {
+                Value::Number(n, _) => match n.parse::<u64>() {
+                    Ok(seed) => Some(seed),
+                    Err(_) => {
+                        return plan_err!(
+                            "TABLESAMPLE seed must be a non-negative integer, got {n}"
+                        );
+                    }
+                },
+                other => {
+                    return plan_err!(
+                        "TABLESAMPLE seed must be a non-negative integer, got {other}"
+                    );
+                }
+            },
+        };
+
+        LogicalPlanBuilder::from(plan)
+            .sample(method, size, seed)?
+            .build()
+    }
+}
@@ -0,0 +1,158 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{DFSchema, Result, TableReference, plan_err};
+use datafusion_expr::{
+    Expr, LogicalPlan, LogicalPlanBuilder, PolymorphicTableFunction,
+    PolymorphicTableFunctionImpl, TableFunctionArgument,
+};
+use sqlparser::ast::{Expr as SQLExpr, FunctionArg, FunctionArgExpr};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plan the arguments of a table function call, returning each argument
+    /// along with its name for `name => value` arguments.
+    ///
+    /// Table functions are implicitly lateral: arguments may refer to
+    /// columns of preceding `FROM` items or of enclosing queries. Such
+    /// columns are passed to the function as outer references carrying
+    /// their real field, so the function sees correctly typed arguments.
+    pub(crate) fn table_function_args_to_exprs(
+        &self,
+        args: Vec<FunctionArg>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<(Vec<Expr>, Vec<Option<String>>)> {
+        let schema = planner_context
+            .outer_query_schema()
+            .cloned()
+            .unwrap_or_else(DFSchema::empty);
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                let (expr, name) = self.split_table_function_arg(arg)?;
+                let expr =
+                    self.sql_expr_to_logical_expr(expr, &schema, planner_context)?;
+                let expr = expr
+                    .transform_up(|expr| match expr {
+                        Expr::Column(col) => {
+                            match schema.qualified_field_from_column(&col) {
+                                Ok((_, field)) => Ok(Transformed::yes(
+                                    Expr::OuterReferenceColumn(Arc::clone(field), col),
+                                )),
+                                Err(_) => Ok(Transformed::no(Expr::Column(col))),
+                            }
+                        }
+                        other => Ok(Transformed::no(other)),
+                    })
+                    .data()?;
+                Ok((expr, name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(args.into_iter().unzip())
+    }
+
+    /// Resolve table function `func_name` and plan a call to it.
+    ///
+    /// When the arguments reference outer columns, the function must expose
+    /// a logical plan that uses them (see [`TableSource::get_logical_plan`]);
+    /// that plan is inlined so the correlation survives into the enclosing
+    /// lateral join. Otherwise the call becomes a scan of the function's
+    /// source named `scan_name`.
+    ///
+    /// [`TableSource::get_logical_plan`]: datafusion_expr::TableSource::get_logical_plan
+    pub(crate) fn table_function_to_plan(
+        &self,
+        func_name: &str,
+        scan_name: TableReference,
+        args: Vec<Expr>,
+        arg_names: Vec<Option<String>>,
+    ) -> Result<LogicalPlan> {
+        let correlated = args.iter().any(|arg| arg.contains_outer());
+        let provider = self
+            .context_provider
+            .get_table_function_source_with_names(func_name, args, arg_names)?;
+        if let Some(inline_plan) = provider.get_logical_plan() {
+            let inline_plan = inline_plan.into_owned();
+            if !inline_plan.all_out_ref_exprs().is_empty() {
+                return LogicalPlanBuilder::new(inline_plan)
+                    .alias(scan_name)?
+                    .build();
+            }
+        }
+        if correlated {
+            return plan_err!(
+                "Table function {func_name} does not support arguments that reference preceding FROM items"
+            );
+        }
+        LogicalPlanBuilder::scan(scan_name, provider, None)?.build()
+    }
+
+    /// Plan a call to a polymorphic table function, `TABLE(func(TABLE t, ...))`.
+    ///
+    /// Table arguments, `TABLE t` or a parenthesized query, reach the planner
+    /// as subqueries and become the inputs of the call. Other arguments are
+    /// scalar and cannot reference columns. The function's describe phase
+    /// runs here and fixes the output schema.
+    pub(super) fn plan_polymorphic_table_function(
+        &self,
+        func: Arc<dyn PolymorphicTableFunctionImpl>,
+        args: Vec<FunctionArg>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let mut arg_names = Vec::with_capacity(args.len());
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                let (expr, name) = self.split_table_function_arg(arg)?;
+                arg_names.push(name);
+                Ok(match expr {
+                    SQLExpr::Subquery(query) => TableFunctionArgument::Table(Arc::new(
+                        self.query_to_plan(*query, planner_context)?,
+                    )),
+                    expr => TableFunctionArgument::Scalar(self.sql_to_expr(
+                        expr,
+                        &DFSchema::empty(),
+                        planner_context,
+                    )?),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        PolymorphicTableFunction::try_new(func, args, arg_names)
+            .map(LogicalPlan::PolymorphicTableFunction)
+    }
+
+    /// Split a table function argument into its expression and, for
+    /// `name => value` arguments, its normalized name
+    fn split_table_function_arg(
+        &self,
+        arg: FunctionArg,
+    ) -> Result<(SQLExpr, Option<String>)> {
+        match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+            | FunctionArg::Variadic(FunctionArgExpr::Expr(expr)) => Ok((expr, None)),
+            FunctionArg::Named {
+                name,
+                arg: FunctionArgExpr::Expr(expr),
+                ..
+            } => Ok((expr, Some(self.ident_normalizer.normalize(name)))),
+            _ => plan_err!("Unsupported function argument: {arg:?}"),
+        }
+    }
+}
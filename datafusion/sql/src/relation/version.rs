@@ -0,0 +1,147 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::{
+    Column, DFSchema, Result, TableReference, not_impl_err, plan_err,
+};
+use datafusion_expr::planner::{
+    SYSTEM_TIME_METADATA_KEY, SYSTEM_TIME_ROW_END, SYSTEM_TIME_ROW_START, SnapshotPoint,
+    TableVersion,
+};
+use datafusion_expr::{Expr, TableSource, and};
+use sqlparser::ast::{self, Expr as SQLExpr, FunctionArg, FunctionArgExpr};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plan the version clause of a time travel table reference.
+    ///
+    /// The version expression cannot refer to columns, so it is planned
+    /// against an empty schema.
+    pub(crate) fn plan_table_version(
+        &self,
+        version: ast::TableVersion,
+        planner_context: &mut PlannerContext,
+    ) -> Result<TableVersion> {
+        match version {
+            ast::TableVersion::ForSystemTimeAsOf(expr) => {
+                let expr = self.sql_to_expr(expr, &DFSchema::empty(), planner_context)?;
+                Ok(TableVersion::AsOf(expr))
+            }
+            ast::TableVersion::Function(clause) => {
+                self.plan_snapshot_clause(clause, planner_context)
+            }
+        }
+    }
+
+    /// Plan `FOR SYSTEM_TIME AS OF <point>` over a system-versioned table,
+    /// which keeps its history as row versions that are current from their
+    /// `ROW START` column up to, but excluding, their `ROW END` column.
+    ///
+    /// Returns the source of the table and the predicate selecting the row
+    /// versions current at the point, or `None` for the other tables, whose
+    /// history is served by [`ContextProvider::get_table_source_at`].
+    pub(crate) fn plan_system_time_version(
+        &self,
+        table_ref: &TableReference,
+        version: &TableVersion,
+        planner_context: &PlannerContext,
+    ) -> Result<Option<(Arc<dyn TableSource>, Expr)>> {
+        let TableVersion::AsOf(point) = version else {
+            return Ok(None);
+        };
+        let Ok(source) = self.get_table_source(table_ref.clone(), planner_context) else {
+            return Ok(None);
+        };
+        let schema = source.schema();
+        let period_column = |bound: &str| {
+            schema
+                .fields()
+                .iter()
+                .find(|field| {
+                    field
+                        .metadata()
+                        .get(SYSTEM_TIME_METADATA_KEY)
+                        .map(String::as_str)
+                        == Some(bound)
+                })
+                .map(|field| {
+                    Expr::Column(Column::new(Some(table_ref.clone()), field.name()))
+                })
+        };
+        let (Some(row_start), Some(row_end)) = (
+            period_column(SYSTEM_TIME_ROW_START),
+            period_column(SYSTEM_TIME_ROW_END),
+        ) else {
+            return Ok(None);
+        };
+        let predicate = and(row_start.lt_eq(point.clone()), row_end.gt(point.clone()));
+        Ok(Some((source, predicate)))
+    }
+
+    /// Plan `AT(<kind> => <expr>)` / `BEFORE(<kind> => <expr>)`, which the
+    /// parser hands over as a function call
+    fn plan_snapshot_clause(
+        &self,
+        clause: SQLExpr,
+        planner_context: &mut PlannerContext,
+    ) -> Result<TableVersion> {
+        let SQLExpr::Function(function) = &clause else {
+            return not_impl_err!("Table version clause {clause} is not supported");
+        };
+        let clause_name = function.name.to_string().to_ascii_uppercase();
+        if clause_name != "AT" && clause_name != "BEFORE" {
+            return not_impl_err!("Table version clause {clause} is not supported");
+        }
+        let args = match &function.args {
+            ast::FunctionArguments::List(list) => list.args.as_slice(),
+            _ => &[],
+        };
+        let [
+            FunctionArg::Named {
+                name,
+                arg: FunctionArgExpr::Expr(arg),
+                ..
+            },
+        ] = args
+        else {
+            return plan_err!(
+                "{clause_name} expects a single argument such as TIMESTAMP => <expr>, got {clause}"
+            );
+        };
+
+        let expr =
+            self.sql_to_expr(arg.clone(), &DFSchema::empty(), planner_context)?;
+        let point = match name.value.to_ascii_uppercase().as_str() {
+            "TIMESTAMP" => SnapshotPoint::Timestamp(expr),
+            "OFFSET" => SnapshotPoint::Offset(expr),
+            "STATEMENT" => SnapshotPoint::Statement(expr),
+            "STREAM" => SnapshotPoint::Stream(expr),
+            other => {
+                return plan_err!(
+                    "Unknown {clause_name} argument {other}, expected one of TIMESTAMP, OFFSET, STATEMENT or STREAM"
+                );
+            }
+        };
+        Ok(if clause_name == "AT" {
+            TableVersion::At(point)
+        } else {
+            TableVersion::Before(point)
+        })
+    }
+}
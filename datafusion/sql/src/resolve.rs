@@ -51,6 +51,9 @@ struct RelationVisitor {
     relations: BTreeSet<ObjectName>,
     all_ctes: BTreeSet<ObjectName>,
     ctes_in_scope: Vec<ObjectName>,
+    /// The property graphs queried by `GRAPH_TABLE`, whose element tables
+    /// are only known to the catalog
+    graphs: BTreeSet<ObjectName>,
 }
 
 impl RelationVisitor {
@@ -99,14 +102,49 @@ impl Visitor for RelationVisitor {
         ControlFlow::Continue(())
     }
 
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<()> {
+        if let TableFactor::GraphTable { graph_name, .. } = table_factor {
+            self.graphs.insert(graph_name.clone());
+        }
+        ControlFlow::Continue(())
+    }
+
     fn pre_visit_statement(&mut self, statement: &Statement) -> ControlFlow<()> {
-        if let Statement::ShowCreate {
-            obj_type: ShowCreateObject::Table | ShowCreateObject::View,
-            obj_name,
+        match statement {
+            Statement::ShowCreate {
+                obj_type: ShowCreateObject::Table | ShowCreateObject::View,
+                obj_name,
+                ..
+            } => self.insert_relation(obj_name),
+            Statement::ExplainTable { table_name, .. } => {
+                self.insert_relation(table_name)
+            }
+            Statement::ShowColumns { show_options, .. } => {
+                if let Some(ShowStatementIn {
+                    parent_name: Some(table_name),
+                    ..
+                }) = &show_options.show_in
+                {
+                    self.insert_relation(table_name)
+                }
+            }
+            // The element tables of the graph are checked when planning it
+            Statement::CreatePropertyGraph(graph) => {
+                for vertex in &graph.vertex_tables {
+                    self.insert_relation(&vertex.table);
+                }
+                for edge in &graph.edge_tables {
+                    self.insert_relation(&edge.table);
+                }
+            }
+            _ => {}
+        }
+        if let Statement::CreateTable(CreateTable {
+            clone: Some(source),
             ..
-        } = statement
+        }) = statement
         {
-            self.insert_relation(obj_name)
+            self.insert_relation(source)
         }
 
         // SHOW statements will later be rewritten into a SELECT from the information_schema
@@ -152,8 +190,17 @@ fn visit_statement(statement: &DFStatement, visitor: &mut RelationVisitor) {
         DFStatement::CopyFrom(CopyFromStatement { table_name, .. }) => {
             visitor.insert_relation(table_name);
         }
+        DFStatement::PortionOf(statement) => {
+            visitor.insert_relation(&statement.table_name);
+            for expr in statement.exprs() {
+                let _ = expr.visit(visitor);
+            }
+        }
         DFStatement::Explain(explain) => visit_statement(&explain.statement, visitor),
-        DFStatement::Reset(_) => {}
+        DFStatement::Reset(_)
+        | DFStatement::CreateUser(_)
+        | DFStatement::AlterUser(_)
+        | DFStatement::DropUser(_) => {}
     }
 }
 
@@ -195,27 +242,58 @@ pub fn resolve_table_references(
     statement: &crate::parser::Statement,
     enable_ident_normalization: bool,
 ) -> datafusion_common::Result<(Vec<TableReference>, Vec<TableReference>)> {
-    let mut visitor = RelationVisitor {
-        relations: BTreeSet::new(),
-        all_ctes: BTreeSet::new(),
-        ctes_in_scope: vec![],
-    };
+    let (relations, ctes) = resolve_object_names(statement);
 
-    visit_statement(statement, &mut visitor);
-
-    let table_refs = visitor
-        .relations
+    let table_refs = relations
         .into_iter()
         .map(|x| object_name_to_table_reference(x, enable_ident_normalization))
         .collect::<datafusion_common::Result<_>>()?;
-    let ctes = visitor
-        .all_ctes
+    let ctes = ctes
         .into_iter()
         .map(|x| object_name_to_table_reference(x, enable_ident_normalization))
         .collect::<datafusion_common::Result<_>>()?;
     Ok((table_refs, ctes))
 }
 
+/// Collects the names of the tables and views referenced in the SQL
+/// statement, and the names of its CTEs, as written
+pub(crate) fn resolve_object_names(
+    statement: &crate::parser::Statement,
+) -> (BTreeSet<ObjectName>, BTreeSet<ObjectName>) {
+    let visitor = collect_object_names(|visitor| visit_statement(statement, visitor));
+    (visitor.relations, visitor.all_ctes)
+}
+
+/// Collects the names of the property graphs queried by `GRAPH_TABLE` in the
+/// SQL statement, as written
+pub(crate) fn resolve_graph_names(
+    statement: &crate::parser::Statement,
+) -> BTreeSet<ObjectName> {
+    collect_object_names(|visitor| visit_statement(statement, visitor)).graphs
+}
+
+/// Collects the names of the tables and views referenced in the SQL
+/// statement, as written
+pub(crate) fn resolve_sql_object_names(statement: &Statement) -> BTreeSet<ObjectName> {
+    collect_object_names(|visitor| {
+        let _ = statement.visit(visitor);
+    })
+    .relations
+}
+
+fn collect_object_names(visit: impl FnOnce(&mut RelationVisitor)) -> RelationVisitor {
+    let mut visitor = RelationVisitor {
+        relations: BTreeSet::new(),
+        all_ctes: BTreeSet::new(),
+        ctes_in_scope: vec![],
+        graphs: BTreeSet::new(),
+    };
+
+    visit(&mut visitor);
+
+    visitor
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
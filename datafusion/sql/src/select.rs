@@ -20,7 +20,7 @@ use std::ops::ControlFlow;
 use std::sync::Arc;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use crate::query::to_order_by_exprs_with_select;
+use crate::query::to_order_by_exprs;
 use crate::utils::{
     CheckColumnsMustReferenceAggregatePurpose, CheckColumnsSatisfyExprsPurpose,
     check_columns_satisfy_exprs, extract_aliases, rebase_expr, resolve_aliases_to_exprs,
@@ -29,9 +29,9 @@ use crate::utils::{
 
 use datafusion_common::error::DataFusionErrorBuilder;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
-use datafusion_common::{Column, DFSchema, RecursionUnnestOption, UnnestOptions};
+use datafusion_common::{Column, RecursionUnnestOption, TableReference, UnnestOptions};
 use datafusion_common::{Result, not_impl_err, plan_err};
-use datafusion_expr::expr::{Alias, PlannedReplaceSelectItem, WildcardOptions};
+use datafusion_expr::expr::{PlannedReplaceSelectItem, WildcardOptions};
 use datafusion_expr::expr_rewriter::{
     normalize_col, normalize_col_with_schemas_and_ambiguity_check, normalize_sorts,
 };
@@ -46,11 +46,12 @@ use datafusion_expr::{
 
 use indexmap::IndexMap;
 use sqlparser::ast::{
-    AttachedToken, Distinct, Expr as SQLExpr, FunctionArg, FunctionArgExpr,
-    FunctionArguments, GroupByExpr, Ident, Join, JoinConstraint, JoinOperator,
-    NamedWindowExpr, ObjectName, OrderBy, Query as SQLQuery, SelectFlavor,
-    SelectItemQualifiedWildcardKind, SetExpr, TableAlias, TableFactor,
-    WildcardAdditionalOptions, WindowType, visit_expressions_mut,
+    AttachedToken, Distinct, ExceptSelectItem, Expr as SQLExpr, FunctionArg,
+    FunctionArgExpr, FunctionArguments, GroupByExpr, Ident, Join, JoinConstraint,
+    JoinOperator, NamedWindowExpr, ObjectName, OrderBy, Query as SQLQuery,
+    ReplaceSelectElement, SelectFlavor, SelectItemQualifiedWildcardKind, SetExpr,
+    TableAlias, TableFactor, WildcardAdditionalOptions, WindowSpec, WindowType,
+    visit_expressions_mut,
 };
 use sqlparser::ast::{NamedWindowDefinition, Select, SelectItem, TableWithJoins};
 
@@ -103,27 +104,47 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             (plan, empty_from)
         };
 
+        // Errors of independent clauses, when errors are collected
+        let mut errors = DataFusionErrorBuilder::new();
+
         // Process `where` clause
-        let base_plan = self.plan_selection(select.selection, plan, planner_context)?;
+        let base_plan = if self.options.collect_errors {
+            // A WHERE clause does not change the columns of the plan, so the
+            // rest of the query can be planned without it
+            self.plan_selection(select.selection, plan.clone(), planner_context)
+                .unwrap_or_else(|err| {
+                    errors.add_error(err);
+                    plan
+                })
+        } else {
+            self.plan_selection(select.selection, plan, planner_context)?
+        };
 
         // Handle named windows before processing the projection expression
         check_conflicting_windows(&select.named_window)?;
         self.match_window_definitions(&mut select.projection, &select.named_window)?;
 
-        // Process the SELECT expressions
-        let select_exprs = self.prepare_select_exprs(
+        // Process the SELECT expressions. The later clauses may refer to the
+        // select list, so planning stops at its errors.
+        let select_exprs = match self.prepare_select_exprs(
             &base_plan,
             select.projection,
             empty_from,
             planner_context,
-        )?;
+        ) {
+            Ok(select_exprs) => select_exprs,
+            Err(err) => {
+                errors.add_error(err);
+                return errors.error_or(base_plan);
+            }
+        };
 
         // Having and group by clause may reference aliases defined in select projection
         let projected_plan = self.project(base_plan.clone(), select_exprs)?;
         let select_exprs = projected_plan.expressions();
 
         let order_by =
-            to_order_by_exprs_with_select(query_order_by, Some(&select_exprs))?;
+            to_order_by_exprs(query_order_by, projected_plan.schema().fields().len())?;
 
         // Place the fields of the base plan at the front so that when there are references
         // with the same name, the fields of the base plan will be searched first.
@@ -133,14 +154,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         // Order-by expressions prioritize referencing columns from the select list,
         // then from the FROM clause.
-        let order_by_rex = self.order_by_to_sort_expr(
-            order_by,
-            projected_plan.schema().as_ref(),
-            planner_context,
-            true,
-            Some(base_plan.schema().as_ref()),
-        )?;
-        let order_by_rex = normalize_sorts(order_by_rex, &projected_plan)?;
+        let order_by_rex = self
+            .order_by_to_sort_expr(
+                order_by,
+                projected_plan.schema().as_ref(),
+                planner_context,
+                true,
+                Some(base_plan.schema().as_ref()),
+            )
+            .and_then(|order_by_rex| normalize_sorts(order_by_rex, &projected_plan));
+        let order_by_rex = self.collect_error(order_by_rex, &mut errors)?;
 
         // This alias map is resolved and looked up in both having exprs and group by exprs
         let alias_map = extract_aliases(&select_exprs);
@@ -170,7 +193,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let having_expr = resolve_aliases_to_exprs(having_expr, &alias_map)?;
                 normalize_col(having_expr, &projected_plan)
             })
-            .transpose()?;
+            .transpose();
+        let having_expr_opt = self.collect_error(having_expr_opt, &mut errors)?;
 
         // All of the group by expressions
         let group_by_exprs = if let GroupByExpr::Expressions(exprs, _) = select.group_by {
@@ -197,23 +221,31 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         base_plan.schema(),
                         std::slice::from_ref(&group_by_expr),
                     )?;
-                    Ok(group_by_expr)
+                    Ok(Some(group_by_expr))
                 })
+                .filter_map(|e| self.collect_error(e, &mut errors).transpose())
                 .collect::<Result<Vec<Expr>>>()?
         } else {
-            // 'group by all' groups wrt. all select expressions except 'AggregateFunction's.
-            // Filter and collect non-aggregate select expressions
-            select_exprs
-                .iter()
-                .filter(|select_expr| match select_expr {
-                    Expr::AggregateFunction(_) => false,
-                    Expr::Alias(Alias { expr, name: _, .. }) => {
-                        !matches!(**expr, Expr::AggregateFunction(_))
-                    }
-                    _ => true,
-                })
-                .cloned()
-                .collect()
+            // 'group by all' groups by every select expression that is not
+            // computed from an aggregate or window function. Expressions that
+            // reference no column are constant within any group and skipped,
+            // so that `SELECT 1, COUNT(*) FROM t GROUP BY ALL` still returns a
+            // row for an empty input.
+            let mut group_by_exprs: Vec<Expr> = vec![];
+            for select_expr in &select_exprs {
+                let exprs = std::slice::from_ref(select_expr);
+                if !find_aggregate_exprs(exprs).is_empty()
+                    || !find_window_exprs(exprs).is_empty()
+                    || !select_expr.any_column_refs()
+                {
+                    continue;
+                }
+                let group_by_expr = select_expr.clone().unalias();
+                if !group_by_exprs.contains(&group_by_expr) {
+                    group_by_exprs.push(group_by_expr);
+                }
+            }
+            group_by_exprs
         };
 
         // Optionally the QUALIFY expression.
@@ -243,6 +275,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             })
             .transpose()?;
 
+        // Return the errors collected so far together
+        errors.error_or(())?;
+
         // The outer expressions we will search through for aggregates.
         // First, find aggregates in SELECT, HAVING, and QUALIFY
         let select_having_qualify_aggrs = find_aggregate_exprs(
@@ -667,32 +702,21 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         let func_name = func.name.to_string().to_ascii_lowercase();
 
-        let schema = DFSchema::empty();
-        let func_args = match func.args {
-            FunctionArguments::List(list) => list
-                .args
-                .into_iter()
-                .map(|arg| match arg {
-                    FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
-                    | FunctionArg::Variadic(FunctionArgExpr::Expr(expr))
-                    | FunctionArg::Named {
-                        arg: FunctionArgExpr::Expr(expr),
-                        ..
-                    } => self.sql_expr_to_logical_expr(expr, &schema, planner_context),
-                    _ => plan_err!("Unsupported function argument: {arg:?}"),
-                })
-                .collect::<Result<Vec<Expr>>>()?,
+        let args = match func.args {
+            FunctionArguments::List(list) => list.args,
             FunctionArguments::None => vec![],
             _ => return Ok(None),
         };
-
-        match self
-            .context_provider
-            .get_table_function_source(&func_name, func_args)
-        {
-            Ok(provider) => {
-                let plan =
-                    LogicalPlanBuilder::scan(&func_name, provider, None)?.build()?;
+        let (func_args, arg_names) =
+            self.table_function_args_to_exprs(args, planner_context)?;
+
+        match self.table_function_to_plan(
+            &func_name,
+            TableReference::from(func_name.as_str()),
+            func_args,
+            arg_names,
+        ) {
+            Ok(plan) => {
                 // `SELECT srf(args) AS name` aliases the function's single output
                 // column to `name`; the unaliased form exposes all its columns.
                 select.projection = match alias {
@@ -1077,6 +1101,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 Ok(SelectExpr::Expression(col))
             }
             SelectItem::ExprWithAlias { expr, alias } => {
+                self.check_unreserved(&alias)?;
                 let select_expr =
                     self.sql_to_expr(expr, plan.schema(), planner_context)?;
                 let col = normalize_col_with_schemas_and_ambiguity_check(
@@ -1151,6 +1176,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     /// If there is a REPLACE statement in the projected expression in the form of
     /// "REPLACE (some_column_within_an_expr AS some_column)", we should plan the
     /// replace expressions first.
+    ///
+    /// The column names of EXCLUDE or EXCEPT and of REPLACE are normalized
+    /// like any other column reference.
     fn plan_wildcard_options(
         &self,
         plan: &LogicalPlan,
@@ -1158,15 +1186,44 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         planner_context: &mut PlannerContext,
         options: WildcardAdditionalOptions,
     ) -> Result<WildcardOptions> {
+        let normalize = |ident: Ident| Ident {
+            value: self.ident_normalizer.normalize(ident.clone()),
+            ..ident
+        };
+        let except = options.opt_except.map(|except| ExceptSelectItem {
+            first_element: normalize(except.first_element),
+            additional_elements: except
+                .additional_elements
+                .into_iter()
+                .map(normalize)
+                .collect(),
+        });
         let planned_option = WildcardOptions {
             ilike: options.opt_ilike,
-            except: options.opt_except,
+            except,
             replace: None,
             rename: options.opt_rename,
         };
         if let Some(replace) = options.opt_replace {
-            let replace_expr = replace
+            let items = replace
                 .items
+                .into_iter()
+                .map(|item| ReplaceSelectElement {
+                    column_name: normalize(item.column_name.clone()),
+                    ..*item
+                })
+                .collect::<Vec<_>>();
+            let mut names = HashSet::new();
+            if let Some(item) = items
+                .iter()
+                .find(|item| !names.insert(&item.column_name.value))
+            {
+                return plan_err!(
+                    "REPLACE contains column {} more than once",
+                    item.column_name.value
+                );
+            }
+            let replace_expr = items
                 .iter()
                 .map(|item| {
                     self.sql_select_to_rex(
@@ -1185,7 +1242,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 .collect::<Vec<_>>();
 
             let planned_replace = PlannedReplaceSelectItem {
-                items: replace.items.into_iter().map(|i| *i).collect(),
+                items,
                 planned_expressions: replace_expr,
             };
             Ok(planned_option.with_replace(planned_replace))
@@ -1432,52 +1489,114 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         projection: &mut [SelectItem],
         named_windows: &[NamedWindowDefinition],
     ) -> Result<()> {
-        let named_windows: Vec<(&NamedWindowDefinition, String)> = named_windows
-            .iter()
-            .map(|w| (w, self.ident_normalizer.normalize(w.0.clone())))
-            .collect();
+        // A window definition may only reference the windows defined before it
+        let mut window_specs: Vec<(String, WindowSpec)> = vec![];
+        for NamedWindowDefinition(ident, window_expr) in named_windows {
+            let window_spec = match window_expr {
+                NamedWindowExpr::NamedWindow(base) => {
+                    self.named_window_spec(base, &window_specs)?.clone()
+                }
+                NamedWindowExpr::WindowSpec(spec) => {
+                    self.inherit_window_spec(spec.clone(), &window_specs)?
+                }
+            };
+            window_specs
+                .push((self.ident_normalizer.normalize(ident.clone()), window_spec));
+        }
         for proj in projection.iter_mut() {
             if let SelectItem::ExprWithAlias { expr, alias: _ }
             | SelectItem::UnnamedExpr(expr) = proj
             {
                 let mut err = None;
                 let _ = visit_expressions_mut(expr, |expr| {
-                    if let SQLExpr::Function(f) = expr
-                        && let Some(WindowType::NamedWindow(ident)) = &f.over
-                    {
-                        let normalized_ident =
-                            self.ident_normalizer.normalize(ident.clone());
-                        for (
-                            NamedWindowDefinition(_, window_expr),
-                            normalized_window_ident,
-                        ) in named_windows.iter()
-                        {
-                            if normalized_ident.eq(normalized_window_ident) {
-                                f.over = Some(match window_expr {
-                                    NamedWindowExpr::NamedWindow(ident) => {
-                                        WindowType::NamedWindow(ident.clone())
-                                    }
-                                    NamedWindowExpr::WindowSpec(spec) => {
-                                        WindowType::WindowSpec(spec.clone())
-                                    }
-                                })
+                    if let SQLExpr::Function(f) = expr {
+                        let window_spec = match &f.over {
+                            Some(WindowType::NamedWindow(ident)) => self
+                                .named_window_spec(ident, &window_specs)
+                                .map(|spec| Some(spec.clone())),
+                            Some(WindowType::WindowSpec(spec))
+                                if spec.window_name.is_some() =>
+                            {
+                                self.inherit_window_spec(spec.clone(), &window_specs)
+                                    .map(Some)
+                            }
+                            _ => Ok(None),
+                        };
+                        match window_spec {
+                            Ok(Some(spec)) => f.over = Some(WindowType::WindowSpec(spec)),
+                            Ok(None) => {}
+                            Err(e) => {
+                                err = Some(e);
+                                return ControlFlow::Break(());
                             }
-                        }
-                        // All named windows must be defined with a WindowSpec.
-                        if let Some(WindowType::NamedWindow(ident)) = &f.over {
-                            err = Some(plan_err!("The window {ident} is not defined!"));
-                            return ControlFlow::Break(());
                         }
                     }
                     ControlFlow::Continue(())
                 });
                 if let Some(err) = err {
-                    return err;
+                    return Err(err);
                 }
             }
         }
         Ok(())
     }
+
+    fn named_window_spec<'a>(
+        &self,
+        ident: &Ident,
+        window_specs: &'a [(String, WindowSpec)],
+    ) -> Result<&'a WindowSpec> {
+        let name = self.ident_normalizer.normalize(ident.clone());
+        match window_specs
+            .iter()
+            .find(|(window_name, _)| *window_name == name)
+        {
+            Some((_, spec)) => Ok(spec),
+            None => plan_err!("The window {ident} is not defined!"),
+        }
+    }
+
+    /// Resolves a window specification that starts with the name of an
+    /// existing window, such as `(w ORDER BY b)`, into a specification that
+    /// copies the partitioning and ordering of that window.
+    ///
+    /// As in the SQL standard, the new specification can not override the
+    /// `PARTITION BY` of the existing window, can only add an `ORDER BY` if
+    /// the existing window has none, and the existing window can not have a
+    /// window frame.
+    fn inherit_window_spec(
+        &self,
+        spec: WindowSpec,
+        window_specs: &[(String, WindowSpec)],
+    ) -> Result<WindowSpec> {
+        let Some(base_name) = &spec.window_name else {
+            return Ok(spec);
+        };
+        let base = self.named_window_spec(base_name, window_specs)?;
+        if !spec.partition_by.is_empty() {
+            return plan_err!(
+                "Cannot override PARTITION BY clause of window {base_name}"
+            );
+        }
+        if !spec.order_by.is_empty() && !base.order_by.is_empty() {
+            return plan_err!("Cannot override ORDER BY clause of window {base_name}");
+        }
+        if base.window_frame.is_some() {
+            return plan_err!(
+                "Cannot copy window {base_name} because it has a frame clause"
+            );
+        }
+        Ok(WindowSpec {
+            window_name: None,
+            partition_by: base.partition_by.clone(),
+            order_by: if spec.order_by.is_empty() {
+                base.order_by.clone()
+            } else {
+                spec.order_by
+            },
+            window_frame: spec.window_frame,
+        })
+    }
 }
 
 // If there are any multiple-defined windows, we raise an error.
@@ -15,12 +15,87 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 use datafusion_common::{
     DataFusionError, Diagnostic, Result, Span, not_impl_err, plan_err,
 };
-use datafusion_expr::{LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{SetExpr, SetOperator, SetQuantifier, Spanned};
+use datafusion_expr::{LogicalPlan, LogicalPlanBuilder, Union, wildcard};
+use sqlparser::ast::{
+    Expr as SQLExpr, Ident, ObjectName, SetExpr, SetOperator, SetQuantifier, Spanned,
+    Table, TableFactor, TableWithJoins, Value, ValueWithSpan, visit_expressions,
+};
+
+/// The number of branches a `UNION ALL` chain needs to be planned in
+/// parallel, below which spawning threads costs more than it saves
+const PARALLEL_PLANNING_MIN_BRANCHES: usize = 16;
+
+#[cfg(feature = "parallel_planning")]
+impl<'a, S: ContextProvider + Sync> SqlToRel<'a, S> {
+    /// Plans the branches of large `UNION ALL` chains in parallel, on one
+    /// scoped thread per available core, each with its own copy of the
+    /// [`PlannerContext`].
+    pub fn with_parallel_planning(mut self) -> Self {
+        self.branch_planner = Some(Self::plan_branches_in_parallel);
+        self
+    }
+
+    fn plan_branches_in_parallel(
+        &self,
+        branches: Vec<SetExpr>,
+        planner_context: &PlannerContext,
+    ) -> Vec<Result<LogicalPlan>> {
+        let threads =
+            std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        let chunk_size = branches.len().div_ceil(threads);
+        let mut branches = branches.into_iter().peekable();
+        let mut chunks = vec![];
+        while branches.peek().is_some() {
+            chunks.push(branches.by_ref().take(chunk_size).collect::<Vec<_>>());
+        }
+
+        std::thread::scope(|scope| {
+            let handles = chunks
+                .into_iter()
+                .map(|chunk| {
+                    let mut planner_context = planner_context.clone();
+                    scope.spawn(move || {
+                        chunk
+                            .into_iter()
+                            .map(|branch| {
+                                self.set_expr_to_plan(branch, &mut planner_context)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
+    }
+}
+
+/// Returns whether any of `branches` uses an anonymous placeholder (`?`)
+fn has_anonymous_placeholder(branches: &[SetExpr]) -> bool {
+    branches.iter().any(|branch| {
+        visit_expressions(branch, |expr| match expr {
+            SQLExpr::Value(ValueWithSpan {
+                value: Value::Placeholder(param),
+                ..
+            }) if param == "?" => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        })
+        .is_break()
+    })
+}
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
     #[cfg_attr(feature = "recursive_protection", recursive::recursive)]
@@ -33,6 +108,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         match set_expr {
             SetExpr::Select(s) => self.select_to_plan(*s, None, planner_context),
             SetExpr::Values(v) => self.sql_values_to_plan(v, planner_context),
+            SetExpr::SetOperation {
+                op,
+                left,
+                right,
+                set_quantifier,
+            } if op == SetOperator::Union && set_quantifier == SetQuantifier::All => {
+                self.union_all_to_plan(*left, *right, set_expr_span, planner_context)
+            }
             SetExpr::SetOperation {
                 op,
                 left,
@@ -81,10 +164,154 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 // Handle DELETE statements within a query (e.g., from WITH clause)
                 self.sql_statement_to_plan_with_context(stmt, planner_context)
             }
+            SetExpr::Table(table) => self.table_query_to_plan(*table, planner_context),
             _ => not_impl_err!("Query {set_expr} not implemented yet"),
         }
     }
 
+    /// Plan `TABLE t`, which is equivalent to `SELECT * FROM t`. It is how
+    /// tables are passed to polymorphic table functions, as in
+    /// `TABLE(func(TABLE t))`.
+    fn table_query_to_plan(
+        &self,
+        table: Table,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let Some(table_name) = table.table_name else {
+            return plan_err!("TABLE requires a table name");
+        };
+        let name = table
+            .schema_name
+            .into_iter()
+            .chain(std::iter::once(table_name))
+            .map(Ident::new)
+            .collect::<Vec<_>>();
+        let relation = TableWithJoins {
+            relation: TableFactor::Table {
+                name: ObjectName::from(name),
+                alias: None,
+                args: None,
+                with_hints: vec![],
+                version: None,
+                with_ordinality: false,
+                only: false,
+                partitions: vec![],
+                json_path: None,
+                sample: None,
+                index_hints: vec![],
+            },
+            joins: vec![],
+        };
+        let plan = self.plan_table_with_joins(relation, planner_context)?;
+        LogicalPlanBuilder::from(plan)
+            .project(vec![wildcard()])?
+            .build()
+    }
+
+    /// Fails if the query body `set_expr` chains more set operations than
+    /// the `set_operation_limit` option allows. Parenthesized queries are
+    /// checked on their own.
+    pub(super) fn check_set_operation_limit(&self, set_expr: &SetExpr) -> Result<()> {
+        let limit = self
+            .context_provider
+            .options()
+            .sql_parser
+            .set_operation_limit;
+        let mut count = 0;
+        let mut stack = vec![set_expr];
+        while let Some(set_expr) = stack.pop() {
+            if let SetExpr::SetOperation { left, right, .. } = set_expr {
+                count += 1;
+                stack.push(left);
+                stack.push(right);
+            }
+        }
+        if count > limit {
+            return self.limit_exceeded_error(
+                "Number of set operations",
+                limit,
+                "set_operation_limit",
+                Span::try_from_sqlparser_span(set_expr.span()),
+            );
+        }
+        Ok(())
+    }
+
+    /// Plans a chain of `UNION ALL`, such as `q1 UNION ALL q2 UNION ALL q3`,
+    /// as a single [`Union`] of all its branches rather than a [`Union`] per
+    /// operator, so that statements with hundreds of branches are planned
+    /// without deep recursion or deriving the union schema for each branch.
+    fn union_all_to_plan(
+        &self,
+        left: SetExpr,
+        right: SetExpr,
+        set_expr_span: Option<Span>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        // The parser nests the chain on the left
+        let mut branches = vec![right];
+        let mut left = left;
+        while let SetExpr::SetOperation {
+            op: SetOperator::Union,
+            left: inner_left,
+            right: inner_right,
+            set_quantifier: SetQuantifier::All,
+        } = left
+        {
+            branches.push(*inner_right);
+            left = *inner_left;
+        }
+        branches.push(left);
+        branches.reverse();
+
+        let spans = branches
+            .iter()
+            .map(|branch| Span::try_from_sqlparser_span(branch.span()))
+            .collect::<Vec<_>>();
+        // Anonymous placeholders are numbered in the order they are planned,
+        // so the branches using them are planned serially
+        let results = match self.branch_planner {
+            Some(plan_branches)
+                if branches.len() >= PARALLEL_PLANNING_MIN_BRANCHES
+                    && !has_anonymous_placeholder(&branches) =>
+            {
+                plan_branches(self, branches, planner_context)
+            }
+            _ => branches
+                .into_iter()
+                .map(|branch| self.set_expr_to_plan(branch, planner_context))
+                .collect(),
+        };
+
+        let mut plans = Vec::with_capacity(results.len());
+        let mut errors = vec![];
+        for (result, span) in results.into_iter().zip(spans) {
+            match result {
+                Ok(plan) => plans.push((plan, span)),
+                Err(err) => errors.push(err),
+            }
+        }
+        if errors.len() > 1 {
+            return Err(DataFusionError::Collection(errors));
+        } else if let Some(err) = errors.pop() {
+            return Err(err);
+        }
+
+        let (first_plan, first_span) = &plans[0];
+        for (plan, span) in &plans[1..] {
+            self.validate_set_expr_num_of_columns(
+                SetOperator::Union,
+                *first_span,
+                *span,
+                first_plan,
+                plan,
+                set_expr_span,
+            )?;
+        }
+        let inputs = plans.into_iter().map(|(plan, _)| Arc::new(plan)).collect();
+        Ok(LogicalPlan::Union(Union::try_new_with_loose_types(inputs)?))
+    }
+
     pub(super) fn is_union_all(set_quantifier: SetQuantifier) -> Result<bool> {
         match set_quantifier {
             SetQuantifier::All | SetQuantifier::AllByName => Ok(true),
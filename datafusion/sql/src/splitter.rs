@@ -0,0 +1,300 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`split_statements`] splits a SQL script into the text of its statements
+
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::CharIndices;
+
+use datafusion_common::{DataFusionError, Result, Span};
+use sqlparser::dialect::Dialect;
+use sqlparser::dialect::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Location, Token, TokenWithSpan, Tokenizer, Whitespace};
+
+/// A statement of a SQL script, as split by [`split_statements`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementSlice<'a> {
+    /// The text of the statement, from its first token to its last one,
+    /// excluding the `;` delimiter
+    pub sql: &'a str,
+    /// The byte offsets of `sql` in the script
+    pub range: Range<usize>,
+    /// The location of `sql` in the script
+    pub span: Span,
+    /// The comments between the previous statement and this one, in order
+    pub leading_comments: Vec<CommentSlice<'a>>,
+}
+
+/// A comment of a SQL script, including its `--` or `/* */` markers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentSlice<'a> {
+    /// The text of the comment
+    pub text: &'a str,
+    /// The byte offsets of `text` in the script
+    pub range: Range<usize>,
+}
+
+/// Splits the SQL script `sql` into its statements, without parsing them.
+///
+/// Statements are delimited by the `;` tokens that are not nested in
+/// parentheses, `CASE` expressions or `BEGIN ... END` blocks, such as the
+/// body of a procedure, so that the script only needs to be tokenized with
+/// `dialect`. Empty statements are skipped. A comment that follows the last
+/// statement on its line is a leading comment of the next statement.
+///
+/// # Example
+/// ```
+/// # use datafusion_sql::splitter::split_statements;
+/// # use datafusion_sql::sqlparser::dialect::GenericDialect;
+/// # use datafusion_common::Result;
+/// # fn main() -> Result<()> {
+/// let script = "-- create\nCREATE TABLE t (a INT);\nSELECT a FROM t;";
+/// let statements = split_statements(script, &GenericDialect {})?;
+/// assert_eq!(statements.len(), 2);
+/// assert_eq!(statements[0].sql, "CREATE TABLE t (a INT)");
+/// assert_eq!(statements[0].leading_comments[0].text, "-- create");
+/// assert_eq!(statements[1].range, 34..49);
+/// # Ok(())
+/// # }
+/// ```
+pub fn split_statements<'a>(
+    sql: &'a str,
+    dialect: &dyn Dialect,
+) -> Result<Vec<StatementSlice<'a>>> {
+    let tokens = Tokenizer::new(dialect, sql)
+        .tokenize_with_location()
+        .map_err(|err| DataFusionError::from(ParserError::from(err)))?;
+    let mut offsets = ByteOffsets::new(sql);
+
+    let mut statements = vec![];
+    let mut comments = vec![];
+    // The range and span of the statement being split, once it has a token
+    let mut current: Option<(Range<usize>, Span)> = None;
+    let mut depth = 0_usize;
+    for (index, token) in tokens.iter().enumerate() {
+        let start = offsets.offset(token.span.start);
+        let end = offsets.offset(token.span.end);
+        match &token.token {
+            Token::Whitespace(
+                Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_),
+            ) => {
+                if current.is_none() {
+                    let text = sql[start..end].trim_end_matches(['\r', '\n']);
+                    comments.push(CommentSlice {
+                        text,
+                        range: start..start + text.len(),
+                    });
+                }
+            }
+            Token::Whitespace(_) => {}
+            Token::SemiColon if depth == 0 => {
+                if let Some((range, span)) = current.take() {
+                    statements.push(StatementSlice {
+                        sql: &sql[range.clone()],
+                        range,
+                        span,
+                        leading_comments: std::mem::take(&mut comments),
+                    });
+                }
+            }
+            other => {
+                depth = match other {
+                    Token::LParen => depth + 1,
+                    Token::RParen => depth.saturating_sub(1),
+                    Token::Word(word) => match word.keyword {
+                        Keyword::CASE => depth + 1,
+                        Keyword::BEGIN if begins_block(&tokens[index + 1..]) => depth + 1,
+                        Keyword::END if ends_block(&tokens[index + 1..]) => {
+                            depth.saturating_sub(1)
+                        }
+                        _ => depth,
+                    },
+                    _ => depth,
+                };
+                let span = Span::new(token.span.start.into(), token.span.end.into());
+                current = Some(match current.take() {
+                    Some((range, statement_span)) => {
+                        (range.start..end, statement_span.union(&span))
+                    }
+                    None => (start..end, span),
+                });
+            }
+        }
+    }
+    if let Some((range, span)) = current {
+        statements.push(StatementSlice {
+            sql: &sql[range.clone()],
+            range,
+            span,
+            leading_comments: comments,
+        });
+    }
+    Ok(statements)
+}
+
+/// Returns the first token of `tokens` that is not whitespace or a comment
+fn next_token(tokens: &[TokenWithSpan]) -> Option<&Token> {
+    tokens
+        .iter()
+        .map(|token| &token.token)
+        .find(|token| !matches!(token, Token::Whitespace(_)))
+}
+
+/// Returns true if the `BEGIN` followed by `tokens` opens a block of
+/// statements, rather than starting a transaction
+fn begins_block(tokens: &[TokenWithSpan]) -> bool {
+    match next_token(tokens) {
+        None | Some(Token::SemiColon) => false,
+        Some(Token::Word(word)) => !matches!(
+            word.value.to_ascii_uppercase().as_str(),
+            "TRANSACTION"
+                | "TRAN"
+                | "WORK"
+                | "ISOLATION"
+                | "READ"
+                | "DEFERRED"
+                | "IMMEDIATE"
+                | "EXCLUSIVE"
+        ),
+        Some(_) => true,
+    }
+}
+
+/// Returns true if the `END` followed by `tokens` closes a `CASE` expression
+/// or a `BEGIN ... END` block, rather than a control statement such as
+/// `END IF`, whose opening is not counted
+fn ends_block(tokens: &[TokenWithSpan]) -> bool {
+    match next_token(tokens) {
+        Some(Token::Word(word)) => !matches!(
+            word.keyword,
+            Keyword::IF | Keyword::LOOP | Keyword::WHILE | Keyword::REPEAT | Keyword::FOR
+        ),
+        _ => true,
+    }
+}
+
+/// Converts increasing token locations of a script to byte offsets
+pub(crate) struct ByteOffsets<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    len: usize,
+    line: u64,
+    column: u64,
+}
+
+impl<'a> ByteOffsets<'a> {
+    pub(crate) fn new(sql: &'a str) -> Self {
+        Self {
+            chars: sql.char_indices().peekable(),
+            len: sql.len(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Returns the byte offset of `location`, which must not precede the
+    /// previous location
+    pub(crate) fn offset(&mut self, location: Location) -> usize {
+        while (self.line, self.column) < (location.line, location.column) {
+            match self.chars.next() {
+                Some((_, '\n')) => {
+                    self.line += 1;
+                    self.column = 1;
+                }
+                Some(_) => self.column += 1,
+                None => break,
+            }
+        }
+        self.chars.peek().map_or(self.len, |(offset, _)| *offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::dialect::{GenericDialect, PostgreSqlDialect};
+
+    #[test]
+    fn split_statements_with_comments() {
+        let script = "/* setup */\n-- first\nSELECT 'a;b' AS x;;\n\
+                      SELECT (1); -- trailing\n  SELECT é FROM t -- last\n";
+        let statements = split_statements(script, &GenericDialect {}).unwrap();
+        assert_eq!(statements.len(), 3);
+
+        assert_eq!(statements[0].sql, "SELECT 'a;b' AS x");
+        let comments = statements[0]
+            .leading_comments
+            .iter()
+            .map(|comment| comment.text)
+            .collect::<Vec<_>>();
+        assert_eq!(comments, vec!["/* setup */", "-- first"]);
+        assert_eq!(
+            &script[statements[0].leading_comments[1].range.clone()],
+            "-- first"
+        );
+
+        assert_eq!(statements[1].sql, "SELECT (1)");
+        assert!(statements[1].leading_comments.is_empty());
+
+        assert_eq!(statements[2].sql, "SELECT é FROM t");
+        assert_eq!(&script[statements[2].range.clone()], "SELECT é FROM t");
+        assert_eq!(statements[2].leading_comments[0].text, "-- trailing");
+        assert_eq!(
+            statements[2].span,
+            Span::new(
+                datafusion_common::Location { line: 5, column: 3 },
+                datafusion_common::Location {
+                    line: 5,
+                    column: 18
+                },
+            )
+        );
+    }
+
+    #[test]
+    fn split_statements_with_blocks() {
+        let script = "BEGIN; \
+            CREATE PROCEDURE p() BEGIN \
+              IF x THEN SELECT 1; END IF; \
+              SELECT CASE WHEN y THEN 1 END; \
+            END; \
+            COMMIT";
+        let statements = split_statements(script, &PostgreSqlDialect {}).unwrap();
+        let statements = statements
+            .iter()
+            .map(|statement| statement.sql)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statements,
+            vec![
+                "BEGIN",
+                "CREATE PROCEDURE p() BEGIN \
+                 IF x THEN SELECT 1; END IF; \
+                 SELECT CASE WHEN y THEN 1 END; \
+                 END",
+                "COMMIT"
+            ]
+        );
+    }
+
+    #[test]
+    fn split_statements_tokenizer_error() {
+        let err = split_statements("SELECT 'a", &GenericDialect {}).unwrap_err();
+        assert!(err.to_string().contains("Unterminated string literal"));
+    }
+}
@@ -21,51 +21,62 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::parser::{
-    CopyFromStatement, CopyToSource, CopyToStatement, CreateExternalTable, DFParser,
-    ExplainStatement, LexOrdering, ResetStatement, Statement as DFStatement,
+    AlterUserStatement, CopyFromStatement, CopyToSource, CopyToStatement,
+    CreateExternalTable, CreateUserStatement, DFParser, DropUserStatement,
+    ExplainStatement, LexOrdering, PortionOfChange, PortionOfStatement, ResetStatement,
+    Statement as DFStatement, UserOptionChange, UserOptions,
 };
-use crate::planner::{
-    ContextProvider, PlannerContext, SqlToRel, object_name_to_qualifier,
-};
-use crate::utils::normalize_ident;
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use crate::utils::{is_lossy_numeric_cast, normalize_ident};
 
-use arrow::datatypes::{Field, FieldRef, Fields};
+use arrow::datatypes::{DataType, Field, FieldRef, Fields, Schema, SchemaRef};
 use datafusion_common::error::_plan_err;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
-    Column, Constraint, Constraints, DFSchema, DFSchemaRef, DataFusionError, MatchType,
-    NullsDistinct, ReferentialAction, Result, ScalarValue, SchemaError, SchemaReference,
-    TableReference, ToDFSchema, exec_err, not_impl_err, plan_datafusion_err, plan_err,
-    schema_err, unqualified_field_not_found,
+    Column, Constraint, Constraints, DFSchema, DFSchemaRef, DataFusionError, Diagnostic,
+    MatchType, NullsDistinct, ReferentialAction, Result, ScalarValue, SchemaError,
+    SchemaReference, Spans, TableReference, ToDFSchema, exec_err, not_impl_err,
+    plan_datafusion_err, plan_err, schema_err, unqualified_field_not_found,
 };
 use datafusion_expr::dml::{
-    ConflictAssignment, ConflictTarget, CopyFrom, CopyTo, DoUpdateAction, InsertOp, OnConflict,
-    OnConflictAction,
+    ConflictAssignment, ConflictTarget, CopyErrorHandling, CopyFrom, CopyFromSource,
+    CopyOnError, CopyTo, DoUpdateAction, InsertOp, OnConflict, OnConflictAction,
+    PartitionLayout, PartitionNaming,
 };
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::psm::{ParameterMode, ProcedureArg};
 use datafusion_expr::logical_plan::{DdlStatement, build_join_schema};
+use datafusion_expr::planner::{
+    PERIOD_END_METADATA_KEY, PERIOD_START_METADATA_KEY, SYSTEM_TIME_METADATA_KEY,
+    SYSTEM_TIME_ROW_END, SYSTEM_TIME_ROW_START, StatementPlannerContext,
+    StatementPlanning,
+};
+use datafusion_expr::type_coercion::binary::comparison_coercion;
 use datafusion_expr::utils::{expr_to_columns, exprlist_to_fields};
 use datafusion_expr::{
-    AlterMaterializedView, AlterSequence, Analyze, AnalyzeTable, Call, CreateAssertion,
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable as PlanCreateExternalTable,
-    CreateFunction, CreateFunctionBody, CreateIndex as PlanCreateIndex,
-    CreateMaterializedView, CreateMemoryTable, CreateProcedure, CreatePropertyGraph,
-    CreateRole, CreateSequence, CreateView, Deallocate, DescribeTable, DmlStatement,
-    DropAssertion, DropCatalogSchema, DropFunction, DropIndex, DropMaterializedView,
-    DropPropertyGraph, DropRole, DropSequence, DropTable, DropView, EmptyRelation, Execute,
+    AlterForeignTable, AlterMaterializedView, AlterSequence, AlterServer, AlterUser,
+    Analyze, AnalyzeTable, Call, CreateAssertion, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable as PlanCreateExternalTable, CreateForeignTable, CreateFunction,
+    CreateFunctionBody, CreateIndex as PlanCreateIndex, CreateMaterializedView,
+    CreateMemoryTable, CreateProcedure, CreatePropertyGraph, CreateRole, CreateSequence,
+    CreateServer, CreateUser, CreateUserMapping, CreateView, Deallocate, DescribeTable,
+    DmlStatement, DropAssertion, DropCatalogSchema, DropForeignTable, DropFunction,
+    DropIndex, DropMaterializedView, DropPropertyGraph, DropRole, DropSequence,
+    DropServer, DropTable, DropUser, DropUserMapping, DropView, EmptyRelation, Execute,
     Explain, ExplainFormat, Expr, ExprSchemable, Filter, Grant, GrantRole,
     GraphEdgeEndpoint, GraphEdgeTableDefinition, GraphKeyClause, GraphPropertiesClause,
     GraphVertexTableDefinition, JoinType, LogicalPlan, LogicalPlanBuilder, Merge,
     MergeAction, MergeAssignment, MergeClause, MergeInsertExpr, MergeInsertKind,
-    MergeUpdateExpr, OperateFunctionArg, PlanType, Prepare, RefreshMaterializedView,
-    ReleaseSavepoint, ResetVariable, Revoke, RevokeRole, RollbackToSavepoint, Savepoint,
-    SetTransaction, SetVariable, SortExpr, Statement as PlanStatement, ToStringifiedPlan,
+    MergeUpdateExpr, OperateFunctionArg, Operator, OptionChange, PlanType, Prepare,
+    Privilege, RefreshMaterializedView, ReleaseSavepoint, ResetVariable, Revoke,
+    RevokeRole, RollbackToSavepoint, Savepoint, ScalarUDF, SetRole, SetTransaction,
+    SetVariable, SortExpr, Statement as PlanStatement, Subquery, ToStringifiedPlan,
     TransactionAccessMode, TransactionConclusion, TransactionEnd,
-    TransactionIsolationLevel, TransactionStart, TruncateTable, UseDatabase, Vacuum,
-    Volatility, WriteOp, cast, col,
+    TransactionIsolationLevel, TransactionStart, TruncateTable, UseDatabase,
+    UserMappingTarget, Vacuum, VacuumOptions, Volatility, WriteOp, binary_expr, cast,
+    col, lit, when,
 };
 use sqlparser::ast::{
     self, BeginTransactionKind, IndexColumn, IndexType, OnConflict as SqlOnConflict,
@@ -86,6 +97,65 @@ fn ident_to_string(ident: &Ident) -> String {
     normalize_ident(ident.to_owned())
 }
 
+/// The fields of the `clause` columns of the property graph element
+/// table `table`
+fn element_fields<'a>(
+    schema: &'a Schema,
+    table: &TableReference,
+    clause: &str,
+    columns: &[String],
+) -> Result<Vec<&'a Field>> {
+    columns
+        .iter()
+        .map(|column| match schema.column_with_name(column) {
+            Some((_, field)) => Ok(field),
+            None => plan_err!("{clause} column {column} not found in table {table}"),
+        })
+        .collect()
+}
+
+/// The properties a property graph element table exposes: its `PROPERTIES`
+/// columns, or all of its columns without a `PROPERTIES` clause
+fn element_properties(
+    schema: &Schema,
+    table: &TableReference,
+    properties: &Option<GraphPropertiesClause>,
+) -> Result<Vec<String>> {
+    match properties {
+        Some(properties) => {
+            element_fields(schema, table, "PROPERTIES", &properties.columns)?;
+            Ok(properties.columns.clone())
+        }
+        None => Ok(schema.fields().iter().map(|f| f.name().clone()).collect()),
+    }
+}
+
+/// Record the properties of the label of a property graph element table,
+/// checking that element tables sharing a label expose the same properties.
+/// Unlabeled tables are labeled by their name.
+fn check_label<'a>(
+    labels: &mut HashMap<String, (&'a TableReference, Vec<String>)>,
+    graph: &TableReference,
+    table: &'a TableReference,
+    label: &Option<String>,
+    mut properties: Vec<String>,
+) -> Result<()> {
+    properties.sort();
+    let label = label.clone().unwrap_or_else(|| table.table().to_string());
+    match labels.get(&label) {
+        Some((other, other_properties)) if *other_properties != properties => {
+            plan_err!(
+                "Label {label} of property graph {graph} has different properties in {other} and {table}"
+            )
+        }
+        Some(_) => Ok(()),
+        None => {
+            labels.insert(label, (table, properties));
+            Ok(())
+        }
+    }
+}
+
 /// Convert the `WITH (...)` options of a `CREATE MATERIALIZED VIEW` into
 /// a flat string-keyed map. Each option's value is rendered using its
 /// sqlparser `Display` impl so the engine layer sees the user's literal
@@ -131,6 +201,145 @@ fn object_name_to_string(object_name: &ObjectName) -> String {
         .join(".")
 }
 
+/// The user named by `CREATE USER MAPPING FOR <user>`
+fn user_mapping_target(user: &ast::UserMappingUser) -> UserMappingTarget {
+    let user = user.to_string();
+    match user.to_uppercase().as_str() {
+        "CURRENT_USER" | "CURRENT_ROLE" | "USER" => UserMappingTarget::CurrentUser,
+        "PUBLIC" => UserMappingTarget::Public,
+        _ => match user.strip_prefix('"').and_then(|u| u.strip_suffix('"')) {
+            Some(quoted) => UserMappingTarget::User(quoted.to_string()),
+            None => UserMappingTarget::User(user.to_lowercase()),
+        },
+    }
+}
+
+/// Collect SQL/MED `OPTIONS (key 'value', ...)` into a map keyed by the
+/// normalized option name
+fn server_options_map(
+    options: Option<Vec<ast::CreateServerOption>>,
+) -> BTreeMap<String, String> {
+    options
+        .into_iter()
+        .flatten()
+        .map(|option| (ident_to_string(&option.key), option.value.value))
+        .collect()
+}
+
+/// Take the `partition.*` options of a `COPY TO` out of `options` into the
+/// layout of the written files
+fn copy_to_partition_layout(
+    options: &mut HashMap<String, String>,
+    partitioned: bool,
+) -> Result<PartitionLayout> {
+    let mut layout = PartitionLayout::default();
+    let mut keys: Vec<String> = options
+        .keys()
+        .filter(|key| key.starts_with("partition."))
+        .cloned()
+        .collect();
+    keys.sort();
+    for key in keys {
+        let Some(value) = options.remove(&key) else {
+            continue;
+        };
+        match key.as_str() {
+            "partition.naming" => {
+                layout.naming = match value.to_lowercase().as_str() {
+                    "hive" => PartitionNaming::Hive,
+                    "values" => PartitionNaming::Values,
+                    _ => {
+                        return plan_err!(
+                            "Invalid value {value} for {key}, expected hive or values"
+                        );
+                    }
+                };
+            }
+            "partition.max_file_size" => match value.parse::<u64>() {
+                Ok(size) if size > 0 => layout.max_file_size = Some(size),
+                _ => {
+                    return plan_err!(
+                        "Invalid value {value} for {key}, expected a number of bytes"
+                    );
+                }
+            },
+            "partition.single_file" => match value.to_lowercase().parse::<bool>() {
+                Ok(single_file) => layout.single_file = single_file,
+                Err(_) => {
+                    return plan_err!(
+                        "Invalid value {value} for {key}, expected true or false"
+                    );
+                }
+            },
+            _ => return plan_err!("Unknown COPY TO option {key}"),
+        }
+        if !partitioned && key != "partition.max_file_size" {
+            return plan_err!("COPY TO option {key} requires PARTITIONED BY");
+        }
+    }
+    if layout.single_file && layout.max_file_size.is_some() {
+        return plan_err!(
+            "COPY TO options partition.single_file and partition.max_file_size cannot be used together"
+        );
+    }
+    Ok(layout)
+}
+
+/// Take the `errors.*` options of a `COPY FROM` out of `options` into how
+/// malformed rows are handled
+fn copy_from_error_handling(
+    options: &mut HashMap<String, String>,
+) -> Result<CopyErrorHandling> {
+    let mut error_handling = CopyErrorHandling::default();
+    let mut keys: Vec<String> = options
+        .keys()
+        .filter(|key| key.starts_with("errors."))
+        .cloned()
+        .collect();
+    keys.sort();
+    for key in keys {
+        let Some(value) = options.remove(&key) else {
+            continue;
+        };
+        match key.as_str() {
+            "errors.on_error" => {
+                error_handling.on_error = match value.to_lowercase().as_str() {
+                    "stop" => CopyOnError::Stop,
+                    "skip" => CopyOnError::Skip,
+                    _ => {
+                        return plan_err!(
+                            "Invalid value {value} for {key}, expected stop or skip"
+                        );
+                    }
+                };
+            }
+            "errors.max_errors" => match value.parse::<u64>() {
+                Ok(max_errors) => error_handling.max_errors = Some(max_errors),
+                Err(_) => {
+                    return plan_err!(
+                        "Invalid value {value} for {key}, expected a number of rows"
+                    );
+                }
+            },
+            "errors.reject_location" => error_handling.reject_location = Some(value),
+            _ => return plan_err!("Unknown COPY FROM option {key}"),
+        }
+    }
+    if error_handling.on_error == CopyOnError::Stop {
+        if error_handling.max_errors.is_some() {
+            return plan_err!(
+                "COPY FROM option errors.max_errors requires errors.on_error skip"
+            );
+        }
+        if error_handling.reject_location.is_some() {
+            return plan_err!(
+                "COPY FROM option errors.reject_location requires errors.on_error skip"
+            );
+        }
+    }
+    Ok(error_handling)
+}
+
 fn select_items_to_column_names(items: &[SelectItem]) -> Vec<String> {
     items
         .iter()
@@ -281,6 +490,27 @@ fn rewrite_update_returning_exprs(
     Ok((rewritten_exprs, passthrough_exprs))
 }
 
+/// Name `expr` `name`, unless it is already a column of that name
+fn alias_if_renamed(expr: Expr, name: &str) -> Expr {
+    match &expr {
+        Expr::Column(column) if column.name == name => expr,
+        _ => expr.alias(name),
+    }
+}
+
+/// Whether the fields of `source` have the names and types of the fields of
+/// `target`, so that the rows of `source` are written to `target` as they are
+fn has_target_fields(source: &DFSchema, target: &DFSchema) -> bool {
+    source.fields().len() == target.fields().len()
+        && source
+            .fields()
+            .iter()
+            .zip(target.fields())
+            .all(|(source, target)| {
+                source.name() == target.name() && source.data_type() == target.data_type()
+            })
+}
+
 fn get_schema_name(schema_name: &SchemaName) -> String {
     match schema_name {
         SchemaName::Simple(schema_name) => object_name_to_string(schema_name),
@@ -293,6 +523,30 @@ fn get_schema_name(schema_name: &SchemaName) -> String {
     }
 }
 
+/// The name, start column and end column of a `PERIOD FOR name (start, end)`
+/// table constraint, read from its SQL text
+fn period_definition(constraint: &TableConstraint) -> Option<(Ident, Ident, Ident)> {
+    if !matches!(constraint, TableConstraint::Period { .. }) {
+        return None;
+    }
+    let definition = constraint.to_string();
+    let (name, columns) = definition.strip_prefix("PERIOD FOR ")?.split_once('(')?;
+    let (start, end) = columns.trim_end().strip_suffix(')')?.split_once(',')?;
+    Some((sql_ident(name), sql_ident(start), sql_ident(end)))
+}
+
+/// The identifier written as `text`, quoted or not
+fn sql_ident(text: &str) -> Ident {
+    let text = text.trim();
+    match text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+    {
+        Some(quoted) => Ident::with_quote('"', quoted),
+        None => Ident::new(text),
+    }
+}
+
 /// Construct `TableConstraint`(s) for the given columns by iterating over
 /// `columns` and extracting individual inline constraint definitions.
 fn calc_inline_constraints_from_columns(columns: &[ColumnDef]) -> Vec<TableConstraint> {
@@ -392,13 +646,55 @@ fn calc_inline_constraints_from_columns(columns: &[ColumnDef]) -> Vec<TableConst
     constraints
 }
 
+struct SqlToRelStatementContext<'a, 'b, S: ContextProvider> {
+    planner: &'a SqlToRel<'b, S>,
+    planner_context: &'a mut PlannerContext,
+}
+
+impl<S: ContextProvider> StatementPlannerContext for SqlToRelStatementContext<'_, '_, S> {
+    fn context_provider(&self) -> &dyn ContextProvider {
+        self.planner.context_provider
+    }
+
+    fn plan(&mut self, statement: Statement) -> Result<LogicalPlan> {
+        self.planner
+            .sql_statement_to_plan_with_context(statement, self.planner_context)
+    }
+
+    fn sql_to_expr(&mut self, expr: SQLExpr, schema: &DFSchema) -> Result<Expr> {
+        self.planner.sql_to_expr(expr, schema, self.planner_context)
+    }
+
+    fn normalize_ident(&self, ident: Ident) -> String {
+        self.planner.ident_normalizer.normalize(ident)
+    }
+
+    fn object_name_to_table_reference(&self, name: ObjectName) -> Result<TableReference> {
+        self.planner.object_name_to_table_reference(name)
+    }
+}
+
 impl<S: ContextProvider> SqlToRel<'_, S> {
-    /// Generate a logical plan from an DataFusion SQL statement
+    /// Generate a logical plan from an DataFusion SQL statement, after applying
+    /// the [`StatementRewriter`]s of the [`ContextProvider`]
+    ///
+    /// [`StatementRewriter`]: datafusion_expr::planner::StatementRewriter
     pub fn statement_to_plan(&self, statement: DFStatement) -> Result<LogicalPlan> {
+        let statement = self.rewrite_df_statement(statement)?;
+        let sql = self.audited_sql(&statement);
+        let plan = self.authorize(self.df_statement_to_plan(statement)?)?;
+        self.audit(sql, plan)
+    }
+
+    /// Generate a logical plan from an DataFusion SQL statement, without
+    /// rewriting it
+    fn df_statement_to_plan(&self, statement: DFStatement) -> Result<LogicalPlan> {
         match statement {
             DFStatement::CreateExternalTable(s) => self.external_table_to_plan(s),
-            DFStatement::Statement(s) => self.sql_statement_to_plan(*s),
-            DFStatement::CopyTo(s) => self.copy_to_plan(s),
+            DFStatement::Statement(s) => {
+                self.resolved_statement_to_plan(*s, &mut PlannerContext::new())
+            }
+            DFStatement::CopyTo(s) => self.copy_to_plan(s, &mut PlannerContext::new()),
             DFStatement::CopyFrom(s) => self.copy_from_plan(s),
             DFStatement::Explain(ExplainStatement {
                 verbose,
@@ -407,24 +703,159 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 statement,
             }) => self.explain_to_plan(verbose, analyze, format, *statement),
             DFStatement::Reset(statement) => self.reset_statement_to_plan(statement),
+            DFStatement::CreateUser(statement) => self.create_user_to_plan(statement),
+            DFStatement::AlterUser(statement) => self.alter_user_to_plan(statement),
+            DFStatement::DropUser(statement) => self.drop_user_to_plan(statement),
+            DFStatement::PortionOf(statement) => self.portion_of_to_plan(statement),
         }
     }
 
-    /// Generate a logical plan from an SQL statement
+    /// Generate a logical plan from an SQL statement, after applying the
+    /// [`StatementRewriter`]s of the [`ContextProvider`]
+    ///
+    /// [`StatementRewriter`]: datafusion_expr::planner::StatementRewriter
     pub fn sql_statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
-        self.sql_statement_to_plan_with_context_impl(
-            statement,
-            &mut PlannerContext::new(),
-        )
+        let statement = self.rewrite_statement(statement)?;
+        let sql = self.audited_sql(&statement);
+        let plan =
+            self.resolved_statement_to_plan(statement, &mut PlannerContext::new())?;
+        self.audit(sql, self.authorize(plan)?)
+    }
+
+    /// Generate a logical plan from an SQL statement, after applying the
+    /// [`StatementRewriter`]s of the [`ContextProvider`], along with the
+    /// non-fatal warnings raised while planning it
+    ///
+    /// [`StatementRewriter`]: datafusion_expr::planner::StatementRewriter
+    pub fn sql_statement_to_plan_with_warnings(
+        &self,
+        statement: Statement,
+    ) -> Result<(LogicalPlan, Vec<Diagnostic>)> {
+        let statement = self.rewrite_statement(statement)?;
+        let sql = self.audited_sql(&statement);
+        let mut planner_context = PlannerContext::new();
+        let plan = self.resolved_statement_to_plan(statement, &mut planner_context)?;
+        let plan = self.audit(sql, self.authorize(plan)?)?;
+        Ok((plan, planner_context.take_warnings()))
+    }
+
+    /// Generate a logical plan from an SQL statement, after resolving the
+    /// tables it references in one batch
+    fn resolved_statement_to_plan(
+        &self,
+        statement: Statement,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        self.resolve_table_sources(&statement, planner_context);
+        self.sql_statement_to_plan_with_context(statement, planner_context)
+    }
+
+    /// Check that the current role of the [`ContextProvider`] has the
+    /// privileges that `plan` requires, if the provider has a privilege store
+    pub(crate) fn authorize(&self, plan: LogicalPlan) -> Result<LogicalPlan> {
+        let (Some(role), Some(store)) = (
+            self.context_provider.get_current_role(),
+            self.context_provider.get_privilege_store(),
+        ) else {
+            return Ok(plan);
+        };
+        for (privilege, table) in self.resolved_required_privileges(&plan)? {
+            store.check(&role, privilege, &table)?;
+        }
+        Ok(plan)
+    }
+
+    /// The [`LogicalPlan::required_privileges`] of `plan`, on fully qualified
+    /// tables
+    pub(crate) fn resolved_required_privileges(
+        &self,
+        plan: &LogicalPlan,
+    ) -> Result<Vec<(Privilege, TableReference)>> {
+        Ok(plan
+            .required_privileges()?
+            .into_iter()
+            .map(|(privilege, table)| (privilege, self.resolve_table_reference(table)))
+            .collect())
+    }
+
+    /// The fully qualified reference to `table`, in the default catalog and
+    /// schema unless it names them
+    pub(crate) fn resolve_table_reference(
+        &self,
+        table: TableReference,
+    ) -> TableReference {
+        let catalog = &self.context_provider.options().catalog;
+        table
+            .resolve(&catalog.default_catalog, &catalog.default_schema)
+            .into()
+    }
+
+    /// Apply the [`StatementRewriter`]s of the [`ContextProvider`] to
+    /// `statement`, in order
+    ///
+    /// [`StatementRewriter`]: datafusion_expr::planner::StatementRewriter
+    pub fn rewrite_statement(&self, statement: Statement) -> Result<Statement> {
+        self.context_provider
+            .get_statement_rewriters()
+            .iter()
+            .try_fold(statement, |statement, rewriter| {
+                rewriter.rewrite_statement(statement, self.context_provider)
+            })
+    }
+
+    /// Apply the [`StatementRewriter`]s to the SQL statement of `statement`,
+    /// including the statement of an `EXPLAIN`
+    pub(crate) fn rewrite_df_statement(&self, statement: DFStatement) -> Result<DFStatement> {
+        Ok(match statement {
+            DFStatement::Statement(s) => {
+                DFStatement::Statement(Box::new(self.rewrite_statement(*s)?))
+            }
+            DFStatement::Explain(explain) => DFStatement::Explain(ExplainStatement {
+                statement: Box::new(self.rewrite_df_statement(*explain.statement)?),
+                ..explain
+            }),
+            other => other,
+        })
     }
 
-    /// Generate a logical plan from an SQL statement
+    /// Generate a logical plan from an SQL statement, without rewriting it
     pub fn sql_statement_to_plan_with_context(
         &self,
         statement: Statement,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
+        let span = self.span_of(&statement);
         self.sql_statement_to_plan_with_context_impl(statement, planner_context)
+            .map_err(|err| self.locate_error(err, span))
+    }
+
+    /// Plan `statement` with the [`StatementPlanner`]s of the
+    /// [`ContextProvider`], in order, until one of them handles it
+    ///
+    /// [`StatementPlanner`]: datafusion_expr::planner::StatementPlanner
+    fn create_extension_statement(
+        &self,
+        statement: Statement,
+        planner_context: &mut PlannerContext,
+    ) -> Result<StatementPlanning> {
+        let mut current_statement = statement;
+        for planner in self.context_provider.get_statement_planners() {
+            let mut context = SqlToRelStatementContext {
+                planner: self,
+                planner_context,
+            };
+
+            match planner.plan_statement(current_statement, &mut context)? {
+                StatementPlanning::Planned(plan) => {
+                    return Ok(StatementPlanning::Planned(plan));
+                }
+                StatementPlanning::Original(original) => {
+                    current_statement = original;
+                }
+            }
+        }
+
+        Ok(StatementPlanning::Original(current_statement))
     }
 
     fn sql_statement_to_plan_with_context_impl(
@@ -432,6 +863,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         statement: Statement,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
+        let statement =
+            match self.create_extension_statement(statement, planner_context)? {
+                StatementPlanning::Planned(plan) => return Ok(plan),
+                StatementPlanning::Original(statement) => statement,
+            };
         match statement {
             Statement::ExplainTable {
                 describe_alias: DescribeAlias::Describe | DescribeAlias::Desc, // only parse 'DESCRIBE table_name' or 'DESC table_name' and not 'EXPLAIN table_name'
@@ -506,9 +942,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if like.is_some() {
                     return not_impl_err!("Like not supported")?;
                 }
-                if clone.is_some() {
-                    return not_impl_err!("Clone not supported")?;
-                }
                 if comment.is_some() {
                     return not_impl_err!("Comment not supported")?;
                 }
@@ -518,8 +951,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if dynamic {
                     return not_impl_err!("Dynamic tables not supported")?;
                 }
-                if version.is_some() {
-                    return not_impl_err!("Version not supported")?;
+                if version.is_some() && clone.is_none() {
+                    return plan_err!("A table version requires CLONE")?;
                 }
                 let mut storage_parameters = match table_options {
                     CreateTableOptions::None => BTreeMap::new(),
@@ -558,14 +991,36 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     self.build_column_defaults(&columns, planner_context)?;
 
                 let has_columns = !columns.is_empty();
-                let schema = self.build_schema(columns)?.to_dfschema_ref()?;
+                let schema = self
+                    .with_application_periods(
+                        self.build_schema(columns)?,
+                        &all_constraints,
+                    )?
+                    .to_dfschema_ref()?;
                 if has_columns {
                     planner_context.set_table_schema(Some(Arc::clone(&schema)));
                 }
 
-                match query {
-                    Some(query) => {
-                        let plan = self.query_to_plan(*query, planner_context)?;
+                let input = match (query, clone) {
+                    (Some(query), None) => {
+                        Some(self.query_to_plan(*query, planner_context)?)
+                    }
+                    (None, Some(source)) => {
+                        if has_columns {
+                            return plan_err!(
+                                "CREATE TABLE ... CLONE cannot specify columns"
+                            );
+                        }
+                        Some(self.clone_source_to_plan(source, version, planner_context)?)
+                    }
+                    (Some(_), Some(_)) => {
+                        return plan_err!("CREATE TABLE cannot use both CLONE and AS");
+                    }
+                    (None, None) => None,
+                };
+
+                match input {
+                    Some(plan) => {
                         let input_schema = plan.schema();
 
                         let plan = if has_columns {
@@ -576,23 +1031,27 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                     input_schema.fields().len()
                                 );
                             }
-                            let input_fields = input_schema.fields();
-                            let project_exprs = schema
-                                .fields()
-                                .iter()
-                                .zip(input_fields)
-                                .map(|(field, input_field)| {
-                                    cast(
-                                        col(input_field.name()),
-                                        field.data_type().clone(),
-                                    )
-                                    .alias(field.name())
-                                })
-                                .collect::<Vec<_>>();
-
-                            LogicalPlanBuilder::from(plan.clone())
-                                .project(project_exprs)?
-                                .build()?
+                            if has_target_fields(input_schema, &schema) {
+                                plan
+                            } else {
+                                let input_fields = input_schema.fields();
+                                let project_exprs = schema
+                                    .fields()
+                                    .iter()
+                                    .zip(input_fields)
+                                    .map(|(field, input_field)| {
+                                        let mut expr = col(input_field.name());
+                                        if input_field.data_type() != field.data_type() {
+                                            expr = cast(expr, field.data_type().clone());
+                                        }
+                                        alias_if_renamed(expr, field.name())
+                                    })
+                                    .collect::<Vec<_>>();
+
+                                LogicalPlanBuilder::from(plan.clone())
+                                    .project(project_exprs)?
+                                    .build()?
+                            }
                         } else {
                             plan
                         };
@@ -816,15 +1275,55 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 DropAssertion { name, if_exists },
             ))),
             // SQL/MED (Management of External Data) statements
-            Statement::CreateServer(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::CreateServer(stmt)))
-            }
-            Statement::AlterServer(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::AlterServer(stmt)))
-            }
-            Statement::DropServer(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::DropServer(stmt)))
+            Statement::CreateServer(ast::CreateServerStatement {
+                name,
+                if_not_exists,
+                foreign_data_wrapper,
+                options,
+                ..
+            }) => Ok(LogicalPlan::Ddl(DdlStatement::CreateServer(CreateServer {
+                name: object_name_to_string(&name),
+                wrapper: object_name_to_string(&foreign_data_wrapper),
+                options: server_options_map(options),
+                if_not_exists,
+            }))),
+            Statement::AlterServer(ast::AlterServerStatement { name, operations }) => {
+                let options = operations
+                    .into_iter()
+                    .map(|operation| match operation {
+                        ast::AlterServerOperation::AddOption { key, value } => {
+                            Ok(OptionChange::Add {
+                                key: ident_to_string(&key),
+                                value: value.value,
+                            })
+                        }
+                        ast::AlterServerOperation::SetOption { key, value } => {
+                            Ok(OptionChange::Set {
+                                key: ident_to_string(&key),
+                                value: value.value,
+                            })
+                        }
+                        ast::AlterServerOperation::DropOption { key } => {
+                            Ok(OptionChange::Drop {
+                                key: ident_to_string(&key),
+                            })
+                        }
+                        other => {
+                            not_impl_err!("Unsupported ALTER SERVER operation: {other}")
+                        }
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(LogicalPlan::Ddl(DdlStatement::AlterServer(AlterServer {
+                    name: object_name_to_string(&name),
+                    options,
+                })))
             }
+            Statement::DropServer(ast::DropServerStatement {
+                name, if_exists, ..
+            }) => Ok(LogicalPlan::Ddl(DdlStatement::DropServer(DropServer {
+                name: object_name_to_string(&name),
+                if_exists,
+            }))),
             Statement::CreateForeignDataWrapper(stmt) => Ok(LogicalPlan::Ddl(
                 DdlStatement::CreateForeignDataWrapper(stmt),
             )),
@@ -834,24 +1333,84 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             Statement::DropForeignDataWrapper(stmt) => {
                 Ok(LogicalPlan::Ddl(DdlStatement::DropForeignDataWrapper(stmt)))
             }
-            Statement::CreateForeignTable(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::CreateForeignTable(stmt)))
-            }
-            Statement::AlterForeignTable(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::AlterForeignTable(stmt)))
-            }
-            Statement::DropForeignTable(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::DropForeignTable(stmt)))
-            }
-            Statement::CreateUserMapping(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::CreateUserMapping(stmt)))
+            Statement::CreateForeignTable(stmt) => self.foreign_table_to_plan(stmt),
+            Statement::AlterForeignTable(ast::AlterForeignTableStatement {
+                name,
+                if_exists,
+                operations,
+            }) => {
+                let options = operations
+                    .into_iter()
+                    .map(|operation| match operation {
+                        ast::AlterForeignTableOperation::AddOption { key, value } => {
+                            Ok(OptionChange::Add {
+                                key: ident_to_string(&key),
+                                value: value.value,
+                            })
+                        }
+                        ast::AlterForeignTableOperation::SetOption { key, value } => {
+                            Ok(OptionChange::Set {
+                                key: ident_to_string(&key),
+                                value: value.value,
+                            })
+                        }
+                        ast::AlterForeignTableOperation::DropOption { key } => {
+                            Ok(OptionChange::Drop {
+                                key: ident_to_string(&key),
+                            })
+                        }
+                        other => not_impl_err!(
+                            "Unsupported ALTER FOREIGN TABLE operation: {other}"
+                        ),
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(LogicalPlan::Ddl(DdlStatement::AlterForeignTable(
+                    AlterForeignTable {
+                        name: self.object_name_to_table_reference(name)?,
+                        if_exists,
+                        options,
+                    },
+                )))
             }
+            Statement::DropForeignTable(ast::DropForeignTableStatement {
+                name,
+                if_exists,
+                ..
+            }) => Ok(LogicalPlan::Ddl(DdlStatement::DropForeignTable(
+                DropForeignTable {
+                    name: self.object_name_to_table_reference(name)?,
+                    if_exists,
+                },
+            ))),
+            Statement::CreateUserMapping(ast::CreateUserMappingStatement {
+                user,
+                server_name,
+                options,
+                if_not_exists,
+                ..
+            }) => Ok(LogicalPlan::Ddl(DdlStatement::CreateUserMapping(
+                CreateUserMapping {
+                    user: user_mapping_target(&user),
+                    server: ident_to_string(&server_name),
+                    options: server_options_map(options),
+                    if_not_exists,
+                },
+            ))),
             Statement::AlterUserMapping(stmt) => {
                 Ok(LogicalPlan::Ddl(DdlStatement::AlterUserMapping(stmt)))
             }
-            Statement::DropUserMapping(stmt) => {
-                Ok(LogicalPlan::Ddl(DdlStatement::DropUserMapping(stmt)))
-            }
+            Statement::DropUserMapping(ast::DropUserMappingStatement {
+                user,
+                server_name,
+                if_exists,
+                ..
+            }) => Ok(LogicalPlan::Ddl(DdlStatement::DropUserMapping(
+                DropUserMapping {
+                    user: user_mapping_target(&user),
+                    server: ident_to_string(&server_name),
+                    if_exists,
+                },
+            ))),
             Statement::ImportForeignSchema(stmt) => {
                 Ok(LogicalPlan::Ddl(DdlStatement::ImportForeignSchema(stmt)))
             }
@@ -1778,8 +2337,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let name: Option<String> = name.as_ref().map(object_name_to_string);
                 let table = self.object_name_to_table_reference(table_name)?;
                 let table_schema = self
-                    .context_provider
-                    .get_table_source(table.clone())?
+                    .get_table_source(table.clone(), planner_context)?
                     .schema()
                     .to_dfschema_ref()?;
                 let using: Option<String> =
@@ -1856,9 +2414,36 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 )))
             }
             Statement::Vacuum(vacuum) => {
+                if vacuum.reindex || vacuum.recluster {
+                    return not_impl_err!(
+                        "VACUUM REINDEX and VACUUM RECLUSTER are not supported"
+                    );
+                }
                 let table_name = vacuum.table_name.map(|n| object_name_to_string(&n));
+                let threshold_percent = vacuum
+                    .threshold
+                    .map(|threshold| {
+                        let threshold = threshold.to_string();
+                        threshold
+                            .parse::<u8>()
+                            .ok()
+                            .filter(|percent| *percent <= 100)
+                            .ok_or_else(|| {
+                                plan_datafusion_err!(
+                                    "VACUUM threshold must be a percentage between 0 and 100, got {threshold}"
+                                )
+                            })
+                    })
+                    .transpose()?;
+                let options = VacuumOptions {
+                    compact: !vacuum.delete_only,
+                    purge_deleted: !vacuum.sort_only,
+                    threshold_percent,
+                    retain: None,
+                };
                 Ok(LogicalPlan::Statement(PlanStatement::Vacuum(Vacuum {
                     table_name,
+                    options,
                 })))
             }
             Statement::Use(use_stmt) => {
@@ -2086,15 +2671,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     })
                     .collect::<Result<Vec<_>>>()?;
 
-                Ok(LogicalPlan::Ddl(DdlStatement::CreatePropertyGraph(
-                    CreatePropertyGraph {
-                        name,
-                        or_replace: create_property_graph.or_replace,
-                        if_not_exists: create_property_graph.if_not_exists,
-                        vertex_tables,
-                        edge_tables,
-                    },
-                )))
+                let mut graph = CreatePropertyGraph {
+                    name,
+                    or_replace: create_property_graph.or_replace,
+                    if_not_exists: create_property_graph.if_not_exists,
+                    vertex_tables,
+                    edge_tables,
+                };
+                self.validate_property_graph(&mut graph)?;
+                Ok(LogicalPlan::Ddl(DdlStatement::CreatePropertyGraph(graph)))
             }
             Statement::DropPropertyGraph(drop_property_graph) => {
                 let name = self
@@ -2140,12 +2725,39 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             let query = "SELECT * FROM information_schema.tables;";
             let mut rewrite = DFParser::parse_sql(query)?;
             assert_eq!(rewrite.len(), 1);
-            self.statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
+            self.df_statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
         } else {
             plan_err!("SHOW TABLES is not supported unless information_schema is enabled")
         }
     }
 
+    /// Plan the source of `CREATE TABLE ... CLONE <source> [AT(...) | BEFORE(...)]`
+    /// as a scan of `source`, at the requested version if any
+    fn clone_source_to_plan(
+        &self,
+        source: ObjectName,
+        version: Option<ast::TableVersion>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let table_ref = self.object_name_to_table_reference(source)?;
+        let version = version
+            .map(|version| self.plan_table_version(version, planner_context))
+            .transpose()?;
+        let table_source = match &version {
+            Some(version) => self
+                .context_provider
+                .get_table_source_at(table_ref.clone(), version)?,
+            None => self.get_table_source(table_ref.clone(), planner_context)?,
+        };
+        match LogicalPlanBuilder::scan(table_ref, table_source, None)?.build()? {
+            LogicalPlan::TableScan(mut scan) => {
+                scan.version = version;
+                Ok(LogicalPlan::TableScan(scan))
+            }
+            plan => Ok(plan),
+        }
+    }
+
     fn describe_table_to_plan(&self, table_name: ObjectName) -> Result<LogicalPlan> {
         let table_ref = self.object_name_to_table_reference(table_name)?;
 
@@ -2161,10 +2773,158 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }))
     }
 
-    fn describe_query_to_plan(&self, query: Query) -> Result<LogicalPlan> {
-        let plan = self.query_to_plan(query, &mut PlannerContext::new())?;
-
-        let schema = Arc::new(plan.schema().as_arrow().clone());
+    /// Check a `CREATE PROPERTY GRAPH` definition against its element
+    /// tables: each table must exist and be declared once, `KEY` and
+    /// `PROPERTIES` columns must be columns of their table, and edges must
+    /// reference vertex tables of the graph through keys of comparable
+    /// types. Element tables sharing a label must expose the same
+    /// properties. Vertex tables without a `KEY` use their table's primary
+    /// key, if it has one.
+    fn validate_property_graph(&self, graph: &mut CreatePropertyGraph) -> Result<()> {
+        if !graph.or_replace
+            && !graph.if_not_exists
+            && self
+                .context_provider
+                .get_property_graph(&graph.name)
+                .is_some()
+        {
+            return plan_err!("Property graph {} already exists", graph.name);
+        }
+
+        let schemas = graph
+            .vertex_tables
+            .iter_mut()
+            .map(|vertex| {
+                let source = self
+                    .context_provider
+                    .get_table_source(vertex.table.clone())?;
+                let schema = source.schema();
+                if vertex.key.is_none()
+                    && let Some(primary_key) =
+                        source.constraints().and_then(|constraints| {
+                            constraints.iter().find_map(|constraint| match constraint {
+                                Constraint::PrimaryKey(indices) => Some(indices),
+                                _ => None,
+                            })
+                        })
+                {
+                    vertex.key = Some(GraphKeyClause {
+                        columns: primary_key
+                            .iter()
+                            .map(|&i| schema.field(i).name().clone())
+                            .collect(),
+                    });
+                }
+                Ok(schema)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let name = &graph.name;
+        let mut labels = HashMap::new();
+        let mut vertex_tables = HashMap::new();
+        for (vertex, schema) in graph.vertex_tables.iter().zip(schemas) {
+            if let Some(key) = &vertex.key {
+                element_fields(&schema, &vertex.table, "KEY", &key.columns)?;
+            }
+            check_label(
+                &mut labels,
+                name,
+                &vertex.table,
+                &vertex.label,
+                element_properties(&schema, &vertex.table, &vertex.properties)?,
+            )?;
+            if vertex_tables
+                .insert(&vertex.table, (schema, vertex.key.as_ref()))
+                .is_some()
+            {
+                return plan_err!(
+                    "Vertex table {} is declared more than once in property graph {name}",
+                    vertex.table
+                );
+            }
+        }
+
+        let mut edge_tables = HashSet::new();
+        for edge in &graph.edge_tables {
+            if !edge_tables.insert(&edge.table) {
+                return plan_err!(
+                    "Edge table {} is declared more than once in property graph {name}",
+                    edge.table
+                );
+            }
+            let schema = self
+                .context_provider
+                .get_table_source(edge.table.clone())?
+                .schema();
+            if let Some(key) = &edge.key {
+                element_fields(&schema, &edge.table, "KEY", &key.columns)?;
+            }
+            for (endpoint, clause) in [
+                (&edge.source, "SOURCE KEY"),
+                (&edge.destination, "DESTINATION KEY"),
+            ] {
+                let Some((vertex_schema, vertex_key)) =
+                    vertex_tables.get(&endpoint.references)
+                else {
+                    return plan_err!(
+                        "Edge table {} references {}, which is not a vertex table of property graph {name}",
+                        edge.table,
+                        endpoint.references
+                    );
+                };
+                let Some(key) = &endpoint.key else {
+                    continue;
+                };
+                let fields = element_fields(&schema, &edge.table, clause, &key.columns)?;
+                let Some(vertex_key) = vertex_key else {
+                    continue;
+                };
+                if key.columns.len() != vertex_key.columns.len() {
+                    return plan_err!(
+                        "{clause} of edge table {} has {} columns, but the KEY of vertex table {} has {}",
+                        edge.table,
+                        key.columns.len(),
+                        endpoint.references,
+                        vertex_key.columns.len()
+                    );
+                }
+                let vertex_fields = element_fields(
+                    vertex_schema,
+                    &endpoint.references,
+                    "KEY",
+                    &vertex_key.columns,
+                )?;
+                for (field, vertex_field) in fields.iter().zip(vertex_fields) {
+                    if comparison_coercion(field.data_type(), vertex_field.data_type())
+                        .is_none()
+                    {
+                        return plan_err!(
+                            "{clause} column {} of edge table {} has type {}, which cannot be compared with KEY column {} of vertex table {} of type {}",
+                            field.name(),
+                            edge.table,
+                            field.data_type(),
+                            vertex_field.name(),
+                            endpoint.references,
+                            vertex_field.data_type()
+                        );
+                    }
+                }
+            }
+            check_label(
+                &mut labels,
+                name,
+                &edge.table,
+                &edge.label,
+                element_properties(&schema, &edge.table, &edge.properties)?,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn describe_query_to_plan(&self, query: Query) -> Result<LogicalPlan> {
+        let plan = self.query_to_plan(query, &mut PlannerContext::new())?;
+
+        let schema = Arc::new(plan.schema().as_arrow().clone());
 
         let output_schema = DFSchema::try_from(LogicalPlan::describe_schema()).unwrap();
 
@@ -2174,7 +2934,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }))
     }
 
-    fn copy_to_plan(&self, statement: CopyToStatement) -> Result<LogicalPlan> {
+    fn copy_to_plan(
+        &self,
+        statement: CopyToStatement,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
         // Determine if source is table or query and handle accordingly
         let copy_source = statement.source;
         let (input, input_schema, table_ref) = match copy_source {
@@ -2182,20 +2946,25 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let table_name = object_name_to_string(&object_name);
                 let table_ref = self.object_name_to_table_reference(object_name)?;
                 let table_source =
-                    self.context_provider.get_table_source(table_ref.clone())?;
+                    self.get_table_source(table_ref.clone(), planner_context)?;
                 let plan =
                     LogicalPlanBuilder::scan(table_name, table_source, None)?.build()?;
+                // The rows a query of the table could not see are not copied
+                let plan = self.apply_security_policies(plan)?;
                 let input_schema = Arc::clone(plan.schema());
                 (plan, input_schema, Some(table_ref))
             }
             CopyToSource::Query(query) => {
-                let plan = self.query_to_plan(*query, &mut PlannerContext::new())?;
+                let plan = self.resolved_statement_to_plan(
+                    Statement::Query(query),
+                    planner_context,
+                )?;
                 let input_schema = Arc::clone(plan.schema());
                 (plan, input_schema, None)
             }
         };
 
-        let options_map = self.parse_options_map(statement.options, true)?;
+        let mut options_map = self.parse_options_map(statement.options, true)?;
 
         let maybe_file_type = if let Some(stored_as) = &statement.stored_as {
             self.context_provider.get_file_type(stored_as).ok()
@@ -2224,7 +2993,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         };
 
-        let partition_by = statement
+        let partition_by: Vec<String> = statement
             .partitioned_by
             .iter()
             .map(|col| input_schema.field_with_name(table_ref.as_ref(), col))
@@ -2232,21 +3001,50 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             .into_iter()
             .map(|f| f.name().to_owned())
             .collect();
-
-        Ok(LogicalPlan::Copy(CopyTo::new(
-            Arc::new(input),
-            statement.target,
-            partition_by,
-            file_type,
-            options_map,
-        )))
+        let partition_layout =
+            copy_to_partition_layout(&mut options_map, !partition_by.is_empty())?;
+
+        Ok(LogicalPlan::Copy(
+            CopyTo::new(
+                Arc::new(input),
+                statement.target,
+                partition_by,
+                file_type,
+                options_map,
+            )
+            .with_partition_layout(partition_layout),
+        ))
     }
 
     fn copy_from_plan(&self, statement: CopyFromStatement) -> Result<LogicalPlan> {
         let table_name = self.object_name_to_table_reference(statement.table_name)?;
 
+        // Do a table lookup to verify the table and the copied columns exist
+        let table_source =
+            self.get_table_source(table_name.clone(), &PlannerContext::new())?;
+        let table_schema = DFSchema::try_from(table_source.schema())?;
+        let mut columns: Vec<String> = Vec::with_capacity(statement.columns.len());
+        for column in statement.columns {
+            let column = self.ident_normalizer.normalize(Ident::new(column));
+            if table_schema
+                .index_of_column_by_name(None, &column)
+                .is_none()
+            {
+                return Err(unqualified_field_not_found(&column, &table_schema));
+            }
+            if columns.contains(&column) {
+                return schema_err!(SchemaError::DuplicateUnqualifiedField {
+                    name: column,
+                });
+            }
+            columns.push(column);
+        }
+
         // Parse options into a HashMap
-        let options_map = self.parse_options_map(statement.options, true)?;
+        let mut options_map = self.parse_options_map(statement.options, true)?;
+        let column_exprs =
+            self.copy_from_column_exprs(&mut options_map, &columns, &table_schema)?;
+        let error_handling = copy_from_error_handling(&mut options_map)?;
 
         // Determine file type from stored_as or file extension
         let maybe_file_type = if let Some(stored_as) = &statement.stored_as {
@@ -2255,9 +3053,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             None
         };
 
-        let file_type = match maybe_file_type {
-            Some(ft) => ft,
-            None => {
+        let source = match statement.source {
+            crate::parser::CopyFromSource::File(url) => CopyFromSource::File(url),
+            crate::parser::CopyFromSource::Stdin => CopyFromSource::Stdin,
+            crate::parser::CopyFromSource::Program(command) => {
+                CopyFromSource::Program(command)
+            }
+        };
+
+        let file_type = match (maybe_file_type, &source) {
+            (Some(ft), _) => ft,
+            (None, CopyFromSource::File(url)) => {
                 let e = || {
                     DataFusionError::Configuration(
                         "Format not explicitly set and unable to get file extension! Use STORED AS to define file format."
@@ -2265,7 +3071,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     )
                 };
                 // Try to infer file format from file extension
-                let extension: &str = &Path::new(&statement.source)
+                let extension: &str = &Path::new(url)
                     .extension()
                     .ok_or_else(e)?
                     .to_str()
@@ -2274,15 +3080,109 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
                 self.context_provider.get_file_type(extension)?
             }
+            // There is no file extension to infer the format from
+            (None, source) => {
+                return Err(DataFusionError::Configuration(format!(
+                    "Format not explicitly set for COPY FROM {source}! Use STORED AS to define file format."
+                )));
+            }
         };
 
-        Ok(LogicalPlan::CopyFrom(CopyFrom::new(
-            table_name,
-            statement.source,
-            statement.columns,
-            file_type,
-            options_map,
-        )))
+        Ok(LogicalPlan::CopyFrom(
+            CopyFrom::new(table_name, source, columns, file_type, options_map)
+                .with_column_exprs(column_exprs)
+                .with_error_handling(error_handling),
+        ))
+    }
+
+    /// Plans the `column.<name>.expression` and `column.<name>.format`
+    /// options of `COPY FROM`, removing them from `options`.
+    ///
+    /// The expressions see each copied column as the text of its value. A
+    /// format is planned as a call to `to_date`, `to_time` or `to_timestamp`,
+    /// depending on the type of the column.
+    fn copy_from_column_exprs(
+        &self,
+        options: &mut HashMap<String, String>,
+        columns: &[String],
+        table_schema: &DFSchema,
+    ) -> Result<Vec<(String, Expr)>> {
+        let mut keys: Vec<String> = options
+            .keys()
+            .filter(|key| key.starts_with("column."))
+            .cloned()
+            .collect();
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+        keys.sort();
+
+        let copied: Vec<&String> = if columns.is_empty() {
+            table_schema.fields().iter().map(|f| f.name()).collect()
+        } else {
+            columns.iter().collect()
+        };
+        let text_schema = DFSchema::from_unqualified_fields(
+            copied
+                .iter()
+                .map(|name| Field::new(name.as_str(), DataType::Utf8, true))
+                .collect(),
+            HashMap::new(),
+        )?;
+
+        let mut column_exprs: Vec<(String, Expr)> = vec![];
+        for key in keys {
+            let Some(value) = options.remove(&key) else {
+                continue;
+            };
+            let Some((name, kind)) = key["column.".len()..].rsplit_once('.') else {
+                return plan_err!("Invalid COPY FROM option {key}");
+            };
+            if !copied.iter().any(|c| c.as_str() == name) {
+                return plan_err!(
+                    "Column {name} of COPY FROM option {key} is not copied"
+                );
+            }
+            if column_exprs.iter().any(|(c, _)| c == name) {
+                return plan_err!(
+                    "Column {name} has more than one COPY FROM transformation"
+                );
+            }
+
+            let expr = match kind {
+                "expression" => {
+                    let sql_expr = DFParser::parse_sql_into_expr(&value)?.expr;
+                    self.sql_to_expr(sql_expr, &text_schema, &mut PlannerContext::new())?
+                }
+                "format" => {
+                    let (_, field) =
+                        table_schema.qualified_field_with_unqualified_name(name)?;
+                    let function = match field.data_type() {
+                        DataType::Date32 | DataType::Date64 => "to_date",
+                        DataType::Time32(_) | DataType::Time64(_) => "to_time",
+                        DataType::Timestamp(_, _) => "to_timestamp",
+                        other => {
+                            return plan_err!(
+                                "COPY FROM format is not supported for column {name} of type {other}"
+                            );
+                        }
+                    };
+                    let Some(udf) = self.context_provider.get_function_meta(function)
+                    else {
+                        return plan_err!(
+                            "COPY FROM format of column {name} requires the {function} function"
+                        );
+                    };
+                    udf.call(vec![
+                        Expr::Column(Column::new_unqualified(name)),
+                        lit(value),
+                    ])
+                }
+                _ => return plan_err!("Unknown COPY FROM option {key}"),
+            };
+            column_exprs.push((name.to_string(), expr));
+        }
+        Ok(column_exprs)
     }
 
     fn build_order_by(
@@ -2390,7 +3290,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             .into_iter()
             .collect();
 
-        let schema = self.build_schema(columns)?;
+        // Without columns, the schema is inferred from the files when the
+        // table is registered
+        let infer_schema = columns.is_empty();
+        let schema =
+            self.with_application_periods(self.build_schema(columns)?, &all_constraints)?;
         let df_schema = schema.to_dfschema_ref()?;
         df_schema.check_names()?;
 
@@ -2412,10 +3316,39 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 .with_options(options_map)
                 .with_constraints(constraints)
                 .with_column_defaults(column_defaults)
+                .with_infer_schema(infer_schema)
                 .build(),
         )))
     }
 
+    /// Generate a plan for a SQL/MED `CREATE FOREIGN TABLE` statement
+    fn foreign_table_to_plan(
+        &self,
+        statement: ast::CreateForeignTableStatement,
+    ) -> Result<LogicalPlan> {
+        let ast::CreateForeignTableStatement {
+            name,
+            if_not_exists,
+            columns,
+            server_name,
+            options,
+            ..
+        } = statement;
+
+        let schema = self.build_schema(columns)?.to_dfschema_ref()?;
+        schema.check_names()?;
+
+        Ok(LogicalPlan::Ddl(DdlStatement::CreateForeignTable(
+            CreateForeignTable {
+                name: self.object_name_to_table_reference(name)?,
+                schema,
+                server: ident_to_string(&server_name),
+                options: server_options_map(options),
+                if_not_exists,
+            },
+        )))
+    }
+
     /// Get the indices of the constraint columns in the schema.
     /// If any column is not found, return an error.
     fn get_constraint_column_indices(
@@ -2449,18 +3382,125 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             .collect::<Result<Vec<_>>>()
     }
 
+    /// Records the application-time periods declared by `PERIOD FOR name
+    /// (start, end)` in the metadata of their start and end columns, which
+    /// must be distinct columns of the same date or timestamp type, and are
+    /// not null
+    fn with_application_periods(
+        &self,
+        schema: Schema,
+        constraints: &[TableConstraint],
+    ) -> Result<Schema> {
+        let mut fields = schema
+            .fields()
+            .iter()
+            .map(|field| field.as_ref().clone())
+            .collect::<Vec<_>>();
+        let mut names = HashSet::new();
+        for (name, start, end) in constraints.iter().filter_map(period_definition) {
+            let name = self.ident_normalizer.normalize(name);
+            if name.eq_ignore_ascii_case("system_time") {
+                continue;
+            }
+            if !names.insert(name.clone()) {
+                return plan_err!("Period {name} is defined more than once");
+            }
+            let column_index = |column: Ident| {
+                let column = self.ident_normalizer.normalize(column);
+                schema.index_of(&column).map_err(|_| {
+                    plan_datafusion_err!("Column {column} of period {name} not found")
+                })
+            };
+            let (start, end) = (column_index(start)?, column_index(end)?);
+            if start == end {
+                return plan_err!(
+                    "Period {name} requires distinct start and end columns"
+                );
+            }
+            let (start_type, end_type) =
+                (fields[start].data_type(), fields[end].data_type());
+            if start_type != end_type
+                || !matches!(
+                    start_type,
+                    DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _)
+                )
+            {
+                return plan_err!(
+                    "Columns of period {name} must be of the same date or timestamp type, got {start_type} and {end_type}"
+                );
+            }
+            for (index, key) in [
+                (start, PERIOD_START_METADATA_KEY),
+                (end, PERIOD_END_METADATA_KEY),
+            ] {
+                let mut metadata = fields[index].metadata().clone();
+                metadata.insert(key.to_string(), name.clone());
+                fields[index] = fields[index]
+                    .clone()
+                    .with_nullable(false)
+                    .with_metadata(metadata);
+            }
+        }
+        Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+    }
+
+    /// Checks `PERIOD FOR SYSTEM_TIME (start, end)`, whose columns must be
+    /// the generated columns of the system time period. Application-time
+    /// periods are checked by [`Self::with_application_periods`].
+    fn check_period_definition(
+        &self,
+        name: Ident,
+        start: Ident,
+        end: Ident,
+        df_schema: &DFSchemaRef,
+    ) -> Result<()> {
+        let name = self.ident_normalizer.normalize(name);
+        if !name.eq_ignore_ascii_case("system_time") {
+            return Ok(());
+        }
+        for (column, bound, generated) in [
+            (start, SYSTEM_TIME_ROW_START, "ROW START"),
+            (end, SYSTEM_TIME_ROW_END, "ROW END"),
+        ] {
+            let column = self.ident_normalizer.normalize(column);
+            let field = df_schema.field_with_unqualified_name(&column)?;
+            if field
+                .metadata()
+                .get(SYSTEM_TIME_METADATA_KEY)
+                .map(String::as_str)
+                != Some(bound)
+            {
+                return plan_err!(
+                    "Column {column} of PERIOD FOR SYSTEM_TIME must be GENERATED ALWAYS AS {generated}"
+                );
+            }
+        }
+        Ok(())
+    }
+
     /// Convert each [TableConstraint] to corresponding [Constraint]
     pub fn new_constraint_from_table_constraints(
         &self,
         constraints: &[TableConstraint],
         df_schema: &DFSchemaRef,
     ) -> Result<Constraints> {
+        for constraint in constraints {
+            if let Some((name, start, end)) = period_definition(constraint) {
+                self.check_period_definition(name, start, end, df_schema)?;
+            }
+        }
         let constraints = constraints
             .iter()
             // EXCLUDE constraints are not representable as a DataFusion
             // `Constraint`; the embedding engine carries them out-of-band
             // (storage-parameter constraint hints), so drop them here.
-            .filter(|c| !matches!(c, TableConstraint::Exclude(_)))
+            // PERIOD definitions are kept in the metadata of their columns.
+            .filter(|c| {
+                !matches!(
+                    c,
+                    TableConstraint::Exclude(_) | TableConstraint::Period { .. }
+                )
+            })
             .map(|c: &TableConstraint| match c {
                 TableConstraint::Unique(unique) => {
                     let constraint_name = match &unique.name {
@@ -2574,8 +3614,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 TableConstraint::FulltextOrSpatial { .. } => {
                     _plan_err!("Indexes are not currently supported")
                 }
+                // Filtered out above; kept only for match exhaustiveness.
                 TableConstraint::Period { .. } => {
-                    _plan_err!("PERIOD constraints are not currently supported")
+                    _plan_err!("PERIOD definitions are handled out-of-band")
                 }
             })
             .collect::<Result<Vec<_>>>()?;
@@ -2670,7 +3711,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         format: Option<String>,
         statement: DFStatement,
     ) -> Result<LogicalPlan> {
-        let plan = self.statement_to_plan(statement)?;
+        let plan = self.df_statement_to_plan(statement)?;
         if matches!(plan, LogicalPlan::Explain(_)) {
             return plan_err!("Nested EXPLAINs are not supported");
         }
@@ -2771,7 +3812,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let mut rewrite = DFParser::parse_sql(&query)?;
         assert_eq!(rewrite.len(), 1);
 
-        self.statement_to_plan(rewrite.pop_front().unwrap())
+        self.df_statement_to_plan(rewrite.pop_front().unwrap())
     }
 
     /// Converts a SQL expression to a string value for SET statement processing
@@ -2845,6 +3886,30 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     },
                 )))
             }
+            Set::SetRole {
+                context_modifier,
+                role_name,
+            } => Ok(LogicalPlan::Statement(PlanStatement::SetRole(SetRole {
+                role: role_name.map(|role| self.ident_normalizer.normalize(role)),
+                session_authorization: false,
+                local: matches!(context_modifier, Some(ast::ContextModifier::Local)),
+            }))),
+            Set::SetSessionAuthorization(ast::SetSessionAuthorizationParam {
+                scope,
+                kind,
+            }) => {
+                let role = match kind {
+                    ast::SetSessionAuthorizationParamKind::Default => None,
+                    ast::SetSessionAuthorizationParamKind::User(user) => {
+                        Some(self.ident_normalizer.normalize(user))
+                    }
+                };
+                Ok(LogicalPlan::Statement(PlanStatement::SetRole(SetRole {
+                    role,
+                    session_authorization: true,
+                    local: matches!(scope, ast::ContextModifier::Local),
+                })))
+            }
             other => not_impl_err!("SET variant not implemented yet: {other:?}"),
         }
     }
@@ -2869,6 +3934,95 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    fn create_user_to_plan(&self, statement: CreateUserStatement) -> Result<LogicalPlan> {
+        let CreateUserStatement {
+            name,
+            if_not_exists,
+            options,
+        } = statement;
+        let (password, default_role, changes) = self.user_options_to_plan(options)?;
+        let mut attributes = BTreeMap::new();
+        for change in changes {
+            match change {
+                OptionChange::Add { key, .. } if attributes.contains_key(&key) => {
+                    return plan_err!("Option {key} provided more than once");
+                }
+                OptionChange::Add { key, value } => {
+                    attributes.insert(key, value);
+                }
+                change => {
+                    return plan_err!("CREATE USER can only add options, found {change}");
+                }
+            }
+        }
+        Ok(LogicalPlan::Ddl(DdlStatement::CreateUser(CreateUser {
+            name: self.ident_normalizer.normalize(name),
+            if_not_exists,
+            password,
+            default_role,
+            attributes,
+        })))
+    }
+
+    fn alter_user_to_plan(&self, statement: AlterUserStatement) -> Result<LogicalPlan> {
+        let AlterUserStatement { name, options } = statement;
+        let (password, default_role, attributes) = self.user_options_to_plan(options)?;
+        Ok(LogicalPlan::Ddl(DdlStatement::AlterUser(AlterUser {
+            name: self.ident_normalizer.normalize(name),
+            password,
+            default_role,
+            attributes,
+        })))
+    }
+
+    fn drop_user_to_plan(&self, statement: DropUserStatement) -> Result<LogicalPlan> {
+        let DropUserStatement { names, if_exists } = statement;
+        Ok(LogicalPlan::Ddl(DdlStatement::DropUser(DropUser {
+            names: names
+                .into_iter()
+                .map(|name| self.ident_normalizer.normalize(name))
+                .collect(),
+            if_exists,
+        })))
+    }
+
+    /// The password, default role and attribute changes of the options of
+    /// `CREATE USER` or `ALTER USER`
+    fn user_options_to_plan(
+        &self,
+        options: UserOptions,
+    ) -> Result<(Option<String>, Option<String>, Vec<OptionChange>)> {
+        let UserOptions {
+            password,
+            default_role,
+            options,
+        } = options;
+        let value_to_string = |key: &str, value: Value| {
+            crate::utils::value_to_string(&value).ok_or_else(|| {
+                plan_datafusion_err!("Unsupported value {value} of option {key}")
+            })
+        };
+        let changes = options
+            .into_iter()
+            .map(|change| match change {
+                UserOptionChange::Add { key, value } => Ok(OptionChange::Add {
+                    value: value_to_string(&key, value)?,
+                    key,
+                }),
+                UserOptionChange::Set { key, value } => Ok(OptionChange::Set {
+                    value: value_to_string(&key, value)?,
+                    key,
+                }),
+                UserOptionChange::Drop { key } => Ok(OptionChange::Drop { key }),
+            })
+            .collect::<Result<_>>()?;
+        Ok((
+            password,
+            default_role.map(|role| self.ident_normalizer.normalize(role)),
+            changes,
+        ))
+    }
+
     fn delete_to_plan(
         &self,
         table: TableWithJoins,
@@ -2885,7 +4039,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         // Do a table lookup to verify the table exists
         let table_ref = self.object_name_to_table_reference(table_name)?;
-        let table_source = self.context_provider.get_table_source(table_ref.clone())?;
+        let table_source =
+            self.get_table_source(table_ref.clone(), outer_planner_context)?;
         let table_schema = DFSchema::try_from(table_source.schema())?;
 
         // Clone the outer planner context to inherit CTEs
@@ -3004,7 +4159,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         };
 
         let table_ref = self.object_name_to_table_reference(table_name)?;
-        let table_source = self.context_provider.get_table_source(table_ref.clone())?;
+        let table_source = self.get_table_source(table_ref.clone(), planner_context)?;
         let mut target_plan =
             LogicalPlanBuilder::scan(table_ref.clone(), table_source, None)?.build()?;
         if let Some(alias) = table_alias {
@@ -3109,6 +4264,233 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         )))
     }
 
+    /// Plans an `UPDATE` or `DELETE` `FOR PORTION OF` an application-time
+    /// period as a [`Merge`] of the table with its rows whose period overlaps
+    /// the portion. Each of these rows is matched once, to be updated with
+    /// its period narrowed to the portion or to be deleted, and the parts of
+    /// its period before and after the portion are inserted as new rows with
+    /// the old values.
+    fn portion_of_to_plan(&self, statement: PortionOfStatement) -> Result<LogicalPlan> {
+        let PortionOfStatement {
+            table_name,
+            period,
+            from,
+            to,
+            alias,
+            change,
+            selection,
+        } = statement;
+        let mut planner_context = PlannerContext::new();
+        let table_ref = self.object_name_to_table_reference(table_name)?;
+        let table_source = self.get_table_source(table_ref.clone(), &planner_context)?;
+        let schema = table_source.schema();
+        let period = self.ident_normalizer.normalize(period);
+        let period_column = |key: &str| {
+            schema
+                .fields()
+                .iter()
+                .position(|field| field.metadata().get(key) == Some(&period))
+                .ok_or_else(|| {
+                    plan_datafusion_err!("Table {table_ref} has no period {period}")
+                })
+        };
+        let start = period_column(PERIOD_START_METADATA_KEY)?;
+        let end = period_column(PERIOD_END_METADATA_KEY)?;
+
+        let mut target =
+            LogicalPlanBuilder::scan(table_ref.clone(), Arc::clone(&table_source), None)?;
+        if let Some(alias) = alias {
+            target = target.alias(self.ident_normalizer.normalize(alias))?;
+        }
+        let target = target.build()?;
+        let target_schema = Arc::clone(target.schema());
+        let columns: Vec<Expr> = target_schema
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect();
+        let (start_column, end_column) = (columns[start].clone(), columns[end].clone());
+
+        // The bounds of the portion are constants of the type of the period
+        let empty_schema = DFSchema::empty();
+        let data_type = schema.field(start).data_type();
+        let mut bound = |expr: SQLExpr| {
+            self.sql_to_expr(expr, &empty_schema, &mut planner_context)?
+                .cast_to(data_type, &empty_schema)
+        };
+        let (from, to) = (bound(from)?, bound(to)?);
+
+        let mut predicate = start_column
+            .clone()
+            .lt(to.clone())
+            .and(end_column.clone().gt(from.clone()));
+        if let Some(selection) = selection {
+            predicate = self
+                .sql_to_expr(selection, &target_schema, &mut planner_context)?
+                .and(predicate);
+        }
+        let overlapping = LogicalPlanBuilder::from(target.clone())
+            .filter(predicate)?
+            .build()?;
+
+        // The source has the overlapping rows to match, with their period
+        // unchanged, and the parts of their period before and after the
+        // portion, told apart by `__portion_kind`
+        let part = |kind: &str, bounds: (Expr, Expr), filter: Option<Expr>| {
+            let mut exprs = columns.clone();
+            exprs[start] = bounds.0.alias(schema.field(start).name());
+            exprs[end] = bounds.1.alias(schema.field(end).name());
+            exprs.push(lit(kind).alias("__portion_kind"));
+            let mut builder = LogicalPlanBuilder::from(overlapping.clone());
+            if let Some(filter) = filter {
+                builder = builder.filter(filter)?;
+            }
+            builder.project(exprs)?.build()
+        };
+        let matched = part("match", (start_column.clone(), end_column.clone()), None)?;
+        let before = part(
+            "before",
+            (start_column.clone(), from.clone()),
+            Some(start_column.clone().lt(from.clone())),
+        )?;
+        let after = part(
+            "after",
+            (to.clone(), end_column.clone()),
+            Some(end_column.clone().gt(to.clone())),
+        )?;
+        let source = LogicalPlanBuilder::from(matched)
+            .union(before)?
+            .union(after)?
+            .alias("__portion")?
+            .build()?;
+        let source_column =
+            |name: &str| Expr::Column(Column::new(Some("__portion"), name));
+
+        // Rows are told apart by their primary key, or else by all their
+        // columns
+        let primary_key = table_source.constraints().and_then(|constraints| {
+            constraints.iter().find_map(|constraint| match constraint {
+                Constraint::PrimaryKey(indices) => Some(indices.clone()),
+                _ => None,
+            })
+        });
+        let key = primary_key.unwrap_or_else(|| (0..columns.len()).collect());
+        let on = key.into_iter().fold(
+            source_column("__portion_kind").eq(lit("match")),
+            |on, i| {
+                on.and(binary_expr(
+                    columns[i].clone(),
+                    Operator::IsNotDistinctFrom,
+                    source_column(schema.field(i).name()),
+                ))
+            },
+        );
+
+        let column_target = |name: &str| {
+            AssignmentTarget::ColumnName(ObjectName::from(vec![Ident::new(name)]))
+        };
+        let matched_action = match change {
+            PortionOfChange::Update(assignments) => {
+                let mut assigned = HashSet::new();
+                let mut merge_assignments = Vec::with_capacity(assignments.len() + 2);
+                for assignment in assignments {
+                    let AssignmentTarget::ColumnName(name) = &assignment.target else {
+                        return not_impl_err!(
+                            "Tuple assignments of UPDATE FOR PORTION OF not supported"
+                        );
+                    };
+                    let column = match name.0.last().and_then(|part| part.as_ident()) {
+                        Some(ident) => self.ident_normalizer.normalize(ident.clone()),
+                        None => return plan_err!("Empty column id"),
+                    };
+                    let index = target_schema
+                        .index_of_column_by_name(None, &column)
+                        .ok_or_else(|| {
+                            unqualified_field_not_found(&column, &target_schema)
+                        })?;
+                    if index == start || index == end {
+                        return plan_err!(
+                            "Column {column} of period {period} can not be assigned by UPDATE FOR PORTION OF"
+                        );
+                    }
+                    if !assigned.insert(index) {
+                        return plan_err!("Column '{column}' assigned more than once");
+                    }
+                    let value = self
+                        .sql_to_expr(
+                            assignment.value,
+                            &target_schema,
+                            &mut planner_context,
+                        )?
+                        .cast_to(schema.field(index).data_type(), &target_schema)?;
+                    merge_assignments.push(MergeAssignment {
+                        target: column_target(&column),
+                        value,
+                    });
+                }
+                // The period of the updated rows is narrowed to the portion
+                for (index, value) in [
+                    (
+                        start,
+                        when(start_column.clone().lt(from.clone()), from)
+                            .otherwise(start_column)?,
+                    ),
+                    (
+                        end,
+                        when(end_column.clone().gt(to.clone()), to)
+                            .otherwise(end_column)?,
+                    ),
+                ] {
+                    merge_assignments.push(MergeAssignment {
+                        target: column_target(schema.field(index).name()),
+                        value,
+                    });
+                }
+                MergeAction::Update(MergeUpdateExpr {
+                    assignments: merge_assignments,
+                    update_predicate: None,
+                    delete_predicate: None,
+                })
+            }
+            PortionOfChange::Delete => MergeAction::Delete,
+        };
+        let insert = MergeInsertExpr {
+            columns: schema
+                .fields()
+                .iter()
+                .map(|field| ObjectName::from(vec![Ident::new(field.name())]))
+                .collect(),
+            kind: MergeInsertKind::Values(vec![
+                schema
+                    .fields()
+                    .iter()
+                    .map(|field| source_column(field.name()))
+                    .collect(),
+            ]),
+            insert_predicate: None,
+        };
+        let clauses = vec![
+            MergeClause {
+                clause_kind: ast::MergeClauseKind::Matched,
+                predicate: None,
+                action: matched_action,
+            },
+            MergeClause {
+                clause_kind: ast::MergeClauseKind::NotMatched,
+                predicate: None,
+                action: MergeAction::Insert(insert),
+            },
+        ];
+
+        Ok(LogicalPlan::Merge(Merge::new(
+            table_ref,
+            Arc::new(target),
+            Arc::new(source),
+            on,
+            clauses,
+        )))
+    }
+
     fn update_to_plan(
         &self,
         table: TableWithJoins,
@@ -3125,7 +4507,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         // Do a table lookup to verify the table exists
         let table_name = self.object_name_to_table_reference(table_name)?;
-        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        let table_source =
+            self.get_table_source(table_name.clone(), outer_planner_context)?;
         let table_schema = Arc::new(DFSchema::try_from_qualified_schema(
             table_name.clone(),
             &table_source.schema(),
@@ -3135,6 +4518,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // Clone the outer planner context to inherit CTEs
         let mut planner_context = outer_planner_context.clone();
         let mut assign_map: HashMap<String, SQLExpr> = HashMap::new();
+        // The subqueries of tuple assignments `(a, b) = (SELECT x, y ...)` are
+        // evaluated once, as structs of their columns, when the struct
+        // functions are available
+        let tuple_functions = self
+            .context_provider
+            .get_function_meta("struct")
+            .zip(self.context_provider.get_function_meta("get_field"));
+        let mut tuple_subqueries: Vec<Query> = vec![];
+        // The index of the tuple subquery and of its column assigned to a
+        // column
+        let mut tuple_fields: HashMap<String, (usize, usize)> = HashMap::new();
 
         // Helper function to extract column name from ObjectName
         let extract_column_name = |obj_name: &ObjectName| -> Result<String> {
@@ -3156,7 +4550,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     let col_name = extract_column_name(cols)?;
                     // Validate that the assignment target column exists
                     table_schema.field_with_unqualified_name(&col_name)?;
-                    if assign_map.contains_key(&col_name) {
+                    if assign_map.contains_key(&col_name)
+                        || tuple_fields.contains_key(&col_name)
+                    {
                         return plan_err!(
                             "Column '{}' assigned more than once",
                             col_name
@@ -3189,9 +4585,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                             }
                         }
                         SQLExpr::Subquery(query) => {
-                            // For subqueries, the subquery is expected to return exactly 1 row with N columns
+                            // For subqueries, the subquery is expected to return at most 1 row with N columns
                             // matching the N target columns in the tuple assignment.
-                            // For (a, b) = (SELECT x, y FROM t), we transform it to:
+                            // For (a, b) = (SELECT x, y FROM t), the subquery is evaluated as the struct of
+                            // its columns, joined with the updated rows, and a and b are assigned its fields.
+                            // Without the struct functions, we transform it to:
                             //   a = (SELECT x FROM (SELECT x, y FROM t))
                             //   b = (SELECT y FROM (SELECT x, y FROM t))
                             //
@@ -3217,6 +4615,23 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 );
                             }
 
+                            if tuple_functions.is_some() {
+                                for (index, col) in columns.into_iter().enumerate() {
+                                    if assign_map.contains_key(&col)
+                                        || tuple_fields.contains_key(&col)
+                                    {
+                                        return plan_err!(
+                                            "Column '{}' assigned more than once",
+                                            col
+                                        );
+                                    }
+                                    tuple_fields
+                                        .insert(col, (tuple_subqueries.len(), index));
+                                }
+                                tuple_subqueries.push((**query).clone());
+                                continue;
+                            }
+
                             // For each target column, create a scalar subquery that selects just that column
                             (0..columns.len())
                                 .map(|idx| {
@@ -3255,7 +4670,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
                     // Add each column-value pair
                     for (col, val) in columns.into_iter().zip(values.into_iter()) {
-                        if assign_map.contains_key(&col) {
+                        if assign_map.contains_key(&col)
+                            || tuple_fields.contains_key(&col)
+                        {
                             return plan_err!("Column '{}' assigned more than once", col);
                         }
                         assign_map.insert(col, val);
@@ -3289,21 +4706,82 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         };
 
+        // Join the updated rows with the structs of the tuple subqueries, named
+        // `__update_tuple_<subquery>`, for the assignments to read their
+        // fields. An uncorrelated subquery is evaluated once, in a relation of
+        // one row, and a correlated one is projected next to the columns of
+        // the rows it is correlated to. Both are scalar subqueries, which fail
+        // when they return more than one row.
+        let mut tuple_values: HashMap<String, Expr> = HashMap::new();
+        if let Some((struct_udf, get_field)) = &tuple_functions
+            && !tuple_subqueries.is_empty()
+        {
+            let source_schema = Arc::clone(source.schema());
+            let mut correlated: Vec<Expr> = vec![];
+            let mut uncorrelated: Vec<Expr> = vec![];
+            for (index, query) in tuple_subqueries.into_iter().enumerate() {
+                let subquery = self.tuple_subquery_to_struct(
+                    query,
+                    &source_schema,
+                    struct_udf,
+                    &mut planner_context,
+                )?;
+                let tuples = if subquery.outer_ref_columns.is_empty() {
+                    &mut uncorrelated
+                } else {
+                    &mut correlated
+                };
+                tuples.push(
+                    Expr::ScalarSubquery(subquery)
+                        .alias(format!("__update_tuple_{index}")),
+                );
+            }
+            if !correlated.is_empty() {
+                let mut exprs: Vec<Expr> = source_schema
+                    .columns()
+                    .into_iter()
+                    .map(Expr::Column)
+                    .collect();
+                exprs.extend(correlated);
+                source = project(source, exprs)?;
+            }
+            if !uncorrelated.is_empty() {
+                let tuples = LogicalPlanBuilder::empty(true)
+                    .project(uncorrelated)?
+                    .build()?;
+                source = LogicalPlanBuilder::from(source)
+                    .cross_join(tuples)?
+                    .build()?;
+            }
+            tuple_values = tuple_fields
+                .into_iter()
+                .map(|(name, (tuple, index))| {
+                    let tuple = col(format!("__update_tuple_{tuple}"));
+                    (name, get_field.call(vec![tuple, lit(format!("c{index}"))]))
+                })
+                .collect();
+        }
+
         // Build updated values for each column, using the previous value if not modified
         let mut projected_exprs = table_schema
             .iter()
             .map(|(qualifier, field)| {
-                let expr = match assign_map.remove(field.name()) {
+                let new_value = match assign_map.remove(field.name()) {
                     Some(new_value) => {
                         let new_value = crate::values::maybe_rewrite_pg_array_literal(
                             new_value,
                             Some(field.data_type()),
                         );
-                        let mut expr = self.sql_to_expr(
+                        Some(self.sql_to_expr(
                             new_value,
                             source.schema(),
                             &mut planner_context,
-                        )?;
+                        )?)
+                    }
+                    None => tuple_values.remove(field.name()),
+                };
+                let expr = match new_value {
+                    Some(mut expr) => {
                         // Update placeholder's datatype to the type of the target column
                         if let Expr::Placeholder(placeholder) = &mut expr {
                             placeholder.field = placeholder
@@ -3312,6 +4790,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 .or_else(|| Some(Arc::clone(field)));
                         }
                         // Cast to target column type, if necessary
+                        if !matches!(expr, Expr::Literal(..) | Expr::Placeholder(_)) {
+                            let data_type = expr.get_type(source.schema())?;
+                            self.warn_lossy_assignment(
+                                &data_type,
+                                field,
+                                &planner_context,
+                            );
+                        }
                         expr.cast_to(field.data_type(), source.schema())?
                     }
                     None => {
@@ -3445,6 +4931,36 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(plan)
     }
 
+    /// Plans the subquery of a tuple assignment `(a, b) = (SELECT x, y ...)`
+    /// as the subquery of a scalar subquery returning the struct of its
+    /// columns, named `c0`, `c1`, ...
+    fn tuple_subquery_to_struct(
+        &self,
+        query: Query,
+        input_schema: &DFSchemaRef,
+        struct_udf: &Arc<ScalarUDF>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Subquery> {
+        let prev_stack_len =
+            planner_context.push_outer_query_schema(Arc::clone(input_schema));
+        let sub_plan = self.query_to_plan(query, planner_context)?;
+        planner_context.pop_outer_query_schema(prev_stack_len);
+
+        let columns = sub_plan
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect();
+        let sub_plan = project(sub_plan, [struct_udf.call(columns)])?;
+        let outer_ref_columns = sub_plan.all_out_ref_exprs();
+        Ok(Subquery {
+            subquery: Arc::new(sub_plan),
+            outer_ref_columns,
+            spans: Spans::new(),
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn insert_to_plan(
         &self,
@@ -3459,7 +4975,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     ) -> Result<LogicalPlan> {
         // Do a table lookup to verify the table exists
         let table_name = self.object_name_to_table_reference(table_name)?;
-        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        let table_source =
+            self.get_table_source(table_name.clone(), outer_planner_context)?;
         let table_schema = DFSchema::try_from(table_source.schema())?;
 
         // Get insert fields and target table's value indices
@@ -3553,30 +5070,48 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             plan_err!("Column count doesn't match insert query!")?;
         }
 
-        let exprs = value_indices
-            .into_iter()
+        // The source is written as it is when it has the columns of the table,
+        // in their order
+        let is_all_columns = value_indices
+            .iter()
             .enumerate()
-            .map(|(i, value_index)| {
-                let target_field = table_schema.field(i);
-                let expr = match value_index {
-                    Some(v) => {
-                        Expr::Column(Column::from(source.schema().qualified_field(v)))
-                            .cast_to(target_field.data_type(), source.schema())?
-                    }
-                    // The value is not specified. Fill in the default value for the column.
-                    None => table_source
-                        .get_column_default(target_field.name())
-                        .cloned()
-                        .unwrap_or_else(|| {
-                            // If there is no default for the column, then the default is NULL
-                            Expr::Literal(ScalarValue::Null, None)
-                        })
-                        .cast_to(target_field.data_type(), &DFSchema::empty())?,
-                };
-                Ok(expr.alias(target_field.name()))
-            })
-            .collect::<Result<Vec<Expr>>>()?;
-        let source = project(source, exprs)?;
+            .all(|(i, value_index)| *value_index == Some(i));
+        let source = if is_all_columns
+            && has_target_fields(source.schema(), &table_schema)
+        {
+            source
+        } else {
+            let exprs = value_indices
+                .into_iter()
+                .enumerate()
+                .map(|(i, value_index)| {
+                    let target_field = table_schema.field(i);
+                    let expr = match value_index {
+                        Some(v) => {
+                            let (qualifier, field) = source.schema().qualified_field(v);
+                            self.warn_lossy_assignment(
+                                field.data_type(),
+                                target_field,
+                                &planner_context,
+                            );
+                            Expr::Column(Column::from((qualifier, field)))
+                                .cast_to(target_field.data_type(), source.schema())?
+                        }
+                        // The value is not specified. Fill in the default value for the column.
+                        None => table_source
+                            .get_column_default(target_field.name())
+                            .cloned()
+                            .unwrap_or_else(|| {
+                                // If there is no default for the column, then the default is NULL
+                                Expr::Literal(ScalarValue::Null, None)
+                            })
+                            .cast_to(target_field.data_type(), &DFSchema::empty())?,
+                    };
+                    Ok(alias_if_renamed(expr, target_field.name()))
+                })
+                .collect::<Result<Vec<Expr>>>()?;
+            project(source, exprs)?
+        };
 
         let insert_op = match (overwrite, replace_into, on_conflict) {
             (false, false, None) => InsertOp::Append,
@@ -3634,6 +5169,26 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(plan)
     }
 
+    /// Warns if assigning a value of type `data_type` to the column `target`
+    /// implicitly casts it with a possible loss of information
+    fn warn_lossy_assignment(
+        &self,
+        data_type: &DataType,
+        target: &Field,
+        planner_context: &PlannerContext,
+    ) {
+        if is_lossy_numeric_cast(data_type, target.data_type()) {
+            planner_context.add_warning(Diagnostic::new_warning(
+                format!(
+                    "Implicit cast from {data_type} to {} for column {} may lose information",
+                    target.data_type(),
+                    target.name()
+                ),
+                None,
+            ));
+        }
+    }
+
     /// Converts a sqlparser OnConflict clause to a DataFusion OnConflict.
     ///
     /// For DO UPDATE SET expressions, we need to plan them in a context that includes
@@ -3742,7 +5297,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     ) -> Result<LogicalPlan> {
         // Do a table lookup to verify the table exists
         let table_name = self.object_name_to_table_reference(table_name)?;
-        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        let table_source = self.get_table_source(table_name.clone(), planner_context)?;
         let table_schema = DFSchema::try_from(table_source.schema())?;
 
         // For INSERT DEFAULT VALUES, we create a synthetic VALUES clause with DEFAULT for each column
@@ -3812,10 +5367,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         sql_table_name: ObjectName,
     ) -> Result<LogicalPlan> {
         // Figure out the where clause
-        let where_clause = object_name_to_qualifier(
-            &sql_table_name,
-            self.options.enable_ident_normalization,
-        )?;
+        let where_clause = self.object_name_to_qualifier(&sql_table_name)?;
 
         if !self.has_table("information_schema", "columns") {
             return plan_err!(
@@ -3840,7 +5392,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         let mut rewrite = DFParser::parse_sql(&query)?;
         assert_eq!(rewrite.len(), 1);
-        self.statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
+        self.df_statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
     }
 
     /// Rewrite `SHOW FUNCTIONS` to another SQL query
@@ -3924,7 +5476,7 @@ ON p.function_name = r.routine_name
         );
         let mut rewrite = DFParser::parse_sql(&query)?;
         assert_eq!(rewrite.len(), 1);
-        self.statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
+        self.df_statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
     }
 
     fn show_create_table_to_plan(
@@ -3937,10 +5489,7 @@ ON p.function_name = r.routine_name
             );
         }
         // Figure out the where clause
-        let where_clause = object_name_to_qualifier(
-            &sql_table_name,
-            self.options.enable_ident_normalization,
-        )?;
+        let where_clause = self.object_name_to_qualifier(&sql_table_name)?;
 
         // Do a table lookup to verify the table exists
         let table_ref = self.object_name_to_table_reference(sql_table_name)?;
@@ -3952,7 +5501,7 @@ ON p.function_name = r.routine_name
 
         let mut rewrite = DFParser::parse_sql(&query)?;
         assert_eq!(rewrite.len(), 1);
-        self.statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
+        self.df_statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
     }
 
     /// Return true if there is a table provider available for "schema.table"
@@ -47,6 +47,25 @@ impl QueryBuilder {
         self.body = Some(value);
         self
     }
+    /// Adds `cte` to the WITH clause, unless a CTE with the same name is
+    /// already defined there
+    pub fn cte(&mut self, cte: ast::Cte) -> &mut Self {
+        let with = self.with.get_or_insert_with(|| ast::With {
+            with_token: AttachedToken::empty(),
+            recursive: false,
+            cte_tables: vec![],
+            cycle: None,
+            search: None,
+        });
+        if !with
+            .cte_tables
+            .iter()
+            .any(|c| c.alias.name == cte.alias.name)
+        {
+            with.cte_tables.push(cte);
+        }
+        self
+    }
     pub fn take_body(&mut self) -> Option<Box<ast::SetExpr>> {
         self.body.take()
     }
@@ -39,14 +39,16 @@ use arrow::datatypes::{
     DataType, Decimal128Type, Decimal256Type, Decimal32Type, Decimal64Type, DecimalType,
 };
 use arrow::util::display::array_value_to_string;
+use datafusion_common::metadata::FieldMetadata;
 use datafusion_common::{
     assert_eq_or_internal_err, assert_or_internal_err, internal_datafusion_err,
     internal_err, not_impl_err, plan_err, Column, Result, ScalarValue,
 };
 use datafusion_expr::{
     expr::{Alias, Exists, InList, ScalarFunction, Sort, WindowFunction},
-    AllExpr, AnyExpr, Between, BinaryExpr, Case, Cast, Expr, GroupingSet, Like, Operator,
-    TryCast,
+    AllExpr, AnyExpr, Between, BinaryExpr, Case, Cast, Expr, GroupingSet, Like,
+    OnOverflow, Operator, TryCast,
+    planner::COLLATION_METADATA_KEY,
 };
 use sqlparser::ast::helpers::attached_token::AttachedToken;
 use sqlparser::tokenizer::Span;
@@ -238,7 +240,10 @@ impl Unparser<'_> {
                 Ok(self.cast_to_sql(expr, data_type)?)
             }
             Expr::Literal(value, _) => Ok(self.scalar_to_sql(value)?),
-            Expr::Alias(Alias { expr, name: _, .. }) => self.expr_to_sql_inner(expr),
+            Expr::Alias(Alias { expr, metadata, .. }) => {
+                let expr = self.expr_to_sql_inner(expr)?;
+                Ok(self.collate_to_sql(expr, metadata.as_ref()))
+            }
             Expr::WindowFunction(window_fun) => {
                 let WindowFunction {
                     fun,
@@ -249,8 +254,8 @@ impl Unparser<'_> {
                             order_by,
                             window_frame,
                             filter,
+                            null_treatment,
                             distinct,
-                            ..
                         },
                 } = window_fun.as_ref();
                 let func_name = fun.name();
@@ -319,7 +324,7 @@ impl Unparser<'_> {
                         .as_ref()
                         .map(|f| self.expr_to_sql_inner(f).map(Box::new))
                         .transpose()?,
-                    null_treatment: None,
+                    null_treatment: null_treatment.map(Into::into),
                     over,
                     within_group: vec![],
                     parameters: ast::FunctionArguments::None,
@@ -378,20 +383,53 @@ impl Unparser<'_> {
                     ..
                 } = &agg.params;
 
+                // The ON OVERFLOW behavior of LISTAGG is planned as the last argument
+                let (args, on_overflow) = match args.split_last() {
+                    Some((Expr::Literal(value, _), rest))
+                        if agg.func.supports_on_overflow_clause() =>
+                    {
+                        match OnOverflow::try_from_scalar(value) {
+                            Ok(on_overflow) => (rest, Some(on_overflow)),
+                            Err(_) => (args.as_slice(), None),
+                        }
+                    }
+                    _ => (args.as_slice(), None),
+                };
+                let mut clauses: Vec<_> = on_overflow
+                    .map(|on_overflow| {
+                        ast::FunctionArgumentClause::OnOverflow(match on_overflow {
+                            OnOverflow::Error => ast::ListAggOnOverflow::Error,
+                            OnOverflow::Truncate { filler, with_count } => {
+                                ast::ListAggOnOverflow::Truncate {
+                                    filler: Some(Box::new(ast::Expr::value(
+                                        SingleQuotedString(filler),
+                                    ))),
+                                    with_count,
+                                }
+                            }
+                        })
+                    })
+                    .into_iter()
+                    .collect();
                 let args = self.function_args_to_sql(args)?;
                 let filter = match filter {
                     Some(filter) => Some(Box::new(self.expr_to_sql_inner(filter)?)),
                     None => None,
                 };
-                let within_group: Vec<ast::OrderByExpr> =
-                    if agg.func.supports_within_group_clause() {
-                        order_by
-                            .iter()
-                            .map(|sort_expr| self.sort_to_sql(sort_expr))
-                            .collect::<Result<Vec<ast::OrderByExpr>>>()?
-                    } else {
-                        Vec::new()
-                    };
+                let order_by = order_by
+                    .iter()
+                    .map(|sort_expr| self.sort_to_sql(sort_expr))
+                    .collect::<Result<Vec<ast::OrderByExpr>>>()?;
+                // Ordered-set aggregates take their ordering in a WITHIN GROUP
+                // clause, the other aggregates in their argument list
+                let within_group = if agg.func.supports_within_group_clause() {
+                    order_by
+                } else {
+                    if !order_by.is_empty() {
+                        clauses.insert(0, ast::FunctionArgumentClause::OrderBy(order_by));
+                    }
+                    Vec::new()
+                };
                 Ok(ast::Expr::Function(Function {
                     name: ObjectName::from(vec![Ident {
                         value: func_name.to_string(),
@@ -402,7 +440,7 @@ impl Unparser<'_> {
                         duplicate_treatment: distinct
                             .then_some(DuplicateTreatment::Distinct),
                         args,
-                        clauses: vec![],
+                        clauses,
                     }),
                     filter,
                     null_treatment: None,
@@ -658,6 +696,7 @@ impl Unparser<'_> {
         match func_name {
             "make_array" => self.make_array_to_sql(args),
             "array_element" => self.array_element_to_sql(args),
+            "array_slice" => self.array_slice_to_sql(args),
             "named_struct" => self.named_struct_to_sql(args),
             "get_field" => self.get_field_to_sql(args),
             "map" => self.map_to_sql(args),
@@ -728,6 +767,36 @@ impl Unparser<'_> {
         })
     }
 
+    /// Unparses `array_slice(arr, start, stop[, stride])` into `arr[start:stop:stride]`,
+    /// leaving out the bounds and stride that the SQL planner fills in by default
+    fn array_slice_to_sql(&self, args: &[Expr]) -> Result<ast::Expr> {
+        assert_or_internal_err!(
+            matches!(args.len(), 3 | 4),
+            "array_slice must have 3 or 4 arguments"
+        );
+        let bound = |arg: Option<&Expr>, default: i64| match arg {
+            Some(Expr::Literal(ScalarValue::Int64(Some(value)), _))
+                if *value == default =>
+            {
+                Ok(None)
+            }
+            Some(arg) => self.expr_to_sql(arg).map(Some),
+            None => Ok(None),
+        };
+        let array = self.expr_to_sql(&args[0])?;
+        let lower_bound = bound(args.get(1), 1)?;
+        let upper_bound = bound(args.get(2), i64::MAX)?;
+        let stride = bound(args.get(3), 1)?;
+        Ok(ast::Expr::CompoundFieldAccess {
+            root: Box::new(array),
+            access_chain: vec![ast::AccessExpr::Subscript(Subscript::Slice {
+                lower_bound,
+                upper_bound,
+                stride,
+            })],
+        })
+    }
+
     fn named_struct_to_sql(&self, args: &[Expr]) -> Result<ast::Expr> {
         // Convert named_struct to a named_struct function call with all args
         assert_or_internal_err!(
@@ -987,6 +1056,24 @@ impl Unparser<'_> {
         }
     }
 
+    /// Returns `expr COLLATE collation` when `metadata` gives `expr` a
+    /// collation, and `expr` otherwise
+    pub(super) fn collate_to_sql(
+        &self,
+        expr: ast::Expr,
+        metadata: Option<&FieldMetadata>,
+    ) -> ast::Expr {
+        match metadata.and_then(|metadata| metadata.inner().get(COLLATION_METADATA_KEY)) {
+            Some(collation) => ast::Expr::Collate {
+                expr: Box::new(expr),
+                collation: ObjectName::from(vec![
+                    self.new_ident_quoted_if_needs(collation.clone()),
+                ]),
+            },
+            None => expr,
+        }
+    }
+
     pub(super) fn new_ident_without_quote_style(&self, str: String) -> Ident {
         Ident {
             value: str,
@@ -1941,7 +2028,7 @@ impl Unparser<'_> {
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, Sub};
-    use std::{any::Any, sync::Arc, vec};
+    use std::{any::Any, collections::BTreeMap, sync::Arc, vec};
 
     use crate::unparser::dialect::SqliteDialect;
     use arrow::array::{LargeListArray, ListArray};
@@ -1949,7 +2036,7 @@ mod tests {
     use ast::ObjectName;
     use datafusion_common::datatype::DataTypeExt;
     use datafusion_common::{Spans, TableReference};
-    use datafusion_expr::expr::WildcardOptions;
+    use datafusion_expr::expr::{NullTreatment, WildcardOptions};
     use datafusion_expr::{
         case, cast, col, cube, exists, grouping_set, interval_datetime_lit,
         interval_year_month_lit, lit, not, not_exists, out_ref_col, placeholder, rollup,
@@ -1957,8 +2044,10 @@ mod tests {
         ScalarUDFImpl, Signature, Volatility, WindowFrame, WindowFunctionDefinition,
     };
     use datafusion_expr::{interval_month_day_nano_lit, ExprFunctionExt};
+    use datafusion_functions_aggregate::array_agg::array_agg_udaf;
     use datafusion_functions_aggregate::count::count_udaf;
     use datafusion_functions_aggregate::expr_fn::sum;
+    use datafusion_functions_aggregate::first_last::first_value_udaf;
     use sqlparser::ast::ExactNumberInfo;
 
     // Note: Tests for make_array, array_element, named_struct, get_field, map,
@@ -2241,6 +2330,16 @@ mod tests {
                     .unwrap(),
                 "count(*) FILTER (WHERE true)",
             ),
+            (
+                array_agg_udaf()
+                    .call(vec![col("a")])
+                    .distinct()
+                    .order_by(vec![col("a").sort(false, true)])
+                    .filter(col("b").gt(lit(10)))
+                    .build()
+                    .unwrap(),
+                "array_agg(DISTINCT a ORDER BY a DESC NULLS FIRST) FILTER (WHERE (b > 10))",
+            ),
             // Test for row_number_udwf is disabled - see note about removed crates
             (
                 #[expect(deprecated)]
@@ -2269,6 +2368,29 @@ mod tests {
                 }),
                 r#"count(*) FILTER (WHERE (a > 100)) OVER (ORDER BY a DESC NULLS FIRST RANGE BETWEEN 6 PRECEDING AND 2 FOLLOWING)"#,
             ),
+            (
+                Expr::from(WindowFunction {
+                    fun: WindowFunctionDefinition::AggregateUDF(first_value_udaf()),
+                    params: WindowFunctionParams {
+                        args: vec![col("a")],
+                        partition_by: vec![],
+                        order_by: vec![Sort::new(col("a"), false, true)],
+                        window_frame: WindowFrame::new_bounds(
+                            datafusion_expr::WindowFrameUnits::Range,
+                            datafusion_expr::WindowFrameBound::Preceding(
+                                ScalarValue::UInt32(Some(6)),
+                            ),
+                            datafusion_expr::WindowFrameBound::Following(
+                                ScalarValue::UInt32(Some(2)),
+                            ),
+                        ),
+                        null_treatment: Some(NullTreatment::IgnoreNulls),
+                        distinct: false,
+                        filter: None,
+                    },
+                }),
+                r#"first_value(a) IGNORE NULLS OVER (ORDER BY a DESC NULLS FIRST RANGE BETWEEN 6 PRECEDING AND 2 FOLLOWING)"#,
+            ),
             (col("a").is_not_null(), r#"a IS NOT NULL"#),
             (col("a").is_null(), r#"a IS NULL"#),
             (
@@ -2449,6 +2571,16 @@ mod tests {
                 }),
                 "(a @> b)",
             ),
+            (
+                col("a").alias_with_metadata(
+                    "a",
+                    Some(FieldMetadata::new(BTreeMap::from([(
+                        COLLATION_METADATA_KEY.to_string(),
+                        "de_DE".to_string(),
+                    )]))),
+                ),
+                "a COLLATE de_DE",
+            ),
         ];
 
         for (expr, expected) in tests {
@@ -2482,6 +2614,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn array_slice_to_sql() -> Result<()> {
+        let unparser = Unparser::default();
+        let tests = [
+            (vec![col("a"), lit(1_i64), lit(3_i64), lit(1_i64)], "a[1:3]"),
+            (
+                vec![col("a"), lit(2_i64), lit(i64::MAX), lit(1_i64)],
+                "a[2:]",
+            ),
+            (vec![col("a"), lit(1_i64), lit(-2_i64)], "a[:-2]"),
+            (
+                vec![col("a"), lit(-3_i64), lit(-1_i64), lit(2_i64)],
+                "a[-3:-1:2]",
+            ),
+        ];
+        for (args, expected) in tests {
+            let ast = unparser.scalar_function_to_sql("array_slice", &args)?;
+            assert_eq!(ast.to_string(), expected);
+        }
+        Ok(())
+    }
+
     #[test]
     fn custom_dialect_with_identifier_quote_style() -> Result<()> {
         let dialect = CustomDialectBuilder::new()
@@ -44,11 +44,13 @@ use datafusion_common::{
     tree_node::{TransformedResult, TreeNode},
 };
 use datafusion_expr::expr::OUTER_REFERENCE_COLUMN_PREFIX;
+use datafusion_expr::planner::{SnapshotPoint, TableVersion};
 use datafusion_expr::{
-    BinaryExpr, Distinct, Expr, JoinConstraint, JoinType, LogicalPlan,
-    LogicalPlanBuilder, Merge, MergeAction, MergeInsertKind, Operator, Projection,
-    RecursiveQuery, SortExpr, TableScan, Unnest, UserDefinedLogicalNode, expr::Alias,
-    logical_plan::DdlStatement, logical_plan::Statement as PlanStatement,
+    BinaryExpr, CteMaterialization, CteReference, Distinct, Expr, JoinConstraint,
+    JoinType, LogicalPlan, LogicalPlanBuilder, Merge, MergeAction, MergeInsertKind,
+    Operator, Projection, RecursiveQuery, SortExpr, TableScan, Unnest,
+    UserDefinedLogicalNode, expr::Alias, logical_plan::DdlStatement,
+    logical_plan::Statement as PlanStatement,
 };
 use sqlparser::ast::helpers::attached_token::AttachedToken;
 use sqlparser::ast::{self, Ident, OrderByKind, SetExpr, TableAliasColumnDef};
@@ -121,7 +123,8 @@ impl Unparser<'_> {
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Limit(_)
             | LogicalPlan::Values(_)
-            | LogicalPlan::Distinct(_) => self.select_to_sql_statement(&plan),
+            | LogicalPlan::Distinct(_)
+            | LogicalPlan::CteReference(_) => self.select_to_sql_statement(&plan),
             LogicalPlan::Statement(statement) => self.statement_to_sql(&statement),
             LogicalPlan::Dml(_) => self.dml_to_sql(&plan),
             LogicalPlan::Merge(merge) => self.merge_to_sql(&merge),
@@ -136,9 +139,14 @@ impl Unparser<'_> {
             | LogicalPlan::CopyFrom(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::Unnest(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::MatchRecognize(_)
             | LogicalPlan::JsonTable(_)
-            | LogicalPlan::GraphTable(_) => not_impl_err!("Unsupported plan: {plan:?}"),
+            | LogicalPlan::XmlTable(_)
+            | LogicalPlan::GraphTable(_)
+            | LogicalPlan::PolymorphicTableFunction(_) => {
+                not_impl_err!("Unsupported plan: {plan:?}")
+            }
         }
     }
 
@@ -195,6 +203,47 @@ impl Unparser<'_> {
         Ok(ast::Statement::Query(Box::new(query)))
     }
 
+    /// Unparse the definition of the CTE of a [`CteReference`] into the WITH
+    /// clause of the query referencing it
+    fn cte_to_sql(&self, cte: &CteReference, query: &mut QueryBuilder) -> Result<()> {
+        // The plan of the CTE is aliased with its name
+        let plan = match cte.input.as_ref() {
+            LogicalPlan::SubqueryAlias(alias) => alias.input.as_ref(),
+            plan => plan,
+        };
+        let ast::Statement::Query(mut cte_query) = self.plan_to_sql(plan)? else {
+            return internal_err!("CTE {} is not a query", cte.name);
+        };
+        // The CTEs this one references are defined before it in the same WITH
+        // clause, as a nested WITH could not reuse their names
+        if let Some(with) = cte_query.with.take_if(|with| !with.recursive) {
+            for cte in with.cte_tables {
+                query.cte(cte);
+            }
+        }
+        let materialized = match cte.materialization {
+            CteMaterialization::Default => None,
+            CteMaterialization::Materialized => {
+                Some(ast::CteAsMaterialized::Materialized)
+            }
+            CteMaterialization::NotMaterialized => {
+                Some(ast::CteAsMaterialized::NotMaterialized)
+            }
+        };
+        query.cte(ast::Cte {
+            alias: ast::TableAlias {
+                name: self.new_ident_quoted_if_needs(cte.name.clone()),
+                columns: vec![],
+                implicit: false,
+            },
+            query: cte_query,
+            from: None,
+            materialized,
+            closing_paren_token: AttachedToken::empty(),
+        });
+        Ok(())
+    }
+
     /// Convert a RecursiveQuery to a SQL statement with WITH RECURSIVE clause.
     ///
     /// Generates SQL like:
@@ -509,6 +558,7 @@ impl Unparser<'_> {
                     self.new_ident_quoted_if_needs(scan.table_name.table().to_string()),
                 );
                 builder.name(ast::ObjectName::from(table_parts));
+                builder.version(self.table_version_to_sql(scan.version.as_ref())?);
                 relation.table(builder);
 
                 Ok(())
@@ -1061,6 +1111,25 @@ impl Unparser<'_> {
                     relation,
                 )
             }
+            LogicalPlan::CteReference(cte) => {
+                let Some(query) = query.as_mut() else {
+                    // Without an enclosing query to hold the WITH clause, the
+                    // CTE is unparsed where it is referenced
+                    return self.select_to_sql_recursively(
+                        cte.input.as_ref(),
+                        query,
+                        select,
+                        relation,
+                    );
+                };
+                self.cte_to_sql(cte, query)?;
+                let mut builder = TableRelationBuilder::default();
+                builder.name(ast::ObjectName::from(vec![
+                    self.new_ident_quoted_if_needs(cte.name.clone()),
+                ]));
+                relation.table(builder);
+                Ok(())
+            }
             LogicalPlan::EmptyRelation(_) => {
                 // An EmptyRelation could be behind an UNNEST node. If the dialect supports UNNEST as a table factor,
                 // a TableRelationBuilder will be created for the UNNEST node first.
@@ -1209,11 +1278,26 @@ impl Unparser<'_> {
                         alias_name: alias_name.clone(),
                     });
 
-                let mut builder = LogicalPlanBuilder::scan(
-                    table_scan.table_name.clone(),
-                    Arc::clone(&table_scan.source),
-                    None,
-                )?;
+                let mut builder = match &table_scan.version {
+                    // Keep the version of a time travel scan so that the
+                    // snapshot it reads is unparsed.
+                    Some(version) => {
+                        let mut scan = TableScan::try_new(
+                            table_scan.table_name.clone(),
+                            Arc::clone(&table_scan.source),
+                            None,
+                            vec![],
+                            None,
+                        )?;
+                        scan.version = Some(version.clone());
+                        LogicalPlanBuilder::from(LogicalPlan::TableScan(scan))
+                    }
+                    None => LogicalPlanBuilder::scan(
+                        table_scan.table_name.clone(),
+                        Arc::clone(&table_scan.source),
+                        None,
+                    )?,
+                };
                 // We will rebase the column references to the new alias if it exists.
                 // If the projection or filters are empty, we will append alias to the table scan.
                 //
@@ -1350,8 +1434,14 @@ impl Unparser<'_> {
 
     fn select_item_to_sql(&self, expr: &Expr) -> Result<ast::SelectItem> {
         match expr {
-            Expr::Alias(Alias { expr, name, .. }) => {
-                let inner = self.expr_to_sql(expr)?;
+            Expr::Alias(Alias {
+                expr,
+                name,
+                metadata,
+                ..
+            }) => {
+                let inner =
+                    self.collate_to_sql(self.expr_to_sql(expr)?, metadata.as_ref());
 
                 // Determine the alias name to use
                 let col_name = if let Some(rewritten_name) =
@@ -1789,7 +1879,7 @@ impl Unparser<'_> {
                     alias: None,
                     args: None,
                     with_hints: vec![],
-                    version: None,
+                    version: self.table_version_to_sql(scan.version.as_ref())?,
                     with_ordinality: false,
                     only: false,
                     partitions: vec![],
@@ -1812,7 +1902,7 @@ impl Unparser<'_> {
                             alias: Some(alias_name),
                             args: None,
                             with_hints: vec![],
-                            version: None,
+                            version: self.table_version_to_sql(scan.version.as_ref())?,
                             with_ordinality: false,
                             only: false,
                             partitions: vec![],
@@ -1836,6 +1926,52 @@ impl Unparser<'_> {
         }
     }
 
+    /// Convert the version of a time travel [`TableScan`] back to the
+    /// `FOR SYSTEM_TIME AS OF` or `AT` / `BEFORE` clause it was planned from
+    fn table_version_to_sql(
+        &self,
+        version: Option<&TableVersion>,
+    ) -> Result<Option<ast::TableVersion>> {
+        let (clause, point) = match version {
+            None => return Ok(None),
+            Some(TableVersion::AsOf(expr)) => {
+                return Ok(Some(ast::TableVersion::ForSystemTimeAsOf(
+                    self.expr_to_sql(expr)?,
+                )));
+            }
+            Some(TableVersion::At(point)) => ("AT", point),
+            Some(TableVersion::Before(point)) => ("BEFORE", point),
+        };
+        let (kind, expr) = match point {
+            SnapshotPoint::Timestamp(expr) => ("TIMESTAMP", expr),
+            SnapshotPoint::Offset(expr) => ("OFFSET", expr),
+            SnapshotPoint::Statement(expr) => ("STATEMENT", expr),
+            SnapshotPoint::Stream(expr) => ("STREAM", expr),
+        };
+        let function = ast::Function {
+            name: ast::ObjectName::from(vec![Ident::new(clause)]),
+            uses_odbc_syntax: false,
+            args: ast::FunctionArguments::List(ast::FunctionArgumentList {
+                duplicate_treatment: None,
+                args: vec![ast::FunctionArg::Named {
+                    name: Ident::new(kind),
+                    arg: ast::FunctionArgExpr::Expr(self.expr_to_sql(expr)?),
+                    operator: ast::FunctionArgOperator::RightArrow,
+                }],
+                clauses: vec![],
+            }),
+            filter: None,
+            null_treatment: None,
+            over: None,
+            within_group: vec![],
+            parameters: ast::FunctionArguments::None,
+            nth_value_order: None,
+        };
+        Ok(Some(ast::TableVersion::Function(ast::Expr::Function(
+            function,
+        ))))
+    }
+
     fn table_reference_to_object_name(
         &self,
         reference: &TableReference,
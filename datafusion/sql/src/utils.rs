@@ -334,6 +334,78 @@ pub(crate) fn make_decimal_type(
     }
 }
 
+/// Returns true if casting a value of the numeric type `from` to the
+/// numeric type `to` may lose information, such as casting a `BIGINT` to an
+/// `INT`, a `DOUBLE` to a `REAL`, or a `DECIMAL(10, 2)` to an `INT`
+pub(crate) fn is_lossy_numeric_cast(from: &DataType, to: &DataType) -> bool {
+    if from == to {
+        return false;
+    }
+    match (numeric_range(from), numeric_range(to)) {
+        (Some(from), Some(to)) => {
+            from.fraction_digits > to.fraction_digits
+                || from.integer_digits > to.integer_digits
+                || from.significant_bits > to.significant_bits
+                || (from.signed && !to.signed)
+        }
+        _ => false,
+    }
+}
+
+/// The values a numeric type holds exactly, to compare numeric types
+struct NumericRange {
+    /// Whether negative values are held
+    signed: bool,
+    /// Decimal digits of the integer part of the values
+    integer_digits: u32,
+    /// Decimal digits of the fractional part of the values
+    fraction_digits: u32,
+    /// Binary digits of the values
+    significant_bits: u32,
+}
+
+fn numeric_range(data_type: &DataType) -> Option<NumericRange> {
+    let integer = |signed, bits: u32| NumericRange {
+        signed,
+        integer_digits: (2_u128.pow(bits) - 1).ilog10() + 1,
+        fraction_digits: 0,
+        significant_bits: bits,
+    };
+    // Floats hold a wide range of values, but only those of the precision
+    // of their mantissa exactly
+    let float = |mantissa_bits| NumericRange {
+        signed: true,
+        integer_digits: u32::MAX,
+        fraction_digits: u32::MAX,
+        significant_bits: mantissa_bits,
+    };
+    let decimal = |precision: u8, scale: i8| {
+        let scale = scale.max(0) as u32;
+        NumericRange {
+            signed: true,
+            integer_digits: (precision as u32).saturating_sub(scale),
+            fraction_digits: scale,
+            significant_bits: ((precision as f64) * 10_f64.log2()).ceil() as u32,
+        }
+    };
+    match data_type {
+        DataType::Int8 => Some(integer(true, 7)),
+        DataType::Int16 => Some(integer(true, 15)),
+        DataType::Int32 => Some(integer(true, 31)),
+        DataType::Int64 => Some(integer(true, 63)),
+        DataType::UInt8 => Some(integer(false, 8)),
+        DataType::UInt16 => Some(integer(false, 16)),
+        DataType::UInt32 => Some(integer(false, 32)),
+        DataType::UInt64 => Some(integer(false, 64)),
+        DataType::Float16 => Some(float(11)),
+        DataType::Float32 => Some(float(24)),
+        DataType::Float64 => Some(float(53)),
+        DataType::Decimal128(precision, scale)
+        | DataType::Decimal256(precision, scale) => Some(decimal(*precision, *scale)),
+        _ => None,
+    }
+}
+
 /// Normalize an owned identifier following PostgreSQL rules: an unquoted
 /// identifier is ASCII-folded to lowercase, a quoted identifier is preserved
 /// verbatim and compares case-sensitively. This matches
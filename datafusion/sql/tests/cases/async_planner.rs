@@ -0,0 +1,182 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use datafusion_common::{Result, TableReference, assert_contains};
+use datafusion_expr::planner::{AsyncContextProvider, ContextProvider};
+use datafusion_expr::test::function_stub::sum_udaf;
+use datafusion_expr::{AggregateUDF, LogicalPlan, TableSource};
+use datafusion_sql::async_planner::AsyncSqlToRel;
+use datafusion_sql::parser::DFParser;
+use futures::executor::block_on;
+use sqlparser::dialect::PostgreSqlDialect;
+
+use crate::{MockContextProvider, MockSessionState};
+
+/// Resolves the tables of [`MockContextProvider`] and the `remote_sum`
+/// aggregate function, recording the requested tables
+struct AsyncMockContextProvider {
+    context: MockContextProvider,
+    requested_tables: Mutex<Vec<String>>,
+}
+
+impl AsyncMockContextProvider {
+    fn new() -> Self {
+        Self {
+            context: MockContextProvider {
+                state: MockSessionState::default(),
+            },
+            requested_tables: Mutex::new(vec![]),
+        }
+    }
+
+    fn plan(&self, sql: &str) -> Result<LogicalPlan> {
+        let statement = DFParser::parse_sql_with_dialect(sql, &PostgreSqlDialect {})?
+            .pop_front()
+            .unwrap();
+        block_on(AsyncSqlToRel::new(self).statement_to_plan(statement))
+    }
+}
+
+#[async_trait]
+impl AsyncContextProvider for AsyncMockContextProvider {
+    fn context_provider(&self) -> &dyn ContextProvider {
+        &self.context
+    }
+
+    async fn get_table_source(
+        &self,
+        name: TableReference,
+    ) -> Result<Arc<dyn TableSource>> {
+        self.requested_tables.lock().unwrap().push(name.to_string());
+        self.context.get_table_source(name)
+    }
+
+    async fn get_aggregate_meta(&self, name: &str) -> Result<Option<Arc<AggregateUDF>>> {
+        Ok((name == "remote_sum").then(sum_udaf))
+    }
+}
+
+#[test]
+fn test_async_resolves_tables_once() {
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider
+        .plan(
+            "WITH p AS (SELECT * FROM person) \
+             SELECT p.id FROM p JOIN orders ON p.id = orders.customer_id \
+             WHERE p.id IN (SELECT id FROM person)",
+        )
+        .unwrap();
+    let plan_str = plan.display_indent().to_string();
+    assert_contains!(&plan_str, "TableScan: person");
+    assert_contains!(&plan_str, "TableScan: orders");
+    assert_eq!(
+        *provider.requested_tables.lock().unwrap(),
+        vec!["orders", "person"]
+    );
+}
+
+#[test]
+fn test_async_resolves_clone_source() {
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider
+        .plan("CREATE TABLE person_copy CLONE person")
+        .unwrap();
+    assert_contains!(plan.display_indent().to_string(), "TableScan: person");
+    assert_eq!(*provider.requested_tables.lock().unwrap(), vec!["person"]);
+}
+
+#[test]
+fn test_async_resolves_copy_from_table() {
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider.plan("COPY person (id) FROM 'people.csv'").unwrap();
+    assert_contains!(plan.display_indent().to_string(), "CopyFrom: table=person");
+    assert_eq!(*provider.requested_tables.lock().unwrap(), vec!["person"]);
+}
+
+#[test]
+fn test_async_resolves_copy_to_sources() {
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider.plan("COPY person TO 'people.csv'").unwrap();
+    assert_contains!(plan.display_indent().to_string(), "TableScan: person");
+    assert_eq!(*provider.requested_tables.lock().unwrap(), vec!["person"]);
+
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider
+        .plan("COPY (SELECT id FROM person) TO 'people.csv'")
+        .unwrap();
+    assert_contains!(plan.display_indent().to_string(), "TableScan: person");
+    assert_eq!(*provider.requested_tables.lock().unwrap(), vec!["person"]);
+}
+
+#[test]
+fn test_async_resolves_graph_element_tables() {
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider
+        .plan(
+            "SELECT * FROM GRAPH_TABLE (social \
+             MATCH (a:person)-[e:knows]->(b:person) \
+             COLUMNS (a.first_name, e.since))",
+        )
+        .unwrap();
+    let plan_str = plan.display_indent().to_string();
+    assert_contains!(&plan_str, "TableScan: person");
+    assert_contains!(&plan_str, "TableScan: knows");
+    let requested = provider.requested_tables.lock().unwrap();
+    assert!(requested.contains(&"person".to_string()));
+    assert!(requested.contains(&"knows".to_string()));
+}
+
+#[test]
+fn test_async_resolves_property_graph_definition_tables() {
+    let provider = AsyncMockContextProvider::new();
+    provider
+        .plan(
+            "CREATE PROPERTY GRAPH friends \
+             VERTEX TABLES (person KEY (id)) \
+             EDGE TABLES (knows SOURCE KEY (src) REFERENCES person \
+                                DESTINATION KEY (dst) REFERENCES person)",
+        )
+        .unwrap();
+    let requested = provider.requested_tables.lock().unwrap();
+    assert!(requested.contains(&"person".to_string()));
+    assert!(requested.contains(&"knows".to_string()));
+}
+
+#[test]
+fn test_async_resolves_described_table() {
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider.plan("DESCRIBE person").unwrap();
+    assert!(matches!(plan, LogicalPlan::DescribeTable(_)));
+    assert_eq!(*provider.requested_tables.lock().unwrap(), vec!["person"]);
+}
+
+#[test]
+fn test_async_resolves_functions() {
+    let provider = AsyncMockContextProvider::new();
+    let plan = provider.plan("SELECT remote_sum(age) FROM person").unwrap();
+    assert_contains!(plan.display_indent().to_string(), "sum(person.age)");
+}
+
+#[test]
+fn test_async_missing_table() {
+    let provider = AsyncMockContextProvider::new();
+    let err = provider.plan("SELECT * FROM missing").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "No table named: missing found");
+}
@@ -16,21 +16,30 @@
 // under the License.
 
 use datafusion_common::{assert_contains, DataFusionError};
-use datafusion_sql::planner::SqlToRel;
+use datafusion_functions_aggregate::grouping::grouping_udaf;
+use datafusion_sql::planner::{ParserOptions, SqlToRel};
 use sqlparser::{dialect::PostgreSqlDialect, parser::Parser};
 
 use crate::{MockContextProvider, MockSessionState};
 
 fn do_query(sql: &'static str) -> DataFusionError {
+    do_query_with_options(sql, ParserOptions::default())
+}
+
+fn do_query_collecting_errors(sql: &'static str) -> DataFusionError {
+    do_query_with_options(sql, ParserOptions::default().with_collect_errors(true))
+}
+
+fn do_query_with_options(sql: &'static str, options: ParserOptions) -> DataFusionError {
     let dialect = PostgreSqlDialect {};
     let statement = Parser::new(&dialect)
         .try_with_sql(sql)
         .expect("unable to create parser")
         .parse_statement()
         .expect("unable to parse query");
-    let state = MockSessionState::default();
+    let state = MockSessionState::default().with_aggregate_function(grouping_udaf());
     let context = MockContextProvider { state };
-    let sql_to_rel = SqlToRel::new(&context);
+    let sql_to_rel = SqlToRel::new_with_options(&context, options);
     sql_to_rel
         .sql_statement_to_plan(statement)
         .expect_err("expected error")
@@ -57,3 +66,45 @@ fn test_collect_set_exprs() {
     assert_contains!(errors[0].to_string(), "No field named first_namex.");
     assert_contains!(errors[1].to_string(), "No field named last_namex.");
 }
+
+#[test]
+fn test_stop_at_where_clause() {
+    let query = "SELECT first_namex FROM person WHERE agex > 1";
+    let error = do_query(query);
+    let errors = error.iter().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert_contains!(errors[0].to_string(), "No field named agex.");
+}
+
+#[test]
+fn test_collect_where_clause() {
+    let query = "SELECT first_namex FROM person WHERE agex > 1";
+    let error = do_query_collecting_errors(query);
+    let errors = error.iter().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 2);
+    assert_contains!(errors[0].to_string(), "No field named agex.");
+    assert_contains!(errors[1].to_string(), "No field named first_namex.");
+}
+
+#[test]
+fn test_collect_clauses() {
+    let query = "SELECT first_name FROM person GROUP BY first_name, statex \
+                 HAVING agex > 1 ORDER BY last_namex";
+    let error = do_query_collecting_errors(query);
+    let errors = error.iter().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 3);
+    assert_contains!(errors[0].to_string(), "No field named last_namex.");
+    assert_contains!(errors[1].to_string(), "No field named agex.");
+    assert_contains!(errors[2].to_string(), "No field named statex.");
+}
+
+#[test]
+fn test_collect_function_args() {
+    let query =
+        "SELECT grouping(first_namex, last_namex) FROM person GROUP BY first_name";
+    let error = do_query_collecting_errors(query);
+    let errors = error.iter().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 2);
+    assert_contains!(errors[0].to_string(), "No field named first_namex.");
+    assert_contains!(errors[1].to_string(), "No field named last_namex.");
+}
@@ -30,6 +30,10 @@ use crate::{MockContextProvider, MockSessionState};
 // Note: string::concat from datafusion_functions was removed
 
 fn do_query(sql: &'static str) -> Diagnostic {
+    do_query_with_state(sql, MockSessionState::default())
+}
+
+fn do_query_with_state(sql: &'static str, state: MockSessionState) -> Diagnostic {
     let statement = DFParserBuilder::new(sql)
         .build()
         .expect("unable to create parser")
@@ -39,7 +43,6 @@ fn do_query(sql: &'static str) -> Diagnostic {
         collect_spans: true,
         ..ParserOptions::default()
     };
-    let state = MockSessionState::default();
     let context = MockContextProvider { state };
     let sql_to_rel = SqlToRel::new_with_options(&context, options);
     match sql_to_rel.statement_to_plan(statement) {
@@ -190,6 +193,16 @@ fn test_missing_non_aggregate_in_group_by() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_error_located_at_innermost_expression() -> Result<()> {
+    let query = "SELECT id + /*a*/X'1'/*a*/ FROM person";
+    let spans = get_spans(query);
+    let diag = do_query(query);
+    assert_snapshot!(diag.message, @"HexStringLiteral '1' has an odd number of hex digits");
+    assert_eq!(diag.span, Some(spans["a"]));
+    Ok(())
+}
+
 #[test]
 fn test_ambiguous_reference() -> Result<()> {
     let query = "SELECT /*a*/first_name/*a*/ FROM person a, person b";
@@ -252,6 +265,33 @@ fn test_field_not_found_suggestion() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_field_not_found_did_you_mean() -> Result<()> {
+    let query = "SELECT first_na FROM person";
+    let diag = do_query(query);
+    assert_eq!(diag.helps.len(), 1);
+    assert_snapshot!(diag.helps[0].message, @"did you mean 'first_name'?");
+    Ok(())
+}
+
+#[test]
+fn test_table_not_found_did_you_mean() -> Result<()> {
+    let query = "SELECT * FROM persn";
+    let diag = do_query(query);
+    assert_eq!(diag.helps.len(), 1);
+    assert_snapshot!(diag.helps[0].message, @"did you mean 'person'?");
+
+    let query = "WITH recent AS (SELECT 1) SELECT * FROM recnt";
+    let diag = do_query(query);
+    assert_eq!(diag.helps.len(), 1);
+    assert_snapshot!(diag.helps[0].message, @"did you mean 'recent'?");
+
+    let query = "SELECT * FROM xyzzy";
+    let diag = do_query(query);
+    assert!(diag.helps.is_empty());
+    Ok(())
+}
+
 #[test]
 fn test_ambiguous_column_suggestion() -> Result<()> {
     let query = "SELECT /*whole*/id/*whole*/ FROM test_decimal, person";
@@ -394,3 +434,38 @@ fn test_syntax_error() -> Result<()> {
         },
     }
 }
+
+#[test]
+fn test_expr_depth_limit() -> Result<()> {
+    let query = "SELECT (((/*a*/age/*a*/))) FROM person";
+    let spans = get_spans(query);
+    let mut state = MockSessionState::default();
+    state.config_options.sql_parser.expr_depth_limit = 3;
+    let diag = do_query_with_state(query, state);
+    assert_snapshot!(diag.message, @"Expression nesting depth exceeds the limit of 3");
+    assert_snapshot!(diag.helps[0].message, @"simplify the query or raise datafusion.sql_parser.expr_depth_limit");
+    assert_eq!(diag.span, Some(spans["a"]));
+    Ok(())
+}
+
+#[test]
+fn test_subquery_depth_limit() -> Result<()> {
+    let query = "SELECT * FROM (SELECT * FROM (SELECT age FROM person) AS t2) AS t1";
+    let mut state = MockSessionState::default();
+    state.config_options.sql_parser.subquery_depth_limit = 1;
+    let diag = do_query_with_state(query, state);
+    assert_snapshot!(diag.message, @"Subquery nesting depth exceeds the limit of 1");
+    assert!(diag.span.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_set_operation_limit() -> Result<()> {
+    let query = "SELECT 1 UNION SELECT 2 INTERSECT SELECT 3 UNION ALL SELECT 4";
+    let mut state = MockSessionState::default();
+    state.config_options.sql_parser.set_operation_limit = 2;
+    let diag = do_query_with_state(query, state);
+    assert_snapshot!(diag.message, @"Number of set operations exceeds the limit of 2");
+    assert!(diag.span.is_some());
+    Ok(())
+}
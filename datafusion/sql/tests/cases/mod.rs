@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod async_planner;
 mod collection;
 mod diagnostic;
 mod params;
@@ -60,7 +60,9 @@ use datafusion_sql::unparser::extension_unparser::{
     UnparseToStatementResult, UnparseWithinStatementResult,
     UserDefinedLogicalNodeUnparser,
 };
-use sqlparser::dialect::{Dialect, PostgreSqlDialect, MySqlDialect};
+use sqlparser::dialect::{
+    Dialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SnowflakeDialect,
+};
 use sqlparser::parser::Parser;
 
 #[test]
@@ -381,6 +383,51 @@ fn roundtrip_crossjoin() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn roundtrip_time_travel_table_version() -> Result<()> {
+    let tests: Vec<(&str, Box<dyn Dialect>)> = vec![
+        (
+            "SELECT * FROM person FOR SYSTEM_TIME AS OF '2020-01-01T00:00:00'",
+            Box::new(MsSqlDialect {}),
+        ),
+        (
+            "SELECT p.id FROM person FOR SYSTEM_TIME AS OF '2020-01-01T00:00:00' AS p",
+            Box::new(MsSqlDialect {}),
+        ),
+        (
+            "SELECT * FROM person AT(TIMESTAMP => '2020-01-01 00:00:00')",
+            Box::new(SnowflakeDialect {}),
+        ),
+        (
+            "SELECT first_name FROM person BEFORE(STATEMENT => '8e5d0ca9')",
+            Box::new(SnowflakeDialect {}),
+        ),
+    ];
+
+    // sql -> ast::Statement (s1) -> LogicalPlan (p1) -> ast::Statement (s2) -> LogicalPlan (p2)
+    // The version of the snapshot read by p1 must survive in p2.
+    for (query, dialect) in tests {
+        let statement = Parser::new(dialect.as_ref())
+            .try_with_sql(query)?
+            .parse_statement()?;
+        let state = MockSessionState::default();
+        let context = MockContextProvider { state };
+        let sql_to_rel = SqlToRel::new(&context);
+        let plan = sql_to_rel.sql_statement_to_plan(statement).unwrap();
+        assert_contains!(plan.display_indent().to_string(), "version=[");
+
+        let roundtrip_statement = plan_to_sql(&plan)?;
+
+        let plan_roundtrip = sql_to_rel
+            .sql_statement_to_plan(roundtrip_statement.clone())
+            .unwrap();
+
+        assert_eq!(plan, plan_roundtrip, "{roundtrip_statement}");
+    }
+
+    Ok(())
+}
+
 #[macro_export]
 macro_rules! roundtrip_statement_with_dialect_helper {
     (
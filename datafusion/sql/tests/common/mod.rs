@@ -16,17 +16,33 @@
 // under the License.
 
 use std::any::Any;
+use std::borrow::Cow;
 #[cfg(test)]
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::sync::Mutex;
 use std::{sync::Arc, vec};
 
 use arrow::datatypes::*;
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::file_options::file_type::FileType;
-use datafusion_common::{plan_err, GetExt, Result, TableReference};
-use datafusion_expr::planner::{ExprPlanner, TypePlanner};
-use datafusion_expr::{AggregateUDF, ScalarUDF, TableSource, WindowUDF};
+use datafusion_common::{
+    not_impl_err, plan_err, GetExt, Result, ScalarValue, TableReference,
+};
+use datafusion_expr::arguments::resolve_function_arguments;
+use datafusion_expr::planner::{
+    AuditLogger, ColumnMask, ExprPlanner, PERIOD_END_METADATA_KEY,
+    PERIOD_START_METADATA_KEY, RowSecurityPolicy, RowSecurityPolicyRegistry,
+    SYSTEM_TIME_METADATA_KEY, SYSTEM_TIME_ROW_END, SYSTEM_TIME_ROW_START,
+    StatementPlanner, StatementRewriter, TableVersion, TypePlanner, WordReservation,
+};
+use datafusion_expr::{
+    AggregateUDF, CreatePropertyGraph, Expr, GraphEdgeEndpoint, GraphEdgeTableDefinition,
+    GraphKeyClause, GraphPropertiesClause, GraphVertexTableDefinition, LogicalPlan,
+    LogicalPlanBuilder, PolymorphicTableFunctionImpl, PrivilegeStore, ScalarUDF,
+    TableFunctionArgument, TableProviderHintSupport, TableProviderRowLockSupport,
+    TableScanRowLock, TableScanRowLockWaitPolicy, TableSource, WindowUDF, col, lit,
+};
 use datafusion_sql::planner::ContextProvider;
 
 // Note: make_array from datafusion_functions_nested was removed
@@ -57,8 +73,19 @@ pub(crate) struct MockSessionState {
     aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
     expr_planners: Vec<Arc<dyn ExprPlanner>>,
     type_planner: Option<Arc<dyn TypePlanner>>,
+    statement_planners: Vec<Arc<dyn StatementPlanner>>,
+    statement_rewriters: Vec<Arc<dyn StatementRewriter>>,
+    row_security_policies: RowSecurityPolicyRegistry,
+    column_masks: Vec<(TableReference, ColumnMask)>,
+    current_role: Option<String>,
+    privilege_store: Option<Arc<PrivilegeStore>>,
+    audit_loggers: Vec<Arc<dyn AuditLogger>>,
     window_functions: HashMap<String, Arc<WindowUDF>>,
     pub config_options: ConfigOptions,
+    /// The tables requested one at a time with `get_table_source`
+    pub table_lookups: Mutex<Vec<String>>,
+    /// The tables requested in batches with `get_table_sources`
+    pub table_batches: Mutex<Vec<Vec<String>>>,
 }
 
 impl MockSessionState {
@@ -80,6 +107,57 @@ impl MockSessionState {
         self
     }
 
+    pub fn with_expr_planner(mut self, expr_planner: Arc<dyn ExprPlanner>) -> Self {
+        self.expr_planners.push(expr_planner);
+        self
+    }
+
+    pub fn with_statement_planner(
+        mut self,
+        statement_planner: Arc<dyn StatementPlanner>,
+    ) -> Self {
+        self.statement_planners.push(statement_planner);
+        self
+    }
+
+    pub fn with_statement_rewriter(
+        mut self,
+        statement_rewriter: Arc<dyn StatementRewriter>,
+    ) -> Self {
+        self.statement_rewriters.push(statement_rewriter);
+        self
+    }
+
+    pub fn with_row_security_policy(
+        mut self,
+        table: &str,
+        policy: RowSecurityPolicy,
+    ) -> Self {
+        self.row_security_policies.add_policy(table, policy);
+        self
+    }
+
+    pub fn with_column_mask(mut self, table: &str, mask: ColumnMask) -> Self {
+        self.column_masks.push((TableReference::from(table), mask));
+        self
+    }
+
+    pub fn with_current_role(mut self, role: &str) -> Self {
+        self.current_role = Some(role.to_string());
+        self
+    }
+
+    pub fn with_privileges(mut self, role: &str, store: PrivilegeStore) -> Self {
+        self.current_role = Some(role.to_string());
+        self.privilege_store = Some(Arc::new(store));
+        self
+    }
+
+    pub fn with_audit_logger(mut self, audit_logger: Arc<dyn AuditLogger>) -> Self {
+        self.audit_loggers.push(audit_logger);
+        self
+    }
+
     #[allow(dead_code)] // Window function crate was pruned but keep method for potential future use
     pub fn with_window_function(mut self, window_function: Arc<WindowUDF>) -> Self {
         self.window_functions
@@ -92,8 +170,8 @@ pub(crate) struct MockContextProvider {
     pub(crate) state: MockSessionState,
 }
 
-impl ContextProvider for MockContextProvider {
-    fn get_table_source(&self, name: TableReference) -> Result<Arc<dyn TableSource>> {
+impl MockContextProvider {
+    fn table_source(&self, name: TableReference) -> Result<Arc<dyn TableSource>> {
         let schema = match name.table() {
             "test" => Ok(Schema::new(vec![
                 Field::new("t_date32", DataType::Date32, false),
@@ -159,6 +237,50 @@ impl ContextProvider for MockContextProvider {
                 Field::new("price", DataType::Float64, false),
                 Field::new("delivered", DataType::Boolean, false),
             ])),
+            "accounts" => Ok(Schema::new(vec![
+                Field::new("id", DataType::UInt32, false),
+                Field::new("balance", DataType::Float64, false),
+                Field::new(
+                    "sys_start",
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                )
+                .with_metadata(
+                    [(
+                        SYSTEM_TIME_METADATA_KEY.to_string(),
+                        SYSTEM_TIME_ROW_START.to_string(),
+                    )]
+                    .into(),
+                ),
+                Field::new(
+                    "sys_end",
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                )
+                .with_metadata(
+                    [(
+                        SYSTEM_TIME_METADATA_KEY.to_string(),
+                        SYSTEM_TIME_ROW_END.to_string(),
+                    )]
+                    .into(),
+                ),
+            ])),
+            // Rates with an application-time period `validity`
+            "contracts" => Ok(Schema::new(vec![
+                Field::new("id", DataType::UInt32, false),
+                Field::new("rate", DataType::Float64, true),
+                Field::new("valid_from", DataType::Date32, false).with_metadata(
+                    [(
+                        PERIOD_START_METADATA_KEY.to_string(),
+                        "validity".to_string(),
+                    )]
+                    .into(),
+                ),
+                Field::new("valid_to", DataType::Date32, false).with_metadata(
+                    [(PERIOD_END_METADATA_KEY.to_string(), "validity".to_string())]
+                        .into(),
+                ),
+            ])),
             "array" => Ok(Schema::new(vec![
                 Field::new(
                     "left",
@@ -177,6 +299,12 @@ impl ContextProvider for MockContextProvider {
                     false,
                 ),
             ])),
+            "knows" => Ok(Schema::new(vec![
+                Field::new("src", DataType::UInt32, false),
+                Field::new("dst", DataType::UInt32, false),
+                Field::new("since", DataType::Int32, true),
+                Field::new("weight", DataType::Float64, true),
+            ])),
             "lineitem" => Ok(Schema::new(vec![
                 Field::new("l_item_id", DataType::UInt32, false),
                 Field::new("l_description", DataType::Utf8, false),
@@ -219,6 +347,37 @@ impl ContextProvider for MockContextProvider {
                     false,
                 ),
             ])),
+            "ledger" => {
+                let schema = Schema::new(vec![
+                    Field::new("entry_id", DataType::UInt32, false),
+                    Field::new("amount", DataType::Float64, false),
+                ]);
+                return Ok(Arc::new(NoSkipLockedTable::new(Arc::new(schema))));
+            }
+            "events" => {
+                let schema = Schema::new(vec![
+                    Field::new("event_id", DataType::UInt32, false),
+                    Field::new("ts", DataType::Int64, false),
+                ]);
+                return Ok(Arc::new(IndexedTable::new(Arc::new(schema))));
+            }
+            // A view of `person`
+            "person_view" => {
+                let person = self.table_source(TableReference::bare("person"))?;
+                let plan = LogicalPlanBuilder::scan("person", person, None)?
+                    .project(vec![col("id"), (col("age") + lit(1)).alias("next_age")])?
+                    .build()?;
+                return Ok(Arc::new(InlineTable::new(plan)));
+            }
+            // A view of `person_view`
+            "adult_view" => {
+                let person_view =
+                    self.table_source(TableReference::bare("person_view"))?;
+                let plan = LogicalPlanBuilder::scan("person_view", person_view, None)?
+                    .filter(col("next_age").gt(lit(18)))?
+                    .build()?;
+                return Ok(Arc::new(InlineTable::new(plan)));
+            }
             _ => plan_err!("No table named: {} found", name.table()),
         };
 
@@ -227,6 +386,143 @@ impl ContextProvider for MockContextProvider {
             Err(e) => Err(e),
         }
     }
+}
+
+impl ContextProvider for MockContextProvider {
+    fn get_table_source(&self, name: TableReference) -> Result<Arc<dyn TableSource>> {
+        self.state
+            .table_lookups
+            .lock()
+            .unwrap()
+            .push(name.to_string());
+        self.table_source(name)
+    }
+
+    fn get_table_sources(
+        &self,
+        names: &[TableReference],
+    ) -> Vec<Result<Arc<dyn TableSource>>> {
+        self.state
+            .table_batches
+            .lock()
+            .unwrap()
+            .push(names.iter().map(ToString::to_string).collect());
+        names
+            .iter()
+            .map(|name| self.table_source(name.clone()))
+            .collect()
+    }
+
+    fn get_table_source_at(
+        &self,
+        name: TableReference,
+        version: &TableVersion,
+    ) -> Result<Arc<dyn TableSource>> {
+        // Only `person` keeps history; its snapshots predate every column
+        // after `first_name`
+        match name.table() {
+            "person" => {
+                let schema = Schema::new(vec![
+                    Field::new("id", DataType::UInt32, false),
+                    Field::new("first_name", DataType::Utf8, false),
+                ]);
+                Ok(Arc::new(EmptyTable::new(Arc::new(schema))))
+            }
+            _ => plan_err!("No snapshot of table {} {version}", name.table()),
+        }
+    }
+
+    fn get_table_function_source(
+        &self,
+        name: &str,
+        args: Vec<Expr>,
+    ) -> Result<Arc<dyn TableSource>> {
+        match name {
+            // Returns its argument as a single row, expressed as a plan so
+            // that column arguments can be correlated
+            "echo" => {
+                let [arg] = args.as_slice() else {
+                    return plan_err!("echo expects one argument");
+                };
+                let plan = LogicalPlanBuilder::empty(true)
+                    .project(vec![arg.clone().alias("value")])?
+                    .build()?;
+                Ok(Arc::new(InlineTable::new(plan)))
+            }
+            // An opaque source that only accepts constant arguments
+            "series" => {
+                let schema =
+                    Schema::new(vec![Field::new("value", DataType::Int64, false)]);
+                Ok(Arc::new(EmptyTable::new(Arc::new(schema))))
+            }
+            _ => plan_err!("No table function named: {name} found"),
+        }
+    }
+
+    fn get_table_function_source_with_names(
+        &self,
+        name: &str,
+        args: Vec<Expr>,
+        arg_names: Vec<Option<String>>,
+    ) -> Result<Arc<dyn TableSource>> {
+        if name != "read_csv" {
+            if arg_names.iter().any(Option::is_some) {
+                return not_impl_err!(
+                    "Table function {name} does not support named arguments"
+                );
+            }
+            return self.get_table_function_source(name, args);
+        }
+
+        // read_csv(path [, header]): the header flag decides the column name
+        let param_names = vec!["path".to_string(), "header".to_string()];
+        let args = resolve_function_arguments(&param_names, args, arg_names)?;
+        let header = match args.get(1) {
+            None => false,
+            Some(Expr::Literal(ScalarValue::Boolean(Some(header)), _)) => *header,
+            Some(other) => {
+                return plan_err!("read_csv header must be a boolean, got {other}");
+            }
+        };
+        let column = if header { "id" } else { "column_1" };
+        let schema = Schema::new(vec![Field::new(column, DataType::Utf8, true)]);
+        Ok(Arc::new(EmptyTable::new(Arc::new(schema))))
+    }
+
+    fn get_polymorphic_table_function(
+        &self,
+        name: &str,
+    ) -> Option<Arc<dyn PolymorphicTableFunctionImpl>> {
+        (name == "sessionize").then(|| Arc::new(Sessionize) as _)
+    }
+
+    fn get_property_graph(
+        &self,
+        name: &TableReference,
+    ) -> Option<Arc<CreatePropertyGraph>> {
+        match name.table() {
+            "social" => Some(Arc::new(social_graph())),
+            "shop" => Some(Arc::new(shop_graph())),
+            _ => None,
+        }
+    }
+
+    /// `case_insensitive` orders strings by their lower case, when `lower`
+    /// is registered
+    fn get_collation(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        match name {
+            "case_insensitive" => self.state.scalar_functions.get("lower").cloned(),
+            _ => None,
+        }
+    }
+
+    /// `latin1` strings are converted by `to_latin1`, when it is registered
+    fn get_character_set(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        match name {
+            "latin1" => self.state.scalar_functions.get("to_latin1").cloned(),
+            _ => None,
+        }
+    }
 
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
         self.state.scalar_functions.get(name).cloned()
@@ -240,6 +536,34 @@ impl ContextProvider for MockContextProvider {
         unimplemented!()
     }
 
+    fn get_variable_field(&self, variable_names: &[String]) -> Option<FieldRef> {
+        match variable_names {
+            [name] if name == "@state" => Some(DataType::Utf8.into_nullable_field_ref()),
+            _ => None,
+        }
+    }
+
+    fn get_row_security_policies(&self, name: &TableReference) -> Vec<RowSecurityPolicy> {
+        self.state.row_security_policies.policies(name)
+    }
+
+    fn get_column_masks(&self, name: &TableReference) -> Vec<ColumnMask> {
+        self.state
+            .column_masks
+            .iter()
+            .filter(|(table, _)| table.resolved_eq(name))
+            .map(|(_, mask)| mask.clone())
+            .collect()
+    }
+
+    fn get_current_role(&self) -> Option<String> {
+        self.state.current_role.clone()
+    }
+
+    fn get_privilege_store(&self) -> Option<Arc<PrivilegeStore>> {
+        self.state.privilege_store.clone()
+    }
+
     fn get_window_meta(&self, name: &str) -> Option<Arc<WindowUDF>> {
         self.state.window_functions.get(name).cloned()
     }
@@ -260,6 +584,40 @@ impl ContextProvider for MockContextProvider {
         Ok(Arc::new(EmptyTable::new(schema)))
     }
 
+    fn table_names(&self) -> Vec<String> {
+        [
+            "test",
+            "j1",
+            "j2",
+            "j3",
+            "test_decimal",
+            "person",
+            "person_quoted_cols",
+            "person_with_uuid_extension",
+            "orders",
+            "accounts",
+            "array",
+            "knows",
+            "lineitem",
+            "aggregate_test_100",
+            "UPPERCASE_test",
+            "unnest_table",
+            "ledger",
+            "events",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+
+    fn word_reservation(&self, word: &str) -> Option<WordReservation> {
+        match word.to_ascii_lowercase().as_str() {
+            "period" => Some(WordReservation::Reserved),
+            "session_user" => Some(WordReservation::Unreserved),
+            _ => None,
+        }
+    }
+
     fn udf_names(&self) -> Vec<String> {
         self.state.scalar_functions.keys().cloned().collect()
     }
@@ -276,6 +634,18 @@ impl ContextProvider for MockContextProvider {
         &self.state.expr_planners
     }
 
+    fn get_statement_planners(&self) -> &[Arc<dyn StatementPlanner>] {
+        &self.state.statement_planners
+    }
+
+    fn get_statement_rewriters(&self) -> &[Arc<dyn StatementRewriter>] {
+        &self.state.statement_rewriters
+    }
+
+    fn get_audit_loggers(&self) -> &[Arc<dyn AuditLogger>] {
+        &self.state.audit_loggers
+    }
+
     fn get_type_planner(&self) -> Option<Arc<dyn TypePlanner>> {
         if let Some(type_planner) = &self.state.type_planner {
             Some(Arc::clone(type_planner))
@@ -304,3 +674,223 @@ impl TableSource for EmptyTable {
         Arc::clone(&self.table_schema)
     }
 }
+
+/// A table backed by a logical plan, like a view.
+struct InlineTable {
+    plan: LogicalPlan,
+}
+
+impl InlineTable {
+    fn new(plan: LogicalPlan) -> Self {
+        Self { plan }
+    }
+}
+
+impl TableSource for InlineTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(self.plan.schema().inner())
+    }
+
+    fn get_logical_plan(&'_ self) -> Option<Cow<'_, LogicalPlan>> {
+        Some(Cow::Borrowed(&self.plan))
+    }
+}
+
+/// `sessionize(TABLE events, gap)`: the input rows with a session number
+#[derive(Debug)]
+struct Sessionize;
+
+impl PolymorphicTableFunctionImpl for Sessionize {
+    fn name(&self) -> &str {
+        "sessionize"
+    }
+
+    fn describe(
+        &self,
+        args: &[TableFunctionArgument],
+        _arg_names: &[Option<String>],
+    ) -> Result<Schema> {
+        let [
+            TableFunctionArgument::Table(input),
+            TableFunctionArgument::Scalar(_),
+        ] = args
+        else {
+            return plan_err!("sessionize expects a table and a gap");
+        };
+        let mut fields = input.schema().as_arrow().fields().to_vec();
+        fields.push(Arc::new(Field::new("session_id", DataType::UInt64, false)));
+        Ok(Schema::new(fields))
+    }
+}
+
+/// `person` vertices connected by `knows` edges
+fn social_graph() -> CreatePropertyGraph {
+    let key = |columns: &[&str]| GraphKeyClause {
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+    };
+    let properties = |columns: &[&str]| GraphPropertiesClause {
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+    };
+    CreatePropertyGraph {
+        name: TableReference::bare("social"),
+        or_replace: false,
+        if_not_exists: false,
+        vertex_tables: vec![GraphVertexTableDefinition {
+            table: TableReference::bare("person"),
+            key: Some(key(&["id"])),
+            label: None,
+            properties: Some(properties(&["id", "first_name", "age", "state"])),
+        }],
+        edge_tables: vec![GraphEdgeTableDefinition {
+            table: TableReference::bare("knows"),
+            source: GraphEdgeEndpoint {
+                key: Some(key(&["src"])),
+                references: TableReference::bare("person"),
+            },
+            destination: GraphEdgeEndpoint {
+                key: Some(key(&["dst"])),
+                references: TableReference::bare("person"),
+            },
+            key: None,
+            label: None,
+            properties: Some(properties(&["since", "weight"])),
+        }],
+    }
+}
+
+/// `person` customers who know each other and buy `lineitem` items, with
+/// labels differing from the table names
+fn shop_graph() -> CreatePropertyGraph {
+    let key = |columns: &[&str]| GraphKeyClause {
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+    };
+    let properties = |columns: &[&str]| GraphPropertiesClause {
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+    };
+    CreatePropertyGraph {
+        name: TableReference::bare("shop"),
+        or_replace: false,
+        if_not_exists: false,
+        vertex_tables: vec![
+            GraphVertexTableDefinition {
+                table: TableReference::bare("person"),
+                key: Some(key(&["id"])),
+                label: Some("customer".to_string()),
+                properties: Some(properties(&["id", "first_name", "age"])),
+            },
+            GraphVertexTableDefinition {
+                table: TableReference::bare("lineitem"),
+                key: Some(key(&["l_item_id"])),
+                label: Some("item".to_string()),
+                properties: Some(properties(&["l_description", "price"])),
+            },
+        ],
+        edge_tables: vec![
+            GraphEdgeTableDefinition {
+                table: TableReference::bare("knows"),
+                source: GraphEdgeEndpoint {
+                    key: Some(key(&["src"])),
+                    references: TableReference::bare("person"),
+                },
+                destination: GraphEdgeEndpoint {
+                    key: Some(key(&["dst"])),
+                    references: TableReference::bare("person"),
+                },
+                key: None,
+                label: None,
+                properties: Some(properties(&["since"])),
+            },
+            GraphEdgeTableDefinition {
+                table: TableReference::bare("orders"),
+                source: GraphEdgeEndpoint {
+                    key: Some(key(&["customer_id"])),
+                    references: TableReference::bare("person"),
+                },
+                destination: GraphEdgeEndpoint {
+                    key: Some(key(&["o_item_id"])),
+                    references: TableReference::bare("lineitem"),
+                },
+                key: None,
+                label: Some("bought".to_string()),
+                properties: Some(properties(&["qty", "price"])),
+            },
+        ],
+    }
+}
+
+/// A table with a single index, `events_ts`, that honors index hints and
+/// ignores `NOLOCK`.
+struct IndexedTable {
+    table_schema: SchemaRef,
+}
+
+impl IndexedTable {
+    fn new(table_schema: SchemaRef) -> Self {
+        Self { table_schema }
+    }
+}
+
+impl TableSource for IndexedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.table_schema)
+    }
+
+    fn supports_hint(
+        &self,
+        name: &str,
+        args: &[String],
+    ) -> Result<TableProviderHintSupport> {
+        if name == "NOLOCK" {
+            return Ok(TableProviderHintSupport::Ignored);
+        }
+        if !name.contains("INDEX") {
+            return Ok(TableProviderHintSupport::Unsupported);
+        }
+        match args.iter().find(|index| *index != "events_ts") {
+            Some(index) => plan_err!("Unknown index {index}"),
+            None => Ok(TableProviderHintSupport::Exact),
+        }
+    }
+}
+
+/// A table that locks rows but cannot skip locked ones, like a backend
+/// without `SKIP LOCKED` support.
+struct NoSkipLockedTable {
+    table_schema: SchemaRef,
+}
+
+impl NoSkipLockedTable {
+    fn new(table_schema: SchemaRef) -> Self {
+        Self { table_schema }
+    }
+}
+
+impl TableSource for NoSkipLockedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.table_schema)
+    }
+
+    fn supports_row_lock(
+        &self,
+        lock: &TableScanRowLock,
+    ) -> Result<TableProviderRowLockSupport> {
+        Ok(match lock.wait_policy {
+            TableScanRowLockWaitPolicy::SkipLocked => {
+                TableProviderRowLockSupport::Unsupported
+            }
+            _ => TableProviderRowLockSupport::Exact,
+        })
+    }
+}
@@ -71,6 +71,7 @@ use datafusion_expr::test::function_stub::{
 };
 
 // Re-export submodules for each standard part
+pub mod part14_xml;
 pub mod part2_foundation;
 pub mod part4_psm;
 
@@ -1143,18 +1144,74 @@ stub_typed_udf!(
 stub_scalar_udf!(RegexpReplace, "regexp_replace");
 stub_scalar_udf!(RegexpSubstr, "regexp_substr");
 
+/// Macro to create a stub SQL/JSON function, which accepts the clauses of the
+/// SQL/JSON functions, such as `ABSENT ON NULL` and `RETURNING`.
+macro_rules! stub_json_udf {
+    ($name:ident, $fn_name:expr, $return_type:expr) => {
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        pub struct $name {
+            signature: Signature,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    signature: Signature::variadic_any(Volatility::Immutable),
+                }
+            }
+        }
+
+        impl ScalarUDFImpl for $name {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn name(&self) -> &str {
+                $fn_name
+            }
+
+            fn signature(&self) -> &Signature {
+                &self.signature
+            }
+
+            fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+                Ok($return_type)
+            }
+
+            // Returns the type of the RETURNING clause, if any
+            fn return_field_from_args(&self, args: datafusion_expr::ReturnFieldArgs) -> Result<Arc<Field>> {
+                let data_type = datafusion_expr::JsonFunctionOptions::from_scalar_arguments(args.scalar_arguments)
+                    .and_then(|options| options.returning)
+                    .unwrap_or($return_type);
+                Ok(Arc::new(Field::new(self.name(), data_type, true)))
+            }
+
+            fn invoke_with_args(&self, _args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+                not_impl_err!("stub function {} should not be invoked", $fn_name)
+            }
+
+            fn supports_json_clauses(&self) -> bool {
+                true
+            }
+        }
+
+        paste::paste! {
+            pub fn [<$name:snake _udf>]() -> Arc<ScalarUDF> {
+                static INSTANCE: std::sync::LazyLock<Arc<ScalarUDF>> =
+                    std::sync::LazyLock::new(|| Arc::new(ScalarUDF::from($name::default())));
+                Arc::clone(&INSTANCE)
+            }
+        }
+    };
+}
+
 // JSON functions
-stub_scalar_udf!(JsonArray, "json_array");
+stub_json_udf!(JsonArray, "json_array", DataType::Utf8);
 // JSON_EXISTS returns true/false for path existence checks
-stub_typed_udf!(
-    JsonExists,
-    "json_exists",
-    Signature::variadic_any(Volatility::Immutable),
-    DataType::Boolean
-);
-stub_scalar_udf!(JsonQuery, "json_query");
-stub_scalar_udf!(JsonValue, "json_value");
-stub_scalar_udf!(JsonObject, "json_object");
+stub_json_udf!(JsonExists, "json_exists", DataType::Boolean);
+stub_json_udf!(JsonQuery, "json_query", DataType::Utf8);
+stub_json_udf!(JsonValue, "json_value", DataType::Utf8);
+stub_json_udf!(JsonObject, "json_object", DataType::Utf8);
 
 // IS JSON predicates - all return Boolean
 stub_typed_udf!(
@@ -1194,6 +1251,20 @@ stub_typed_udf!(
     DataType::Boolean
 );
 
+// XML publishing functions
+stub_typed_udf!(
+    XmlElement,
+    "xmlelement",
+    Signature::variadic_any(Volatility::Immutable),
+    DataType::Utf8
+);
+stub_typed_udf!(
+    XmlForest,
+    "xmlforest",
+    Signature::variadic_any(Volatility::Immutable),
+    DataType::Utf8
+);
+
 // ============================================================================
 // Additional Aggregate Function Stubs
 // ============================================================================
@@ -1321,6 +1392,10 @@ impl datafusion_expr::AggregateUDFImpl for ListAgg {
     fn supports_within_group_clause(&self) -> bool {
         true
     }
+
+    fn supports_on_overflow_clause(&self) -> bool {
+        true
+    }
 }
 
 pub fn list_agg_udaf() -> Arc<AggregateUDF> {
@@ -1329,9 +1404,150 @@ pub fn list_agg_udaf() -> Arc<AggregateUDF> {
     Arc::clone(&INSTANCE)
 }
 
+// Inverse distribution functions are ordered-set aggregates as well
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct InverseDistribution {
+    name: &'static str,
+    signature: Signature,
+}
+
+impl InverseDistribution {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+}
+
+impl datafusion_expr::AggregateUDFImpl for InverseDistribution {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match self.name {
+            "percentile_disc" => Ok(arg_types[0].clone()),
+            _ => Ok(DataType::Float64),
+        }
+    }
+
+    fn accumulator(
+        &self,
+        _args: datafusion_expr::function::AccumulatorArgs,
+    ) -> Result<Box<dyn datafusion_expr::Accumulator>> {
+        not_impl_err!("stub aggregate {} should not be invoked", self.name)
+    }
+
+    fn state_fields(
+        &self,
+        _args: datafusion_expr::function::StateFieldsArgs,
+    ) -> Result<Vec<Arc<Field>>> {
+        not_impl_err!("stub aggregate {} should not have state_fields", self.name)
+    }
+
+    fn supports_within_group_clause(&self) -> bool {
+        true
+    }
+}
+
+pub fn percentile_cont_udaf() -> Arc<AggregateUDF> {
+    static INSTANCE: std::sync::LazyLock<Arc<AggregateUDF>> =
+        std::sync::LazyLock::new(|| {
+            Arc::new(AggregateUDF::from(InverseDistribution::new(
+                "percentile_cont",
+            )))
+        });
+    Arc::clone(&INSTANCE)
+}
+
+pub fn percentile_disc_udaf() -> Arc<AggregateUDF> {
+    static INSTANCE: std::sync::LazyLock<Arc<AggregateUDF>> =
+        std::sync::LazyLock::new(|| {
+            Arc::new(AggregateUDF::from(InverseDistribution::new(
+                "percentile_disc",
+            )))
+        });
+    Arc::clone(&INSTANCE)
+}
+
+/// Macro to create a stub SQL/JSON aggregate function, which accepts the
+/// clauses of the SQL/JSON functions, such as `ABSENT ON NULL` and `RETURNING`.
+macro_rules! stub_json_aggregate_udf {
+    ($name:ident, $fn_name:expr) => {
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        pub struct $name {
+            signature: Signature,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    signature: Signature::variadic_any(Volatility::Immutable),
+                }
+            }
+        }
+
+        impl datafusion_expr::AggregateUDFImpl for $name {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn name(&self) -> &str {
+                $fn_name
+            }
+
+            fn signature(&self) -> &Signature {
+                &self.signature
+            }
+
+            fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+                Ok(DataType::Utf8)
+            }
+
+            fn accumulator(
+                &self,
+                _args: datafusion_expr::function::AccumulatorArgs,
+            ) -> Result<Box<dyn datafusion_expr::Accumulator>> {
+                not_impl_err!("stub aggregate {} should not be invoked", $fn_name)
+            }
+
+            fn state_fields(
+                &self,
+                _args: datafusion_expr::function::StateFieldsArgs,
+            ) -> Result<Vec<Arc<Field>>> {
+                not_impl_err!("stub aggregate {} should not have state_fields", $fn_name)
+            }
+
+            fn supports_json_clauses(&self) -> bool {
+                true
+            }
+        }
+
+        paste::paste! {
+            pub fn [<$name:snake _udaf>]() -> Arc<AggregateUDF> {
+                static INSTANCE: std::sync::LazyLock<Arc<AggregateUDF>> =
+                    std::sync::LazyLock::new(|| Arc::new(AggregateUDF::from($name::default())));
+                Arc::clone(&INSTANCE)
+            }
+        }
+    };
+}
+
 // JSON aggregate functions
-stub_aggregate_udf!(JsonArrayAgg, "json_arrayagg");
-stub_aggregate_udf!(JsonObjectAgg, "json_objectagg");
+stub_json_aggregate_udf!(JsonArrayAgg, "json_arrayagg");
+stub_json_aggregate_udf!(JsonObjectAgg, "json_objectagg");
+
+// XML aggregate functions
+stub_aggregate_udf!(XmlAgg, "xmlagg");
 
 // SQL:2023 aggregate functions
 stub_aggregate_udf!(AnyValue, "any_value");
@@ -1392,6 +1608,11 @@ macro_rules! stub_window_udf {
     };
     // Default variant for variadic window functions
     ($name:ident, $fn_name:expr) => {
+        stub_window_udf!($name, $fn_name, supports_null_handling = false);
+    };
+    // Variant for variadic window functions, such as LEAD and LAG, that may
+    // support IGNORE NULLS / RESPECT NULLS
+    ($name:ident, $fn_name:expr, supports_null_handling = $supports_null_handling:expr) => {
         #[derive(Debug, PartialEq, Eq, Hash)]
         pub struct $name {
             signature: Signature,
@@ -1428,6 +1649,10 @@ macro_rules! stub_window_udf {
             fn field(&self, _field_args: datafusion_expr::function::WindowUDFFieldArgs) -> Result<Arc<Field>> {
                 Ok(Arc::new(Field::new($fn_name, DataType::Int64, true)))
             }
+
+            fn supports_null_handling_clause(&self) -> bool {
+                $supports_null_handling
+            }
         }
 
         paste::paste! {
@@ -1447,11 +1672,11 @@ stub_window_udf!(DenseRank, "dense_rank", zero_arg);
 stub_window_udf!(PercentRank, "percent_rank", zero_arg);
 stub_window_udf!(CumeDist, "cume_dist", zero_arg);
 stub_window_udf!(Ntile, "ntile");
-stub_window_udf!(Lead, "lead");
-stub_window_udf!(Lag, "lag");
-stub_window_udf!(FirstValue, "first_value");
-stub_window_udf!(LastValue, "last_value");
-stub_window_udf!(NthValue, "nth_value");
+stub_window_udf!(Lead, "lead", supports_null_handling = true);
+stub_window_udf!(Lag, "lag", supports_null_handling = true);
+stub_window_udf!(FirstValue, "first_value", supports_null_handling = true);
+stub_window_udf!(LastValue, "last_value", supports_null_handling = true);
+stub_window_udf!(NthValue, "nth_value", supports_null_handling = true);
 
 // MATCH_RECOGNIZE navigation functions (T625)
 stub_window_udf!(First, "first");
@@ -1500,9 +1725,14 @@ impl ConformanceFunctionProvider for DataFusionFunctionProvider {
             "array_agg" => Some(array_agg_udaf()),
             "listagg" => Some(list_agg_udaf()),
             "string_agg" => Some(string_agg_udaf()),
+            // Inverse distribution functions
+            "percentile_cont" => Some(percentile_cont_udaf()),
+            "percentile_disc" => Some(percentile_disc_udaf()),
             // JSON aggregates
             "json_arrayagg" => Some(json_array_agg_udaf()),
             "json_objectagg" => Some(json_object_agg_udaf()),
+            // XML aggregates
+            "xmlagg" => Some(xml_agg_udaf()),
             // SQL:2023 aggregates
             "any_value" => Some(any_value_udaf()),
             _ => None,
@@ -1645,6 +1875,10 @@ impl ConformanceFunctionProvider for DataFusionFunctionProvider {
             "json_value" => Some(json_value_udf()),
             "json_object" => Some(json_object_udf()),
 
+            // XML publishing functions
+            "xmlelement" => Some(xml_element_udf()),
+            "xmlforest" => Some(xml_forest_udf()),
+
             // IS JSON predicates
             "is_json" => Some(is_json_udf()),
             "is_json_array" => Some(is_json_array_udf()),
@@ -2447,6 +2681,7 @@ impl<'a, F: ConformanceFunctionProvider> ContextProvider for ConformanceContextP
                 Field::new("spouse_name", DataType::Utf8, true), // For T151 tests
                 Field::new("status", DataType::Utf8, true), // For F031 view tests
                 Field::new("action", DataType::Utf8, true), // For R010 user session pattern tests
+                Field::new("email", DataType::Utf8, true), // For PGQ001 property graph tests
             ])),
 
             // Orders table for join tests
@@ -2502,6 +2737,12 @@ impl<'a, F: ConformanceFunctionProvider> ContextProvider for ConformanceContextP
                 Field::new("data", DataType::Utf8, true), // JSON as string for now
             ])),
 
+            // XML data test table
+            "xml_data" => Ok(Schema::new(vec![
+                Field::new("id", DataType::Int32, false),
+                Field::new("doc", DataType::Utf8, true), // XML as string
+            ])),
+
             // Events table for JSON testing
             "events" => Ok(Schema::new(vec![
                 Field::new("id", DataType::Int32, false),
@@ -2569,6 +2810,28 @@ impl<'a, F: ConformanceFunctionProvider> ContextProvider for ConformanceContextP
                 Field::new("salary", DataType::Float64, false),
             ])),
 
+            // Element tables for PGQ001 property graph tests
+            "company" => Ok(Schema::new(vec![
+                Field::new("id", DataType::UInt32, false),
+                Field::new("name", DataType::Utf8, false),
+            ])),
+            "knows" => Ok(Schema::new(vec![
+                Field::new("src_id", DataType::UInt32, false),
+                Field::new("dst_id", DataType::UInt32, false),
+                Field::new("src", DataType::UInt32, false),
+                Field::new("dst", DataType::UInt32, false),
+                Field::new("since", DataType::Int32, true),
+                Field::new("strength", DataType::Float64, true),
+            ])),
+            "friendship" => Ok(Schema::new(vec![
+                Field::new("person1_id", DataType::UInt32, false),
+                Field::new("person2_id", DataType::UInt32, false),
+            ])),
+            "worksat" => Ok(Schema::new(vec![
+                Field::new("person_id", DataType::UInt32, false),
+                Field::new("company_id", DataType::UInt32, false),
+            ])),
+
             _ => plan_err!("Table not found: {}", table_name),
         };
 
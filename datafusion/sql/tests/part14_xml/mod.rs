@@ -0,0 +1,41 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SQL:2016 Part 14 - SQL/XML Conformance Tests
+//!
+//! This module contains tests for ISO/IEC 9075-14:2016 (SQL/XML),
+//! which defines the XML support of SQL including:
+//!
+//! - XML publishing functions (XMLELEMENT, XMLFOREST, XMLAGG)
+//! - XML query functions (XMLTABLE)
+//!
+//! # Feature Organization
+//!
+//! Tests are organized by feature ID:
+//! - X-series: SQL/XML features (X010-X400)
+//!
+//! All SQL/XML features are optional.
+//!
+//! | Feature | Description | Status |
+//! |---------|-------------|--------|
+//! | X031 | XMLElement | Partial |
+//! | X032 | XMLForest | Partial |
+//! | X034 | XMLAgg | Partial |
+//! | X300 | XMLTable | Partial |
+
+pub mod x03x_xml_publishing;
+pub mod x300_xmltable;
@@ -0,0 +1,118 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SQL:2016 Features X031-X035 - XML publishing functions
+//!
+//! ISO/IEC 9075-14:2016 Section 6.5 - <XML element>, Section 6.7 - <XML
+//! forest> and Section 10.9 - <aggregate function>
+//!
+//! | Feature | Description | Status |
+//! |---------|-------------|--------|
+//! | X031 | XMLElement | Planned only |
+//! | X032 | XMLForest | Planned only |
+//! | X034 | XMLAgg | Planned only |
+//! | X035 | XMLAgg: ORDER BY option | Planned only |
+//!
+//! The element and attribute names are planned as literal arguments of the
+//! `xmlelement` and `xmlforest` functions, which construct the XML values.
+
+use crate::{assert_plan_error, assert_plans};
+
+// ============================================================================
+// X031: XMLElement
+// ============================================================================
+
+/// X031: XMLELEMENT with attributes and content
+#[test]
+fn x031_xmlelement_attributes_and_content() {
+    let plan = crate::logical_plan(
+        "SELECT XMLELEMENT(NAME item, XMLATTRIBUTES(id, doc AS source), 'text', doc)
+         FROM xml_data",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(plan.contains("xmlelement(Utf8(\"item\"), List("), "{plan}");
+    assert!(
+        plan.contains("xml_data.id, xml_data.doc, Utf8(\"text\"), xml_data.doc)"),
+        "{plan}"
+    );
+}
+
+/// X031: XMLELEMENT with only a name
+#[test]
+fn x031_xmlelement_empty() {
+    assert_plans!("SELECT XMLELEMENT(NAME empty) FROM xml_data");
+}
+
+/// X031: Attributes of expressions other than columns need a name
+#[test]
+fn x031_xmlattributes_unnamed_expression() {
+    assert_plan_error!(
+        "SELECT XMLELEMENT(NAME item, XMLATTRIBUTES(id + 1)) FROM xml_data",
+        "XMLATTRIBUTES requires a name for the value id + 1"
+    );
+}
+
+/// X031: Attribute names must be distinct
+#[test]
+fn x031_xmlattributes_duplicate_name() {
+    assert_plan_error!(
+        "SELECT XMLELEMENT(NAME item, XMLATTRIBUTES(id, doc AS id)) FROM xml_data",
+        "XMLATTRIBUTES has more than one attribute named id"
+    );
+}
+
+// ============================================================================
+// X032: XMLForest
+// ============================================================================
+
+/// X032: XMLFOREST of columns and named expressions
+#[test]
+fn x032_xmlforest() {
+    let plan =
+        crate::logical_plan("SELECT XMLFOREST(id, upper(doc) AS content) FROM xml_data")
+            .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(plan.contains("xmlforest(List("), "{plan}");
+}
+
+/// X032: Elements of expressions other than columns need a name
+#[test]
+fn x032_xmlforest_unnamed_expression() {
+    assert_plan_error!(
+        "SELECT XMLFOREST(upper(doc)) FROM xml_data",
+        "XMLFOREST requires a name for the value"
+    );
+}
+
+// ============================================================================
+// X034/X035: XMLAgg
+// ============================================================================
+
+/// X034: XMLAGG of constructed elements
+#[test]
+fn x034_xmlagg() {
+    assert_plans!("SELECT XMLAGG(XMLELEMENT(NAME item, doc)) FROM xml_data");
+}
+
+/// X035: XMLAGG with ORDER BY
+#[test]
+fn x035_xmlagg_order_by() {
+    assert_plans!(
+        "SELECT XMLAGG(XMLFOREST(id, doc) ORDER BY id DESC) FROM xml_data GROUP BY doc"
+    );
+}
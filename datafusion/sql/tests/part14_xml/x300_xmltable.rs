@@ -0,0 +1,164 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SQL:2016 Feature X300 - XMLTable
+//!
+//! ISO/IEC 9075-14:2016 Section 7.1 - <XML table>
+//!
+//! XMLTABLE produces a row for each item of an XQuery expression evaluated
+//! against the values of its PASSING clause, with columns extracted from
+//! the item by their own XQuery expressions.
+//!
+//! | Subfeature | Description | Status |
+//! |------------|-------------|--------|
+//! | X300 | XMLTable | Planned only |
+//! | X301 | XMLTable: derived column list option | Planned only |
+//! | X302 | XMLTable: ordinality column option | Planned only |
+//! | X303 | XMLTable: column default option | Planned only |
+//! | X304 | XMLTable: passing a context item | Planned only |
+//! | X305 | XMLTable: initializing an XQuery variable | Planned only |
+//!
+//! XMLTABLE is planned into an `XmlTable` logical plan node; evaluating
+//! the XQuery expressions is left to the execution engine.
+
+use crate::{assert_plan_error, assert_plans};
+use arrow::datatypes::DataType;
+
+// ============================================================================
+// X300: XMLTable
+// ============================================================================
+
+/// X300: XMLTABLE over a column of a preceding FROM item
+#[test]
+fn x300_xmltable_basic() {
+    let plan = crate::logical_plan(
+        "SELECT x.* FROM xml_data,
+         XMLTABLE('/orders/order' PASSING xml_data.doc
+             COLUMNS id INT PATH '@id', customer VARCHAR(100) PATH 'customer') AS x",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(
+        plan.contains("XmlTable: row=/orders/order passing=[outer_ref(xml_data.doc)]"),
+        "{plan}"
+    );
+    assert!(plan.contains("Subquery:"), "{plan}");
+}
+
+/// X300: The columns of XMLTABLE have their declared types
+#[test]
+fn x300_xmltable_column_types() {
+    let plan = crate::logical_plan(
+        "SELECT x.* FROM xml_data,
+         XMLTABLE('/a' PASSING doc COLUMNS id INT PATH '@id', name VARCHAR(20) NOT NULL) AS x",
+    )
+    .unwrap();
+    let fields = plan.schema().fields();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].name(), "id");
+    assert_eq!(fields[0].data_type(), &DataType::Int32);
+    assert!(fields[0].is_nullable());
+    assert_eq!(fields[1].name(), "name");
+    assert!(!fields[1].is_nullable());
+}
+
+/// X300: The row expression must be a character string literal
+#[test]
+fn x300_xmltable_row_expression_not_literal() {
+    assert_plan_error!(
+        "SELECT x.* FROM xml_data, XMLTABLE(doc PASSING doc COLUMNS id INT) AS x",
+        "XMLTABLE row expression must be a string literal"
+    );
+}
+
+// ============================================================================
+// X302: XMLTable: ordinality column option
+// ============================================================================
+
+/// X302: FOR ORDINALITY column
+#[test]
+fn x302_xmltable_ordinality() {
+    let plan = crate::logical_plan(
+        "SELECT x.n FROM xml_data,
+         XMLTABLE('/a' PASSING doc COLUMNS n FOR ORDINALITY, id INT PATH '@id') AS x",
+    )
+    .unwrap();
+    assert_eq!(plan.schema().field(0).data_type(), &DataType::Int64);
+}
+
+/// X302: At most one FOR ORDINALITY column
+#[test]
+fn x302_xmltable_two_ordinality_columns() {
+    assert_plan_error!(
+        "SELECT x.* FROM xml_data,
+         XMLTABLE('/a' PASSING doc COLUMNS n FOR ORDINALITY, m FOR ORDINALITY) AS x",
+        "XMLTABLE can have at most one FOR ORDINALITY column"
+    );
+}
+
+// ============================================================================
+// X303: XMLTable: column default option
+// ============================================================================
+
+/// X303: DEFAULT value of a column
+#[test]
+fn x303_xmltable_column_default() {
+    assert_plans!(
+        "SELECT x.* FROM xml_data,
+         XMLTABLE('/a' PASSING doc COLUMNS name VARCHAR(20) PATH 'name' DEFAULT 'unknown') AS x"
+    );
+}
+
+// ============================================================================
+// X304/X305: XMLTable: passing a context item and XQuery variables
+// ============================================================================
+
+/// X305: PASSING named values bound to XQuery variables
+#[test]
+fn x305_xmltable_passing_variables() {
+    let plan = crate::logical_plan(
+        "SELECT x.* FROM xml_data,
+         XMLTABLE('$d/a' PASSING BY VALUE doc AS d, id AS i COLUMNS v INT PATH '.') AS x",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(
+        plan.contains(
+            "passing=[outer_ref(xml_data.doc) AS d, outer_ref(xml_data.id) AS i]"
+        ),
+        "{plan}"
+    );
+}
+
+/// X304: Only one context item may be passed
+#[test]
+fn x304_xmltable_two_context_items() {
+    assert_plan_error!(
+        "SELECT x.* FROM xml_data, XMLTABLE('/a' PASSING doc, doc COLUMNS id INT) AS x",
+        "XMLTABLE passes more than one context item"
+    );
+}
+
+/// X305: XQuery variables must have distinct names
+#[test]
+fn x305_xmltable_duplicate_variable() {
+    assert_plan_error!(
+        "SELECT x.* FROM xml_data,
+         XMLTABLE('$d/a' PASSING doc AS d, doc AS d COLUMNS id INT) AS x",
+        "XMLTABLE passes more than one value named d"
+    );
+}
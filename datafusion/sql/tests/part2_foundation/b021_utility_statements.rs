@@ -33,14 +33,14 @@
 //! | DESCRIBE | Describe table structure | Partial |
 //! | TRUNCATE | Truncate table | Partial |
 //! | ANALYZE | Analyze table statistics | Partial |
-//! | VACUUM | Reclaim storage space | Not Implemented |
+//! | VACUUM | Reclaim storage space | Partial |
 //! | USE | Set current schema/database | Not Implemented |
 //!
 //! B021 is part of Direct SQL support for interactive and batch SQL execution.
 //!
 //! Tests that fail indicate gaps in DataFusion's utility statement support.
 
-use crate::{assert_plans, assert_feature_supported};
+use crate::{assert_feature_supported, assert_plan_error, assert_plans};
 
 // ============================================================================
 // COPY Statement
@@ -784,6 +784,41 @@ fn b021_vacuum_analyze() {
     );
 }
 
+/// VACUUM: SORT ONLY and DELETE ONLY select the maintenance of the tables
+#[test]
+fn b021_vacuum_options() {
+    for (sql, expected) in [
+        ("VACUUM person", "Vacuum: Some(\"person\")"),
+        (
+            "VACUUM SORT ONLY person",
+            "Vacuum: Some(\"person\") SORT ONLY",
+        ),
+        (
+            "VACUUM DELETE ONLY person",
+            "Vacuum: Some(\"person\") DELETE ONLY",
+        ),
+        (
+            "VACUUM FULL person TO 75 PERCENT",
+            "Vacuum: Some(\"person\") TO 75 PERCENT",
+        ),
+    ] {
+        let plan = crate::logical_plan(sql)
+            .unwrap()
+            .display_indent()
+            .to_string();
+        assert_eq!(plan.trim(), expected, "{sql}");
+    }
+}
+
+/// VACUUM: The threshold is a percentage
+#[test]
+fn b021_vacuum_invalid_threshold() {
+    assert_plan_error!(
+        "VACUUM FULL person TO 150 PERCENT",
+        "VACUUM threshold must be a percentage between 0 and 100, got 150"
+    );
+}
+
 // ============================================================================
 // USE Statement (Database/Schema selection)
 // ============================================================================
@@ -38,7 +38,7 @@
 //!
 //! All E021 subfeatures are CORE features (mandatory for SQL:2016 conformance).
 
-use crate::{assert_plans, assert_feature_supported};
+use crate::{assert_feature_supported, assert_plan_error, assert_plans};
 
 // ============================================================================
 // E021-01: CHARACTER data type
@@ -204,6 +204,23 @@ fn e021_03_literal_in_where() {
     );
 }
 
+/// E021-03: Unicode character string literal with escaped code points (F393)
+#[test]
+fn e021_03_unicode_escape_literal() {
+    assert_feature_supported!(
+        "SELECT U&'d\\0061t\\+000061 \\D83D\\DE00'",
+        "F393",
+        "Unicode escapes in literals"
+    );
+}
+
+/// E021-03: A Unicode escape of a high surrogate must be followed by the
+/// escape of a low surrogate (F393)
+#[test]
+fn e021_03_unicode_escape_lone_surrogate() {
+    assert_plan_error!("SELECT U&'\\D83D'", "Invalid Unicode surrogate pair");
+}
+
 // ============================================================================
 // E021-04: CHARACTER_LENGTH function
 // ============================================================================
@@ -700,6 +717,26 @@ fn e021_12_column_comparison() {
     );
 }
 
+/// E021-12: Comparison and ordering with an explicit collation
+#[test]
+fn e021_12_collate() {
+    assert_feature_supported!(
+        "SELECT first_name FROM person WHERE first_name COLLATE \"C\" < 'M' \
+         ORDER BY first_name COLLATE \"C\"",
+        "E021-12",
+        "COLLATE in comparison and ORDER BY"
+    );
+}
+
+/// E021-12: COLLATE requires a known collation
+#[test]
+fn e021_12_collate_unknown_collation() {
+    assert_plan_error!(
+        "SELECT * FROM person WHERE first_name COLLATE no_such_collation < 'M'",
+        "Unknown collation no_such_collation"
+    );
+}
+
 // ============================================================================
 // Summary Tests - Verify overall E021 support
 // ============================================================================
@@ -23,10 +23,10 @@
 //!
 //! | Feature | Subfeature | Description | Status |
 //! |---------|------------|-------------|--------|
-//! | E081 | E081-01 | SELECT privilege at table level | Not Implemented |
-//! | E081 | E081-02 | DELETE privilege | Not Implemented |
-//! | E081 | E081-03 | INSERT privilege at table level | Not Implemented |
-//! | E081 | E081-04 | UPDATE privilege at table level | Not Implemented |
+//! | E081 | E081-01 | SELECT privilege at table level | Partial |
+//! | E081 | E081-02 | DELETE privilege | Partial |
+//! | E081 | E081-03 | INSERT privilege at table level | Partial |
+//! | E081 | E081-04 | UPDATE privilege at table level | Partial |
 //! | E081 | E081-05 | UPDATE privilege at column level | Not Implemented |
 //! | E081 | E081-06 | REFERENCES privilege at table level | Not Implemented |
 //! | E081 | E081-07 | REFERENCES privilege at column level | Not Implemented |
@@ -39,7 +39,7 @@
 //! | Feature | Description | Status |
 //! |---------|-------------|--------|
 //! | T331 | Basic roles (CREATE ROLE, DROP ROLE) | Not Implemented |
-//! | T332 | Extended roles (GRANT role TO user) | Not Implemented |
+//! | T332 | Extended roles (GRANT role TO user) | Partial |
 //!
 //! E081 is a CORE feature (mandatory for SQL:2016 conformance).
 //!
@@ -945,6 +945,28 @@ fn t332_revoke_admin_option_for() {
     );
 }
 
+/// T332: SET ROLE
+#[test]
+fn t332_set_role() {
+    assert_feature_supported!("SET ROLE reporting_role", "T332", "SET ROLE");
+}
+
+/// T332: SET ROLE NONE
+#[test]
+fn t332_set_role_none() {
+    assert_feature_supported!("SET ROLE NONE", "T332", "SET ROLE NONE");
+}
+
+/// T332: SET SESSION AUTHORIZATION
+#[test]
+fn t332_set_session_authorization() {
+    assert_feature_supported!(
+        "SET SESSION AUTHORIZATION alice",
+        "T332",
+        "SET SESSION AUTHORIZATION"
+    );
+}
+
 // ============================================================================
 // Role usage in privileges
 // ============================================================================
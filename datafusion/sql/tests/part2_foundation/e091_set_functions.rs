@@ -719,6 +719,26 @@ fn e091_07_distinct_in_having() {
     );
 }
 
+/// E091-07: DISTINCT combined with FILTER
+#[test]
+fn e091_07_distinct_with_filter() {
+    assert_feature_supported!(
+        "SELECT SUM(DISTINCT a) FILTER (WHERE b > 10), COUNT(DISTINCT b) FILTER (WHERE a < 5) FROM t",
+        "E091-07",
+        "DISTINCT combined with FILTER"
+    );
+}
+
+/// E091-07: DISTINCT combined with ORDER BY and FILTER
+#[test]
+fn e091_07_distinct_with_order_by_and_filter() {
+    assert_feature_supported!(
+        "SELECT category, ARRAY_AGG(DISTINCT customer_id ORDER BY customer_id DESC) FILTER (WHERE price > 100) FROM orders GROUP BY category",
+        "E091-07",
+        "DISTINCT combined with ORDER BY and FILTER"
+    );
+}
+
 // ============================================================================
 // Mixed aggregate function scenarios
 // ============================================================================
@@ -72,7 +72,7 @@
 //! supported and tested. The failures are due to the test framework not registering
 //! built-in aggregate functions, not due to join-related conformance issues.
 
-use crate::assert_feature_supported;
+use crate::{assert_feature_supported, assert_plan_error};
 
 // ============================================================================
 // F041-01: Inner join (but not necessarily the INNER keyword)
@@ -1014,3 +1014,55 @@ fn complex_multiple_joins_using() {
         "Multiple JOINs with USING"
     );
 }
+
+// ============================================================================
+// ASOF JOIN (extension)
+// ============================================================================
+
+/// ASOF JOIN keeps, for each left row, the closest right row by the match
+/// condition
+#[test]
+fn asof_join_closest_match() {
+    let plan = crate::logical_plan(
+        "SELECT p.symbol, p.close, q.close FROM stock_prices AS p \
+         ASOF JOIN stock_prices AS q \
+         MATCH_CONDITION (p.trade_date >= q.trade_date) \
+         ON p.symbol = q.symbol",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(plan.contains("Left Join:"), "{plan}");
+    assert!(
+        plan.contains("ORDER BY [q.trade_date DESC NULLS LAST]"),
+        "{plan}"
+    );
+    assert!(plan.contains("= UInt64(1)"), "{plan}");
+}
+
+/// ASOF JOIN orders by the right key ascending when the closest key is the
+/// smallest one
+#[test]
+fn asof_join_closest_following_match() {
+    let plan = crate::logical_plan(
+        "SELECT * FROM stock_prices AS p \
+         ASOF JOIN stock_prices AS q \
+         MATCH_CONDITION (q.trade_date >= p.trade_date)",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(
+        plan.contains("ORDER BY [q.trade_date ASC NULLS LAST]"),
+        "{plan}"
+    );
+}
+
+/// ASOF JOIN requires an inequality between a key of each side
+#[test]
+fn asof_join_equality_match_condition() {
+    assert_plan_error!(
+        "SELECT * FROM stock_prices AS p \
+         ASOF JOIN stock_prices AS q \
+         MATCH_CONDITION (p.trade_date = q.trade_date)",
+        "ASOF JOIN MATCH_CONDITION must compare a key of each side"
+    );
+}
@@ -44,6 +44,12 @@
 //! | F052 | Date/time arithmetic | Supported |
 //! | F052 | EXTRACT function | Supported |
 //!
+//! # F053: OVERLAPS predicate
+//!
+//! | Subfeature | Description | Status |
+//! |------------|-------------|--------|
+//! | F053 | OVERLAPS predicate | Supported |
+//!
 //! # F411: Time zone specification
 //!
 //! | Subfeature | Description | Status |
@@ -54,7 +60,7 @@
 //!
 //! F051 is a CORE feature (mandatory for SQL:2016 conformance).
 
-use crate::{assert_feature_supported, assert_plans};
+use crate::{assert_feature_supported, assert_plan_error, assert_plans};
 
 // ============================================================================
 // F051-01: DATE data type (including DATE literal)
@@ -792,6 +798,51 @@ fn f052_interval_literal_postgres_style() {
     );
 }
 
+/// F052: INTERVAL data type with fractional seconds precision
+#[test]
+fn f052_interval_day_to_second_precision_type() {
+    assert_feature_supported!(
+        "CREATE TABLE t (duration INTERVAL DAY TO SECOND(3))",
+        "F052",
+        "INTERVAL DAY TO SECOND(3) data type"
+    );
+}
+
+/// F052: INTERVAL literal - leading field precision
+#[test]
+fn f052_interval_literal_leading_precision() {
+    assert_feature_supported!(
+        "SELECT INTERVAL '120' DAY(3)",
+        "F052",
+        "INTERVAL literal with leading field precision"
+    );
+}
+
+/// F052: INTERVAL literal - fractional seconds precision
+#[test]
+fn f052_interval_literal_fractional_seconds_precision() {
+    assert_feature_supported!(
+        "SELECT INTERVAL '1 12:30:45.123' DAY TO SECOND(3)",
+        "F052",
+        "INTERVAL literal with fractional seconds precision"
+    );
+}
+
+/// F052: INTERVAL literal leading field must fit its precision
+#[test]
+fn f052_interval_literal_leading_precision_overflow() {
+    assert_plan_error!("SELECT INTERVAL '1200' DAY(3)", "Interval field overflow");
+}
+
+/// F052: INTERVAL literal fractional seconds must fit their precision
+#[test]
+fn f052_interval_literal_fractional_seconds_precision_overflow() {
+    assert_plan_error!(
+        "SELECT INTERVAL '45.1234' SECOND(2, 3)",
+        "Interval field overflow"
+    );
+}
+
 // ============================================================================
 // F052: Date/time arithmetic
 // ============================================================================
@@ -1074,6 +1125,43 @@ fn f052_extract_quarter() {
     );
 }
 
+// ============================================================================
+// F053: OVERLAPS predicate
+// ============================================================================
+
+/// F053: OVERLAPS between two periods of dates
+#[test]
+fn f053_overlaps_dates() {
+    assert_feature_supported!(
+        "SELECT (DATE '2024-01-01', DATE '2024-06-30') \
+         OVERLAPS (DATE '2024-04-01', DATE '2024-12-31')",
+        "F053",
+        "OVERLAPS predicate"
+    );
+}
+
+/// F053: OVERLAPS with a period given by its start and its length
+#[test]
+fn f053_overlaps_interval_length() {
+    assert_feature_supported!(
+        "SELECT * FROM datetime_types \
+         WHERE (timestamp_col, INTERVAL '1' HOUR) \
+         OVERLAPS (TIMESTAMP '2024-01-01 00:00:00', TIMESTAMP '2024-01-02 00:00:00')",
+        "F053",
+        "OVERLAPS with an interval end"
+    );
+}
+
+/// F053: OVERLAPS requires periods of two values
+#[test]
+fn f053_overlaps_requires_pairs() {
+    assert_plan_error!(
+        "SELECT (DATE '2024-01-01', DATE '2024-02-01', DATE '2024-03-01') \
+         OVERLAPS (DATE '2024-04-01', DATE '2024-12-31')",
+        "OVERLAPS requires tuple arguments with exactly two elements"
+    );
+}
+
 // ============================================================================
 // F411: Time zone specification
 // ============================================================================
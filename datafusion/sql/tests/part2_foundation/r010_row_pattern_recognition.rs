@@ -62,7 +62,7 @@
 //! All tests in this module are expected to FAIL as DataFusion does not currently
 //! implement MATCH_RECOGNIZE. These tests document the conformance gap.
 
-use crate::assert_feature_supported;
+use crate::{assert_feature_supported, assert_plan_error};
 
 // ============================================================================
 // R010: Basic MATCH_RECOGNIZE structure
@@ -904,3 +904,34 @@ fn r010_match_with_join() {
         "MATCH_RECOGNIZE with JOIN"
     );
 }
+
+/// R010: Pattern variable with the name of the input table
+#[test]
+fn r010_pattern_variable_named_like_table() {
+    assert_feature_supported!(
+        "SELECT * FROM t AS a \
+         MATCH_RECOGNIZE ( \
+             ORDER BY id \
+             MEASURES LAST(A.value) AS last_value \
+             PATTERN (A+) \
+             DEFINE A AS a.value > PREV(a.value) \
+         )",
+        "R010",
+        "pattern variable named like the input table"
+    );
+}
+
+/// R010: Pattern variables only qualify the columns of the input
+#[test]
+fn r010_pattern_variable_unknown_column() {
+    assert_plan_error!(
+        "SELECT * FROM t \
+         MATCH_RECOGNIZE ( \
+             ORDER BY id \
+             MEASURES A.missing AS missing_value \
+             PATTERN (A+) \
+             DEFINE A AS value > 0 \
+         )",
+        "No field named missing"
+    );
+}
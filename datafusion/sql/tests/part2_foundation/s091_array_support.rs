@@ -43,7 +43,7 @@
 //!
 //! Note: Tests that fail indicate conformance gaps in DataFusion's array support.
 
-use crate::assert_feature_supported;
+use crate::{assert_feature_supported, assert_plan_error};
 
 // ============================================================================
 // S091-01: Arrays of built-in data types
@@ -429,6 +429,26 @@ fn s091_03_nested_array_constructor() {
     );
 }
 
+/// S091-03: CAST of a nested array constructor to a multi-dimensional array type
+#[test]
+fn s091_03_cast_multidimensional_array() {
+    assert_feature_supported!(
+        "SELECT CAST(ARRAY[ARRAY[1, 2], ARRAY[3, 4]] AS BIGINT[][])",
+        "S091-03",
+        "CAST to multi-dimensional array"
+    );
+}
+
+/// S091-03: CARDINALITY of a multi-dimensional array
+#[test]
+fn s091_03_cardinality_multidimensional_array() {
+    assert_feature_supported!(
+        "SELECT CARDINALITY(ARRAY[ARRAY[1, 2], ARRAY[3, 4]][1])",
+        "S091-03",
+        "CARDINALITY of a nested array element"
+    );
+}
+
 /// S091-03: Array with CASE expression
 #[test]
 fn s091_03_array_with_case() {
@@ -642,6 +662,35 @@ fn s098_array_agg_filter() {
     );
 }
 
+/// S098: ARRAY_AGG with DISTINCT, ORDER BY and FILTER
+#[test]
+fn s098_array_agg_distinct_order_by_filter() {
+    assert_feature_supported!(
+        "SELECT ARRAY_AGG(DISTINCT a ORDER BY a) FILTER (WHERE b > 10) FROM t",
+        "S098",
+        "ARRAY_AGG with DISTINCT, ORDER BY and FILTER"
+    );
+}
+
+/// S098: ARRAY_AGG with DISTINCT, descending ORDER BY and FILTER per group
+#[test]
+fn s098_array_agg_distinct_order_by_filter_group_by() {
+    assert_feature_supported!(
+        "SELECT c, ARRAY_AGG(DISTINCT a ORDER BY a DESC NULLS LAST) FILTER (WHERE a IS NOT NULL) FROM t GROUP BY c",
+        "S098",
+        "ARRAY_AGG with DISTINCT, ORDER BY and FILTER per group"
+    );
+}
+
+/// S098: With DISTINCT, ARRAY_AGG can only be ordered by its argument
+#[test]
+fn s098_array_agg_distinct_order_by_other_column() {
+    assert_plan_error!(
+        "SELECT ARRAY_AGG(DISTINCT a ORDER BY b) FILTER (WHERE b > 10) FROM t",
+        "In an aggregate with DISTINCT, ORDER BY expressions must appear in argument list"
+    );
+}
+
 /// S098: ARRAY_AGG with expression
 #[test]
 fn s098_array_agg_expression() {
@@ -786,6 +835,33 @@ fn s301_unnest_with_ordinality() {
     );
 }
 
+/// S301: UNNEST with a 0-based offset column (BigQuery extension)
+#[test]
+fn s301_unnest_with_offset() {
+    assert_feature_supported!(
+        "SELECT * FROM UNNEST(ARRAY[1, 2, 3]) AS t WITH OFFSET AS pos",
+        "S301",
+        "UNNEST with OFFSET"
+    );
+
+    let plan = crate::logical_plan(
+        "SELECT * FROM UNNEST(ARRAY[1, 2, 3]) WITH OFFSET",
+    )
+    .unwrap();
+    let names = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names.last().map(String::as_str), Some("offset"));
+    assert!(
+        plan.display_indent().to_string().contains("ordinality - Int64(1) AS offset"),
+        "{}",
+        plan.display_indent()
+    );
+}
+
 /// S301: UNNEST nested arrays
 #[test]
 fn s301_unnest_nested() {
@@ -796,6 +872,29 @@ fn s301_unnest_nested() {
     );
 }
 
+/// S301: UNNEST nested arrays down a given depth
+#[test]
+fn s301_unnest_max_depth() {
+    assert_feature_supported!(
+        "SELECT UNNEST(ARRAY[ARRAY[1, 2], ARRAY[3, 4]], max_depth => 2)",
+        "S301",
+        "UNNEST with max_depth"
+    );
+}
+
+/// S301: UNNEST cannot go deeper than the nesting of its argument
+#[test]
+fn s301_unnest_max_depth_exceeds_nesting() {
+    assert_plan_error!(
+        "SELECT UNNEST(int_array, max_depth => 2) FROM array_types",
+        "unnest() max_depth 2 exceeds the 1 levels of nesting"
+    );
+    assert_plan_error!(
+        "SELECT UNNEST(int_array, max_depth => 0) FROM array_types",
+        "unnest() max_depth must be a positive integer"
+    );
+}
+
 /// S301: UNNEST in WHERE clause
 #[test]
 fn s301_unnest_where() {
@@ -958,6 +1057,26 @@ fn array_slice_to_end() {
     );
 }
 
+/// Array slice with negative bounds counting from the end
+#[test]
+fn array_slice_negative_bounds() {
+    assert_feature_supported!(
+        "SELECT int_array[-3:-1] FROM array_types",
+        "S091",
+        "Array slice with negative bounds"
+    );
+}
+
+/// Array slice with a stride
+#[test]
+fn array_slice_with_stride() {
+    assert_feature_supported!(
+        "SELECT int_array[1:5:2] FROM array_types",
+        "S091",
+        "Array slice with stride"
+    );
+}
+
 // ============================================================================
 // Advanced array operations
 // ============================================================================
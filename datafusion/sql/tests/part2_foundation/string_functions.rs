@@ -45,7 +45,7 @@
 //! Each test uses `assert_feature_supported!` macro which fails if the
 //! feature is not implemented - thus identifying conformance gaps.
 
-use crate::assert_feature_supported;
+use crate::{assert_feature_supported, assert_plan_error};
 
 // ============================================================================
 // CHARACTER_LENGTH / CHAR_LENGTH / LENGTH
@@ -1368,6 +1368,28 @@ fn translate_null_string() {
     );
 }
 
+// ============================================================================
+// CONVERT ... USING
+// Convert character strings to a character set
+// ============================================================================
+
+#[test]
+fn convert_using_utf8() {
+    assert_feature_supported!(
+        "SELECT CONVERT(first_name USING utf8) FROM person",
+        "STRING_FUNC",
+        "CONVERT USING UTF8"
+    );
+}
+
+#[test]
+fn convert_using_unknown_character_set() {
+    assert_plan_error!(
+        "SELECT CONVERT(first_name USING klingon) FROM person",
+        "Unknown character set klingon"
+    );
+}
+
 #[test]
 fn translate_column() {
     assert_feature_supported!(
@@ -1536,6 +1558,41 @@ fn listagg_with_group_by() {
     );
 }
 
+#[test]
+fn listagg_on_overflow_error() {
+    assert_feature_supported!(
+        "SELECT LISTAGG(first_name, ', ' ON OVERFLOW ERROR) WITHIN GROUP (ORDER BY first_name) FROM person",
+        "STRING_FUNC",
+        "LISTAGG ON OVERFLOW ERROR"
+    );
+}
+
+#[test]
+fn listagg_on_overflow_truncate() {
+    assert_feature_supported!(
+        "SELECT LISTAGG(first_name, ', ' ON OVERFLOW TRUNCATE '~' WITH COUNT) WITHIN GROUP (ORDER BY first_name) FROM person",
+        "STRING_FUNC",
+        "LISTAGG ON OVERFLOW TRUNCATE WITH COUNT"
+    );
+}
+
+#[test]
+fn listagg_on_overflow_truncate_default_filler() {
+    assert_feature_supported!(
+        "SELECT LISTAGG(first_name, ', ' ON OVERFLOW TRUNCATE WITHOUT COUNT) FROM person",
+        "STRING_FUNC",
+        "LISTAGG ON OVERFLOW TRUNCATE WITHOUT COUNT"
+    );
+}
+
+#[test]
+fn on_overflow_rejected_for_other_aggregates() {
+    assert_plan_error!(
+        "SELECT STRING_AGG(first_name, ', ' ON OVERFLOW TRUNCATE) FROM person",
+        "ON OVERFLOW is not permitted for string_agg"
+    );
+}
+
 // ============================================================================
 // Combined/Complex String Operations
 // Test realistic scenarios with multiple string functions
@@ -41,7 +41,7 @@
 //! All tests in this module are expected to FAIL as DataFusion does not currently
 //! implement the DISTINCT predicate. These tests document the conformance gap.
 
-use crate::assert_feature_supported;
+use crate::{assert_feature_supported, assert_plan_error};
 
 // ============================================================================
 // T151: Basic DISTINCT predicate
@@ -363,6 +363,59 @@ fn t151_coalesce_alternative() {
     );
 }
 
+// ============================================================================
+// T151: Row values
+// ============================================================================
+
+/// T151: IS DISTINCT FROM with row value constructors
+#[test]
+fn t151_is_distinct_from_row_values() {
+    assert_feature_supported!(
+        "SELECT * FROM t WHERE (a, b) IS DISTINCT FROM (1, NULL)",
+        "T151",
+        "IS DISTINCT FROM with row values"
+    );
+}
+
+/// T151: IS NOT DISTINCT FROM with ROW constructors
+#[test]
+fn t151_is_not_distinct_from_row_constructors() {
+    assert_feature_supported!(
+        "SELECT * FROM t WHERE ROW(a, b) IS NOT DISTINCT FROM ROW(b, a)",
+        "T151",
+        "IS NOT DISTINCT FROM with ROW constructors"
+    );
+}
+
+/// T151: IS DISTINCT FROM with nested row values
+#[test]
+fn t151_is_distinct_from_nested_row_values() {
+    assert_feature_supported!(
+        "SELECT * FROM t WHERE (a, (b, c)) IS DISTINCT FROM (1, (2, NULL))",
+        "T151",
+        "IS DISTINCT FROM with nested row values"
+    );
+}
+
+/// T151: IS DISTINCT FROM between a struct column and a row value
+#[test]
+fn t151_is_distinct_from_struct_column() {
+    assert_feature_supported!(
+        "SELECT * FROM struct_types WHERE struct_col IS DISTINCT FROM ROW(1, NULL)",
+        "T151",
+        "IS DISTINCT FROM with struct values"
+    );
+}
+
+/// T151: Row values must have the same number of fields
+#[test]
+fn t151_row_values_field_count_mismatch() {
+    assert_plan_error!(
+        "SELECT * FROM t WHERE (a, b) IS DISTINCT FROM (1, 2, 3)",
+        "Row values compared by IS [NOT] DISTINCT FROM must have the same number of fields, got 2 and 3"
+    );
+}
+
 // ============================================================================
 // T151: Edge cases
 // ============================================================================
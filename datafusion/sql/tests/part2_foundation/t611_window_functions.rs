@@ -54,12 +54,13 @@
 //! | T612-11 | Named window definitions (WINDOW clause) | Supported |
 //! | T612-12 | Aggregate functions as window functions | Supported |
 //! | T612-13 | FILTER clause with window functions | Supported |
-//! | T612-14 | RESPECT NULLS / IGNORE NULLS | Partial |
+//! | T612-14 | RESPECT NULLS / IGNORE NULLS | Supported |
 //! | T612-15 | EXCLUDE clause | Not Implemented |
+//! | T612-16 | Inverse distribution functions (PERCENTILE_CONT, PERCENTILE_DISC) | Supported |
 //!
 //! Window functions are not Core SQL features but are widely used for analytical queries.
 
-use crate::assert_feature_supported;
+use crate::{assert_feature_supported, assert_plan_error};
 
 // ============================================================================
 // T611: Elementary OLAP operations
@@ -1049,6 +1050,26 @@ fn t612_11_reuse_named_window() {
     );
 }
 
+/// T612-11: Named window inheriting another named window
+#[test]
+fn t612_11_chained_named_windows() {
+    assert_feature_supported!(
+        "SELECT SUM(a) OVER w2 FROM t WINDOW w1 AS (PARTITION BY c), w2 AS (w1 ORDER BY a)",
+        "T612-11",
+        "Named window inheriting another named window"
+    );
+}
+
+/// T612-11: Window specification refining a named window
+#[test]
+fn t612_11_over_refined_named_window() {
+    assert_feature_supported!(
+        "SELECT SUM(a) OVER (w ORDER BY a ROWS UNBOUNDED PRECEDING) FROM t WINDOW w AS (PARTITION BY c)",
+        "T612-11",
+        "Window specification refining a named window"
+    );
+}
+
 // ----------------------------------------------------------------------------
 // T612-12: Aggregate functions as window functions
 // ----------------------------------------------------------------------------
@@ -1231,6 +1252,25 @@ fn t612_14_first_value_respect_nulls() {
     );
 }
 
+/// T612-14: LAG() with the null treatment after its arguments
+#[test]
+fn t612_14_lag_ignore_nulls_after_arguments() {
+    assert_feature_supported!(
+        "SELECT LAG(a, 1) IGNORE NULLS OVER (ORDER BY a) FROM t",
+        "T612-14",
+        "LAG() IGNORE NULLS after the arguments"
+    );
+}
+
+/// T612-14: IGNORE NULLS is rejected for functions that do not support it
+#[test]
+fn t612_14_row_number_ignore_nulls_rejected() {
+    assert_plan_error!(
+        "SELECT ROW_NUMBER() IGNORE NULLS OVER (ORDER BY a) FROM t",
+        "[IGNORE | RESPECT] NULLS are not permitted for row_number"
+    );
+}
+
 // ----------------------------------------------------------------------------
 // T612-15: EXCLUDE clause
 // ----------------------------------------------------------------------------
@@ -1275,6 +1315,58 @@ fn t612_15_exclude_no_others() {
     );
 }
 
+// ----------------------------------------------------------------------------
+// T612-16: Inverse distribution functions
+// ----------------------------------------------------------------------------
+
+/// T612-16: PERCENTILE_CONT() WITHIN GROUP
+#[test]
+fn t612_16_percentile_cont() {
+    assert_feature_supported!(
+        "SELECT PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY a) FROM t",
+        "T612-16",
+        "PERCENTILE_CONT() WITHIN GROUP"
+    );
+}
+
+/// T612-16: PERCENTILE_DISC() WITHIN GROUP with a descending ordering
+#[test]
+fn t612_16_percentile_disc_descending() {
+    assert_feature_supported!(
+        "SELECT PERCENTILE_DISC(0.25) WITHIN GROUP (ORDER BY a DESC) FROM t",
+        "T612-16",
+        "PERCENTILE_DISC() WITHIN GROUP DESC"
+    );
+}
+
+/// T612-16: PERCENTILE_DISC() of a non-numeric value per group
+#[test]
+fn t612_16_percentile_disc_grouped() {
+    assert_feature_supported!(
+        "SELECT state, PERCENTILE_DISC(0.5) WITHIN GROUP (ORDER BY first_name) FROM person GROUP BY state",
+        "T612-16",
+        "PERCENTILE_DISC() WITHIN GROUP per group"
+    );
+}
+
+/// T612-16: The percentile must be between 0 and 1
+#[test]
+fn t612_16_percentile_out_of_range() {
+    assert_plan_error!(
+        "SELECT PERCENTILE_CONT(1.5) WITHIN GROUP (ORDER BY a) FROM t",
+        "The percentile of percentile_cont must be between 0 and 1, got 1.5"
+    );
+}
+
+/// T612-16: The percentile must be a constant
+#[test]
+fn t612_16_percentile_not_constant() {
+    assert_plan_error!(
+        "SELECT PERCENTILE_DISC(a) WITHIN GROUP (ORDER BY a) FROM t",
+        "The percentile of percentile_disc must be a constant"
+    );
+}
+
 // ============================================================================
 // Complex scenarios and real-world use cases
 // ============================================================================
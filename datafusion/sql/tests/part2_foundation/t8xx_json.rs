@@ -45,7 +45,7 @@
 //! These tests document the conformance gaps and serve as a roadmap for
 //! future JSON support implementation.
 
-use crate::{assert_plans, assert_feature_supported};
+use crate::{assert_feature_supported, assert_plan_error, assert_plans};
 
 // ============================================================================
 // T803: String-based JSON storage
@@ -137,6 +137,36 @@ fn t811_json_object_absent_on_null() {
     );
 }
 
+/// T811: JSON_OBJECT with WITH UNIQUE KEYS and RETURNING
+#[test]
+fn t811_json_object_unique_keys_returning() {
+    assert_feature_supported!(
+        "SELECT JSON_OBJECT('key': 1 WITH UNIQUE KEYS RETURNING VARCHAR)",
+        "T811",
+        "JSON_OBJECT WITH UNIQUE KEYS and RETURNING clauses"
+    );
+    assert_feature_supported!(
+        "SELECT JSON_OBJECT('key': 1 WITHOUT UNIQUE KEYS)",
+        "T811",
+        "JSON_OBJECT WITHOUT UNIQUE KEYS clause"
+    );
+}
+
+/// T811: The clauses of JSON_OBJECT are passed to the function as its last
+/// argument
+#[test]
+fn t811_json_object_clauses_passed_to_function() {
+    let plan = crate::logical_plan(
+        "SELECT JSON_OBJECT('key': NULL ABSENT ON NULL WITH UNIQUE KEYS)",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(
+        plan.contains("null_on_null:false") && plan.contains("unique_keys:true"),
+        "{plan}"
+    );
+}
+
 // ============================================================================
 // T812: JSON_OBJECTAGG aggregate function
 // ============================================================================
@@ -225,6 +255,16 @@ fn t813_json_arrayagg_absent_on_null() {
     );
 }
 
+/// T813: JSON_ARRAYAGG with RETURNING
+#[test]
+fn t813_json_arrayagg_returning() {
+    assert_feature_supported!(
+        "SELECT JSON_ARRAYAGG(first_name ABSENT ON NULL RETURNING VARCHAR) FROM person",
+        "T813",
+        "JSON_ARRAYAGG RETURNING"
+    );
+}
+
 // ============================================================================
 // T814: JSON_ARRAY constructor function
 // ============================================================================
@@ -435,6 +475,24 @@ fn t823_json_value_returning() {
         "T823",
         "JSON_VALUE with RETURNING INT"
     );
+
+    // The function returns the declared type itself
+    let plan = crate::logical_plan(
+        "SELECT JSON_VALUE(data, '$.age' RETURNING INT) FROM json_data",
+    )
+    .unwrap();
+    assert_eq!(plan.schema().field(0).data_type().to_string(), "Int32");
+    let plan = plan.display_indent().to_string();
+    assert!(!plan.contains("CAST"), "{plan}");
+}
+
+/// T823: JSON_VALUE returns an SQL scalar
+#[test]
+fn t823_json_value_returning_nested_type() {
+    assert_plan_error!(
+        "SELECT JSON_VALUE(data, '$.tags' RETURNING INTEGER ARRAY) FROM json_data",
+        "JSON_VALUE cannot return values of type"
+    );
 }
 
 /// T823: JSON_VALUE with DEFAULT ON EMPTY
@@ -527,6 +585,23 @@ fn t824_json_query_with_wrapper() {
     );
 }
 
+/// T824: The wrapper behavior of JSON_QUERY is passed to the function
+#[test]
+fn t824_json_query_conditional_wrapper() {
+    assert_feature_supported!(
+        "SELECT JSON_QUERY(data, '$.items' WITH CONDITIONAL ARRAY WRAPPER) FROM json_data",
+        "T824",
+        "JSON_QUERY WITH CONDITIONAL ARRAY WRAPPER"
+    );
+
+    let plan = crate::logical_plan(
+        "SELECT JSON_QUERY(data, '$.items' WITH CONDITIONAL ARRAY WRAPPER) FROM json_data",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(plan.contains("wrapper:conditional"), "{plan}");
+}
+
 /// T824: JSON_QUERY with EMPTY ARRAY ON EMPTY
 #[test]
 fn t824_json_query_empty_array_on_empty() {
@@ -581,6 +656,30 @@ fn t825_default_expression_on_empty() {
     );
 }
 
+/// T825: The ON EMPTY and ON ERROR behaviors are passed to the function, with
+/// the DEFAULT value cast to the RETURNING type
+#[test]
+fn t825_behaviors_passed_to_function() {
+    let plan = crate::logical_plan(
+        "SELECT JSON_VALUE(data, '$.count' DEFAULT '0' ON EMPTY ERROR ON ERROR RETURNING INT) FROM json_data",
+    )
+    .unwrap();
+    let plan = plan.display_indent().to_string();
+    assert!(
+        plan.contains("on_empty:default,on_empty_default:0,on_error:error"),
+        "{plan}"
+    );
+}
+
+/// T825: A DEFAULT value must be a value of the RETURNING type
+#[test]
+fn t825_default_not_of_returning_type() {
+    assert_plan_error!(
+        "SELECT JSON_VALUE(data, '$.count' DEFAULT 'none' ON EMPTY RETURNING INT) FROM json_data",
+        "DEFAULT value of a SQL/JSON function cannot be cast to Int32"
+    );
+}
+
 // ============================================================================
 // T827: JSON_TABLE function - transform JSON to relational table
 // ============================================================================
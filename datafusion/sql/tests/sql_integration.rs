@@ -28,16 +28,27 @@ use std::vec;
 use arrow::datatypes::{TimeUnit::Nanosecond, *};
 use common::MockContextProvider;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
-use datafusion_common::{assert_contains, DataFusionError, Result};
+use datafusion_common::{assert_contains, assert_not_contains, DataFusionError, Result};
 use datafusion_expr::{
-    col, logical_plan::LogicalPlan, test::function_stub::sum_udaf, ColumnarValue,
+    col, logical_plan::LogicalPlan,
+    planner::{
+        CHARACTER_LENGTH_METADATA_KEY, CHARACTER_SET_METADATA_KEY,
+        COLLATION_METADATA_KEY, INTERVAL_FIELDS_METADATA_KEY,
+        INTERVAL_PRECISION_METADATA_KEY, NATIONAL_CHARACTER_SET, PERIOD_END_METADATA_KEY,
+        PERIOD_START_METADATA_KEY, SYSTEM_TIME_METADATA_KEY, SYSTEM_TIME_ROW_END,
+        SYSTEM_TIME_ROW_START,
+    },
+    test::function_stub::sum_udaf, ColumnarValue,
     CreateIndex, CreateMemoryTable, DdlStatement, ScalarFunctionArgs, ScalarUDF,
     ScalarUDFImpl, Signature, TableScanRowLockMode, TableScanRowLockWaitPolicy,
-    Volatility,
+    UserMappingTarget, Volatility,
 };
 use datafusion_sql::{
-    parser::DFParser,
-    planner::{NullOrdering, ParserOptions, SqlToRel},
+    parser::{DFParser, Statement as DFStatement},
+    plan_cache::PlanCache,
+    planner::{
+        FunctionNameConflict, IdentCaseFolding, NullOrdering, ParserOptions, SqlToRel,
+    },
 };
 
 use crate::common::MockSessionState;
@@ -53,7 +64,10 @@ use datafusion_functions_aggregate::{
 // or datafusion_functions_window are disabled as those crates were removed.
 use insta::{allow_duplicates, assert_snapshot};
 use rstest::rstest;
-use sqlparser::dialect::{Dialect, PostgreSqlDialect, MySqlDialect};
+use sqlparser::dialect::{
+    Dialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect,
+    SnowflakeDialect,
+};
 
 mod cases;
 mod common;
@@ -231,6 +245,41 @@ fn within_group_rejected_for_non_ordered_set_udaf() {
     );
 }
 
+#[test]
+fn hypothetical_set_aggregates() {
+    let sql = "SELECT state, rank(30) WITHIN GROUP (ORDER BY age), \
+               cume_dist(30, 'x') WITHIN GROUP (ORDER BY age DESC, last_name) \
+               FROM person GROUP BY state";
+    let plan = logical_plan(sql).unwrap().to_string();
+    assert_contains!(
+        &plan,
+        "AS rank(Int32(30)) WITHIN GROUP [person.age ASC NULLS LAST]"
+    );
+    assert_contains!(
+        &plan,
+        "AS cume_dist(Int32(30), Utf8(\"x\")) WITHIN GROUP [person.age DESC NULLS FIRST, person.last_name ASC NULLS LAST]"
+    );
+    assert_contains!(&plan, "count(Int64(1)) FILTER (WHERE");
+
+    let err =
+        logical_plan("SELECT rank(30, 'x') WITHIN GROUP (ORDER BY age) FROM person")
+            .expect_err("more arguments than ordering expressions");
+    assert_contains!(
+        err.to_string(),
+        "Hypothetical-set aggregate rank requires as many arguments as WITHIN GROUP \
+         ordering expressions, got 2 arguments and 1 ordering expressions"
+    );
+
+    let err = logical_plan(
+        "SELECT percent_rank(DISTINCT 30) WITHIN GROUP (ORDER BY age) FROM person",
+    )
+    .expect_err("DISTINCT hypothetical-set aggregate");
+    assert_contains!(
+        err.to_string(),
+        "DISTINCT is not supported for hypothetical-set aggregate percent_rank"
+    );
+}
+
 #[test]
 fn parse_ident_normalization_5() {
     // With PG dialect, unquoted identifiers are always lowercased by the parser,
@@ -279,6 +328,71 @@ fn parse_ident_normalization_7() {
     );
 }
 
+#[test]
+fn parse_ident_case_folding_upper() {
+    let sql = r#"SELECT "age" AS total FROM "person""#;
+    let options =
+        ParserOptions::default().with_ident_case_folding(IdentCaseFolding::Upper);
+    let plan =
+        logical_plan_with_dialect_and_options(sql, &MsSqlDialect {}, options).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+        Projection: person.age AS TOTAL
+          TableScan: person
+        "#
+    );
+}
+
+#[test]
+fn parse_ident_case_folding_preserve() {
+    let sql = "SELECT Id FROM UPPERCASE_test";
+    let options =
+        ParserOptions::default().with_ident_case_folding(IdentCaseFolding::Preserve);
+    let plan =
+        logical_plan_with_dialect_and_options(sql, &MsSqlDialect {}, options).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+        Projection: UPPERCASE_test.Id
+          TableScan: UPPERCASE_test
+        "#
+    );
+}
+
+#[test]
+fn parse_ident_case_insensitive_resolution() {
+    let options = ParserOptions::default()
+        .with_ident_case_folding(IdentCaseFolding::CaseInsensitive);
+
+    let sql = "SELECT ID, t.LOWER FROM uppercase_test t WHERE id > 1";
+    let plan =
+        logical_plan_with_dialect_and_options(sql, &MsSqlDialect {}, options).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+        Projection: t.Id, t.lower
+          Filter: t.Id > Int32(1)
+            SubqueryAlias: t
+              TableScan: UPPERCASE_test
+        "#
+    );
+
+    // Quoted identifiers still match exactly
+    let sql = r#"SELECT "ID" FROM UPPERCASE_test"#;
+    let err = logical_plan_with_dialect_and_options(sql, &MsSqlDialect {}, options)
+        .unwrap_err();
+    assert_contains!(err.strip_backtrace(), "No field named \"ID\"");
+
+    let sql = r#"SELECT id FROM (SELECT age AS "ID", salary AS "Id" FROM person) t"#;
+    let err = logical_plan_with_dialect_and_options(sql, &MsSqlDialect {}, options)
+        .unwrap_err();
+    assert_snapshot!(
+        err.strip_backtrace(),
+        @r#"Error during planning: Identifier 'id' matches several names that differ only in case: "ID", "Id""#
+    );
+}
+
 #[test]
 fn select_no_relation() {
     let plan = logical_plan("SELECT 1").unwrap();
@@ -291,6 +405,30 @@ fn select_no_relation() {
     );
 }
 
+#[test]
+fn select_hex_and_binary_string_literals() {
+    let plan = logical_plan("SELECT X'DEAD BEEF' AS x, B'1010' AS b").unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: Binary("222,173,190,239") AS x, Binary("10") AS b
+      EmptyRelation: rows=1
+    "#
+    );
+
+    let err = logical_plan("SELECT X'ABC'").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "HexStringLiteral 'ABC' has an odd number of hex digits"
+    );
+
+    let err = logical_plan("SELECT B'102'").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Invalid binary string literal B'102'"
+    );
+}
+
 #[test]
 fn test_real_f32() {
     let plan = logical_plan("SELECT CAST(1.1 AS REAL)").unwrap();
@@ -572,6 +710,195 @@ fn plan_explain_copy_to_format() {
     );
 }
 
+#[test]
+fn plan_copy_to_partition_layout() {
+    let sql = "COPY person TO 'people' STORED AS CSV PARTITIONED BY (state) \
+               OPTIONS ('partition.naming' 'values', 'partition.single_file' true)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    CopyTo: format=csv output_url=people partition_layout=(naming=values, single_file=true) options: ()
+      TableScan: person
+    "#
+    );
+
+    let sql = "COPY person TO 'people.csv' OPTIONS ('partition.max_file_size' 1048576)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    CopyTo: format=csv output_url=people.csv partition_layout=(naming=hive, max_file_size=1048576) options: ()
+      TableScan: person
+    "#
+    );
+
+    let err =
+        logical_plan("COPY person TO 'people.csv' OPTIONS ('partition.naming' 'values')")
+            .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "COPY TO option partition.naming requires PARTITIONED BY"
+    );
+    let err = logical_plan(
+        "COPY person TO 'people' STORED AS CSV PARTITIONED BY (state) \
+         OPTIONS ('partition.single_file' true, 'partition.max_file_size' 1024)",
+    )
+    .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "partition.single_file and partition.max_file_size cannot be used together"
+    );
+}
+
+#[test]
+fn plan_copy_from() {
+    let sql = "COPY person (ID, first_name) FROM 'people.csv'";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @"CopyFrom: table=person format=csv source=people.csv columns=[id, first_name] options: ()"
+    );
+
+    let plan = logical_plan("COPY person FROM STDIN STORED AS CSV").unwrap();
+    assert_snapshot!(
+        plan,
+        @"CopyFrom: table=person format=csv source=STDIN options: ()"
+    );
+    let plan =
+        logical_plan("COPY person FROM PROGRAM 'gunzip -c people.csv.gz' STORED AS CSV")
+            .unwrap();
+    assert_snapshot!(
+        plan,
+        @"CopyFrom: table=person format=csv source=PROGRAM 'gunzip -c people.csv.gz' options: ()"
+    );
+    let err = logical_plan("COPY person FROM STDIN").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Format not explicitly set for COPY FROM STDIN"
+    );
+
+    let err = logical_plan("COPY person (id, nickname) FROM 'people.csv'").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "No field named nickname");
+    let err = logical_plan("COPY person (id, id) FROM 'people.csv'").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "duplicate unqualified field name id");
+}
+
+#[test]
+fn plan_copy_from_skipping_errors() {
+    let sql = "COPY person FROM 'people.csv' WITH ('errors.on_error' 'skip', \
+               'errors.max_errors' 10, 'errors.reject_location' 'rejected.csv')";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @"CopyFrom: table=person format=csv source=people.csv errors=(on_error=skip, max_errors=10, reject_location=rejected.csv) options: ()"
+    );
+    let output_columns: Vec<_> = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect();
+    assert_eq!(output_columns, vec!["count", "rejected"]);
+
+    let err = logical_plan("COPY person FROM 'people.csv' WITH ('errors.max_errors' 10)")
+        .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "COPY FROM option errors.max_errors requires errors.on_error skip"
+    );
+}
+
+#[test]
+fn plan_copy_from_column_exprs() {
+    let sql = "COPY person (id, age) FROM 'people.csv' \
+               WITH ('column.age.expression' 'CAST(age AS INT) + 1')";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @"CopyFrom: table=person format=csv source=people.csv columns=[id, age] column_exprs=[age=CAST(age AS Int32) + Int32(1)] options: ()"
+    );
+
+    let err = logical_plan(
+        "COPY person (id, age) FROM 'people.csv' WITH ('column.birth_date.format' '%Y')",
+    )
+    .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Column birth_date of COPY FROM option column.birth_date.format is not copied"
+    );
+    let err =
+        logical_plan("COPY person FROM 'people.csv' WITH ('column.state.format' '%Y')")
+            .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "COPY FROM format is not supported for column state of type Utf8"
+    );
+    let err = logical_plan(
+        "COPY person FROM 'people.csv' WITH ('column.birth_date.format' '%d/%m/%Y')",
+    )
+    .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "COPY FROM format of column birth_date requires the to_timestamp function"
+    );
+}
+
+#[test]
+fn copy_from_inserts_into_table() {
+    use datafusion_expr::logical_plan::builder::LogicalTableSource;
+    use datafusion_expr::planner::ContextProvider;
+
+    let LogicalPlan::CopyFrom(copy) =
+        logical_plan("COPY j1 (j1_string) FROM 'strings.csv'").unwrap()
+    else {
+        panic!("Expected CopyFrom");
+    };
+    let context = MockContextProvider {
+        state: MockSessionState::default(),
+    };
+    let target = context.get_table_source("j1".into()).unwrap();
+    let source = Arc::new(LogicalTableSource::new(Arc::new(Schema::new(vec![
+        Field::new("column_1", DataType::LargeUtf8, true),
+    ]))));
+    let plan = copy.insert_plan(Arc::clone(&target), source).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Dml: op=[Insert Into] table=[j1]
+      Projection: CAST(NULL AS Int32) AS j1_id, CAST(strings.csv.column_1 AS Utf8) AS j1_string
+        TableScan: strings.csv
+    "#
+    );
+
+    let source = Arc::new(LogicalTableSource::new(Arc::new(Schema::empty())));
+    let err = copy.insert_plan(Arc::clone(&target), source).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: COPY FROM source strings.csv has 0 columns, but 1 columns of j1 are copied"
+    );
+
+    let LogicalPlan::CopyFrom(copy) = logical_plan(
+        "COPY j1 FROM 'j1.csv' WITH ('column.j1_id.expression' 'CAST(j1_id AS INT) * 10')",
+    )
+    .unwrap() else {
+        panic!("Expected CopyFrom");
+    };
+    let source = Arc::new(LogicalTableSource::new(Arc::new(Schema::new(vec![
+        Field::new("column_1", DataType::Int64, true),
+        Field::new("column_2", DataType::Utf8, true),
+    ]))));
+    let plan = copy.insert_plan(target, source).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Dml: op=[Insert Into] table=[j1]
+      Projection: CAST(CAST(j1.csv.column_1 AS Utf8) AS Int32) * Int32(10) AS j1_id, j1.csv.column_2 AS j1_string
+        TableScan: j1.csv
+    "#
+    );
+}
+
 #[test]
 fn plan_insert() {
     let sql =
@@ -615,6 +942,27 @@ fn plan_insert_no_target_columns() {
     );
 }
 
+#[test]
+fn plan_insert_select_without_redundant_casts() {
+    // A source with the columns of the table is inserted as it is
+    let plan = logical_plan("INSERT INTO person SELECT * FROM person").unwrap();
+    let LogicalPlan::Dml(dml) = &plan else {
+        panic!("Expected Dml plan, got {plan:?}");
+    };
+    assert_not_contains!(dml.input.display_indent().to_string(), " AS ");
+
+    // Columns of the types of the table are only renamed
+    let sql = "INSERT INTO person (id, age) SELECT order_id, qty FROM orders";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "orders.order_id AS id, ");
+    assert_contains!(&plan, "orders.qty AS age, ");
+
+    let sql = "CREATE TABLE t (a INT, b DOUBLE) AS SELECT qty AS a, price FROM orders";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "Projection: a, orders.price AS b");
+    assert_not_contains!(&plan, "CAST(");
+}
+
 #[test]
 fn plan_create_table_interval_day_to_second() {
     let sql = "CREATE TABLE intervals (span INTERVAL DAY TO SECOND)";
@@ -830,6 +1178,30 @@ fn select_neg_filter() {
     );
 }
 
+#[test]
+fn select_distinct_predicate_row_values() {
+    let sql = "SELECT id FROM person \
+               WHERE (age, first_name) IS NOT DISTINCT FROM (id, NULL) \
+               OR ROW(age, (state, last_name)) IS DISTINCT FROM ROW(id, (NULL, first_name))";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+        Projection: person.id
+          Filter: person.age IS NOT DISTINCT FROM person.id AND person.first_name IS NOT DISTINCT FROM NULL OR person.age IS DISTINCT FROM person.id OR person.state IS DISTINCT FROM NULL OR person.last_name IS DISTINCT FROM person.first_name
+            TableScan: person
+        "#
+    );
+
+    let sql =
+        "SELECT id FROM person WHERE (age, first_name) IS DISTINCT FROM (id, age, state)";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_contains!(
+        err.to_string(),
+        "must have the same number of fields, got 2 and 3"
+    );
+}
+
 #[test]
 fn select_compound_filter() {
     let sql = "SELECT id, first_name, last_name \
@@ -1060,6 +1432,63 @@ fn table_with_column_alias_special_chars() {
     );
 }
 
+#[test]
+fn provider_reserved_word_must_be_quoted() {
+    let err = logical_plan("SELECT age AS period FROM person").unwrap_err();
+    assert_snapshot!(
+        err.strip_backtrace(),
+        @"Error during planning: 'period' is a reserved word and must be quoted to be used as an identifier"
+    );
+
+    let err = logical_plan("SELECT period FROM person").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "'period' is a reserved word");
+
+    let plan =
+        logical_plan(r#"SELECT "period" FROM (SELECT age AS "period" FROM person)"#)
+            .unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: period
+      Projection: person.age AS period
+        TableScan: person
+    "#
+    );
+}
+
+#[test]
+fn provider_unreserved_word_references_column() {
+    let sql = "SELECT session_user FROM (SELECT first_name AS session_user FROM person)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: session_user
+      Projection: person.first_name AS session_user
+        TableScan: person
+    "#
+    );
+}
+
+#[test]
+fn function_name_conflict_prefers_column() {
+    let sql = "SELECT current_user FROM (SELECT first_name AS current_user FROM person)";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Invalid function 'current_user'");
+
+    let options = ParserOptions::default()
+        .with_function_name_conflict(FunctionNameConflict::PreferColumn);
+    let plan = logical_plan_with_options(sql, options).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: current_user
+      Projection: person.first_name AS current_user
+        TableScan: person
+    "#
+    );
+}
+
 #[test]
 fn table_with_column_alias_reserved_keywords() {
     // Reserved keywords as aliases (must be quoted)
@@ -1720,12 +2149,31 @@ fn select_from_typed_string_values() {
         @r#"
     Projection: t.col1, t.col2
       SubqueryAlias: t
-        Projection: column1 AS col1, column2 AS col2
-          Values: (CAST(Utf8("2021-06-10 17:01:00Z") AS Timestamp(ns)), CAST(Utf8("2004-04-09") AS Date32))
+        Values: (CAST(Utf8("2021-06-10 17:01:00Z") AS Timestamp(ns)), CAST(Utf8("2004-04-09") AS Date32))
     "#
     );
 }
 
+#[test]
+fn select_from_values_with_column_aliases() {
+    // Fewer names than columns only rename the first columns
+    let plan =
+        logical_plan("SELECT * FROM (VALUES (1, 'a'), (2, 'b')) AS t(id)").unwrap();
+    assert_eq!(plan.schema().field_names(), ["t.id", "t.column2"]);
+
+    let err = logical_plan("SELECT * FROM (VALUES (1, 'a')) AS t(a, b, c)").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Source table contains 2 columns but 3 names given as column alias"
+    );
+
+    let err = logical_plan("SELECT * FROM (VALUES (1, 'a')) AS t(a, A)").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Column alias a is given more than once"
+    );
+}
+
 #[test]
 fn select_simple_aggregate_repeated_aggregate_with_repeated_aliases() {
     let sql = "SELECT MIN(age) AS a, MIN(age) AS a FROM person";
@@ -2241,14 +2689,39 @@ fn select_order_by_index_of_0() {
 }
 
 #[test]
-fn select_order_by_index_oob() {
-    let sql = "SELECT id FROM person ORDER BY 2";
-    let err = logical_plan(sql)
-        .expect_err("query should have failed")
-        .strip_backtrace();
-
+fn select_order_by_all() {
+    let sql = "SELECT id, age + 1 AS next_age FROM person ORDER BY ALL DESC";
+    let plan = logical_plan(sql).unwrap();
     assert_snapshot!(
-        err,
+        plan,
+        @r#"
+Sort: person.id DESC NULLS FIRST, next_age DESC NULLS FIRST
+  Projection: person.id, person.age + Int32(1) AS next_age
+    TableScan: person
+"#
+    );
+}
+
+#[test]
+fn union_order_by_all() {
+    let sql = "SELECT order_id, qty FROM orders UNION ALL SELECT order_id, qty FROM orders ORDER BY ALL";
+    let plan = logical_plan(sql).unwrap();
+    let plan_str = plan.display_indent().to_string();
+    let sort = plan_str.lines().next().unwrap();
+    assert_contains!(sort, "Sort: ");
+    assert_contains!(sort, "order_id ASC NULLS LAST, ");
+    assert_contains!(sort, "qty ASC NULLS LAST");
+}
+
+#[test]
+fn select_order_by_index_oob() {
+    let sql = "SELECT id FROM person ORDER BY 2";
+    let err = logical_plan(sql)
+        .expect_err("query should have failed")
+        .strip_backtrace();
+
+    assert_snapshot!(
+        err,
         @r#"
         Error during planning: Order by column out of bounds, specified: 2, max: 1
         "#
@@ -2362,6 +2835,20 @@ fn select_group_by_needs_projection() {
     );
 }
 
+#[test]
+fn select_group_by_all() {
+    let sql = "SELECT state, age + 1 AS b, 1, sum(id) + 1 FROM person GROUP BY ALL";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+        Projection: person.state, person.age + Int32(1) AS b, Int32(1), sum(person.id) + Int32(1)
+          Aggregate: groupBy=[[person.state, person.age + Int32(1)]], aggr=[[sum(person.id)]]
+            TableScan: person
+        "#
+    );
+}
+
 #[test]
 fn select_7480_1() {
     let sql = "SELECT c1, MIN(c12) FROM aggregate_test_100 GROUP BY c1, c13";
@@ -2580,6 +3067,46 @@ CreateExternalTable: Bare { table: "t" }
     );
 }
 
+#[test]
+fn create_external_table_inferred_schema() {
+    use datafusion_common::ToDFSchema;
+
+    let sql = "CREATE EXTERNAL TABLE t STORED AS PARQUET LOCATION 'foo.parquet' WITH ORDER (id)";
+    let LogicalPlan::Ddl(DdlStatement::CreateExternalTable(table)) =
+        logical_plan(sql).unwrap()
+    else {
+        panic!("Expected CreateExternalTable");
+    };
+    assert!(table.infer_schema);
+    assert!(table.schema.fields().is_empty());
+
+    let inferred = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ])
+    .to_dfschema_ref()
+    .unwrap();
+    let table = table.with_inferred_schema(Arc::clone(&inferred)).unwrap();
+    assert_eq!(table.schema, inferred);
+
+    let inferred = Schema::new(vec![Field::new("name", DataType::Utf8, true)])
+        .to_dfschema_ref()
+        .unwrap();
+    let err = table.with_inferred_schema(inferred).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Column id of the order of external table t is not in the schema inferred from foo.parquet"
+    );
+
+    let sql = "CREATE EXTERNAL TABLE t(c1 int) STORED AS CSV LOCATION 'foo.csv'";
+    let LogicalPlan::Ddl(DdlStatement::CreateExternalTable(table)) =
+        logical_plan(sql).unwrap()
+    else {
+        panic!("Expected CreateExternalTable");
+    };
+    assert!(!table.infer_schema);
+}
+
 #[test]
 fn equijoin_explicit_syntax() {
     let sql = "SELECT id, order_id \
@@ -2670,6 +3197,67 @@ Projection: person.id, orders.order_id
     );
 }
 
+#[test]
+fn left_semi_join() {
+    let sql = "SELECT id, first_name \
+            FROM person \
+            LEFT SEMI JOIN orders \
+            ON id = customer_id AND order_id < 100";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Projection: person.id, person.first_name
+  LeftSemi Join:  Filter: person.id = orders.customer_id AND orders.order_id < Int32(100)
+    TableScan: person
+    TableScan: orders
+"#
+    );
+
+    // Only the columns of the left side are visible above the join
+    let sql = "SELECT order_id FROM person LEFT SEMI JOIN orders ON id = customer_id";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(err.strip_backtrace(), "order_id");
+}
+
+#[test]
+fn left_anti_join() {
+    let sql = "SELECT id \
+            FROM person \
+            LEFT ANTI JOIN person AS person2 \
+            USING (id)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Projection: person.id
+  LeftAnti Join: Using person.id = person2.id
+    TableScan: person
+    SubqueryAlias: person2
+      TableScan: person
+"#
+    );
+}
+
+#[test]
+fn semi_and_anti_join_without_left() {
+    let sql =
+        "SELECT p.id FROM person AS p SEMI JOIN orders AS o ON p.id = o.customer_id";
+    let plan = logical_plan_with_dialect(sql, &GenericDialect {})
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan, "LeftSemi Join:  Filter: p.id = o.customer_id");
+
+    let sql =
+        "SELECT p.id FROM person AS p ANTI JOIN orders AS o ON p.id = o.customer_id";
+    let plan = logical_plan_with_dialect(sql, &GenericDialect {})
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan, "LeftAnti Join:  Filter: p.id = o.customer_id");
+}
+
 #[test]
 fn join_with_table_name() {
     let sql = "SELECT id, order_id \
@@ -2812,6 +3400,86 @@ Union
     );
 }
 
+#[test]
+fn union_all_chain() {
+    let sql = "SELECT order_id from orders \
+               UNION ALL SELECT customer_id FROM orders \
+               UNION ALL SELECT o_item_id FROM orders";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Union
+  Projection: orders.order_id
+    TableScan: orders
+  Projection: orders.customer_id
+    TableScan: orders
+  Projection: orders.o_item_id
+    TableScan: orders
+"#
+    );
+
+    let sql = "SELECT order_id from orders \
+               UNION ALL SELECT customer_id FROM orders \
+               UNION ALL SELECT order_id, qty FROM orders";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "UNION queries have different number of columns"
+    );
+}
+
+#[cfg(feature = "parallel_planning")]
+#[test]
+fn union_all_chain_parallel_planning() {
+    let plan_with = |sql: &str, parallel: bool| {
+        let state = MockSessionState::default();
+        let context = MockContextProvider { state };
+        let mut planner = SqlToRel::new(&context);
+        if parallel {
+            planner = planner.with_parallel_planning();
+        }
+        let statement = DFParser::parse_sql(sql).unwrap().pop_front().unwrap();
+        planner
+            .statement_to_plan(statement)
+            .map(|plan| plan.display_indent().to_string())
+            .map_err(|err| {
+                err.iter()
+                    .map(|err| err.strip_backtrace())
+                    .collect::<Vec<_>>()
+            })
+    };
+
+    let sql = (0..40)
+        .map(|i| format!("SELECT order_id + {i} FROM orders WHERE qty > {i}"))
+        .collect::<Vec<_>>()
+        .join(" UNION ALL ");
+    let plan = plan_with(&sql, true).unwrap();
+    assert_eq!(plan, plan_with(&sql, false).unwrap());
+    assert_eq!(plan.matches("TableScan: orders").count(), 40);
+
+    // Anonymous placeholders keep their numbering in statement order
+    let sql = (0..40)
+        .map(|_| "SELECT order_id FROM orders WHERE qty > ?")
+        .collect::<Vec<_>>()
+        .join(" UNION ALL ");
+    let plan = plan_with(&sql, true).unwrap();
+    assert_eq!(plan, plan_with(&sql, false).unwrap());
+    assert_contains!(&plan, "orders.qty > $1\n");
+    assert_contains!(&plan, "orders.qty > $40\n");
+
+    // The errors of all branches are reported, in statement order
+    let sql = (0..40)
+        .map(|i| format!("SELECT missing_{i} FROM orders"))
+        .collect::<Vec<_>>()
+        .join(" UNION ALL ");
+    let errors = plan_with(&sql, true).unwrap_err();
+    assert_eq!(errors, plan_with(&sql, false).unwrap_err());
+    assert_eq!(errors.len(), 40);
+    assert_contains!(&errors[0], "missing_0");
+    assert_contains!(&errors[39], "missing_39");
+}
+
 #[test]
 fn union_all_by_name_different_columns() {
     let sql =
@@ -3169,6 +3837,83 @@ Projection: orders.order_id, max(orders.qty) PARTITION BY [orders.order_id] ORDE
     );
 }
 
+#[test]
+fn over_named_window_inherits_window() {
+    let sql = "SELECT order_id, MAX(qty) OVER w2 from orders \
+               WINDOW w1 AS (PARTITION BY order_id), w2 AS (w1 ORDER BY qty)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Projection: orders.order_id, max(orders.qty) PARTITION BY [orders.order_id] ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+  WindowAggr: windowExpr=[[max(orders.qty) PARTITION BY [orders.order_id] ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]
+    TableScan: orders
+"#
+    );
+
+    let sql = "SELECT order_id, MAX(qty) OVER (w ORDER BY qty) from orders \
+               WINDOW w AS (PARTITION BY order_id)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Projection: orders.order_id, max(orders.qty) PARTITION BY [orders.order_id] ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+  WindowAggr: windowExpr=[[max(orders.qty) PARTITION BY [orders.order_id] ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]
+    TableScan: orders
+"#
+    );
+}
+
+#[test]
+fn over_null_treatment_not_supported() {
+    let err = logical_plan("SELECT sum(age) IGNORE NULLS OVER () FROM person")
+        .expect_err("IGNORE NULLS for sum");
+    assert_contains!(
+        err.to_string(),
+        "[IGNORE | RESPECT] NULLS are not permitted for sum"
+    );
+}
+
+#[test]
+fn over_named_window_inheritance_errors() {
+    let err = logical_plan(
+        "SELECT MAX(qty) OVER w2 from orders \
+         WINDOW w1 AS (PARTITION BY order_id), w2 AS (w1 PARTITION BY qty)",
+    )
+    .expect_err("PARTITION BY overridden");
+    assert_contains!(
+        err.to_string(),
+        "Cannot override PARTITION BY clause of window w1"
+    );
+
+    let err = logical_plan(
+        "SELECT MAX(qty) OVER (w ORDER BY order_id) from orders \
+         WINDOW w AS (ORDER BY qty)",
+    )
+    .expect_err("ORDER BY overridden");
+    assert_contains!(
+        err.to_string(),
+        "Cannot override ORDER BY clause of window w"
+    );
+
+    let err = logical_plan(
+        "SELECT MAX(qty) OVER w2 from orders \
+         WINDOW w1 AS (ORDER BY qty ROWS UNBOUNDED PRECEDING), w2 AS (w1)",
+    )
+    .expect_err("window with frame copied");
+    assert_contains!(
+        err.to_string(),
+        "Cannot copy window w1 because it has a frame clause"
+    );
+
+    let err = logical_plan(
+        "SELECT MAX(qty) OVER w1 from orders \
+         WINDOW w1 AS (w2 ORDER BY qty), w2 AS (PARTITION BY order_id)",
+    )
+    .expect_err("window referenced before its definition");
+    assert_contains!(err.to_string(), "The window w2 is not defined!");
+}
+
 /// psql result
 /// ```text
 ///                               QUERY PLAN
@@ -3358,160 +4103,2485 @@ fn select_groupby_orderby() {
 
     let plan = logical_plan(sql).unwrap();
     assert_snapshot!(
-        plan,
-        @r#"
-Sort: avg(person.age) + avg(person.age) ASC NULLS LAST
-  Projection: avg(person.age) + avg(person.age), date_trunc(Utf8("month"), person.birth_date) AS birth_date
-    Aggregate: groupBy=[[person.birth_date]], aggr=[[avg(person.age)]]
+        plan,
+        @r#"
+Sort: avg(person.age) + avg(person.age) ASC NULLS LAST
+  Projection: avg(person.age) + avg(person.age), date_trunc(Utf8("month"), person.birth_date) AS birth_date
+    Aggregate: groupBy=[[person.birth_date]], aggr=[[avg(person.age)]]
+      TableScan: person
+"#
+    );
+}
+
+#[test]
+fn select_groupby_orderby_aggregate_on_non_selected_column() {
+    let sql = "SELECT state FROM person GROUP BY state ORDER BY MIN(age)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Projection: person.state
+  Sort: min(person.age) ASC NULLS LAST
+    Projection: person.state, min(person.age)
+      Aggregate: groupBy=[[person.state]], aggr=[[min(person.age)]]
+        TableScan: person
+"#
+    );
+}
+
+#[test]
+fn select_groupby_orderby_multiple_aggregates_on_non_selected_columns() {
+    let sql =
+        "SELECT state FROM person GROUP BY state ORDER BY MIN(age), MAX(salary) DESC";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Projection: person.state
+  Sort: min(person.age) ASC NULLS LAST, max(person.salary) DESC NULLS FIRST
+    Projection: person.state, min(person.age), max(person.salary)
+      Aggregate: groupBy=[[person.state]], aggr=[[min(person.age), max(person.salary)]]
+        TableScan: person
+"#
+    );
+}
+
+#[test]
+fn select_groupby_orderby_aggregate_on_non_selected_column_original_issue() {
+    let sql = "SELECT id FROM person GROUP BY id ORDER BY min(age)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+Projection: person.id
+  Sort: min(person.age) ASC NULLS LAST
+    Projection: person.id, min(person.age)
+      Aggregate: groupBy=[[person.id]], aggr=[[min(person.age)]]
+        TableScan: person
+"#
+    );
+}
+
+#[test]
+fn for_update_of_alias_annotates_only_target_table_scan() -> Result<()> {
+    let plan = logical_plan(
+        "SELECT o.order_id, l.l_item_id \
+         FROM orders o JOIN lineitem l ON o.o_item_id = l.l_item_id \
+         FOR UPDATE OF o NOWAIT",
+    )?;
+
+    let mut scan_locks = vec![];
+    plan.apply(|node| {
+        if let LogicalPlan::TableScan(scan) = node {
+            scan_locks.push((
+                scan.table_name.to_string(),
+                scan.row_lock.map(|lock| (lock.mode, lock.wait_policy)),
+            ));
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    scan_locks.sort_by(|left, right| left.0.cmp(&right.0));
+
+    assert_eq!(
+        scan_locks,
+        vec![
+            ("lineitem".to_string(), None),
+            (
+                "orders".to_string(),
+                Some((
+                    TableScanRowLockMode::ForUpdate,
+                    TableScanRowLockWaitPolicy::Nowait
+                ))
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn for_share_without_of_annotates_all_table_scans() -> Result<()> {
+    let plan = logical_plan(
+        "SELECT o.order_id, l.l_item_id \
+         FROM orders o JOIN lineitem l ON o.o_item_id = l.l_item_id \
+         FOR SHARE SKIP LOCKED",
+    )?;
+
+    let mut scan_locks = vec![];
+    plan.apply(|node| {
+        if let LogicalPlan::TableScan(scan) = node {
+            scan_locks.push((
+                scan.table_name.to_string(),
+                scan.row_lock.map(|lock| (lock.mode, lock.wait_policy)),
+            ));
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    scan_locks.sort_by(|left, right| left.0.cmp(&right.0));
+
+    assert_eq!(
+        scan_locks,
+        vec![
+            (
+                "lineitem".to_string(),
+                Some((
+                    TableScanRowLockMode::ForShare,
+                    TableScanRowLockWaitPolicy::SkipLocked
+                ))
+            ),
+            (
+                "orders".to_string(),
+                Some((
+                    TableScanRowLockMode::ForShare,
+                    TableScanRowLockWaitPolicy::SkipLocked
+                ))
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn for_update_of_unknown_relation_errors() {
+    let err = logical_plan(
+        "SELECT o.order_id, l.l_item_id \
+         FROM orders o JOIN lineitem l ON o.o_item_id = l.l_item_id \
+         FOR UPDATE OF missing",
+    )
+    .expect_err("unknown lock target should fail planning");
+
+    assert_contains!(
+        err.to_string(),
+        "FOR UPDATE OF missing did not match any table scan in the query"
+    );
+}
+
+#[test]
+fn for_update_skip_locked_rejected_by_table_without_support() {
+    let err = logical_plan("SELECT entry_id FROM ledger FOR UPDATE SKIP LOCKED")
+        .expect_err("source without SKIP LOCKED support should fail planning");
+
+    assert_contains!(
+        err.to_string(),
+        "FOR UPDATE SKIP LOCKED is not supported by table ledger"
+    );
+}
+
+#[test]
+fn for_update_nowait_accepted_by_table_with_lock_support() -> Result<()> {
+    let plan = logical_plan("SELECT entry_id FROM ledger FOR UPDATE NOWAIT")?;
+
+    let mut scan_locks = vec![];
+    plan.apply(|node| {
+        if let LogicalPlan::TableScan(scan) = node {
+            scan_locks.push(scan.row_lock.map(|lock| (lock.mode, lock.wait_policy)));
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+
+    assert_eq!(
+        scan_locks,
+        vec![Some((
+            TableScanRowLockMode::ForUpdate,
+            TableScanRowLockWaitPolicy::Nowait
+        ))]
+    );
+    Ok(())
+}
+
+#[test]
+fn tablesample_bernoulli_with_seed() {
+    let sql = "SELECT p.id FROM person AS p TABLESAMPLE BERNOULLI (10) REPEATABLE (42)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r"
+    Projection: p.id
+      SubqueryAlias: p
+        Sample: method=BERNOULLI, size=10%, seed=42
+          TableScan: person
+    "
+    );
+}
+
+#[test]
+fn tablesample_system_percent() {
+    let sql = "SELECT id FROM person TABLESAMPLE SYSTEM (2.5)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r"
+    Projection: person.id
+      Sample: method=SYSTEM, size=2.5%
+        TableScan: person
+    "
+    );
+}
+
+#[test]
+fn tablesample_row_count() {
+    let sql = "SELECT id FROM person TABLESAMPLE (5 ROWS)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r"
+    Projection: person.id
+      Sample: method=BERNOULLI, size=5 ROWS
+        TableScan: person
+    "
+    );
+}
+
+#[test]
+fn tablesample_invalid_sizes() {
+    let err = logical_plan("SELECT id FROM person TABLESAMPLE BERNOULLI (150)")
+        .expect_err("percentage above 100 should fail planning");
+    assert_contains!(
+        err.to_string(),
+        "TABLESAMPLE percentage must be between 0 and 100"
+    );
+
+    let err = logical_plan("SELECT id FROM person TABLESAMPLE SYSTEM (5 ROWS)")
+        .expect_err("SYSTEM sampling by row count should fail planning");
+    assert_contains!(
+        err.to_string(),
+        "TABLESAMPLE SYSTEM does not support a fixed number of rows"
+    );
+}
+
+#[test]
+fn for_system_time_as_of_reads_table_snapshot() {
+    let sql = "SELECT * FROM person FOR SYSTEM_TIME AS OF '2020-01-01T00:00:00' AS p";
+    let plan = logical_plan_with_dialect(sql, &MsSqlDialect {}).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: p.id, p.first_name
+      SubqueryAlias: p
+        TableScan: person, version=[FOR SYSTEM_TIME AS OF Utf8("2020-01-01T00:00:00")]
+    "#
+    );
+}
+
+#[test]
+fn for_system_time_as_of_errors() {
+    let err = logical_plan_with_dialect(
+        "SELECT * FROM j1 FOR SYSTEM_TIME AS OF '2020-01-01T00:00:00'",
+        &MsSqlDialect {},
+    )
+    .expect_err("table without history should fail planning");
+    assert_contains!(
+        err.to_string(),
+        "No snapshot of table j1 FOR SYSTEM_TIME AS OF Utf8(\"2020-01-01T00:00:00\")"
+    );
+
+    let err = logical_plan_with_dialect(
+        "WITH cte AS (SELECT 1 AS a) \
+         SELECT * FROM cte FOR SYSTEM_TIME AS OF '2020-01-01T00:00:00'",
+        &MsSqlDialect {},
+    )
+    .expect_err("CTE has no history");
+    assert_contains!(
+        err.to_string(),
+        "Time travel is not supported for common table expression cte"
+    );
+}
+
+#[test]
+fn for_system_time_as_of_filters_system_versioned_table() {
+    let sql = "SELECT id FROM accounts FOR SYSTEM_TIME AS OF '2020-01-01T00:00:00'";
+    let plan = logical_plan_with_dialect(sql, &MsSqlDialect {}).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: accounts.id
+      Filter: accounts.sys_start <= Utf8("2020-01-01T00:00:00") AND accounts.sys_end > Utf8("2020-01-01T00:00:00")
+        TableScan: accounts
+    "#
+    );
+}
+
+#[test]
+fn create_system_versioned_table() {
+    let sql = "CREATE TABLE t (id INT, \
+               sys_start TIMESTAMP GENERATED ALWAYS AS ROW START, \
+               sys_end TIMESTAMP GENERATED ALWAYS AS ROW END, \
+               PERIOD FOR SYSTEM_TIME (sys_start, sys_end))";
+    let LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(create)) =
+        logical_plan(sql).unwrap()
+    else {
+        panic!("Expected CreateMemoryTable");
+    };
+    let schema = create.input.schema();
+    for (i, bound) in [(1, SYSTEM_TIME_ROW_START), (2, SYSTEM_TIME_ROW_END)] {
+        assert_eq!(
+            schema
+                .field(i)
+                .metadata()
+                .get(SYSTEM_TIME_METADATA_KEY)
+                .map(String::as_str),
+            Some(bound)
+        );
+    }
+
+    let err = logical_plan(
+        "CREATE TABLE t (id INT, sys_start TIMESTAMP GENERATED ALWAYS AS ROW START)",
+    )
+    .expect_err("a ROW START column requires a ROW END column");
+    assert_contains!(
+        err.to_string(),
+        "A system-versioned table requires one GENERATED ALWAYS AS ROW START and one GENERATED ALWAYS AS ROW END column"
+    );
+
+    let err = logical_plan(
+        "CREATE TABLE t (id INT, \
+         sys_start TIMESTAMP GENERATED ALWAYS AS ROW START, \
+         sys_end TIMESTAMP GENERATED ALWAYS AS ROW END, \
+         PERIOD FOR SYSTEM_TIME (id, sys_end))",
+    )
+    .expect_err("the period must start at the ROW START column");
+    assert_contains!(
+        err.to_string(),
+        "Column id of PERIOD FOR SYSTEM_TIME must be GENERATED ALWAYS AS ROW START"
+    );
+}
+
+#[test]
+fn create_table_with_application_time_period() {
+    let sql = "CREATE TABLE emp (id INT, valid_from DATE, valid_to DATE, \
+               PERIOD FOR validity (valid_from, valid_to))";
+    let LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(create)) =
+        logical_plan(sql).unwrap()
+    else {
+        panic!("Expected CreateMemoryTable");
+    };
+    let schema = create.input.schema();
+    assert!(create.constraints.is_empty());
+    for (i, key) in [(1, PERIOD_START_METADATA_KEY), (2, PERIOD_END_METADATA_KEY)] {
+        let field = schema.field(i);
+        assert!(!field.is_nullable());
+        assert_eq!(
+            field.metadata().get(key).map(String::as_str),
+            Some("validity")
+        );
+    }
+
+    let err = logical_plan(
+        "CREATE TABLE emp (id INT, valid_from DATE, valid_to TIMESTAMP, \
+         PERIOD FOR validity (valid_from, valid_to))",
+    )
+    .expect_err("the bounds of a period must have the same type");
+    assert_contains!(
+        err.to_string(),
+        "Columns of period validity must be of the same date or timestamp type, got Date32 and Timestamp(ns)"
+    );
+
+    let err = logical_plan(
+        "CREATE TABLE emp (id INT, valid_from DATE, \
+         PERIOD FOR validity (valid_from, valid_to))",
+    )
+    .expect_err("the bounds of a period must be columns of the table");
+    assert_contains!(
+        err.to_string(),
+        "Column valid_to of period validity not found"
+    );
+}
+
+#[test]
+fn update_for_portion_of_splits_rows() {
+    use datafusion_expr::MergeAction;
+
+    let sql = "UPDATE contracts FOR PORTION OF validity \
+               FROM DATE '2024-01-01' TO DATE '2025-01-01' \
+               SET rate = rate * 2 WHERE id = 1";
+    let LogicalPlan::Merge(merge) = logical_plan(sql).unwrap() else {
+        panic!("Expected Merge");
+    };
+    assert_eq!(merge.target_table.to_string(), "contracts");
+    // The overlapping rows are matched, the parts of their period before and
+    // after the portion are inserted
+    let source = merge.source.display_indent().to_string();
+    assert_contains!(&source, "SubqueryAlias: __portion");
+    assert_contains!(&source, "Union");
+    assert_contains!(&source, "Utf8(\"before\") AS __portion_kind");
+    assert_contains!(&source, "Utf8(\"after\") AS __portion_kind");
+    assert_contains!(
+        merge.on.to_string(),
+        "__portion.__portion_kind = Utf8(\"match\")"
+    );
+    let [matched, not_matched] = merge.clauses.as_slice() else {
+        panic!("Expected two MERGE clauses");
+    };
+    let MergeAction::Update(update) = &matched.action else {
+        panic!("Expected the matched rows to be updated");
+    };
+    let targets: Vec<_> = update
+        .assignments
+        .iter()
+        .map(|assignment| assignment.target.to_string())
+        .collect();
+    assert_eq!(targets, ["rate", "valid_from", "valid_to"]);
+    assert!(matches!(not_matched.action, MergeAction::Insert(_)));
+
+    let sql = "DELETE FROM contracts FOR PORTION OF validity \
+               FROM DATE '2024-01-01' TO DATE '2025-01-01' AS c WHERE c.id = 1";
+    let LogicalPlan::Merge(merge) = logical_plan(sql).unwrap() else {
+        panic!("Expected Merge");
+    };
+    assert_contains!(
+        merge.target.display_indent().to_string(),
+        "SubqueryAlias: c"
+    );
+    assert!(matches!(merge.clauses[0].action, MergeAction::Delete));
+    assert!(matches!(merge.clauses[1].action, MergeAction::Insert(_)));
+
+    let err = logical_plan(
+        "UPDATE contracts FOR PORTION OF other \
+         FROM DATE '2024-01-01' TO DATE '2025-01-01' SET rate = 1",
+    )
+    .unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Table contracts has no period other");
+    let err = logical_plan(
+        "UPDATE contracts FOR PORTION OF validity \
+         FROM DATE '2024-01-01' TO DATE '2025-01-01' SET valid_to = DATE '2026-01-01'",
+    )
+    .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Column valid_to of period validity can not be assigned by UPDATE FOR PORTION OF"
+    );
+}
+
+#[test]
+fn cte_references_share_plan() {
+    let sql = "WITH c AS MATERIALIZED (SELECT id FROM person) \
+               SELECT c1.id FROM c AS c1 JOIN c AS c2 ON c1.id = c2.id";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r"
+        Projection: c1.id
+          Inner Join:  Filter: c1.id = c2.id
+            SubqueryAlias: c1
+              CteReference: c MATERIALIZED
+                SubqueryAlias: c
+                  Projection: person.id
+                    TableScan: person
+            SubqueryAlias: c2
+              CteReference: c MATERIALIZED
+                SubqueryAlias: c
+                  Projection: person.id
+                    TableScan: person
+        "
+    );
+}
+
+#[test]
+fn recursive_cte_search_and_cycle_clause_errors() {
+    let err = logical_plan(
+        "WITH nodes(n) AS (SELECT 1) CYCLE n SET is_cycle USING path \
+         SELECT * FROM nodes",
+    )
+    .expect_err("CYCLE without RECURSIVE");
+    assert_contains!(err.to_string(), "CYCLE clause requires WITH RECURSIVE");
+
+    let err = logical_plan(
+        "WITH RECURSIVE nodes(n) AS (SELECT 1 UNION SELECT n + 1 FROM nodes) \
+         CYCLE m SET is_cycle USING path SELECT * FROM nodes",
+    )
+    .expect_err("unknown CYCLE column");
+    assert_contains!(err.to_string(), "CYCLE column m is not a column of nodes");
+
+    let err = logical_plan(
+        "WITH RECURSIVE nodes(n, path) AS (SELECT 1, 2 UNION SELECT n + 1, path FROM nodes) \
+         CYCLE n SET is_cycle USING path SELECT * FROM nodes",
+    )
+    .expect_err("CYCLE path column already used");
+    assert_contains!(
+        err.to_string(),
+        "CYCLE column path conflicts with a column of nodes"
+    );
+
+    let err = logical_plan(
+        "WITH nodes(n) AS (SELECT 1) SEARCH DEPTH FIRST BY n SET seq \
+         SELECT * FROM nodes",
+    )
+    .expect_err("SEARCH without RECURSIVE");
+    assert_contains!(err.to_string(), "SEARCH clause requires WITH RECURSIVE");
+
+    let err = logical_plan(
+        "WITH RECURSIVE nodes(n) AS (SELECT 1 UNION SELECT n + 1 FROM nodes) \
+         SEARCH BREADTH FIRST BY m SET seq SELECT * FROM nodes",
+    )
+    .expect_err("unknown SEARCH column");
+    assert_contains!(err.to_string(), "SEARCH column m is not a column of nodes");
+
+    let err = logical_plan(
+        "WITH RECURSIVE nodes(n) AS (SELECT 1 UNION SELECT n + 1 FROM nodes) \
+         SEARCH DEPTH FIRST BY n SET path CYCLE n SET is_cycle USING path \
+         SELECT * FROM nodes",
+    )
+    .expect_err("SEARCH and CYCLE columns share a name");
+    assert_contains!(
+        err.to_string(),
+        "CYCLE column path conflicts with a column of nodes"
+    );
+}
+
+#[test]
+fn at_timestamp_reads_table_snapshot() {
+    let sql = "CREATE TABLE person_2020 AS \
+               SELECT * FROM person AT(TIMESTAMP => '2020-01-01 00:00:00')";
+    let plan = logical_plan_with_dialect(sql, &SnowflakeDialect {}).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    CreateMemoryTable: Bare { table: "person_2020" }
+      Projection: person.id, person.first_name
+        TableScan: person, version=[AT(TIMESTAMP => Utf8("2020-01-01 00:00:00"))]
+    "#
+    );
+}
+
+#[test]
+fn before_statement_passed_to_provider() {
+    let err = logical_plan_with_dialect(
+        "SELECT * FROM j1 BEFORE(STATEMENT => '8e5d0ca9')",
+        &SnowflakeDialect {},
+    )
+    .expect_err("table without history should fail planning");
+    assert_contains!(
+        err.to_string(),
+        "No snapshot of table j1 BEFORE(STATEMENT => Utf8(\"8e5d0ca9\"))"
+    );
+
+    let err = logical_plan_with_dialect(
+        "SELECT * FROM person AT(CHANGES => 1)",
+        &SnowflakeDialect {},
+    )
+    .expect_err("unknown snapshot kind should fail planning");
+    assert_contains!(err.to_string(), "Unknown AT argument CHANGES");
+}
+
+#[test]
+fn create_table_clone() {
+    let plan = logical_plan("CREATE TABLE person_copy CLONE person").unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    CreateMemoryTable: Bare { table: "person_copy" }
+      TableScan: person
+    "#
+    );
+}
+
+#[test]
+fn pivot_conditional_aggregation() {
+    let sql = "SELECT * FROM (SELECT age, state, salary FROM person) \
+               PIVOT (SUM(salary) FOR state IN ('CA', 'NY' AS new_york)) AS p";
+    let plan = logical_plan_with_dialect(sql, &SnowflakeDialect {}).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: p.age, p.CA, p.new_york
+      SubqueryAlias: p
+        Aggregate: groupBy=[[person.age]], aggr=[[sum(person.salary) FILTER (WHERE person.state = Utf8("CA")) AS CA, sum(person.salary) FILTER (WHERE person.state = Utf8("NY")) AS new_york]]
+          Projection: person.age, person.state, person.salary
+            TableScan: person
+    "#
+    );
+}
+
+#[test]
+fn pivot_multiple_aggregates_and_default() {
+    let sql = "SELECT * FROM (SELECT age, state, salary FROM person) \
+               PIVOT (SUM(salary) AS total, SUM(age) AS ages \
+                      FOR state IN ('CA', 'NY') DEFAULT ON NULL (0)) AS p";
+    let plan = logical_plan_with_dialect(sql, &SnowflakeDialect {}).unwrap();
+    let names = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect::<Vec<_>>();
+    // `age` feeds an aggregate, so nothing is left to group by
+    assert_eq!(names, vec!["CA_total", "CA_ages", "NY_total", "NY_ages"]);
+    assert_contains!(plan.display_indent().to_string(), "CASE WHEN CA_total IS NULL");
+
+    let err = logical_plan_with_dialect(
+        "SELECT * FROM person PIVOT (SUM(salary) FOR state IN (ANY))",
+        &SnowflakeDialect {},
+    )
+    .expect_err("dynamic pivot values are not supported");
+    assert_contains!(err.to_string(), "PIVOT ... IN (ANY) is not supported");
+}
+
+#[test]
+fn unpivot_excludes_nulls_by_default() {
+    let sql = "SELECT * FROM (SELECT id, first_name, last_name FROM person) \
+               UNPIVOT (name_part FOR kind IN (first_name, last_name AS surname)) AS u";
+    let plan = logical_plan_with_dialect(sql, &SnowflakeDialect {}).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: u.id, u.kind, u.name_part
+      SubqueryAlias: u
+        Union
+          Projection: person.id, Utf8("first_name") AS kind, person.first_name AS name_part
+            Filter: person.first_name IS NOT NULL
+              Projection: person.id, person.first_name, person.last_name
+                TableScan: person
+          Projection: person.id, Utf8("surname") AS kind, person.last_name AS name_part
+            Filter: person.last_name IS NOT NULL
+              Projection: person.id, person.first_name, person.last_name
+                TableScan: person
+    "#
+    );
+}
+
+#[test]
+fn unpivot_include_nulls() {
+    let sql = "SELECT * FROM (SELECT id, first_name, last_name FROM person) \
+               UNPIVOT INCLUDE NULLS (name_part FOR kind IN (first_name, last_name))";
+    let plan = logical_plan_with_dialect(sql, &SnowflakeDialect {}).unwrap();
+    let plan_str = plan.display_indent().to_string();
+    assert!(!plan_str.contains("IS NOT NULL"), "{plan_str}");
+    assert_contains!(plan_str, "Union");
+}
+
+#[test]
+fn lateral_table_function_references_preceding_from_item() {
+    let sql = "SELECT person.id, e.value FROM person, LATERAL echo(person.id) AS e";
+    let plan = logical_plan(sql).unwrap();
+    let plan_str = plan.display_indent().to_string();
+    assert_contains!(plan_str, "Subquery:");
+    assert_contains!(plan_str, "outer_ref(person.id) AS value");
+
+    let err =
+        logical_plan("SELECT * FROM person, LATERAL series(person.id)").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Table function series does not support arguments that reference preceding FROM items"
+    );
+}
+
+#[test]
+fn table_function_is_implicitly_lateral() {
+    let sql = "SELECT person.id, e.value FROM person, echo(person.id) AS e";
+    let plan = logical_plan(sql).unwrap();
+    let plan_str = plan.display_indent().to_string();
+    assert_contains!(plan_str, "Subquery:");
+    assert_contains!(plan_str, "outer_ref(person.id) AS value");
+
+    // Constant arguments still plan as a plain scan
+    let plan = logical_plan("SELECT * FROM person, series(3)").unwrap();
+    assert!(!plan.display_indent().to_string().contains("Subquery:"));
+}
+
+#[test]
+fn table_function_rejects_uncorrelatable_arguments() {
+    let err = logical_plan("SELECT * FROM person, series(person.id)").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Table function series does not support arguments that reference preceding FROM items"
+    );
+
+    // Argument errors are reported rather than silently dropped
+    let err = logical_plan("SELECT * FROM person, echo(no_such_column)").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "no_such_column");
+}
+
+#[test]
+fn table_function_named_arguments() {
+    let plan =
+        logical_plan("SELECT * FROM read_csv(header => true, path => 'a.csv')").unwrap();
+    assert_eq!(plan.schema().field(0).name(), "id");
+
+    // Positional arguments may precede named ones
+    let plan = logical_plan("SELECT * FROM read_csv('a.csv', header => false)").unwrap();
+    assert_eq!(plan.schema().field(0).name(), "column_1");
+
+    let err = logical_plan("SELECT * FROM read_csv(path => 'a.csv', delimiter => ';')")
+        .unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Unknown parameter name 'delimiter'");
+
+    let err = logical_plan("SELECT * FROM series(stop => 3)").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Table function series does not support named arguments"
+    );
+
+    // Names are normalized like other identifiers, in FROM and in the
+    // SELECT list
+    let plan =
+        logical_plan("SELECT * FROM read_csv(PATH => 'a.csv', HEADER => true)").unwrap();
+    assert_eq!(plan.schema().field(0).name(), "id");
+    let plan = logical_plan("SELECT read_csv(PATH => 'a.csv', HEADER => true)").unwrap();
+    assert_eq!(plan.schema().field(0).name(), "id");
+
+    let err = logical_plan_with_options(
+        "SELECT * FROM read_csv(PATH => 'a.csv')",
+        ident_normalization_parser_options_no_ident_normalization(),
+    )
+    .unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Unknown parameter name 'PATH'");
+}
+
+#[test]
+fn polymorphic_table_function_describes_output() {
+    let sql = "SELECT * FROM TABLE(sessionize((SELECT id, age FROM person), 30)) AS s";
+    let plan = logical_plan(sql).unwrap();
+    let plan_str = plan.display_indent().to_string();
+    assert_contains!(plan_str, "Projection: s.id, s.age, s.session_id");
+    assert_contains!(plan_str, "PolymorphicTableFunction: sessionize(TABLE, Int32(30))");
+    assert_contains!(plan_str, "TableScan: person");
+
+    // `TABLE t` passes the whole table
+    let sql = "SELECT * FROM TABLE(sessionize(TABLE j1, 30)) AS s";
+    let plan = logical_plan(sql).unwrap();
+    let plan_str = plan.display_indent().to_string();
+    assert_contains!(plan_str, "Projection: s.j1_id, s.j1_string, s.session_id");
+    assert_contains!(
+        plan_str,
+        "PolymorphicTableFunction: sessionize(TABLE, Int32(30))"
+    );
+    assert_contains!(plan_str, "TableScan: j1");
+
+    let err = logical_plan("SELECT * FROM TABLE(sessionize(30))").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "sessionize expects a table and a gap");
+}
+
+#[test]
+fn table_hints_reach_the_scan() {
+    let sql = "SELECT event_id FROM events WITH (NOLOCK, INDEX(events_ts))";
+    let plan = logical_plan_with_dialect(sql, &MsSqlDialect {}).unwrap();
+    assert_contains!(
+        plan.display_indent().to_string(),
+        "TableScan: events, hints=[INDEX(events_ts), NOLOCK]"
+    );
+
+    let sql = "SELECT event_id FROM events FORCE INDEX FOR ORDER BY (events_ts)";
+    let plan = logical_plan_with_dialect(sql, &MySqlDialect {}).unwrap();
+    assert_contains!(
+        plan.display_indent().to_string(),
+        "TableScan: events, hints=[FORCE INDEX FOR ORDER BY(events_ts)]"
+    );
+}
+
+#[test]
+fn table_hints_are_validated_by_the_provider() {
+    let sql = "SELECT event_id FROM events WITH (TABLOCKX)";
+    let err = logical_plan_with_dialect(sql, &MsSqlDialect {}).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Table events does not support the TABLOCKX hint"
+    );
+
+    let sql = "SELECT event_id FROM events USE INDEX (missing)";
+    let err = logical_plan_with_dialect(sql, &MySqlDialect {}).unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Unknown index missing");
+}
+
+/// The messages of the warnings raised while planning `sql`
+fn planner_warnings(sql: &str, dialect: &dyn Dialect) -> Vec<String> {
+    let context = MockContextProvider {
+        state: MockSessionState::default(),
+    };
+    let planner = SqlToRel::new(&context);
+    let mut ast = DFParser::parse_sql_with_dialect(sql, dialect).unwrap();
+    let Some(DFStatement::Statement(statement)) = ast.pop_front() else {
+        panic!("expected a SQL statement");
+    };
+    let (_, warnings) = planner
+        .sql_statement_to_plan_with_warnings(*statement)
+        .unwrap();
+    warnings
+        .into_iter()
+        .map(|warning| warning.message)
+        .collect()
+}
+
+#[test]
+fn planner_warns_about_ignored_hints() {
+    let sql = "SELECT event_id FROM events WITH (NOLOCK, INDEX(events_ts))";
+    assert_eq!(
+        planner_warnings(sql, &MsSqlDialect {}),
+        vec!["Table events ignores the NOLOCK hint"]
+    );
+}
+
+#[test]
+fn planner_warns_about_unused_ctes() {
+    let sql = "WITH unused AS (SELECT 1), used AS (SELECT 2) SELECT * FROM used";
+    assert_eq!(
+        planner_warnings(sql, &PostgreSqlDialect {}),
+        vec![r#"WITH query "unused" is not used"#]
+    );
+
+    // CTEs referenced from subqueries or other CTEs are used
+    let sql = "WITH a AS (SELECT 1 AS x), b AS (SELECT x FROM a), c AS (SELECT 2 AS y) \
+               SELECT * FROM b WHERE x IN (SELECT y FROM c)";
+    assert!(planner_warnings(sql, &PostgreSqlDialect {}).is_empty());
+}
+
+#[test]
+fn planner_warns_about_select_into() {
+    let sql = "SELECT id INTO person_ids FROM person";
+    assert_eq!(
+        planner_warnings(sql, &PostgreSqlDialect {}),
+        vec!["SELECT INTO to create a table is deprecated, use CREATE TABLE AS instead"]
+    );
+}
+
+#[test]
+fn planner_warns_about_lossy_assignments() {
+    let sql = "INSERT INTO person (id, age) SELECT order_id, price FROM orders";
+    assert_eq!(
+        planner_warnings(sql, &PostgreSqlDialect {}),
+        vec!["Implicit cast from Float64 to Int32 for column age may lose information"]
+    );
+
+    // Literals are cast to the type of the column when planned
+    let sql = "UPDATE person SET age = 1, salary = age";
+    assert!(planner_warnings(sql, &PostgreSqlDialect {}).is_empty());
+}
+
+#[test]
+fn graph_table_joins_element_tables() {
+    let sql = "SELECT * FROM GRAPH_TABLE (social
+        MATCH (a:person)-[e:knows]->(b:person)
+        WHERE a.age > 30
+        COLUMNS (a.first_name AS name, b.first_name AS friend, e.since)
+    ) AS g";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(
+        &plan,
+        "Projection: a.first_name AS name, b.first_name AS friend, e.since AS since"
+    );
+    assert_contains!(&plan, "Filter: a.age > Int32(30)");
+    assert_contains!(&plan, "Inner Join:  Filter: e.dst = b.id");
+    assert_contains!(&plan, "Inner Join:  Filter: e.src = a.id");
+    assert_contains!(&plan, "SubqueryAlias: e");
+
+    // An undirected edge may be traversed either way
+    let sql = "SELECT * FROM GRAPH_TABLE (social
+        MATCH (a {id: 1})-[:knows]-(b)
+        COLUMNS (b.first_name)
+    )";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(
+        &plan,
+        "Filter: __graph_element_1.src = a.id OR __graph_element_1.dst = a.id"
+    );
+    assert_contains!(&plan, "Filter: a.id = Int32(1)");
+}
+
+#[test]
+fn graph_table_validates_patterns_against_the_graph() {
+    let sql = "SELECT * FROM GRAPH_TABLE (social MATCH (c:city) COLUMNS (c.id))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "No vertex table of property graph social matches (c:city)"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (social MATCH (a:person) COLUMNS (a.salary))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "salary is not a property of person in property graph social"
+    );
+}
+
+#[test]
+fn graph_table_unrolls_quantified_edges() {
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH ANY SHORTEST (a:person)-[e:knows{1,2}]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(
+        &plan,
+        "DistinctOn: on_expr=[[__graph_endpoint_0, __graph_endpoint_1]], select_expr=[[src, dst]]"
+    );
+    assert_contains!(&plan, "Union");
+    assert_contains!(&plan, "UInt64(1) AS __graph_path_length");
+    assert_contains!(&plan, "UInt64(2) AS __graph_path_length");
+    assert_contains!(
+        &plan,
+        "Inner Join:  Filter: __graph_element_3.src = __graph_element_2.id"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH ALL SHORTEST (a:person)-[e:knows*]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "LeftAnti Join:");
+    assert_contains!(
+        &plan,
+        "__graph_better.__graph_path_length < __graph_paths.__graph_path_length"
+    );
+    // Zero repetitions stay on the starting vertex
+    assert_contains!(&plan, "Inner Join:  Filter: a.id = b.id");
+}
+
+#[test]
+fn graph_table_cheapest_paths() {
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH ANY CHEAPEST (a:person)-[e:knows COST e.weight]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "e.weight AS __graph_path_cost");
+    assert_contains!(&plan, "__graph_path_cost ASC NULLS LAST");
+
+    // Edges without a COST expression cost 1
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH ALL CHEAPEST (a:person)-[e:knows COST e.weight]->(m:person)-[f:knows]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "e.weight + Int64(1) AS __graph_path_cost");
+    assert_contains!(
+        &plan,
+        "__graph_better.__graph_path_cost < __graph_paths.__graph_path_cost"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH ANY CHEAPEST (a:person)-[e:knows COST a.age]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "COST expression a.age of -[e:knows]- may only reference properties of the edge"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH ANY CHEAPEST (a:person)-[e:knows COST e.src]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "src is not a property of knows in property graph social"
+    );
+}
+
+#[test]
+fn graph_table_path_modes() {
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH TRAIL (a:person)-[e:knows{2}]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    // `knows` has neither a KEY nor a primary key, so its edges are told
+    // apart by all their columns: parallel edges between the same persons
+    // are distinct, also when they differ only by a null `since` or `weight`
+    assert_contains!(
+        &plan,
+        "__graph_element_1.src IS DISTINCT FROM __graph_element_3.src \
+         OR __graph_element_1.dst IS DISTINCT FROM __graph_element_3.dst \
+         OR __graph_element_1.since IS DISTINCT FROM __graph_element_3.since \
+         OR __graph_element_1.weight IS DISTINCT FROM __graph_element_3.weight"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH ACYCLIC (a:person)-[e:knows{2}]->(b:person) \
+               COLUMNS (a.id AS src, b.id AS dst))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "a.id IS DISTINCT FROM __graph_element_2.id");
+    assert_contains!(&plan, "a.id IS DISTINCT FROM b.id");
+}
+
+#[test]
+fn graph_table_aggregates_along_quantified_paths() {
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH p = (a:person)-[e:knows{1,2}]->(b:person) \
+               WHERE SUM(e.weight) < 10 \
+               COLUMNS (a.id AS src, PATH_LENGTH(p) AS hops, COUNT(e.since) AS dated, \
+                        MAX(e.weight) AS heaviest))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "UInt64(1) AS hops");
+    assert_contains!(&plan, "UInt64(2) AS hops");
+    assert_contains!(
+        &plan,
+        "CASE WHEN __graph_element_1.since IS NOT NULL THEN Int64(1) ELSE Int64(0) END \
+         + CASE WHEN __graph_element_3.since IS NOT NULL THEN Int64(1) ELSE Int64(0) END AS dated"
+    );
+    assert_contains!(&plan, "__graph_element_1.weight AS heaviest");
+    assert_contains!(&plan, "Filter: __graph_element_1.weight < Int32(10)");
+
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH (a:person)-[e:knows+]->(b:person) COLUMNS (e.since))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Quantified edge variable e can only be referenced in an aggregate"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH (a:person)-[e:knows+]->(b:person) COLUMNS (SUM(a.age) AS total))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "must read a single quantified edge variable"
+    );
+}
+
+#[test]
+fn graph_table_label_expressions() {
+    // A disjunction matching several edge tables unions a branch for each;
+    // properties the matched table does not expose are null
+    let sql = "SELECT * FROM GRAPH_TABLE (shop \
+               MATCH (c:customer)-[e:knows|bought]->(x) \
+               COLUMNS (c.first_name AS name, e.price, x.l_description AS item))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "Union");
+    assert_contains!(&plan, "TableScan: knows");
+    assert_contains!(&plan, "TableScan: orders");
+    assert_contains!(
+        &plan,
+        "Projection: c.first_name AS name, e.price AS price, x.l_description AS item"
+    );
+    assert_contains!(
+        &plan,
+        "Projection: c.first_name AS name, NULL AS price, NULL AS item"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (shop \
+               MATCH (x:%&!customer) COLUMNS (x.l_description AS item))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "TableScan: lineitem");
+    assert!(!plan.contains("TableScan: person"), "{plan}");
+
+    // Endpoints of different vertex tables are told apart by table name
+    let sql = "SELECT * FROM GRAPH_TABLE (shop \
+               MATCH ANY (a:customer)-[e:knows|bought]->(b) COLUMNS (a.id AS src))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan, "Utf8(\"lineitem\") AS __graph_endpoint_2");
+
+    let sql = "SELECT * FROM GRAPH_TABLE (shop \
+               MATCH (x:customer&item) COLUMNS (x.id))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "No vertex table of property graph shop matches (x:customer&item)"
+    );
+
+    let sql = "SELECT * FROM GRAPH_TABLE (shop MATCH (x:%) COLUMNS (x.salary))";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "salary is not a property of person in property graph shop"
+    );
+}
+
+#[test]
+fn graph_table_exports_element_variables() {
+    let sql = "SELECT * FROM GRAPH_TABLE (social \
+               MATCH (a:person)-[e:knows]->(b:person) \
+               COLUMNS (a.*, e.since, ELEMENT_ID(b) AS friend, EDGE_ID(e)))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(
+        &plan,
+        "Projection: a.id AS id, a.first_name AS first_name, a.age AS age, a.state AS state, e.since AS since"
+    );
+    assert_contains!(&plan, "Utf8(\"person:\") || CAST(b.id AS Utf8) AS friend");
+    assert_contains!(&plan, "CAST(e.weight AS Utf8) AS edge_id(e)");
+
+    // Properties of the tables a variable may be bound to, null where the
+    // matched table does not expose them
+    let sql = "SELECT * FROM GRAPH_TABLE (shop \
+               MATCH (c:customer)-[e:knows|bought]->(x) COLUMNS (e.*))";
+    let plan = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(
+        &plan,
+        "Projection: e.since AS since, NULL AS qty, NULL AS price"
+    );
+    assert_contains!(
+        &plan,
+        "Projection: NULL AS since, e.qty AS qty, e.price AS price"
+    );
+
+    let cases = [
+        (
+            "SELECT * FROM GRAPH_TABLE (social MATCH (a:person) COLUMNS (c.*))",
+            "c.* does not refer to an element variable of the MATCH clause",
+        ),
+        (
+            "SELECT * FROM GRAPH_TABLE (social \
+             MATCH (a:person)-[e:knows]->(b:person) COLUMNS (*))",
+            "GRAPH_TABLE column id is specified more than once",
+        ),
+        (
+            "SELECT * FROM GRAPH_TABLE (social \
+             MATCH (a:person)-[e:knows]->(b:person) COLUMNS (VERTEX_ID(e)))",
+            "VERTEX_ID(e) requires a vertex variable",
+        ),
+        (
+            "SELECT * FROM GRAPH_TABLE (social \
+             MATCH (a:person)-[e:knows+]->(b:person) COLUMNS (e.*))",
+            "Quantified edge variable e can only be referenced in an aggregate",
+        ),
+    ];
+    for (sql, expected) in cases {
+        let err = logical_plan(sql).unwrap_err();
+        assert_contains!(err.strip_backtrace(), expected);
+    }
+}
+
+#[test]
+fn create_property_graph_validates_element_tables() {
+    let sql = "CREATE PROPERTY GRAPH friends \
+               VERTEX TABLES (person KEY (id) PROPERTIES (first_name, age)) \
+               EDGE TABLES (knows SOURCE KEY (src) REFERENCES person \
+                                  DESTINATION KEY (dst) REFERENCES person \
+                                  PROPERTIES (since))";
+    match logical_plan(sql).unwrap() {
+        LogicalPlan::Ddl(DdlStatement::CreatePropertyGraph(graph)) => {
+            assert_eq!(graph.vertex_tables.len(), 1);
+            assert_eq!(graph.edge_tables.len(), 1);
+        }
+        plan => panic!("Expected CreatePropertyGraph DDL statement, got {plan:?}"),
+    }
+
+    let cases = [
+        (
+            "CREATE PROPERTY GRAPH friends VERTEX TABLES (person KEY (person_id))",
+            "KEY column person_id not found in table person",
+        ),
+        (
+            "CREATE PROPERTY GRAPH friends VERTEX TABLES (person PROPERTIES (nickname))",
+            "PROPERTIES column nickname not found in table person",
+        ),
+        (
+            "CREATE PROPERTY GRAPH friends VERTEX TABLES (nobody)",
+            "No table named: nobody found",
+        ),
+        (
+            "CREATE PROPERTY GRAPH friends VERTEX TABLES (person, person)",
+            "Vertex table person is declared more than once in property graph friends",
+        ),
+        (
+            "CREATE PROPERTY GRAPH friends VERTEX TABLES (person) \
+             EDGE TABLES (knows SOURCE KEY (src) REFERENCES person \
+                                DESTINATION KEY (dst) REFERENCES lineitem)",
+            "Edge table knows references lineitem, which is not a vertex table of property graph friends",
+        ),
+        (
+            "CREATE PROPERTY GRAPH friends VERTEX TABLES (person) \
+             EDGE TABLES (knows SOURCE KEY (source) REFERENCES person \
+                                DESTINATION KEY (dst) REFERENCES person)",
+            "SOURCE KEY column source not found in table knows",
+        ),
+        (
+            "CREATE PROPERTY GRAPH friends VERTEX TABLES (person KEY (id, state)) \
+             EDGE TABLES (knows SOURCE KEY (src) REFERENCES person \
+                                DESTINATION KEY (dst) REFERENCES person)",
+            "SOURCE KEY of edge table knows has 1 columns, but the KEY of vertex table person has 2",
+        ),
+        (
+            "CREATE PROPERTY GRAPH friends \
+             VERTEX TABLES (person LABEL named PROPERTIES (first_name), \
+                            person_quoted_cols LABEL named PROPERTIES (id))",
+            "Label named of property graph friends has different properties in person and person_quoted_cols",
+        ),
+        (
+            "CREATE PROPERTY GRAPH social VERTEX TABLES (person)",
+            "Property graph social already exists",
+        ),
+    ];
+    for (sql, expected) in cases {
+        let err = logical_plan(sql).unwrap_err();
+        assert_contains!(err.strip_backtrace(), expected);
+    }
+
+    logical_plan("CREATE OR REPLACE PROPERTY GRAPH social VERTEX TABLES (person)")
+        .unwrap();
+}
+
+fn logical_plan(sql: &str) -> Result<LogicalPlan> {
+    logical_plan_with_options(sql, ParserOptions::default())
+}
+
+#[test]
+fn update_joins_tuple_subquery() {
+    let state = MockSessionState::default()
+        .with_scalar_function(Arc::new(make_udf(
+            "struct",
+            vec![DataType::Int32, DataType::Float64],
+            DataType::Utf8,
+        )))
+        .with_scalar_function(Arc::new(make_udf(
+            "get_field",
+            vec![DataType::Utf8, DataType::Utf8],
+            DataType::Int32,
+        )));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &PostgreSqlDialect {})?;
+        planner
+            .statement_to_plan(ast.pop_front().unwrap())
+            .map(|plan| plan.display_indent().to_string())
+    };
+
+    // A correlated subquery, which is not aggregated, is evaluated once per
+    // updated row, and fails when it returns more than one row
+    let plan_str = plan(
+        "UPDATE person SET (age, salary) = \
+         (SELECT qty, price FROM orders WHERE customer_id = person.id)",
+    )
+    .unwrap();
+    assert_eq!(plan_str.matches("Subquery:").count(), 1);
+    assert_contains!(&plan_str, "AS __update_tuple_0");
+    assert_contains!(
+        &plan_str,
+        "get_field(__update_tuple_0, Utf8(\"c0\")) AS age"
+    );
+    assert_contains!(&plan_str, "get_field(__update_tuple_0, Utf8(\"c1\"))");
+    assert_not_contains!(&plan_str, "Join");
+
+    // An uncorrelated subquery is evaluated once, in a relation of one row
+    // joined with the updated rows
+    let plan_str = plan(
+        "UPDATE person SET (age, salary) = \
+         (SELECT qty, price FROM orders WHERE order_id = 1)",
+    )
+    .unwrap();
+    assert_eq!(plan_str.matches("Subquery:").count(), 1);
+    assert_contains!(&plan_str, "Cross Join:");
+    assert_contains!(
+        &plan_str,
+        "get_field(__update_tuple_0, Utf8(\"c0\")) AS age"
+    );
+}
+
+#[test]
+fn collate_compares_and_orders_by_sort_key() {
+    let state = MockSessionState::default().with_scalar_function(Arc::new(make_udf(
+        "lower",
+        vec![DataType::Utf8],
+        DataType::Utf8,
+    )));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &PostgreSqlDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    let sql = "SELECT first_name COLLATE case_insensitive AS name FROM person \
+               WHERE last_name COLLATE case_insensitive = 'smith' ORDER BY name";
+    let plan_str = plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan_str, "Sort: lower(name) ASC NULLS LAST");
+    assert_contains!(
+        &plan_str,
+        "Filter: lower(person.last_name) = lower(Utf8(\"smith\"))"
+    );
+
+    // Binary collations need no sort key
+    let sql = "SELECT first_name FROM person ORDER BY first_name COLLATE \"C\"";
+    let plan_str = plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan_str, "Sort: person.first_name ASC NULLS LAST");
+
+    // Columns have the collation of their definition
+    let sql = "CREATE TABLE t (name VARCHAR COLLATE case_insensitive, id INT)";
+    let LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(create)) = plan(sql).unwrap()
+    else {
+        panic!("Expected CreateMemoryTable");
+    };
+    let schema = create.input.schema();
+    assert_eq!(
+        schema.field(0).metadata().get(COLLATION_METADATA_KEY),
+        Some(&"case_insensitive".to_string())
+    );
+
+    let err = plan("SELECT first_name COLLATE klingon FROM person").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Unknown collation klingon");
+
+    let err = plan("SELECT age COLLATE \"C\" FROM person").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "COLLATE can only be applied to character strings, got Int32"
+    );
+
+    let err = plan(
+        "SELECT * FROM person \
+         WHERE first_name COLLATE \"C\" = last_name COLLATE case_insensitive",
+    )
+    .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Cannot compare character strings with collations C and case_insensitive"
+    );
+}
+
+#[test]
+fn plan_json_operators() {
+    let sql = r#"SELECT '{"a": 1}' -> 'a' AS x, '{"a": 1}' ->> 'a' AS y,
+                 '{"a": [1]}' #> '{a,0}' AS z, '{"a": 1}' OPERATOR(pg_catalog.#>>) '{a}' AS w"#;
+    let plan_str = logical_plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan_str, r#"Utf8("{"a": 1}") -> Utf8("a") AS x"#);
+    assert_contains!(&plan_str, r#"Utf8("{"a": 1}") ->> Utf8("a") AS y"#);
+    assert_contains!(&plan_str, r#"Utf8("{"a": [1]}") #> Utf8("{a,0}") AS z"#);
+    assert_contains!(&plan_str, r#"Utf8("{"a": 1}") #>> Utf8("{a}") AS w"#);
+}
+
+#[test]
+fn expr_planners_plan_json_operators() {
+    use datafusion_common::DFSchema;
+    use datafusion_expr::expr::ScalarFunction;
+    use datafusion_expr::planner::{ExprPlanner, PlannerResult, RawBinaryExpr};
+    use datafusion_expr::{Expr, ExprSchemable};
+    use sqlparser::ast::BinaryOperator;
+
+    /// Plans `->` on character strings as a call of `json_get`
+    #[derive(Debug)]
+    struct JsonGetPlanner {
+        json_get: Arc<ScalarUDF>,
+    }
+
+    impl ExprPlanner for JsonGetPlanner {
+        fn plan_binary_op(
+            &self,
+            expr: RawBinaryExpr,
+            schema: &DFSchema,
+        ) -> Result<PlannerResult<RawBinaryExpr>> {
+            if expr.op == BinaryOperator::Arrow
+                && expr.left.get_type(schema)? == DataType::Utf8
+            {
+                let args = vec![expr.left, expr.right];
+                return Ok(PlannerResult::Planned(Expr::ScalarFunction(
+                    ScalarFunction::new_udf(Arc::clone(&self.json_get), args),
+                )));
+            }
+            Ok(PlannerResult::Original(expr))
+        }
+    }
+
+    let json_get = Arc::new(make_udf(
+        "json_get",
+        vec![DataType::Utf8, DataType::Utf8],
+        DataType::Utf8,
+    ));
+    let state = MockSessionState::default()
+        .with_expr_planner(Arc::new(JsonGetPlanner { json_get }));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &PostgreSqlDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    let plan_str = plan("SELECT first_name -> 'a', first_name ->> 'a' FROM person")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan_str, r#"json_get(person.first_name, Utf8("a"))"#);
+    // Operators the planner does not plan keep their built-in mapping
+    assert_contains!(&plan_str, r#"person.first_name ->> Utf8("a")"#);
+}
+
+#[test]
+fn statement_rewriters_run_before_planning() {
+    use std::ops::ControlFlow;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use datafusion_expr::planner::{ContextProvider, StatementRewriter};
+    use sqlparser::ast::{Ident, ObjectName, Statement, visit_relations_mut};
+
+    /// Renames the table `people` to `person`
+    #[derive(Debug, Default)]
+    struct RenamePeople {
+        rewrites: AtomicUsize,
+    }
+
+    impl StatementRewriter for RenamePeople {
+        fn rewrite_statement(
+            &self,
+            mut statement: Statement,
+            _context_provider: &dyn ContextProvider,
+        ) -> Result<Statement> {
+            self.rewrites.fetch_add(1, Ordering::Relaxed);
+            let _ = visit_relations_mut(&mut statement, |name| {
+                if name.to_string() == "people" {
+                    *name = ObjectName::from(vec![Ident::new("person")]);
+                }
+                ControlFlow::<()>::Continue(())
+            });
+            Ok(statement)
+        }
+    }
+
+    /// Rejects `DELETE` statements
+    #[derive(Debug)]
+    struct ReadOnly;
+
+    impl StatementRewriter for ReadOnly {
+        fn rewrite_statement(
+            &self,
+            statement: Statement,
+            _context_provider: &dyn ContextProvider,
+        ) -> Result<Statement> {
+            match statement {
+                Statement::Delete(_) => Err(DataFusionError::Plan(
+                    "The session is read-only".to_string(),
+                )),
+                statement => Ok(statement),
+            }
+        }
+    }
+
+    let rename = Arc::new(RenamePeople::default());
+    let state = MockSessionState::default()
+        .with_statement_rewriter(Arc::clone(&rename) as Arc<dyn StatementRewriter>)
+        .with_statement_rewriter(Arc::new(ReadOnly));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    let plan_str = plan("SELECT id FROM people")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan_str, "TableScan: person");
+    assert_eq!(rename.rewrites.load(Ordering::Relaxed), 1);
+
+    // The statement of an EXPLAIN is rewritten once
+    let plan_str = plan("EXPLAIN SELECT id FROM people")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan_str, "TableScan: person");
+    assert_eq!(rename.rewrites.load(Ordering::Relaxed), 2);
+
+    let err = plan("DELETE FROM people WHERE id = 1").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "The session is read-only");
+}
+
+#[test]
+fn row_security_policies_filter_scans() {
+    use datafusion_expr::planner::RowSecurityPolicy;
+
+    let state = MockSessionState::default()
+        .with_row_security_policy(
+            "person",
+            RowSecurityPolicy::new("own_state", "state = @state"),
+        )
+        .with_row_security_policy("person", RowSecurityPolicy::new("adults", "age >= 18"))
+        .with_row_security_policy("j1", RowSecurityPolicy::new("broken", "missing > 0"));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    let plan_str = plan("SELECT id FROM person WHERE salary > 0")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(
+        &plan_str,
+        "Filter: person.state = @state AND person.age >= Int32(18)"
+    );
+    assert_contains!(&plan_str, "TableScan: person, policies=[own_state, adults]");
+
+    // The scans of expanded views are filtered too
+    let plan_str = plan("SELECT id FROM adult_view")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan_str, "TableScan: person, policies=[own_state, adults]");
+
+    let plan_str = plan("SELECT id FROM orders")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_not_contains!(&plan_str, "policies=");
+
+    let err = plan("SELECT j1_id FROM j1").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Invalid predicate of row security policy broken of table j1"
+    );
+}
+
+#[test]
+fn row_security_policies_filter_copy_and_graph_scans() {
+    use datafusion_expr::planner::RowSecurityPolicy;
+
+    let state = MockSessionState::default().with_row_security_policy(
+        "person",
+        RowSecurityPolicy::new("adults", "age >= 18"),
+    );
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &PostgreSqlDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    let plan_str = plan("COPY person TO 'people.csv'")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan_str, "Filter: person.age >= Int32(18)");
+    assert_contains!(&plan_str, "TableScan: person, policies=[adults]");
+
+    // Each element table of a graph is filtered before it is joined
+    let plan_str = plan(
+        "SELECT * FROM GRAPH_TABLE (social \
+         MATCH (a:person)-[e:knows]->(b:person) \
+         COLUMNS (a.first_name, b.first_name AS friend))",
+    )
+    .unwrap()
+    .display_indent()
+    .to_string();
+    assert_eq!(
+        plan_str
+            .matches("TableScan: person, policies=[adults]")
+            .count(),
+        2
+    );
+    assert_not_contains!(&plan_str, "TableScan: knows, policies");
+}
+
+#[test]
+fn security_policies_apply_to_qualified_table_names() {
+    use datafusion_expr::planner::{ColumnMask, RowSecurityPolicy};
+
+    // Each spelling of the table, for the policies and for the queries
+    let tables = ["person", "public.person", "datafusion.public.person"];
+    for policy_table in tables {
+        let state = MockSessionState::default()
+            .with_row_security_policy(
+                policy_table,
+                RowSecurityPolicy::new("adults", "age >= 18"),
+            )
+            .with_column_mask(policy_table, ColumnMask::new("state", "'XX'"));
+        let context = MockContextProvider { state };
+        let planner = SqlToRel::new(&context);
+        for table in tables {
+            let sql = format!("SELECT state FROM {table}");
+            let mut ast =
+                DFParser::parse_sql_with_dialect(&sql, &GenericDialect {}).unwrap();
+            let plan_str = planner
+                .statement_to_plan(ast.pop_front().unwrap())
+                .unwrap()
+                .display_indent()
+                .to_string();
+            assert_contains!(&plan_str, "Filter: ");
+            assert_contains!(&plan_str, "age >= Int32(18)");
+            assert_contains!(&plan_str, "Utf8(\"XX\") AS state");
+            assert_contains!(&plan_str, "policies=[adults], masks=[state]");
+        }
+    }
+}
+
+#[test]
+fn column_masks_replace_columns() {
+    use datafusion_expr::planner::{ColumnMask, RowSecurityPolicy};
+
+    let state = MockSessionState::default()
+        .with_column_mask("person", ColumnMask::new("state", "'XX'"))
+        .with_row_security_policy(
+            "person",
+            RowSecurityPolicy::new("in_ca", "state = 'CA'"),
+        )
+        .with_column_mask("j1", ColumnMask::new("missing", "0"));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    // The wildcard expands to the mask, and the policies see the original
+    // values
+    let plan_str = plan("SELECT * FROM person")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(
+        &plan_str,
+        "person.age, Utf8(\"XX\") AS state, person.salary"
+    );
+    assert_contains!(&plan_str, "Filter: person.state = Utf8(\"CA\")");
+    assert_contains!(
+        &plan_str,
+        "TableScan: person, policies=[in_ca], masks=[state]"
+    );
+
+    // The original column is not available to the query
+    let plan_str = plan("SELECT id FROM person WHERE state = 'NY'")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan_str, "Filter: person.state = Utf8(\"NY\")");
+    assert_contains!(&plan_str, "Utf8(\"XX\") AS state");
+
+    let err = plan("SELECT j1_id FROM j1").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Masked column missing not found in table j1"
+    );
+}
+
+#[test]
+fn column_masks_replace_copy_and_graph_columns() {
+    use datafusion_expr::planner::ColumnMask;
+
+    let state = MockSessionState::default()
+        .with_column_mask("person", ColumnMask::new("state", "'XX'"));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &PostgreSqlDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    let plan_str = plan("COPY person TO 'people.csv'")
+        .unwrap()
+        .display_indent()
+        .to_string();
+    assert_contains!(&plan_str, "Utf8(\"XX\") AS state");
+    assert_contains!(&plan_str, "TableScan: person, masks=[state]");
+
+    let plan_str = plan(
+        "SELECT * FROM GRAPH_TABLE (social \
+         MATCH (a:person)-[e:knows]->(b:person) \
+         COLUMNS (a.state, b.state AS friend_state))",
+    )
+    .unwrap()
+    .display_indent()
+    .to_string();
+    assert_eq!(plan_str.matches("Utf8(\"XX\") AS state").count(), 2);
+    assert_eq!(
+        plan_str.matches("TableScan: person, masks=[state]").count(),
+        2
+    );
+}
+
+/// A session of `role` that applies GRANT and REVOKE statements
+#[derive(Debug)]
+struct GrantorSession {
+    role: &'static str,
+    superuser: bool,
+}
+
+impl datafusion_expr::SessionProvider for GrantorSession {
+    fn session_id(&self) -> u64 {
+        1
+    }
+    fn timezone(&self) -> &str {
+        "UTC"
+    }
+    fn current_user(&self) -> &str {
+        self.role
+    }
+    fn current_database(&self) -> &str {
+        ""
+    }
+    fn is_superuser(&self) -> bool {
+        self.superuser
+    }
+    fn active_txn_id(&self) -> Option<u64> {
+        None
+    }
+    fn search_path(&self) -> &[String] {
+        &[]
+    }
+    fn statement_deadline(&self) -> Option<std::time::Instant> {
+        None
+    }
+    fn session_var(&self, _name: &str) -> Option<String> {
+        None
+    }
+    fn set_session_var(
+        &self,
+        name: &str,
+        _value: &str,
+        _is_local: bool,
+    ) -> Result<String> {
+        datafusion_common::not_impl_err!("Setting {name} is not supported")
+    }
+}
+
+#[test]
+fn privileges_checked_for_current_role() {
+    use datafusion_expr::PrivilegeStore;
+
+    let admin = GrantorSession {
+        role: "admin",
+        superuser: true,
+    };
+    let mut store = PrivilegeStore::new();
+    for sql in [
+        "GRANT SELECT ON person TO analyst",
+        "GRANT analyst TO alice",
+        "GRANT INSERT, DELETE ON person TO alice",
+        "GRANT SELECT ON orders TO PUBLIC",
+        "REVOKE DELETE ON person FROM alice",
+        "GRANT DELETE ON orders TO alice",
+        "GRANT INSERT ON j1 TO alice",
+        "GRANT SELECT ON j2 TO bob WITH GRANT OPTION",
+    ] {
+        let LogicalPlan::Statement(statement) = logical_plan(sql).unwrap() else {
+            panic!("Expected a statement plan for {sql}");
+        };
+        assert!(store.apply(&admin, &statement).unwrap());
+    }
+
+    // Only the holders of a grant option, or of the admin option of a role,
+    // may grant it
+    let bob = GrantorSession {
+        role: "bob",
+        superuser: false,
+    };
+    let LogicalPlan::Statement(statement) =
+        logical_plan("GRANT SELECT ON j2 TO carol").unwrap()
+    else {
+        panic!("Expected a statement plan");
+    };
+    assert!(store.apply(&bob, &statement).unwrap());
+    for sql in [
+        "GRANT ALL ON j1 TO bob",
+        "REVOKE INSERT ON j1 FROM alice",
+        "GRANT analyst TO bob",
+        "REVOKE analyst FROM alice",
+    ] {
+        let LogicalPlan::Statement(statement) = logical_plan(sql).unwrap() else {
+            panic!("Expected a statement plan for {sql}");
+        };
+        let err = store.apply(&bob, &statement).unwrap_err();
+        assert_contains!(err.strip_backtrace(), "Permission denied to");
+        assert_contains!(err.strip_backtrace(), "(SQLSTATE 42501)");
+    }
+    assert!(!store.is_member("bob", "analyst"));
+    assert!(store.is_member("alice", "analyst"));
+
+    let state = MockSessionState::default().with_privileges("alice", store);
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    // Through the granted role, and through PUBLIC
+    plan("SELECT id FROM person").unwrap();
+    plan("INSERT INTO person (id, age) SELECT order_id, price FROM orders").unwrap();
+
+    let err = plan("SELECT j1_id FROM j1").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Permission denied for table datafusion.public.j1: role alice lacks the SELECT privilege (SQLSTATE 42501)"
+    );
+    let err = plan("EXPLAIN SELECT j1_id FROM j1").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "lacks the SELECT privilege");
+    let err = plan("DELETE FROM person WHERE id = 1").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "lacks the DELETE privilege");
+
+    // COPY FROM inserts into the table
+    plan("COPY person FROM 'people.csv'").unwrap();
+    let err = plan("COPY orders FROM 'orders.csv'").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "lacks the INSERT privilege");
+
+    // Reading the columns of the target of a DELETE requires SELECT
+    plan("DELETE FROM orders").unwrap();
+    plan("DELETE FROM orders WHERE order_id = 1").unwrap();
+    let context = MockContextProvider {
+        state: MockSessionState::default().with_privileges("alice", {
+            let mut store = PrivilegeStore::new();
+            let LogicalPlan::Statement(statement) =
+                logical_plan("GRANT DELETE ON person TO alice").unwrap()
+            else {
+                panic!("Expected a statement plan");
+            };
+            store.apply(&admin, &statement).unwrap();
+            store
+        }),
+    };
+    let planner = SqlToRel::new(&context);
+    let plan_delete = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &PostgreSqlDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+    plan_delete("DELETE FROM person").unwrap();
+    for sql in [
+        "DELETE FROM person WHERE age > 60",
+        "DELETE FROM person RETURNING *",
+    ] {
+        let err = plan_delete(sql).unwrap_err();
+        assert_contains!(err.strip_backtrace(), "lacks the SELECT privilege");
+    }
+
+    // A view requires the privilege on the view, not on its tables
+    let err = plan("SELECT id FROM adult_view").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Permission denied for table datafusion.public.adult_view"
+    );
+
+    // Plans cached for another role are not returned
+    let cache = PlanCache::new(1);
+    let unchecked = MockContextProvider {
+        state: MockSessionState::default(),
+    };
+    let cached_plan = |context: &MockContextProvider| {
+        let sql = "SELECT j1_id FROM j1";
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        cache.statement_to_plan(&SqlToRel::new(context), ast.pop_front().unwrap(), 0)
+    };
+    cached_plan(&unchecked).unwrap();
+    let err = cached_plan(&context).unwrap_err();
+    assert_contains!(err.strip_backtrace(), "lacks the SELECT privilege");
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn privileges_of_views_not_granted_to_aliases_of_view_names() {
+    use datafusion_expr::PrivilegeStore;
+
+    let admin = GrantorSession {
+        role: "admin",
+        superuser: true,
+    };
+    let mut store = PrivilegeStore::new();
+    let LogicalPlan::Statement(statement) =
+        logical_plan("GRANT SELECT ON adult_view TO alice").unwrap()
+    else {
+        panic!("Expected a statement plan");
+    };
+    store.apply(&admin, &statement).unwrap();
+
+    let state = MockSessionState::default().with_privileges("alice", store);
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    plan("SELECT id FROM adult_view").unwrap();
+    plan("SELECT * FROM (SELECT id FROM adult_view) AS adult_view").unwrap();
+
+    // An alias named after the view does not hide the tables it reads
+    for sql in [
+        "SELECT * FROM (SELECT adult_view.id, j1.j1_string \
+         FROM adult_view CROSS JOIN j1) AS adult_view",
+        "SELECT * FROM (SELECT adult_view.id FROM adult_view \
+         JOIN j1 ON adult_view.id = j1.j1_id) AS adult_view",
+    ] {
+        let err = plan(sql).unwrap_err();
+        assert_contains!(
+            err.strip_backtrace(),
+            "Permission denied for table datafusion.public.j1"
+        );
+    }
+}
+
+#[test]
+fn set_role_statements() {
+    let plan = logical_plan("SET ROLE Analyst").unwrap();
+    assert_snapshot!(plan, @"SetRole: analyst session_authorization:=false local:=false");
+
+    let plan = logical_plan("SET LOCAL ROLE NONE").unwrap();
+    assert_snapshot!(plan, @"SetRole: NONE session_authorization:=false local:=true");
+
+    let plan = logical_plan("SET SESSION AUTHORIZATION bob").unwrap();
+    assert_snapshot!(plan, @"SetRole: bob session_authorization:=true local:=false");
+
+    let plan = logical_plan("SET SESSION AUTHORIZATION DEFAULT").unwrap();
+    assert_snapshot!(plan, @"SetRole: NONE session_authorization:=true local:=false");
+}
+
+#[test]
+fn audit_loggers_record_statements() {
+    use datafusion_common::{TableReference, plan_err};
+    use datafusion_expr::Privilege;
+    use datafusion_expr::planner::{AuditLogger, AuditRecord, REDACTED_SQL};
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct RecordingLogger {
+        records: Mutex<Vec<AuditRecord>>,
+    }
+
+    impl AuditLogger for RecordingLogger {
+        fn log_statement(&self, record: &AuditRecord) -> Result<()> {
+            if record.kind == "DropTable" {
+                return plan_err!("Audit log unavailable");
+            }
+            self.records.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+    }
+
+    let logger = Arc::new(RecordingLogger::default());
+    let state = MockSessionState::default()
+        .with_audit_logger(Arc::clone(&logger) as Arc<dyn AuditLogger>);
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let cache = PlanCache::new(1);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        cache.statement_to_plan(&planner, ast.pop_front().unwrap(), 0)
+    };
+    let table = |name: &str| TableReference::full("datafusion", "public", name);
+
+    let sql = "SELECT id FROM person WHERE age > $1 AND first_name = 'x' LIMIT 10";
+    plan(sql).unwrap();
+    // Cached plans are recorded too
+    plan(sql).unwrap();
+    plan("INSERT INTO person (id, age) SELECT order_id, 5 FROM orders").unwrap();
+    plan("CREATE USER alice PASSWORD 'secret'").unwrap();
+    // The text of the statement does not tokenize once displayed, because the
+    // file is not quoted, and is recorded redacted
+    plan("COPY person FROM 'a\"b.csv'").unwrap();
+    // Statements that can not be recorded fail
+    let err = plan("DROP TABLE person").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Audit log unavailable");
+
+    let query = AuditRecord {
+        kind: "Query".to_string(),
+        objects: vec![(Privilege::Select, table("person"))],
+        sql: "SELECT id FROM person WHERE age > $1 AND first_name = $2 LIMIT $3"
+            .to_string(),
+    };
+    assert_eq!(
+        *logger.records.lock().unwrap(),
+        vec![
+            query.clone(),
+            query,
+            AuditRecord {
+                kind: "Insert Into".to_string(),
+                objects: vec![
+                    (Privilege::Insert, table("person")),
+                    (Privilege::Select, table("orders")),
+                ],
+                sql: "INSERT INTO person (id, age) SELECT order_id, $1 FROM orders"
+                    .to_string(),
+            },
+            AuditRecord {
+                kind: "CreateUser".to_string(),
+                objects: vec![],
+                sql: "CREATE USER alice PASSWORD $1".to_string(),
+            },
+            AuditRecord {
+                kind: "CopyFrom".to_string(),
+                objects: vec![(Privilege::Insert, table("person"))],
+                sql: REDACTED_SQL.to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn user_statements() {
+    let plan = logical_plan(
+        "CREATE USER Alice WITH PASSWORD 'secret' DEFAULT ROLE Analyst OPTIONS (team 'sales')",
+    )
+    .unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"CreateUser: "alice" if not exist:=false password:=true default role:=Some("analyst") options:=(team 'sales')"#
+    );
+
+    let plan = logical_plan(
+        "ALTER USER alice PASSWORD 'new secret' OPTIONS (SET team 'support', DROP site)",
+    )
+    .unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"AlterUser: "alice" password:=true default role:=None options:=(SET team 'support', DROP site)"#
+    );
+
+    let plan = logical_plan("DROP USER IF EXISTS alice, bob").unwrap();
+    assert_snapshot!(plan, @r#"DropUser: ["alice", "bob"] if exists:=true"#);
+
+    let err = logical_plan("CREATE USER alice OPTIONS (team 'a', team 'b')").unwrap_err();
+    assert_contains!(err.to_string(), "Option team provided more than once");
+}
+
+#[test]
+fn column_lineage_through_views() {
+    use datafusion_common::{Column, TableReference};
+    use datafusion_expr::{ColumnOrigin, VIEW_METADATA_KEY};
+
+    let plan = logical_plan(
+        "SELECT v.id, v.next_age * 2 AS doubled, 'x' AS x FROM adult_view v",
+    )
+    .unwrap();
+    let origin = |column: &str, derived| ColumnOrigin {
+        table: TableReference::bare("person"),
+        column: column.to_string(),
+        views: vec![
+            TableReference::bare("adult_view"),
+            TableReference::bare("person_view"),
+        ],
+        derived,
+    };
+
+    let lineage = plan.column_lineage().unwrap();
+    assert_eq!(lineage.len(), 3);
+    assert_eq!(lineage[0].column, Column::new(Some("v"), "id"));
+    assert_eq!(lineage[0].origins, vec![origin("id", false)]);
+    assert_eq!(lineage[1].column, Column::from_name("doubled"));
+    assert_eq!(lineage[1].origins, vec![origin("age", true)]);
+    assert!(lineage[2].origins.is_empty());
+
+    // The columns read through a view keep the name of the outermost view
+    let field = plan.schema().field(0);
+    assert_eq!(
+        field.metadata().get(VIEW_METADATA_KEY).map(String::as_str),
+        Some("adult_view")
+    );
+}
+
+#[test]
+fn column_lineage_of_plan_nodes() {
+    use datafusion_common::TableReference;
+    use datafusion_expr::ColumnOrigin;
+
+    let origin = |table: &str, column: &str, derived| ColumnOrigin {
+        table: TableReference::bare(table),
+        column: column.to_string(),
+        views: vec![],
+        derived,
+    };
+    let origins = |sql: &str| {
+        logical_plan(sql)
+            .unwrap()
+            .column_lineage()
+            .unwrap()
+            .into_iter()
+            .map(|lineage| lineage.origins)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        origins(
+            "SELECT p.state, max(o.price) FROM person p \
+             JOIN orders o ON p.id = o.customer_id GROUP BY p.state"
+        ),
+        vec![
+            vec![origin("person", "state", false)],
+            vec![origin("orders", "price", true)],
+        ]
+    );
+
+    // The value of a correlated subquery
+    assert_eq!(
+        origins(
+            "SELECT id, (SELECT max(price) FROM orders \
+             WHERE customer_id = person.id) AS max_price FROM person"
+        ),
+        vec![
+            vec![origin("person", "id", false)],
+            vec![origin("orders", "price", true)],
+        ]
+    );
+
+    assert_eq!(
+        origins("SELECT id FROM person UNION ALL SELECT order_id FROM orders"),
+        vec![vec![
+            origin("orders", "order_id", false),
+            origin("person", "id", false),
+        ]]
+    );
+
+    // MERGE returns the number of rows it changed
+    assert_eq!(
+        origins(
+            "MERGE INTO j1 USING j2 ON j1.j1_id = j2.j2_id \
+             WHEN MATCHED THEN UPDATE SET j1_string = j2.j2_string"
+        ),
+        vec![vec![]]
+    );
+}
+
+#[test]
+fn table_sources_resolved_in_one_batch() {
+    let context = MockContextProvider {
+        state: MockSessionState::default(),
+    };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    plan(
+        "WITH cte AS (SELECT order_id FROM orders) \
+         SELECT p.id FROM person p JOIN person q ON p.id = q.id \
+         WHERE p.id IN (SELECT order_id FROM cte)",
+    )
+    .unwrap();
+    assert_eq!(
+        *context.state.table_batches.lock().unwrap(),
+        vec![vec!["orders".to_string(), "person".to_string()]]
+    );
+    assert!(context.state.table_lookups.lock().unwrap().is_empty());
+
+    // Tables missing from the batch are looked up again when planned
+    let err = plan("SELECT * FROM person, missing").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "No table named: missing found");
+    assert_eq!(
+        *context.state.table_lookups.lock().unwrap(),
+        vec!["missing".to_string()]
+    );
+
+    // The source of `CREATE TABLE ... CLONE` is resolved in the batch too
+    context.state.table_batches.lock().unwrap().clear();
+    context.state.table_lookups.lock().unwrap().clear();
+    plan("CREATE TABLE person_copy CLONE person").unwrap();
+    assert_eq!(
+        *context.state.table_batches.lock().unwrap(),
+        vec![vec!["person".to_string()]]
+    );
+    assert!(context.state.table_lookups.lock().unwrap().is_empty());
+}
+
+#[test]
+fn statement_planners_plan_custom_statements() {
+    use datafusion_expr::planner::{
+        StatementPlanner, StatementPlannerContext, StatementPlanning,
+    };
+    use datafusion_expr::{LogicalPlanBuilder, lit};
+    use sqlparser::ast::Statement;
+
+    /// Plans `KILL <id>` as a projection of the id
+    #[derive(Debug)]
+    struct KillPlanner;
+
+    impl StatementPlanner for KillPlanner {
+        fn plan_statement(
+            &self,
+            statement: Statement,
+            _context: &mut dyn StatementPlannerContext,
+        ) -> Result<StatementPlanning> {
+            match statement {
+                Statement::Kill { id, .. } => {
+                    let plan = LogicalPlanBuilder::empty(true)
+                        .project(vec![lit(id).alias("killed")])?
+                        .build()?;
+                    Ok(StatementPlanning::Planned(plan))
+                }
+                statement => Ok(StatementPlanning::Original(statement)),
+            }
+        }
+    }
+
+    /// Plans `CACHE TABLE t AS <query>` as the plan of the query, planned by
+    /// the next planners
+    #[derive(Debug)]
+    struct CachePlanner;
+
+    impl StatementPlanner for CachePlanner {
+        fn plan_statement(
+            &self,
+            statement: Statement,
+            context: &mut dyn StatementPlannerContext,
+        ) -> Result<StatementPlanning> {
+            match statement {
+                Statement::Cache {
+                    query: Some(query), ..
+                } => {
+                    let plan = context.plan(Statement::Query(query))?;
+                    Ok(StatementPlanning::Planned(plan))
+                }
+                statement => Ok(StatementPlanning::Original(statement)),
+            }
+        }
+    }
+
+    let state = MockSessionState::default()
+        .with_statement_planner(Arc::new(CachePlanner))
+        .with_statement_planner(Arc::new(KillPlanner));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    assert_snapshot!(
+        plan("KILL 42").unwrap(),
+        @r"
+    Projection: UInt64(42) AS killed
+      EmptyRelation: rows=1
+    "
+    );
+    assert_snapshot!(
+        plan("CACHE TABLE c AS SELECT id FROM person").unwrap(),
+        @r"
+    Projection: person.id
       TableScan: person
-"#
+    "
     );
-}
-
-#[test]
-fn select_groupby_orderby_aggregate_on_non_selected_column() {
-    let sql = "SELECT state FROM person GROUP BY state ORDER BY MIN(age)";
-    let plan = logical_plan(sql).unwrap();
+    // Statements that no planner handles are planned as usual
     assert_snapshot!(
-        plan,
-        @r#"
-Projection: person.state
-  Sort: min(person.age) ASC NULLS LAST
-    Projection: person.state, min(person.age)
-      Aggregate: groupBy=[[person.state]], aggr=[[min(person.age)]]
-        TableScan: person
-"#
+        plan("SELECT 1").unwrap(),
+        @r"
+    Projection: Int32(1)
+      EmptyRelation: rows=1
+    "
     );
 }
 
 #[test]
-fn select_groupby_orderby_multiple_aggregates_on_non_selected_columns() {
-    let sql =
-        "SELECT state FROM person GROUP BY state ORDER BY MIN(age), MAX(salary) DESC";
-    let plan = logical_plan(sql).unwrap();
-    assert_snapshot!(
-        plan,
-        @r#"
-Projection: person.state
-  Sort: min(person.age) ASC NULLS LAST, max(person.salary) DESC NULLS FIRST
-    Projection: person.state, min(person.age), max(person.salary)
-      Aggregate: groupBy=[[person.state]], aggr=[[min(person.age), max(person.salary)]]
-        TableScan: person
-"#
+fn cast_with_datetime_format() {
+    let state = MockSessionState::default()
+        .with_scalar_function(Arc::new(make_udf(
+            "to_date",
+            vec![DataType::Utf8, DataType::Utf8],
+            DataType::Date32,
+        )))
+        .with_scalar_function(Arc::new(make_udf(
+            "to_char",
+            vec![
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                DataType::Utf8,
+            ],
+            DataType::Utf8,
+        )));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
+
+    let sql = "SELECT CAST(first_name AS DATE FORMAT 'YYYY-MM-DD'), \
+               CAST(birth_date AS VARCHAR FORMAT 'DD.MM.YYYY HH24:MI') FROM person";
+    let plan_str = plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(
+        &plan_str,
+        "to_date(person.first_name, Utf8(\"%Y-%m-%d\")) AS first_name"
+    );
+    assert_contains!(
+        &plan_str,
+        "to_char(person.birth_date, Utf8(\"%d.%m.%Y %H:%M\"))"
     );
-}
 
-#[test]
-fn select_groupby_orderby_aggregate_on_non_selected_column_original_issue() {
-    let sql = "SELECT id FROM person GROUP BY id ORDER BY min(age)";
-    let plan = logical_plan(sql).unwrap();
-    assert_snapshot!(
-        plan,
-        @r#"
-Projection: person.id
-  Sort: min(person.age) ASC NULLS LAST
-    Projection: person.id, min(person.age)
-      Aggregate: groupBy=[[person.id]], aggr=[[min(person.age)]]
-        TableScan: person
-"#
+    let err =
+        plan("SELECT CAST(first_name AS DATE FORMAT 'YYYY-MM-DD-YYYY') FROM person")
+            .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Datetime template element YYYY appears more than once"
+    );
+
+    let err = plan("SELECT CAST(age AS VARCHAR FORMAT 'YYYY') FROM person").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "CAST with FORMAT from Int32 to");
+
+    let err = logical_plan("SELECT CAST(first_name AS DATE FORMAT 'YYYY') FROM person")
+        .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "CAST with FORMAT to Date32 requires the to_date function"
     );
 }
 
 #[test]
-fn for_update_of_alias_annotates_only_target_table_scan() -> Result<()> {
-    let plan = logical_plan(
-        "SELECT o.order_id, l.l_item_id \
-         FROM orders o JOIN lineitem l ON o.o_item_id = l.l_item_id \
-         FOR UPDATE OF o NOWAIT",
-    )?;
+fn plan_cache_reuses_plans() {
+    let context = MockContextProvider {
+        state: MockSessionState::default(),
+    };
+    let planner = SqlToRel::new(&context);
+    let cache = PlanCache::new(2);
+    let plan = |sql: &str, catalog_version: u64| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        cache.statement_to_plan(&planner, ast.pop_front().unwrap(), catalog_version)
+    };
 
-    let mut scan_locks = vec![];
-    plan.apply(|node| {
-        if let LogicalPlan::TableScan(scan) = node {
-            scan_locks.push((
-                scan.table_name.to_string(),
-                scan.row_lock.map(|lock| (lock.mode, lock.wait_policy)),
-            ));
-        }
-        Ok(TreeNodeRecursion::Continue)
-    })?;
-    scan_locks.sort_by(|left, right| left.0.cmp(&right.0));
+    let sql = "SELECT id FROM person WHERE age > $1";
+    let first = plan(sql, 0).unwrap();
+    assert_eq!(cache.len(), 1);
+    // Whitespace and keyword case are normalized away
+    let second = plan("select  id\nfrom person where age > $1", 0).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(cache.len(), 1);
+    // Cached plans keep their placeholders
+    assert_contains!(first.display_indent().to_string(), "person.age > $1");
+
+    // Plans of other catalog versions are not reused
+    plan(sql, 1).unwrap();
+    assert_eq!(cache.len(), 2);
+    // The least recently used plan is evicted
+    plan("SELECT id FROM orders", 1).unwrap();
+    assert_eq!(cache.len(), 2);
+
+    // DDL invalidates the cache, and is not cached
+    plan("CREATE VIEW v AS SELECT id FROM person", 1).unwrap();
+    assert!(cache.is_empty());
+    // Failed plans are not cached
+    plan("SELECT doesnotexist FROM person", 1).unwrap_err();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn plan_cache_keys_plans_by_role() {
+    use datafusion_expr::planner::ColumnMask;
+
+    // The masks of the provider depend on its current role
+    let masked = MockContextProvider {
+        state: MockSessionState::default()
+            .with_current_role("analyst")
+            .with_column_mask("person", ColumnMask::new("state", "'XX'")),
+    };
+    let unmasked = MockContextProvider {
+        state: MockSessionState::default().with_current_role("admin"),
+    };
+    let cache = PlanCache::new(2);
+    let plan = |context: &MockContextProvider| {
+        let sql = "SELECT state FROM person";
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &GenericDialect {})?;
+        cache
+            .statement_to_plan(&SqlToRel::new(context), ast.pop_front().unwrap(), 0)
+            .map(|plan| plan.display_indent().to_string())
+    };
 
-    assert_eq!(
-        scan_locks,
-        vec![
-            ("lineitem".to_string(), None),
-            (
-                "orders".to_string(),
-                Some((
-                    TableScanRowLockMode::ForUpdate,
-                    TableScanRowLockWaitPolicy::Nowait
-                ))
-            ),
-        ]
-    );
-    Ok(())
+    assert_contains!(plan(&masked).unwrap(), "masks=[state]");
+    assert_not_contains!(plan(&unmasked).unwrap(), "masks=[state]");
+    assert_eq!(cache.len(), 2);
+    assert_contains!(plan(&masked).unwrap(), "masks=[state]");
+    assert_eq!(cache.len(), 2);
 }
 
 #[test]
-fn for_share_without_of_annotates_all_table_scans() -> Result<()> {
-    let plan = logical_plan(
-        "SELECT o.order_id, l.l_item_id \
-         FROM orders o JOIN lineitem l ON o.o_item_id = l.l_item_id \
-         FOR SHARE SKIP LOCKED",
-    )?;
+fn convert_using_character_set() {
+    let state = MockSessionState::default().with_scalar_function(Arc::new(make_udf(
+        "to_latin1",
+        vec![DataType::Utf8],
+        DataType::Utf8,
+    )));
+    let context = MockContextProvider { state };
+    let planner = SqlToRel::new(&context);
+    let plan = |sql: &str| {
+        let mut ast = DFParser::parse_sql_with_dialect(sql, &MySqlDialect {})?;
+        planner.statement_to_plan(ast.pop_front().unwrap())
+    };
 
-    let mut scan_locks = vec![];
-    plan.apply(|node| {
-        if let LogicalPlan::TableScan(scan) = node {
-            scan_locks.push((
-                scan.table_name.to_string(),
-                scan.row_lock.map(|lock| (lock.mode, lock.wait_policy)),
-            ));
-        }
-        Ok(TreeNodeRecursion::Continue)
-    })?;
-    scan_locks.sort_by(|left, right| left.0.cmp(&right.0));
+    let sql = "SELECT CONVERT(first_name USING latin1), CONVERT(first_name USING utf8mb4) \
+               FROM person";
+    let plan_str = plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(
+        &plan_str,
+        "Projection: to_latin1(person.first_name), person.first_name"
+    );
 
-    assert_eq!(
-        scan_locks,
-        vec![
-            (
-                "lineitem".to_string(),
-                Some((
-                    TableScanRowLockMode::ForShare,
-                    TableScanRowLockWaitPolicy::SkipLocked
-                ))
-            ),
-            (
-                "orders".to_string(),
-                Some((
-                    TableScanRowLockMode::ForShare,
-                    TableScanRowLockWaitPolicy::SkipLocked
-                ))
-            ),
-        ]
+    // The CHARACTER SET of the target type is converted to after the cast
+    let sql = "SELECT CONVERT(age, CHAR CHARACTER SET latin1) FROM person";
+    let plan_str = plan(sql).unwrap().display_indent().to_string();
+    assert_contains!(&plan_str, "to_latin1(CAST(person.age AS Utf8))");
+
+    let err = plan("SELECT CONVERT(first_name USING ebcdic) FROM person").unwrap_err();
+    assert_contains!(err.strip_backtrace(), "Unknown character set ebcdic");
+
+    let err = plan("SELECT CONVERT(age USING utf8) FROM person").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "CONVERT USING utf8 requires a character or binary string, got Int32"
     );
-    Ok(())
 }
 
 #[test]
-fn for_update_of_unknown_relation_errors() {
-    let err = logical_plan(
-        "SELECT o.order_id, l.l_item_id \
-         FROM orders o JOIN lineitem l ON o.o_item_id = l.l_item_id \
-         FOR UPDATE OF missing",
-    )
-    .expect_err("unknown lock target should fail planning");
+fn national_character_types_keep_character_set() {
+    let sql = "CREATE TABLE t (a NCHAR(10), b NVARCHAR(20), c NCLOB)";
+    let LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(create)) =
+        logical_plan(sql).unwrap()
+    else {
+        panic!("Expected CreateMemoryTable");
+    };
+    let schema = create.input.schema();
+    for (i, length) in [Some("10"), Some("20"), None].into_iter().enumerate() {
+        let metadata = schema.field(i).metadata();
+        assert_eq!(
+            metadata.get(CHARACTER_SET_METADATA_KEY).map(String::as_str),
+            Some(NATIONAL_CHARACTER_SET)
+        );
+        assert_eq!(
+            metadata
+                .get(CHARACTER_LENGTH_METADATA_KEY)
+                .map(String::as_str),
+            length
+        );
+    }
 
+    // Casts keep the declared character set in the field of their result,
+    // which operators ignore
+    let sql = "SELECT CAST(first_name AS NCHAR(5)) FROM person \
+               WHERE CAST(last_name AS NVARCHAR(5)) = 'smith'";
+    let plan = logical_plan(sql).unwrap();
+    let field = plan.schema().field(0);
+    assert_eq!(field.name(), "first_name");
+    assert_eq!(
+        field
+            .metadata()
+            .get(CHARACTER_SET_METADATA_KEY)
+            .map(String::as_str),
+        Some(NATIONAL_CHARACTER_SET)
+    );
+    assert_eq!(
+        field
+            .metadata()
+            .get(CHARACTER_LENGTH_METADATA_KEY)
+            .map(String::as_str),
+        Some("5")
+    );
     assert_contains!(
-        err.to_string(),
-        "FOR UPDATE OF missing did not match any table scan in the query"
+        plan.display_indent().to_string(),
+        "Filter: CAST(person.last_name AS Utf8View) = Utf8(\"smith\")"
     );
 }
 
-fn logical_plan(sql: &str) -> Result<LogicalPlan> {
-    logical_plan_with_options(sql, ParserOptions::default())
+#[test]
+fn interval_qualifier_in_field_metadata() {
+    let sql = "CREATE TABLE t (d INTERVAL DAY TO SECOND(3), m INTERVAL YEAR TO MONTH)";
+    let LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(create)) =
+        logical_plan(sql).unwrap()
+    else {
+        panic!("Expected CreateMemoryTable");
+    };
+    let schema = create.input.schema();
+    let metadata = schema.field(0).metadata();
+    assert_eq!(
+        metadata
+            .get(INTERVAL_FIELDS_METADATA_KEY)
+            .map(String::as_str),
+        Some("DAY TO SECOND")
+    );
+    assert_eq!(
+        metadata
+            .get(INTERVAL_PRECISION_METADATA_KEY)
+            .map(String::as_str),
+        Some("3")
+    );
+    let metadata = schema.field(1).metadata();
+    assert_eq!(
+        metadata
+            .get(INTERVAL_FIELDS_METADATA_KEY)
+            .map(String::as_str),
+        Some("YEAR TO MONTH")
+    );
+    assert_eq!(metadata.get(INTERVAL_PRECISION_METADATA_KEY), None);
+
+    let plan =
+        logical_plan("SELECT CAST('1 day' AS INTERVAL HOUR TO SECOND(6))").unwrap();
+    let metadata = plan.schema().field(0).metadata();
+    assert_eq!(
+        metadata
+            .get(INTERVAL_FIELDS_METADATA_KEY)
+            .map(String::as_str),
+        Some("HOUR TO SECOND")
+    );
+
+    let err = logical_plan("CREATE TABLE t (d INTERVAL SECOND(10))").unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Interval fractional seconds precision must be at most 9, got 10"
+    );
 }
 
 #[test]
@@ -3644,11 +6714,14 @@ fn parse_decimals_parser_options() -> ParserOptions {
     ParserOptions {
         parse_float_as_decimal: true,
         enable_ident_normalization: false,
+        ident_case_folding: IdentCaseFolding::Lower,
         support_varchar_with_length: false,
         map_string_types_to_utf8view: true,
         enable_options_value_normalization: false,
         collect_spans: false,
+        collect_errors: false,
         default_null_ordering: NullOrdering::NullsMax,
+        function_name_conflict: FunctionNameConflict::PreferFunction,
     }
 }
 
@@ -3656,11 +6729,14 @@ fn ident_normalization_parser_options_no_ident_normalization() -> ParserOptions
     ParserOptions {
         parse_float_as_decimal: true,
         enable_ident_normalization: false,
+        ident_case_folding: IdentCaseFolding::Lower,
         support_varchar_with_length: false,
         map_string_types_to_utf8view: true,
         enable_options_value_normalization: false,
         collect_spans: false,
+        collect_errors: false,
         default_null_ordering: NullOrdering::NullsMax,
+        function_name_conflict: FunctionNameConflict::PreferFunction,
     }
 }
 
@@ -3668,11 +6744,14 @@ fn ident_normalization_parser_options_ident_normalization() -> ParserOptions {
     ParserOptions {
         parse_float_as_decimal: true,
         enable_ident_normalization: true,
+        ident_case_folding: IdentCaseFolding::Lower,
         support_varchar_with_length: false,
         map_string_types_to_utf8view: true,
         enable_options_value_normalization: false,
         collect_spans: false,
+        collect_errors: false,
         default_null_ordering: NullOrdering::NullsMax,
+        function_name_conflict: FunctionNameConflict::PreferFunction,
     }
 }
 
@@ -5066,16 +8145,96 @@ fn test_using_join_wildcard_schema() {
     );
 }
 
+#[test]
+fn test_wildcard_except() {
+    let dialect = &GenericDialect {};
+    let sql = "SELECT * EXCEPT (O_ITEM_ID, price) FROM orders";
+    let plan = logical_plan_with_dialect(sql, dialect).unwrap();
+    assert_eq!(
+        plan.schema().field_names(),
+        [
+            "orders.order_id",
+            "orders.customer_id",
+            "orders.qty",
+            "orders.delivered"
+        ]
+    );
+
+    // The columns of every table with an excluded name are excluded
+    let sql = "SELECT * EXCEPT (qty) FROM orders o1 JOIN orders o2 USING (order_id)";
+    let plan = logical_plan_with_dialect(sql, dialect).unwrap();
+    assert!(!plan.schema().iter().any(|(_, f)| f.name() == "qty"));
+
+    let sql = "SELECT o2.* EXCEPT (qty) FROM orders o1 JOIN orders o2 USING (order_id)";
+    let plan = logical_plan_with_dialect(sql, dialect).unwrap();
+    assert_eq!(plan.schema().fields().len(), 5);
+
+    let sql = "SELECT * EXCEPT (qty, missing) FROM orders";
+    let err = logical_plan_with_dialect(sql, dialect).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "EXCLUDE or EXCEPT contains column missing, which is not selected by the wildcard"
+    );
+
+    let sql = "SELECT * EXCEPT (qty, QTY) FROM orders";
+    let err = logical_plan_with_dialect(sql, dialect).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "EXCLUDE or EXCEPT contains duplicate column names"
+    );
+}
+
+#[test]
+fn test_wildcard_replace() {
+    let dialect = &GenericDialect {};
+    let sql = "SELECT * REPLACE (qty * 2 AS QTY) FROM orders";
+    let plan = logical_plan_with_dialect(sql, dialect).unwrap();
+    // The replaced column keeps its name, qualifier and position
+    assert_eq!(
+        plan.schema().field_names(),
+        [
+            "orders.order_id",
+            "orders.customer_id",
+            "orders.o_item_id",
+            "orders.qty",
+            "orders.price",
+            "orders.delivered"
+        ]
+    );
+    assert_contains!(
+        plan.display_indent().to_string(),
+        "orders.qty * Int32(2) AS qty, orders.price"
+    );
+
+    let sql = "SELECT * REPLACE (1 AS missing) FROM orders";
+    let err = logical_plan_with_dialect(sql, dialect).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "REPLACE contains column missing, which is not selected by the wildcard"
+    );
+
+    let sql = "SELECT * REPLACE (1 AS qty, 2 AS qty) FROM orders";
+    let err = logical_plan_with_dialect(sql, dialect).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "REPLACE contains column qty more than once"
+    );
+}
+
 // ==================== SQL/MED (Management of External Data) Tests ====================
 
 #[test]
 fn sqlmed_create_server() {
     let sql = "CREATE SERVER myserver FOREIGN DATA WRAPPER postgres_fdw OPTIONS (host 'localhost', port '5432')";
     let plan = logical_plan(sql).unwrap();
-    match plan {
+    match &plan {
         LogicalPlan::Ddl(DdlStatement::CreateServer(_)) => {}
         _ => panic!("Expected CreateServer DDL statement"),
     }
+    assert_eq!(
+        plan.display_indent().to_string(),
+        "CreateServer: myserver wrapper:=postgres_fdw options:=(host 'localhost', port '5432')"
+    );
 }
 
 #[test]
@@ -5090,22 +8249,45 @@ fn sqlmed_create_foreign_data_wrapper() {
 
 #[test]
 fn sqlmed_create_foreign_table() {
-    let sql = "CREATE FOREIGN TABLE remote_users (id INT, name VARCHAR(100)) SERVER myserver OPTIONS (table_name 'users')";
+    let sql = "CREATE FOREIGN TABLE remote_users (id INT, name VARCHAR(100)) SERVER MyServer OPTIONS (Table_Name 'users')";
     let plan = logical_plan(sql).unwrap();
-    match plan {
-        LogicalPlan::Ddl(DdlStatement::CreateForeignTable(_)) => {}
+    match &plan {
+        LogicalPlan::Ddl(DdlStatement::CreateForeignTable(table)) => {
+            assert_eq!(table.server, "myserver");
+            assert_eq!(table.schema.fields().len(), 2);
+            assert_eq!(table.schema.field(1).name(), "name");
+        }
         _ => panic!("Expected CreateForeignTable DDL statement"),
     }
+    assert_eq!(
+        plan.display_indent().to_string(),
+        "CreateForeignTable: Bare { table: \"remote_users\" } server:=myserver options:=(table_name 'users')"
+    );
 }
 
 #[test]
 fn sqlmed_create_user_mapping() {
     let sql = "CREATE USER MAPPING FOR current_user SERVER myserver OPTIONS (user 'remote_user', password 'secret')";
     let plan = logical_plan(sql).unwrap();
-    match plan {
-        LogicalPlan::Ddl(DdlStatement::CreateUserMapping(_)) => {}
+    match &plan {
+        LogicalPlan::Ddl(DdlStatement::CreateUserMapping(mapping)) => {
+            assert_eq!(mapping.user, UserMappingTarget::CurrentUser);
+            assert_eq!(mapping.options["password"], "secret");
+        }
         _ => panic!("Expected CreateUserMapping DDL statement"),
     }
+    // Option values are credentials and are not displayed
+    assert_eq!(
+        plan.display_indent().to_string(),
+        "CreateUserMapping: CURRENT_USER server:=myserver options:=(password, user)"
+    );
+
+    let sql = "CREATE USER MAPPING FOR Alice SERVER myserver OPTIONS (password_secret 'alice_pw')";
+    let plan = logical_plan(sql).unwrap();
+    assert_eq!(
+        plan.display_indent().to_string(),
+        "CreateUserMapping: alice server:=myserver options:=(password_secret)"
+    );
 }
 
 #[test]
@@ -5123,7 +8305,10 @@ fn sqlmed_drop_server() {
     let sql = "DROP SERVER IF EXISTS myserver CASCADE";
     let plan = logical_plan(sql).unwrap();
     match plan {
-        LogicalPlan::Ddl(DdlStatement::DropServer(_)) => {}
+        LogicalPlan::Ddl(DdlStatement::DropServer(drop)) => {
+            assert_eq!(drop.name, "myserver");
+            assert!(drop.if_exists);
+        }
         _ => panic!("Expected DropServer DDL statement"),
     }
 }
@@ -5143,7 +8328,13 @@ fn sqlmed_drop_foreign_table() {
     let sql = "DROP FOREIGN TABLE IF EXISTS remote_users";
     let plan = logical_plan(sql).unwrap();
     match plan {
-        LogicalPlan::Ddl(DdlStatement::DropForeignTable(_)) => {}
+        LogicalPlan::Ddl(DdlStatement::DropForeignTable(drop)) => {
+            assert_eq!(
+                drop.name,
+                datafusion_common::TableReference::bare("remote_users")
+            );
+            assert!(drop.if_exists);
+        }
         _ => panic!("Expected DropForeignTable DDL statement"),
     }
 }
@@ -5153,19 +8344,27 @@ fn sqlmed_drop_user_mapping() {
     let sql = "DROP USER MAPPING IF EXISTS FOR current_user SERVER myserver";
     let plan = logical_plan(sql).unwrap();
     match plan {
-        LogicalPlan::Ddl(DdlStatement::DropUserMapping(_)) => {}
+        LogicalPlan::Ddl(DdlStatement::DropUserMapping(drop)) => {
+            assert_eq!(drop.user, UserMappingTarget::CurrentUser);
+            assert_eq!(drop.server, "myserver");
+            assert!(drop.if_exists);
+        }
         _ => panic!("Expected DropUserMapping DDL statement"),
     }
 }
 
 #[test]
 fn sqlmed_alter_server() {
-    let sql = "ALTER SERVER myserver OPTIONS (SET host 'newhost')";
+    let sql = "ALTER SERVER myserver OPTIONS (SET host 'newhost', ADD Port '5433', DROP dbname)";
     let plan = logical_plan(sql).unwrap();
-    match plan {
+    match &plan {
         LogicalPlan::Ddl(DdlStatement::AlterServer(_)) => {}
         _ => panic!("Expected AlterServer DDL statement"),
     }
+    assert_eq!(
+        plan.display_indent().to_string(),
+        "AlterServer: myserver options:=(SET host 'newhost', ADD port '5433', DROP dbname)"
+    );
 }
 
 #[test]
@@ -5182,10 +8381,14 @@ fn sqlmed_alter_foreign_data_wrapper() {
 fn sqlmed_alter_foreign_table() {
     let sql = "ALTER FOREIGN TABLE remote_users OPTIONS (SET table_name 'new_users')";
     let plan = logical_plan(sql).unwrap();
-    match plan {
+    match &plan {
         LogicalPlan::Ddl(DdlStatement::AlterForeignTable(_)) => {}
         _ => panic!("Expected AlterForeignTable DDL statement"),
     }
+    assert_eq!(
+        plan.display_indent().to_string(),
+        "AlterForeignTable: Bare { table: \"remote_users\" } if_exists:=false options:=(SET table_name 'new_users')"
+    );
 }
 
 #[test]